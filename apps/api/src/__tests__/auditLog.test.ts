@@ -0,0 +1,261 @@
+import { describe, it, expect, beforeEach, vi } from 'vitest'
+import { AuditCleanupService, AuditLogService, PolicyService } from '@stina/auth'
+import type { AuditEvent, User } from '@stina/auth'
+import type {
+  AuditEventRepository,
+  CapabilityPolicyRepository,
+  UserRepository,
+} from '@stina/auth/db'
+import { createAuditRoutes } from '../routes/audit.js'
+import {
+  admin,
+  alice,
+  createLocalAuthService,
+  createMockAuditEventRepository,
+  createMockPolicyRepository,
+  createMockUserRepository,
+  createServerForUser,
+  createTestAuthRoutes,
+  createTestAuthService,
+  createTestServer,
+} from './helpers.js'
+
+describe('Audit log', () => {
+  let auditRepository: ReturnType<typeof createMockAuditEventRepository>
+  let userRepository: ReturnType<typeof createMockUserRepository>
+  let auditLogService: AuditLogService
+
+  beforeEach(() => {
+    auditRepository = createMockAuditEventRepository()
+    userRepository = createMockUserRepository([alice, admin])
+    auditLogService = new AuditLogService(
+      auditRepository as unknown as AuditEventRepository,
+      userRepository as unknown as UserRepository
+    )
+  })
+
+  describe('AuditLogService', () => {
+    it('stores the username of the actor', async () => {
+      const event = await auditLogService.record({
+        action: 'tool.approved',
+        actorUserId: alice.id,
+        targetType: 'tool',
+        targetId: 'weather.get',
+      })
+
+      expect(event?.actorUsername).toBe('alice')
+    })
+
+    it('logs failed writes instead of throwing them', async () => {
+      const logger = { error: vi.fn() }
+      const service = new AuditLogService(
+        auditRepository as unknown as AuditEventRepository,
+        userRepository as unknown as UserRepository,
+        logger
+      )
+      vi.spyOn(auditRepository, 'record').mockRejectedValue(new Error('database is locked'))
+
+      const event = await service.record({ action: 'auth.login', actorUserId: alice.id })
+
+      expect(event).toBeNull()
+      expect(logger.error).toHaveBeenCalledWith(
+        'Failed to write audit event',
+        expect.objectContaining({ action: 'auth.login', error: 'database is locked' })
+      )
+    })
+
+    it('rejects invalid pages and time ranges', async () => {
+      await expect(auditLogService.list({ limit: 0 })).rejects.toThrow(
+        'limit must be between 1 and 500'
+      )
+      await expect(auditLogService.list({ offset: -1 })).rejects.toThrow(
+        'offset must be zero or more'
+      )
+      const now = new Date()
+      await expect(auditLogService.list({ from: now, to: now })).rejects.toThrow(
+        'from must be before to'
+      )
+    })
+  })
+
+  describe('AuthService', () => {
+    it('records role changes and deleted users with the acting admin', async () => {
+      const authService = createTestAuthService({ userRepository, auditLogService })
+      const actor = { userId: admin.id, ip: '10.0.0.1' }
+
+      await authService.updateUserRole(alice.id, 'admin', actor)
+      await authService.updateUserRole(alice.id, 'admin', actor)
+      await authService.deleteUser(alice.id, actor)
+
+      expect(auditRepository.events.map((event) => event.action)).toEqual([
+        'user.role_changed',
+        'user.deleted',
+      ])
+      expect(auditRepository.events[0]).toMatchObject({
+        actorUserId: admin.id,
+        actorUsername: 'admin',
+        targetId: alice.id,
+        details: { username: 'alice', from: 'user', to: 'admin' },
+        ip: '10.0.0.1',
+      })
+    })
+  })
+
+  describe('AuditCleanupService', () => {
+    it('removes events older than the retention', async () => {
+      await auditLogService.record({ action: 'auth.login', actorUserId: alice.id })
+      await auditLogService.record({ action: 'auth.login', actorUserId: admin.id })
+      const [oldest] = auditRepository.events
+
+      const cleanup = new AuditCleanupService({
+        repository: auditRepository as unknown as AuditEventRepository,
+        retentionDays: 1,
+        now: () => new Date(oldest!.createdAt.getTime() + 24 * 60 * 60 * 1000 + 500),
+      })
+
+      expect(await cleanup.runOnce()).toBe(1)
+      expect(auditRepository.events.map((event) => event.actorUserId)).toEqual([admin.id])
+    })
+
+    it('keeps events forever when retention is disabled', async () => {
+      await auditLogService.record({ action: 'auth.login', actorUserId: alice.id })
+
+      const cleanup = new AuditCleanupService({
+        repository: auditRepository as unknown as AuditEventRepository,
+        retentionDays: 0,
+        now: () => new Date('2100-01-01T00:00:00Z'),
+      })
+
+      expect(await cleanup.runOnce()).toBe(0)
+      expect(auditRepository.events).toHaveLength(1)
+    })
+  })
+
+  describe('routes', () => {
+    function createServer(user: User) {
+      const authService = createLocalAuthService(userRepository)
+      const policyService = new PolicyService(
+        createMockPolicyRepository() as unknown as CapabilityPolicyRepository,
+        userRepository as unknown as UserRepository
+      )
+      return createServerForUser(user, { authService, policyService, auditLogService }, [
+        createTestAuthRoutes(authService, { policyService }),
+        createAuditRoutes(auditLogService),
+      ])
+    }
+
+    it('records permission grants made by admins', async () => {
+      const fastify = await createServer(admin)
+
+      await fastify.inject({
+        method: 'PUT',
+        url: `/auth/users/${alice.id}/policy`,
+        payload: { capabilities: ['extensions:install'], allowedModelConfigIds: null },
+      })
+
+      expect(auditRepository.events).toHaveLength(1)
+      expect(auditRepository.events[0]).toMatchObject({
+        action: 'policy.user_updated',
+        actorUserId: admin.id,
+        targetType: 'user',
+        targetId: alice.id,
+        details: { capabilities: ['extensions:install'], allowedModelConfigIds: null },
+      })
+    })
+
+    it('keeps changes that succeeded when the audit write fails', async () => {
+      vi.spyOn(auditRepository, 'record').mockRejectedValue(new Error('database is locked'))
+      const fastify = await createServer(admin)
+
+      const updated = await fastify.inject({
+        method: 'PUT',
+        url: `/auth/users/${alice.id}/policy`,
+        payload: { capabilities: ['extensions:install'], allowedModelConfigIds: null },
+      })
+      expect(updated.statusCode).toBe(200)
+
+      const reset = await fastify.inject({
+        method: 'DELETE',
+        url: `/auth/users/${alice.id}/policy`,
+      })
+      expect(reset.statusCode).toBe(200)
+      expect(reset.json()).toEqual({ success: true })
+    })
+
+    it('records the address the login came from, not the one the client sent', async () => {
+      const authService = createTestAuthService({
+        userRepository,
+        authConfigRepository: {
+          getOidcConfig: async () => ({
+            issuer: 'https://login.example.com',
+            clientId: 'stina',
+            redirectUri: 'http://localhost:3002/auth/oidc/callback',
+            scopes: ['openid'],
+            autoProvision: false,
+            defaultRole: 'user',
+          }),
+        },
+        auditLogService,
+      })
+      const fastify = await createTestServer({ authService, requireAuth: true }, [
+        createTestAuthRoutes(authService),
+      ])
+
+      const response = await fastify.inject({
+        method: 'POST',
+        url: '/auth/oidc/callback',
+        remoteAddress: '198.51.100.7',
+        payload: { code: 'code', state: 'unknown', deviceInfo: { ip: '203.0.113.9' } },
+      })
+
+      expect(response.statusCode).toBe(401)
+      expect(auditRepository.events).toHaveLength(1)
+      expect(auditRepository.events[0]).toMatchObject({
+        action: 'auth.login_failed',
+        ip: '198.51.100.7',
+      })
+    })
+
+    it('lets only admins read the audit log', async () => {
+      await auditLogService.record({ action: 'auth.login', actorUserId: alice.id })
+
+      const userServer = await createServer(alice)
+      const forbidden = await userServer.inject({ method: 'GET', url: '/admin/audit' })
+      expect(forbidden.statusCode).toBe(403)
+
+      const adminServer = await createServer(admin)
+      const response = await adminServer.inject({ method: 'GET', url: '/admin/audit' })
+      expect(response.statusCode).toBe(200)
+      expect(response.json()).toMatchObject({
+        total: 1,
+        events: [{ action: 'auth.login', actorUserId: alice.id, actorUsername: 'alice' }],
+      })
+    })
+
+    it('filters the audit log', async () => {
+      await auditLogService.record({ action: 'auth.login', actorUserId: alice.id })
+      await auditLogService.record({ action: 'auth.login', actorUserId: admin.id })
+      await auditLogService.record({ action: 'tool.denied', actorUserId: alice.id })
+      const fastify = await createServer(admin)
+
+      const response = await fastify.inject({
+        method: 'GET',
+        url: `/admin/audit?action=auth.login&userId=${alice.id}`,
+      })
+      expect(response.json().total).toBe(1)
+
+      const page = await fastify.inject({ method: 'GET', url: '/admin/audit?limit=2&offset=0' })
+      expect(page.json().total).toBe(3)
+      expect(page.json().events.map((event: AuditEvent) => event.action)).toEqual([
+        'tool.denied',
+        'auth.login',
+      ])
+
+      const unknown = await fastify.inject({ method: 'GET', url: '/admin/audit?action=everything' })
+      expect(unknown.statusCode).toBe(400)
+
+      const badDate = await fastify.inject({ method: 'GET', url: '/admin/audit?from=yesterday' })
+      expect(badDate.statusCode).toBe(400)
+    })
+  })
+})
@@ -0,0 +1,196 @@
+import { describe, it, expect, beforeEach } from 'vitest'
+import type { FastifyRequest } from 'fastify'
+import { PolicyService, requireCapability } from '@stina/auth'
+import type { User } from '@stina/auth'
+import type { CapabilityPolicyRepository, UserRepository } from '@stina/auth/db'
+import {
+  admin,
+  alice,
+  bob,
+  callPreHandler,
+  createLocalAuthService,
+  createMockPolicyRepository,
+  createMockReply,
+  createMockUserRepository,
+  createServerForUser,
+  createTestAuthRoutes,
+} from './helpers.js'
+
+const userRepository = createMockUserRepository([alice, bob, admin])
+
+describe('Capability policies', () => {
+  let policyRepository: ReturnType<typeof createMockPolicyRepository>
+  let policyService: PolicyService
+
+  beforeEach(() => {
+    policyRepository = createMockPolicyRepository()
+    policyService = new PolicyService(
+      policyRepository as unknown as CapabilityPolicyRepository,
+      userRepository as unknown as UserRepository
+    )
+  })
+
+  describe('PolicyService', () => {
+    it('keeps the current behavior without stored policies', async () => {
+      expect(await policyService.getEffectivePolicy(alice.id)).toEqual({
+        capabilities: ['tools:approve'],
+        allowedModelConfigIds: null,
+      })
+      expect(await policyService.can(alice.id, 'extensions:install')).toBe(false)
+      expect(await policyService.can(admin.id, 'extensions:install')).toBe(true)
+      expect(await policyService.canUseModelConfig(alice.id, 'any-model')).toBe(true)
+    })
+
+    it('lets user policies override the values they set', async () => {
+      await policyService.setRolePolicy('user', {
+        capabilities: [],
+        allowedModelConfigIds: ['small'],
+      })
+      await policyService.setUserPolicy(bob.id, {
+        capabilities: ['extensions:install', 'tools:approve'],
+        allowedModelConfigIds: null,
+      })
+
+      expect(await policyService.getEffectivePolicy(alice.id)).toEqual({
+        capabilities: [],
+        allowedModelConfigIds: ['small'],
+      })
+      expect(await policyService.getEffectivePolicy(bob.id)).toEqual({
+        capabilities: ['extensions:install', 'tools:approve'],
+        allowedModelConfigIds: ['small'],
+      })
+      expect(await policyService.canUseModelConfig(bob.id, 'large')).toBe(false)
+
+      await policyService.deleteUserPolicy(bob.id)
+      expect(await policyService.can(bob.id, 'extensions:install')).toBe(false)
+    })
+
+    it('never restricts admins', async () => {
+      await policyService.setRolePolicy('user', { capabilities: [], allowedModelConfigIds: [] })
+
+      await expect(
+        policyService.setRolePolicy('admin', { capabilities: [], allowedModelConfigIds: null })
+      ).rejects.toThrow('Admins always have every capability')
+      await expect(
+        policyService.setUserPolicy(admin.id, { capabilities: [], allowedModelConfigIds: null })
+      ).rejects.toThrow('Admins always have every capability')
+      expect(await policyService.canUseModelConfig(admin.id, 'large')).toBe(true)
+    })
+
+    it('rejects unknown capabilities and users', async () => {
+      await expect(
+        policyService.setRolePolicy('user', {
+          capabilities: ['extensions:install', 'everything'] as never,
+          allowedModelConfigIds: null,
+        })
+      ).rejects.toThrow('Unknown capabilities: everything')
+      await expect(
+        policyService.setUserPolicy('missing', { capabilities: [], allowedModelConfigIds: null })
+      ).rejects.toThrow('User not found')
+      expect(await policyService.getEffectivePolicy('missing')).toEqual({
+        capabilities: [],
+        allowedModelConfigIds: [],
+      })
+    })
+  })
+
+  describe('requireCapability middleware', () => {
+    function createMockRequest(user: User | null, withPolicyService = true): FastifyRequest {
+      return {
+        isAuthenticated: !!user,
+        user,
+        server: { policyService: withPolicyService ? policyService : null },
+      } as unknown as FastifyRequest
+    }
+
+    async function check(request: FastifyRequest) {
+      const reply = createMockReply()
+      await callPreHandler(requireCapability('extensions:install'), request, reply)
+      return reply
+    }
+
+    it('returns 401 for unauthenticated requests', async () => {
+      const reply = await check(createMockRequest(null))
+      expect(reply.statusCode).toBe(401)
+    })
+
+    it('returns 403 when the capability is missing', async () => {
+      const reply = await check(createMockRequest(alice))
+      expect(reply.statusCode).toBe(403)
+      expect(reply.sentData).toEqual({
+        error: {
+          code: 'FORBIDDEN',
+          message: 'The extensions:install capability is required',
+        },
+      })
+    })
+
+    it('allows users whose policy grants the capability', async () => {
+      await policyService.setUserPolicy(alice.id, {
+        capabilities: ['extensions:install'],
+        allowedModelConfigIds: null,
+      })
+      const reply = await check(createMockRequest(alice))
+      expect(reply.statusCode).toBe(200)
+      expect(reply.sentData).toBeNull()
+    })
+
+    it('uses the built-in role capabilities without a policy service', async () => {
+      expect((await check(createMockRequest(alice, false))).statusCode).toBe(403)
+      expect((await check(createMockRequest(admin, false))).statusCode).toBe(200)
+    })
+  })
+
+  describe('routes', () => {
+    function createServer(user: User) {
+      const authService = createLocalAuthService(userRepository)
+      return createServerForUser(user, { authService, policyService }, [
+        createTestAuthRoutes(authService, { policyService }),
+      ])
+    }
+
+    it('returns the policy of the current user', async () => {
+      const fastify = await createServer(alice)
+
+      const response = await fastify.inject({ method: 'GET', url: '/auth/me/policy' })
+
+      expect(response.json()).toEqual({
+        capabilities: ['tools:approve'],
+        allowedModelConfigIds: null,
+      })
+    })
+
+    it('only lets admins change policies', async () => {
+      const body = { capabilities: ['extensions:install'], allowedModelConfigIds: ['small'] }
+
+      const userServer = await createServer(alice)
+      const forbidden = await userServer.inject({
+        method: 'PUT',
+        url: `/auth/users/${alice.id}/policy`,
+        payload: body,
+      })
+      expect(forbidden.statusCode).toBe(403)
+
+      const adminServer = await createServer(admin)
+      const updated = await adminServer.inject({
+        method: 'PUT',
+        url: `/auth/users/${alice.id}/policy`,
+        payload: body,
+      })
+      expect(updated.json()).toEqual(body)
+
+      const policy = await adminServer.inject({
+        method: 'GET',
+        url: `/auth/users/${alice.id}/policy`,
+      })
+      expect(policy.json().effective).toEqual(body)
+
+      const adminRole = await adminServer.inject({
+        method: 'PUT',
+        url: '/auth/policies/roles/admin',
+        payload: body,
+      })
+      expect(adminRole.statusCode).toBe(400)
+    })
+  })
+})
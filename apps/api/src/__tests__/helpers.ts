@@ -0,0 +1,470 @@
+/**
+ * Shared fixtures, in-memory repositories and server builders for the API tests
+ */
+
+import Fastify from 'fastify'
+import type { FastifyInstance, FastifyPluginAsync, FastifyReply, FastifyRequest } from 'fastify'
+import { AuthService, TokenService, authPlugin } from '@stina/auth'
+import type {
+  AuditEvent,
+  AuditEventFilter,
+  AuditLogService,
+  AuthPluginOptions,
+  CapabilityPolicy,
+  CreateUserInput,
+  OidcIdentity,
+  OidcService,
+  PasskeyService,
+  PersonalAccessTokenData,
+  PersonalAccessTokenService,
+  PolicyService,
+  RecordAuditEventInput,
+  RefreshTokenData,
+  UpdateUserInput,
+  User,
+  UserRole,
+} from '@stina/auth'
+import type {
+  AuthConfigRepository,
+  CreatePersonalAccessTokenInput,
+  CreateRefreshTokenInput,
+  InvitationRepository,
+  PasskeyCredentialRepository,
+  RefreshTokenRepository,
+  UserRepository,
+} from '@stina/auth/db'
+import { createAuthRoutes } from '../routes/auth.js'
+
+// =============================================================================
+// Users
+// =============================================================================
+
+/**
+ * Create a user fixture. Role defaults to 'user'.
+ */
+export function createTestUser(user: Pick<User, 'id' | 'username'> & Partial<User>): User {
+  const createdAt = new Date('2026-01-01T00:00:00Z')
+  return {
+    displayName: null,
+    role: 'user',
+    createdAt,
+    updatedAt: createdAt,
+    lastLoginAt: null,
+    oidcLinked: false,
+    ...user,
+  }
+}
+
+export const alice = createTestUser({ id: 'user-1', username: 'alice' })
+export const bob = createTestUser({ id: 'user-2', username: 'bob' })
+export const admin = createTestUser({ id: 'user-3', username: 'admin', role: 'admin' })
+
+// =============================================================================
+// In-memory repositories
+// =============================================================================
+
+type StoredUser = User & { oidc: OidcIdentity | null }
+
+/**
+ * In-memory stand-in for UserRepository
+ * @param initialUsers Users the repository starts with
+ */
+export function createMockUserRepository(initialUsers: User[] = []) {
+  const users = new Map<string, StoredUser>(
+    initialUsers.map((user) => [user.id, { ...user, oidc: null }])
+  )
+  let nextId = 1
+
+  const toUser = (row: StoredUser): User => {
+    const { oidc, ...user } = row
+    return { ...user, oidcLinked: oidc !== null }
+  }
+
+  return {
+    users,
+    async create(input: CreateUserInput) {
+      let id = input.id
+      while (!id || users.has(id)) {
+        id = `user-${nextId++}`
+      }
+      const now = new Date()
+      users.set(id, {
+        id,
+        username: input.username,
+        displayName: input.displayName ?? null,
+        role: input.role ?? 'user',
+        oidcLinked: false,
+        createdAt: now,
+        updatedAt: now,
+        lastLoginAt: null,
+        oidc: null,
+      })
+      return toUser(users.get(id)!)
+    },
+    async getById(id: string) {
+      const row = users.get(id)
+      return row ? toUser(row) : null
+    },
+    async getByUsername(username: string) {
+      const row = [...users.values()].find((u) => u.username === username)
+      return row ? toUser(row) : null
+    },
+    async getByOidcIdentity(identity: OidcIdentity) {
+      const row = [...users.values()].find(
+        (u) => u.oidc?.issuer === identity.issuer && u.oidc?.subject === identity.subject
+      )
+      return row ? toUser(row) : null
+    },
+    async list() {
+      return [...users.values()].map(toUser)
+    },
+    async update(id: string, input: UpdateUserInput) {
+      const row = users.get(id)
+      if (!row) return null
+      if (input.displayName !== undefined) row.displayName = input.displayName
+      if (input.role !== undefined) row.role = input.role
+      row.updatedAt = new Date()
+      return toUser(row)
+    },
+    async linkOidcIdentity(id: string, identity: OidcIdentity) {
+      users.get(id)!.oidc = { issuer: identity.issuer, subject: identity.subject }
+    },
+    async unlinkOidcIdentity(id: string) {
+      users.get(id)!.oidc = null
+    },
+    async updateLastLogin(id: string) {
+      users.get(id)!.lastLoginAt = new Date()
+    },
+    async delete(id: string) {
+      users.delete(id)
+    },
+    async isEmpty() {
+      return users.size === 0
+    },
+  }
+}
+
+/**
+ * In-memory stand-in for RefreshTokenRepository
+ */
+export function createMockRefreshTokenRepository() {
+  const rows = new Map<string, RefreshTokenData>()
+
+  return {
+    rows,
+    async create(input: CreateRefreshTokenInput) {
+      const id = input.id ?? `token-${rows.size + 1}`
+      rows.set(id, {
+        id,
+        userId: input.userId,
+        tokenHash: input.tokenHash,
+        expiresAt: input.expiresAt,
+        createdAt: new Date(),
+        revokedAt: null,
+        lastUsedAt: null,
+        deviceInfo: input.deviceInfo ?? null,
+      })
+      return { ...rows.get(id)! }
+    },
+    async getById(id: string) {
+      const row = rows.get(id)
+      return row ? { ...row } : null
+    },
+    async getValidByTokenHash(tokenHash: string) {
+      const row = [...rows.values()].find((r) => r.tokenHash === tokenHash)
+      if (!row || row.revokedAt || row.expiresAt < new Date()) return null
+      return { ...row }
+    },
+    async getActiveByUserId(userId: string) {
+      return [...rows.values()].filter(
+        (r) => r.userId === userId && !r.revokedAt && r.expiresAt >= new Date()
+      )
+    },
+    async updateLastUsed(id: string, lastUsedAt: Date) {
+      rows.get(id)!.lastUsedAt = lastUsedAt
+    },
+    async revokeForUser(userId: string, id: string) {
+      const row = rows.get(id)
+      if (!row || row.userId !== userId || row.revokedAt) return false
+      row.revokedAt = new Date()
+      return true
+    },
+    async revokeAllByUserId(userId: string) {
+      for (const row of rows.values()) {
+        if (row.userId === userId && !row.revokedAt) {
+          row.revokedAt = new Date()
+        }
+      }
+    },
+  }
+}
+
+/**
+ * In-memory stand-in for PersonalAccessTokenRepository
+ */
+export function createMockPersonalAccessTokenRepository() {
+  const rows = new Map<string, PersonalAccessTokenData & { tokenHash: string }>()
+  let nextId = 1
+
+  return {
+    rows,
+    async create(input: CreatePersonalAccessTokenInput) {
+      const id = `pat-${nextId++}`
+      rows.set(id, {
+        ...input,
+        id,
+        createdAt: new Date(),
+        lastUsedAt: null,
+        revokedAt: null,
+      })
+      return { ...rows.get(id)! }
+    },
+    async getValidByTokenHash(tokenHash: string) {
+      const row = [...rows.values()].find((r) => r.tokenHash === tokenHash)
+      if (!row || row.revokedAt) return null
+      if (row.expiresAt && row.expiresAt < new Date()) return null
+      return { ...row }
+    },
+    async getActiveByUserId(userId: string) {
+      return [...rows.values()].filter((r) => r.userId === userId && !r.revokedAt)
+    },
+    async updateLastUsed(id: string, lastUsedAt: Date) {
+      rows.get(id)!.lastUsedAt = lastUsedAt
+    },
+    async revoke(userId: string, id: string) {
+      const row = rows.get(id)
+      if (!row || row.userId !== userId || row.revokedAt) return false
+      row.revokedAt = new Date()
+      return true
+    },
+  }
+}
+
+/**
+ * In-memory stand-in for CapabilityPolicyRepository
+ */
+export function createMockPolicyRepository() {
+  const rolePolicies = new Map<UserRole, CapabilityPolicy>()
+  const userPolicies = new Map<string, CapabilityPolicy>()
+
+  return {
+    rolePolicies,
+    userPolicies,
+    async getRolePolicy(role: UserRole) {
+      return rolePolicies.get(role) ?? null
+    },
+    async setRolePolicy(role: UserRole, policy: CapabilityPolicy) {
+      rolePolicies.set(role, policy)
+    },
+    async getUserPolicy(userId: string) {
+      return userPolicies.get(userId) ?? null
+    },
+    async setUserPolicy(userId: string, policy: CapabilityPolicy) {
+      userPolicies.set(userId, policy)
+    },
+    async deleteUserPolicy(userId: string) {
+      return userPolicies.delete(userId)
+    },
+  }
+}
+
+/**
+ * In-memory stand-in for AuditEventRepository.
+ * Each event is recorded one second after the previous one.
+ */
+export function createMockAuditEventRepository() {
+  const events: AuditEvent[] = []
+  let nextId = 1
+  let clock = Date.parse('2026-01-01T00:00:00Z')
+
+  const matches = (event: AuditEvent, filter: AuditEventFilter) =>
+    (!filter.action || event.action === filter.action) &&
+    (!filter.actorUserId || event.actorUserId === filter.actorUserId) &&
+    (!filter.targetType || event.targetType === filter.targetType) &&
+    (!filter.targetId || event.targetId === filter.targetId) &&
+    (!filter.from || event.createdAt >= filter.from) &&
+    (!filter.to || event.createdAt < filter.to)
+
+  return {
+    events,
+    async record(input: RecordAuditEventInput) {
+      const event: AuditEvent = {
+        id: `event-${nextId++}`,
+        createdAt: new Date((clock += 1000)),
+        actorUserId: input.actorUserId ?? null,
+        actorUsername: input.actorUsername ?? null,
+        action: input.action,
+        targetType: input.targetType ?? null,
+        targetId: input.targetId ?? null,
+        details: input.details ?? null,
+        ip: input.ip ?? null,
+      }
+      events.push(event)
+      return event
+    },
+    async list(filter: AuditEventFilter = {}) {
+      const offset = filter.offset ?? 0
+      return events
+        .filter((event) => matches(event, filter))
+        .reverse()
+        .slice(offset, offset + (filter.limit ?? 100))
+    },
+    async count(filter: AuditEventFilter = {}) {
+      return events.filter((event) => matches(event, filter)).length
+    },
+    async deleteBefore(before: Date) {
+      const kept = events.filter((event) => event.createdAt >= before)
+      const deleted = events.length - kept.length
+      events.splice(0, events.length, ...kept)
+      return deleted
+    },
+  }
+}
+
+// =============================================================================
+// Services
+// =============================================================================
+
+/**
+ * Create a token service with fixed test secrets
+ */
+export function createTestTokenService(): TokenService {
+  return new TokenService({
+    accessTokenSecret: 'access-secret-for-tests',
+    refreshTokenSecret: 'refresh-secret-for-tests',
+  })
+}
+
+export interface TestAuthServiceDependencies {
+  userRepository?: ReturnType<typeof createMockUserRepository>
+  refreshTokenRepository?: ReturnType<typeof createMockRefreshTokenRepository>
+  authConfigRepository?: Partial<AuthConfigRepository>
+  tokenService?: TokenService
+  oidcService?: OidcService
+  auditLogService?: AuditLogService
+}
+
+/**
+ * Create an AuthService backed by in-memory repositories.
+ * Passkeys and invitations are not available.
+ */
+export function createTestAuthService(dependencies: TestAuthServiceDependencies = {}): AuthService {
+  return new AuthService(
+    (dependencies.userRepository ?? createMockUserRepository()) as unknown as UserRepository,
+    {} as PasskeyCredentialRepository,
+    (dependencies.refreshTokenRepository ??
+      createMockRefreshTokenRepository()) as unknown as RefreshTokenRepository,
+    (dependencies.authConfigRepository ?? {}) as AuthConfigRepository,
+    {} as InvitationRepository,
+    dependencies.tokenService ?? createTestTokenService(),
+    {} as PasskeyService,
+    dependencies.oidcService,
+    dependencies.auditLogService
+  )
+}
+
+/**
+ * Auth service stand-in for local mode, where the auth plugin only looks up the default user
+ */
+export function createLocalAuthService(userRepository: {
+  getById(id: string): Promise<User | null>
+}): AuthService {
+  return { getUserById: (id: string) => userRepository.getById(id) } as unknown as AuthService
+}
+
+// =============================================================================
+// Servers
+// =============================================================================
+
+/**
+ * Create a server with the auth plugin and the given routes
+ */
+export async function createTestServer(
+  options: AuthPluginOptions,
+  routes: FastifyPluginAsync[] = []
+): Promise<FastifyInstance> {
+  const fastify = Fastify()
+  await fastify.register(authPlugin, options)
+  for (const route of routes) {
+    await fastify.register(route)
+  }
+  return fastify
+}
+
+/**
+ * Create a server in local mode, where every request is made by the given user
+ */
+export async function createServerForUser(
+  user: User,
+  options: Omit<AuthPluginOptions, 'requireAuth' | 'defaultUserId'>,
+  routes: FastifyPluginAsync[] = []
+): Promise<FastifyInstance> {
+  return createTestServer({ ...options, requireAuth: false, defaultUserId: user.id }, routes)
+}
+
+/**
+ * The auth routes, with stand-ins for the services a test does not use
+ */
+export function createTestAuthRoutes(
+  authService: AuthService,
+  services: {
+    personalAccessTokenService?: PersonalAccessTokenService
+    policyService?: PolicyService
+  } = {}
+): FastifyPluginAsync {
+  return createAuthRoutes(
+    authService,
+    services.personalAccessTokenService ?? ({} as PersonalAccessTokenService),
+    services.policyService ?? ({} as PolicyService)
+  )
+}
+
+// =============================================================================
+// Middleware
+// =============================================================================
+
+/**
+ * Creates a mock Fastify reply object that records the status code and body
+ */
+export function createMockReply(): FastifyReply & {
+  statusCode: number
+  sentData: unknown
+} {
+  let statusCode = 200
+  let sentData: unknown = null
+
+  const reply = {
+    get statusCode() {
+      return statusCode
+    },
+    get sentData() {
+      return sentData
+    },
+    code(code: number) {
+      statusCode = code
+      return reply
+    },
+    send(data: unknown) {
+      sentData = data
+      return reply
+    },
+  }
+
+  return reply as unknown as FastifyReply & { statusCode: number; sentData: unknown }
+}
+
+/**
+ * Call a preHandler with a mock done callback
+ */
+export async function callPreHandler(
+  handler: (
+    request: FastifyRequest,
+    reply: FastifyReply,
+    done: (err?: Error) => void
+  ) => void | Promise<void>,
+  request: FastifyRequest,
+  reply: FastifyReply
+): Promise<void> {
+  const done = () => {}
+  await handler(request, reply, done)
+}
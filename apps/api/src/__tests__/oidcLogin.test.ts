@@ -0,0 +1,357 @@
+import { describe, it, expect, beforeAll, afterAll, beforeEach } from 'vitest'
+import { createServer } from 'node:http'
+import type { Server } from 'node:http'
+import type { AddressInfo } from 'node:net'
+import { createHash, createSign, generateKeyPairSync } from 'node:crypto'
+import { OidcService } from '@stina/auth'
+import type { AuthService, OidcConfig } from '@stina/auth'
+import {
+  createMockUserRepository,
+  createTestAuthRoutes,
+  createTestAuthService,
+  createTestServer,
+} from './helpers.js'
+
+interface IssuedCode {
+  codeChallenge: string
+  claims: Record<string, unknown>
+}
+
+/**
+ * Minimal OpenID Connect provider serving discovery, JWKS and the token endpoint.
+ * The authorization step is simulated with authorize(), which returns a code for a URL.
+ */
+function createMockIssuer() {
+  const { privateKey, publicKey } = generateKeyPairSync('rsa', { modulusLength: 2048 })
+  const jwk = { ...publicKey.export({ format: 'jwk' }), kid: 'test-key', alg: 'RS256', use: 'sig' }
+  const codes = new Map<string, IssuedCode>()
+  let nextCode = 1
+  let issuer = ''
+
+  function signIdToken(claims: Record<string, unknown>): string {
+    const encode = (value: object) => Buffer.from(JSON.stringify(value)).toString('base64url')
+    const now = Math.floor(Date.now() / 1000)
+    const header = encode({ alg: 'RS256', typ: 'JWT', kid: 'test-key' })
+    const payload = encode({ iss: issuer, iat: now, exp: now + 300, ...claims })
+    const signature = createSign('RSA-SHA256')
+      .update(`${header}.${payload}`)
+      .sign(privateKey)
+      .toString('base64url')
+    return `${header}.${payload}.${signature}`
+  }
+
+  const server: Server = createServer((req, res) => {
+    const json = (status: number, body: unknown) => {
+      res.writeHead(status, { 'Content-Type': 'application/json' })
+      res.end(JSON.stringify(body))
+    }
+
+    if (req.url === '/.well-known/openid-configuration') {
+      return json(200, {
+        issuer,
+        authorization_endpoint: `${issuer}/authorize`,
+        token_endpoint: `${issuer}/token`,
+        jwks_uri: `${issuer}/jwks`,
+        token_endpoint_auth_methods_supported: ['client_secret_basic'],
+      })
+    }
+    if (req.url === '/jwks') {
+      return json(200, { keys: [jwk] })
+    }
+    if (req.url === '/token' && req.method === 'POST') {
+      let body = ''
+      req.on('data', (chunk) => (body += chunk))
+      req.on('end', () => {
+        const params = new URLSearchParams(body)
+        const issued = codes.get(params.get('code') ?? '')
+        codes.delete(params.get('code') ?? '')
+        const verifier = params.get('code_verifier') ?? ''
+        const challenge = createHash('sha256').update(verifier).digest('base64url')
+        if (!issued || challenge !== issued.codeChallenge) {
+          return json(400, { error: 'invalid_grant' })
+        }
+        const credentials = Buffer.from('stina:secret').toString('base64')
+        if (req.headers.authorization !== `Basic ${credentials}`) {
+          return json(401, { error: 'invalid_client' })
+        }
+        json(200, {
+          access_token: 'unused',
+          token_type: 'Bearer',
+          id_token: signIdToken(issued.claims),
+        })
+      })
+      return
+    }
+    json(404, { error: 'not_found' })
+  })
+
+  return {
+    get issuer() {
+      return issuer
+    },
+    async start() {
+      await new Promise<void>((resolve) => server.listen(0, '127.0.0.1', resolve))
+      issuer = `http://127.0.0.1:${(server.address() as AddressInfo).port}`
+    },
+    async stop() {
+      await new Promise<void>((resolve) => server.close(() => resolve()))
+    },
+    /**
+     * Act as the user approving the login and return the code for the redirect
+     */
+    authorize(authorizationUrl: string, claims: Record<string, unknown> = {}): string {
+      const url = new URL(authorizationUrl)
+      const code = `code-${nextCode++}`
+      codes.set(code, {
+        codeChallenge: url.searchParams.get('code_challenge') ?? '',
+        claims: {
+          aud: url.searchParams.get('client_id'),
+          nonce: url.searchParams.get('nonce'),
+          sub: 'subject-1',
+          preferred_username: 'alice',
+          name: 'Alice',
+          ...claims,
+        },
+      })
+      return code
+    },
+  }
+}
+
+describe('OpenID Connect login', () => {
+  const mockIssuer = createMockIssuer()
+  let config: OidcConfig
+
+  beforeAll(async () => {
+    await mockIssuer.start()
+  })
+
+  afterAll(async () => {
+    await mockIssuer.stop()
+  })
+
+  beforeEach(() => {
+    config = {
+      issuer: mockIssuer.issuer,
+      clientId: 'stina',
+      clientSecret: 'secret',
+      redirectUri: 'http://localhost:3002/auth/oidc/callback',
+      scopes: ['openid', 'profile', 'email'],
+      autoProvision: false,
+      defaultRole: 'user',
+    }
+  })
+
+  describe('OidcService', () => {
+    it('builds an authorization URL with PKCE', async () => {
+      const service = new OidcService()
+      const { authorizationUrl, browserBinding } = await service.createAuthorizationUrl(config)
+      const url = new URL(authorizationUrl)
+
+      expect(url.origin + url.pathname).toBe(`${mockIssuer.issuer}/authorize`)
+      expect(url.searchParams.get('response_type')).toBe('code')
+      expect(url.searchParams.get('client_id')).toBe('stina')
+      expect(url.searchParams.get('scope')).toBe('openid profile email')
+      expect(url.searchParams.get('code_challenge_method')).toBe('S256')
+      expect(url.searchParams.get('state')).toBeTruthy()
+      expect(url.searchParams.get('nonce')).toBeTruthy()
+      expect(browserBinding).toBeTruthy()
+      expect(authorizationUrl).not.toContain(browserBinding)
+    })
+
+    it('exchanges the code and returns the verified profile', async () => {
+      const service = new OidcService()
+      const { authorizationUrl: url, browserBinding } = await service.createAuthorizationUrl(
+        config,
+        { linkUserId: 'user-7' }
+      )
+      const code = mockIssuer.authorize(url, { email: 'alice@example.com' })
+      const state = new URL(url).searchParams.get('state')!
+
+      const result = await service.handleCallback(config, { code, state, browserBinding })
+
+      expect(result).toEqual({
+        profile: {
+          issuer: mockIssuer.issuer,
+          subject: 'subject-1',
+          username: 'alice',
+          displayName: 'Alice',
+          email: 'alice@example.com',
+        },
+        linkUserId: 'user-7',
+      })
+    })
+
+    it('only accepts a state once', async () => {
+      const service = new OidcService()
+      const { authorizationUrl: url, browserBinding } = await service.createAuthorizationUrl(config)
+      const state = new URL(url).searchParams.get('state')!
+
+      const callback = () =>
+        service.handleCallback(config, { code: mockIssuer.authorize(url), state, browserBinding })
+
+      await callback()
+      await expect(callback()).rejects.toThrow('expired or not found')
+    })
+
+    it('rejects a state from a login started in another browser', async () => {
+      const service = new OidcService()
+      const attacker = await service.createAuthorizationUrl(config)
+      const victim = await service.createAuthorizationUrl(config)
+
+      // The victim's browser is sent to the callback with the attacker's code and state
+      await expect(
+        service.handleCallback(config, {
+          code: mockIssuer.authorize(attacker.authorizationUrl),
+          state: new URL(attacker.authorizationUrl).searchParams.get('state')!,
+          browserBinding: victim.browserBinding,
+        })
+      ).rejects.toThrow('started in another browser')
+
+      await expect(
+        service.handleCallback(config, {
+          code: mockIssuer.authorize(victim.authorizationUrl),
+          state: new URL(victim.authorizationUrl).searchParams.get('state')!,
+        })
+      ).rejects.toThrow('started in another browser')
+    })
+
+    it('rejects ID tokens with another nonce or audience', async () => {
+      const service = new OidcService()
+
+      const nonceLogin = await service.createAuthorizationUrl(config)
+      await expect(
+        service.handleCallback(config, {
+          code: mockIssuer.authorize(nonceLogin.authorizationUrl, { nonce: 'other' }),
+          state: new URL(nonceLogin.authorizationUrl).searchParams.get('state')!,
+          browserBinding: nonceLogin.browserBinding,
+        })
+      ).rejects.toThrow('nonce')
+
+      const audienceLogin = await service.createAuthorizationUrl(config)
+      await expect(
+        service.handleCallback(config, {
+          code: mockIssuer.authorize(audienceLogin.authorizationUrl, { aud: 'other-client' }),
+          state: new URL(audienceLogin.authorizationUrl).searchParams.get('state')!,
+          browserBinding: audienceLogin.browserBinding,
+        })
+      ).rejects.toThrow('Invalid ID token')
+    })
+  })
+
+  describe('AuthService', () => {
+    let userRepository: ReturnType<typeof createMockUserRepository>
+    let authService: AuthService
+
+    beforeEach(() => {
+      userRepository = createMockUserRepository()
+      authService = createTestAuthService({
+        userRepository,
+        authConfigRepository: { getOidcConfig: async () => config },
+        oidcService: new OidcService(),
+      })
+    })
+
+    async function login(claims?: Record<string, unknown>, linkUserId?: string) {
+      const { authorizationUrl, browserBinding } = await authService.startOidcLogin({ linkUserId })
+      return authService.completeOidcLogin({
+        code: mockIssuer.authorize(authorizationUrl, claims),
+        state: new URL(authorizationUrl).searchParams.get('state')!,
+        browserBinding,
+      })
+    }
+
+    it('refuses unknown identities unless auto-provisioning is on', async () => {
+      const result = await login()
+      expect(result.success).toBe(false)
+      expect(result.error).toContain('No account is linked')
+      expect(userRepository.users.size).toBe(0)
+    })
+
+    it('creates accounts with the default role', async () => {
+      config.autoProvision = true
+      await userRepository.create({ username: 'admin', role: 'admin' })
+
+      const result = await login()
+
+      expect(result.success).toBe(true)
+      expect(result.tokens?.accessToken).toBeTruthy()
+      expect(result.user).toMatchObject({
+        username: 'alice',
+        displayName: 'Alice',
+        role: 'user',
+        oidcLinked: true,
+      })
+    })
+
+    it('logs in to the account the identity was linked to', async () => {
+      const bob = await userRepository.create({ username: 'bob' })
+
+      const linked = await login({}, bob.id)
+      expect(linked.user).toMatchObject({ id: bob.id, oidcLinked: true })
+
+      const result = await login()
+      expect(result.success).toBe(true)
+      expect(result.user?.id).toBe(bob.id)
+    })
+
+    it('does not link an identity to a second account', async () => {
+      const bob = await userRepository.create({ username: 'bob' })
+      const carol = await userRepository.create({ username: 'carol' })
+      await login({}, bob.id)
+
+      const result = await login({}, carol.id)
+      expect(result.success).toBe(false)
+      expect(result.error).toContain('already linked')
+    })
+  })
+
+  describe('routes', () => {
+    it('binds the login to the browser with a cookie', async () => {
+      const userRepository = createMockUserRepository()
+      await userRepository.create({ username: 'admin', role: 'admin' })
+      config.autoProvision = true
+      const authService = createTestAuthService({
+        userRepository,
+        authConfigRepository: { getOidcConfig: async () => config },
+        oidcService: new OidcService(),
+      })
+      const fastify = await createTestServer({ authService, requireAuth: true }, [
+        createTestAuthRoutes(authService),
+      ])
+
+      async function startLogin() {
+        const response = await fastify.inject({ method: 'POST', url: '/auth/oidc/authorize' })
+        const { authorizationUrl } = response.json()
+        return {
+          setCookie: response.headers['set-cookie'] as string,
+          code: mockIssuer.authorize(authorizationUrl),
+          state: new URL(authorizationUrl).searchParams.get('state'),
+        }
+      }
+
+      function completeLogin(login: { code: string; state: string | null }, cookie: string) {
+        return fastify.inject({
+          method: 'POST',
+          url: '/auth/oidc/callback',
+          headers: { cookie },
+          payload: { code: login.code, state: login.state },
+        })
+      }
+
+      const first = await startLogin()
+      expect(first.setCookie).toMatch(/^stina_oidc_binding=[\w-]+; Max-Age=600; Path=\/; HttpOnly;/)
+      expect(first.setCookie).toMatch(/SameSite=Lax$/)
+
+      const otherBrowser = await completeLogin(first, 'stina_oidc_binding=other')
+      expect(otherBrowser.statusCode).toBe(401)
+      expect(otherBrowser.json().error).toContain('started in another browser')
+      expect(otherBrowser.headers['set-cookie']).toContain('stina_oidc_binding=; Max-Age=0')
+
+      const second = await startLogin()
+      const sameBrowser = await completeLogin(second, second.setCookie.split(';')[0]!)
+      expect(sameBrowser.statusCode).toBe(200)
+      expect(sameBrowser.json().user).toMatchObject({ username: 'alice' })
+    })
+  })
+})
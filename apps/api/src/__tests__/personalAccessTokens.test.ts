@@ -0,0 +1,156 @@
+import { describe, it, expect, beforeEach } from 'vitest'
+import { PersonalAccessTokenService } from '@stina/auth'
+import type { AuthService, User } from '@stina/auth'
+import type { PersonalAccessTokenRepository } from '@stina/auth/db'
+import { getPersonalAccessTokenScope } from '../personalAccessTokenScopes.js'
+import { alice, createMockPersonalAccessTokenRepository, createTestServer } from './helpers.js'
+
+describe('getPersonalAccessTokenScope', () => {
+  it('maps reads and writes to the resource scope', () => {
+    expect(getPersonalAccessTokenScope('GET', '/chat/conversations')).toBe('chat:read')
+    expect(getPersonalAccessTokenScope('POST', '/chat/stream')).toBe('chat:write')
+    expect(getPersonalAccessTokenScope('GET', '/scheduled-jobs')).toBe('jobs:read')
+    expect(getPersonalAccessTokenScope('DELETE', '/scheduled-jobs/job-1')).toBe('jobs:write')
+    expect(getPersonalAccessTokenScope('PUT', '/settings/app')).toBe('settings:write')
+  })
+
+  it('does not allow tokens for other routes', () => {
+    expect(getPersonalAccessTokenScope('GET', '/auth/tokens')).toBeNull()
+    expect(getPersonalAccessTokenScope('POST', '/auth/tokens')).toBeNull()
+    expect(getPersonalAccessTokenScope('GET', '/extensions')).toBeNull()
+    expect(getPersonalAccessTokenScope('GET', '/chatbot')).toBeNull()
+  })
+})
+
+describe('PersonalAccessTokenService', () => {
+  let repository: ReturnType<typeof createMockPersonalAccessTokenRepository>
+  let service: PersonalAccessTokenService
+
+  beforeEach(() => {
+    repository = createMockPersonalAccessTokenRepository()
+    service = new PersonalAccessTokenService(
+      repository as unknown as PersonalAccessTokenRepository
+    )
+  })
+
+  it('creates a token that can be verified', async () => {
+    const { token, data } = await service.create('user-1', {
+      name: ' Backup ',
+      scopes: ['chat:read', 'chat:read'],
+    })
+
+    expect(PersonalAccessTokenService.isPersonalAccessToken(token)).toBe(true)
+    expect(data).toMatchObject({ name: 'Backup', scopes: ['chat:read'], expiresAt: null })
+    expect(token.startsWith(data.tokenPrefix)).toBe(true)
+    expect(repository.rows.get(data.id)!.tokenHash).not.toContain(token)
+
+    const verified = await service.verify(token)
+    expect(verified?.userId).toBe('user-1')
+    expect(verified?.lastUsedAt).toBeInstanceOf(Date)
+  })
+
+  it('rejects invalid input', async () => {
+    await expect(service.create('user-1', { name: '', scopes: ['chat:read'] })).rejects.toThrow(
+      'Name'
+    )
+    await expect(service.create('user-1', { name: 'Script', scopes: [] })).rejects.toThrow(
+      'scope'
+    )
+    await expect(
+      service.create('user-1', {
+        name: 'Script',
+        scopes: ['admin' as unknown as 'chat:read'],
+      })
+    ).rejects.toThrow('Unknown scope')
+    await expect(
+      service.create('user-1', { name: 'Script', scopes: ['chat:read'], expiresInDays: 0 })
+    ).rejects.toThrow('expiresInDays')
+  })
+
+  it('does not verify revoked, expired or unknown tokens', async () => {
+    const { token, data } = await service.create('user-1', { name: 'A', scopes: ['chat:read'] })
+    expect(await service.revoke('user-2', data.id)).toBe(false)
+    expect(await service.revoke('user-1', data.id)).toBe(true)
+    expect(await service.verify(token)).toBeNull()
+
+    const expiring = await service.create('user-1', {
+      name: 'B',
+      scopes: ['chat:read'],
+      expiresInDays: 1,
+    })
+    repository.rows.get(expiring.data.id)!.expiresAt = new Date(Date.now() - 1000)
+    expect(await service.verify(expiring.token)).toBeNull()
+
+    expect(await service.verify('stina_pat_unknown')).toBeNull()
+    expect(await service.verify('not-a-token')).toBeNull()
+  })
+
+  it('updates the last used timestamp at most once a minute', async () => {
+    const { token, data } = await service.create('user-1', { name: 'A', scopes: ['chat:read'] })
+    await service.verify(token)
+    const firstUse = repository.rows.get(data.id)!.lastUsedAt
+    await service.verify(token)
+    expect(repository.rows.get(data.id)!.lastUsedAt).toBe(firstUse)
+  })
+})
+
+describe('authPlugin with personal access tokens', () => {
+  const user = alice
+
+  async function createServer() {
+    const repository = createMockPersonalAccessTokenRepository()
+    const personalAccessTokenService = new PersonalAccessTokenService(
+      repository as unknown as PersonalAccessTokenRepository
+    )
+    const authService = {
+      getUserById: async (id: string) => (id === user.id ? user : null),
+      verifyAccessToken: async () => {
+        throw new Error('Not a JWT')
+      },
+    } as unknown as AuthService
+
+    const fastify = await createTestServer({
+      authService,
+      requireAuth: true,
+      personalAccessTokenService,
+      getRequiredScope: getPersonalAccessTokenScope,
+    })
+    const handler = async (request: { user: User | null }) => ({ userId: request.user?.id ?? null })
+    fastify.get('/chat/conversations', handler)
+    fastify.post('/chat/stream', handler)
+    fastify.get('/auth/tokens', handler)
+
+    return { fastify, personalAccessTokenService }
+  }
+
+  it('authenticates requests covered by the token scopes', async () => {
+    const { fastify, personalAccessTokenService } = await createServer()
+    const { token } = await personalAccessTokenService.create(user.id, {
+      name: 'Script',
+      scopes: ['chat:read'],
+    })
+
+    const response = await fastify.inject({
+      method: 'GET',
+      url: '/chat/conversations?limit=5',
+      headers: { authorization: `Bearer ${token}` },
+    })
+    expect(response.json()).toEqual({ userId: 'user-1' })
+  })
+
+  it('rejects requests outside the token scopes', async () => {
+    const { fastify, personalAccessTokenService } = await createServer()
+    const { token } = await personalAccessTokenService.create(user.id, {
+      name: 'Script',
+      scopes: ['chat:read'],
+    })
+    const headers = { authorization: `Bearer ${token}` }
+
+    const write = await fastify.inject({ method: 'POST', url: '/chat/stream', headers })
+    expect(write.statusCode).toBe(403)
+    expect(write.json().error.code).toBe('FORBIDDEN')
+
+    const auth = await fastify.inject({ method: 'GET', url: '/auth/tokens', headers })
+    expect(auth.json()).toEqual({ userId: null })
+  })
+})
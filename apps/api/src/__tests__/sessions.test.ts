@@ -0,0 +1,157 @@
+import { describe, it, expect, beforeEach } from 'vitest'
+import type { AuthService, TokenService, User } from '@stina/auth'
+import {
+  admin,
+  alice,
+  createMockRefreshTokenRepository,
+  createMockUserRepository,
+  createTestAuthRoutes,
+  createTestAuthService,
+  createTestServer,
+  createTestTokenService,
+} from './helpers.js'
+
+describe('Session management', () => {
+  let refreshTokenRepository: ReturnType<typeof createMockRefreshTokenRepository>
+  let tokenService: TokenService
+  let authService: AuthService
+
+  beforeEach(() => {
+    refreshTokenRepository = createMockRefreshTokenRepository()
+    tokenService = createTestTokenService()
+    authService = createTestAuthService({
+      userRepository: createMockUserRepository([alice, admin]),
+      refreshTokenRepository,
+      tokenService,
+    })
+  })
+
+  /**
+   * Sign in the way AuthService does after a successful login
+   */
+  async function signIn(user: User, userAgent: string) {
+    const { tokens, refreshTokenData } = await tokenService.generateTokenPair(user)
+    await refreshTokenRepository.create({
+      id: refreshTokenData.tokenId,
+      userId: user.id,
+      tokenHash: refreshTokenData.tokenHash,
+      expiresAt: refreshTokenData.expiresAt,
+      deviceInfo: { userAgent, ip: '192.0.2.1' },
+    })
+    return { tokens, sessionId: refreshTokenData.tokenId }
+  }
+
+  describe('AuthService', () => {
+    it('includes the session ID in access tokens', async () => {
+      const { tokens, sessionId } = await signIn(alice, 'Firefox')
+
+      const payload = await authService.verifyAccessToken(tokens.accessToken)
+      expect(payload.sid).toBe(sessionId)
+
+      const refreshed = await authService.refreshAccessToken(tokens.refreshToken)
+      const refreshedPayload = await authService.verifyAccessToken(refreshed.tokens!.accessToken)
+      expect(refreshedPayload.sid).toBe(sessionId)
+      expect(refreshTokenRepository.rows.get(sessionId)!.lastUsedAt).toBeInstanceOf(Date)
+    })
+
+    it('lists active sessions with the current one flagged', async () => {
+      const laptop = await signIn(alice, 'Firefox')
+      const phone = await signIn(alice, 'Safari')
+      await signIn(admin, 'Chrome')
+      refreshTokenRepository.rows.get(phone.sessionId)!.lastUsedAt = new Date(Date.now() + 1000)
+
+      const sessions = await authService.listSessions(alice.id, laptop.sessionId)
+
+      expect(sessions.map((s) => s.id)).toEqual([phone.sessionId, laptop.sessionId])
+      expect(sessions.map((s) => s.current)).toEqual([false, true])
+      expect(sessions[1]!.deviceInfo).toEqual({ userAgent: 'Firefox', ip: '192.0.2.1' })
+    })
+
+    it('only revokes sessions of the given user', async () => {
+      const { tokens, sessionId } = await signIn(alice, 'Firefox')
+
+      expect(await authService.revokeSession(admin.id, sessionId)).toBe(false)
+      expect(await authService.revokeSession(alice.id, sessionId)).toBe(true)
+      expect(await authService.revokeSession(alice.id, sessionId)).toBe(false)
+
+      expect(await authService.listSessions(alice.id)).toEqual([])
+      const refreshed = await authService.refreshAccessToken(tokens.refreshToken)
+      expect(refreshed.success).toBe(false)
+    })
+
+    it('rejects access tokens of revoked sessions', async () => {
+      const { tokens, sessionId } = await signIn(alice, 'Firefox')
+      await authService.verifyAccessToken(tokens.accessToken)
+
+      await authService.revokeSession(alice.id, sessionId)
+
+      await expect(authService.verifyAccessToken(tokens.accessToken)).rejects.toThrow(
+        'Session revoked'
+      )
+    })
+  })
+
+  describe('routes', () => {
+    function createServer() {
+      return createTestServer({ authService, requireAuth: true }, [
+        createTestAuthRoutes(authService),
+      ])
+    }
+
+    it('lists and revokes sessions of the current user', async () => {
+      const fastify = await createServer()
+      const laptop = await signIn(alice, 'Firefox')
+      const phone = await signIn(alice, 'Safari')
+      const headers = { authorization: `Bearer ${laptop.tokens.accessToken}` }
+
+      const list = await fastify.inject({ method: 'GET', url: '/auth/sessions', headers })
+      expect(list.statusCode).toBe(200)
+      const current = list.json().find((s: { current: boolean }) => s.current)
+      expect(current).toMatchObject({ id: laptop.sessionId, userAgent: 'Firefox' })
+
+      const revoke = await fastify.inject({
+        method: 'DELETE',
+        url: `/auth/sessions/${phone.sessionId}`,
+        headers,
+      })
+      expect(revoke.json()).toEqual({ success: true })
+
+      const signedOut = await fastify.inject({
+        method: 'GET',
+        url: '/auth/sessions',
+        headers: { authorization: `Bearer ${phone.tokens.accessToken}` },
+      })
+      expect(signedOut.statusCode).toBe(401)
+
+      const missing = await fastify.inject({
+        method: 'DELETE',
+        url: `/auth/sessions/${phone.sessionId}`,
+        headers,
+      })
+      expect(missing.statusCode).toBe(404)
+    })
+
+    it('only lets admins manage sessions of other users', async () => {
+      const fastify = await createServer()
+      const { sessionId } = await signIn(alice, 'Firefox')
+      const adminSession = await signIn(admin, 'Chrome')
+      const aliceSession = await signIn(alice, 'Safari')
+      const url = `/auth/users/${alice.id}/sessions`
+
+      const forbidden = await fastify.inject({
+        method: 'GET',
+        url,
+        headers: { authorization: `Bearer ${aliceSession.tokens.accessToken}` },
+      })
+      expect(forbidden.statusCode).toBe(403)
+
+      const headers = { authorization: `Bearer ${adminSession.tokens.accessToken}` }
+      const list = await fastify.inject({ method: 'GET', url, headers })
+      expect(list.json()).toHaveLength(2)
+
+      const revoke = await fastify.inject({ method: 'DELETE', url: `${url}/${sessionId}`, headers })
+      expect(revoke.json()).toEqual({ success: true })
+      expect(refreshTokenRepository.rows.get(sessionId)!.revokedAt).toBeInstanceOf(Date)
+    })
+  })
+})
@@ -0,0 +1,29 @@
+import type { PersonalAccessTokenScope } from '@stina/shared'
+
+/**
+ * Route prefixes personal access tokens can be used for, and the resource
+ * their scope covers. Other routes (such as /auth) only accept sessions.
+ */
+const SCOPED_ROUTE_PREFIXES: Array<{ prefix: string; resource: 'chat' | 'jobs' | 'settings' }> = [
+  { prefix: '/chat', resource: 'chat' },
+  { prefix: '/scheduled-jobs', resource: 'jobs' },
+  { prefix: '/settings', resource: 'settings' },
+]
+
+/**
+ * Get the scope a personal access token needs for a request.
+ * Reading (GET and HEAD) needs the read scope, everything else the write scope.
+ * @returns The scope, or null if personal access tokens cannot be used for the route
+ */
+export function getPersonalAccessTokenScope(
+  method: string,
+  path: string
+): PersonalAccessTokenScope | null {
+  const route = SCOPED_ROUTE_PREFIXES.find(
+    ({ prefix }) => path === prefix || path.startsWith(`${prefix}/`)
+  )
+  if (!route) return null
+
+  const access = method === 'GET' || method === 'HEAD' ? 'read' : 'write'
+  return `${route.resource}:${access}`
+}
@@ -0,0 +1,100 @@
+import type { FastifyPluginAsync } from 'fastify'
+import { requireAdmin } from '@stina/auth'
+import type { AuditEvent, AuditEventFilter, AuditLogService } from '@stina/auth'
+import { isAuditAction } from '@stina/shared'
+import type { AuditEventDTO, AuditEventListDTO } from '@stina/shared'
+
+/**
+ * Convert an audit event to a DTO
+ */
+function toAuditEventDTO(event: AuditEvent): AuditEventDTO {
+  return {
+    id: event.id,
+    createdAt: event.createdAt.toISOString(),
+    actorUserId: event.actorUserId,
+    actorUsername: event.actorUsername,
+    action: event.action,
+    targetType: event.targetType,
+    targetId: event.targetId,
+    details: event.details,
+    ip: event.ip,
+  }
+}
+
+/**
+ * Parse an optional date query parameter
+ * @returns The date, undefined if not given, or null if it is not a valid date
+ */
+function parseDate(value: string | undefined): Date | undefined | null {
+  if (!value) return undefined
+  const date = new Date(value)
+  return Number.isNaN(date.getTime()) ? null : date
+}
+
+/**
+ * Parse an optional integer query parameter
+ * @returns The number, undefined if not given, or NaN if it is not an integer
+ */
+function parseInteger(value: string | undefined): number | undefined {
+  if (!value) return undefined
+  return /^\d+$/.test(value) ? parseInt(value, 10) : NaN
+}
+
+/**
+ * Audit log routes for admins
+ * @param auditLogService The audit log to read
+ */
+export function createAuditRoutes(auditLogService: AuditLogService): FastifyPluginAsync {
+  return async (fastify) => {
+    /**
+     * List audit events, newest first (admin only)
+     * GET /admin/audit?action=&userId=&targetType=&targetId=&from=&to=&limit=50&offset=0
+     */
+    fastify.get<{
+      Querystring: {
+        action?: string
+        userId?: string
+        targetType?: string
+        targetId?: string
+        from?: string
+        to?: string
+        limit?: string
+        offset?: string
+      }
+      Reply: AuditEventListDTO | { error: string }
+    }>('/admin/audit', { preHandler: requireAdmin }, async (request, reply) => {
+      const { action, userId, targetType, targetId } = request.query
+
+      if (action !== undefined && !isAuditAction(action)) {
+        reply.code(400)
+        return { error: `Unknown action: ${action}` }
+      }
+
+      const from = parseDate(request.query.from)
+      const to = parseDate(request.query.to)
+      if (from === null || to === null) {
+        reply.code(400)
+        return { error: 'from and to must be ISO 8601 dates' }
+      }
+
+      const filter: AuditEventFilter = {
+        action,
+        actorUserId: userId || undefined,
+        targetType: targetType || undefined,
+        targetId: targetId || undefined,
+        from,
+        to,
+        limit: parseInteger(request.query.limit),
+        offset: parseInteger(request.query.offset),
+      }
+
+      try {
+        const { events, total } = await auditLogService.list(filter)
+        return { events: events.map(toAuditEventDTO), total }
+      } catch (error) {
+        reply.code(400)
+        return { error: error instanceof Error ? error.message : 'Failed to list audit events' }
+      }
+    })
+  }
+}
@@ -1,4 +1,7 @@
 import type { FastifyRequest } from 'fastify'
+import type { UserCapability } from '@stina/shared'
+import { DEFAULT_ROLE_CAPABILITIES } from '@stina/auth'
+import type { AuditActor, RecordAuditEventInput } from '@stina/auth'
 
 /**
  * Extract the authenticated user's ID from the request.
@@ -12,3 +15,59 @@ export function getUserId(request: FastifyRequest): string {
   }
   return request.user.id
 }
+
+/**
+ * Check whether the authenticated user has a capability.
+ * Admins have every capability; without a policy service, the built-in role capabilities apply.
+ */
+export async function hasCapability(
+  request: FastifyRequest,
+  capability: UserCapability
+): Promise<boolean> {
+  if (!request.user) return false
+  if (request.user.role === 'admin') return true
+
+  const policyService = request.server.policyService
+  if (!policyService) {
+    return DEFAULT_ROLE_CAPABILITIES[request.user.role].includes(capability)
+  }
+  return policyService.can(request.user.id, capability)
+}
+
+/**
+ * Check whether the authenticated user may use a model config
+ */
+export async function canUseModelConfig(
+  request: FastifyRequest,
+  modelConfigId: string
+): Promise<boolean> {
+  if (!request.user) return false
+  if (request.user.role === 'admin') return true
+
+  const policyService = request.server.policyService
+  return policyService ? policyService.canUseModelConfig(request.user.id, modelConfigId) : true
+}
+
+/**
+ * The user making the request and where from, for the audit log
+ */
+export function getAuditActor(request: FastifyRequest): AuditActor {
+  return { userId: getUserId(request), ip: request.ip }
+}
+
+/**
+ * Record an action of the authenticated user in the audit log, if the server keeps one
+ */
+export async function recordAudit(
+  request: FastifyRequest,
+  input: Omit<RecordAuditEventInput, 'actorUserId' | 'ip'>
+): Promise<void> {
+  const auditLogService = request.server.auditLogService
+  if (!auditLogService) return
+
+  await auditLogService.record({
+    ...input,
+    actorUserId: request.user?.id ?? null,
+    ip: request.ip,
+  })
+}
@@ -1,11 +1,127 @@
-import type { FastifyPluginAsync } from 'fastify'
-import { requireAuth, requireAdmin } from '@stina/auth'
-import type { AuthService, User } from '@stina/auth'
+import type { FastifyPluginAsync, FastifyReply, FastifyRequest } from 'fastify'
+import { AUTH_CONFIG, requireAuth, requireAdmin } from '@stina/auth'
+import type {
+  AuthService,
+  DeviceInfo,
+  OidcConfig,
+  OidcConfigInput,
+  OidcStatus,
+  PersonalAccessTokenData,
+  PersonalAccessTokenService,
+  PolicyService,
+  Session,
+  User,
+  UserRole,
+} from '@stina/auth'
+import type {
+  CapabilityPolicyDTO,
+  CreatedPersonalAccessTokenDTO,
+  EffectivePolicyDTO,
+  PersonalAccessTokenDTO,
+  PersonalAccessTokenScope,
+  SessionDTO,
+} from '@stina/shared'
+import { getAuditActor, recordAudit } from './auth-helpers.js'
+
+/**
+ * Convert personal access token data to a DTO.
+ * The token hash and owner are left out.
+ */
+function toPersonalAccessTokenDTO(data: PersonalAccessTokenData): PersonalAccessTokenDTO {
+  return {
+    id: data.id,
+    name: data.name,
+    tokenPrefix: data.tokenPrefix,
+    scopes: data.scopes,
+    createdAt: data.createdAt.toISOString(),
+    expiresAt: data.expiresAt?.toISOString() ?? null,
+    lastUsedAt: data.lastUsedAt?.toISOString() ?? null,
+  }
+}
+
+/**
+ * Convert a session to a DTO
+ */
+function toSessionDTO(session: Session): SessionDTO {
+  return {
+    id: session.id,
+    userAgent: session.deviceInfo?.userAgent ?? null,
+    ip: session.deviceInfo?.ip ?? null,
+    createdAt: session.createdAt.toISOString(),
+    lastUsedAt: session.lastUsedAt?.toISOString() ?? null,
+    expiresAt: session.expiresAt.toISOString(),
+    current: session.current,
+  }
+}
+
+/**
+ * Device information for a new session.
+ * The request's user agent and IP are used unless the client sent its own.
+ */
+function getDeviceInfo(request: FastifyRequest, deviceInfo?: DeviceInfo): DeviceInfo {
+  return {
+    userAgent: deviceInfo?.userAgent ?? request.headers['user-agent'],
+    ip: deviceInfo?.ip ?? request.ip,
+  }
+}
+
+/**
+ * Cookie binding a single sign-on login to the browser that started it
+ */
+const OIDC_BINDING_COOKIE = 'stina_oidc_binding'
+
+/**
+ * Set or clear the single sign-on binding cookie.
+ * It is HttpOnly so scripts cannot read it, and SameSite=Lax so it comes back with the callback.
+ */
+function setOidcBindingCookie(
+  request: FastifyRequest,
+  reply: FastifyReply,
+  value: string | null
+): void {
+  const maxAge = value ? Math.floor(AUTH_CONFIG.OIDC_LOGIN_TTL_MS / 1000) : 0
+  const attributes = [`Max-Age=${maxAge}`, 'Path=/', 'HttpOnly', 'SameSite=Lax']
+  if (request.protocol === 'https') attributes.push('Secure')
+  reply.header('set-cookie', `${OIDC_BINDING_COOKIE}=${value ?? ''}; ${attributes.join('; ')}`)
+}
+
+/**
+ * Read the single sign-on binding cookie sent with the request
+ */
+function getOidcBindingCookie(request: FastifyRequest): string | undefined {
+  for (const part of (request.headers.cookie ?? '').split(';')) {
+    const [name, ...value] = part.trim().split('=')
+    if (name === OIDC_BINDING_COOKIE) return value.join('=') || undefined
+  }
+  return undefined
+}
+
+/**
+ * Whether a route parameter is a known role
+ */
+function isUserRole(value: string): value is UserRole {
+  return value === 'admin' || value === 'user'
+}
+
+/**
+ * OpenID Connect settings as shown to admins. The client secret is never sent back.
+ */
+type OidcConfigView = Omit<OidcConfig, 'clientSecret'> & { hasClientSecret: boolean }
+
+function toOidcConfigView(config: OidcConfig | null): OidcConfigView | null {
+  if (!config) return null
+  const { clientSecret, ...rest } = config
+  return { ...rest, hasClientSecret: !!clientSecret }
+}
 
 /**
  * Auth routes factory
  */
-export function createAuthRoutes(authService: AuthService): FastifyPluginAsync {
+export function createAuthRoutes(
+  authService: AuthService,
+  personalAccessTokenService: PersonalAccessTokenService,
+  policyService: PolicyService
+): FastifyPluginAsync {
   return async (fastify) => {
     // ========================================
     // Setup
@@ -80,6 +196,8 @@ export function createAuthRoutes(authService: AuthService): FastifyPluginAsync {
         username: request.body.username,
         credential: request.body.credential,
         invitationToken: request.body.invitationToken,
+        deviceInfo: getDeviceInfo(request),
+        ip: request.ip,
       })
 
       if (!result.success || !result.user || !result.tokens) {
@@ -117,7 +235,8 @@ export function createAuthRoutes(authService: AuthService): FastifyPluginAsync {
     }>('/auth/login/verify', async (request, reply) => {
       const result = await authService.verifyAuthentication({
         credential: request.body.credential,
-        deviceInfo: request.body.deviceInfo,
+        deviceInfo: getDeviceInfo(request, request.body.deviceInfo),
+        ip: request.ip,
       })
 
       if (!result.success || !result.user || !result.tokens) {
@@ -128,6 +247,134 @@ export function createAuthRoutes(authService: AuthService): FastifyPluginAsync {
       return { user: result.user, tokens: result.tokens }
     })
 
+    // ========================================
+    // Single Sign-On (OpenID Connect)
+    // ========================================
+
+    /**
+     * Check whether single sign-on is available (public)
+     * GET /auth/oidc/status
+     */
+    fastify.get<{
+      Reply: OidcStatus
+    }>('/auth/oidc/status', async () => {
+      return authService.getOidcStatus()
+    })
+
+    /**
+     * Start single sign-on login
+     * POST /auth/oidc/authorize
+     */
+    fastify.post<{
+      Reply: { authorizationUrl: string } | { error: string }
+    }>('/auth/oidc/authorize', async (request, reply) => {
+      try {
+        const { authorizationUrl, browserBinding } = await authService.startOidcLogin()
+        setOidcBindingCookie(request, reply, browserBinding)
+        return { authorizationUrl }
+      } catch (error) {
+        reply.code(400)
+        return { error: error instanceof Error ? error.message : 'Failed to start single sign-on' }
+      }
+    })
+
+    /**
+     * Complete single sign-on with the code and state from the provider redirect
+     * POST /auth/oidc/callback
+     */
+    fastify.post<{
+      Body: { code: string; state: string; deviceInfo?: { userAgent?: string; ip?: string } }
+      Reply: { user: User; tokens: { accessToken: string; refreshToken: string } } | { error: string }
+    }>('/auth/oidc/callback', async (request, reply) => {
+      const result = await authService.completeOidcLogin({
+        code: request.body.code,
+        state: request.body.state,
+        browserBinding: getOidcBindingCookie(request),
+        deviceInfo: getDeviceInfo(request, request.body.deviceInfo),
+        ip: request.ip,
+      })
+      setOidcBindingCookie(request, reply, null)
+
+      if (!result.success || !result.user || !result.tokens) {
+        reply.code(401)
+        return { error: result.error ?? 'Single sign-on failed' }
+      }
+
+      return { user: result.user, tokens: result.tokens }
+    })
+
+    /**
+     * Start linking the current user to an identity at the provider
+     * POST /auth/oidc/link
+     */
+    fastify.post<{
+      Reply: { authorizationUrl: string } | { error: string }
+    }>('/auth/oidc/link', { preHandler: requireAuth }, async (request, reply) => {
+      try {
+        const { authorizationUrl, browserBinding } = await authService.startOidcLogin({
+          linkUserId: request.user!.id,
+        })
+        setOidcBindingCookie(request, reply, browserBinding)
+        return { authorizationUrl }
+      } catch (error) {
+        reply.code(400)
+        return { error: error instanceof Error ? error.message : 'Failed to start single sign-on' }
+      }
+    })
+
+    /**
+     * Unlink the current user from their identity at the provider
+     * DELETE /auth/oidc/link
+     */
+    fastify.delete<{
+      Reply: { success: boolean } | { error: string }
+    }>('/auth/oidc/link', { preHandler: requireAuth }, async (request, reply) => {
+      try {
+        await authService.unlinkOidc(request.user!.id)
+        return { success: true }
+      } catch (error) {
+        reply.code(400)
+        return { error: error instanceof Error ? error.message : 'Failed to unlink single sign-on' }
+      }
+    })
+
+    /**
+     * Get single sign-on settings (admin only)
+     * GET /auth/oidc/config
+     */
+    fastify.get<{
+      Reply: OidcConfigView | null
+    }>('/auth/oidc/config', { preHandler: requireAdmin }, async () => {
+      return toOidcConfigView(await authService.getOidcConfig())
+    })
+
+    /**
+     * Save single sign-on settings (admin only)
+     * PUT /auth/oidc/config
+     */
+    fastify.put<{
+      Body: OidcConfigInput
+      Reply: OidcConfigView | null | { error: string }
+    }>('/auth/oidc/config', { preHandler: requireAdmin }, async (request, reply) => {
+      try {
+        return toOidcConfigView(await authService.updateOidcConfig(request.body))
+      } catch (error) {
+        reply.code(400)
+        return { error: error instanceof Error ? error.message : 'Failed to save settings' }
+      }
+    })
+
+    /**
+     * Disable single sign-on (admin only)
+     * DELETE /auth/oidc/config
+     */
+    fastify.delete<{
+      Reply: { success: boolean }
+    }>('/auth/oidc/config', { preHandler: requireAdmin }, async () => {
+      await authService.disableOidc()
+      return { success: true }
+    })
+
     // ========================================
     // Token Management
     // ========================================
@@ -162,6 +409,37 @@ export function createAuthRoutes(authService: AuthService): FastifyPluginAsync {
       return { success: true }
     })
 
+    // ========================================
+    // Sessions
+    // ========================================
+
+    /**
+     * List the current user's active sessions
+     * GET /auth/sessions
+     */
+    fastify.get<{
+      Reply: SessionDTO[]
+    }>('/auth/sessions', { preHandler: requireAuth }, async (request) => {
+      const sessions = await authService.listSessions(request.user!.id, request.sessionId)
+      return sessions.map(toSessionDTO)
+    })
+
+    /**
+     * Sign out one of the current user's sessions
+     * DELETE /auth/sessions/:id
+     */
+    fastify.delete<{
+      Params: { id: string }
+      Reply: { success: boolean } | { error: string }
+    }>('/auth/sessions/:id', { preHandler: requireAuth }, async (request, reply) => {
+      const revoked = await authService.revokeSession(request.user!.id, request.params.id)
+      if (!revoked) {
+        reply.code(404)
+        return { error: 'Session not found' }
+      }
+      return { success: true }
+    })
+
     // ========================================
     // User Profile
     // ========================================
@@ -181,6 +459,73 @@ export function createAuthRoutes(authService: AuthService): FastifyPluginAsync {
       return user
     })
 
+    /**
+     * Get what the current user may do
+     * GET /auth/me/policy
+     */
+    fastify.get<{
+      Reply: EffectivePolicyDTO
+    }>('/auth/me/policy', { preHandler: requireAuth }, async (request) => {
+      return policyService.getEffectivePolicy(request.user!.id)
+    })
+
+    // ========================================
+    // Personal Access Tokens
+    // ========================================
+
+    /**
+     * List the current user's personal access tokens
+     * GET /auth/tokens
+     */
+    fastify.get<{
+      Reply: PersonalAccessTokenDTO[]
+    }>('/auth/tokens', { preHandler: requireAuth }, async (request) => {
+      const tokens = await personalAccessTokenService.list(request.user!.id)
+      return tokens.map(toPersonalAccessTokenDTO)
+    })
+
+    /**
+     * Create a personal access token. The token is only returned here.
+     * POST /auth/tokens
+     */
+    fastify.post<{
+      Body: { name: string; scopes: PersonalAccessTokenScope[]; expiresInDays?: number }
+      Reply: CreatedPersonalAccessTokenDTO | { error: string }
+    }>('/auth/tokens', { preHandler: requireAuth }, async (request, reply) => {
+      try {
+        const created = await personalAccessTokenService.create(request.user!.id, {
+          name: request.body.name,
+          scopes: request.body.scopes,
+          expiresInDays: request.body.expiresInDays,
+        })
+        return {
+          token: created.token,
+          personalAccessToken: toPersonalAccessTokenDTO(created.data),
+        }
+      } catch (error) {
+        reply.code(400)
+        return {
+          error: error instanceof Error ? error.message : 'Failed to create personal access token',
+        }
+      }
+    })
+
+    /**
+     * Revoke one of the current user's personal access tokens
+     * DELETE /auth/tokens/:id
+     */
+    fastify.delete<{
+      Params: { id: string }
+      Reply: { success: boolean } | { error: string }
+    }>('/auth/tokens/:id', { preHandler: requireAuth }, async (request, reply) => {
+      const revoked = await personalAccessTokenService.revoke(request.user!.id, request.params.id)
+      if (!revoked) {
+        reply.code(404)
+        return { error: 'Personal access token not found' }
+      }
+      return { success: true }
+    })
+
     // ========================================
     // Admin: User Management
     // ========================================
@@ -204,7 +549,11 @@ export function createAuthRoutes(authService: AuthService): FastifyPluginAsync {
       Body: { role: 'admin' | 'user' }
       Reply: User | { error: string }
     }>('/auth/users/:id/role', { preHandler: requireAdmin }, async (request, reply) => {
-      const user = await authService.updateUserRole(request.params.id, request.body.role)
+      const user = await authService.updateUserRole(
+        request.params.id,
+        request.body.role,
+        getAuditActor(request)
+      )
       if (!user) {
         reply.code(404)
         return { error: 'User not found' }
@@ -226,7 +575,152 @@ export function createAuthRoutes(authService: AuthService): FastifyPluginAsync {
         return { error: 'Cannot delete yourself' }
       }
 
-      await authService.deleteUser(request.params.id)
+      await authService.deleteUser(request.params.id, getAuditActor(request))
+      return { success: true }
+    })
+
+    /**
+     * List a user's active sessions (admin only)
+     * GET /auth/users/:id/sessions
+     */
+    fastify.get<{
+      Params: { id: string }
+      Reply: SessionDTO[]
+    }>('/auth/users/:id/sessions', { preHandler: requireAdmin }, async (request) => {
+      const sessions = await authService.listSessions(request.params.id, request.sessionId)
+      return sessions.map(toSessionDTO)
+    })
+
+    /**
+     * Sign a user out of one of their sessions (admin only)
+     * DELETE /auth/users/:id/sessions/:sessionId
+     */
+    fastify.delete<{
+      Params: { id: string; sessionId: string }
+      Reply: { success: boolean } | { error: string }
+    }>(
+      '/auth/users/:id/sessions/:sessionId',
+      { preHandler: requireAdmin },
+      async (request, reply) => {
+        const { id, sessionId } = request.params
+        const revoked = await authService.revokeSession(id, sessionId)
+        if (!revoked) {
+          reply.code(404)
+          return { error: 'Session not found' }
+        }
+        return { success: true }
+      }
+    )
+
+    // ========================================
+    // Admin: Capability Policies
+    // ========================================
+
+    /**
+     * Get the policy of a role (admin only)
+     * GET /auth/policies/roles/:role
+     */
+    fastify.get<{
+      Params: { role: UserRole }
+      Reply: CapabilityPolicyDTO | { error: string }
+    }>('/auth/policies/roles/:role', { preHandler: requireAdmin }, async (request, reply) => {
+      if (!isUserRole(request.params.role)) {
+        reply.code(404)
+        return { error: 'Role not found' }
+      }
+      return policyService.getRolePolicy(request.params.role)
+    })
+
+    /**
+     * Replace the policy of a role (admin only)
+     * PUT /auth/policies/roles/:role
+     */
+    fastify.put<{
+      Params: { role: UserRole }
+      Body: CapabilityPolicyDTO
+      Reply: CapabilityPolicyDTO | { error: string }
+    }>('/auth/policies/roles/:role', { preHandler: requireAdmin }, async (request, reply) => {
+      if (!isUserRole(request.params.role)) {
+        reply.code(404)
+        return { error: 'Role not found' }
+      }
+      let policy: CapabilityPolicyDTO
+      try {
+        policy = await policyService.setRolePolicy(request.params.role, request.body)
+      } catch (error) {
+        reply.code(400)
+        return { error: error instanceof Error ? error.message : 'Failed to update policy' }
+      }
+      await recordAudit(request, {
+        action: 'policy.role_updated',
+        targetType: 'role',
+        targetId: request.params.role,
+        details: { ...policy },
+      })
+      return policy
+    })
+
+    /**
+     * Get the policy of a single user (admin only)
+     * GET /auth/users/:id/policy
+     */
+    fastify.get<{
+      Params: { id: string }
+      Reply: { policy: CapabilityPolicyDTO; effective: EffectivePolicyDTO } | { error: string }
+    }>('/auth/users/:id/policy', { preHandler: requireAdmin }, async (request, reply) => {
+      const user = await authService.getUserById(request.params.id)
+      if (!user) {
+        reply.code(404)
+        return { error: 'User not found' }
+      }
+      return {
+        policy: await policyService.getUserPolicy(user.id),
+        effective: await policyService.getEffectivePolicy(user.id),
+      }
+    })
+
+    /**
+     * Replace the policy of a single user (admin only)
+     * PUT /auth/users/:id/policy
+     */
+    fastify.put<{
+      Params: { id: string }
+      Body: CapabilityPolicyDTO
+      Reply: CapabilityPolicyDTO | { error: string }
+    }>('/auth/users/:id/policy', { preHandler: requireAdmin }, async (request, reply) => {
+      let policy: CapabilityPolicyDTO
+      try {
+        policy = await policyService.setUserPolicy(request.params.id, request.body)
+      } catch (error) {
+        const message = error instanceof Error ? error.message : 'Failed to update policy'
+        reply.code(message === 'User not found' ? 404 : 400)
+        return { error: message }
+      }
+      await recordAudit(request, {
+        action: 'policy.user_updated',
+        targetType: 'user',
+        targetId: request.params.id,
+        details: { ...policy },
+      })
+      return policy
+    })
+
+    /**
+     * Remove the policy of a single user, so the role's policy applies (admin only)
+     * DELETE /auth/users/:id/policy
+     */
+    fastify.delete<{
+      Params: { id: string }
+      Reply: { success: boolean }
+    }>('/auth/users/:id/policy', { preHandler: requireAdmin }, async (request) => {
+      const removed = await policyService.deleteUserPolicy(request.params.id)
+      if (removed) {
+        await recordAudit(request, {
+          action: 'policy.user_removed',
+          targetType: 'user',
+          targetId: request.params.id,
+        })
+      }
       return { success: true }
     })
 
@@ -243,10 +737,11 @@ export function createAuthRoutes(authService: AuthService): FastifyPluginAsync {
       Reply: { token: string; expiresAt: Date } | { error: string }
     }>('/auth/users/invite', { preHandler: requireAdmin }, async (request, reply) => {
       try {
-        const invitation = await authService.createInvitation(request.user!.id, {
-          username: request.body.username,
-          role: request.body.role,
-        })
+        const invitation = await authService.createInvitation(
+          request.user!.id,
+          { username: request.body.username, role: request.body.role },
+          request.ip
+        )
         return { token: invitation.token, expiresAt: invitation.expiresAt }
       } catch (error) {
         reply.code(400)
@@ -294,7 +789,7 @@ export function createAuthRoutes(authService: AuthService): FastifyPluginAsync {
       Params: { id: string }
       Reply: { success: boolean }
     }>('/auth/invitations/:id', { preHandler: requireAdmin }, async (request) => {
-      await authService.deleteInvitation(request.params.id)
+      await authService.deleteInvitation(request.params.id, getAuditActor(request))
       return { success: true }
     })
   }
@@ -1,5 +1,5 @@
 import type { FastifyPluginAsync } from 'fastify'
-import { ConversationRepository } from '@stina/chat/db'
+import { ConversationRepository, UserSettingsRepository } from '@stina/chat/db'
 import {
   interactionToDTO,
   conversationToDTO,
@@ -10,13 +10,23 @@ import type {
   ChatConversationSummaryDTO,
   ChatConversationDTO,
   ChatInteractionDTO,
+  ChatSearchHitDTO,
 } from '@stina/shared'
 import type { Conversation } from '@stina/chat'
+import {
+  exportConversation,
+  importConversation,
+  isConversationExportFormat,
+  parseConversationExport,
+} from '@stina/chat'
 import { getDatabase } from '@stina/adapters-node'
 import { asChatDb } from '../asChatDb.js'
 import { getUserId } from './auth-helpers.js'
 import { requireAuth } from '@stina/auth'
 
+/** Maximum size of an imported conversation export (50 MB) */
+const IMPORT_BODY_LIMIT = 50 * 1024 * 1024
+
 export const chatRoutes: FastifyPluginAsync = async (fastify) => {
   const db = asChatDb(getDatabase())
 
@@ -37,6 +47,30 @@ export const chatRoutes: FastifyPluginAsync = async (fastify) => {
     return conversations.map(conversationToSummaryDTO)
   })
 
+  /**
+   * Search messages across the user's conversations
+   * GET /chat/search?query=weather&limit=20&offset=0
+   */
+  fastify.get<{
+    Querystring: { query?: string; limit?: string; offset?: string }
+    Reply: ChatSearchHitDTO[] | { error: string }
+  }>('/chat/search', { preHandler: requireAuth }, async (request, reply) => {
+    const query = request.query.query?.trim()
+    if (!query) {
+      reply.code(400)
+      return { error: 'query is required' }
+    }
+
+    const limit = request.query.limit ? parseInt(request.query.limit, 10) : NaN
+    const offset = request.query.offset ? parseInt(request.query.offset, 10) : NaN
+
+    const repository = getRepository(getUserId(request))
+    return repository.searchMessages(query, {
+      limit: Number.isNaN(limit) ? undefined : limit,
+      offset: Number.isNaN(offset) ? undefined : offset,
+    })
+  })
+
   /**
    * Get latest active conversation (without full interactions)
    * GET /chat/conversations/latest
@@ -184,4 +218,69 @@ export const chatRoutes: FastifyPluginAsync = async (fastify) => {
     await repository.saveInteraction(interaction)
     return { success: true }
   })
+
+  /**
+   * Export a conversation as Markdown, JSON or HTML
+   * GET /chat/conversations/:id/export?format=markdown&timezone=Europe/Stockholm
+   */
+  fastify.get<{
+    Params: { id: string }
+    Querystring: { format?: string; timezone?: string }
+  }>('/chat/conversations/:id/export', { preHandler: requireAuth }, async (request, reply) => {
+    const userId = getUserId(request)
+    const format = request.query.format ?? 'markdown'
+    if (!isConversationExportFormat(format)) {
+      reply.code(400)
+      return { error: 'format must be markdown, json or html' }
+    }
+
+    const timeZone =
+      request.query.timezone ?? (await new UserSettingsRepository(db, userId).get()).timezone ?? 'UTC'
+    try {
+      new Intl.DateTimeFormat('en-CA', { timeZone })
+    } catch {
+      reply.code(400)
+      return { error: `Invalid timezone: ${timeZone}` }
+    }
+
+    const conversation = await getRepository(userId).getConversationWithBranches(request.params.id)
+    if (!conversation) {
+      reply.code(404)
+      return { error: 'Conversation not found' }
+    }
+
+    const file = exportConversation(conversation, format, { timeZone })
+    reply
+      .header('Content-Type', file.mimeType)
+      .header('Content-Disposition', `attachment; filename="${file.fileName}"`)
+    return file.content
+  })
+
+  /**
+   * Import a conversation from the JSON export format.
+   * The conversation gets new IDs and is owned by the authenticated user.
+   * POST /chat/conversations/import
+   */
+  fastify.post<{
+    Body: unknown
+    Reply: ChatConversationDTO | { error: string }
+  }>(
+    '/chat/conversations/import',
+    { preHandler: requireAuth, bodyLimit: IMPORT_BODY_LIMIT },
+    async (request, reply) => {
+      try {
+        parseConversationExport(request.body)
+      } catch (error) {
+        reply.code(400)
+        return { error: error instanceof Error ? error.message : String(error) }
+      }
+
+      const conversation = await importConversation(
+        getRepository(getUserId(request)),
+        request.body
+      )
+      reply.code(201)
+      return conversationToDTO(conversation)
+    }
+  )
 }
@@ -1,19 +1,22 @@
 import { randomUUID } from 'node:crypto'
-import type { FastifyPluginAsync } from 'fastify'
+import type { FastifyPluginAsync, FastifyRequest } from 'fastify'
 import { ChatOrchestrator } from '@stina/chat/orchestrator'
 import type { OrchestratorEvent, QueuedMessageRole } from '@stina/chat/orchestrator'
+import { ConversationRepository, UserSettingsRepository, AppSettingsStore } from '@stina/chat/db'
 import {
-  ConversationRepository,
-  ModelConfigRepository,
-  UserSettingsRepository,
-  AppSettingsStore,
-} from '@stina/chat/db'
-import { providerRegistry, toolRegistry } from '@stina/chat'
+  providerRegistry,
+  toolRegistry,
+  storeAttachments,
+  MAX_ATTACHMENTS_PER_MESSAGE,
+  MAX_ATTACHMENT_BYTES_PER_MESSAGE,
+} from '@stina/chat'
+import type { AttachmentUpload, MessageAttachment } from '@stina/chat'
 import { interactionToDTO, conversationToDTO } from '@stina/chat/mappers'
+import type { ChatConversationOverridesDTO } from '@stina/shared'
 import { getDatabase } from '@stina/adapters-node'
 import { asChatDb } from '../../asChatDb.js'
 import { requireAuth } from '@stina/auth'
-import { getUserId } from '../auth-helpers.js'
+import { getUserId, hasCapability, canUseModelConfig, recordAudit } from '../auth-helpers.js'
 import { APP_NAMESPACE } from '@stina/core'
 import { instructionRetryQueue } from '../instructionRetryQueue.js'
 
@@ -28,7 +31,9 @@ import {
   conversationEventBus,
   pendingConfirmationStore,
   getSessionManager,
+  createUserModelConfigProvider,
   createToolDisplayNameResolver,
+  attachmentStore,
 } from './sessionManager.js'
 
 // Re-export everything needed by external consumers
@@ -42,43 +47,82 @@ export {
 export {
   invalidateUserSessionManager,
   queueInstructionForUser,
+  createUserMemoryService,
+  setSessionPolicyService,
 } from './sessionManager.js'
 
+interface ChatStreamBody {
+  conversationId?: string
+  message: string
+  queueId?: string
+  role?: QueuedMessageRole
+  sessionId?: string
+  context?: 'conversation-start' | 'settings-update'
+  /** Answer this interaction's message again, as a new branch (message is ignored) */
+  regenerateInteractionId?: string
+  /** Replace this interaction's user message with `message`, as a new branch */
+  editInteractionId?: string
+}
+
+/**
+ * Read a multipart chat stream request: the same fields as the JSON body,
+ * plus the attached files.
+ * @throws Error as soon as there are more files, or more bytes, than one message may have
+ */
+async function readMultipartStreamRequest(
+  request: FastifyRequest
+): Promise<{ body: ChatStreamBody; uploads: AttachmentUpload[] }> {
+  const fields: Record<string, string> = {}
+  const uploads: AttachmentUpload[] = []
+  let totalBytes = 0
+
+  for await (const part of request.parts()) {
+    if (part.type === 'file') {
+      if (uploads.length === MAX_ATTACHMENTS_PER_MESSAGE) {
+        throw new Error(`A message can have at most ${MAX_ATTACHMENTS_PER_MESSAGE} attachments`)
+      }
+      const data = await part.toBuffer()
+      totalBytes += data.byteLength
+      if (totalBytes > MAX_ATTACHMENT_BYTES_PER_MESSAGE) {
+        throw new Error('The attachments of a message can be at most 50 MB in total')
+      }
+      uploads.push({ name: part.filename, mimeType: part.mimetype, data: new Uint8Array(data) })
+    } else if (typeof part.value === 'string') {
+      fields[part.fieldname] = part.value
+    }
+  }
+
+  return {
+    body: {
+      conversationId: fields['conversationId'] || undefined,
+      message: fields['message'] ?? '',
+      queueId: fields['queueId'] || undefined,
+      role: (fields['role'] || undefined) as QueuedMessageRole | undefined,
+      sessionId: fields['sessionId'] || undefined,
+      context: (fields['context'] || undefined) as ChatStreamBody['context'],
+      regenerateInteractionId: fields['regenerateInteractionId'] || undefined,
+      editInteractionId: fields['editInteractionId'] || undefined,
+    },
+    uploads,
+  }
+}
+
 /**
  * SSE streaming routes for chat
  */
 export const chatStreamRoutes: FastifyPluginAsync = async (fastify) => {
   const db = asChatDb(getDatabase())
 
-  const modelConfigRepo = new ModelConfigRepository(db)
-
   const getRepository = (userId: string) => new ConversationRepository(db, userId)
   const getUserSettingsRepository = (userId: string) => new UserSettingsRepository(db, userId)
 
-  const createModelConfigProvider = (userId: string) => ({
-    async getDefault() {
-      const userSettingsRepo = getUserSettingsRepository(userId)
-      const defaultModelId = await userSettingsRepo.getDefaultModelConfigId()
-      if (!defaultModelId) return null
-
-      const config = await modelConfigRepo.get(defaultModelId)
-      if (!config) return null
-
-      return {
-        providerId: config.providerId,
-        modelId: config.modelId,
-        settingsOverride: config.settingsOverride,
-      }
-    },
-  })
-
   const createGetToolDisplayName = createToolDisplayNameResolver
 
   const resolveSessionManager = async (userId: string) => {
     return getSessionManager(userId, {
       getRepository,
       getUserSettingsRepo: getUserSettingsRepository,
-      getModelConfigProvider: createModelConfigProvider,
+      getModelConfigProvider: createUserModelConfigProvider,
       getToolDisplayName: createGetToolDisplayName,
     })
   }
@@ -86,27 +130,67 @@ export const chatStreamRoutes: FastifyPluginAsync = async (fastify) => {
   /**
    * Stream chat response via SSE
    * POST /chat/stream
+   *
+   * Accepts a JSON body, or multipart form data with the same fields and
+   * attached files (images, PDFs, ...).
+   * With regenerateInteractionId or editInteractionId, the message is sent as a
+   * new branch next to that interaction instead of at the end of the conversation.
    */
   fastify.post<{
-    Body: {
-      conversationId?: string
-      message: string
-      queueId?: string
-      role?: QueuedMessageRole
-      sessionId?: string
-      context?: 'conversation-start' | 'settings-update'
-    }
+    Body: ChatStreamBody
   }>('/chat/stream', { preHandler: requireAuth }, async (request, reply) => {
-    const { conversationId, message, queueId: providedQueueId, role, sessionId, context } =
-      request.body
+    let body = request.body
+    let uploads: AttachmentUpload[] = []
+    if (request.isMultipart()) {
+      try {
+        const multipartRequest = await readMultipartStreamRequest(request)
+        body = multipartRequest.body
+        uploads = multipartRequest.uploads
+      } catch (err) {
+        reply.code((err as { statusCode?: number }).statusCode ?? 400)
+        return { error: err instanceof Error ? err.message : 'Invalid upload' }
+      }
+    }
+
+    const {
+      conversationId,
+      message,
+      queueId: providedQueueId,
+      role,
+      sessionId,
+      context,
+      regenerateInteractionId,
+      editInteractionId,
+    } = body
     const queueId = providedQueueId ?? randomUUID()
     const userId = getUserId(request)
 
-    if (typeof message !== 'string' || (!message.trim() && !context)) {
+    if (
+      !regenerateInteractionId &&
+      (typeof message !== 'string' || (!message.trim() && !context && uploads.length === 0))
+    ) {
       reply.code(400)
       return { error: 'Message is required' }
     }
 
+    if (uploads.length > 0 && (regenerateInteractionId || editInteractionId)) {
+      reply.code(400)
+      return { error: 'Attachments cannot be changed when editing or regenerating a message' }
+    }
+
+    if (uploads.length > 0 && role === 'instruction') {
+      reply.code(400)
+      return { error: 'Attachments can only be sent with user messages' }
+    }
+
+    let attachments: MessageAttachment[] = []
+    try {
+      attachments = await storeAttachments(attachmentStore, uploads)
+    } catch (err) {
+      reply.code(400)
+      return { error: err instanceof Error ? err.message : 'Failed to store attachments' }
+    }
+
     reply.hijack()
 
     const origin = request.headers.origin
@@ -195,7 +279,13 @@ export const chatStreamRoutes: FastifyPluginAsync = async (fastify) => {
         await orchestrator.loadConversation(conversationId)
       }
 
-      await orchestrator.enqueueMessage(message, role ?? 'user', queueId, context)
+      if (regenerateInteractionId) {
+        await orchestrator.regenerate(regenerateInteractionId, queueId)
+      } else if (editInteractionId) {
+        await orchestrator.editAndResend(editInteractionId, message, queueId)
+      } else {
+        await orchestrator.enqueueMessage(message, role ?? 'user', queueId, context, attachments)
+      }
     } catch (err) {
       if (!ended) {
         const errorMessage = err instanceof Error ? err.message : 'Unknown error'
@@ -220,7 +310,7 @@ export const chatStreamRoutes: FastifyPluginAsync = async (fastify) => {
     const offset = parseInt(request.query.offset || '0', 10)
     const userId = getUserId(request)
     const repository = getRepository(userId)
-    const modelConfigProvider = createModelConfigProvider(userId)
+    const modelConfigProvider = createUserModelConfigProvider(userId)
 
     const userSettingsRepo = getUserSettingsRepository(userId)
     const userSettings = await userSettingsRepo.get()
@@ -278,6 +368,41 @@ export const chatStreamRoutes: FastifyPluginAsync = async (fastify) => {
     return session.orchestrator.getQueueState()
   })
 
+  /**
+   * Show another branch of a conversation
+   * POST /chat/branch/switch
+   */
+  fastify.post<{
+    Body: { interactionId: string; conversationId: string; sessionId?: string }
+  }>('/chat/branch/switch', { preHandler: requireAuth }, async (request, reply) => {
+    const { interactionId, conversationId, sessionId } = request.body
+    const userId = getUserId(request)
+
+    if (!interactionId || !conversationId) {
+      reply.code(400)
+      return { error: 'interactionId and conversationId are required' }
+    }
+
+    const sessionManager = await resolveSessionManager(userId)
+    const session = sessionManager.getSession({ sessionId, conversationId })
+    const orchestrator = session.orchestrator
+
+    try {
+      if (orchestrator.conversation?.id !== conversationId) {
+        await orchestrator.loadConversation(conversationId)
+      }
+    } catch {
+      reply.code(404)
+      return { error: 'Conversation not found' }
+    }
+
+    const switched = await orchestrator.switchBranch(interactionId)
+    if (switched) {
+      emitChatEvent({ type: 'conversation-updated', userId, conversationId, sessionId })
+    }
+    return { success: switched }
+  })
+
   /**
    * Remove a queued message
    * POST /chat/queue/remove
@@ -388,6 +513,19 @@ export const chatStreamRoutes: FastifyPluginAsync = async (fastify) => {
       return { error: 'denialReason must be 1000 characters or less' }
     }
 
+    if (approved && !(await hasCapability(request, 'tools:approve'))) {
+      reply.code(403)
+      return { error: 'You are not allowed to approve tool calls' }
+    }
+
+    const recordResponse = () =>
+      recordAudit(request, {
+        action: approved ? 'tool.approved' : 'tool.denied',
+        targetType: 'tool',
+        targetId: toolCallName,
+        details: { conversationId: conversationId ?? null, denialReason: denialReason ?? null },
+      })
+
     const centralResolved = pendingConfirmationStore.resolve(
       toolCallName,
       { approved, denialReason },
@@ -395,6 +533,7 @@ export const chatStreamRoutes: FastifyPluginAsync = async (fastify) => {
     )
 
     if (centralResolved) {
+      await recordResponse()
       return { success: true }
     }
 
@@ -416,6 +555,7 @@ export const chatStreamRoutes: FastifyPluginAsync = async (fastify) => {
       return { error: 'No pending confirmation found for this tool' }
     }
 
+    await recordResponse()
     return { success: true }
   })
 
@@ -595,6 +735,78 @@ export const chatStreamRoutes: FastifyPluginAsync = async (fastify) => {
     reply.raw.on('close', cleanup)
   })
 
+  /**
+   * Revert a context summary so the summarized interactions are sent in full again
+   * POST /chat/conversation/:id/compactions/:interactionId/revert
+   */
+  fastify.post<{
+    Params: { id: string; interactionId: string }
+  }>(
+    '/chat/conversation/:id/compactions/:interactionId/revert',
+    { preHandler: requireAuth },
+    async (request, reply) => {
+      const { id: conversationId, interactionId } = request.params
+      const userId = getUserId(request)
+      const sessionManager = await resolveSessionManager(userId)
+      const session = sessionManager.getSession({ conversationId })
+      const orchestrator = session.orchestrator
+
+      try {
+        if (orchestrator.conversation?.id !== conversationId) {
+          await orchestrator.loadConversation(conversationId)
+        }
+      } catch {
+        reply.code(404)
+        return { error: 'Conversation not found' }
+      }
+
+      const reverted = await orchestrator.revertContextCompaction(interactionId)
+      if (!reverted) {
+        reply.code(404)
+        return { error: 'Context summary not found' }
+      }
+
+      return { success: true }
+    }
+  )
+
+  /**
+   * Replace the conversation's model and personality overrides
+   * PUT /chat/conversation/:id/overrides
+   */
+  fastify.put<{
+    Params: { id: string }
+    Body: ChatConversationOverridesDTO | null
+  }>('/chat/conversation/:id/overrides', { preHandler: requireAuth }, async (request, reply) => {
+    const { id: conversationId } = request.params
+    const userId = getUserId(request)
+    const sessionManager = await resolveSessionManager(userId)
+    const session = sessionManager.getSession({ conversationId })
+    const orchestrator = session.orchestrator
+
+    try {
+      if (orchestrator.conversation?.id !== conversationId) {
+        await orchestrator.loadConversation(conversationId)
+      }
+    } catch {
+      reply.code(404)
+      return { error: 'Conversation not found' }
+    }
+
+    const modelConfigId = request.body?.modelConfigId
+    if (modelConfigId && !(await canUseModelConfig(request, modelConfigId))) {
+      reply.code(403)
+      return { error: 'You are not allowed to use this model' }
+    }
+
+    try {
+      return await orchestrator.setConversationOverrides(request.body)
+    } catch (error) {
+      reply.code(400)
+      return { error: error instanceof Error ? error.message : 'Invalid overrides' }
+    }
+  })
+
   /**
    * Mark all interactions in a conversation as read
    * POST /chat/conversation/:id/mark-read
@@ -1,11 +1,14 @@
 import { randomUUID } from 'node:crypto'
 import { ChatOrchestrator } from '@stina/chat/orchestrator'
+import type { IModelConfigProvider } from '@stina/chat/orchestrator'
 import {
   ConversationRepository,
-  ModelConfigRepository,
   UserSettingsRepository,
   AppSettingsStore,
   ToolConfirmationRepository,
+  MemoryRepository,
+  FileAttachmentStore,
+  createModelConfigProvider,
 } from '@stina/chat/db'
 import type { ChatDb } from '@stina/chat/db'
 import {
@@ -14,11 +17,14 @@ import {
   ConversationEventBus,
   PendingConfirmationStore,
   ChatSessionManager,
+  MemoryService,
+  createProviderEmbedder,
 } from '@stina/chat'
-import { getDatabase } from '@stina/adapters-node'
+import { getDatabase, getAttachmentsPath } from '@stina/adapters-node'
 import { asChatDb } from '../../asChatDb.js'
 import { resolveLocalizedString } from '@stina/extension-api'
 import { APP_NAMESPACE } from '@stina/core'
+import type { PolicyService } from '@stina/auth'
 import { emitChatEvent } from './eventBroadcaster.js'
 
 /**
@@ -31,6 +37,11 @@ export const conversationEventBus = new ConversationEventBus()
  */
 export const pendingConfirmationStore = new PendingConfirmationStore()
 
+/**
+ * Storage for files attached to chat messages, shared by all users.
+ */
+export const attachmentStore = new FileAttachmentStore(getAttachmentsPath())
+
 /**
  * Simple async mutex implementation to prevent race conditions.
  * Ensures only one async operation can proceed at a time per key.
@@ -101,13 +112,15 @@ const getDb = (): ChatDb => {
   return _db
 }
 
-// Model config repo (singleton, global)
-let _modelConfigRepo: ModelConfigRepository | null = null
-const getModelConfigRepo = (): ModelConfigRepository => {
-  if (!_modelConfigRepo) {
-    _modelConfigRepo = new ModelConfigRepository(getDb())
-  }
-  return _modelConfigRepo
+// Capability policies deciding which model configs users may use, set by the server
+let _policyService: PolicyService | null = null
+
+/**
+ * Set the policy service that decides which model configs users may chat with.
+ * Without one, every user may use every model config.
+ */
+export function setSessionPolicyService(policyService: PolicyService | null): void {
+  _policyService = policyService
 }
 
 /**
@@ -116,22 +129,25 @@ const getModelConfigRepo = (): ModelConfigRepository => {
 const createUserRepository = (userId: string) => new ConversationRepository(getDb(), userId)
 const createUserSettingsRepository = (userId: string) => new UserSettingsRepository(getDb(), userId)
 
-const createUserModelConfigProvider = (userId: string) => ({
-  async getDefault() {
-    const userSettingsRepo = createUserSettingsRepository(userId)
-    const defaultModelId = await userSettingsRepo.getDefaultModelConfigId()
-    if (!defaultModelId) return null
-
-    const config = await getModelConfigRepo().get(defaultModelId)
-    if (!config) return null
+/**
+ * Create the model config provider for a user's chats,
+ * limited to the model configs their policy allows
+ */
+export const createUserModelConfigProvider = (userId: string): IModelConfigProvider =>
+  createModelConfigProvider(getDb(), userId, {
+    getAllowedModelConfigIds: async () =>
+      _policyService ? _policyService.getAllowedModelConfigIds(userId) : null,
+  })
 
-    return {
-      providerId: config.providerId,
-      modelId: config.modelId,
-      settingsOverride: config.settingsOverride,
-    }
-  },
-})
+/**
+ * Create the long-term memory service for a user.
+ * Embeddings use the provider of the user's default model.
+ */
+export const createUserMemoryService = (userId: string) =>
+  new MemoryService(
+    new MemoryRepository(getDb(), userId),
+    createProviderEmbedder(providerRegistry, createUserModelConfigProvider(userId))
+  )
 
 export const createToolDisplayNameResolver = (userLanguage: string) => {
   return (toolId: string): string | undefined => {
@@ -157,6 +173,8 @@ function createSessionManager(userId: string, deps: {
           providerRegistry,
           modelConfigProvider: deps.modelConfigProvider,
           toolRegistry,
+          memory: createUserMemoryService(userId),
+          attachmentStore,
           settingsStore: deps.settingsStore,
           getToolDisplayName: deps.getToolDisplayName,
           userLanguage: deps.userLanguage,
@@ -13,6 +13,8 @@ export {
   unregisterWriter,
   invalidateUserSessionManager,
   queueInstructionForUser,
+  createUserMemoryService,
+  setSessionPolicyService,
 } from './chat/index.js'
 
 export type {
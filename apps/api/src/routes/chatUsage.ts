@@ -0,0 +1,74 @@
+import type { FastifyPluginAsync, FastifyReply } from 'fastify'
+import { TokenUsageRepository, UserSettingsRepository } from '@stina/chat/db'
+import type { TokenUsageFilter } from '@stina/chat/db'
+import type { TokenUsageQueryDTO, TokenUsageReportDTO } from '@stina/shared'
+import { getDatabase } from '@stina/adapters-node'
+import { requireAdmin, requireAuth } from '@stina/auth'
+import { asChatDb } from '../asChatDb.js'
+import { getUserId } from './auth-helpers.js'
+
+/**
+ * Token usage routes for tracking spend on paid providers
+ */
+export const chatUsageRoutes: FastifyPluginAsync = async (fastify) => {
+  const db = asChatDb(getDatabase())
+  const usageRepo = new TokenUsageRepository(db)
+
+  /**
+   * Parse the query into a filter and timezone.
+   * Sends a 400 reply and returns null if the query is invalid.
+   */
+  const parseQuery = async (
+    query: TokenUsageQueryDTO,
+    userId: string,
+    reply: FastifyReply
+  ): Promise<{ filter: TokenUsageFilter; timezone: string } | null> => {
+    const from = query.from ? new Date(query.from) : undefined
+    const to = query.to ? new Date(query.to) : undefined
+    if ((from && isNaN(from.getTime())) || (to && isNaN(to.getTime()))) {
+      reply.status(400).send({ error: 'Invalid from/to date' })
+      return null
+    }
+
+    const timezone =
+      query.timezone ?? (await new UserSettingsRepository(db, userId).get()).timezone ?? 'UTC'
+    try {
+      new Intl.DateTimeFormat('en-CA', { timeZone: timezone })
+    } catch {
+      reply.status(400).send({ error: `Invalid timezone: ${timezone}` })
+      return null
+    }
+
+    return { filter: { from, to }, timezone }
+  }
+
+  /**
+   * Get token usage for the authenticated user
+   * GET /chat/usage
+   */
+  fastify.get<{
+    Querystring: TokenUsageQueryDTO
+    Reply: TokenUsageReportDTO | { error: string }
+  }>('/chat/usage', { preHandler: requireAuth }, async (request, reply) => {
+    const userId = getUserId(request)
+    const parsed = await parseQuery(request.query, userId, reply)
+    if (!parsed) return reply
+
+    return usageRepo.getReport({ ...parsed.filter, userId }, parsed.timezone)
+  })
+
+  /**
+   * Get token usage for all users (admin only)
+   * GET /chat/usage/all
+   */
+  fastify.get<{
+    Querystring: TokenUsageQueryDTO
+    Reply: TokenUsageReportDTO | { error: string }
+  }>('/chat/usage/all', { preHandler: requireAdmin }, async (request, reply) => {
+    const userId = getUserId(request)
+    const parsed = await parseQuery(request.query, userId, reply)
+    if (!parsed) return reply
+
+    return usageRepo.getReport(parsed.filter, parsed.timezone)
+  })
+}
@@ -71,6 +71,7 @@ export function createElectronAuthRoutes(
         const result = await authService.verifyAuthentication({
           credential,
           deviceInfo,
+          ip: request.ip,
         })
 
         if (!result.success || !result.user) {
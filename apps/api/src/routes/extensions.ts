@@ -4,14 +4,17 @@ import type { ExtensionSummary } from '@stina/shared'
 import { getExtensionInstaller, getExtensionHost, syncExtensions } from '../setup.js'
 import { getPanelViews } from '@stina/adapters-node'
 import type { RegistryEntry, ExtensionDetails, InstalledExtensionInfo, InstallLocalResult } from '@stina/extension-installer'
-import { requireAuth, requireAdmin } from '@stina/auth'
-import { ToolConfirmationRepository } from '@stina/chat/db'
+import { requireAuth, requireAdmin, requireCapability } from '@stina/auth'
+import { ToolConfirmationRepository, ExtensionFileRootRepository } from '@stina/chat/db'
 import { getDatabase } from '@stina/adapters-node'
 import { asChatDb } from '../asChatDb.js'
-import { getUserId } from './auth-helpers.js'
+import { getAuditActor, getUserId, hasCapability, recordAudit } from './auth-helpers.js'
+import { stat } from 'node:fs/promises'
+import { isAbsolute, resolve } from 'node:path'
 
 export const extensionRoutes: FastifyPluginAsync = async (fastify) => {
   const db = asChatDb(getDatabase())
+  const installExtensions = requireCapability('extensions:install')
 
   // ===========================================================================
   // Local Extensions (currently loaded)
@@ -228,12 +231,12 @@ export const extensionRoutes: FastifyPluginAsync = async (fastify) => {
   })
 
   /**
-   * Install an extension (admin only)
+   * Install an extension (requires the extensions:install capability)
    */
   fastify.post<{
     Body: { extensionId: string; version?: string }
     Reply: { success: boolean; extensionId: string; version: string; error?: string }
-  }>('/extensions/install', { preHandler: requireAdmin }, async (request, reply) => {
+  }>('/extensions/install', { preHandler: installExtensions }, async (request, reply) => {
     const installer = getExtensionInstaller()
     if (!installer) {
       return reply.status(503).send({
@@ -255,7 +258,7 @@ export const extensionRoutes: FastifyPluginAsync = async (fastify) => {
       })
     }
 
-    const result = await installer.install(extensionId, version)
+    const result = await installer.install(extensionId, version, getAuditActor(request))
 
     if (!result.success) {
       return reply.status(400).send(result)
@@ -266,13 +269,13 @@ export const extensionRoutes: FastifyPluginAsync = async (fastify) => {
   })
 
   /**
-   * Uninstall an extension (admin only)
+   * Uninstall an extension (requires the extensions:install capability)
    */
   fastify.delete<{
     Params: { id: string }
     Querystring: { deleteData?: string }
     Reply: { success: boolean; error?: string }
-  }>('/extensions/:id', { preHandler: requireAdmin }, async (request, reply) => {
+  }>('/extensions/:id', { preHandler: installExtensions }, async (request, reply) => {
     const installer = getExtensionInstaller()
     if (!installer) {
       return reply.status(503).send({
@@ -282,7 +285,7 @@ export const extensionRoutes: FastifyPluginAsync = async (fastify) => {
     }
 
     const deleteData = request.query.deleteData === 'true'
-    const result = await installer.uninstall(request.params.id, deleteData)
+    const result = await installer.uninstall(request.params.id, deleteData, getAuditActor(request))
 
     if (!result.success) {
       return reply.status(400).send(result)
@@ -341,13 +344,13 @@ export const extensionRoutes: FastifyPluginAsync = async (fastify) => {
   })
 
   /**
-   * Update an extension (admin only)
+   * Update an extension (requires the extensions:install capability)
    */
   fastify.post<{
     Params: { id: string }
     Body: { version?: string }
     Reply: { success: boolean; extensionId: string; version: string; error?: string }
-  }>('/extensions/:id/update', { preHandler: requireAdmin }, async (request, reply) => {
+  }>('/extensions/:id/update', { preHandler: installExtensions }, async (request, reply) => {
     const installer = getExtensionInstaller()
     if (!installer) {
       return reply.status(503).send({
@@ -358,7 +361,11 @@ export const extensionRoutes: FastifyPluginAsync = async (fastify) => {
       })
     }
 
-    const result = await installer.update(request.params.id, request.body?.version)
+    const result = await installer.update(
+      request.params.id,
+      request.body?.version,
+      getAuditActor(request)
+    )
 
     if (!result.success) {
       return reply.status(400).send(result)
@@ -373,11 +380,11 @@ export const extensionRoutes: FastifyPluginAsync = async (fastify) => {
   // ===========================================================================
 
   /**
-   * Upload and install a local extension from ZIP file (admin only)
+   * Upload and install a local extension from ZIP file (requires the extensions:install capability)
    */
   fastify.post<{
     Reply: InstallLocalResult
-  }>('/extensions/upload', { preHandler: requireAdmin }, async (request, reply) => {
+  }>('/extensions/upload', { preHandler: installExtensions }, async (request, reply) => {
     const installer = getExtensionInstaller()
     if (!installer) {
       return reply.status(503).send({
@@ -485,7 +492,7 @@ export const extensionRoutes: FastifyPluginAsync = async (fastify) => {
       })(),
     )
 
-    const result = await installer.installLocalExtension(completeStream)
+    const result = await installer.installLocalExtension(completeStream, getAuditActor(request))
 
     if (!result.success) {
       return reply.status(400).send(result)
@@ -576,15 +583,30 @@ export const extensionRoutes: FastifyPluginAsync = async (fastify) => {
   /**
    * Set tool confirmation override for a specific tool.
    * Allows the user to override whether a tool requires confirmation before execution.
+   * Turning the confirmation off requires the tools:approve capability.
    */
   fastify.put<{
     Params: { id: string; toolId: string }
     Body: { requiresConfirmation: boolean }
-    Reply: { success: boolean }
-  }>('/extensions/:id/tool-confirmations/:toolId', { preHandler: requireAuth }, async (request) => {
+    Reply: { success: boolean } | { error: string }
+  }>('/extensions/:id/tool-confirmations/:toolId', { preHandler: requireAuth }, async (request, reply) => {
+    if (!request.body.requiresConfirmation && !(await hasCapability(request, 'tools:approve'))) {
+      reply.code(403)
+      return { error: 'You are not allowed to approve tool calls' }
+    }
+
     const userId = getUserId(request)
     const repo = new ToolConfirmationRepository(db, userId)
     await repo.set(request.params.id, request.params.toolId, request.body.requiresConfirmation)
+    await recordAudit(request, {
+      action: 'tool.confirmation_changed',
+      targetType: 'tool',
+      targetId: request.params.toolId,
+      details: {
+        extensionId: request.params.id,
+        requiresConfirmation: request.body.requiresConfirmation,
+      },
+    })
     return { success: true }
   })
 
@@ -615,4 +637,72 @@ export const extensionRoutes: FastifyPluginAsync = async (fastify) => {
     await repo.resetForExtension(request.params.id)
     return { success: true }
   })
+
+  // ===========================================================================
+  // File Roots
+  // ===========================================================================
+
+  /**
+   * Get the directories the current user has approved for an extension's file access.
+   */
+  fastify.get<{
+    Params: { id: string }
+    Reply: Array<{ extensionId: string; path: string; access: 'read' | 'readwrite'; createdAt: string }>
+  }>('/extensions/:id/file-roots', { preHandler: requireAuth }, async (request) => {
+    const userId = getUserId(request)
+    const repo = new ExtensionFileRootRepository(db, userId)
+    const roots = await repo.getForExtension(request.params.id)
+    return roots.map((r) => ({
+      ...r,
+      createdAt: r.createdAt.toISOString(),
+    }))
+  })
+
+  /**
+   * Approve a directory for an extension's file access.
+   * The directory is on the server's filesystem, so only admins may approve roots.
+   */
+  fastify.put<{
+    Params: { id: string }
+    Body: { path: string; access: 'read' | 'readwrite' }
+    Reply: { success: boolean } | { error: string }
+  }>('/extensions/:id/file-roots', { preHandler: requireAdmin }, async (request, reply) => {
+    const { path, access } = request.body
+    if (typeof path !== 'string' || !isAbsolute(path)) {
+      return reply.status(400).send({ error: 'path must be an absolute path' })
+    }
+    if (access !== 'read' && access !== 'readwrite') {
+      return reply.status(400).send({ error: "access must be 'read' or 'readwrite'" })
+    }
+
+    const info = await stat(path).catch(() => null)
+    if (!info?.isDirectory()) {
+      return reply.status(400).send({ error: `Not a directory: ${path}` })
+    }
+
+    const userId = getUserId(request)
+    const repo = new ExtensionFileRootRepository(db, userId)
+    await repo.set(request.params.id, resolve(path), access)
+    return { success: true }
+  })
+
+  /**
+   * Revoke an approved directory for an extension.
+   * The path is normalized like when it was approved.
+   */
+  fastify.delete<{
+    Params: { id: string }
+    Querystring: { path: string }
+    Reply: { success: boolean } | { error: string }
+  }>('/extensions/:id/file-roots', { preHandler: requireAuth }, async (request, reply) => {
+    const { path } = request.query
+    if (typeof path !== 'string' || !isAbsolute(path)) {
+      return reply.status(400).send({ error: 'path must be an absolute path' })
+    }
+
+    const userId = getUserId(request)
+    const repo = new ExtensionFileRootRepository(db, userId)
+    await repo.remove(request.params.id, resolve(path))
+    return { success: true }
+  })
 }
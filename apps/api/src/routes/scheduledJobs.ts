@@ -1,11 +1,27 @@
 import type { FastifyPluginAsync } from 'fastify'
 import { SchedulerRepository, getScheduleDescription } from '@stina/scheduler'
-import type { ScheduledJobSummaryDTO, ScheduledJobDetailDTO } from '@stina/shared'
+import type { SchedulerService } from '@stina/scheduler'
+import type {
+  ScheduledJobSummaryDTO,
+  ScheduledJobDetailDTO,
+  ScheduledJobRunDTO,
+  ScheduledJobResumeOptions,
+} from '@stina/shared'
 import { getDatabase } from '@stina/adapters-node'
 import { requireAuth } from '@stina/auth'
 import { getExtensionHost } from '../setup.js'
 import { getUserId } from './auth-helpers.js'
 
+const DEFAULT_RUNS_LIMIT = 20
+const MAX_RUNS_LIMIT = 100
+
+declare module 'fastify' {
+  interface FastifyInstance {
+    /** The running scheduler, used to pause, resume and run jobs */
+    scheduler: SchedulerService
+  }
+}
+
 /**
  * Scheduled jobs routes for viewing and managing scheduled jobs
  */
@@ -33,16 +49,18 @@ export const scheduledJobsRoutes: FastifyPluginAsync = async (fastify) => {
       nextRunAt: job.nextRunAt,
       lastRunAt: job.lastRunAt,
       enabled: job.enabled,
+      paused: job.pausedAt !== null,
       createdAt: job.createdAt,
     }))
   })
 
   /**
-   * Get a specific scheduled job for the authenticated user
-   * GET /scheduled-jobs/:id
+   * Get a specific scheduled job for the authenticated user, with a page of its run history
+   * GET /scheduled-jobs/:id?runsLimit=20&runsOffset=0
    */
   fastify.get<{
     Params: { id: string }
+    Querystring: { runsLimit?: string; runsOffset?: string }
     Reply: ScheduledJobDetailDTO | { error: string }
   }>('/scheduled-jobs/:id', { preHandler: requireAuth }, async (request, reply) => {
     const userId = getUserId(request)
@@ -52,6 +70,14 @@ export const scheduledJobsRoutes: FastifyPluginAsync = async (fastify) => {
       return reply.status(404).send({ error: 'Job not found' })
     }
 
+    const parsedLimit = request.query.runsLimit ? parseInt(request.query.runsLimit, 10) : NaN
+    const parsedOffset = request.query.runsOffset ? parseInt(request.query.runsOffset, 10) : NaN
+    const limit = Number.isNaN(parsedLimit)
+      ? DEFAULT_RUNS_LIMIT
+      : Math.min(Math.max(parsedLimit, 1), MAX_RUNS_LIMIT)
+    const offset = Number.isNaN(parsedOffset) ? 0 : Math.max(parsedOffset, 0)
+    const history = schedulerRepo.listRunsForUser(job.id, userId, { limit, offset })
+
     // Get extension name if available
     let extensionName: string | null = null
     const extensionHost = getExtensionHost()
@@ -85,10 +111,92 @@ export const scheduledJobsRoutes: FastifyPluginAsync = async (fastify) => {
       nextRunAt: job.nextRunAt,
       lastRunAt: job.lastRunAt,
       enabled: job.enabled,
+      paused: job.pausedAt !== null,
       createdAt: job.createdAt,
       payload,
       extensionName,
+      retryAt: job.retryAt,
+      history: {
+        runs: history.runs.map(
+          (run): ScheduledJobRunDTO => ({
+            id: run.id,
+            scheduledFor: run.scheduledFor,
+            firedAt: run.firedAt,
+            delayMs: run.delayMs,
+            durationMs: run.durationMs,
+            status: run.status,
+            error: run.error,
+            attempt: run.attempt,
+            manual: run.manual,
+          })
+        ),
+        total: history.total,
+        limit,
+        offset,
+      },
+    }
+  })
+
+  /**
+   * Pause a scheduled job. It does not fire until it is resumed.
+   * POST /scheduled-jobs/:id/pause
+   */
+  fastify.post<{
+    Params: { id: string }
+    Reply: { success: boolean } | { error: string }
+  }>('/scheduled-jobs/:id/pause', { preHandler: requireAuth }, async (request, reply) => {
+    const userId = getUserId(request)
+    try {
+      if (!fastify.scheduler.pauseJob(request.params.id, userId)) {
+        return reply.status(404).send({ error: 'Job not found' })
+      }
+    } catch (error) {
+      return reply
+        .status(409)
+        .send({ error: error instanceof Error ? error.message : String(error) })
+    }
+    return { success: true }
+  })
+
+  /**
+   * Resume a paused scheduled job
+   * POST /scheduled-jobs/:id/resume
+   */
+  fastify.post<{
+    Params: { id: string }
+    Body: ScheduledJobResumeOptions | undefined
+    Reply: { success: boolean } | { error: string }
+  }>('/scheduled-jobs/:id/resume', { preHandler: requireAuth }, async (request, reply) => {
+    const userId = getUserId(request)
+    const misfire = request.body?.misfire ?? 'run_once'
+    if (misfire !== 'run_once' && misfire !== 'skip') {
+      return reply.status(400).send({ error: 'Invalid misfire value' })
+    }
+    if (!fastify.scheduler.resumeJob(request.params.id, userId, misfire)) {
+      return reply.status(404).send({ error: 'Paused job not found' })
+    }
+    return { success: true }
+  })
+
+  /**
+   * Run a scheduled job once right away, without changing its schedule
+   * POST /scheduled-jobs/:id/run
+   */
+  fastify.post<{
+    Params: { id: string }
+    Reply: { success: boolean } | { error: string }
+  }>('/scheduled-jobs/:id/run', { preHandler: requireAuth }, async (request, reply) => {
+    const userId = getUserId(request)
+    try {
+      if (!fastify.scheduler.runJobNow(request.params.id, userId)) {
+        return reply.status(404).send({ error: 'Job not found' })
+      }
+    } catch (error) {
+      return reply
+        .status(409)
+        .send({ error: error instanceof Error ? error.message : String(error) })
     }
+    return { success: true }
   })
 
   /**
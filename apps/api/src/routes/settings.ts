@@ -5,7 +5,7 @@ import { getDatabase } from '@stina/adapters-node'
 import { asChatDb } from '../asChatDb.js'
 import { randomUUID } from 'node:crypto'
 import { requireAuth, requireAdmin } from '@stina/auth'
-import { getUserId } from './auth-helpers.js'
+import { getUserId, canUseModelConfig } from './auth-helpers.js'
 import { invalidateUserSessionManager } from './chatStream.js'
 
 /**
@@ -28,18 +28,27 @@ export const settingsRoutes: FastifyPluginAsync = async (fastify) => {
   const getQuickCommandRepository = (userId: string) => new QuickCommandRepository(db, userId)
 
   // ===========================================================================
-  // Model Configurations (Global - Admin manages, all users can read)
+  // Model Configurations (Global - Admin manages, users read those their policy allows)
   // ===========================================================================
 
   /**
-   * List all model configurations
+   * List the model configurations the user may use
    * GET /settings/ai/models
    */
   fastify.get<{
     Reply: ModelConfigDTO[]
-  }>('/settings/ai/models', { preHandler: requireAuth }, async () => {
+  }>('/settings/ai/models', { preHandler: requireAuth }, async (request) => {
     const configs = await modelConfigRepo.list()
-    return configs
+    const policyService = fastify.policyService
+    if (!policyService || request.user?.role === 'admin') {
+      return configs
+    }
+
+    const { allowedModelConfigIds } = await policyService.getEffectivePolicy(getUserId(request))
+    if (allowedModelConfigIds === null) {
+      return configs
+    }
+    return configs.filter((config) => allowedModelConfigIds.includes(config.id))
   })
 
   /**
@@ -52,7 +61,7 @@ export const settingsRoutes: FastifyPluginAsync = async (fastify) => {
   }>('/settings/ai/models/:id', { preHandler: requireAuth }, async (request, reply) => {
     const config = await modelConfigRepo.get(request.params.id)
 
-    if (!config) {
+    if (!config || !(await canUseModelConfig(request, config.id))) {
       return reply.status(404).send({ error: 'Model config not found' } as unknown as ModelConfigDTO)
     }
 
@@ -67,7 +76,7 @@ export const settingsRoutes: FastifyPluginAsync = async (fastify) => {
     Body: Omit<ModelConfigDTO, 'id' | 'createdAt' | 'updatedAt'>
     Reply: ModelConfigDTO
   }>('/settings/ai/models', { preHandler: requireAdmin }, async (request, reply) => {
-    const { name, providerId, providerExtensionId, modelId, settingsOverride } = request.body
+    const { name, providerId, providerExtensionId, modelId, settingsOverride, contextLength } = request.body
 
     if (!name || !providerId || !providerExtensionId || !modelId) {
       return reply.status(400).send({
@@ -82,6 +91,7 @@ export const settingsRoutes: FastifyPluginAsync = async (fastify) => {
       providerExtensionId,
       modelId,
       settingsOverride,
+      contextLength,
     })
 
     return config
@@ -97,13 +107,14 @@ export const settingsRoutes: FastifyPluginAsync = async (fastify) => {
     Reply: ModelConfigDTO
   }>('/settings/ai/models/:id', { preHandler: requireAdmin }, async (request, reply) => {
     // Extract only the fields that can be updated (exclude isDefault which is now per-user)
-    const { name, providerId, providerExtensionId, modelId, settingsOverride } = request.body
+    const { name, providerId, providerExtensionId, modelId, settingsOverride, contextLength } = request.body
     const updated = await modelConfigRepo.update(request.params.id, {
       name,
       providerId,
       providerExtensionId,
       modelId,
       settingsOverride,
+      contextLength,
     })
 
     if (!updated) {
@@ -165,6 +176,9 @@ export const settingsRoutes: FastifyPluginAsync = async (fastify) => {
       if (!config) {
         return reply.status(404).send({ success: false, error: 'Model config not found' } as unknown as { success: boolean })
       }
+      if (!(await canUseModelConfig(request, modelConfigId))) {
+        return reply.status(403).send({ success: false, error: 'You are not allowed to use this model' } as unknown as { success: boolean })
+      }
     }
 
     await userSettingsRepo.setDefaultModelConfigId(modelConfigId)
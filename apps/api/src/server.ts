@@ -6,11 +6,19 @@ import { helloRoutes } from './routes/hello.js'
 import { themeRoutes } from './routes/themes.js'
 import { extensionRoutes } from './routes/extensions.js'
 import { chatRoutes } from './routes/chat.js'
-import { chatStreamRoutes, queueInstructionForUser } from './routes/chatStream.js'
+import {
+  chatStreamRoutes,
+  queueInstructionForUser,
+  createUserMemoryService,
+  setSessionPolicyService,
+} from './routes/chatStream.js'
+import { chatUsageRoutes } from './routes/chatUsage.js'
 import { settingsRoutes } from './routes/settings.js'
 import { toolsRoutes } from './routes/tools.js'
 import { createAuthRoutes } from './routes/auth.js'
+import { createAuditRoutes } from './routes/audit.js'
 import { createElectronAuthRoutes } from './routes/electronAuth.js'
+import { getPersonalAccessTokenScope } from './personalAccessTokenScopes.js'
 import { scheduledJobsRoutes } from './routes/scheduledJobs.js'
 import { systemRoutes } from './routes/system.js'
 import { setupExtensions, getExtensionHost } from './setup.js'
@@ -19,7 +27,18 @@ import {
   initAppSettingsStore,
   getChatMigrationsPath,
   UserSettingsRepository,
+  ChatHistoryReader,
+  ExtensionFileRootRepository,
+  TaskRepository,
+  ReminderRepository,
+  createQuietHoursService,
 } from '@stina/chat/db'
+import { ReminderService } from '@stina/chat'
+import {
+  BUILTIN_EXTENSION_ID,
+  createReminderScheduler,
+  handleBuiltinJobFire,
+} from '@stina/builtin-tools'
 import { asChatDb } from './asChatDb.js'
 import {
   SchedulerService,
@@ -34,6 +53,11 @@ import {
   TokenService,
   PasskeyService,
   ElectronAuthService,
+  PersonalAccessTokenService,
+  PolicyService,
+  OidcService,
+  AuditLogService,
+  AuditCleanupService,
 } from '@stina/auth'
 import {
   UserRepository,
@@ -41,6 +65,9 @@ import {
   RefreshTokenRepository,
   AuthConfigRepository,
   InvitationRepository,
+  PersonalAccessTokenRepository,
+  CapabilityPolicyRepository,
+  AuditEventRepository,
 } from '@stina/auth/db'
 import type { Logger } from '@stina/core'
 
@@ -93,6 +120,9 @@ export async function createServer(options: ServerOptions) {
   const refreshTokenRepository = new RefreshTokenRepository(db)
   const authConfigRepository = new AuthConfigRepository(db)
   const invitationRepository = new InvitationRepository(db)
+  const personalAccessTokenRepository = new PersonalAccessTokenRepository(db)
+  const capabilityPolicyRepository = new CapabilityPolicyRepository(db)
+  const auditEventRepository = new AuditEventRepository(db)
 
   // Get RP config from database (set during initial setup)
   const rpId = await authConfigRepository.getRpId()
@@ -149,6 +179,9 @@ export async function createServer(options: ServerOptions) {
         : allowedOrigins,
   })
 
+  // Initialize the audit log of security-relevant actions
+  const auditLogService = new AuditLogService(auditEventRepository, userRepository, logger)
+
   const authService = new AuthService(
     userRepository,
     passkeyCredentialRepository,
@@ -156,7 +189,9 @@ export async function createServer(options: ServerOptions) {
     authConfigRepository,
     invitationRepository,
     tokenService,
-    passkeyService
+    passkeyService,
+    new OidcService(),
+    auditLogService
   )
 
   // Initialize Electron auth service for external browser authentication
@@ -166,14 +201,26 @@ export async function createServer(options: ServerOptions) {
     refreshTokenRepository
   )
 
+  // Initialize personal access token service for scripts using the API
+  const personalAccessTokenService = new PersonalAccessTokenService(personalAccessTokenRepository)
+
+  // Initialize policy service deciding what non-admin users may do
+  const policyService = new PolicyService(capabilityPolicyRepository, userRepository)
+  setSessionPolicyService(policyService)
+
   // Register auth plugin
   await fastify.register(authPlugin, {
     authService,
     requireAuth: options.requireAuth ?? true,
     defaultUserId: options.defaultUserId,
+    personalAccessTokenService,
+    getRequiredScope: getPersonalAccessTokenScope,
+    policyService,
+    auditLogService,
   })
 
   const chatDb = asChatDb(db)
+  const chatHistoryReader = new ChatHistoryReader(chatDb)
 
   // Initialize settings store for local mode (with default user)
   // In multi-user mode, settings are fetched per-request via UserSettingsRepository
@@ -181,10 +228,48 @@ export async function createServer(options: ServerOptions) {
     await initAppSettingsStore(chatDb, options.defaultUserId)
   }
 
+  const quietHours = createQuietHoursService(chatDb, {
+    deliver: async (userId, text, conversationId) => {
+      const result = await queueInstructionForUser(userId, text, conversationId, logger)
+      if (!result.queued) {
+        throw new Error('Failed to queue instruction message')
+      }
+    },
+    logger,
+  })
+
   const scheduler = new SchedulerService({
     db,
     logger,
     onFire: (event) => {
+      // Reminders created with the built-in tools
+      if (event.extensionId === BUILTIN_EXTENSION_ID) {
+        void handleBuiltinJobFire(event.payload, {
+          getReminderService,
+          appendInstruction: async (userId, text) => {
+            try {
+              await quietHours.appendInstruction(userId, text)
+            } catch {
+              logger.warn('Failed to queue reminder instruction', { userId })
+            }
+          },
+          getLanguage: async (userId) =>
+            new UserSettingsRepository(chatDb, userId).getValue('language'),
+          getTimezone: async (userId) =>
+            new UserSettingsRepository(chatDb, userId).getValue('timezone'),
+        })
+          .then(() => scheduler.updateJobResult(BUILTIN_EXTENSION_ID, event.payload.id, true))
+          .catch((error) => {
+            const message = error instanceof Error ? error.message : String(error)
+            logger.error('Failed to deliver built-in scheduler job', {
+              jobId: event.payload.id,
+              error: message,
+            })
+            scheduler.updateJobResult(BUILTIN_EXTENSION_ID, event.payload.id, false, message)
+          })
+        return true
+      }
+
       const extensionHost = getExtensionHost()
       if (!extensionHost) return false
 
@@ -196,6 +281,12 @@ export async function createServer(options: ServerOptions) {
     },
   })
 
+  const getReminderService = (userId: string) =>
+    new ReminderService(
+      new ReminderRepository(chatDb, userId),
+      createReminderScheduler(scheduler, userId)
+    )
+
   // Setup extensions and themes (async to load provider extensions)
   await setupExtensions(logger, {
     scheduler: {
@@ -215,19 +306,19 @@ export async function createServer(options: ServerOptions) {
           return
         }
 
-        // Queue the instruction through the session manager
+        // Queue the instruction through the session manager, or hold it back during quiet hours
         // This will stream to connected clients if they have an active SSE connection
-        const result = await queueInstructionForUser(
-          userId,
-          message.text,
-          message.conversationId,
-          logger
-        )
-
-        if (!result.queued) {
+        try {
+          await quietHours.appendInstruction(userId, message.text, message.conversationId)
+        } catch {
           logger.warn('Failed to queue instruction message', { userId })
         }
       },
+      listConversations: async (_extensionId, query) => chatHistoryReader.listConversations(query),
+      getInteractions: async (_extensionId, conversationId, query) =>
+        chatHistoryReader.getInteractions(conversationId, query),
+      getCurrentConversation: async (_extensionId, query) =>
+        chatHistoryReader.getCurrentConversation(query),
     },
     user: {
       listIds: async () => {
@@ -235,9 +326,29 @@ export async function createServer(options: ServerOptions) {
         return allUsers.map((u) => u.id)
       },
     },
+    fileRoots: {
+      list: async (extensionId, userId) =>
+        new ExtensionFileRootRepository(chatDb, userId).getForExtension(extensionId),
+    },
+    getMemoryService: createUserMemoryService,
+    getTaskRepository: (userId) => new TaskRepository(chatDb, userId),
+    getReminderService,
+    scheduler,
+    onExtensionAuditEvent: async ({ action, extensionId, version, permissions, source, actor }) => {
+      await auditLogService.record({
+        action,
+        actorUserId: actor?.userId,
+        targetType: 'extension',
+        targetId: extensionId,
+        details: { version, permissions, source },
+        ip: actor?.ip,
+      })
+    },
   })
 
+  fastify.decorate('scheduler', scheduler)
   scheduler.start()
+  quietHours.start()
 
   // Periodically remove old completed (disabled) scheduled jobs based on
   // each user's retention preference (AppSettingsDTO.scheduledJobsRetentionDays).
@@ -252,8 +363,20 @@ export async function createServer(options: ServerOptions) {
   })
   schedulerCleanup.start()
 
+  // Periodically remove audit events older than STINA_AUDIT_RETENTION_DAYS (default 90 days,
+  // 0 keeps them forever)
+  const auditRetentionDays = parseInt(process.env['STINA_AUDIT_RETENTION_DAYS'] ?? '', 10)
+  const auditCleanup = new AuditCleanupService({
+    repository: auditEventRepository,
+    logger,
+    retentionDays: Number.isNaN(auditRetentionDays) ? undefined : auditRetentionDays,
+  })
+  auditCleanup.start()
+
   fastify.addHook('onClose', async () => {
     schedulerCleanup.stop()
+    auditCleanup.stop()
+    quietHours.stop()
   })
 
   // Register routes
@@ -264,10 +387,12 @@ export async function createServer(options: ServerOptions) {
   await fastify.register(extensionRoutes)
   await fastify.register(chatRoutes)
   await fastify.register(chatStreamRoutes)
+  await fastify.register(chatUsageRoutes)
   await fastify.register(settingsRoutes)
   await fastify.register(toolsRoutes)
   await fastify.register(scheduledJobsRoutes)
-  await fastify.register(createAuthRoutes(authService))
+  await fastify.register(createAuthRoutes(authService, personalAccessTokenService, policyService))
+  await fastify.register(createAuditRoutes(auditLogService))
   await fastify.register(createElectronAuthRoutes(authService, electronAuthService))
 
   return fastify
@@ -9,14 +9,28 @@ import {
   getRawDb,
 } from '@stina/adapters-node'
 import { NodeExtensionHost, ExtensionProviderBridge, ExtensionToolBridge } from '@stina/extension-host'
+import type { FileRootsProvider } from '@stina/extension-host'
 import { ExtensionInstaller } from '@stina/extension-installer'
-import type { InstalledExtension } from '@stina/extension-installer'
-import { providerRegistry, toolRegistry } from '@stina/chat'
-import { registerBuiltinTools } from '@stina/builtin-tools'
+import type { ExtensionAuditEvent, InstalledExtension } from '@stina/extension-installer'
+import {
+  providerRegistry,
+  toolRegistry,
+  registerOpenAICompatibleProvider,
+} from '@stina/chat'
+import type { ITaskRepository, MemoryService, ReminderService } from '@stina/chat'
+import { registerBuiltinTools, type BuiltinJobScheduler } from '@stina/builtin-tools'
 import { APP_NAMESPACE } from '@stina/core'
 import type { Logger } from '@stina/core'
 import { getAppSettingsStore } from '@stina/chat/db'
-import type { SchedulerJobRequest, ChatInstructionMessage, Platform } from '@stina/extension-api'
+import type {
+  SchedulerJobRequest,
+  ChatInstructionMessage,
+  ChatHistoryQuery,
+  ChatConversationSummary,
+  ChatHistoryInteraction,
+  ChatCurrentConversation,
+  Platform,
+} from '@stina/extension-api'
 
 // Global extension host instance
 let extensionHost: NodeExtensionHost | null = null
@@ -54,10 +68,32 @@ export interface ExtensionSetupOptions {
   }
   chat?: {
     appendInstruction: (extensionId: string, message: ChatInstructionMessage) => Promise<void>
+    listConversations: (extensionId: string, query: ChatHistoryQuery) => Promise<ChatConversationSummary[]>
+    getInteractions: (
+      extensionId: string,
+      conversationId: string,
+      query: ChatHistoryQuery
+    ) => Promise<ChatHistoryInteraction[]>
+    getCurrentConversation: (
+      extensionId: string,
+      query: ChatHistoryQuery
+    ) => Promise<ChatCurrentConversation | null>
   }
   user?: {
     listIds: () => Promise<string[]>
   }
+  /** Directories users have approved for extension file access */
+  fileRoots?: FileRootsProvider
+  /** Long-term memory per user, used by the built-in memory tools */
+  getMemoryService?: (userId: string) => MemoryService
+  /** Tasks per user, used by the built-in task tools */
+  getTaskRepository?: (userId: string) => ITaskRepository
+  /** Reminders per user, used by the built-in reminder tools */
+  getReminderService?: (userId: string) => ReminderService
+  /** Scheduler for built-in jobs, used by the scheduled reminder tool */
+  scheduler?: BuiltinJobScheduler
+  /** Records extension installs, updates and uninstalls in the audit log */
+  onExtensionAuditEvent?: (event: ExtensionAuditEvent) => Promise<void>
 }
 
 /**
@@ -75,15 +111,22 @@ export async function setupExtensions(
       const settingsStore = getAppSettingsStore()
       return settingsStore?.get<string>(APP_NAMESPACE, 'timezone')
     },
+    getMemoryService: options?.getMemoryService,
+    getTaskRepository: options?.getTaskRepository,
+    getReminderService: options?.getReminderService,
+    scheduler: options?.scheduler,
   })
   logger.info('Registered built-in tools', { count: builtinCount })
 
+  registerOpenAICompatibleProvider(providerRegistry, process.env)
+
   const runtime = await createNodeExtensionRuntime({
     logger,
     stinaVersion: STINA_VERSION,
     platform: options?.platform ?? 'web',
     scheduler: options?.scheduler,
     chat: options?.chat,
+    fileRoots: options?.fileRoots,
     user: {
       getProfile: async (_extensionId: string): Promise<UserProfile> => {
         const settingsStore = getAppSettingsStore()
@@ -148,6 +191,7 @@ export async function setupExtensions(
         modelConfigsDeleted: result.modelConfigsDeleted,
       })
     },
+    onAuditEvent: options?.onExtensionAuditEvent,
   })
 
   extensionInstaller = runtime.extensionInstaller
@@ -45,8 +45,14 @@ import {
   initAppSettingsStore,
   getAppSettingsStore,
   ConversationRepository,
-  ModelConfigRepository,
   UserSettingsRepository,
+  ChatHistoryReader,
+  ExtensionFileRootRepository,
+  MemoryRepository,
+  TaskRepository,
+  ReminderRepository,
+  createQuietHoursService,
+  createModelConfigProvider,
 } from '@stina/chat/db'
 import type { ChatDb } from '@stina/chat/db'
 import type { UserProfile } from '@stina/extension-api'
@@ -55,10 +61,33 @@ import {
   SchedulerRepository,
   SchedulerCleanupService,
 } from '@stina/scheduler'
-import { providerRegistry, toolRegistry, runInstructionMessage } from '@stina/chat'
-import { registerBuiltinTools } from '@stina/builtin-tools'
-import { DefaultUserService } from '@stina/auth'
-import { UserRepository } from '@stina/auth/db'
+import {
+  providerRegistry,
+  toolRegistry,
+  runInstructionMessage,
+  MemoryService,
+  ReminderService,
+  type QuietHoursService,
+  registerOpenAICompatibleProvider,
+  createProviderEmbedder,
+} from '@stina/chat'
+import {
+  registerBuiltinTools,
+  BUILTIN_EXTENSION_ID,
+  createReminderScheduler,
+  handleBuiltinJobFire,
+} from '@stina/builtin-tools'
+import {
+  DefaultUserService,
+  PolicyService,
+  AuditLogService,
+  AuditCleanupService,
+} from '@stina/auth'
+import {
+  UserRepository,
+  CapabilityPolicyRepository,
+  AuditEventRepository,
+} from '@stina/auth/db'
 
 const logger = createConsoleLogger(getLogLevelFromEnv())
 const repoRoot = path.resolve(__dirname, '../../..')
@@ -148,6 +177,8 @@ let secretsManager: { close(): void } | null = null
 let database: DB | null = null
 let scheduler: SchedulerService | null = null
 let schedulerCleanup: SchedulerCleanupService | null = null
+let auditCleanup: AuditCleanupService | null = null
+let quietHours: QuietHoursService | null = null
 
 // Initialize i18n for this process (language detection per session)
 initI18n()
@@ -396,6 +427,10 @@ async function initializeApp() {
     const defaultUserService = new DefaultUserService(userRepository)
     const defaultUser = await defaultUserService.ensureDefaultUser()
     logger.info(`Using default user: ${defaultUser.username} (${defaultUser.id})`)
+    const capabilityPolicyRepository = new CapabilityPolicyRepository(database)
+    const policyService = new PolicyService(capabilityPolicyRepository, userRepository)
+    const auditEventRepository = new AuditEventRepository(database)
+    const auditLogService = new AuditLogService(auditEventRepository, userRepository, logger)
 
     // adapters-node DB and ChatDb are structurally compatible but have different generic schema types
     const chatDb = database as unknown as ChatDb
@@ -403,13 +438,38 @@ async function initializeApp() {
     // Initialize settings store with the default user
     await initAppSettingsStore(chatDb, defaultUser.id)
 
-    // Model configs are now global (no userId required)
-    const modelConfigRepository = new ModelConfigRepository(chatDb)
+    const chatHistoryReader = new ChatHistoryReader(chatDb)
     const settingsStore = getAppSettingsStore()
     const schedulerInstance = new SchedulerService({
       db: database,
       logger,
       onFire: (event) => {
+        // Reminders created with the built-in tools
+        if (event.extensionId === BUILTIN_EXTENSION_ID) {
+          void handleBuiltinJobFire(event.payload, {
+            getReminderService,
+            appendInstruction: async (userId, text) => {
+              await quietHoursInstance.appendInstruction(userId, text)
+            },
+            getLanguage: async (userId) =>
+              new UserSettingsRepository(chatDb, userId).getValue('language'),
+            getTimezone: async (userId) =>
+              new UserSettingsRepository(chatDb, userId).getValue('timezone'),
+          })
+            .then(() =>
+              schedulerInstance.updateJobResult(BUILTIN_EXTENSION_ID, event.payload.id, true)
+            )
+            .catch((error) => {
+              const message = error instanceof Error ? error.message : String(error)
+              logger.error('Failed to deliver built-in scheduler job', {
+                jobId: event.payload.id,
+                error: message,
+              })
+              schedulerInstance.updateJobResult(BUILTIN_EXTENSION_ID, event.payload.id, false, message)
+            })
+          return true
+        }
+
         if (!extensionHost) return false
 
         const extension = extensionHost.getExtension(event.extensionId)
@@ -426,15 +486,70 @@ async function initializeApp() {
       extensionRegistry.register(ext)
     }
 
+    // Model configs the user's policy does not allow fail the message
+    const createUserModelConfigProvider = (userId: string) =>
+      createModelConfigProvider(chatDb, userId, {
+        getAllowedModelConfigIds: () => policyService.getAllowedModelConfigIds(userId),
+      })
+
+    // Long-term memory, embedded with the provider of the user's default model
+    const getMemoryService = (userId: string) =>
+      new MemoryService(
+        new MemoryRepository(chatDb, userId),
+        createProviderEmbedder(providerRegistry, createUserModelConfigProvider(userId))
+      )
+
+    const getReminderService = (userId: string) =>
+      new ReminderService(
+        new ReminderRepository(chatDb, userId),
+        createReminderScheduler(schedulerInstance, userId)
+      )
+
+    const appendInstructionForUser = async (
+      userId: string,
+      text: string,
+      conversationId?: string
+    ) => {
+      const result = await runInstructionMessage(
+        {
+          repository: new ConversationRepository(chatDb, userId),
+          providerRegistry,
+          toolRegistry,
+          modelConfigProvider: createUserModelConfigProvider(userId),
+          settingsStore,
+        },
+        { text, conversationId }
+      )
+
+      // Emit chat event to notify renderer about the instruction message
+      emitChatEvent({
+        type: 'instruction-received',
+        userId,
+        conversationId: result.conversationId,
+      })
+    }
+
+    const quietHoursInstance = createQuietHoursService(chatDb, {
+      deliver: appendInstructionForUser,
+      logger,
+    })
+    quietHours = quietHoursInstance
+
     // Register built-in tools before extension runtime (so they're always available)
     const builtinToolCount = registerBuiltinTools(toolRegistry, {
       getTimezone: async () => {
         const store = getAppSettingsStore()
         return store?.get<string>(APP_NAMESPACE, 'timezone')
       },
+      getMemoryService,
+      getTaskRepository: (userId) => new TaskRepository(chatDb, userId),
+      getReminderService,
+      scheduler: schedulerInstance,
     })
     logger.info('Registered built-in tools', { count: builtinToolCount })
 
+    registerOpenAICompatibleProvider(providerRegistry, process.env)
+
     const runtime = await createNodeExtensionRuntime({
       logger,
       stinaVersion: app.getVersion() ?? '0.5.0',
@@ -448,45 +563,20 @@ async function initializeApp() {
       },
       chat: {
         appendInstruction: async (_extensionId, message) => {
-          // Use message.userId if provided, otherwise fall back to defaultUser
-          const userId = message.userId ?? defaultUser.id
-          const userConversationRepo = new ConversationRepository(chatDb, userId)
-          const userSettingsRepo = new UserSettingsRepository(chatDb, userId)
-          const userModelConfigProvider = {
-            async getDefault() {
-              const defaultModelId = await userSettingsRepo.getDefaultModelConfigId()
-              if (!defaultModelId) return null
-              const config = await modelConfigRepository.get(defaultModelId)
-              if (!config) return null
-              return {
-                providerId: config.providerId,
-                modelId: config.modelId,
-                settingsOverride: config.settingsOverride,
-              }
-            },
-          }
-
-          const result = await runInstructionMessage(
-            {
-              repository: userConversationRepo,
-              providerRegistry,
-              toolRegistry,
-              modelConfigProvider: userModelConfigProvider,
-              settingsStore,
-            },
-            {
-              text: message.text,
-              conversationId: message.conversationId,
-            }
+          // Use message.userId if provided, otherwise fall back to defaultUser.
+          // Held back during the user's quiet hours.
+          await quietHoursInstance.appendInstruction(
+            message.userId ?? defaultUser.id,
+            message.text,
+            message.conversationId
           )
-
-          // Emit chat event to notify renderer about the instruction message
-          emitChatEvent({
-            type: 'instruction-received',
-            userId,
-            conversationId: result.conversationId,
-          })
         },
+        listConversations: async (_extensionId, query) =>
+          chatHistoryReader.listConversations(query),
+        getInteractions: async (_extensionId, conversationId, query) =>
+          chatHistoryReader.getInteractions(conversationId, query),
+        getCurrentConversation: async (_extensionId, query) =>
+          chatHistoryReader.getCurrentConversation(query),
       },
       user: {
         getProfile: async (_extensionId: string): Promise<UserProfile> => {
@@ -504,6 +594,10 @@ async function initializeApp() {
           return [defaultUser.id]
         },
       },
+      fileRoots: {
+        list: async (extensionId, userId) =>
+          new ExtensionFileRootRepository(chatDb, userId).getForExtension(extensionId),
+      },
       callbacks: {
         onProviderRegistered: (provider) => {
           try {
@@ -547,6 +641,15 @@ async function initializeApp() {
           })
         }
       },
+      onAuditEvent: async ({ action, extensionId, version, permissions, source, actor }) => {
+        await auditLogService.record({
+          action,
+          actorUserId: actor?.userId,
+          targetType: 'extension',
+          targetId: extensionId,
+          details: { version, permissions, source },
+        })
+      },
     })
 
     extensionHost = runtime.extensionHost
@@ -567,6 +670,7 @@ async function initializeApp() {
     await registerThemesFromExtensions()
 
     schedulerInstance.start()
+    quietHoursInstance.start()
 
     // Periodically remove old completed (disabled) scheduled jobs based on the
     // user's retention preference (AppSettingsDTO.scheduledJobsRetentionDays).
@@ -582,6 +686,14 @@ async function initializeApp() {
     schedulerCleanup = schedulerCleanupInstance
     schedulerCleanupInstance.start()
 
+    // Periodically remove audit events older than the default retention
+    const auditCleanupInstance = new AuditCleanupService({
+      repository: auditEventRepository,
+      logger,
+    })
+    auditCleanup = auditCleanupInstance
+    auditCleanupInstance.start()
+
     registerIpcHandlers(ipcMain, {
       getGreeting,
       themeRegistry,
@@ -593,6 +705,9 @@ async function initializeApp() {
       db: database ?? undefined,
       defaultUserId: defaultUser.id,
       appVersion: getStinaVersion(),
+      scheduler,
+      policyService,
+      auditLogService,
     })
   } catch (error) {
     const errorMsg = error instanceof Error ? error.stack || error.message : String(error)
@@ -655,10 +770,18 @@ app.on('before-quit', () => {
     schedulerCleanup.stop()
     schedulerCleanup = null
   }
+  if (auditCleanup) {
+    auditCleanup.stop()
+    auditCleanup = null
+  }
   if (scheduler) {
     scheduler.stop()
     scheduler = null
   }
+  if (quietHours) {
+    quietHours.stop()
+    quietHours = null
+  }
   // Close storage and secrets database connections to prevent resource leaks
   if (storageExecutor) {
     storageExecutor.close()
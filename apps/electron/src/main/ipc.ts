@@ -1,4 +1,6 @@
 import { randomUUID } from 'node:crypto'
+import { stat } from 'node:fs/promises'
+import { isAbsolute, resolve } from 'node:path'
 import { shell, type IpcMain } from 'electron'
 import type {
   Greeting,
@@ -9,6 +11,14 @@ import type {
   AppSettingsDTO,
   QuickCommandDTO,
   ServerTimeResponse,
+  TokenUsageQueryDTO,
+  TokenUsageReportDTO,
+  ChatSearchQueryDTO,
+  ChatSearchHitDTO,
+  ConversationExportFormat,
+  ChatAttachmentUploadDTO,
+  ChatConversationOverridesDTO,
+  UserCapability,
 } from '@stina/shared'
 import { toIsoWithTimeZone } from '@stina/shared'
 import type { ThemeRegistry, ExtensionRegistry, Logger } from '@stina/core'
@@ -16,6 +26,7 @@ import { APP_NAMESPACE } from '@stina/core'
 import type { NodeExtensionHost } from '@stina/extension-host'
 import type { ExtensionInstaller } from '@stina/extension-installer'
 import type { DB } from '@stina/adapters-node'
+import type { AuditLogService, PolicyService, RecordAuditEventInput } from '@stina/auth'
 import type { Conversation, OrchestratorEvent, QueuedMessageRole, QueueState } from '@stina/chat'
 import {
   builtinExtensions,
@@ -23,8 +34,9 @@ import {
   getToolSettingsViews,
   mapExtensionManifestToCore,
   syncEnabledExtensions,
+  getAttachmentsPath,
 } from '@stina/adapters-node'
-import { ConversationRepository, ModelConfigRepository, UserSettingsRepository, QuickCommandRepository, ToolConfirmationRepository, getAppSettingsStore } from '@stina/chat/db'
+import { ConversationRepository, ModelConfigRepository, UserSettingsRepository, QuickCommandRepository, ToolConfirmationRepository, ExtensionFileRootRepository, TokenUsageRepository, MemoryRepository, FileAttachmentStore, getAppSettingsStore, createModelConfigProvider } from '@stina/chat/db'
 import type { ChatDb } from '@stina/chat/db'
 import {
   conversationToDTO,
@@ -36,11 +48,18 @@ import { updateAppSettingsStore } from '@stina/chat/db'
 import {
   ChatOrchestrator,
   ChatSessionManager,
+  exportConversation,
+  importConversation,
+  isConversationExportFormat,
   providerRegistry,
   toolRegistry,
+  MemoryService,
+  createProviderEmbedder,
+  storeAttachments,
 } from '@stina/chat'
 import { resolveLocalizedString } from '@stina/extension-api'
 import { SchedulerRepository, getScheduleDescription } from '@stina/scheduler'
+import type { SchedulerService } from '@stina/scheduler'
 import type { ChatStreamEvent } from './ipc/types.js'
 import { conversationEventBus, pendingConfirmationStore, emitChatEvent, onChatEvent } from './ipc/types.js'
 
@@ -64,6 +83,12 @@ export interface IpcContext {
   defaultUserId?: string
   /** Application version */
   appVersion?: string
+  /** Running scheduler, used to pause, resume and run scheduled jobs */
+  scheduler?: SchedulerService | null
+  /** Capability policies of the local user. Without it, the local user may do everything */
+  policyService?: PolicyService
+  /** Audit log that security-relevant actions of the local user are recorded in */
+  auditLogService?: AuditLogService
 }
 
 /**
@@ -81,6 +106,9 @@ export function registerIpcHandlers(ipcMain: IpcMain, ctx: IpcContext): void {
     db,
     defaultUserId,
     appVersion,
+    scheduler,
+    policyService,
+    auditLogService,
   } = ctx
 
   const ensureDb = (): DB => {
@@ -122,6 +150,34 @@ export function registerIpcHandlers(ipcMain: IpcMain, ctx: IpcContext): void {
     }
   }
 
+  /**
+   * Check whether the local user has a capability
+   */
+  const hasCapability = async (capability: UserCapability): Promise<boolean> => {
+    if (!policyService || !defaultUserId) return true
+    return policyService.can(defaultUserId, capability)
+  }
+
+  /**
+   * Check whether the local user may use a model config
+   */
+  const canUseModelConfig = async (modelConfigId: string): Promise<boolean> => {
+    if (!policyService || !defaultUserId) return true
+    return policyService.canUseModelConfig(defaultUserId, modelConfigId)
+  }
+
+  /**
+   * Record an action of the local user in the audit log
+   */
+  const recordAudit = async (
+    input: Omit<RecordAuditEventInput, 'actorUserId' | 'ip'>
+  ): Promise<void> => {
+    await auditLogService?.record({ ...input, actorUserId: defaultUserId ?? null })
+  }
+
+  /** The local user, for installer actions in the audit log */
+  const auditActor = { userId: defaultUserId }
+
   const getConversationRepo = () => {
     conversationRepo ??= new ConversationRepository(ensureChatDb(), defaultUserId!)
     return conversationRepo
@@ -462,8 +518,24 @@ export function registerIpcHandlers(ipcMain: IpcMain, ctx: IpcContext): void {
         return { success: false, error: 'User not initialized' }
       }
 
+      if (response.approved && !(await hasCapability('tools:approve'))) {
+        return { success: false, error: 'You are not allowed to approve tool calls' }
+      }
+
+      const recordResponse = () =>
+        recordAudit({
+          action: response.approved ? 'tool.approved' : 'tool.denied',
+          targetType: 'tool',
+          targetId: toolCallName,
+          details: {
+            conversationId: conversationId ?? null,
+            denialReason: response.denialReason ?? null,
+          },
+        })
+
       const centralResolved = pendingConfirmationStore.resolve(toolCallName, response, defaultUserId)
       if (centralResolved) {
+        await recordResponse()
         return { success: true }
       }
 
@@ -482,6 +554,7 @@ export function registerIpcHandlers(ipcMain: IpcMain, ctx: IpcContext): void {
         return { success: false, error: 'No pending confirmation found for this tool' }
       }
 
+      await recordResponse()
       return { success: true }
     }
   )
@@ -565,6 +638,62 @@ export function registerIpcHandlers(ipcMain: IpcMain, ctx: IpcContext): void {
     return { success: true }
   })
 
+  ipcMain.handle(
+    'chat-search',
+    async (_event, query: ChatSearchQueryDTO): Promise<ChatSearchHitDTO[]> => {
+      if (!query?.query?.trim()) {
+        throw new Error('query is required')
+      }
+      return getConversationRepo().searchMessages(query.query, {
+        limit: query.limit,
+        offset: query.offset,
+      })
+    }
+  )
+
+  ipcMain.handle(
+    'chat-usage',
+    async (_event, query: TokenUsageQueryDTO = {}): Promise<TokenUsageReportDTO> => {
+      if (!defaultUserId) {
+        throw new Error('No user initialized')
+      }
+
+      const from = query.from ? new Date(query.from) : undefined
+      const to = query.to ? new Date(query.to) : undefined
+      const timezone = query.timezone ?? (await getUserSettingsRepo().get()).timezone ?? 'UTC'
+
+      return new TokenUsageRepository(ensureChatDb()).getReport(
+        { userId: defaultUserId, from, to },
+        timezone
+      )
+    }
+  )
+
+  ipcMain.handle(
+    'chat-export-conversation',
+    async (_event, conversationId: string, format: ConversationExportFormat): Promise<string> => {
+      if (!isConversationExportFormat(format)) {
+        throw new Error('format must be markdown, json or html')
+      }
+
+      const conversation = await getConversationRepo().getConversationWithBranches(conversationId)
+      if (!conversation) {
+        throw new Error('Conversation not found')
+      }
+
+      const timeZone = (await getUserSettingsRepo().get()).timezone ?? 'UTC'
+      return exportConversation(conversation, format, { timeZone }).content
+    }
+  )
+
+  ipcMain.handle(
+    'chat-import-conversation',
+    async (_event, data: unknown): Promise<ChatConversationDTO> => {
+      const conversation = await importConversation(getConversationRepo(), data)
+      return conversationToDTO(conversation)
+    }
+  )
+
   ipcMain.handle('chat-archive-conversation', async (_event, conversationId: string): Promise<void> => {
     await getConversationRepo().archiveConversation(conversationId)
   })
@@ -595,6 +724,7 @@ export function registerIpcHandlers(ipcMain: IpcMain, ctx: IpcContext): void {
 
   // Chat session management for streaming
   let chatSessionManager: ChatSessionManager | null = null
+  const attachmentStore = new FileAttachmentStore(getAttachmentsPath())
   const chatStreamListeners = new Map<number, {
     queueId: string
     handler: (event: OrchestratorEvent) => void
@@ -608,26 +738,12 @@ export function registerIpcHandlers(ipcMain: IpcMain, ctx: IpcContext): void {
 
     const settingsStore = getAppSettingsStore()
     const conversationRepo = getConversationRepo()
-    const globalModelConfigRepo = getModelConfigRepo()
-    const userSettingsRepo = getUserSettingsRepo()
-
-    // Create model config provider adapter
-    // Gets the user's default model from user_settings, then fetches the full config from model_configs
-    const modelConfigProvider = {
-      async getDefault() {
-        const defaultModelId = await userSettingsRepo.getDefaultModelConfigId()
-        if (!defaultModelId) return null
 
-        const config = await globalModelConfigRepo.get(defaultModelId)
-        if (!config) return null
-
-        return {
-          providerId: config.providerId,
-          modelId: config.modelId,
-          settingsOverride: config.settingsOverride,
-        }
-      },
-    }
+    // Model configs the user's policy does not allow fail the message
+    const modelConfigProvider = createModelConfigProvider(ensureChatDb(), defaultUserId!, {
+      getAllowedModelConfigIds: async () =>
+        policyService ? policyService.getAllowedModelConfigIds(defaultUserId!) : null,
+    })
 
     // Get user's language for localization
     const userLanguage = settingsStore?.get<string>(APP_NAMESPACE, 'language') ?? 'en'
@@ -648,6 +764,11 @@ export function registerIpcHandlers(ipcMain: IpcMain, ctx: IpcContext): void {
             providerRegistry,
             modelConfigProvider,
             toolRegistry,
+            memory: new MemoryService(
+              new MemoryRepository(ensureChatDb(), defaultUserId!),
+              createProviderEmbedder(providerRegistry, modelConfigProvider)
+            ),
+            attachmentStore,
             settingsStore,
             getToolDisplayName,
             userLanguage,
@@ -684,9 +805,13 @@ export function registerIpcHandlers(ipcMain: IpcMain, ctx: IpcContext): void {
         role?: QueuedMessageRole
         context?: 'conversation-start' | 'settings-update'
         sessionId?: string
+        attachments?: ChatAttachmentUploadDTO[]
+        regenerateInteractionId?: string
+        editInteractionId?: string
       }
     ): Promise<{ success: boolean; error?: string }> => {
       const { queueId, role = 'user', context, sessionId } = options
+      const { regenerateInteractionId, editInteractionId } = options
       const sender = event.sender
       const senderId = sender.id
 
@@ -778,6 +903,7 @@ export function registerIpcHandlers(ipcMain: IpcMain, ctx: IpcContext): void {
                 conversationId: orcEvent.conversationId,
                 role: orcEvent.role,
                 text: orcEvent.text,
+                attachments: orcEvent.attachments,
                 queueId: orcEvent.queueId,
               }
               break
@@ -828,8 +954,25 @@ export function registerIpcHandlers(ipcMain: IpcMain, ctx: IpcContext): void {
           await orchestrator.loadConversation(conversationId)
         }
 
+        // Editing or regenerating starts a new branch next to the interaction
+        if (regenerateInteractionId) {
+          await orchestrator.regenerate(regenerateInteractionId, queueId)
+          return { success: true }
+        }
+        if (editInteractionId) {
+          await orchestrator.editAndResend(editInteractionId, message, queueId)
+          return { success: true }
+        }
+
+        // Store attached files before the message is queued
+        const uploads = options.attachments ?? []
+        if (uploads.length > 0 && role === 'instruction') {
+          throw new Error('Attachments can only be sent with user messages')
+        }
+        const attachments = await storeAttachments(attachmentStore, uploads)
+
         // Enqueue the message - this triggers streaming
-        await orchestrator.enqueueMessage(message, role, queueId, context)
+        await orchestrator.enqueueMessage(message, role, queueId, context, attachments)
 
         return { success: true }
       } catch (err) {
@@ -903,6 +1046,66 @@ export function registerIpcHandlers(ipcMain: IpcMain, ctx: IpcContext): void {
     }
   )
 
+  // Revert a context summary so the summarized interactions are sent in full again
+  ipcMain.handle(
+    'chat-revert-context-summary',
+    async (_event, conversationId: string, interactionId: string): Promise<{ success: boolean }> => {
+      const session = getChatSessionManager().getSession({ conversationId })
+      const orchestrator = session.orchestrator
+
+      if (orchestrator.conversation?.id !== conversationId) {
+        await orchestrator.loadConversation(conversationId)
+      }
+
+      const reverted = await orchestrator.revertContextCompaction(interactionId)
+      return { success: reverted }
+    }
+  )
+
+  // Replace a conversation's model and personality overrides
+  ipcMain.handle(
+    'chat-set-conversation-overrides',
+    async (
+      _event,
+      conversationId: string,
+      overrides: ChatConversationOverridesDTO
+    ): Promise<ChatConversationOverridesDTO> => {
+      if (overrides?.modelConfigId && !(await canUseModelConfig(overrides.modelConfigId))) {
+        throw new Error('You are not allowed to use this model')
+      }
+
+      const session = getChatSessionManager().getSession({ conversationId })
+      const orchestrator = session.orchestrator
+
+      if (orchestrator.conversation?.id !== conversationId) {
+        await orchestrator.loadConversation(conversationId)
+      }
+
+      return orchestrator.setConversationOverrides(overrides)
+    }
+  )
+
+  // Show another branch of a conversation
+  ipcMain.handle(
+    'chat-switch-branch',
+    async (
+      _event,
+      conversationId: string,
+      interactionId: string,
+      sessionId?: string
+    ): Promise<{ success: boolean }> => {
+      const session = getChatSessionManager().getSession({ sessionId, conversationId })
+      const orchestrator = session.orchestrator
+
+      if (orchestrator.conversation?.id !== conversationId) {
+        await orchestrator.loadConversation(conversationId)
+      }
+
+      const switched = await orchestrator.switchBranch(interactionId)
+      return { success: switched }
+    }
+  )
+
   // Reset conversation and clear queue
   ipcMain.handle(
     'chat-queue-reset',
@@ -968,7 +1171,15 @@ export function registerIpcHandlers(ipcMain: IpcMain, ctx: IpcContext): void {
           error: 'Extension installer not initialized',
         }
       }
-      const result = await extensionInstaller.install(extensionId, version)
+      if (!(await hasCapability('extensions:install'))) {
+        return {
+          success: false,
+          extensionId,
+          version: 'unknown',
+          error: 'You are not allowed to install extensions',
+        }
+      }
+      const result = await extensionInstaller.install(extensionId, version, auditActor)
       if (result.success) {
         await syncExtensions()
       }
@@ -980,6 +1191,9 @@ export function registerIpcHandlers(ipcMain: IpcMain, ctx: IpcContext): void {
     if (!extensionInstaller) {
       return { success: false, error: 'Extension installer not initialized' }
     }
+    if (!(await hasCapability('extensions:install'))) {
+      return { success: false, error: 'You are not allowed to uninstall extensions' }
+    }
 
     // Unload extension from host before uninstalling
     if (extensionHost) {
@@ -994,7 +1208,7 @@ export function registerIpcHandlers(ipcMain: IpcMain, ctx: IpcContext): void {
       }
     }
 
-    const result = await extensionInstaller.uninstall(extensionId, deleteData)
+    const result = await extensionInstaller.uninstall(extensionId, deleteData, auditActor)
     if (result.success) {
       await syncExtensions()
     }
@@ -1030,6 +1244,9 @@ export function registerIpcHandlers(ipcMain: IpcMain, ctx: IpcContext): void {
     if (!extensionInstaller) {
       return { success: false, extensionId: 'unknown', error: 'Extension installer not initialized' }
     }
+    if (!(await hasCapability('extensions:install'))) {
+      return { success: false, extensionId: 'unknown', error: 'You are not allowed to install extensions' }
+    }
 
     // Validate filename
     if (!filename.toLowerCase().endsWith('.zip')) {
@@ -1052,7 +1269,7 @@ export function registerIpcHandlers(ipcMain: IpcMain, ctx: IpcContext): void {
     const { Readable } = await import('stream')
     const stream = Readable.from(fileBuffer)
 
-    const result = await extensionInstaller.installLocalExtension(stream)
+    const result = await extensionInstaller.installLocalExtension(stream, auditActor)
     if (result.success) await syncExtensions()
     return result
   })
@@ -1066,7 +1283,15 @@ export function registerIpcHandlers(ipcMain: IpcMain, ctx: IpcContext): void {
         error: 'Extension installer not initialized',
       }
     }
-    const result = await extensionInstaller.update(extensionId, version)
+    if (!(await hasCapability('extensions:install'))) {
+      return {
+        success: false,
+        extensionId,
+        version: 'unknown',
+        error: 'You are not allowed to update extensions',
+      }
+    }
+    const result = await extensionInstaller.update(extensionId, version, auditActor)
     if (result.success) {
       await syncExtensions()
     }
@@ -1120,8 +1345,17 @@ export function registerIpcHandlers(ipcMain: IpcMain, ctx: IpcContext): void {
   })
 
   ipcMain.handle('extensions-set-tool-confirmation', async (_e, extensionId: string, toolId: string, requiresConfirmation: boolean) => {
+    if (!requiresConfirmation && !(await hasCapability('tools:approve'))) {
+      throw new Error('You are not allowed to approve tool calls')
+    }
     const repo = new ToolConfirmationRepository(ensureChatDb(), defaultUserId!)
     await repo.set(extensionId, toolId, requiresConfirmation)
+    await recordAudit({
+      action: 'tool.confirmation_changed',
+      targetType: 'tool',
+      targetId: toolId,
+      details: { extensionId, requiresConfirmation },
+    })
     return { success: true }
   })
 
@@ -1137,9 +1371,49 @@ export function registerIpcHandlers(ipcMain: IpcMain, ctx: IpcContext): void {
     return { success: true }
   })
 
+  // Approved directories for extension file access
+  ipcMain.handle('extensions-get-file-roots', async (_e, extensionId: string) => {
+    const repo = new ExtensionFileRootRepository(ensureChatDb(), defaultUserId!)
+    const roots = await repo.getForExtension(extensionId)
+    return roots.map((r) => ({
+      ...r,
+      createdAt: r.createdAt.toISOString(),
+    }))
+  })
+
+  ipcMain.handle('extensions-set-file-root', async (_e, extensionId: string, path: string, access: 'read' | 'readwrite') => {
+    if (typeof path !== 'string' || !isAbsolute(path)) {
+      throw new Error('path must be an absolute path')
+    }
+    if (access !== 'read' && access !== 'readwrite') {
+      throw new Error("access must be 'read' or 'readwrite'")
+    }
+    const info = await stat(path).catch(() => null)
+    if (!info?.isDirectory()) {
+      throw new Error(`Not a directory: ${path}`)
+    }
+    const repo = new ExtensionFileRootRepository(ensureChatDb(), defaultUserId!)
+    await repo.set(extensionId, resolve(path), access)
+    return { success: true }
+  })
+
+  ipcMain.handle('extensions-remove-file-root', async (_e, extensionId: string, path: string) => {
+    if (typeof path !== 'string' || !isAbsolute(path)) {
+      throw new Error('path must be an absolute path')
+    }
+    const repo = new ExtensionFileRootRepository(ensureChatDb(), defaultUserId!)
+    await repo.remove(extensionId, resolve(path))
+    return { success: true }
+  })
+
   // Model configs (global - managed by admin)
   ipcMain.handle('model-configs-list', async (): Promise<ModelConfigDTO[]> => {
-    return getModelConfigRepo().list()
+    const configs = await getModelConfigRepo().list()
+    if (!policyService || !defaultUserId) return configs
+
+    const { allowedModelConfigIds } = await policyService.getEffectivePolicy(defaultUserId)
+    if (allowedModelConfigIds === null) return configs
+    return configs.filter((config) => allowedModelConfigIds.includes(config.id))
   })
 
   ipcMain.handle('model-configs-get', async (_event, id: string): Promise<ModelConfigDTO> => {
@@ -1153,7 +1427,7 @@ export function registerIpcHandlers(ipcMain: IpcMain, ctx: IpcContext): void {
   ipcMain.handle(
     'model-configs-create',
     async (_event, config: Omit<ModelConfigDTO, 'id' | 'createdAt' | 'updatedAt'>): Promise<ModelConfigDTO> => {
-      const { name, providerId, providerExtensionId, modelId, settingsOverride } = config
+      const { name, providerId, providerExtensionId, modelId, settingsOverride, contextLength } = config
 
       if (!name || !providerId || !providerExtensionId || !modelId) {
         throw new Error('Missing required fields: name, providerId, providerExtensionId, modelId')
@@ -1166,6 +1440,7 @@ export function registerIpcHandlers(ipcMain: IpcMain, ctx: IpcContext): void {
         providerExtensionId,
         modelId,
         settingsOverride,
+        contextLength,
       })
     }
   )
@@ -1178,13 +1453,14 @@ export function registerIpcHandlers(ipcMain: IpcMain, ctx: IpcContext): void {
       config: Partial<Omit<ModelConfigDTO, 'id' | 'createdAt' | 'updatedAt'>>
     ): Promise<ModelConfigDTO> => {
       // Extract only fields that can be updated (exclude isDefault which is now per-user)
-      const { name, providerId, providerExtensionId, modelId, settingsOverride } = config
+      const { name, providerId, providerExtensionId, modelId, settingsOverride, contextLength } = config
       const updated = await getModelConfigRepo().update(id, {
         name,
         providerId,
         providerExtensionId,
         modelId,
         settingsOverride,
+        contextLength,
       })
       if (!updated) {
         throw new Error('Model config not found')
@@ -1212,6 +1488,9 @@ export function registerIpcHandlers(ipcMain: IpcMain, ctx: IpcContext): void {
       if (!config) {
         throw new Error('Model config not found')
       }
+      if (!(await canUseModelConfig(modelConfigId))) {
+        throw new Error('You are not allowed to use this model')
+      }
     }
     await getUserSettingsRepo().setDefaultModelConfigId(modelConfigId)
     return { success: true }
@@ -1315,56 +1594,82 @@ export function registerIpcHandlers(ipcMain: IpcMain, ctx: IpcContext): void {
       nextRunAt: job.nextRunAt,
       lastRunAt: job.lastRunAt,
       enabled: job.enabled,
+      paused: job.pausedAt !== null,
       createdAt: job.createdAt,
     }))
   })
 
-  ipcMain.handle('scheduled-jobs-get', async (_event, id: string) => {
-    if (!defaultUserId) {
-      throw new Error('User not initialized')
-    }
-    const job = schedulerRepo.getByIdForUser(id, defaultUserId)
-    if (!job) {
-      throw new Error('Job not found')
-    }
+  ipcMain.handle(
+    'scheduled-jobs-get',
+    async (_event, id: string, options?: { runsLimit?: number; runsOffset?: number }) => {
+      if (!defaultUserId) {
+        throw new Error('User not initialized')
+      }
+      const job = schedulerRepo.getByIdForUser(id, defaultUserId)
+      if (!job) {
+        throw new Error('Job not found')
+      }
 
-    // Get extension name if available
-    let extensionName: string | null = null
-    if (extensionHost) {
-      const extension = extensionHost.getExtension(job.extensionId)
-      if (extension) {
-        extensionName = extension.manifest.name ?? null
+      const limit = Math.min(Math.max(options?.runsLimit ?? 20, 1), 100)
+      const offset = Math.max(options?.runsOffset ?? 0, 0)
+      const history = schedulerRepo.listRunsForUser(job.id, defaultUserId, { limit, offset })
+
+      // Get extension name if available
+      let extensionName: string | null = null
+      if (extensionHost) {
+        const extension = extensionHost.getExtension(job.extensionId)
+        if (extension) {
+          extensionName = extension.manifest.name ?? null
+        }
       }
-    }
 
-    // Parse payload
-    let payload: Record<string, unknown> | null = null
-    if (job.payloadJson) {
-      try {
-        payload = JSON.parse(job.payloadJson) as Record<string, unknown>
-      } catch {
-        // Invalid JSON, leave as null
+      // Parse payload
+      let payload: Record<string, unknown> | null = null
+      if (job.payloadJson) {
+        try {
+          payload = JSON.parse(job.payloadJson) as Record<string, unknown>
+        } catch {
+          // Invalid JSON, leave as null
+        }
       }
-    }
 
-    return {
-      id: job.id,
-      extensionId: job.extensionId,
-      jobId: job.jobId,
-      userId: job.userId ?? defaultUserId,
-      scheduleType: job.scheduleType,
-      scheduleDescription: getScheduleDescription(job.scheduleType, job.scheduleValue, job.timezone),
-      scheduleValue: job.scheduleValue,
-      timezone: job.timezone,
-      misfirePolicy: job.misfirePolicy,
-      nextRunAt: job.nextRunAt,
-      lastRunAt: job.lastRunAt,
-      enabled: job.enabled,
-      createdAt: job.createdAt,
-      payload,
-      extensionName,
-    }
-  })
+      return {
+        id: job.id,
+        extensionId: job.extensionId,
+        jobId: job.jobId,
+        userId: job.userId ?? defaultUserId,
+        scheduleType: job.scheduleType,
+        scheduleDescription: getScheduleDescription(job.scheduleType, job.scheduleValue, job.timezone),
+        scheduleValue: job.scheduleValue,
+        timezone: job.timezone,
+        misfirePolicy: job.misfirePolicy,
+        nextRunAt: job.nextRunAt,
+        lastRunAt: job.lastRunAt,
+        enabled: job.enabled,
+        paused: job.pausedAt !== null,
+        createdAt: job.createdAt,
+        payload,
+        extensionName,
+        retryAt: job.retryAt,
+        history: {
+          runs: history.runs.map((run) => ({
+            id: run.id,
+            scheduledFor: run.scheduledFor,
+            firedAt: run.firedAt,
+            delayMs: run.delayMs,
+            durationMs: run.durationMs,
+            status: run.status,
+            error: run.error,
+            attempt: run.attempt,
+            manual: run.manual,
+          })),
+          total: history.total,
+          limit,
+          offset,
+        },
+      }
+  }
+  )
 
   ipcMain.handle('scheduled-jobs-delete', async (_event, id: string): Promise<{ success: boolean }> => {
     if (!defaultUserId) {
@@ -1374,6 +1679,29 @@ export function registerIpcHandlers(ipcMain: IpcMain, ctx: IpcContext): void {
     return { success: deleted }
   })
 
+  const ensureScheduler = (): SchedulerService => {
+    if (!scheduler || !defaultUserId) {
+      throw new Error('Scheduler not initialized')
+    }
+    return scheduler
+  }
+
+  ipcMain.handle('scheduled-jobs-pause', async (_event, id: string): Promise<{ success: boolean }> => {
+    return { success: ensureScheduler().pauseJob(id, defaultUserId!) }
+  })
+
+  ipcMain.handle(
+    'scheduled-jobs-resume',
+    async (_event, id: string, options?: { misfire?: 'run_once' | 'skip' }): Promise<{ success: boolean }> => {
+      const misfire = options?.misfire === 'skip' ? 'skip' : 'run_once'
+      return { success: ensureScheduler().resumeJob(id, defaultUserId!, misfire) }
+    }
+  )
+
+  ipcMain.handle('scheduled-jobs-run-now', async (_event, id: string): Promise<{ success: boolean }> => {
+    return { success: ensureScheduler().runJobNow(id, defaultUserId!) }
+  })
+
   logger.info('IPC handlers registered')
 }
 
@@ -1,5 +1,6 @@
 import { EventEmitter } from 'node:events'
 import type {
+  ChatAttachmentDTO,
   ChatConversationDTO,
   ChatInteractionDTO,
 } from '@stina/shared'
@@ -29,7 +30,16 @@ export type ChatStreamEvent =
   | { type: 'stream-error'; error: string; queueId?: string }
   | { type: 'interaction-saved'; interaction: ChatInteractionDTO; queueId?: string }
   | { type: 'conversation-created'; conversation: ChatConversationDTO; queueId?: string }
-  | { type: 'interaction-started'; interactionId: string; conversationId: string; role: string; text: string; queueId?: string }
+  | { type: 'conversation-title-updated'; conversationId: string; title: string; queueId?: string }
+  | {
+      type: 'interaction-started'
+      interactionId: string
+      conversationId: string
+      role: string
+      text: string
+      attachments?: ChatAttachmentDTO[]
+      queueId?: string
+    }
   | { type: 'queue-update'; queue: QueueState; queueId?: string }
 
 /**
@@ -13,7 +13,16 @@ import type {
   NotificationResult,
   ScheduledJobSummaryDTO,
   ScheduledJobDetailDTO,
+  ScheduledJobHistoryOptions,
+  ScheduledJobResumeOptions,
   ServerTimeResponse,
+  TokenUsageQueryDTO,
+  TokenUsageReportDTO,
+  ChatSearchQueryDTO,
+  ChatSearchHitDTO,
+  ConversationExportFormat,
+  ChatAttachmentUploadDTO,
+  ChatConversationOverridesDTO,
 } from '@stina/shared'
 import type { ThemeTokens, ConnectionConfig } from '@stina/core'
 import type {
@@ -144,6 +153,21 @@ const electronAPI = {
     ipcRenderer.invoke('chat-archive-conversation', conversationId),
   chatMarkRead: (conversationId: string): Promise<{ success: boolean }> =>
     ipcRenderer.invoke('chat-mark-read', conversationId),
+  chatGetUsage: (query?: TokenUsageQueryDTO): Promise<TokenUsageReportDTO> =>
+    ipcRenderer.invoke('chat-usage', query),
+  chatSearch: (query: ChatSearchQueryDTO): Promise<ChatSearchHitDTO[]> =>
+    ipcRenderer.invoke('chat-search', query),
+  chatExportConversation: (conversationId: string, format: ConversationExportFormat): Promise<string> =>
+    ipcRenderer.invoke('chat-export-conversation', conversationId, format),
+  chatImportConversation: (data: unknown): Promise<ChatConversationDTO> =>
+    ipcRenderer.invoke('chat-import-conversation', data),
+  chatRevertContextSummary: (conversationId: string, interactionId: string): Promise<{ success: boolean }> =>
+    ipcRenderer.invoke('chat-revert-context-summary', conversationId, interactionId),
+  chatSetConversationOverrides: (
+    conversationId: string,
+    overrides: ChatConversationOverridesDTO
+  ): Promise<ChatConversationOverridesDTO> =>
+    ipcRenderer.invoke('chat-set-conversation-overrides', conversationId, overrides),
   chatCreateConversation: (
     id: string,
     title: string | undefined,
@@ -164,6 +188,9 @@ const electronAPI = {
       role?: QueuedMessageRole
       context?: 'conversation-start' | 'settings-update'
       sessionId?: string
+      attachments?: ChatAttachmentUploadDTO[]
+      regenerateInteractionId?: string
+      editInteractionId?: string
     }
   ): Promise<{ success: boolean; error?: string }> =>
     ipcRenderer.invoke('chat-stream-message', conversationId, message, options),
@@ -194,6 +221,13 @@ const electronAPI = {
   chatQueueReset: (sessionId?: string, conversationId?: string): Promise<{ success: boolean }> =>
     ipcRenderer.invoke('chat-queue-reset', sessionId, conversationId),
 
+  chatSwitchBranch: (
+    conversationId: string,
+    interactionId: string,
+    sessionId?: string
+  ): Promise<{ success: boolean }> =>
+    ipcRenderer.invoke('chat-switch-branch', conversationId, interactionId, sessionId),
+
   // Extensions
   getAvailableExtensions: (): Promise<ExtensionListItem[]> =>
     ipcRenderer.invoke('extensions-get-available'),
@@ -242,6 +276,12 @@ const electronAPI = {
     ipcRenderer.invoke('extensions-remove-tool-confirmation', extensionId, toolId),
   resetToolConfirmations: (extensionId: string): Promise<{ success: boolean }> =>
     ipcRenderer.invoke('extensions-reset-tool-confirmations', extensionId),
+  getFileRoots: (extensionId: string): Promise<Array<{ extensionId: string; path: string; access: 'read' | 'readwrite'; createdAt: string }>> =>
+    ipcRenderer.invoke('extensions-get-file-roots', extensionId),
+  setFileRoot: (extensionId: string, path: string, access: 'read' | 'readwrite'): Promise<{ success: boolean }> =>
+    ipcRenderer.invoke('extensions-set-file-root', extensionId, path, access),
+  removeFileRoot: (extensionId: string, path: string): Promise<{ success: boolean }> =>
+    ipcRenderer.invoke('extensions-remove-file-root', extensionId, path),
   uploadLocalExtension: (buffer: ArrayBuffer, filename: string): Promise<InstallLocalResult> =>
     ipcRenderer.invoke('extensions-upload-local', buffer, filename),
 
@@ -290,10 +330,20 @@ const electronAPI = {
   // Scheduled jobs
   scheduledJobsList: (): Promise<ScheduledJobSummaryDTO[]> =>
     ipcRenderer.invoke('scheduled-jobs-list'),
-  scheduledJobsGet: (id: string): Promise<ScheduledJobDetailDTO> =>
-    ipcRenderer.invoke('scheduled-jobs-get', id),
+  scheduledJobsGet: (
+    id: string,
+    options?: ScheduledJobHistoryOptions
+  ): Promise<ScheduledJobDetailDTO> => ipcRenderer.invoke('scheduled-jobs-get', id, options),
   scheduledJobsDelete: (id: string): Promise<{ success: boolean }> =>
     ipcRenderer.invoke('scheduled-jobs-delete', id),
+  scheduledJobsPause: (id: string): Promise<{ success: boolean }> =>
+    ipcRenderer.invoke('scheduled-jobs-pause', id),
+  scheduledJobsResume: (
+    id: string,
+    options?: ScheduledJobResumeOptions
+  ): Promise<{ success: boolean }> => ipcRenderer.invoke('scheduled-jobs-resume', id, options),
+  scheduledJobsRunNow: (id: string): Promise<{ success: boolean }> =>
+    ipcRenderer.invoke('scheduled-jobs-run-now', id),
 
   // Dev: re-register themes to pick up tokenSpec changes without full restart
   reloadThemes: (): Promise<void> => ipcRenderer.invoke('reload-themes'),
@@ -1,5 +1,6 @@
 import type { ApiClient, ChatEvent, ChatStreamEvent, ChatStreamOptions } from '@stina/ui-vue'
 import type { InstallLocalResult } from '@stina/extension-installer'
+import { USER_CAPABILITIES } from '@stina/shared'
 
 /**
  * Default user for local mode (no authentication required)
@@ -33,10 +34,33 @@ function createLocalAuthClient(): ApiClient['auth'] {
     listUsers: () => Promise.resolve([LOCAL_DEFAULT_USER]),
     updateUserRole: notSupportedError,
     deleteUser: notSupportedError,
+    listUserSessions: () => Promise.resolve([]),
+    revokeUserSession: notSupportedError,
+    getRolePolicy: notSupportedError,
+    setRolePolicy: notSupportedError,
+    getUserPolicy: notSupportedError,
+    setUserPolicy: notSupportedError,
+    deleteUserPolicy: notSupportedError,
+    listAuditEvents: notSupportedError,
     createInvitation: notSupportedError,
     listInvitations: () => Promise.resolve([]),
     validateInvitation: () => Promise.resolve({ valid: false }),
     deleteInvitation: notSupportedError,
+    listPersonalAccessTokens: () => Promise.resolve([]),
+    createPersonalAccessToken: notSupportedError,
+    revokePersonalAccessToken: notSupportedError,
+    listSessions: () => Promise.resolve([]),
+    getMyPolicy: () =>
+      Promise.resolve({ capabilities: [...USER_CAPABILITIES], allowedModelConfigIds: null }),
+    revokeSession: notSupportedError,
+    getOidcStatus: () => Promise.resolve({ enabled: false, displayName: null }),
+    startOidcLogin: notSupportedError,
+    completeOidcLogin: notSupportedError,
+    startOidcLink: notSupportedError,
+    unlinkOidc: notSupportedError,
+    getOidcConfig: () => Promise.resolve(null),
+    updateOidcConfig: notSupportedError,
+    disableOidc: notSupportedError,
   }
 }
 
@@ -72,6 +96,18 @@ export function createIpcApiClient(): ApiClient {
         api.chatSendMessage(conversationId, message),
       archiveConversation: (id: string) => api.chatArchiveConversation(id),
       markRead: (conversationId: string) => api.chatMarkRead(conversationId).then(() => {}),
+      exportConversation: (id, format) => api.chatExportConversation(id, format),
+      importConversation: (data) => api.chatImportConversation(data),
+      getUsage: (query) => api.chatGetUsage(query),
+      search: (query) => api.chatSearch(query),
+      revertContextSummary: async (conversationId: string, interactionId: string) => {
+        const result = await api.chatRevertContextSummary(conversationId, interactionId)
+        if (!result.success) {
+          throw new Error('Context summary not found')
+        }
+      },
+      setConversationOverrides: (conversationId: string, overrides) =>
+        api.chatSetConversationOverrides(conversationId, overrides),
       createConversation: (id: string, title: string | undefined, createdAt: string) =>
         api.chatCreateConversation(id, title, createdAt),
       saveInteraction: (conversationId: string, interaction) =>
@@ -83,7 +119,8 @@ export function createIpcApiClient(): ApiClient {
         message: string,
         options: ChatStreamOptions
       ): Promise<() => void> => {
-        const { queueId, role, context, sessionId, onEvent } = options
+        const { queueId, role, context, sessionId, attachments, onEvent } = options
+        const { regenerateInteractionId, editInteractionId } = options
 
         // Subscribe to stream events
         const unsubscribe = api.chatStreamSubscribe((event: ChatStreamEvent) => {
@@ -101,6 +138,9 @@ export function createIpcApiClient(): ApiClient {
             role,
             context,
             sessionId,
+            attachments,
+            regenerateInteractionId,
+            editInteractionId,
           })
         } catch (err) {
           unsubscribe()
@@ -128,6 +168,9 @@ export function createIpcApiClient(): ApiClient {
       resetQueue: (sessionId?: string, conversationId?: string) =>
         api.chatQueueReset(sessionId, conversationId),
 
+      switchBranch: (conversationId: string, interactionId: string, sessionId?: string) =>
+        api.chatSwitchBranch(conversationId, interactionId, sessionId),
+
       // Multi-window synchronization
       subscribeToConversation: (
         conversationId: string,
@@ -164,6 +207,10 @@ export function createIpcApiClient(): ApiClient {
       removeToolConfirmation: (extensionId: string, toolId: string) =>
         api.removeToolConfirmation(extensionId, toolId),
       resetToolConfirmations: (extensionId: string) => api.resetToolConfirmations(extensionId),
+      getFileRoots: (extensionId: string) => api.getFileRoots(extensionId),
+      setFileRoot: (extensionId: string, path: string, access: 'read' | 'readwrite') =>
+        api.setFileRoot(extensionId, path, access),
+      removeFileRoot: (extensionId: string, path: string) => api.removeFileRoot(extensionId, path),
       uploadLocal: async (file: File): Promise<InstallLocalResult> => {
         const buffer = await file.arrayBuffer()
         return api.uploadLocalExtension(buffer, file.name)
@@ -220,8 +267,11 @@ export function createIpcApiClient(): ApiClient {
     },
     scheduledJobs: {
       list: () => api.scheduledJobsList(),
-      get: (id: string) => api.scheduledJobsGet(id),
+      get: (id, options) => api.scheduledJobsGet(id, options),
       delete: (id: string) => api.scheduledJobsDelete(id),
+      pause: (id: string) => api.scheduledJobsPause(id),
+      resume: (id, options) => api.scheduledJobsResume(id, options),
+      runNow: (id: string) => api.scheduledJobsRunNow(id),
     },
   }
 }
@@ -0,0 +1,159 @@
+import type { Interaction, OrchestratorEvent, ToolCall } from '@stina/chat'
+
+/** Longest tool payload/result shown inline before truncating */
+const MAX_TOOL_TEXT_LENGTH = 200
+
+const useColor = process.stdout.isTTY === true && !process.env['NO_COLOR']
+
+const style = (code: string) => (text: string) =>
+  useColor ? `\x1b[${code}m${text}\x1b[0m` : text
+
+export const dim = style('2')
+export const bold = style('1')
+export const italic = style('3')
+export const cyan = style('36')
+export const yellow = style('33')
+export const red = style('31')
+export const green = style('32')
+
+/**
+ * Writes orchestrator events to the terminal.
+ *
+ * Streaming events carry the full text so far, so only the part that has not
+ * been printed yet is written.
+ */
+export class ChatRenderer {
+  private printedContent = 0
+  private printedThinking = 0
+  private inThinking = false
+  private inContent = false
+
+  constructor(private readonly out: NodeJS.WriteStream = process.stdout) {}
+
+  handle(event: OrchestratorEvent): void {
+    switch (event.type) {
+      case 'interaction-started':
+        this.resetStream()
+        break
+
+      case 'thinking-update':
+        if (!this.inThinking) {
+          this.breakLine()
+          this.out.write(dim(italic('thinking… ')))
+          this.inThinking = true
+        }
+        this.out.write(dim(italic(event.text.slice(this.printedThinking))))
+        this.printedThinking = event.text.length
+        break
+
+      case 'thinking-done':
+        if (this.inThinking) {
+          this.out.write('\n')
+          this.inThinking = false
+        }
+        break
+
+      case 'content-update':
+        if (!this.inContent) {
+          this.breakLine()
+          this.out.write(bold(cyan('stina › ')))
+          this.inContent = true
+        }
+        this.out.write(event.text.slice(this.printedContent))
+        this.printedContent = event.text.length
+        break
+
+      case 'tool-start':
+        this.breakLine()
+        this.line(yellow(`⚙ ${event.displayName ?? event.name}`) + formatPayload(event.payload))
+        break
+
+      case 'tool-complete':
+        this.line(formatToolResult(event.tool))
+        break
+
+      case 'context-compacted':
+        this.breakLine()
+        this.line(
+          dim(
+            `(summarized ${event.summarizedInteractionIds.length} earlier interactions to fit the context window)`
+          )
+        )
+        break
+
+      case 'stream-complete':
+        this.breakLine()
+        this.resetStream()
+        break
+
+      case 'stream-error':
+        this.breakLine()
+        this.line(red(`Error: ${event.error.message}`))
+        this.resetStream()
+        break
+    }
+  }
+
+  /**
+   * Print a saved interaction, e.g. when loading a conversation.
+   */
+  printInteraction(interaction: Interaction): void {
+    for (const message of interaction.messages) {
+      switch (message.type) {
+        case 'user':
+          this.line(`${bold('you › ')}${message.text}`)
+          break
+        case 'stina':
+          this.line(`${bold(cyan('stina › '))}${message.text}`)
+          break
+        case 'tools':
+          for (const tool of message.tools) {
+            this.line(yellow(`⚙ ${tool.displayName ?? tool.name}`))
+          }
+          break
+      }
+    }
+  }
+
+  line(text = ''): void {
+    this.out.write(`${text}\n`)
+  }
+
+  /**
+   * End any partially written streaming line so the next output starts clean.
+   */
+  breakLine(): void {
+    if (this.inThinking || this.inContent) {
+      this.out.write('\n')
+      this.inThinking = false
+      this.inContent = false
+    }
+  }
+
+  private resetStream(): void {
+    this.printedContent = 0
+    this.printedThinking = 0
+    this.inThinking = false
+    this.inContent = false
+  }
+}
+
+function truncate(text: string): string {
+  const singleLine = text.replace(/\s+/g, ' ').trim()
+  return singleLine.length > MAX_TOOL_TEXT_LENGTH
+    ? `${singleLine.slice(0, MAX_TOOL_TEXT_LENGTH - 1)}…`
+    : singleLine
+}
+
+function formatPayload(payload?: string): string {
+  if (!payload || payload === '{}') return ''
+  return dim(` ${truncate(payload)}`)
+}
+
+function formatToolResult(tool: ToolCall): string {
+  if (tool.confirmationStatus === 'denied') {
+    const reason = tool.confirmationDenialReason ? `: ${tool.confirmationDenialReason}` : ''
+    return red(`  ✗ denied${reason}`)
+  }
+  return dim(`  → ${truncate(tool.result)}`)
+}
@@ -0,0 +1,169 @@
+import type { DB } from '@stina/adapters-node'
+import { createNodeExtensionRuntime } from '@stina/adapters-node'
+import { registerBuiltinTools, createReminderScheduler } from '@stina/builtin-tools'
+import {
+  ChatOrchestrator,
+  MemoryService,
+  ReminderService,
+  createProviderEmbedder,
+  providerRegistry,
+  toolRegistry,
+  registerOpenAICompatibleProvider,
+} from '@stina/chat'
+import {
+  ConversationRepository,
+  ExtensionFileRootRepository,
+  MemoryRepository,
+  ReminderRepository,
+  TaskRepository,
+  ToolConfirmationRepository,
+  createModelConfigProvider,
+  getAppSettingsStore,
+  initAppSettingsStore,
+} from '@stina/chat/db'
+import type { ChatDb } from '@stina/chat/db'
+import { APP_NAMESPACE } from '@stina/core'
+import { SchedulerService } from '@stina/scheduler'
+import type { Logger } from '@stina/core'
+import { resolveLocalizedString } from '@stina/extension-api'
+import type { UserProfile } from '@stina/extension-api'
+
+export interface ChatRuntimeOptions {
+  db: DB
+  userId: string
+  logger: Logger
+  stinaVersion: string
+}
+
+/**
+ * Everything the terminal chat needs to talk to providers directly
+ */
+export interface ChatRuntime {
+  orchestrator: ChatOrchestrator
+  repository: ConversationRepository
+  /** Whether a default model is configured (greetings are skipped otherwise) */
+  hasDefaultModel(): Promise<boolean>
+  /** Close extension storage and secrets */
+  close(): void
+}
+
+/**
+ * Load built-in tools and enabled extensions, then create a ChatOrchestrator
+ * for the local user against the local database.
+ */
+export async function createChatRuntime(options: ChatRuntimeOptions): Promise<ChatRuntime> {
+  const { db, userId, logger } = options
+
+  // adapters-node DB and ChatDb are structurally compatible but have different generic schema types
+  const chatDb = db as unknown as ChatDb
+
+  await initAppSettingsStore(chatDb, userId)
+  const settingsStore = getAppSettingsStore()
+
+  const repository = new ConversationRepository(chatDb, userId)
+  const modelConfigProvider = createModelConfigProvider(chatDb, userId)
+
+  // Long-term memory, embedded with the provider of the default model
+  const memory = new MemoryService(
+    new MemoryRepository(chatDb, userId),
+    createProviderEmbedder(providerRegistry, modelConfigProvider)
+  )
+
+  // The terminal only stores reminder jobs; they are delivered by the desktop
+  // app or server, whichever runs the scheduler against this database
+  const reminderJobs = new SchedulerService({ db, onFire: () => false, logger })
+  const reminders = new ReminderService(
+    new ReminderRepository(chatDb, userId),
+    createReminderScheduler(reminderJobs, userId)
+  )
+  const tasks = new TaskRepository(chatDb, userId)
+
+  registerBuiltinTools(toolRegistry, {
+    getTimezone: async () => getAppSettingsStore()?.get<string>(APP_NAMESPACE, 'timezone'),
+    getMemoryService: (memoryUserId) => (memoryUserId === userId ? memory : undefined),
+    getTaskRepository: (taskUserId) => (taskUserId === userId ? tasks : undefined),
+    getReminderService: (reminderUserId) => (reminderUserId === userId ? reminders : undefined),
+    scheduler: reminderJobs,
+  })
+
+  registerOpenAICompatibleProvider(providerRegistry, process.env)
+
+  const runtime = await createNodeExtensionRuntime({
+    logger,
+    stinaVersion: options.stinaVersion,
+    platform: 'tui',
+    user: {
+      getProfile: async (): Promise<UserProfile> => {
+        const store = getAppSettingsStore()
+        if (!store) return {}
+        return {
+          firstName: store.get<string>(APP_NAMESPACE, 'firstName'),
+          nickname: store.get<string>(APP_NAMESPACE, 'nickname'),
+          language: store.get<string>(APP_NAMESPACE, 'language'),
+          timezone: store.get<string>(APP_NAMESPACE, 'timezone'),
+        }
+      },
+      // The terminal runs as the local default user only
+      listIds: async () => [userId],
+    },
+    fileRoots: {
+      list: async (extensionId, rootUserId) =>
+        new ExtensionFileRootRepository(chatDb, rootUserId).getForExtension(extensionId),
+    },
+    callbacks: {
+      onProviderRegistered: (provider) => {
+        try {
+          providerRegistry.register(provider)
+        } catch (error) {
+          logger.warn('Failed to register extension provider', {
+            id: provider.id,
+            error: String(error),
+          })
+        }
+      },
+      onProviderUnregistered: (providerId) => providerRegistry.unregister(providerId),
+      onToolRegistered: (tool) => {
+        try {
+          toolRegistry.register(tool)
+        } catch (error) {
+          logger.warn('Failed to register extension tool', { id: tool.id, error: String(error) })
+        }
+      },
+      onToolUnregistered: (toolId) => toolRegistry.unregister(toolId),
+    },
+  })
+
+  const userLanguage = settingsStore?.get<string>(APP_NAMESPACE, 'language') ?? 'en'
+
+  const orchestrator = new ChatOrchestrator(
+    {
+      userId,
+      repository,
+      providerRegistry,
+      modelConfigProvider,
+      toolRegistry,
+      memory,
+      settingsStore,
+      userLanguage,
+      getToolDisplayName: (toolId) => {
+        const tool = toolRegistry.get(toolId)
+        if (!tool) return undefined
+        return resolveLocalizedString(tool.name, userLanguage, 'en')
+      },
+      getToolConfirmationOverride: async (extensionId, toolId) =>
+        new ToolConfirmationRepository(chatDb, userId).get(extensionId, toolId),
+    },
+    { pageSize: 10 }
+  )
+
+  return {
+    orchestrator,
+    repository,
+    hasDefaultModel: async () => (await modelConfigProvider.getDefault()) !== null,
+    close: () => {
+      orchestrator.destroy()
+      runtime.storageExecutor.close()
+      runtime.secretsManager.close()
+    },
+  }
+}
@@ -0,0 +1,338 @@
+import { createInterface, emitKeypressEvents } from 'node:readline'
+import type { Interface } from 'node:readline'
+import type { OrchestratorEvent } from '@stina/chat'
+import { ChatHistoryReader } from '@stina/chat/db'
+import type { ChatDb } from '@stina/chat/db'
+import type { ChatConversationSummary } from '@stina/extension-api'
+import type { ChatRuntime } from './runtime.js'
+import { ChatRenderer, bold, dim, green, red, yellow } from './renderer.js'
+
+/** Number of conversations shown by /list */
+const CONVERSATION_LIST_LIMIT = 20
+
+const HELP_TEXT = `Commands:
+  /new              Start a new conversation
+  /list             List recent conversations
+  /switch <n|id>    Switch to a conversation from /list, or by ID
+  /queue            Show queued messages
+  /abort            Stop the current response
+  /quit             Exit
+
+Keys:
+  Esc               Stop the current response
+  Ctrl+X            Clear queued messages
+  Ctrl+C            Stop the current response, or exit when idle
+
+Messages sent while Stina is responding are queued.`
+
+export interface ChatSessionOptions {
+  db: ChatDb
+  userId: string
+  runtime: ChatRuntime
+  /** Conversation to open instead of the latest one */
+  conversationId?: string
+  /** Start a new conversation instead of opening the latest one */
+  newConversation?: boolean
+}
+
+type PendingConfirmation = Extract<OrchestratorEvent, { type: 'tool-confirmation-pending' }>
+
+/**
+ * Interactive terminal chat driving a ChatOrchestrator.
+ * Resolves when the user exits.
+ */
+export async function runChatSession(options: ChatSessionOptions): Promise<void> {
+  const session = new TerminalChatSession(options)
+  await session.start()
+  await session.closed
+}
+
+class TerminalChatSession {
+  readonly closed: Promise<void>
+
+  private readonly rl: Interface
+  private readonly renderer = new ChatRenderer()
+  private readonly historyReader: ChatHistoryReader
+  private readonly confirmations: PendingConfirmation[] = []
+  private lastListing: ChatConversationSummary[] = []
+  private resolveClosed!: () => void
+
+  constructor(private readonly options: ChatSessionOptions) {
+    this.historyReader = new ChatHistoryReader(options.db)
+    this.closed = new Promise((resolve) => {
+      this.resolveClosed = resolve
+    })
+
+    this.rl = createInterface({ input: process.stdin, output: process.stdout, terminal: true })
+    this.rl.setPrompt(bold('you › '))
+  }
+
+  private get orchestrator() {
+    return this.options.runtime.orchestrator
+  }
+
+  async start(): Promise<void> {
+    this.orchestrator.on('event', (event) => this.handleEvent(event))
+
+    emitKeypressEvents(process.stdin, this.rl)
+    process.stdin.on('keypress', this.handleKeypress)
+    this.rl.on('SIGINT', () => this.handleInterrupt())
+    this.rl.on('line', (line) => void this.handleLine(line))
+    this.rl.on('close', () => this.resolveClosed())
+
+    this.renderer.line(dim('Type /help for commands.'))
+
+    if (this.options.conversationId) {
+      await this.orchestrator.loadConversation(this.options.conversationId)
+      this.printHistory()
+    } else if (!this.options.newConversation && (await this.orchestrator.loadLatestConversation())) {
+      this.printHistory()
+    } else {
+      await this.startNewConversation()
+    }
+
+    this.prompt()
+  }
+
+  private handleEvent(event: OrchestratorEvent): void {
+    this.renderer.handle(event)
+
+    switch (event.type) {
+      case 'tool-confirmation-pending':
+        this.confirmations.push(event)
+        if (this.confirmations.length === 1) {
+          this.askConfirmation()
+        }
+        break
+
+      case 'queue-update':
+        if (!event.queue.isProcessing) {
+          this.prompt()
+        }
+        break
+    }
+  }
+
+  private async handleLine(line: string): Promise<void> {
+    const text = line.trim()
+
+    if (this.confirmations.length > 0) {
+      this.answerConfirmation(text)
+      return
+    }
+
+    if (!text) {
+      this.prompt()
+      return
+    }
+
+    if (text.startsWith('/')) {
+      try {
+        await this.runCommand(text)
+      } catch (error) {
+        this.renderer.line(red(error instanceof Error ? error.message : String(error)))
+      }
+      this.prompt()
+      return
+    }
+
+    if (this.isBusy()) {
+      this.renderer.line(dim('(queued)'))
+    }
+    void this.orchestrator.enqueueMessage(text, 'user')
+  }
+
+  private async runCommand(input: string): Promise<void> {
+    const [command = '', ...args] = input.slice(1).split(/\s+/)
+
+    switch (command) {
+      case 'help':
+        this.renderer.line(HELP_TEXT)
+        break
+
+      case 'new':
+        await this.startNewConversation()
+        break
+
+      case 'list':
+        await this.listConversations()
+        break
+
+      case 'switch':
+        await this.switchConversation(args[0])
+        break
+
+      case 'queue': {
+        const { queued } = this.orchestrator.getQueueState()
+        if (queued.length === 0) {
+          this.renderer.line(dim('Queue is empty.'))
+        }
+        queued.forEach((item, index) => this.renderer.line(`${index + 1}. ${item.preview}`))
+        break
+      }
+
+      case 'abort':
+        this.abort()
+        break
+
+      case 'quit':
+      case 'exit':
+        this.rl.close()
+        break
+
+      default:
+        this.renderer.line(red(`Unknown command: /${command}. Type /help for commands.`))
+    }
+  }
+
+  private async startNewConversation(): Promise<void> {
+    this.orchestrator.resetConversation()
+    this.renderer.line(dim('New conversation.'))
+
+    if (!(await this.options.runtime.hasDefaultModel())) {
+      this.renderer.line(
+        yellow('No default model is configured. Set one up in the Stina app before chatting.')
+      )
+      return
+    }
+
+    void this.orchestrator.enqueueMessage('', 'instruction', undefined, 'conversation-start')
+  }
+
+  private async listConversations(): Promise<void> {
+    this.lastListing = await this.historyReader.listConversations({
+      userId: this.options.userId,
+      limit: CONVERSATION_LIST_LIMIT,
+    })
+
+    if (this.lastListing.length === 0) {
+      this.renderer.line(dim('No conversations yet.'))
+      return
+    }
+
+    const currentId = this.orchestrator.conversation?.id
+    this.lastListing.forEach((conversation, index) => {
+      const marker = conversation.id === currentId ? green('*') : ' '
+      const title = conversation.title ?? dim('(untitled)')
+      const when = conversation.lastMessageAt ?? conversation.createdAt
+      this.renderer.line(
+        `${marker} ${index + 1}. ${title} ${dim(`${formatDate(when)} · ${conversation.id}`)}`
+      )
+    })
+  }
+
+  private async switchConversation(target?: string): Promise<void> {
+    if (!target) {
+      throw new Error('Usage: /switch <number|id>')
+    }
+    if (this.isBusy()) {
+      throw new Error('Wait for the current response to finish, or /abort it first.')
+    }
+
+    const index = Number(target)
+    const id = Number.isInteger(index) ? this.lastListing[index - 1]?.id : target
+    if (!id) {
+      throw new Error(`No conversation numbered ${target}. Run /list first.`)
+    }
+
+    await this.orchestrator.loadConversation(id)
+    this.printHistory()
+  }
+
+  /**
+   * Print the loaded interactions, oldest first.
+   */
+  private printHistory(): void {
+    const { conversation, loadedInteractions, totalInteractionsCount } = this.orchestrator.getState()
+    if (!conversation) return
+
+    this.renderer.line(dim(`Conversation ${conversation.title ?? conversation.id}`))
+    if (totalInteractionsCount > loadedInteractions.length) {
+      this.renderer.line(dim(`(${totalInteractionsCount - loadedInteractions.length} earlier interactions not shown)`))
+    }
+    for (const interaction of [...loadedInteractions].reverse()) {
+      this.renderer.printInteraction(interaction)
+    }
+  }
+
+  private askConfirmation(): void {
+    const pending = this.confirmations[0]
+    if (!pending) return
+
+    this.renderer.breakLine()
+    this.renderer.line(yellow(`${pending.toolDisplayName ?? pending.toolCallName}: ${pending.confirmationPrompt}`))
+    this.rl.setPrompt(bold('Allow? [y/N] (or type a reason to deny) '))
+    this.rl.prompt()
+  }
+
+  private answerConfirmation(answer: string): void {
+    const pending = this.confirmations.shift()
+    if (!pending) return
+
+    const normalized = answer.toLowerCase()
+    const approved = normalized === 'y' || normalized === 'yes'
+    const denialReason =
+      approved || normalized === '' || normalized === 'n' || normalized === 'no' ? undefined : answer
+
+    this.orchestrator.resolveToolConfirmation(pending.toolCallName, { approved, denialReason })
+    this.rl.setPrompt(bold('you › '))
+
+    if (this.confirmations.length > 0) {
+      this.askConfirmation()
+    }
+  }
+
+  private readonly handleKeypress = (_input: string | undefined, key?: { name?: string; ctrl?: boolean }): void => {
+    if (!key) return
+
+    if (key.name === 'escape' && this.isBusy()) {
+      this.abort()
+      this.renderer.breakLine()
+      this.renderer.line(dim('(stopped)'))
+    } else if (key.ctrl && key.name === 'x') {
+      const { queued } = this.orchestrator.getQueueState()
+      if (queued.length > 0) {
+        this.orchestrator.clearQueue()
+        this.renderer.breakLine()
+        this.renderer.line(dim(`(cleared ${queued.length} queued messages)`))
+      }
+    }
+  }
+
+  private handleInterrupt(): void {
+    if (this.isBusy()) {
+      this.abort({ continueQueue: false })
+      this.orchestrator.clearQueue()
+      this.renderer.breakLine()
+      this.renderer.line(dim('(stopped, press Ctrl+C again to exit)'))
+      return
+    }
+    this.rl.close()
+  }
+
+  /**
+   * Abort the current response. Pending confirmations are rejected by the
+   * orchestrator, so stop asking for them.
+   */
+  private abort(options?: { continueQueue?: boolean }): void {
+    this.orchestrator.abort(options)
+    if (this.confirmations.length > 0) {
+      this.confirmations.length = 0
+      this.rl.setPrompt(bold('you › '))
+    }
+  }
+
+  private isBusy(): boolean {
+    return this.orchestrator.getQueueState().isProcessing
+  }
+
+  private prompt(): void {
+    if (this.confirmations.length > 0 || this.isBusy()) return
+    this.rl.prompt()
+  }
+}
+
+function formatDate(iso: string): string {
+  const date = new Date(iso)
+  return Number.isNaN(date.getTime()) ? iso : date.toLocaleString()
+}
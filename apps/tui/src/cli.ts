@@ -1,8 +1,21 @@
+import { writeFile } from 'node:fs/promises'
 import { Command } from 'commander'
 import { getGreeting, themeRegistry, ExtensionRegistry } from '@stina/core'
-import { builtinExtensions, getExtensionsPath } from '@stina/adapters-node'
+import {
+  builtinExtensions,
+  getExtensionsPath,
+  getDatabase,
+  createConsoleLogger,
+  getLogLevelFromEnv,
+} from '@stina/adapters-node'
+import { exportConversation, isConversationExportFormat } from '@stina/chat'
+import { ConversationRepository, UserSettingsRepository } from '@stina/chat/db'
+import type { ChatDb } from '@stina/chat/db'
 import { ExtensionInstaller } from '@stina/extension-installer'
 import { t } from '@stina/i18n'
+import { createChatRuntime } from './chat/runtime.js'
+import type { ChatRuntime } from './chat/runtime.js'
+import { runChatSession } from './chat/session.js'
 
 // App version
 const STINA_VERSION = '0.5.0'
@@ -62,6 +75,87 @@ export function createCli(): Command {
       }
     })
 
+  // Chat command
+  const chat = program
+    .command('chat')
+    .description('Chat with Stina in the terminal')
+    .option('-c, --conversation <id>', 'Open a specific conversation')
+    .option('-n, --new', 'Start a new conversation')
+    .action(async (options: { conversation?: string; new?: boolean }) => {
+      const userId = process.env['STINA_SYSTEM_USER_ID']
+      if (!userId) {
+        console.error('Error starting chat: no local user available')
+        process.exit(1)
+      }
+
+      // Only warnings and errors, so log lines don't break up the conversation
+      const logger = createConsoleLogger(process.env['LOG_LEVEL'] ? getLogLevelFromEnv() : 'warn')
+      const db = getDatabase()
+
+      let runtime: ChatRuntime | undefined
+      try {
+        runtime = await createChatRuntime({ db, userId, logger, stinaVersion: STINA_VERSION })
+        await runChatSession({
+          // adapters-node DB and ChatDb are structurally compatible but have different generic schema types
+          db: db as unknown as ChatDb,
+          userId,
+          runtime,
+          conversationId: options.conversation,
+          newConversation: options.new,
+        })
+        runtime.close()
+      } catch (error) {
+        console.error('Error in chat:', error instanceof Error ? error.message : error)
+        runtime?.close()
+        process.exit(1)
+      }
+
+      process.exit(0)
+    })
+
+  // chat export <id>
+  chat
+    .command('export <conversationId>')
+    .description('Export a conversation as Markdown, JSON or HTML')
+    .option('-f, --format <format>', 'Output format (markdown, json, html)', 'markdown')
+    .option('-o, --output <file>', 'Write to a file instead of stdout')
+    .action(async (conversationId: string, options: { format: string; output?: string }) => {
+      if (!isConversationExportFormat(options.format)) {
+        console.error(`Unknown format: ${options.format}. Use markdown, json or html.`)
+        process.exit(1)
+      }
+
+      const userId = process.env['STINA_SYSTEM_USER_ID']
+      if (!userId) {
+        console.error('Error exporting conversation: no local user available')
+        process.exit(1)
+      }
+
+      try {
+        // adapters-node DB and ChatDb are structurally compatible but have different generic schema types
+        const db = getDatabase() as unknown as ChatDb
+        const repository = new ConversationRepository(db, userId)
+        const conversation = await repository.getConversationWithBranches(conversationId)
+        if (!conversation) {
+          console.error(`Conversation not found: ${conversationId}`)
+          process.exit(1)
+        }
+
+        const timeZone = (await new UserSettingsRepository(db, userId).get()).timezone ?? 'UTC'
+        const file = exportConversation(conversation, options.format, { timeZone })
+
+        if (options.output) {
+          await writeFile(options.output, file.content, 'utf8')
+          console.error(`✓ Exported to ${options.output}`)
+        } else {
+          process.stdout.write(file.content)
+        }
+      } catch (error) {
+        console.error('Error exporting conversation:', error instanceof Error ? error.message : error)
+        process.exit(1)
+      }
+    })
+
   // Extension command group
   const ext = program
     .command('ext')
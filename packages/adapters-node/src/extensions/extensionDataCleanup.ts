@@ -21,6 +21,7 @@ function isValidExtensionTableName(tableName: string): boolean {
  * This includes:
  * 1. Tables with prefix `ext_{sanitizedExtensionId}_` (where extensionId has dashes replaced with underscores)
  * 2. Model configs where `provider_extension_id` matches the extensionId
+ * 3. Directories users have approved for the extension's file access
  */
 export async function deleteExtensionData(
   db: Database,
@@ -98,6 +99,9 @@ export async function deleteExtensionData(
     logger.info('Deleted model configs for extension', { extensionId, count: modelConfigsDeleted })
   }
 
+  // Revoke approved file roots so a reinstall starts without access
+  db.prepare(`DELETE FROM extension_file_roots WHERE extension_id = ?`).run(extensionId)
+
   return {
     tablesDropped,
     modelConfigsDeleted
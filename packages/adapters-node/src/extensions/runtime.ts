@@ -4,6 +4,10 @@ import type {
   PanelDefinition,
   SchedulerJobRequest,
   ChatInstructionMessage,
+  ChatHistoryQuery,
+  ChatConversationSummary,
+  ChatHistoryInteraction,
+  ChatCurrentConversation,
   UserProfile,
 } from '@stina/extension-api'
 import type {
@@ -13,7 +17,11 @@ import type {
   ExtensionTheme,
   Logger,
 } from '@stina/core'
-import type { InstalledExtension, Platform } from '@stina/extension-installer'
+import type {
+  ExtensionAuditEvent,
+  InstalledExtension,
+  Platform,
+} from '@stina/extension-installer'
 import { ExtensionInstaller } from '@stina/extension-installer'
 import {
   NodeExtensionHost,
@@ -23,6 +31,7 @@ import {
   deriveEncryptionKey,
   type AdaptedTool,
   type ChatAIProvider,
+  type FileRootsProvider,
 } from '@stina/extension-host'
 import { getExtensionsPath } from '../paths.js'
 import { createStorageExecutor } from './storageExecutor.js'
@@ -64,14 +73,28 @@ export interface NodeExtensionRuntimeOptions {
   }
   chat?: {
     appendInstruction: (extensionId: string, message: ChatInstructionMessage) => Promise<void>
+    listConversations: (extensionId: string, query: ChatHistoryQuery) => Promise<ChatConversationSummary[]>
+    getInteractions: (
+      extensionId: string,
+      conversationId: string,
+      query: ChatHistoryQuery
+    ) => Promise<ChatHistoryInteraction[]>
+    getCurrentConversation: (
+      extensionId: string,
+      query: ChatHistoryQuery
+    ) => Promise<ChatCurrentConversation | null>
   }
   user?: {
     getProfile: (extensionId: string) => Promise<UserProfile>
     listIds: () => Promise<string[]>
   }
+  /** Directories users have approved for extension file access */
+  fileRoots?: FileRootsProvider
   callbacks?: NodeExtensionRuntimeCallbacks
   /** Callback to delete extension data from the database when uninstalling */
   onDeleteExtensionData?: (extensionId: string) => Promise<void>
+  /** Callback to record installs, updates and uninstalls in an audit log */
+  onAuditEvent?: (event: ExtensionAuditEvent) => Promise<void> | void
 }
 
 export interface NodeExtensionRuntime {
@@ -167,6 +190,7 @@ export async function createNodeExtensionRuntime(
     platform: options.platform,
     logger: proxyLogger,
     onDeleteExtensionData: options.onDeleteExtensionData,
+    onAuditEvent: options.onAuditEvent,
   })
 
   // Migrate extension data from old paths to _data/ directory
@@ -222,6 +246,7 @@ export async function createNodeExtensionRuntime(
     scheduler: options.scheduler,
     chat: options.chat,
     user: options.user,
+    fileRoots: options.fileRoots,
     storageCallbacks: storageExecutor,
     secretsCallbacks: {
       // Extension-scoped
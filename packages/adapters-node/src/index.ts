@@ -39,4 +39,11 @@ export type { LogLevel } from './logging/consoleLogger.js'
 export { EncryptedSettingsStore, deriveKey } from './settings/encryptedSettingsStore.js'
 
 // Paths
-export { getAppDataDir, getDbPath, getExtensionsPath, getConfigPath, getLogsPath } from './paths.js'
+export {
+  getAppDataDir,
+  getDbPath,
+  getExtensionsPath,
+  getConfigPath,
+  getLogsPath,
+  getAttachmentsPath,
+} from './paths.js'
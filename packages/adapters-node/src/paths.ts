@@ -45,3 +45,10 @@ export function getConfigPath(): string {
 export function getLogsPath(): string {
   return path.join(getAppDataDir(), 'logs')
 }
+
+/**
+ * Get directory for message attachments (images and files sent in chat)
+ */
+export function getAttachmentsPath(): string {
+  return path.join(getAppDataDir(), 'attachments')
+}
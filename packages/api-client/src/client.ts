@@ -11,7 +11,24 @@ import type {
   QuickCommandDTO,
   ScheduledJobSummaryDTO,
   ScheduledJobDetailDTO,
+  ScheduledJobHistoryOptions,
+  ScheduledJobResumeOptions,
   ServerTimeResponse,
+  TokenUsageQueryDTO,
+  TokenUsageReportDTO,
+  ChatSearchQueryDTO,
+  ChatSearchHitDTO,
+  ConversationExportFormat,
+  ChatAttachmentUploadDTO,
+  ChatConversationOverridesDTO,
+  PersonalAccessTokenDTO,
+  CreatedPersonalAccessTokenDTO,
+  PersonalAccessTokenScope,
+  SessionDTO,
+  CapabilityPolicyDTO,
+  EffectivePolicyDTO,
+  AuditEventQueryDTO,
+  AuditEventListDTO,
 } from '@stina/shared'
 import type { ThemeTokens } from '@stina/core'
 import type { ModelInfo, ToolResult, ActionResult } from '@stina/extension-api'
@@ -33,6 +50,7 @@ import type {
   ActionInfo,
   ExtensionToolInfo,
   ToolConfirmationOverride,
+  ExtensionFileRoot,
   User,
   DeviceInfo,
   Invitation,
@@ -40,6 +58,9 @@ import type {
   RegistrationOptionsResponse,
   AuthResponse,
   InvitationValidation,
+  OidcStatus,
+  OidcConfig,
+  OidcConfigInput,
   ChatStreamEvent,
   ChatStreamOptions,
 } from './types.js'
@@ -55,6 +76,36 @@ function getAuthHeaders(opts: ApiClientOptions): HeadersInit {
   return {}
 }
 
+/**
+ * Build the query string for token usage requests.
+ */
+function buildUsageQuery(query?: TokenUsageQueryDTO): string {
+  const params = new URLSearchParams()
+  if (query?.from) params.append('from', query.from)
+  if (query?.to) params.append('to', query.to)
+  if (query?.timezone) params.append('timezone', query.timezone)
+  return params.toString() ? `?${params}` : ''
+}
+
+/**
+ * Build a multipart chat stream request body from the message fields and files.
+ * Fields come first so the server has them before reading the files.
+ */
+function createChatStreamFormData(
+  fields: Record<string, string | null | undefined>,
+  attachments: ChatAttachmentUploadDTO[]
+): FormData {
+  const form = new FormData()
+  for (const [name, value] of Object.entries(fields)) {
+    if (value !== null && value !== undefined) form.append(name, value)
+  }
+  for (const attachment of attachments) {
+    const blob = new Blob([attachment.data], { type: attachment.mimeType })
+    form.append('files', blob, attachment.name)
+  }
+  return form
+}
+
 /**
  * Dispatch a custom event for admin data changes.
  */
@@ -222,6 +273,114 @@ export function createHttpApiClient(options: ApiClientOptions): ApiClient {
         return result
       },
 
+      async listUserSessions(userId: string): Promise<SessionDTO[]> {
+        const response = await fetch(
+          `${API_BASE}/auth/users/${encodeURIComponent(userId)}/sessions`,
+          { headers: getAuthHeaders(options) }
+        )
+        if (!response.ok) {
+          throw new Error(`Failed to list sessions: ${response.statusText}`)
+        }
+        return response.json()
+      },
+
+      async revokeUserSession(userId: string, sessionId: string): Promise<{ success: boolean }> {
+        const path = `/auth/users/${encodeURIComponent(userId)}/sessions`
+        const response = await fetch(`${API_BASE}${path}/${encodeURIComponent(sessionId)}`, {
+          method: 'DELETE',
+          headers: getAuthHeaders(options),
+        })
+        if (!response.ok) {
+          throw new Error(`Failed to revoke session: ${response.statusText}`)
+        }
+        return response.json()
+      },
+
+      async getRolePolicy(role: 'admin' | 'user'): Promise<CapabilityPolicyDTO> {
+        const response = await fetch(`${API_BASE}/auth/policies/roles/${role}`, {
+          headers: getAuthHeaders(options),
+        })
+        if (!response.ok) {
+          throw new Error(`Failed to get role policy: ${response.statusText}`)
+        }
+        return response.json()
+      },
+
+      async setRolePolicy(
+        role: 'admin' | 'user',
+        policy: CapabilityPolicyDTO
+      ): Promise<CapabilityPolicyDTO> {
+        const response = await fetch(`${API_BASE}/auth/policies/roles/${role}`, {
+          method: 'PUT',
+          headers: { 'Content-Type': 'application/json', ...getAuthHeaders(options) },
+          body: JSON.stringify(policy),
+        })
+        if (!response.ok) {
+          const error = await response.json().catch(() => ({}))
+          throw new Error(error.error || `Failed to update role policy: ${response.statusText}`)
+        }
+        return response.json()
+      },
+
+      async getUserPolicy(
+        userId: string
+      ): Promise<{ policy: CapabilityPolicyDTO; effective: EffectivePolicyDTO }> {
+        const response = await fetch(
+          `${API_BASE}/auth/users/${encodeURIComponent(userId)}/policy`,
+          { headers: getAuthHeaders(options) }
+        )
+        if (!response.ok) {
+          throw new Error(`Failed to get user policy: ${response.statusText}`)
+        }
+        return response.json()
+      },
+
+      async setUserPolicy(
+        userId: string,
+        policy: CapabilityPolicyDTO
+      ): Promise<CapabilityPolicyDTO> {
+        const response = await fetch(
+          `${API_BASE}/auth/users/${encodeURIComponent(userId)}/policy`,
+          {
+            method: 'PUT',
+            headers: { 'Content-Type': 'application/json', ...getAuthHeaders(options) },
+            body: JSON.stringify(policy),
+          }
+        )
+        if (!response.ok) {
+          const error = await response.json().catch(() => ({}))
+          throw new Error(error.error || `Failed to update user policy: ${response.statusText}`)
+        }
+        return response.json()
+      },
+
+      async deleteUserPolicy(userId: string): Promise<{ success: boolean }> {
+        const response = await fetch(
+          `${API_BASE}/auth/users/${encodeURIComponent(userId)}/policy`,
+          { method: 'DELETE', headers: getAuthHeaders(options) }
+        )
+        if (!response.ok) {
+          throw new Error(`Failed to reset user policy: ${response.statusText}`)
+        }
+        return response.json()
+      },
+
+      async listAuditEvents(query?: AuditEventQueryDTO): Promise<AuditEventListDTO> {
+        const params = new URLSearchParams()
+        for (const [key, value] of Object.entries(query ?? {})) {
+          if (value !== undefined && value !== '') params.append(key, String(value))
+        }
+        const search = params.toString() ? `?${params}` : ''
+
+        const response = await fetch(`${API_BASE}/admin/audit${search}`, {
+          headers: getAuthHeaders(options),
+        })
+        if (!response.ok) {
+          throw new Error(`Failed to list audit events: ${response.statusText}`)
+        }
+        return response.json()
+      },
+
       async createInvitation(
         username: string,
         role?: 'admin' | 'user'
@@ -274,6 +433,171 @@ export function createHttpApiClient(options: ApiClientOptions): ApiClient {
         dispatchAdminEvent('invitations-changed')
         return result
       },
+
+      async listPersonalAccessTokens(): Promise<PersonalAccessTokenDTO[]> {
+        const response = await fetch(`${API_BASE}/auth/tokens`, {
+          headers: getAuthHeaders(options),
+        })
+        if (!response.ok) {
+          throw new Error(`Failed to list personal access tokens: ${response.statusText}`)
+        }
+        return response.json()
+      },
+
+      async createPersonalAccessToken(
+        name: string,
+        scopes: PersonalAccessTokenScope[],
+        expiresInDays?: number
+      ): Promise<CreatedPersonalAccessTokenDTO> {
+        const response = await fetch(`${API_BASE}/auth/tokens`, {
+          method: 'POST',
+          headers: { 'Content-Type': 'application/json', ...getAuthHeaders(options) },
+          body: JSON.stringify({ name, scopes, expiresInDays }),
+        })
+        if (!response.ok) {
+          const error = await response.json().catch(() => ({}))
+          throw new Error(
+            error.error || `Failed to create personal access token: ${response.statusText}`
+          )
+        }
+        return response.json()
+      },
+
+      async revokePersonalAccessToken(id: string): Promise<{ success: boolean }> {
+        const response = await fetch(`${API_BASE}/auth/tokens/${encodeURIComponent(id)}`, {
+          method: 'DELETE',
+          headers: getAuthHeaders(options),
+        })
+        if (!response.ok) {
+          throw new Error(`Failed to revoke personal access token: ${response.statusText}`)
+        }
+        return response.json()
+      },
+
+      async getMyPolicy(): Promise<EffectivePolicyDTO> {
+        const response = await fetch(`${API_BASE}/auth/me/policy`, {
+          headers: getAuthHeaders(options),
+        })
+        if (!response.ok) {
+          throw new Error(`Failed to get policy: ${response.statusText}`)
+        }
+        return response.json()
+      },
+
+      async listSessions(): Promise<SessionDTO[]> {
+        const response = await fetch(`${API_BASE}/auth/sessions`, {
+          headers: getAuthHeaders(options),
+        })
+        if (!response.ok) {
+          throw new Error(`Failed to list sessions: ${response.statusText}`)
+        }
+        return response.json()
+      },
+
+      async revokeSession(id: string): Promise<{ success: boolean }> {
+        const response = await fetch(`${API_BASE}/auth/sessions/${encodeURIComponent(id)}`, {
+          method: 'DELETE',
+          headers: getAuthHeaders(options),
+        })
+        if (!response.ok) {
+          throw new Error(`Failed to revoke session: ${response.statusText}`)
+        }
+        return response.json()
+      },
+
+      async getOidcStatus(): Promise<OidcStatus> {
+        const response = await fetch(`${API_BASE}/auth/oidc/status`)
+        if (!response.ok) {
+          throw new Error(`Failed to get single sign-on status: ${response.statusText}`)
+        }
+        return response.json()
+      },
+
+      async startOidcLogin(): Promise<{ authorizationUrl: string }> {
+        const response = await fetch(`${API_BASE}/auth/oidc/authorize`, { method: 'POST' })
+        if (!response.ok) {
+          const error = await response.json().catch(() => ({}))
+          throw new Error(error.error || `Failed to start single sign-on: ${response.statusText}`)
+        }
+        return response.json()
+      },
+
+      async completeOidcLogin(
+        code: string,
+        state: string,
+        deviceInfo?: DeviceInfo
+      ): Promise<AuthResponse> {
+        const response = await fetch(`${API_BASE}/auth/oidc/callback`, {
+          method: 'POST',
+          headers: { 'Content-Type': 'application/json' },
+          body: JSON.stringify({ code, state, deviceInfo }),
+        })
+        if (!response.ok) {
+          const error = await response.json().catch(() => ({}))
+          throw new Error(error.error || `Single sign-on failed: ${response.statusText}`)
+        }
+        return response.json()
+      },
+
+      async startOidcLink(): Promise<{ authorizationUrl: string }> {
+        const response = await fetch(`${API_BASE}/auth/oidc/link`, {
+          method: 'POST',
+          headers: getAuthHeaders(options),
+        })
+        if (!response.ok) {
+          const error = await response.json().catch(() => ({}))
+          throw new Error(error.error || `Failed to start single sign-on: ${response.statusText}`)
+        }
+        return response.json()
+      },
+
+      async unlinkOidc(): Promise<{ success: boolean }> {
+        const response = await fetch(`${API_BASE}/auth/oidc/link`, {
+          method: 'DELETE',
+          headers: getAuthHeaders(options),
+        })
+        if (!response.ok) {
+          const error = await response.json().catch(() => ({}))
+          throw new Error(error.error || `Failed to unlink single sign-on: ${response.statusText}`)
+        }
+        return response.json()
+      },
+
+      async getOidcConfig(): Promise<OidcConfig | null> {
+        const response = await fetch(`${API_BASE}/auth/oidc/config`, {
+          headers: getAuthHeaders(options),
+        })
+        if (!response.ok) {
+          throw new Error(`Failed to get single sign-on settings: ${response.statusText}`)
+        }
+        return response.json()
+      },
+
+      async updateOidcConfig(config: OidcConfigInput): Promise<OidcConfig> {
+        const response = await fetch(`${API_BASE}/auth/oidc/config`, {
+          method: 'PUT',
+          headers: { 'Content-Type': 'application/json', ...getAuthHeaders(options) },
+          body: JSON.stringify(config),
+        })
+        if (!response.ok) {
+          const error = await response.json().catch(() => ({}))
+          throw new Error(
+            error.error || `Failed to save single sign-on settings: ${response.statusText}`
+          )
+        }
+        return response.json()
+      },
+
+      async disableOidc(): Promise<{ success: boolean }> {
+        const response = await fetch(`${API_BASE}/auth/oidc/config`, {
+          method: 'DELETE',
+          headers: getAuthHeaders(options),
+        })
+        if (!response.ok) {
+          throw new Error(`Failed to disable single sign-on: ${response.statusText}`)
+        }
+        return response.json()
+      },
     },
 
     async getGreeting(name?: string): Promise<Greeting> {
@@ -414,6 +738,22 @@ export function createHttpApiClient(options: ApiClientOptions): ApiClient {
         return data.count
       },
 
+      async search(query: ChatSearchQueryDTO): Promise<ChatSearchHitDTO[]> {
+        const params = new URLSearchParams({ query: query.query })
+        if (query.limit !== undefined) params.append('limit', String(query.limit))
+        if (query.offset !== undefined) params.append('offset', String(query.offset))
+
+        const response = await fetch(`${API_BASE}/chat/search?${params}`, {
+          headers: getAuthHeaders(options),
+        })
+
+        if (!response.ok) {
+          throw new Error(`Failed to search conversations: ${response.statusText}`)
+        }
+
+        return response.json()
+      },
+
       async sendMessage(_conversationId: string | null, _message: string): Promise<void> {
         throw new Error('sendMessage not yet implemented')
       },
@@ -488,32 +828,136 @@ export function createHttpApiClient(options: ApiClientOptions): ApiClient {
         }
       },
 
+      async exportConversation(id: string, format: ConversationExportFormat): Promise<string> {
+        const params = new URLSearchParams({ format })
+        const response = await fetch(
+          `${API_BASE}/chat/conversations/${encodeURIComponent(id)}/export?${params}`,
+          {
+            headers: getAuthHeaders(options),
+          }
+        )
+
+        if (!response.ok) {
+          throw new Error(`Failed to export conversation: ${response.statusText}`)
+        }
+
+        return response.text()
+      },
+
+      async importConversation(data: unknown): Promise<ChatConversationDTO> {
+        const response = await fetch(`${API_BASE}/chat/conversations/import`, {
+          method: 'POST',
+          headers: {
+            'Content-Type': 'application/json',
+            ...getAuthHeaders(options),
+          },
+          body: JSON.stringify(data),
+        })
+
+        if (!response.ok) {
+          const error = await response.json().catch(() => ({}))
+          throw new Error(error.error || `Failed to import conversation: ${response.statusText}`)
+        }
+
+        return response.json()
+      },
+
+      async revertContextSummary(conversationId: string, interactionId: string): Promise<void> {
+        const response = await fetch(
+          `${API_BASE}/chat/conversation/${encodeURIComponent(conversationId)}/compactions/${encodeURIComponent(interactionId)}/revert`,
+          {
+            method: 'POST',
+            headers: getAuthHeaders(options),
+          }
+        )
+
+        if (!response.ok) {
+          throw new Error(`Failed to revert context summary: ${response.statusText}`)
+        }
+      },
+
+      async setConversationOverrides(
+        conversationId: string,
+        overrides: ChatConversationOverridesDTO
+      ): Promise<ChatConversationOverridesDTO> {
+        const response = await fetch(
+          `${API_BASE}/chat/conversation/${encodeURIComponent(conversationId)}/overrides`,
+          {
+            method: 'PUT',
+            headers: {
+              'Content-Type': 'application/json',
+              ...getAuthHeaders(options),
+            },
+            body: JSON.stringify(overrides),
+          }
+        )
+
+        if (!response.ok) {
+          const error = await response.json().catch(() => ({}))
+          throw new Error(
+            error.error || `Failed to update conversation overrides: ${response.statusText}`
+          )
+        }
+
+        return response.json()
+      },
+
+      async getUsage(query?: TokenUsageQueryDTO): Promise<TokenUsageReportDTO> {
+        const response = await fetch(`${API_BASE}/chat/usage${buildUsageQuery(query)}`, {
+          headers: getAuthHeaders(options),
+        })
+
+        if (!response.ok) {
+          throw new Error(`Failed to get token usage: ${response.statusText}`)
+        }
+
+        return response.json()
+      },
+
+      async getAllUsage(query?: TokenUsageQueryDTO): Promise<TokenUsageReportDTO> {
+        const response = await fetch(`${API_BASE}/chat/usage/all${buildUsageQuery(query)}`, {
+          headers: getAuthHeaders(options),
+        })
+
+        if (!response.ok) {
+          throw new Error(`Failed to get token usage: ${response.statusText}`)
+        }
+
+        return response.json()
+      },
+
       async streamMessage(
         conversationId: string | null,
         message: string,
         streamOptions: ChatStreamOptions
       ): Promise<() => void> {
-        const { queueId, role, context, sessionId, onEvent } = streamOptions
+        const { queueId, role, context, sessionId, attachments, onEvent } = streamOptions
+        const { regenerateInteractionId, editInteractionId } = streamOptions
         let active = true
 
-        const body = JSON.stringify({
+        const fields = {
           conversationId,
           message,
           queueId,
           role,
           context,
           sessionId,
-        })
+          regenerateInteractionId,
+          editInteractionId,
+        }
 
         // Use fetch with streaming response
         const response = await fetch(`${API_BASE}/chat/stream`, {
           method: 'POST',
           headers: {
-            'Content-Type': 'application/json',
+            // Multipart requests get their Content-Type (with boundary) from the form data
+            ...(attachments?.length ? {} : { 'Content-Type': 'application/json' }),
             Accept: 'text/event-stream',
             ...getAuthHeaders(options),
           },
-          body,
+          body: attachments?.length
+            ? createChatStreamFormData(fields, attachments)
+            : JSON.stringify(fields),
         })
 
         if (!response.ok) {
@@ -632,6 +1076,25 @@ export function createHttpApiClient(options: ApiClientOptions): ApiClient {
         return response.json()
       },
 
+      async switchBranch(
+        conversationId: string,
+        interactionId: string,
+        sessionId?: string
+      ): Promise<{ success: boolean }> {
+        const response = await fetch(`${API_BASE}/chat/branch/switch`, {
+          method: 'POST',
+          headers: {
+            'Content-Type': 'application/json',
+            ...getAuthHeaders(options),
+          },
+          body: JSON.stringify({ conversationId, interactionId, sessionId }),
+        })
+        if (!response.ok) {
+          throw new Error(`Failed to switch branch: ${response.statusText}`)
+        }
+        return response.json()
+      },
+
       subscribeToConversation(
         conversationId: string,
         onEvent: (event: ChatStreamEvent) => void
@@ -1022,6 +1485,61 @@ export function createHttpApiClient(options: ApiClientOptions): ApiClient {
         return response.json()
       },
 
+      async getFileRoots(extensionId: string): Promise<ExtensionFileRoot[]> {
+        const response = await fetch(
+          `${API_BASE}/extensions/${encodeURIComponent(extensionId)}/file-roots`,
+          {
+            headers: getAuthHeaders(options),
+          }
+        )
+
+        if (!response.ok) {
+          throw new Error(`Failed to fetch file roots: ${response.statusText}`)
+        }
+
+        return response.json()
+      },
+
+      async setFileRoot(
+        extensionId: string,
+        path: string,
+        access: 'read' | 'readwrite'
+      ): Promise<{ success: boolean }> {
+        const response = await fetch(
+          `${API_BASE}/extensions/${encodeURIComponent(extensionId)}/file-roots`,
+          {
+            method: 'PUT',
+            headers: {
+              ...getAuthHeaders(options),
+              'Content-Type': 'application/json',
+            },
+            body: JSON.stringify({ path, access }),
+          }
+        )
+
+        if (!response.ok) {
+          throw new Error(`Failed to set file root: ${response.statusText}`)
+        }
+
+        return response.json()
+      },
+
+      async removeFileRoot(extensionId: string, path: string): Promise<{ success: boolean }> {
+        const response = await fetch(
+          `${API_BASE}/extensions/${encodeURIComponent(extensionId)}/file-roots?path=${encodeURIComponent(path)}`,
+          {
+            method: 'DELETE',
+            headers: getAuthHeaders(options),
+          }
+        )
+
+        if (!response.ok) {
+          throw new Error(`Failed to remove file root: ${response.statusText}`)
+        }
+
+        return response.json()
+      },
+
       async uploadLocal(file: File): Promise<InstallLocalResult> {
         const formData = new FormData()
         formData.append('file', file)
@@ -1556,9 +2074,14 @@ export function createHttpApiClient(options: ApiClientOptions): ApiClient {
         return response.json()
       },
 
-      async get(id: string): Promise<ScheduledJobDetailDTO> {
+      async get(id: string, historyOptions?: ScheduledJobHistoryOptions): Promise<ScheduledJobDetailDTO> {
+        const params = new URLSearchParams()
+        if (historyOptions?.runsLimit !== undefined) params.append('runsLimit', String(historyOptions.runsLimit))
+        if (historyOptions?.runsOffset !== undefined) params.append('runsOffset', String(historyOptions.runsOffset))
+        const query = params.toString()
+
         const response = await fetch(
-          `${API_BASE}/scheduled-jobs/${encodeURIComponent(id)}`,
+          `${API_BASE}/scheduled-jobs/${encodeURIComponent(id)}${query ? `?${query}` : ''}`,
           {
             headers: getAuthHeaders(options),
           }
@@ -1586,6 +2109,52 @@ export function createHttpApiClient(options: ApiClientOptions): ApiClient {
 
         return response.json()
       },
+
+      async pause(id: string): Promise<{ success: boolean }> {
+        const response = await fetch(`${API_BASE}/scheduled-jobs/${encodeURIComponent(id)}/pause`, {
+          method: 'POST',
+          headers: getAuthHeaders(options),
+        })
+
+        if (!response.ok) {
+          const error = await response.json().catch(() => ({ error: response.statusText }))
+          throw new Error(error.error || 'Failed to pause scheduled job')
+        }
+
+        return response.json()
+      },
+
+      async resume(
+        id: string,
+        resumeOptions?: ScheduledJobResumeOptions
+      ): Promise<{ success: boolean }> {
+        const response = await fetch(`${API_BASE}/scheduled-jobs/${encodeURIComponent(id)}/resume`, {
+          method: 'POST',
+          headers: { 'Content-Type': 'application/json', ...getAuthHeaders(options) },
+          body: JSON.stringify(resumeOptions ?? {}),
+        })
+
+        if (!response.ok) {
+          const error = await response.json().catch(() => ({ error: response.statusText }))
+          throw new Error(error.error || 'Failed to resume scheduled job')
+        }
+
+        return response.json()
+      },
+
+      async runNow(id: string): Promise<{ success: boolean }> {
+        const response = await fetch(`${API_BASE}/scheduled-jobs/${encodeURIComponent(id)}/run`, {
+          method: 'POST',
+          headers: getAuthHeaders(options),
+        })
+
+        if (!response.ok) {
+          const error = await response.json().catch(() => ({ error: response.statusText }))
+          throw new Error(error.error || 'Failed to run scheduled job')
+        }
+
+        return response.json()
+      },
     },
   }
 }
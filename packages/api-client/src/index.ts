@@ -11,12 +11,16 @@ export type {
   RegistrationOptionsResponse,
   AuthResponse,
   InvitationValidation,
+  OidcStatus,
+  OidcConfig,
+  OidcConfigInput,
   // API interface types
   ProviderInfo,
   ToolSettingsViewInfo,
   PanelViewInfo,
   ExtensionToolInfo,
   ToolConfirmationOverride,
+  ExtensionFileRoot,
   ActionInfo,
   ExtensionEvent,
   ChatEvent,
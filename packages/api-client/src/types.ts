@@ -20,7 +20,25 @@ import type {
   QuickCommandDTO,
   ScheduledJobSummaryDTO,
   ScheduledJobDetailDTO,
+  ScheduledJobHistoryOptions,
+  ScheduledJobResumeOptions,
   ServerTimeResponse,
+  TokenUsageQueryDTO,
+  TokenUsageReportDTO,
+  ChatSearchQueryDTO,
+  ChatSearchHitDTO,
+  ConversationExportFormat,
+  ChatAttachmentDTO,
+  ChatAttachmentUploadDTO,
+  ChatConversationOverridesDTO,
+  PersonalAccessTokenDTO,
+  CreatedPersonalAccessTokenDTO,
+  PersonalAccessTokenScope,
+  SessionDTO,
+  CapabilityPolicyDTO,
+  EffectivePolicyDTO,
+  AuditEventQueryDTO,
+  AuditEventListDTO,
 } from '@stina/shared'
 import type {
   ExtensionListItem,
@@ -59,6 +77,8 @@ export interface User {
   role: 'admin' | 'user'
   createdAt: Date
   lastLoginAt?: Date
+  /** Whether the user is linked to a single sign-on (OpenID Connect) identity */
+  oidcLinked?: boolean
 }
 
 /**
@@ -136,6 +156,39 @@ export interface InvitationValidation {
   role?: string
 }
 
+/**
+ * Single sign-on availability, shown on the login page
+ */
+export interface OidcStatus {
+  enabled: boolean
+  /** Name of the identity provider, e.g. "Authelia" */
+  displayName: string | null
+}
+
+/**
+ * Single sign-on (OpenID Connect) settings. The client secret is never returned.
+ */
+export interface OidcConfig {
+  issuer: string
+  clientId: string
+  redirectUri: string
+  scopes: string[]
+  displayName?: string
+  /** Create an account the first time an unknown identity logs in */
+  autoProvision: boolean
+  /** Role of auto-provisioned users */
+  defaultRole: 'admin' | 'user'
+  hasClientSecret: boolean
+}
+
+/**
+ * Single sign-on settings input.
+ * A clientSecret left out keeps the saved secret; null removes it.
+ */
+export interface OidcConfigInput extends Omit<OidcConfig, 'hasClientSecret'> {
+  clientSecret?: string | null
+}
+
 // ── API interface types ─────────────────────────────────────────────────────
 
 /**
@@ -191,6 +244,16 @@ export interface ToolConfirmationOverride {
   updatedAt: string
 }
 
+/**
+ * Directory a user has approved for an extension's file access
+ */
+export interface ExtensionFileRoot {
+  extensionId: string
+  path: string
+  access: 'read' | 'readwrite'
+  createdAt: string
+}
+
 /**
  * Action info from extension host
  */
@@ -231,12 +294,14 @@ export type ChatStreamEvent =
   | { type: 'stream-error'; error: string; queueId?: string }
   | { type: 'interaction-saved'; interaction: ChatInteractionDTO; queueId?: string }
   | { type: 'conversation-created'; conversation: ChatConversationDTO; queueId?: string }
+  | { type: 'conversation-title-updated'; conversationId: string; title: string; queueId?: string }
   | {
       type: 'interaction-started'
       interactionId: string
       conversationId: string
       role: string
       text: string
+      attachments?: ChatAttachmentDTO[]
       queueId?: string
     }
   | { type: 'queue-update'; queue: unknown; queueId?: string }
@@ -249,6 +314,12 @@ export interface ChatStreamOptions {
   role?: 'user' | 'instruction'
   context?: 'conversation-start' | 'settings-update'
   sessionId?: string
+  /** Files to attach to a user message */
+  attachments?: ChatAttachmentUploadDTO[]
+  /** Answer this interaction's message again, as a new branch (the message is ignored) */
+  regenerateInteractionId?: string
+  /** Replace this interaction's user message with the message, as a new branch */
+  editInteractionId?: string
   onEvent: (event: ChatStreamEvent) => void
 }
 
@@ -304,6 +375,32 @@ export interface ApiClient {
     /** Delete a user (admin only) */
     deleteUser(id: string): Promise<{ success: boolean }>
 
+    /** List a user's active sessions (admin only) */
+    listUserSessions(userId: string): Promise<SessionDTO[]>
+
+    /** Sign a user out of one of their sessions (admin only) */
+    revokeUserSession(userId: string, sessionId: string): Promise<{ success: boolean }>
+
+    /** Get the capability policy of a role (admin only) */
+    getRolePolicy(role: 'admin' | 'user'): Promise<CapabilityPolicyDTO>
+
+    /** Replace the capability policy of a role (admin only) */
+    setRolePolicy(role: 'admin' | 'user', policy: CapabilityPolicyDTO): Promise<CapabilityPolicyDTO>
+
+    /** Get a user's own policy and what they may do with it (admin only) */
+    getUserPolicy(
+      userId: string
+    ): Promise<{ policy: CapabilityPolicyDTO; effective: EffectivePolicyDTO }>
+
+    /** Replace a user's own policy (admin only) */
+    setUserPolicy(userId: string, policy: CapabilityPolicyDTO): Promise<CapabilityPolicyDTO>
+
+    /** Remove a user's own policy, so their role's policy applies (admin only) */
+    deleteUserPolicy(userId: string): Promise<{ success: boolean }>
+
+    /** List audit log entries, newest first (admin only) */
+    listAuditEvents(query?: AuditEventQueryDTO): Promise<AuditEventListDTO>
+
     /** Create an invitation for a new user (admin only) */
     createInvitation(
       username: string,
@@ -318,6 +415,52 @@ export interface ApiClient {
 
     /** Delete an invitation (admin only) */
     deleteInvitation(id: string): Promise<{ success: boolean }>
+
+    /** List the current user's personal access tokens */
+    listPersonalAccessTokens(): Promise<PersonalAccessTokenDTO[]>
+
+    /** Create a personal access token. The token itself is only returned once. */
+    createPersonalAccessToken(
+      name: string,
+      scopes: PersonalAccessTokenScope[],
+      expiresInDays?: number
+    ): Promise<CreatedPersonalAccessTokenDTO>
+
+    /** Revoke one of the current user's personal access tokens */
+    revokePersonalAccessToken(id: string): Promise<{ success: boolean }>
+
+    /** List the current user's active sessions */
+    listSessions(): Promise<SessionDTO[]>
+
+    /** Get what the current user may do */
+    getMyPolicy(): Promise<EffectivePolicyDTO>
+
+    /** Sign out one of the current user's sessions */
+    revokeSession(id: string): Promise<{ success: boolean }>
+
+    /** Check whether single sign-on is available */
+    getOidcStatus(): Promise<OidcStatus>
+
+    /** Start single sign-on. The browser should be sent to the returned URL. */
+    startOidcLogin(): Promise<{ authorizationUrl: string }>
+
+    /** Complete single sign-on with the code and state from the provider redirect */
+    completeOidcLogin(code: string, state: string, deviceInfo?: DeviceInfo): Promise<AuthResponse>
+
+    /** Start linking the current user to a single sign-on identity */
+    startOidcLink(): Promise<{ authorizationUrl: string }>
+
+    /** Unlink the current user from their single sign-on identity */
+    unlinkOidc(): Promise<{ success: boolean }>
+
+    /** Get single sign-on settings (admin only) */
+    getOidcConfig(): Promise<OidcConfig | null>
+
+    /** Save single sign-on settings (admin only) */
+    updateOidcConfig(config: OidcConfigInput): Promise<OidcConfig>
+
+    /** Disable single sign-on (admin only) */
+    disableOidc(): Promise<{ success: boolean }>
   }
 
   /** Get a greeting message */
@@ -367,6 +510,9 @@ export interface ApiClient {
     /** Count total interactions for a conversation */
     countConversationInteractions(conversationId: string): Promise<number>
 
+    /** Search messages across the user's conversations, best match first */
+    search(query: ChatSearchQueryDTO): Promise<ChatSearchHitDTO[]>
+
     /** Send a message (starts streaming) - legacy, use streamMessage instead */
     sendMessage(conversationId: string | null, message: string): Promise<void>
 
@@ -412,6 +558,16 @@ export interface ApiClient {
      */
     resetQueue?(sessionId?: string, conversationId?: string): Promise<{ success: boolean }>
 
+    /**
+     * Show another branch of a conversation, down to its newest interaction.
+     * Fails while a message is being processed.
+     */
+    switchBranch?(
+      conversationId: string,
+      interactionId: string,
+      sessionId?: string
+    ): Promise<{ success: boolean }>
+
     /** Archive a conversation */
     archiveConversation(id: string): Promise<void>
 
@@ -428,6 +584,39 @@ export interface ApiClient {
     /** Mark all interactions in a conversation as read */
     markRead(conversationId: string): Promise<void>
 
+    /** Export a conversation as Markdown, JSON or HTML and return the file content */
+    exportConversation(id: string, format: ConversationExportFormat): Promise<string>
+
+    /**
+     * Import a conversation from the JSON export format.
+     * The conversation gets new IDs and is owned by the current user.
+     */
+    importConversation(data: unknown): Promise<ChatConversationDTO>
+
+    /**
+     * Revert a context summary so the summarized interactions are sent in full again.
+     * Automatic summarization is turned off for the conversation afterwards.
+     */
+    revertContextSummary(conversationId: string, interactionId: string): Promise<void>
+
+    /**
+     * Replace the conversation's model and personality overrides.
+     * Left out values use the user's settings. Returns the stored overrides.
+     */
+    setConversationOverrides(
+      conversationId: string,
+      overrides: ChatConversationOverridesDTO
+    ): Promise<ChatConversationOverridesDTO>
+
+    /** Get token usage for the current user, grouped per model and per day */
+    getUsage(query?: TokenUsageQueryDTO): Promise<TokenUsageReportDTO>
+
+    /**
+     * Get token usage for all users (optional, admin only).
+     * Not available in Electron, which has a single local user.
+     */
+    getAllUsage?(query?: TokenUsageQueryDTO): Promise<TokenUsageReportDTO>
+
     /**
      * Subscribe to a conversation's event stream for real-time multi-client synchronization.
      * Returns an unsubscribe function to clean up the subscription.
@@ -515,6 +704,19 @@ export interface ApiClient {
     /** Reset all tool confirmation overrides for an extension */
     resetToolConfirmations(extensionId: string): Promise<{ success: boolean }>
 
+    /** Get directories approved for an extension's file access */
+    getFileRoots(extensionId: string): Promise<ExtensionFileRoot[]>
+
+    /** Approve a directory for an extension's file access */
+    setFileRoot(
+      extensionId: string,
+      path: string,
+      access: 'read' | 'readwrite'
+    ): Promise<{ success: boolean }>
+
+    /** Revoke an approved directory */
+    removeFileRoot(extensionId: string, path: string): Promise<{ success: boolean }>
+
     /** Upload and install a local extension from a file */
     uploadLocal(file: File): Promise<InstallLocalResult>
   }
@@ -649,10 +851,19 @@ export interface ApiClient {
     /** List all scheduled jobs for the current user */
     list(): Promise<ScheduledJobSummaryDTO[]>
 
-    /** Get details for a specific scheduled job */
-    get(id: string): Promise<ScheduledJobDetailDTO>
+    /** Get details for a specific scheduled job, with a page of its run history */
+    get(id: string, options?: ScheduledJobHistoryOptions): Promise<ScheduledJobDetailDTO>
 
     /** Delete a scheduled job */
     delete(id: string): Promise<{ success: boolean }>
+
+    /** Pause a scheduled job until it is resumed */
+    pause(id: string): Promise<{ success: boolean }>
+
+    /** Resume a paused scheduled job */
+    resume(id: string, options?: ScheduledJobResumeOptions): Promise<{ success: boolean }>
+
+    /** Run a scheduled job once right away, without changing its schedule */
+    runNow(id: string): Promise<{ success: boolean }>
   }
 }
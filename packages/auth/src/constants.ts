@@ -1,3 +1,7 @@
+import { USER_CAPABILITIES } from '@stina/shared'
+import type { UserCapability } from '@stina/shared'
+import type { UserRole } from './types/user.js'
+
 /**
  * Authentication configuration constants
  */
@@ -8,6 +12,24 @@ export const AUTH_CONFIG = {
   /** Refresh token expires after 7 days */
   REFRESH_TOKEN_EXPIRY: '7d',
 
+  /** Prefix of personal access tokens, which tells them apart from JWTs */
+  PERSONAL_ACCESS_TOKEN_PREFIX: 'stina_pat_',
+
+  /**
+   * How long access tokens trust a checked session before looking it up again (ms).
+   * Sessions revoked through the same server are rejected at once.
+   */
+  SESSION_CHECK_INTERVAL_MS: 30 * 1000,
+
+  /** Minimum time between updates of a personal access token's last used timestamp */
+  PERSONAL_ACCESS_TOKEN_LAST_USED_INTERVAL_MS: 60 * 1000,
+
+  /** Scopes requested from OpenID Connect providers unless configured otherwise */
+  OIDC_DEFAULT_SCOPES: ['openid', 'profile', 'email'],
+
+  /** Time a user has to finish an OpenID Connect login (ms) */
+  OIDC_LOGIN_TTL_MS: 10 * 60 * 1000,
+
   /** Issuer for JWT tokens */
   TOKEN_ISSUER: 'stina',
 
@@ -39,4 +61,37 @@ export const AUTH_CONFIG_KEYS = {
 
   /** Refresh token secret */
   REFRESH_TOKEN_SECRET: 'refresh_token_secret',
+
+  /** OpenID Connect issuer URL */
+  OIDC_ISSUER: 'oidc_issuer',
+
+  /** OpenID Connect client ID */
+  OIDC_CLIENT_ID: 'oidc_client_id',
+
+  /** OpenID Connect client secret (empty for public clients) */
+  OIDC_CLIENT_SECRET: 'oidc_client_secret',
+
+  /** URL the identity provider redirects back to after login */
+  OIDC_REDIRECT_URI: 'oidc_redirect_uri',
+
+  /** Space-separated scopes requested from the identity provider */
+  OIDC_SCOPES: 'oidc_scopes',
+
+  /** Name of the identity provider shown on the login button */
+  OIDC_DISPLAY_NAME: 'oidc_display_name',
+
+  /** Whether unknown identities get a new account on first login */
+  OIDC_AUTO_PROVISION: 'oidc_auto_provision',
+
+  /** Role given to auto-provisioned users */
+  OIDC_DEFAULT_ROLE: 'oidc_default_role',
 } as const
+
+/**
+ * Capabilities of roles without a stored policy.
+ * Admins always have every capability, whatever their policy says.
+ */
+export const DEFAULT_ROLE_CAPABILITIES: Record<UserRole, readonly UserCapability[]> = {
+  admin: USER_CAPABILITIES,
+  user: ['tools:approve'],
+}
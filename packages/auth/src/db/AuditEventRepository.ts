@@ -0,0 +1,99 @@
+import { and, count as drizzleCount, desc, eq, gte, lt } from 'drizzle-orm'
+import type { SQL } from 'drizzle-orm'
+import { nanoid } from 'nanoid'
+import type { AuthDb } from './schema.js'
+import { auditEvents } from './schema.js'
+import type { AuditEvent, AuditEventFilter, RecordAuditEventInput } from '../types/audit.js'
+
+const DEFAULT_LIMIT = 100
+
+/**
+ * Repository for the audit log.
+ * Events are only ever inserted, and removed by the retention cleanup.
+ */
+export class AuditEventRepository {
+  constructor(private db: AuthDb) {}
+
+  /**
+   * Append an event to the audit log
+   */
+  async record(input: RecordAuditEventInput): Promise<AuditEvent> {
+    const event: AuditEvent = {
+      id: nanoid(),
+      createdAt: new Date(),
+      actorUserId: input.actorUserId ?? null,
+      actorUsername: input.actorUsername ?? null,
+      action: input.action,
+      targetType: input.targetType ?? null,
+      targetId: input.targetId ?? null,
+      details: input.details ?? null,
+      ip: input.ip ?? null,
+    }
+
+    await this.db.insert(auditEvents).values(event)
+    return event
+  }
+
+  /**
+   * List events matching the filters, newest first (100 unless a limit is given)
+   */
+  async list(filter: AuditEventFilter = {}): Promise<AuditEvent[]> {
+    const rows = await this.db
+      .select()
+      .from(auditEvents)
+      .where(this.buildWhere(filter))
+      .orderBy(desc(auditEvents.createdAt))
+      .limit(filter.limit ?? DEFAULT_LIMIT)
+      .offset(filter.offset ?? 0)
+
+    return rows.map((row) => this.mapToEvent(row))
+  }
+
+  /**
+   * Count events matching the filters, ignoring limit and offset
+   */
+  async count(filter: AuditEventFilter = {}): Promise<number> {
+    const result = await this.db
+      .select({ count: drizzleCount() })
+      .from(auditEvents)
+      .where(this.buildWhere(filter))
+    return result[0]?.count ?? 0
+  }
+
+  /**
+   * Delete events recorded before a point in time
+   * @returns Number of events deleted
+   */
+  async deleteBefore(before: Date): Promise<number> {
+    const result = await this.db.delete(auditEvents).where(lt(auditEvents.createdAt, before))
+    return result.changes
+  }
+
+  private buildWhere(filter: AuditEventFilter): SQL | undefined {
+    const conditions: SQL[] = []
+    if (filter.action) conditions.push(eq(auditEvents.action, filter.action))
+    if (filter.actorUserId) conditions.push(eq(auditEvents.actorUserId, filter.actorUserId))
+    if (filter.targetType) conditions.push(eq(auditEvents.targetType, filter.targetType))
+    if (filter.targetId) conditions.push(eq(auditEvents.targetId, filter.targetId))
+    if (filter.from) conditions.push(gte(auditEvents.createdAt, filter.from))
+    if (filter.to) conditions.push(lt(auditEvents.createdAt, filter.to))
+    return conditions.length > 0 ? and(...conditions) : undefined
+  }
+
+  /**
+   * Map database row to AuditEvent type
+   */
+  private mapToEvent(row: typeof auditEvents.$inferSelect): AuditEvent {
+    return {
+      id: row.id,
+      createdAt: row.createdAt,
+      actorUserId: row.actorUserId,
+      actorUsername: row.actorUsername,
+      action: row.action,
+      targetType: row.targetType,
+      targetId: row.targetId,
+      details: row.details ?? null,
+      ip: row.ip,
+    }
+  }
+}
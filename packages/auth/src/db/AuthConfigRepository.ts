@@ -1,7 +1,20 @@
 import { eq } from 'drizzle-orm'
 import type { AuthDb } from './schema.js'
 import { authConfig } from './schema.js'
-import { AUTH_CONFIG_KEYS } from '../constants.js'
+import { AUTH_CONFIG, AUTH_CONFIG_KEYS } from '../constants.js'
+import type { OidcConfig } from '../types/oidc.js'
+
+/** auth_config keys holding the OpenID Connect settings */
+const OIDC_CONFIG_KEYS = [
+  AUTH_CONFIG_KEYS.OIDC_ISSUER,
+  AUTH_CONFIG_KEYS.OIDC_CLIENT_ID,
+  AUTH_CONFIG_KEYS.OIDC_CLIENT_SECRET,
+  AUTH_CONFIG_KEYS.OIDC_REDIRECT_URI,
+  AUTH_CONFIG_KEYS.OIDC_SCOPES,
+  AUTH_CONFIG_KEYS.OIDC_DISPLAY_NAME,
+  AUTH_CONFIG_KEYS.OIDC_AUTO_PROVISION,
+  AUTH_CONFIG_KEYS.OIDC_DEFAULT_ROLE,
+]
 
 /**
  * Repository for auth configuration data access
@@ -128,4 +141,51 @@ export class AuthConfigRepository {
   async setRefreshTokenSecret(secret: string): Promise<void> {
     return this.set(AUTH_CONFIG_KEYS.REFRESH_TOKEN_SECRET, secret)
   }
+
+  /**
+   * Get the OpenID Connect settings
+   * @returns The settings, or null if OpenID Connect login is not configured
+   */
+  async getOidcConfig(): Promise<OidcConfig | null> {
+    const values = await this.getAll()
+    const issuer = values.get(AUTH_CONFIG_KEYS.OIDC_ISSUER)
+    const clientId = values.get(AUTH_CONFIG_KEYS.OIDC_CLIENT_ID)
+    const redirectUri = values.get(AUTH_CONFIG_KEYS.OIDC_REDIRECT_URI)
+    if (!issuer || !clientId || !redirectUri) return null
+
+    const scopes = values.get(AUTH_CONFIG_KEYS.OIDC_SCOPES)
+    return {
+      issuer,
+      clientId,
+      clientSecret: values.get(AUTH_CONFIG_KEYS.OIDC_CLIENT_SECRET) || undefined,
+      redirectUri,
+      scopes: scopes ? scopes.split(' ') : [...AUTH_CONFIG.OIDC_DEFAULT_SCOPES],
+      displayName: values.get(AUTH_CONFIG_KEYS.OIDC_DISPLAY_NAME) || undefined,
+      autoProvision: values.get(AUTH_CONFIG_KEYS.OIDC_AUTO_PROVISION) === 'true',
+      defaultRole: values.get(AUTH_CONFIG_KEYS.OIDC_DEFAULT_ROLE) === 'admin' ? 'admin' : 'user',
+    }
+  }
+
+  /**
+   * Save the OpenID Connect settings
+   */
+  async setOidcConfig(config: OidcConfig): Promise<void> {
+    await this.set(AUTH_CONFIG_KEYS.OIDC_ISSUER, config.issuer)
+    await this.set(AUTH_CONFIG_KEYS.OIDC_CLIENT_ID, config.clientId)
+    await this.set(AUTH_CONFIG_KEYS.OIDC_CLIENT_SECRET, config.clientSecret ?? '')
+    await this.set(AUTH_CONFIG_KEYS.OIDC_REDIRECT_URI, config.redirectUri)
+    await this.set(AUTH_CONFIG_KEYS.OIDC_SCOPES, config.scopes.join(' '))
+    await this.set(AUTH_CONFIG_KEYS.OIDC_DISPLAY_NAME, config.displayName ?? '')
+    await this.set(AUTH_CONFIG_KEYS.OIDC_AUTO_PROVISION, String(config.autoProvision))
+    await this.set(AUTH_CONFIG_KEYS.OIDC_DEFAULT_ROLE, config.defaultRole)
+  }
+
+  /**
+   * Remove the OpenID Connect settings, which disables OpenID Connect login
+   */
+  async clearOidcConfig(): Promise<void> {
+    for (const key of OIDC_CONFIG_KEYS) {
+      await this.delete(key)
+    }
+  }
 }
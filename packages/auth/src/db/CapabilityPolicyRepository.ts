@@ -0,0 +1,90 @@
+import { eq } from 'drizzle-orm'
+import type { AuthDb, UserRole } from './schema.js'
+import { rolePolicies, userPolicies } from './schema.js'
+import type { CapabilityPolicy } from '../types/policy.js'
+
+/**
+ * Repository for role and user capability policies
+ */
+export class CapabilityPolicyRepository {
+  constructor(private db: AuthDb) {}
+
+  /**
+   * Get the stored policy of a role, or null if none is stored
+   */
+  async getRolePolicy(role: UserRole): Promise<CapabilityPolicy | null> {
+    const result = await this.db
+      .select()
+      .from(rolePolicies)
+      .where(eq(rolePolicies.role, role))
+      .limit(1)
+
+    const row = result[0]
+    if (!row) return null
+
+    return {
+      capabilities: row.capabilities ?? null,
+      allowedModelConfigIds: row.allowedModelConfigIds ?? null,
+    }
+  }
+
+  /**
+   * Create or replace the policy of a role
+   */
+  async setRolePolicy(role: UserRole, policy: CapabilityPolicy): Promise<void> {
+    const values = {
+      capabilities: policy.capabilities,
+      allowedModelConfigIds: policy.allowedModelConfigIds,
+      updatedAt: new Date(),
+    }
+
+    await this.db
+      .insert(rolePolicies)
+      .values({ role, ...values })
+      .onConflictDoUpdate({ target: rolePolicies.role, set: values })
+  }
+
+  /**
+   * Get the stored policy of a user, or null if none is stored
+   */
+  async getUserPolicy(userId: string): Promise<CapabilityPolicy | null> {
+    const result = await this.db
+      .select()
+      .from(userPolicies)
+      .where(eq(userPolicies.userId, userId))
+      .limit(1)
+
+    const row = result[0]
+    if (!row) return null
+
+    return {
+      capabilities: row.capabilities ?? null,
+      allowedModelConfigIds: row.allowedModelConfigIds ?? null,
+    }
+  }
+
+  /**
+   * Create or replace the policy of a user
+   */
+  async setUserPolicy(userId: string, policy: CapabilityPolicy): Promise<void> {
+    const values = {
+      capabilities: policy.capabilities,
+      allowedModelConfigIds: policy.allowedModelConfigIds,
+      updatedAt: new Date(),
+    }
+
+    await this.db
+      .insert(userPolicies)
+      .values({ userId, ...values })
+      .onConflictDoUpdate({ target: userPolicies.userId, set: values })
+  }
+
+  /**
+   * Remove the policy of a user, so the role's policy applies again
+   * @returns true if a policy was removed
+   */
+  async deleteUserPolicy(userId: string): Promise<boolean> {
+    const result = await this.db.delete(userPolicies).where(eq(userPolicies.userId, userId))
+    return result.changes > 0
+  }
+}
@@ -0,0 +1,162 @@
+import { eq, and, isNull, desc } from 'drizzle-orm'
+import { nanoid } from 'nanoid'
+import type { PersonalAccessTokenScope } from '@stina/shared'
+import type { AuthDb } from './schema.js'
+import { personalAccessTokens } from './schema.js'
+import type { PersonalAccessTokenData } from '../types/session.js'
+
+/**
+ * Input for creating a personal access token
+ */
+export interface CreatePersonalAccessTokenInput {
+  userId: string
+  name: string
+  tokenHash: string
+  tokenPrefix: string
+  scopes: PersonalAccessTokenScope[]
+  expiresAt: Date | null
+}
+
+/**
+ * Repository for personal access token data access
+ */
+export class PersonalAccessTokenRepository {
+  constructor(private db: AuthDb) {}
+
+  /**
+   * Create a new personal access token
+   */
+  async create(input: CreatePersonalAccessTokenInput): Promise<PersonalAccessTokenData> {
+    const id = nanoid()
+
+    await this.db.insert(personalAccessTokens).values({
+      id,
+      userId: input.userId,
+      name: input.name,
+      tokenHash: input.tokenHash,
+      tokenPrefix: input.tokenPrefix,
+      scopes: input.scopes,
+      expiresAt: input.expiresAt,
+      createdAt: new Date(),
+      lastUsedAt: null,
+      revokedAt: null,
+    })
+
+    const token = await this.getById(id)
+    if (!token) {
+      throw new Error('Failed to create personal access token')
+    }
+    return token
+  }
+
+  /**
+   * Get personal access token by ID
+   */
+  async getById(id: string): Promise<PersonalAccessTokenData | null> {
+    const result = await this.db
+      .select()
+      .from(personalAccessTokens)
+      .where(eq(personalAccessTokens.id, id))
+      .limit(1)
+
+    const row = result[0]
+    if (!row) return null
+
+    return this.mapToToken(row)
+  }
+
+  /**
+   * Get valid (not revoked, not expired) personal access token by hash
+   */
+  async getValidByTokenHash(tokenHash: string): Promise<PersonalAccessTokenData | null> {
+    const result = await this.db
+      .select()
+      .from(personalAccessTokens)
+      .where(
+        and(eq(personalAccessTokens.tokenHash, tokenHash), isNull(personalAccessTokens.revokedAt))
+      )
+      .limit(1)
+
+    const row = result[0]
+    if (!row) return null
+
+    if (row.expiresAt && row.expiresAt < new Date()) {
+      return null
+    }
+
+    return this.mapToToken(row)
+  }
+
+  /**
+   * Get all personal access tokens of a user that have not been revoked, newest first
+   */
+  async getActiveByUserId(userId: string): Promise<PersonalAccessTokenData[]> {
+    const result = await this.db
+      .select()
+      .from(personalAccessTokens)
+      .where(
+        and(eq(personalAccessTokens.userId, userId), isNull(personalAccessTokens.revokedAt))
+      )
+      .orderBy(desc(personalAccessTokens.createdAt))
+
+    return result.map((row) => this.mapToToken(row))
+  }
+
+  /**
+   * Record that a token was used
+   */
+  async updateLastUsed(id: string, lastUsedAt: Date = new Date()): Promise<void> {
+    await this.db
+      .update(personalAccessTokens)
+      .set({ lastUsedAt })
+      .where(eq(personalAccessTokens.id, id))
+  }
+
+  /**
+   * Revoke one of a user's personal access tokens
+   * @returns true if an active token was revoked
+   */
+  async revoke(userId: string, id: string): Promise<boolean> {
+    const result = await this.db
+      .update(personalAccessTokens)
+      .set({ revokedAt: new Date() })
+      .where(
+        and(
+          eq(personalAccessTokens.id, id),
+          eq(personalAccessTokens.userId, userId),
+          isNull(personalAccessTokens.revokedAt)
+        )
+      )
+
+    return result.changes > 0
+  }
+
+  /**
+   * Revoke all personal access tokens for a user
+   */
+  async revokeAllByUserId(userId: string): Promise<void> {
+    await this.db
+      .update(personalAccessTokens)
+      .set({ revokedAt: new Date() })
+      .where(
+        and(eq(personalAccessTokens.userId, userId), isNull(personalAccessTokens.revokedAt))
+      )
+  }
+
+  /**
+   * Map database row to PersonalAccessTokenData type
+   */
+  private mapToToken(row: typeof personalAccessTokens.$inferSelect): PersonalAccessTokenData {
+    return {
+      id: row.id,
+      userId: row.userId,
+      name: row.name,
+      tokenPrefix: row.tokenPrefix,
+      scopes: row.scopes,
+      expiresAt: row.expiresAt,
+      createdAt: row.createdAt,
+      lastUsedAt: row.lastUsedAt,
+      revokedAt: row.revokedAt,
+    }
+  }
+}
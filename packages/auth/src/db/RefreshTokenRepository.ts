@@ -8,6 +8,8 @@ import type { RefreshTokenData, DeviceInfo } from '../types/session.js'
  * Input for creating a refresh token
  */
 export interface CreateRefreshTokenInput {
+  /** Defaults to a random ID. Token pairs use the refresh token's jti */
+  id?: string
   userId: string
   tokenHash: string
   expiresAt: Date
@@ -25,7 +27,7 @@ export class RefreshTokenRepository {
    */
   async create(input: CreateRefreshTokenInput): Promise<RefreshTokenData> {
     const now = new Date()
-    const id = nanoid()
+    const id = input.id ?? nanoid()
 
     await this.db.insert(refreshTokens).values({
       id,
@@ -34,6 +36,7 @@ export class RefreshTokenRepository {
       expiresAt: input.expiresAt,
       createdAt: now,
       revokedAt: null,
+      lastUsedAt: null,
       deviceInfo: input.deviceInfo ?? null,
     })
 
@@ -132,6 +135,35 @@ export class RefreshTokenRepository {
       .where(eq(refreshTokens.id, id))
   }
 
+  /**
+   * Revoke one of a user's refresh tokens
+   * @returns false if the user has no active token with that ID
+   */
+  async revokeForUser(userId: string, id: string): Promise<boolean> {
+    const result = await this.db
+      .update(refreshTokens)
+      .set({ revokedAt: new Date() })
+      .where(
+        and(
+          eq(refreshTokens.id, id),
+          eq(refreshTokens.userId, userId),
+          isNull(refreshTokens.revokedAt)
+        )
+      )
+
+    return result.changes > 0
+  }
+
+  /**
+   * Record that a refresh token was used
+   */
+  async updateLastUsed(id: string, lastUsedAt: Date): Promise<void> {
+    await this.db
+      .update(refreshTokens)
+      .set({ lastUsedAt })
+      .where(eq(refreshTokens.id, id))
+  }
+
   /**
    * Revoke a refresh token by hash
    */
@@ -180,6 +212,7 @@ export class RefreshTokenRepository {
     expiresAt: Date
     createdAt: Date
     revokedAt: Date | null
+    lastUsedAt: Date | null
     deviceInfo: { userAgent?: string; ip?: string } | null
   }): RefreshTokenData {
     return {
@@ -189,6 +222,7 @@ export class RefreshTokenRepository {
       expiresAt: row.expiresAt,
       createdAt: row.createdAt,
       revokedAt: row.revokedAt,
+      lastUsedAt: row.lastUsedAt,
       deviceInfo: row.deviceInfo,
     }
   }
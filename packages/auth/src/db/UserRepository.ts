@@ -1,8 +1,9 @@
-import { eq, count as drizzleCount } from 'drizzle-orm'
+import { eq, and, count as drizzleCount } from 'drizzle-orm'
 import { nanoid } from 'nanoid'
 import type { AuthDb, UserRole } from './schema.js'
 import { users } from './schema.js'
 import type { User, CreateUserInput, UpdateUserInput } from '../types/user.js'
+import type { OidcIdentity } from '../types/oidc.js'
 
 /**
  * Repository for user data access
@@ -62,6 +63,22 @@ export class UserRepository {
     return this.mapToUser(row)
   }
 
+  /**
+   * Get the user linked to an OpenID Connect identity
+   */
+  async getByOidcIdentity(identity: OidcIdentity): Promise<User | null> {
+    const result = await this.db
+      .select()
+      .from(users)
+      .where(and(eq(users.oidcIssuer, identity.issuer), eq(users.oidcSubject, identity.subject)))
+      .limit(1)
+
+    const row = result[0]
+    if (!row) return null
+
+    return this.mapToUser(row)
+  }
+
   /**
    * List all users
    */
@@ -103,6 +120,34 @@ export class UserRepository {
       .where(eq(users.id, id))
   }
 
+  /**
+   * Link a user to an OpenID Connect identity, replacing any previous link
+   */
+  async linkOidcIdentity(id: string, identity: OidcIdentity): Promise<void> {
+    await this.db
+      .update(users)
+      .set({
+        oidcIssuer: identity.issuer,
+        oidcSubject: identity.subject,
+        updatedAt: new Date(),
+      })
+      .where(eq(users.id, id))
+  }
+
+  /**
+   * Remove the link between a user and their OpenID Connect identity
+   */
+  async unlinkOidcIdentity(id: string): Promise<void> {
+    await this.db
+      .update(users)
+      .set({
+        oidcIssuer: null,
+        oidcSubject: null,
+        updatedAt: new Date(),
+      })
+      .where(eq(users.id, id))
+  }
+
   /**
    * Delete a user
    */
@@ -137,6 +182,7 @@ export class UserRepository {
     createdAt: Date
     updatedAt: Date
     lastLoginAt: Date | null
+    oidcSubject: string | null
   }): User {
     return {
       id: row.id,
@@ -146,6 +192,7 @@ export class UserRepository {
       createdAt: row.createdAt,
       updatedAt: row.updatedAt,
       lastLoginAt: row.lastLoginAt,
+      oidcLinked: row.oidcSubject !== null,
     }
   }
 }
@@ -6,6 +6,10 @@ export {
   users,
   passkeyCredentials,
   refreshTokens,
+  personalAccessTokens,
+  rolePolicies,
+  userPolicies,
+  auditEvents,
   authConfig,
   invitations,
   authSchema,
@@ -18,6 +22,10 @@ export { PasskeyCredentialRepository } from './PasskeyCredentialRepository.js'
 export type { PasskeyCredential, CreatePasskeyCredentialInput } from './PasskeyCredentialRepository.js'
 export { RefreshTokenRepository } from './RefreshTokenRepository.js'
 export type { CreateRefreshTokenInput } from './RefreshTokenRepository.js'
+export { PersonalAccessTokenRepository } from './PersonalAccessTokenRepository.js'
+export type { CreatePersonalAccessTokenInput } from './PersonalAccessTokenRepository.js'
+export { CapabilityPolicyRepository } from './CapabilityPolicyRepository.js'
+export { AuditEventRepository } from './AuditEventRepository.js'
 export { AuthConfigRepository } from './AuthConfigRepository.js'
 export { InvitationRepository } from './InvitationRepository.js'
 export type { Invitation, CreateInvitationInput } from './InvitationRepository.js'
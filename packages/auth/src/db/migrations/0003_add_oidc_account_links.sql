@@ -0,0 +1,6 @@
+-- OpenID Connect account links. A user is linked to at most one identity,
+-- identified by the issuer and the subject (sub claim) of its ID tokens.
+ALTER TABLE users ADD COLUMN oidc_issuer TEXT;
+ALTER TABLE users ADD COLUMN oidc_subject TEXT;
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_users_oidc_identity ON users(oidc_issuer, oidc_subject);
@@ -0,0 +1,2 @@
+-- Track when each session last refreshed its access token
+ALTER TABLE refresh_tokens ADD COLUMN last_used_at INTEGER;
@@ -0,0 +1,15 @@
+-- Capability policies. Role policies apply to every user with the role,
+-- user policies override them for a single user. NULL columns are not set.
+CREATE TABLE IF NOT EXISTS role_policies (
+  role TEXT PRIMARY KEY,
+  capabilities TEXT,
+  allowed_model_config_ids TEXT,
+  updated_at INTEGER NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS user_policies (
+  user_id TEXT PRIMARY KEY REFERENCES users(id) ON DELETE CASCADE,
+  capabilities TEXT,
+  allowed_model_config_ids TEXT,
+  updated_at INTEGER NOT NULL
+);
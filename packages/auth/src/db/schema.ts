@@ -1,5 +1,6 @@
-import { sqliteTable, text, integer, index } from 'drizzle-orm/sqlite-core'
+import { sqliteTable, text, integer, index, uniqueIndex } from 'drizzle-orm/sqlite-core'
 import type { BetterSQLite3Database } from 'drizzle-orm/better-sqlite3'
+import type { AuditAction, PersonalAccessTokenScope, UserCapability } from '@stina/shared'
 
 export type UserRole = 'admin' | 'user'
 
@@ -16,9 +17,14 @@ export const users = sqliteTable(
     createdAt: integer('created_at', { mode: 'timestamp' }).notNull(),
     updatedAt: integer('updated_at', { mode: 'timestamp' }).notNull(),
     lastLoginAt: integer('last_login_at', { mode: 'timestamp' }),
+    /** Issuer of the linked OpenID Connect identity */
+    oidcIssuer: text('oidc_issuer'),
+    /** Subject (sub claim) of the linked OpenID Connect identity */
+    oidcSubject: text('oidc_subject'),
   },
   (table) => ({
     usernameIdx: index('idx_users_username').on(table.username),
+    oidcIdentityIdx: uniqueIndex('idx_users_oidc_identity').on(table.oidcIssuer, table.oidcSubject),
   })
 )
 
@@ -68,6 +74,8 @@ export const refreshTokens = sqliteTable(
     expiresAt: integer('expires_at', { mode: 'timestamp' }).notNull(),
     createdAt: integer('created_at', { mode: 'timestamp' }).notNull(),
     revokedAt: integer('revoked_at', { mode: 'timestamp' }),
+    /** Last time the token was used to get a new access token */
+    lastUsedAt: integer('last_used_at', { mode: 'timestamp' }),
     /** JSON object with device information */
     deviceInfo: text('device_info', { mode: 'json' }).$type<{
       userAgent?: string
@@ -80,6 +88,87 @@ export const refreshTokens = sqliteTable(
   })
 )
 
+/**
+ * Personal access tokens table - long-lived, scoped tokens for scripts
+ */
+export const personalAccessTokens = sqliteTable(
+  'personal_access_tokens',
+  {
+    id: text('id').primaryKey(),
+    userId: text('user_id')
+      .notNull()
+      .references(() => users.id, { onDelete: 'cascade' }),
+    name: text('name').notNull(),
+    /** SHA-256 hash of the token */
+    tokenHash: text('token_hash').notNull().unique(),
+    /** Start of the token, shown to help the user recognize it */
+    tokenPrefix: text('token_prefix').notNull(),
+    /** JSON array of scopes */
+    scopes: text('scopes', { mode: 'json' }).$type<PersonalAccessTokenScope[]>().notNull(),
+    /** Null for tokens that never expire */
+    expiresAt: integer('expires_at', { mode: 'timestamp' }),
+    createdAt: integer('created_at', { mode: 'timestamp' }).notNull(),
+    lastUsedAt: integer('last_used_at', { mode: 'timestamp' }),
+    revokedAt: integer('revoked_at', { mode: 'timestamp' }),
+  },
+  (table) => ({
+    userIdx: index('idx_personal_access_tokens_user').on(table.userId),
+  })
+)
+
+/**
+ * Role policies table - capabilities and allowed model configs of a role
+ */
+export const rolePolicies = sqliteTable('role_policies', {
+  role: text('role').$type<UserRole>().primaryKey(),
+  /** JSON array of capabilities, or null for the built-in defaults */
+  capabilities: text('capabilities', { mode: 'json' }).$type<UserCapability[]>(),
+  /** JSON array of model config IDs, or null for all */
+  allowedModelConfigIds: text('allowed_model_config_ids', { mode: 'json' }).$type<string[]>(),
+  updatedAt: integer('updated_at', { mode: 'timestamp' }).notNull(),
+})
+
+/**
+ * User policies table - overrides of the role policy for a single user
+ */
+export const userPolicies = sqliteTable('user_policies', {
+  userId: text('user_id')
+    .primaryKey()
+    .references(() => users.id, { onDelete: 'cascade' }),
+  /** JSON array of capabilities, or null to use the role's */
+  capabilities: text('capabilities', { mode: 'json' }).$type<UserCapability[]>(),
+  /** JSON array of model config IDs, or null to use the role's */
+  allowedModelConfigIds: text('allowed_model_config_ids', { mode: 'json' }).$type<string[]>(),
+  updatedAt: integer('updated_at', { mode: 'timestamp' }).notNull(),
+})
+
+/**
+ * Audit events table - append-only log of security-relevant actions.
+ * The actor is not a foreign key so entries outlive the users they mention.
+ */
+export const auditEvents = sqliteTable(
+  'audit_events',
+  {
+    id: text('id').primaryKey(),
+    /** Milliseconds, so events within the same second keep their order */
+    createdAt: integer('created_at', { mode: 'timestamp_ms' }).notNull(),
+    actorUserId: text('actor_user_id'),
+    /** Username of the actor at the time of the action */
+    actorUsername: text('actor_username'),
+    action: text('action').$type<AuditAction>().notNull(),
+    targetType: text('target_type'),
+    targetId: text('target_id'),
+    /** JSON object with extra information about the action */
+    details: text('details', { mode: 'json' }).$type<Record<string, unknown>>(),
+    ip: text('ip'),
+  },
+  (table) => ({
+    createdIdx: index('idx_audit_events_created').on(table.createdAt),
+    actionIdx: index('idx_audit_events_action').on(table.action),
+    actorIdx: index('idx_audit_events_actor').on(table.actorUserId),
+  })
+)
+
 /**
  * Auth configuration table - server-wide settings like rpId
  */
@@ -126,6 +215,10 @@ export const authSchema = {
   users,
   passkeyCredentials,
   refreshTokens,
+  personalAccessTokens,
+  rolePolicies,
+  userPolicies,
+  auditEvents,
   authConfig,
   invitations,
 }
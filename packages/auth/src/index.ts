@@ -1,5 +1,5 @@
 // Constants
-export { AUTH_CONFIG, AUTH_CONFIG_KEYS } from './constants.js'
+export { AUTH_CONFIG, AUTH_CONFIG_KEYS, DEFAULT_ROLE_CAPABILITIES } from './constants.js'
 
 // Types
 export type {
@@ -14,7 +14,17 @@ export type {
   TokenPair,
   RefreshTokenData,
   DeviceInfo,
+  Session,
+  PersonalAccessTokenData,
 } from './types/session.js'
+export type { OidcConfig, OidcIdentity, OidcProfile } from './types/oidc.js'
+export type { CapabilityPolicy, EffectivePolicy } from './types/policy.js'
+export type {
+  AuditEvent,
+  RecordAuditEventInput,
+  AuditActor,
+  AuditEventFilter,
+} from './types/audit.js'
 
 // Re-export from submodules for convenience
 export { getAuthMigrationsPath } from './db/index.js'
@@ -26,6 +36,12 @@ export {
   AuthService,
   DefaultUserService,
   ElectronAuthService,
+  PersonalAccessTokenService,
+  OidcService,
+  PolicyService,
+  AuditLogService,
+  AuditCleanupService,
+  DEFAULT_AUDIT_RETENTION_DAYS,
   base64UrlToUint8Array,
   uint8ArrayToBase64Url,
 } from './services/index.js'
@@ -42,8 +58,20 @@ export type {
   AuthenticationVerifyInput,
   AuthResult,
   CreateInvitationInput,
+  OidcConfigInput,
+  OidcLoginInput,
+  OidcStatus,
+  OidcProviderMetadata,
+  OidcAuthorization,
+  OidcAuthorizationOptions,
+  OidcCallbackResult,
   ElectronAuthSession,
   ElectronAuthServiceConfig,
+  CreatePersonalAccessTokenOptions,
+  CreatedPersonalAccessToken,
+  AuditLogLogger,
+  AuditCleanupServiceOptions,
+  AuditCleanupLogger,
 } from './services/index.js'
 
 export {
@@ -51,5 +79,6 @@ export {
   requireAuth,
   requireAdmin,
   requireRole,
+  requireCapability,
 } from './middleware/index.js'
 export type { AuthPluginOptions } from './middleware/index.js'
@@ -1,6 +1,10 @@
-import type { FastifyPluginAsync, FastifyRequest } from 'fastify'
+import type { FastifyPluginAsync, FastifyReply, FastifyRequest } from 'fastify'
 import fp from 'fastify-plugin'
+import type { PersonalAccessTokenScope } from '@stina/shared'
 import type { AuthService } from '../../services/AuthService.js'
+import { PersonalAccessTokenService } from '../../services/PersonalAccessTokenService.js'
+import type { PolicyService } from '../../services/PolicyService.js'
+import type { AuditLogService } from '../../services/AuditLogService.js'
 import type { User } from '../../types/user.js'
 
 declare module 'fastify' {
@@ -9,10 +13,21 @@ declare module 'fastify' {
     user: User | null
     /** Whether the request is authenticated */
     isAuthenticated: boolean
+    /**
+     * Scopes of the personal access token the request was authenticated with,
+     * or null for full access (passkey sessions and local mode)
+     */
+    tokenScopes: PersonalAccessTokenScope[] | null
+    /** Session (refresh token ID) of the access token, or null if not known */
+    sessionId: string | null
   }
   interface FastifyInstance {
     /** The auth service instance */
     authService: AuthService
+    /** Service deciding the capabilities of users, or null if only admins have them */
+    policyService: PolicyService | null
+    /** Audit log of security-relevant actions, or null if actions are not recorded */
+    auditLogService: AuditLogService | null
   }
 }
 
@@ -26,29 +41,54 @@ export interface AuthPluginOptions {
   requireAuth: boolean
   /** Default user ID for local mode (when requireAuth is false) */
   defaultUserId?: string
+  /** Service for personal access tokens. Without it, they are not accepted */
+  personalAccessTokenService?: PersonalAccessTokenService
+  /**
+   * Get the scope a personal access token needs for a request.
+   * Return null for endpoints personal access tokens cannot be used for;
+   * such requests are handled as unauthenticated.
+   */
+  getRequiredScope?: (method: string, path: string) => PersonalAccessTokenScope | null
+  /** Service for capability policies, used by requireCapability */
+  policyService?: PolicyService
+  /** Audit log that routes record security-relevant actions in */
+  auditLogService?: AuditLogService
 }
 
 /**
  * Fastify plugin for authentication.
  *
  * This plugin:
- * - Decorates the Fastify instance with the auth service
- * - Decorates each request with `user` and `isAuthenticated`
+ * - Decorates the Fastify instance with the auth, policy and audit log services
+ * - Decorates each request with `user`, `isAuthenticated` and `sessionId`
  * - Extracts and verifies JWT from Authorization header
+ * - Accepts personal access tokens for the endpoints their scopes cover
  * - In local mode (requireAuth=false), uses a default user
  */
 const authPluginImpl: FastifyPluginAsync<AuthPluginOptions> = async (fastify, options) => {
-  const { authService, requireAuth, defaultUserId } = options
+  const {
+    authService,
+    requireAuth,
+    defaultUserId,
+    personalAccessTokenService,
+    getRequiredScope,
+    policyService,
+    auditLogService,
+  } = options
 
-  // Decorate Fastify instance with auth service
+  // Decorate Fastify instance with auth, policy and audit log services
   fastify.decorate('authService', authService)
+  fastify.decorate('policyService', policyService ?? null)
+  fastify.decorate('auditLogService', auditLogService ?? null)
 
   // Decorate requests with user and isAuthenticated
   fastify.decorateRequest('user', null)
   fastify.decorateRequest('isAuthenticated', false)
+  fastify.decorateRequest('tokenScopes', null)
+  fastify.decorateRequest('sessionId', null)
 
   // Add hook to extract and verify user on each request
-  fastify.addHook('onRequest', async (request: FastifyRequest) => {
+  fastify.addHook('onRequest', async (request: FastifyRequest, reply: FastifyReply) => {
     // Local mode: use default user
     if (!requireAuth && defaultUserId) {
       const user = await authService.getUserById(defaultUserId)
@@ -75,11 +115,40 @@ const authPluginImpl: FastifyPluginAsync<AuthPluginOptions> = async (fastify, op
       return
     }
 
+    if (PersonalAccessTokenService.isPersonalAccessToken(token)) {
+      request.user = null
+      request.isAuthenticated = false
+
+      const path = request.url.split('?')[0] ?? request.url
+      const requiredScope = getRequiredScope?.(request.method, path) ?? null
+      if (!personalAccessTokenService || !requiredScope) return
+
+      const accessToken = await personalAccessTokenService.verify(token)
+      if (!accessToken) return
+
+      if (!accessToken.scopes.includes(requiredScope)) {
+        reply.code(403).send({
+          error: {
+            code: 'FORBIDDEN',
+            message: `Personal access token is missing the ${requiredScope} scope`,
+          },
+        })
+        return reply
+      }
+
+      const user = await authService.getUserById(accessToken.userId)
+      request.user = user
+      request.isAuthenticated = !!user
+      request.tokenScopes = accessToken.scopes
+      return
+    }
+
     try {
       const payload = await authService.verifyAccessToken(token)
       const user = await authService.getUserById(payload.sub)
       request.user = user
       request.isAuthenticated = !!user
+      request.sessionId = payload.sid ?? null
     } catch {
       request.user = null
       request.isAuthenticated = false
@@ -1,4 +1,6 @@
 import type { FastifyReply, FastifyRequest, preHandlerHookHandler } from 'fastify'
+import type { UserCapability } from '@stina/shared'
+import { DEFAULT_ROLE_CAPABILITIES } from '../../constants.js'
 
 /**
  * Prehandler to require authentication.
@@ -81,3 +83,42 @@ export function requireRole(role: 'admin' | 'user'): preHandlerHookHandler {
     }
   }
 }
+
+/**
+ * Create a prehandler that requires a capability.
+ * Admins always pass; other users need the capability in their policy.
+ * Without a policy service, the built-in role capabilities are used.
+ * Returns 401 if not authenticated, 403 if the capability is missing.
+ */
+export function requireCapability(capability: UserCapability): preHandlerHookHandler {
+  return async (request: FastifyRequest, reply: FastifyReply) => {
+    if (!request.isAuthenticated || !request.user) {
+      reply.code(401).send({
+        error: {
+          code: 'UNAUTHORIZED',
+          message: 'Authentication required',
+        },
+      })
+      return
+    }
+
+    if (request.user.role === 'admin') {
+      return
+    }
+
+    const policyService = request.server.policyService
+    const allowed = policyService
+      ? await policyService.can(request.user.id, capability)
+      : DEFAULT_ROLE_CAPABILITIES[request.user.role].includes(capability)
+
+    if (!allowed) {
+      reply.code(403).send({
+        error: {
+          code: 'FORBIDDEN',
+          message: `The ${capability} capability is required`,
+        },
+      })
+      return
+    }
+  }
+}
@@ -1,4 +1,4 @@
 export { authPlugin } from './fastify/authPlugin.js'
 export type { AuthPluginOptions } from './fastify/authPlugin.js'
 
-export { requireAuth, requireAdmin, requireRole } from './fastify/requireAuth.js'
+export { requireAuth, requireAdmin, requireRole, requireCapability } from './fastify/requireAuth.js'
@@ -0,0 +1,123 @@
+import type { AuditEventRepository } from '../db/AuditEventRepository.js'
+
+export interface AuditCleanupLogger {
+  debug(message: string, context?: Record<string, unknown>): void
+  info(message: string, context?: Record<string, unknown>): void
+  warn(message: string, context?: Record<string, unknown>): void
+  error(message: string, context?: Record<string, unknown>): void
+}
+
+export interface AuditCleanupServiceOptions {
+  repository: AuditEventRepository
+  /**
+   * How long (in days) audit events are kept. Defaults to 90 days.
+   * Set to `0` or a negative number to keep them forever.
+   */
+  retentionDays?: number
+  /**
+   * How often the cleanup loop runs. Defaults to 6 hours.
+   */
+  intervalMs?: number
+  /**
+   * Initial delay before the first run after `start()`. Defaults to 1 minute,
+   * to avoid blocking startup and to give the rest of the app time to settle.
+   */
+  initialDelayMs?: number
+  logger?: AuditCleanupLogger
+  now?: () => Date
+}
+
+const DEFAULT_INTERVAL_MS = 6 * 60 * 60 * 1000
+const DEFAULT_INITIAL_DELAY_MS = 60 * 1000
+export const DEFAULT_AUDIT_RETENTION_DAYS = 90
+const MS_PER_DAY = 24 * 60 * 60 * 1000
+
+/**
+ * Periodically removes audit events that are older than the retention window.
+ * Runs in-process and is safe to start/stop together with the server.
+ */
+export class AuditCleanupService {
+  private readonly repository: AuditEventRepository
+  private readonly retentionDays: number
+  private readonly intervalMs: number
+  private readonly initialDelayMs: number
+  private readonly logger?: AuditCleanupLogger
+  private readonly now: () => Date
+  private timer: ReturnType<typeof setTimeout> | null = null
+  private running = false
+
+  constructor(options: AuditCleanupServiceOptions) {
+    this.repository = options.repository
+    this.retentionDays = options.retentionDays ?? DEFAULT_AUDIT_RETENTION_DAYS
+    this.intervalMs = options.intervalMs ?? DEFAULT_INTERVAL_MS
+    this.initialDelayMs = options.initialDelayMs ?? DEFAULT_INITIAL_DELAY_MS
+    this.logger = options.logger
+    this.now = options.now ?? (() => new Date())
+  }
+
+  /**
+   * Start the cleanup loop. Safe to call multiple times.
+   */
+  start(): void {
+    if (this.running) return
+    this.running = true
+    this.scheduleNext(this.initialDelayMs)
+  }
+
+  /**
+   * Stop the cleanup loop and clear any pending timer.
+   */
+  stop(): void {
+    this.running = false
+    if (this.timer) {
+      clearTimeout(this.timer)
+      this.timer = null
+    }
+  }
+
+  /**
+   * Run a single cleanup pass. Exposed for testing and for ad-hoc invocation.
+   * @returns Number of audit events deleted
+   */
+  async runOnce(): Promise<number> {
+    if (!Number.isFinite(this.retentionDays) || this.retentionDays <= 0) {
+      return 0
+    }
+
+    try {
+      const cutoff = new Date(this.now().getTime() - this.retentionDays * MS_PER_DAY)
+      const deleted = await this.repository.deleteBefore(cutoff)
+      if (deleted > 0) {
+        this.logger?.info('Audit cleanup removed old events', {
+          retentionDays: this.retentionDays,
+          deleted,
+        })
+      }
+      return deleted
+    } catch (error) {
+      this.logger?.error('Audit cleanup failed', {
+        error: error instanceof Error ? error.message : String(error),
+      })
+      return 0
+    }
+  }
+
+  private scheduleNext(delayMs: number): void {
+    if (!this.running) return
+    if (this.timer) {
+      clearTimeout(this.timer)
+    }
+    this.timer = setTimeout(() => {
+      void this.tick()
+    }, Math.max(0, delayMs))
+  }
+
+  private async tick(): Promise<void> {
+    if (!this.running) return
+    try {
+      await this.runOnce()
+    } finally {
+      this.scheduleNext(this.intervalMs)
+    }
+  }
+}
@@ -0,0 +1,75 @@
+import type { AuditEventRepository } from '../db/AuditEventRepository.js'
+import type { UserRepository } from '../db/UserRepository.js'
+import type { AuditEvent, AuditEventFilter, RecordAuditEventInput } from '../types/audit.js'
+
+const DEFAULT_PAGE_SIZE = 50
+const MAX_PAGE_SIZE = 500
+
+export interface AuditLogLogger {
+  error(message: string, context?: Record<string, unknown>): void
+}
+
+/**
+ * Service for the append-only audit log of security-relevant actions.
+ *
+ * Events keep the actor's username, so they stay readable after the user is deleted.
+ * Old events are removed by the AuditCleanupService.
+ * Events are recorded after the action succeeded, so a failed write is logged
+ * instead of thrown and does not fail the action.
+ */
+export class AuditLogService {
+  constructor(
+    private auditEventRepository: AuditEventRepository,
+    private userRepository: UserRepository,
+    private logger?: AuditLogLogger
+  ) {}
+
+  /**
+   * Append an event to the audit log
+   * @returns The recorded event, or null if it could not be written
+   */
+  async record(input: RecordAuditEventInput): Promise<AuditEvent | null> {
+    try {
+      let actorUsername = input.actorUsername ?? null
+      if (input.actorUserId && actorUsername === null) {
+        const actor = await this.userRepository.getById(input.actorUserId)
+        actorUsername = actor?.username ?? null
+      }
+
+      return await this.auditEventRepository.record({ ...input, actorUsername })
+    } catch (error) {
+      this.logger?.error('Failed to write audit event', {
+        action: input.action,
+        targetType: input.targetType,
+        targetId: input.targetId,
+        error: error instanceof Error ? error.message : String(error),
+      })
+      return null
+    }
+  }
+
+  /**
+   * List events matching the filters, newest first
+   * @throws Error if the time range or page is invalid
+   */
+  async list(filter: AuditEventFilter = {}): Promise<{ events: AuditEvent[]; total: number }> {
+    const limit = filter.limit ?? DEFAULT_PAGE_SIZE
+    const offset = filter.offset ?? 0
+
+    if (!Number.isInteger(limit) || limit < 1 || limit > MAX_PAGE_SIZE) {
+      throw new Error(`limit must be between 1 and ${MAX_PAGE_SIZE}`)
+    }
+    if (!Number.isInteger(offset) || offset < 0) {
+      throw new Error('offset must be zero or more')
+    }
+    if (filter.from && filter.to && filter.from >= filter.to) {
+      throw new Error('from must be before to')
+    }
+
+    const [events, total] = await Promise.all([
+      this.auditEventRepository.list({ ...filter, limit, offset }),
+      this.auditEventRepository.count(filter),
+    ])
+    return { events, total }
+  }
+}
@@ -4,11 +4,21 @@ import type { RefreshTokenRepository } from '../db/RefreshTokenRepository.js'
 import type { AuthConfigRepository } from '../db/AuthConfigRepository.js'
 import type { InvitationRepository, Invitation } from '../db/InvitationRepository.js'
 import type { User, UserRole } from '../types/user.js'
-import type { TokenPair, DeviceInfo } from '../types/session.js'
+import type { TokenPair, DeviceInfo, Session } from '../types/session.js'
+import type { OidcConfig, OidcProfile } from '../types/oidc.js'
+import type { AuditActor, RecordAuditEventInput } from '../types/audit.js'
 import { TokenService } from './TokenService.js'
 import type { RegistrationResult, AuthenticationResult } from './PasskeyService.js'
 import { PasskeyService } from './PasskeyService.js'
+import { OidcService } from './OidcService.js'
+import type {
+  OidcAuthorization,
+  OidcAuthorizationOptions,
+  OidcCallbackResult,
+} from './OidcService.js'
+import type { AuditLogService } from './AuditLogService.js'
 import { nanoid } from 'nanoid'
+import { AUTH_CONFIG } from '../constants.js'
 
 /**
  * Challenge store entry
@@ -38,6 +48,9 @@ export interface RegistrationVerifyInput {
   /** WebAuthn RegistrationResponseJSON from the browser */
   credential: unknown
   invitationToken?: string
+  deviceInfo?: DeviceInfo
+  /** Address the request came from, for the audit log. The client cannot set it. */
+  ip?: string
 }
 
 /**
@@ -54,6 +67,8 @@ export interface AuthenticationVerifyInput {
   /** WebAuthn AuthenticationResponseJSON from the browser */
   credential: unknown
   deviceInfo?: DeviceInfo
+  /** Address the request came from, for the audit log. The client cannot set it. */
+  ip?: string
 }
 
 /**
@@ -75,10 +90,43 @@ export interface CreateInvitationInput {
 }
 
 /**
- * Main authentication service that orchestrates all auth operations
+ * OpenID Connect settings input.
+ * A clientSecret left out keeps the saved secret; null removes it.
+ */
+export interface OidcConfigInput extends Omit<OidcConfig, 'clientSecret'> {
+  clientSecret?: string | null
+}
+
+/**
+ * OpenID Connect login completion input
+ */
+export interface OidcLoginInput {
+  code: string
+  state: string
+  /** The browser binding returned when the login was started */
+  browserBinding?: string
+  deviceInfo?: DeviceInfo
+  /** Address the request came from, for the audit log. The client cannot set it. */
+  ip?: string
+}
+
+/**
+ * Public OpenID Connect status, shown on the login page
+ */
+export interface OidcStatus {
+  enabled: boolean
+  displayName: string | null
+}
+
+/**
+ * Main authentication service that orchestrates all auth operations.
+ * Logins, registrations and user management are recorded in the audit log if one is given.
  */
 export class AuthService {
   private challengeStore: Map<string, ChallengeEntry> = new Map()
+  /** Recently checked sessions, so access tokens do not look up their session on every request */
+  private checkedSessions: Map<string, { userId: string; active: boolean; checkedAt: number }> =
+    new Map()
 
   constructor(
     private userRepository: UserRepository,
@@ -87,7 +135,9 @@ export class AuthService {
     private authConfigRepository: AuthConfigRepository,
     private invitationRepository: InvitationRepository,
     private tokenService: TokenService,
-    private passkeyService: PasskeyService
+    private passkeyService: PasskeyService,
+    private oidcService: OidcService = new OidcService(),
+    private auditLog: AuditLogService | null = null
   ) {}
 
   // ========================================
@@ -253,14 +303,35 @@ export class AuthService {
       await this.invitationRepository.markUsed(invitation.id, user.id)
     }
 
+    await this.audit({
+      action: 'user.registered',
+      actorUserId: user.id,
+      targetType: 'user',
+      targetId: user.id,
+      details: { role, method: 'passkey' },
+      ip: input.ip,
+    })
+    if (invitation) {
+      await this.audit({
+        action: 'invitation.used',
+        actorUserId: user.id,
+        targetType: 'invitation',
+        targetId: invitation.id,
+        details: { role: invitation.role, createdBy: invitation.createdBy },
+        ip: input.ip,
+      })
+    }
+
     // Generate tokens
     const { tokens, refreshTokenData } = await this.tokenService.generateTokenPair(user)
 
     // Store refresh token
     await this.refreshTokenRepository.create({
+      id: refreshTokenData.tokenId,
       userId: user.id,
       tokenHash: refreshTokenData.tokenHash,
       expiresAt: refreshTokenData.expiresAt,
+      deviceInfo: input.deviceInfo,
     })
 
     // Update last login
@@ -319,16 +390,18 @@ export class AuthService {
       return { success: false, error: 'Challenge expired or not found' }
     }
 
+    const ip = input.ip
+
     // Find the credential
     const storedCredential = await this.passkeyCredentialRepository.getByCredentialId(credential.id)
     if (!storedCredential) {
-      return { success: false, error: 'Credential not found' }
+      return this.loginFailed('passkey', 'Credential not found', ip)
     }
 
     // Get the user
     const user = await this.userRepository.getById(storedCredential.userId)
     if (!user) {
-      return { success: false, error: 'User not found' }
+      return this.loginFailed('passkey', 'User not found', ip)
     }
 
     // Verify the authentication response
@@ -345,14 +418,12 @@ export class AuthService {
         }
       )
     } catch (error) {
-      return {
-        success: false,
-        error: error instanceof Error ? error.message : 'Verification failed',
-      }
+      const reason = error instanceof Error ? error.message : 'Verification failed'
+      return this.loginFailed('passkey', reason, ip, user.id)
     }
 
     if (!verification.verified) {
-      return { success: false, error: 'Authentication verification failed' }
+      return this.loginFailed('passkey', 'Authentication verification failed', ip, user.id)
     }
 
     // Update credential counter
@@ -366,6 +437,7 @@ export class AuthService {
 
     // Store refresh token
     await this.refreshTokenRepository.create({
+      id: refreshTokenData.tokenId,
       userId: user.id,
       tokenHash: refreshTokenData.tokenHash,
       expiresAt: refreshTokenData.expiresAt,
@@ -378,18 +450,258 @@ export class AuthService {
     // Clear challenge
     this.clearChallenge(challengeEntry.challenge)
 
+    await this.audit({
+      action: 'auth.login',
+      actorUserId: user.id,
+      targetType: 'user',
+      targetId: user.id,
+      details: { method: 'passkey' },
+      ip,
+    })
+
     return { success: true, user, tokens }
   }
 
+  // ========================================
+  // Single Sign-On (OpenID Connect)
+  // ========================================
+
+  /**
+   * Check whether OpenID Connect login is available
+   */
+  async getOidcStatus(): Promise<OidcStatus> {
+    const config = await this.authConfigRepository.getOidcConfig()
+    return { enabled: !!config, displayName: config?.displayName ?? null }
+  }
+
+  /**
+   * Get the OpenID Connect settings
+   */
+  async getOidcConfig(): Promise<OidcConfig | null> {
+    return this.authConfigRepository.getOidcConfig()
+  }
+
+  /**
+   * Validate and save the OpenID Connect settings.
+   * The provider's discovery document is fetched to check the issuer.
+   * @throws Error if a setting is invalid or the provider cannot be reached
+   */
+  async updateOidcConfig(input: OidcConfigInput): Promise<OidcConfig> {
+    if (!isHttpUrl(input.issuer)) throw new Error('Issuer must be an http(s) URL')
+    if (!input.clientId?.trim()) throw new Error('Client ID is required')
+    if (!isHttpUrl(input.redirectUri)) throw new Error('Redirect URI must be an http(s) URL')
+    if (!input.scopes?.includes('openid')) throw new Error('Scopes must include openid')
+    if (input.defaultRole !== 'admin' && input.defaultRole !== 'user') {
+      throw new Error('Default role must be admin or user')
+    }
+
+    const existing = await this.authConfigRepository.getOidcConfig()
+    const config: OidcConfig = {
+      issuer: input.issuer.trim(),
+      clientId: input.clientId.trim(),
+      clientSecret:
+        input.clientSecret === undefined
+          ? existing?.clientSecret
+          : input.clientSecret?.trim() || undefined,
+      redirectUri: input.redirectUri.trim(),
+      scopes: input.scopes,
+      displayName: input.displayName?.trim() || undefined,
+      autoProvision: input.autoProvision,
+      defaultRole: input.defaultRole,
+    }
+
+    this.oidcService.clearCache()
+    await this.oidcService.discover(config.issuer)
+    await this.authConfigRepository.setOidcConfig(config)
+    return config
+  }
+
+  /**
+   * Remove the OpenID Connect settings. Linked accounts stay linked.
+   */
+  async disableOidc(): Promise<void> {
+    await this.authConfigRepository.clearOidcConfig()
+    this.oidcService.clearCache()
+  }
+
+  /**
+   * Start an OpenID Connect login, or link the identity to a user
+   * @returns URL of the provider's login page and the binding the browser must keep
+   * @throws Error if OpenID Connect is not configured
+   */
+  async startOidcLogin(options: OidcAuthorizationOptions = {}): Promise<OidcAuthorization> {
+    const config = await this.authConfigRepository.getOidcConfig()
+    if (!config) {
+      throw new Error('Single sign-on is not configured')
+    }
+    return this.oidcService.createAuthorizationUrl(config, options)
+  }
+
+  /**
+   * Complete an OpenID Connect login and issue tokens.
+   * Unknown identities get an account if auto-provisioning is enabled.
+   */
+  async completeOidcLogin(input: OidcLoginInput): Promise<AuthResult> {
+    const config = await this.authConfigRepository.getOidcConfig()
+    if (!config) {
+      return { success: false, error: 'Single sign-on is not configured' }
+    }
+
+    const ip = input.ip
+
+    let result: OidcCallbackResult
+    try {
+      result = await this.oidcService.handleCallback(config, input)
+    } catch (error) {
+      const reason = error instanceof Error ? error.message : 'Single sign-on failed'
+      return this.loginFailed('oidc', reason, ip)
+    }
+    const { profile, linkUserId } = result
+
+    let user = await this.userRepository.getByOidcIdentity(profile)
+
+    if (linkUserId) {
+      if (user && user.id !== linkUserId) {
+        return this.loginFailed(
+          'oidc',
+          'This identity is already linked to another account',
+          ip,
+          linkUserId
+        )
+      }
+      await this.userRepository.linkOidcIdentity(linkUserId, profile)
+      user = await this.userRepository.getById(linkUserId)
+      await this.audit({
+        action: 'auth.oidc_linked',
+        actorUserId: linkUserId,
+        targetType: 'user',
+        targetId: linkUserId,
+        details: { issuer: profile.issuer, subject: profile.subject },
+        ip,
+      })
+    } else if (!user) {
+      if (!config.autoProvision) {
+        return this.loginFailed(
+          'oidc',
+          'No account is linked to this identity. Log in and link it from your profile.',
+          ip
+        )
+      }
+      const provisioned = await this.provisionOidcUser(config, profile)
+      if (!provisioned.user) {
+        return this.loginFailed('oidc', provisioned.error ?? 'Failed to create user', ip)
+      }
+      user = provisioned.user
+      await this.audit({
+        action: 'user.registered',
+        actorUserId: user.id,
+        targetType: 'user',
+        targetId: user.id,
+        details: { role: user.role, method: 'oidc' },
+        ip,
+      })
+    }
+
+    if (!user) {
+      return this.loginFailed('oidc', 'User not found', ip)
+    }
+
+    const tokens = await this.issueTokens(user, input.deviceInfo)
+    await this.audit({
+      action: 'auth.login',
+      actorUserId: user.id,
+      targetType: 'user',
+      targetId: user.id,
+      details: { method: 'oidc' },
+      ip,
+    })
+    return { success: true, user, tokens }
+  }
+
+  /**
+   * Remove the link between a user and their OpenID Connect identity
+   * @throws Error if the user has no passkey to log in with afterwards
+   */
+  async unlinkOidc(userId: string): Promise<void> {
+    const credentials = await this.passkeyCredentialRepository.getByUserId(userId)
+    if (credentials.length === 0) {
+      throw new Error('Cannot unlink single sign-on from an account without a passkey')
+    }
+    await this.userRepository.unlinkOidcIdentity(userId)
+    await this.audit({
+      action: 'auth.oidc_unlinked',
+      actorUserId: userId,
+      targetType: 'user',
+      targetId: userId,
+    })
+  }
+
+  /**
+   * Create an account for an identity that logged in for the first time
+   */
+  private async provisionOidcUser(
+    config: OidcConfig,
+    profile: OidcProfile
+  ): Promise<{ user?: User; error?: string }> {
+    if (!profile.username) {
+      return { error: 'The identity provider did not share a username' }
+    }
+
+    const existingUser = await this.userRepository.getByUsername(profile.username)
+    if (existingUser) {
+      return {
+        error: `An account named ${profile.username} already exists. Log in and link it from your profile.`,
+      }
+    }
+
+    // The first user is admin, like with passkey registration
+    const isFirstUser = await this.isFirstUser()
+    const created = await this.userRepository.create({
+      username: profile.username,
+      displayName: profile.displayName ?? undefined,
+      role: isFirstUser ? 'admin' : config.defaultRole,
+    })
+    await this.userRepository.linkOidcIdentity(created.id, profile)
+
+    const user = await this.userRepository.getById(created.id)
+    return user ? { user } : { error: 'Failed to create user' }
+  }
+
+  /**
+   * Generate and store tokens for a user who just logged in
+   */
+  private async issueTokens(user: User, deviceInfo?: DeviceInfo): Promise<TokenPair> {
+    const { tokens, refreshTokenData } = await this.tokenService.generateTokenPair(user)
+
+    await this.refreshTokenRepository.create({
+      id: refreshTokenData.tokenId,
+      userId: user.id,
+      tokenHash: refreshTokenData.tokenHash,
+      expiresAt: refreshTokenData.expiresAt,
+      deviceInfo,
+    })
+
+    await this.userRepository.updateLastLogin(user.id)
+
+    return tokens
+  }
+
   // ========================================
   // Token Management
   // ========================================
 
   /**
    * Verify an access token and return the payload
+   * @throws Error if the token is invalid or its session was revoked
    */
-  async verifyAccessToken(token: string): Promise<{ sub: string; username: string; role: UserRole }> {
-    return this.tokenService.verifyAccessToken(token)
+  async verifyAccessToken(
+    token: string
+  ): Promise<{ sub: string; username: string; role: UserRole; sid?: string }> {
+    const payload = await this.tokenService.verifyAccessToken(token)
+    if (payload.sid && !(await this.isSessionActive(payload.sid))) {
+      throw new Error('Session revoked')
+    }
+    return payload
   }
 
   /**
@@ -418,7 +730,8 @@ export class AuthService {
     }
 
     // Generate new access token only (keep the same refresh token)
-    const accessToken = await this.tokenService.generateAccessToken(user)
+    const accessToken = await this.tokenService.generateAccessToken(user, storedToken.id)
+    await this.refreshTokenRepository.updateLastUsed(storedToken.id, new Date())
 
     return {
       success: true,
@@ -436,6 +749,7 @@ export class AuthService {
   async revokeRefreshToken(refreshToken: string): Promise<void> {
     const tokenHash = this.tokenService.hashToken(refreshToken)
     await this.refreshTokenRepository.revokeByTokenHash(tokenHash)
+    this.checkedSessions.clear()
   }
 
   /**
@@ -443,6 +757,72 @@ export class AuthService {
    */
   async revokeAllRefreshTokens(userId: string): Promise<void> {
     await this.refreshTokenRepository.revokeAllByUserId(userId)
+    this.forgetSessions(userId)
+  }
+
+  // ========================================
+  // Session Management
+  // ========================================
+
+  /**
+   * List a user's active sessions, most recently used first
+   * @param currentSessionId Session of the request, which is flagged as current
+   */
+  async listSessions(userId: string, currentSessionId?: string | null): Promise<Session[]> {
+    const tokens = await this.refreshTokenRepository.getActiveByUserId(userId)
+
+    return tokens
+      .map((token) => ({
+        id: token.id,
+        userId: token.userId,
+        deviceInfo: token.deviceInfo,
+        createdAt: token.createdAt,
+        lastUsedAt: token.lastUsedAt,
+        expiresAt: token.expiresAt,
+        current: token.id === currentSessionId,
+      }))
+      .sort(
+        (a, b) =>
+          (b.lastUsedAt ?? b.createdAt).getTime() - (a.lastUsedAt ?? a.createdAt).getTime()
+      )
+  }
+
+  /**
+   * Revoke one of a user's sessions, together with its access token
+   * @returns false if the user has no active session with that ID
+   */
+  async revokeSession(userId: string, sessionId: string): Promise<boolean> {
+    const revoked = await this.refreshTokenRepository.revokeForUser(userId, sessionId)
+    this.checkedSessions.delete(sessionId)
+    return revoked
+  }
+
+  /**
+   * Whether a session is still active, checked at most every SESSION_CHECK_INTERVAL_MS
+   */
+  private async isSessionActive(sessionId: string): Promise<boolean> {
+    const checked = this.checkedSessions.get(sessionId)
+    if (checked && Date.now() - checked.checkedAt < AUTH_CONFIG.SESSION_CHECK_INTERVAL_MS) {
+      return checked.active
+    }
+
+    const token = await this.refreshTokenRepository.getById(sessionId)
+    const active = !!token && !token.revokedAt && token.expiresAt >= new Date()
+    if (token) {
+      this.checkedSessions.set(sessionId, { userId: token.userId, active, checkedAt: Date.now() })
+    }
+    return active
+  }
+
+  /**
+   * Look up the user's sessions again on their next request
+   */
+  private forgetSessions(userId: string): void {
+    for (const [sessionId, checked] of this.checkedSessions) {
+      if (checked.userId === userId) {
+        this.checkedSessions.delete(sessionId)
+      }
+    }
   }
 
   // ========================================
@@ -472,19 +852,47 @@ export class AuthService {
 
   /**
    * Update user role
+   * @param actor The admin making the change, for the audit log
    */
-  async updateUserRole(id: string, role: UserRole): Promise<User | null> {
-    return this.userRepository.update(id, { role })
+  async updateUserRole(id: string, role: UserRole, actor?: AuditActor): Promise<User | null> {
+    const previous = await this.userRepository.getById(id)
+    const user = await this.userRepository.update(id, { role })
+
+    if (user && previous && previous.role !== role) {
+      await this.audit({
+        action: 'user.role_changed',
+        actorUserId: actor?.userId,
+        targetType: 'user',
+        targetId: id,
+        details: { username: user.username, from: previous.role, to: role },
+        ip: actor?.ip,
+      })
+    }
+    return user
   }
 
   /**
    * Delete a user and all their data
+   * @param actor The admin deleting the user, for the audit log
    */
-  async deleteUser(id: string): Promise<void> {
+  async deleteUser(id: string, actor?: AuditActor): Promise<void> {
+    const user = await this.userRepository.getById(id)
     // Revoke all tokens first
     await this.refreshTokenRepository.revokeAllByUserId(id)
+    this.forgetSessions(id)
     // Delete user (cascades to credentials)
     await this.userRepository.delete(id)
+
+    if (user) {
+      await this.audit({
+        action: 'user.deleted',
+        actorUserId: actor?.userId,
+        targetType: 'user',
+        targetId: id,
+        details: { username: user.username, role: user.role },
+        ip: actor?.ip,
+      })
+    }
   }
 
   // ========================================
@@ -493,19 +901,34 @@ export class AuthService {
 
   /**
    * Create an invitation for a new user
+   * @param ip Address the invitation was created from, for the audit log
    */
-  async createInvitation(createdBy: string, input: CreateInvitationInput): Promise<Invitation> {
+  async createInvitation(
+    createdBy: string,
+    input: CreateInvitationInput,
+    ip?: string
+  ): Promise<Invitation> {
     // Check if username is already taken
     const existingUser = await this.userRepository.getByUsername(input.username)
     if (existingUser) {
       throw new Error('Username already exists')
     }
 
-    return this.invitationRepository.create({
+    const invitation = await this.invitationRepository.create({
       username: input.username,
       role: input.role,
       createdBy,
     })
+
+    await this.audit({
+      action: 'invitation.created',
+      actorUserId: createdBy,
+      targetType: 'invitation',
+      targetId: invitation.id,
+      details: { username: invitation.username, role: invitation.role },
+      ip,
+    })
+    return invitation
   }
 
   /**
@@ -524,9 +947,45 @@ export class AuthService {
 
   /**
    * Delete an invitation
+   * @param actor The admin deleting the invitation, for the audit log
    */
-  async deleteInvitation(id: string): Promise<void> {
-    return this.invitationRepository.delete(id)
+  async deleteInvitation(id: string, actor?: AuditActor): Promise<void> {
+    await this.invitationRepository.delete(id)
+    await this.audit({
+      action: 'invitation.deleted',
+      actorUserId: actor?.userId,
+      targetType: 'invitation',
+      targetId: id,
+      ip: actor?.ip,
+    })
+  }
+
+  // ========================================
+  // Audit Log (Private)
+  // ========================================
+
+  private async audit(input: RecordAuditEventInput): Promise<void> {
+    await this.auditLog?.record(input)
+  }
+
+  /**
+   * Record a rejected login attempt and return the failed result
+   */
+  private async loginFailed(
+    method: 'passkey' | 'oidc',
+    reason: string,
+    ip?: string,
+    userId?: string
+  ): Promise<AuthResult> {
+    await this.audit({
+      action: 'auth.login_failed',
+      actorUserId: userId,
+      targetType: userId ? 'user' : null,
+      targetId: userId,
+      details: { method, reason },
+      ip,
+    })
+    return { success: false, error: reason }
   }
 
   // ========================================
@@ -585,3 +1044,13 @@ export class AuthService {
     }
   }
 }
+
+function isHttpUrl(value: string | undefined): boolean {
+  if (!value) return false
+  try {
+    const url = new URL(value.trim())
+    return url.protocol === 'https:' || url.protocol === 'http:'
+  } catch {
+    return false
+  }
+}
@@ -278,6 +278,7 @@ export class ElectronAuthService {
     refreshTokenData: GeneratedRefreshToken
   ): Promise<void> {
     await this.refreshTokenRepository.create({
+      id: refreshTokenData.tokenId,
       userId,
       tokenHash: refreshTokenData.tokenHash,
       expiresAt: refreshTokenData.expiresAt,
@@ -0,0 +1,293 @@
+import { createHash, randomBytes, timingSafeEqual } from 'node:crypto'
+import { createRemoteJWKSet, jwtVerify } from 'jose'
+import type { JWTPayload } from 'jose'
+import { AUTH_CONFIG } from '../constants.js'
+import type { OidcConfig, OidcProfile } from '../types/oidc.js'
+
+/**
+ * The parts of the provider's discovery document used by the login flow
+ */
+export interface OidcProviderMetadata {
+  issuer: string
+  authorization_endpoint: string
+  token_endpoint: string
+  jwks_uri: string
+  token_endpoint_auth_methods_supported?: string[]
+}
+
+/**
+ * A started login, waiting for the provider to redirect back
+ */
+interface PendingLogin {
+  codeVerifier: string
+  nonce: string
+  issuer: string
+  /** Held by the browser that started the login, e.g. in a cookie */
+  browserBinding: string
+  /** Set when an authenticated user links their account instead of logging in */
+  linkUserId?: string
+  expiresAt: Date
+}
+
+/**
+ * Options for starting a login
+ */
+export interface OidcAuthorizationOptions {
+  /** Link the identity to this user instead of logging in */
+  linkUserId?: string
+}
+
+/**
+ * A started login
+ */
+export interface OidcAuthorization {
+  /** Provider URL to send the browser to */
+  authorizationUrl: string
+  /** Secret the browser must present with the callback, so a state cannot be used elsewhere */
+  browserBinding: string
+}
+
+/**
+ * Result of a completed authorization
+ */
+export interface OidcCallbackResult {
+  profile: OidcProfile
+  linkUserId?: string
+}
+
+type RemoteJwks = ReturnType<typeof createRemoteJWKSet>
+
+/**
+ * OpenID Connect client implementing the authorization code flow with PKCE.
+ *
+ * Flow:
+ * 1. createAuthorizationUrl() stores a PKCE verifier, nonce and browser binding under a random
+ *    state. The browser binding is kept by the browser that started the login.
+ * 2. The browser is sent to the provider and comes back with a code and the state
+ * 3. handleCallback() checks the browser binding, exchanges the code and verifies the ID token
+ *
+ * Pending logins are kept in memory, like passkey challenges.
+ */
+export class OidcService {
+  private metadataCache: Map<string, OidcProviderMetadata> = new Map()
+  private jwksCache: Map<string, RemoteJwks> = new Map()
+  private pendingLogins: Map<string, PendingLogin> = new Map()
+
+  /**
+   * Fetch the provider's discovery document
+   * @throws Error if the document cannot be fetched or is for another issuer
+   */
+  async discover(issuer: string): Promise<OidcProviderMetadata> {
+    const cached = this.metadataCache.get(issuer)
+    if (cached) return cached
+
+    const url = `${trimTrailingSlash(issuer)}/.well-known/openid-configuration`
+    let response: Response
+    try {
+      response = await fetch(url, { headers: { Accept: 'application/json' } })
+    } catch (error) {
+      const reason = error instanceof Error ? error.message : String(error)
+      throw new Error(`Failed to reach OpenID Connect provider: ${reason}`)
+    }
+    if (!response.ok) {
+      throw new Error(`Failed to fetch OpenID Connect discovery document: ${response.status}`)
+    }
+
+    const metadata = (await response.json()) as Partial<OidcProviderMetadata>
+    if (
+      !metadata.issuer ||
+      !metadata.authorization_endpoint ||
+      !metadata.token_endpoint ||
+      !metadata.jwks_uri
+    ) {
+      throw new Error('OpenID Connect discovery document is missing required endpoints')
+    }
+    if (trimTrailingSlash(metadata.issuer) !== trimTrailingSlash(issuer)) {
+      throw new Error(`Discovery document is for another issuer: ${metadata.issuer}`)
+    }
+
+    this.metadataCache.set(issuer, metadata as OidcProviderMetadata)
+    return metadata as OidcProviderMetadata
+  }
+
+  /**
+   * Forget cached discovery documents and keys, e.g. after the settings change
+   */
+  clearCache(): void {
+    this.metadataCache.clear()
+    this.jwksCache.clear()
+  }
+
+  /**
+   * Start a login and get the provider URL to send the browser to.
+   * The browser binding must be kept by the browser and sent back with the callback.
+   */
+  async createAuthorizationUrl(
+    config: OidcConfig,
+    options: OidcAuthorizationOptions = {}
+  ): Promise<OidcAuthorization> {
+    const metadata = await this.discover(config.issuer)
+
+    const state = randomBytes(16).toString('base64url')
+    const nonce = randomBytes(16).toString('base64url')
+    const codeVerifier = randomBytes(32).toString('base64url')
+    const codeChallenge = createHash('sha256').update(codeVerifier).digest('base64url')
+    const browserBinding = randomBytes(32).toString('base64url')
+
+    this.cleanupPendingLogins()
+    this.pendingLogins.set(state, {
+      codeVerifier,
+      nonce,
+      issuer: config.issuer,
+      browserBinding,
+      linkUserId: options.linkUserId,
+      expiresAt: new Date(Date.now() + AUTH_CONFIG.OIDC_LOGIN_TTL_MS),
+    })
+
+    const url = new URL(metadata.authorization_endpoint)
+    url.searchParams.set('response_type', 'code')
+    url.searchParams.set('client_id', config.clientId)
+    url.searchParams.set('redirect_uri', config.redirectUri)
+    url.searchParams.set('scope', config.scopes.join(' '))
+    url.searchParams.set('state', state)
+    url.searchParams.set('nonce', nonce)
+    url.searchParams.set('code_challenge', codeChallenge)
+    url.searchParams.set('code_challenge_method', 'S256')
+    return { authorizationUrl: url.toString(), browserBinding }
+  }
+
+  /**
+   * Complete a login with the code and state the provider redirected back with
+   * @throws Error if the state is unknown or expired, was started in another browser,
+   * or the ID token is invalid
+   */
+  async handleCallback(
+    config: OidcConfig,
+    input: { code: string; state: string; browserBinding?: string }
+  ): Promise<OidcCallbackResult> {
+    // A state can only be used once
+    const pending = this.pendingLogins.get(input.state)
+    this.pendingLogins.delete(input.state)
+    if (!pending || pending.expiresAt < new Date()) {
+      throw new Error('Login expired or not found, please try again')
+    }
+    if (!input.browserBinding || !safeEqual(input.browserBinding, pending.browserBinding)) {
+      throw new Error('Login was started in another browser, please try again')
+    }
+    if (pending.issuer !== config.issuer) {
+      throw new Error('OpenID Connect settings changed during login, please try again')
+    }
+
+    const metadata = await this.discover(config.issuer)
+    const idToken = await this.exchangeCode(config, metadata, input.code, pending.codeVerifier)
+
+    let payload: JWTPayload
+    try {
+      const result = await jwtVerify(idToken, this.getJwks(metadata.jwks_uri), {
+        issuer: metadata.issuer,
+        audience: config.clientId,
+      })
+      payload = result.payload
+    } catch (error) {
+      const reason = error instanceof Error ? error.message : String(error)
+      throw new Error(`Invalid ID token: ${reason}`)
+    }
+
+    if (payload['nonce'] !== pending.nonce) {
+      throw new Error('Invalid ID token: nonce does not match')
+    }
+    if (!payload.sub) {
+      throw new Error('Invalid ID token: missing subject')
+    }
+
+    const email = typeof payload['email'] === 'string' ? payload['email'] : null
+    const preferredUsername =
+      typeof payload['preferred_username'] === 'string' ? payload['preferred_username'] : null
+
+    return {
+      profile: {
+        issuer: metadata.issuer,
+        subject: payload.sub,
+        username: preferredUsername ?? email?.split('@')[0] ?? null,
+        displayName: typeof payload['name'] === 'string' ? payload['name'] : null,
+        email,
+      },
+      linkUserId: pending.linkUserId,
+    }
+  }
+
+  /**
+   * Exchange an authorization code for an ID token
+   */
+  private async exchangeCode(
+    config: OidcConfig,
+    metadata: OidcProviderMetadata,
+    code: string,
+    codeVerifier: string
+  ): Promise<string> {
+    const body = new URLSearchParams({
+      grant_type: 'authorization_code',
+      code,
+      redirect_uri: config.redirectUri,
+      code_verifier: codeVerifier,
+    })
+    const headers: Record<string, string> = {
+      'Content-Type': 'application/x-www-form-urlencoded',
+      Accept: 'application/json',
+    }
+
+    // client_secret_basic is the default when the provider does not list its methods
+    const authMethods = metadata.token_endpoint_auth_methods_supported ?? ['client_secret_basic']
+    if (config.clientSecret && authMethods.includes('client_secret_basic')) {
+      const clientId = encodeURIComponent(config.clientId)
+      const credentials = `${clientId}:${encodeURIComponent(config.clientSecret)}`
+      headers['Authorization'] = `Basic ${Buffer.from(credentials).toString('base64')}`
+    } else {
+      body.set('client_id', config.clientId)
+      if (config.clientSecret) body.set('client_secret', config.clientSecret)
+    }
+
+    const response = await fetch(metadata.token_endpoint, { method: 'POST', headers, body })
+    const data = (await response.json().catch(() => ({}))) as {
+      id_token?: string
+      error?: string
+      error_description?: string
+    }
+    if (!response.ok) {
+      const reason = data.error_description ?? data.error ?? String(response.status)
+      throw new Error(`Failed to exchange authorization code: ${reason}`)
+    }
+    if (!data.id_token) {
+      throw new Error('Token response did not include an ID token')
+    }
+    return data.id_token
+  }
+
+  private getJwks(jwksUri: string): RemoteJwks {
+    let jwks = this.jwksCache.get(jwksUri)
+    if (!jwks) {
+      jwks = createRemoteJWKSet(new URL(jwksUri))
+      this.jwksCache.set(jwksUri, jwks)
+    }
+    return jwks
+  }
+
+  private cleanupPendingLogins(): void {
+    const now = new Date()
+    for (const [state, pending] of this.pendingLogins) {
+      if (pending.expiresAt < now) {
+        this.pendingLogins.delete(state)
+      }
+    }
+  }
+}
+
+function safeEqual(a: string, b: string): boolean {
+  const left = Buffer.from(a)
+  const right = Buffer.from(b)
+  return left.length === right.length && timingSafeEqual(left, right)
+}
+
+function trimTrailingSlash(url: string): string {
+  return url.replace(/\/+$/, '')
+}
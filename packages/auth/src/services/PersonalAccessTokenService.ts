@@ -0,0 +1,136 @@
+import { createHash, randomBytes } from 'node:crypto'
+import { isPersonalAccessTokenScope } from '@stina/shared'
+import type { PersonalAccessTokenScope } from '@stina/shared'
+import { AUTH_CONFIG } from '../constants.js'
+import type { PersonalAccessTokenRepository } from '../db/PersonalAccessTokenRepository.js'
+import type { PersonalAccessTokenData } from '../types/session.js'
+
+/** Longest allowed name of a personal access token */
+const MAX_NAME_LENGTH = 100
+
+/**
+ * Create personal access token input
+ */
+export interface CreatePersonalAccessTokenOptions {
+  name: string
+  scopes: PersonalAccessTokenScope[]
+  /** Days until the token expires. Leave out for a token that never expires */
+  expiresInDays?: number
+}
+
+/**
+ * A newly created personal access token
+ */
+export interface CreatedPersonalAccessToken {
+  /** The token itself. Only its hash is stored, so it cannot be shown again */
+  token: string
+  data: PersonalAccessTokenData
+}
+
+/**
+ * Service for long-lived, scoped personal access tokens used by scripts.
+ *
+ * Tokens are random strings with a recognizable prefix, so the auth plugin can
+ * tell them apart from JWT access tokens. Only a SHA-256 hash is stored.
+ */
+export class PersonalAccessTokenService {
+  constructor(private repository: PersonalAccessTokenRepository) {}
+
+  /**
+   * Check whether a bearer token looks like a personal access token
+   */
+  static isPersonalAccessToken(token: string): boolean {
+    return token.startsWith(AUTH_CONFIG.PERSONAL_ACCESS_TOKEN_PREFIX)
+  }
+
+  /**
+   * Create a token for a user
+   * @throws Error if the name, scopes or expiry are invalid
+   */
+  async create(
+    userId: string,
+    options: CreatePersonalAccessTokenOptions
+  ): Promise<CreatedPersonalAccessToken> {
+    const name = options.name?.trim() ?? ''
+    if (!name || name.length > MAX_NAME_LENGTH) {
+      throw new Error(`Name must be 1-${MAX_NAME_LENGTH} characters`)
+    }
+
+    const scopes = [...new Set(options.scopes ?? [])]
+    if (scopes.length === 0) {
+      throw new Error('At least one scope is required')
+    }
+    const unknownScope = scopes.find((scope) => !isPersonalAccessTokenScope(scope))
+    if (unknownScope) {
+      throw new Error(`Unknown scope: ${unknownScope}`)
+    }
+
+    let expiresAt: Date | null = null
+    if (options.expiresInDays !== undefined) {
+      if (!Number.isInteger(options.expiresInDays) || options.expiresInDays < 1) {
+        throw new Error('expiresInDays must be a positive whole number')
+      }
+      expiresAt = new Date(Date.now() + options.expiresInDays * 24 * 60 * 60 * 1000)
+    }
+
+    const prefix = AUTH_CONFIG.PERSONAL_ACCESS_TOKEN_PREFIX
+    const token = `${prefix}${randomBytes(32).toString('base64url')}`
+    const data = await this.repository.create({
+      userId,
+      name,
+      tokenHash: this.hashToken(token),
+      tokenPrefix: token.slice(0, prefix.length + 4),
+      scopes,
+      expiresAt,
+    })
+
+    return { token, data }
+  }
+
+  /**
+   * Verify a token and record that it was used
+   * @returns The token data, or null if the token is unknown, revoked or expired
+   */
+  async verify(token: string): Promise<PersonalAccessTokenData | null> {
+    if (!PersonalAccessTokenService.isPersonalAccessToken(token)) return null
+
+    const data = await this.repository.getValidByTokenHash(this.hashToken(token))
+    if (!data) return null
+
+    // Scripts can make many requests in a row, so the timestamp is only updated now and then
+    const now = new Date()
+    const lastUsedAt = data.lastUsedAt?.getTime() ?? 0
+    if (now.getTime() - lastUsedAt >= AUTH_CONFIG.PERSONAL_ACCESS_TOKEN_LAST_USED_INTERVAL_MS) {
+      await this.repository.updateLastUsed(data.id, now)
+      data.lastUsedAt = now
+    }
+
+    return data
+  }
+
+  /**
+   * List a user's tokens that have not been revoked
+   */
+  async list(userId: string): Promise<PersonalAccessTokenData[]> {
+    return this.repository.getActiveByUserId(userId)
+  }
+
+  /**
+   * Revoke one of a user's tokens
+   * @returns true if the token was found and revoked
+   */
+  async revoke(userId: string, id: string): Promise<boolean> {
+    return this.repository.revoke(userId, id)
+  }
+
+  /**
+   * Revoke all of a user's tokens
+   */
+  async revokeAll(userId: string): Promise<void> {
+    await this.repository.revokeAllByUserId(userId)
+  }
+
+  private hashToken(token: string): string {
+    return createHash('sha256').update(token).digest('hex')
+  }
+}
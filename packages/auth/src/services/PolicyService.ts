@@ -0,0 +1,166 @@
+import { USER_CAPABILITIES, isUserCapability } from '@stina/shared'
+import type { UserCapability } from '@stina/shared'
+import { DEFAULT_ROLE_CAPABILITIES } from '../constants.js'
+import type { CapabilityPolicyRepository } from '../db/CapabilityPolicyRepository.js'
+import type { UserRepository } from '../db/UserRepository.js'
+import type { CapabilityPolicy, EffectivePolicy } from '../types/policy.js'
+import type { UserRole } from '../types/user.js'
+
+/**
+ * Service deciding what non-admin users may do.
+ *
+ * Each role has a policy with capabilities and the model configs its users may use.
+ * A user policy overrides the values it sets for a single user.
+ * Admins always have every capability and may use every model config.
+ */
+export class PolicyService {
+  constructor(
+    private policyRepository: CapabilityPolicyRepository,
+    private userRepository: UserRepository
+  ) {}
+
+  /**
+   * Get what a user may do. Unknown users may do nothing.
+   */
+  async getEffectivePolicy(userId: string): Promise<EffectivePolicy> {
+    const user = await this.userRepository.getById(userId)
+    if (!user) {
+      return { capabilities: [], allowedModelConfigIds: [] }
+    }
+
+    if (user.role === 'admin') {
+      return { capabilities: [...USER_CAPABILITIES], allowedModelConfigIds: null }
+    }
+
+    const rolePolicy = await this.getRolePolicy(user.role)
+    const userPolicy = await this.policyRepository.getUserPolicy(userId)
+
+    return {
+      capabilities: userPolicy?.capabilities ?? rolePolicy.capabilities ?? [],
+      allowedModelConfigIds: userPolicy?.allowedModelConfigIds ?? rolePolicy.allowedModelConfigIds,
+    }
+  }
+
+  /**
+   * Check whether a user has a capability
+   */
+  async can(userId: string, capability: UserCapability): Promise<boolean> {
+    const policy = await this.getEffectivePolicy(userId)
+    return policy.capabilities.includes(capability)
+  }
+
+  /**
+   * Check whether a user may use a model config
+   */
+  async canUseModelConfig(userId: string, modelConfigId: string): Promise<boolean> {
+    const allowedModelConfigIds = await this.getAllowedModelConfigIds(userId)
+    return allowedModelConfigIds === null || allowedModelConfigIds.includes(modelConfigId)
+  }
+
+  /**
+   * Get the model configs a user may use, or null if they may use every model config
+   */
+  async getAllowedModelConfigIds(userId: string): Promise<string[] | null> {
+    const { allowedModelConfigIds } = await this.getEffectivePolicy(userId)
+    return allowedModelConfigIds
+  }
+
+  // ===========================================================================
+  // Role Policies
+  // ===========================================================================
+
+  /**
+   * Get the policy of a role, with the built-in capabilities if none are stored
+   */
+  async getRolePolicy(role: UserRole): Promise<CapabilityPolicy> {
+    const stored = await this.policyRepository.getRolePolicy(role)
+
+    return {
+      capabilities: stored?.capabilities ?? [...DEFAULT_ROLE_CAPABILITIES[role]],
+      allowedModelConfigIds: stored?.allowedModelConfigIds ?? null,
+    }
+  }
+
+  /**
+   * Replace the policy of a role
+   * @throws Error for the admin role or an invalid policy
+   */
+  async setRolePolicy(role: UserRole, policy: CapabilityPolicy): Promise<CapabilityPolicy> {
+    if (role === 'admin') {
+      throw new Error('Admins always have every capability')
+    }
+
+    await this.policyRepository.setRolePolicy(role, this.normalizePolicy(policy))
+    return this.getRolePolicy(role)
+  }
+
+  // ===========================================================================
+  // User Policies
+  // ===========================================================================
+
+  /**
+   * Get the policy of a single user. Null values use the role's policy.
+   */
+  async getUserPolicy(userId: string): Promise<CapabilityPolicy> {
+    const stored = await this.policyRepository.getUserPolicy(userId)
+    return stored ?? { capabilities: null, allowedModelConfigIds: null }
+  }
+
+  /**
+   * Replace the policy of a single user
+   * @throws Error if the user is not found, is an admin, or the policy is invalid
+   */
+  async setUserPolicy(userId: string, policy: CapabilityPolicy): Promise<CapabilityPolicy> {
+    const user = await this.userRepository.getById(userId)
+    if (!user) {
+      throw new Error('User not found')
+    }
+    if (user.role === 'admin') {
+      throw new Error('Admins always have every capability')
+    }
+
+    const normalized = this.normalizePolicy(policy)
+    await this.policyRepository.setUserPolicy(userId, normalized)
+    return normalized
+  }
+
+  /**
+   * Remove the policy of a single user, so the role's policy applies again
+   * @returns true if a policy was removed
+   */
+  async deleteUserPolicy(userId: string): Promise<boolean> {
+    return this.policyRepository.deleteUserPolicy(userId)
+  }
+
+  /**
+   * Validate a policy and remove duplicate values
+   * @throws Error if the policy contains unknown capabilities or invalid model config IDs
+   */
+  private normalizePolicy(policy: CapabilityPolicy): CapabilityPolicy {
+    if (!policy) {
+      throw new Error('Policy is required')
+    }
+    const { capabilities, allowedModelConfigIds } = policy
+
+    if (capabilities !== null && !Array.isArray(capabilities)) {
+      throw new Error('capabilities must be an array or null')
+    }
+    const unknown = capabilities?.filter((capability) => !isUserCapability(capability)) ?? []
+    if (unknown.length > 0) {
+      throw new Error(`Unknown capabilities: ${unknown.join(', ')}`)
+    }
+
+    if (
+      allowedModelConfigIds !== null &&
+      (!Array.isArray(allowedModelConfigIds) ||
+        allowedModelConfigIds.some((id) => typeof id !== 'string' || id === ''))
+    ) {
+      throw new Error('allowedModelConfigIds must be an array of model config IDs or null')
+    }
+
+    return {
+      capabilities: capabilities ? [...new Set(capabilities)] : null,
+      allowedModelConfigIds: allowedModelConfigIds ? [...new Set(allowedModelConfigIds)] : null,
+    }
+  }
+}
@@ -45,15 +45,20 @@ export class TokenService {
 
   /**
    * Generate an access token (short-lived, for API calls)
+   * @param sessionId ID of the stored refresh token, to tell which session a request belongs to
    */
-  async generateAccessToken(user: {
-    id: string
-    username: string
-    role: UserRole
-  }): Promise<string> {
+  async generateAccessToken(
+    user: {
+      id: string
+      username: string
+      role: UserRole
+    },
+    sessionId?: string
+  ): Promise<string> {
     return new jose.SignJWT({
       username: user.username,
       role: user.role,
+      ...(sessionId ? { sid: sessionId } : {}),
     })
       .setProtectedHeader({ alg: 'HS256' })
       .setSubject(user.id)
@@ -89,17 +94,16 @@ export class TokenService {
   }
 
   /**
-   * Generate a token pair (access + refresh)
+   * Generate a token pair (access + refresh).
+   * Store the refresh token with refreshTokenData.tokenId as ID, which the access token refers to.
    */
   async generateTokenPair(user: {
     id: string
     username: string
     role: UserRole
   }): Promise<{ tokens: TokenPair; refreshTokenData: GeneratedRefreshToken }> {
-    const [accessToken, refreshTokenData] = await Promise.all([
-      this.generateAccessToken(user),
-      this.generateRefreshToken(user.id),
-    ])
+    const refreshTokenData = await this.generateRefreshToken(user.id)
+    const accessToken = await this.generateAccessToken(user, refreshTokenData.tokenId)
 
     return {
       tokens: {
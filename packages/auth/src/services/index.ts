@@ -19,8 +19,32 @@ export type {
   AuthenticationVerifyInput,
   AuthResult,
   CreateInvitationInput,
+  OidcConfigInput,
+  OidcLoginInput,
+  OidcStatus,
 } from './AuthService.js'
 
+export { PersonalAccessTokenService } from './PersonalAccessTokenService.js'
+export type {
+  CreatePersonalAccessTokenOptions,
+  CreatedPersonalAccessToken,
+} from './PersonalAccessTokenService.js'
+
+export { PolicyService } from './PolicyService.js'
+
+export { AuditLogService } from './AuditLogService.js'
+export type { AuditLogLogger } from './AuditLogService.js'
+export { AuditCleanupService, DEFAULT_AUDIT_RETENTION_DAYS } from './AuditCleanupService.js'
+export type { AuditCleanupServiceOptions, AuditCleanupLogger } from './AuditCleanupService.js'
+
+export { OidcService } from './OidcService.js'
+export type {
+  OidcProviderMetadata,
+  OidcAuthorization,
+  OidcAuthorizationOptions,
+  OidcCallbackResult,
+} from './OidcService.js'
+
 export { DefaultUserService } from './DefaultUserService.js'
 
 export { ElectronAuthService } from './ElectronAuthService.js'
@@ -0,0 +1,57 @@
+import type { AuditAction } from '@stina/shared'
+
+/**
+ * An entry in the audit log
+ */
+export interface AuditEvent {
+  id: string
+  createdAt: Date
+  /** User who performed the action, or null if not known (e.g. a failed login) */
+  actorUserId: string | null
+  /** Username of the actor at the time of the action */
+  actorUsername: string | null
+  action: AuditAction
+  /** Kind of thing the action was performed on, e.g. "user" or "extension" */
+  targetType: string | null
+  targetId: string | null
+  details: Record<string, unknown> | null
+  ip: string | null
+}
+
+/**
+ * Input for recording an audit event
+ */
+export interface RecordAuditEventInput {
+  action: AuditAction
+  actorUserId?: string | null
+  /** Looked up from actorUserId if left out */
+  actorUsername?: string | null
+  targetType?: string | null
+  targetId?: string | null
+  details?: Record<string, unknown> | null
+  ip?: string | null
+}
+
+/**
+ * Who performed an action, passed to services that record audit events
+ */
+export interface AuditActor {
+  userId: string
+  ip?: string
+}
+
+/**
+ * Filters for listing audit events
+ */
+export interface AuditEventFilter {
+  action?: AuditAction
+  actorUserId?: string
+  targetType?: string
+  targetId?: string
+  /** Only events at or after this time */
+  from?: Date
+  /** Only events before this time */
+  to?: Date
+  limit?: number
+  offset?: number
+}
@@ -5,4 +5,9 @@ export type {
   TokenPair,
   RefreshTokenData,
   DeviceInfo,
+  Session,
+  PersonalAccessTokenData,
 } from './session.js'
+export type { OidcConfig, OidcIdentity, OidcProfile } from './oidc.js'
+export type { CapabilityPolicy, EffectivePolicy } from './policy.js'
+export type { AuditEvent, RecordAuditEventInput, AuditActor, AuditEventFilter } from './audit.js'
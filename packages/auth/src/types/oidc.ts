@@ -0,0 +1,40 @@
+import type { UserRole } from './user.js'
+
+/**
+ * OpenID Connect provider settings, stored in auth_config
+ */
+export interface OidcConfig {
+  /** Issuer URL, used for discovery (`/.well-known/openid-configuration`) */
+  issuer: string
+  clientId: string
+  /** Client secret. Leave out for public clients, which rely on PKCE alone */
+  clientSecret?: string
+  /** URL the provider redirects back to, e.g. https://stina.example.com/auth/oidc/callback */
+  redirectUri: string
+  /** Scopes to request. Must include openid */
+  scopes: string[]
+  /** Name of the provider shown on the login button, e.g. "Authelia" */
+  displayName?: string
+  /** Create an account the first time an unknown identity logs in */
+  autoProvision: boolean
+  /** Role of auto-provisioned users */
+  defaultRole: UserRole
+}
+
+/**
+ * OpenID Connect identity, identified by issuer and subject
+ */
+export interface OidcIdentity {
+  issuer: string
+  subject: string
+}
+
+/**
+ * Identity and profile claims from a verified ID token
+ */
+export interface OidcProfile extends OidcIdentity {
+  /** preferred_username, or the local part of the email address */
+  username: string | null
+  displayName: string | null
+  email: string | null
+}
@@ -0,0 +1,22 @@
+import type { UserCapability } from '@stina/shared'
+
+/**
+ * Capability policy of a role or a single user.
+ * Null values are not set: role policies then use the built-in defaults,
+ * and user policies use their role's policy.
+ */
+export interface CapabilityPolicy {
+  /** Capabilities granted */
+  capabilities: UserCapability[] | null
+  /** Model configs that may be used (null for all) */
+  allowedModelConfigIds: string[] | null
+}
+
+/**
+ * What a user may do after combining the role and user policies
+ */
+export interface EffectivePolicy {
+  capabilities: UserCapability[]
+  /** Model configs the user may use, or null for all */
+  allowedModelConfigIds: string[] | null
+}
@@ -1,3 +1,4 @@
+import type { PersonalAccessTokenScope } from '@stina/shared'
 import type { UserRole } from './user.js'
 
 /**
@@ -10,6 +11,8 @@ export interface AccessTokenPayload {
   username: string
   /** User role */
   role: UserRole
+  /** ID of the session (refresh token) the access token was issued for */
+  sid?: string
   /** Issued at timestamp */
   iat: number
   /** Expiration timestamp */
@@ -48,6 +51,7 @@ export interface RefreshTokenData {
   expiresAt: Date
   createdAt: Date
   revokedAt: Date | null
+  lastUsedAt: Date | null
   deviceInfo: DeviceInfo | null
 }
 
@@ -58,3 +62,32 @@ export interface DeviceInfo {
   userAgent?: string
   ip?: string
 }
+
+/**
+ * An active session (a refresh token that is not revoked or expired)
+ */
+export interface Session {
+  id: string
+  userId: string
+  deviceInfo: DeviceInfo | null
+  createdAt: Date
+  lastUsedAt: Date | null
+  expiresAt: Date
+  /** Whether this is the session the request was made with */
+  current: boolean
+}
+
+/**
+ * Personal access token data stored in database
+ */
+export interface PersonalAccessTokenData {
+  id: string
+  userId: string
+  name: string
+  tokenPrefix: string
+  scopes: PersonalAccessTokenScope[]
+  expiresAt: Date | null
+  createdAt: Date
+  lastUsedAt: Date | null
+  revokedAt: Date | null
+}
@@ -14,6 +14,8 @@ export interface User {
   createdAt: Date
   updatedAt: Date
   lastLoginAt: Date | null
+  /** Whether the user is linked to an OpenID Connect identity */
+  oidcLinked: boolean
 }
 
 /**
@@ -1,12 +1,49 @@
-import type { ToolRegistry, RegisteredTool, ToolExecutionContext } from '@stina/chat'
+import type {
+  ToolRegistry,
+  RegisteredTool,
+  ToolExecutionContext,
+  MemoryService,
+  ITaskRepository,
+  ReminderService,
+} from '@stina/chat'
 import type { BuiltinTool, BuiltinToolContext, BuiltinToolFactory } from './types.js'
-import { createDateTimeTool } from './tools/index.js'
-
-/** Extension ID used for all built-in tools */
-export const BUILTIN_EXTENSION_ID = 'stina.builtin'
+import {
+  createDateTimeTool,
+  createRememberTool,
+  createRecallTool,
+  createForgetTool,
+  createTaskCreateTool,
+  createTaskListTool,
+  createTaskUpdateTool,
+  createTaskCompleteTool,
+  createTaskDeleteTool,
+  createReminderCreateTool,
+  createReminderScheduleTool,
+  createReminderListTool,
+  createReminderUpdateTool,
+  createReminderCompleteTool,
+  createReminderDeleteTool,
+} from './tools/index.js'
+import { BUILTIN_EXTENSION_ID, type BuiltinJobScheduler } from './reminders.js'
 
 /** All built-in tool factories */
-const builtinToolFactories: BuiltinToolFactory[] = [createDateTimeTool]
+const builtinToolFactories: BuiltinToolFactory[] = [
+  createDateTimeTool,
+  createRememberTool,
+  createRecallTool,
+  createForgetTool,
+  createTaskCreateTool,
+  createTaskListTool,
+  createTaskUpdateTool,
+  createTaskCompleteTool,
+  createTaskDeleteTool,
+  createReminderCreateTool,
+  createReminderScheduleTool,
+  createReminderListTool,
+  createReminderUpdateTool,
+  createReminderCompleteTool,
+  createReminderDeleteTool,
+]
 
 /**
  * Convert a BuiltinTool to RegisteredTool format.
@@ -35,6 +72,26 @@ export interface RegisterBuiltinToolsOptions {
    * @returns The IANA timezone string (e.g., "Europe/Stockholm") or undefined if not set
    */
   getTimezone: () => Promise<string | undefined>
+  /**
+   * Get the long-term memory service for a user.
+   * Required for the memory tools to work.
+   */
+  getMemoryService?: (userId: string) => MemoryService | undefined
+  /**
+   * Get the task repository for a user.
+   * Required for the task tools to work.
+   */
+  getTaskRepository?: (userId: string) => ITaskRepository | undefined
+  /**
+   * Get the reminder service for a user.
+   * Required for the reminder tools to work.
+   */
+  getReminderService?: (userId: string) => ReminderService | undefined
+  /**
+   * Scheduler for built-in jobs.
+   * Required for the scheduled reminder tool to work.
+   */
+  scheduler?: BuiltinJobScheduler
 }
 
 /**
@@ -48,6 +105,10 @@ export interface RegisterBuiltinToolsOptions {
 export function registerBuiltinTools(registry: ToolRegistry, options: RegisterBuiltinToolsOptions): number {
   const context: BuiltinToolContext = {
     getTimezone: options.getTimezone,
+    getMemoryService: options.getMemoryService,
+    getTaskRepository: options.getTaskRepository,
+    getReminderService: options.getReminderService,
+    scheduler: options.scheduler,
   }
 
   let count = 0
@@ -74,4 +135,29 @@ export function getBuiltinTools(context: BuiltinToolContext): BuiltinTool[] {
 
 // Re-export types and individual tool factories
 export type { BuiltinTool, BuiltinToolContext, BuiltinToolFactory } from './types.js'
-export { createDateTimeTool } from './tools/index.js'
+export {
+  createDateTimeTool,
+  createRememberTool,
+  createRecallTool,
+  createForgetTool,
+  createTaskCreateTool,
+  createTaskListTool,
+  createTaskUpdateTool,
+  createTaskCompleteTool,
+  createTaskDeleteTool,
+  createReminderCreateTool,
+  createReminderScheduleTool,
+  createReminderListTool,
+  createReminderUpdateTool,
+  createReminderCompleteTool,
+  createReminderDeleteTool,
+} from './tools/index.js'
+export {
+  BUILTIN_EXTENSION_ID,
+  SCHEDULED_REMINDER_JOB_PREFIX,
+  createReminderScheduler,
+  handleBuiltinJobFire,
+  type BuiltinJobScheduler,
+  type BuiltinJobFire,
+  type BuiltinJobFireOptions,
+} from './reminders.js'
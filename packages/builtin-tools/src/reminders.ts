@@ -0,0 +1,137 @@
+import type { ReminderScheduler, ReminderService } from '@stina/chat'
+import { createTranslator } from '@stina/i18n'
+import type { SchedulerSchedule } from '@stina/scheduler'
+import { formatDate } from './tools/shared.js'
+
+/** Extension ID used for all built-in tools and their scheduler jobs */
+export const BUILTIN_EXTENSION_ID = 'stina.builtin'
+
+/** Prefix of scheduler job IDs for reminders */
+const REMINDER_JOB_PREFIX = 'reminder:'
+
+/** Prefix of scheduler job IDs for scheduled (possibly recurring) reminders */
+export const SCHEDULED_REMINDER_JOB_PREFIX = 'scheduled-reminder:'
+
+/**
+ * The parts of SchedulerService used for reminders
+ */
+export interface BuiltinJobScheduler {
+  schedule(
+    extensionId: string,
+    job: {
+      id: string
+      schedule: SchedulerSchedule
+      payload?: Record<string, unknown>
+      misfire?: 'run_once' | 'skip'
+      userId: string
+    }
+  ): void | Promise<void>
+  cancel(extensionId: string, jobId: string): void | Promise<void>
+  /**
+   * Get the next run times of a schedule without registering a job.
+   * @throws If the schedule is invalid
+   */
+  getUpcomingRuns(schedule: SchedulerSchedule, count: number): Date[]
+}
+
+/**
+ * Create a ReminderScheduler for one user that schedules each reminder as a
+ * one-off scheduler job owned by the built-in extension.
+ */
+export function createReminderScheduler(scheduler: BuiltinJobScheduler, userId: string): ReminderScheduler {
+  return {
+    async schedule(reminder) {
+      await scheduler.schedule(BUILTIN_EXTENSION_ID, {
+        id: `${REMINDER_JOB_PREFIX}${reminder.id}`,
+        schedule: { type: 'at', at: reminder.remindAt.toISOString() },
+        payload: { reminderId: reminder.id },
+        // Deliver reminders missed while the app was closed
+        misfire: 'run_once',
+        userId,
+      })
+    },
+    async cancel(reminderId) {
+      await scheduler.cancel(BUILTIN_EXTENSION_ID, `${REMINDER_JOB_PREFIX}${reminderId}`)
+    },
+  }
+}
+
+/**
+ * Fired scheduler job owned by the built-in extension
+ */
+export interface BuiltinJobFire {
+  id: string
+  userId: string
+  payload?: Record<string, unknown>
+  scheduledFor?: string
+}
+
+export interface BuiltinJobFireOptions {
+  getReminderService: (userId: string) => ReminderService | undefined
+  /** Append an instruction to the user's active conversation */
+  appendInstruction: (userId: string, text: string) => Promise<void>
+  /** The user's language, for the instruction text. Defaults to English */
+  getLanguage?: (userId: string) => Promise<string | undefined>
+  /** The user's timezone, for the time in the instruction text */
+  getTimezone?: (userId: string) => Promise<string | undefined>
+}
+
+/**
+ * Handle a fired scheduler job owned by the built-in extension.
+ * Due reminders are appended as an instruction to the user's active conversation.
+ * @returns True if the job was a built-in job
+ */
+export async function handleBuiltinJobFire(
+  job: BuiltinJobFire,
+  options: BuiltinJobFireOptions
+): Promise<boolean> {
+  if (job.id.startsWith(SCHEDULED_REMINDER_JOB_PREFIX)) {
+    await deliverScheduledReminder(job, options)
+    return true
+  }
+  if (!job.id.startsWith(REMINDER_JOB_PREFIX)) return false
+
+  const reminderId = job.id.slice(REMINDER_JOB_PREFIX.length)
+  const reminder = await options.getReminderService(job.userId)?.markFired(reminderId)
+  if (!reminder) return true
+
+  const [language, timezone] = await Promise.all([
+    options.getLanguage?.(job.userId),
+    options.getTimezone?.(job.userId),
+  ])
+  const { t } = createTranslator(language ?? 'en')
+  await options.appendInstruction(
+    job.userId,
+    t('tools.builtin.reminder_due.instruction', {
+      id: reminder.id,
+      text: reminder.text,
+      time: formatDate(reminder.remindAt, { timezone }),
+    })
+  )
+  return true
+}
+
+/**
+ * Append a due scheduled reminder to the user's active conversation.
+ * Unlike reminders, scheduled reminders have no entity of their own; the
+ * text is stored in the job payload.
+ */
+async function deliverScheduledReminder(job: BuiltinJobFire, options: BuiltinJobFireOptions) {
+  const text = job.payload?.['text']
+  if (typeof text !== 'string' || !text) {
+    throw new Error('Scheduled reminder has no text')
+  }
+
+  const [language, timezone] = await Promise.all([
+    options.getLanguage?.(job.userId),
+    options.getTimezone?.(job.userId),
+  ])
+  const { t } = createTranslator(language ?? 'en')
+  await options.appendInstruction(
+    job.userId,
+    t('tools.builtin.scheduled_reminder_due.instruction', {
+      text,
+      time: formatDate(job.scheduledFor ? new Date(job.scheduledFor) : new Date(), { timezone }),
+    })
+  )
+}
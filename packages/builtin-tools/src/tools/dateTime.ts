@@ -69,7 +69,7 @@ function getUtcOffsetMinutesForTimeZone(date: Date, timeZone: string): number {
 /**
  * Formats an ISO timestamp including a specific timezone offset (not `Z`) for reliable scheduling.
  */
-function toIsoWithTimeZone(date: Date, timeZone: string): string {
+export function toIsoWithTimeZone(date: Date, timeZone: string): string {
   const parts = new Intl.DateTimeFormat('en-CA', {
     timeZone,
     year: 'numeric',
@@ -104,7 +104,7 @@ function toIsoWithTimeZone(date: Date, timeZone: string): string {
 /**
  * Validates that a timezone string is a valid IANA timezone identifier.
  */
-function isValidTimeZone(timeZone: string): boolean {
+export function isValidTimeZone(timeZone: string): boolean {
   try {
     new Intl.DateTimeFormat('en-US', { timeZone }).format(new Date())
     return true
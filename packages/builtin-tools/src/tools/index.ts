@@ -1 +1,17 @@
 export { createDateTimeTool } from './dateTime.js'
+export { createRememberTool, createRecallTool, createForgetTool } from './memory.js'
+export {
+  createTaskCreateTool,
+  createTaskListTool,
+  createTaskUpdateTool,
+  createTaskCompleteTool,
+  createTaskDeleteTool,
+} from './tasks.js'
+export {
+  createReminderCreateTool,
+  createReminderScheduleTool,
+  createReminderListTool,
+  createReminderUpdateTool,
+  createReminderCompleteTool,
+  createReminderDeleteTool,
+} from './reminders.js'
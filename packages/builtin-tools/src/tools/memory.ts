@@ -0,0 +1,137 @@
+import type { MemoryService } from '@stina/chat'
+import type { ToolResult } from '@stina/extension-api'
+import type { BuiltinToolContext, BuiltinToolFactory, ToolExecutionContext } from '../types.js'
+import { localized, toErrorResult } from './shared.js'
+
+/** Maximum number of memories a recall may return */
+const MAX_RECALL_LIMIT = 20
+
+/**
+ * Resolve the memory service for the user running the tool.
+ */
+function resolveMemory(
+  context: BuiltinToolContext,
+  executionContext?: ToolExecutionContext
+): { memory: MemoryService } | { error: ToolResult } {
+  const userId = executionContext?.userId
+  const memory = userId ? context.getMemoryService?.(userId) : undefined
+  if (!memory) {
+    return { error: { success: false, error: 'Memory is not available' } }
+  }
+  return { memory }
+}
+
+/**
+ * Factory for the tool that saves a fact to long-term memory.
+ */
+export const createRememberTool: BuiltinToolFactory = (context) => ({
+  id: 'core_remember',
+  ...localized('remember'),
+  parameters: {
+    type: 'object',
+    properties: {
+      content: {
+        type: 'string',
+        description: 'The fact to remember, written as a short self-contained sentence.',
+      },
+    },
+    required: ['content'],
+    additionalProperties: false,
+  },
+  // Saving a memory is what the user asked for and can be undone with core_forget
+  requiresConfirmation: false,
+  execute: async (params, executionContext) => {
+    const resolved = resolveMemory(context, executionContext)
+    if ('error' in resolved) return resolved.error
+
+    const content = typeof params['content'] === 'string' ? params['content'] : ''
+    try {
+      const memory = await resolved.memory.remember(content)
+      return { success: true, data: { id: memory.id, content: memory.content } }
+    } catch (error) {
+      return toErrorResult(error)
+    }
+  },
+})
+
+/**
+ * Factory for the tool that searches long-term memory.
+ */
+export const createRecallTool: BuiltinToolFactory = (context) => ({
+  id: 'core_recall',
+  ...localized('recall'),
+  parameters: {
+    type: 'object',
+    properties: {
+      query: {
+        type: 'string',
+        description: 'What to look for in memory.',
+      },
+      limit: {
+        type: 'number',
+        description: `Maximum number of memories to return (1-${MAX_RECALL_LIMIT}). Defaults to 5.`,
+      },
+    },
+    required: ['query'],
+    additionalProperties: false,
+  },
+  // Reading memories is side-effect free
+  requiresConfirmation: false,
+  execute: async (params, executionContext) => {
+    const resolved = resolveMemory(context, executionContext)
+    if ('error' in resolved) return resolved.error
+
+    const query = typeof params['query'] === 'string' ? params['query'] : ''
+    const limit =
+      typeof params['limit'] === 'number' && Number.isFinite(params['limit'])
+        ? Math.min(Math.max(Math.floor(params['limit']), 1), MAX_RECALL_LIMIT)
+        : undefined
+
+    try {
+      const matches = await resolved.memory.recall(query, { limit })
+      return {
+        success: true,
+        data: {
+          memories: matches.map(({ memory, score }) => ({
+            id: memory.id,
+            content: memory.content,
+            created_at: memory.createdAt.toISOString(),
+            score: Math.round(score * 100) / 100,
+          })),
+        },
+      }
+    } catch (error) {
+      return toErrorResult(error)
+    }
+  },
+})
+
+/**
+ * Factory for the tool that deletes a memory.
+ * Requires confirmation since the memory can't be restored.
+ */
+export const createForgetTool: BuiltinToolFactory = (context) => ({
+  id: 'core_forget',
+  ...localized('forget'),
+  parameters: {
+    type: 'object',
+    properties: {
+      id: {
+        type: 'string',
+        description: 'ID of the memory to delete, as returned by core_recall.',
+      },
+    },
+    required: ['id'],
+    additionalProperties: false,
+  },
+  execute: async (params, executionContext) => {
+    const resolved = resolveMemory(context, executionContext)
+    if ('error' in resolved) return resolved.error
+
+    const id = typeof params['id'] === 'string' ? params['id'] : ''
+    const deleted = id ? await resolved.memory.forget(id) : false
+    return deleted
+      ? { success: true, data: { id } }
+      : { success: false, error: `Memory "${id}" not found` }
+  },
+})
@@ -0,0 +1,334 @@
+import { randomUUID } from 'node:crypto'
+import type { Reminder, ReminderService } from '@stina/chat'
+import type { ToolResult } from '@stina/extension-api'
+import { getScheduleDescription, getScheduleValue, type SchedulerSchedule } from '@stina/scheduler'
+import type { BuiltinToolContext, BuiltinToolFactory, ToolExecutionContext } from '../types.js'
+import { BUILTIN_EXTENSION_ID, SCHEDULED_REMINDER_JOB_PREFIX } from '../reminders.js'
+import { isValidTimeZone } from './dateTime.js'
+import { formatDate, getStringParam, localized, parseDateParam, toErrorResult } from './shared.js'
+
+const DATE_DESCRIPTION =
+  'ISO 8601 date-time with timezone offset, e.g. "2025-03-14T17:00:00+01:00". Use core_get_datetime for the current time.'
+
+/**
+ * Resolve the reminder service for the user running the tool.
+ */
+function resolveReminders(
+  context: BuiltinToolContext,
+  executionContext?: ToolExecutionContext
+): { reminders: ReminderService } | { error: ToolResult } {
+  const userId = executionContext?.userId
+  const reminders = userId ? context.getReminderService?.(userId) : undefined
+  if (!reminders) {
+    return { error: { success: false, error: 'Reminders are not available' } }
+  }
+  return { reminders }
+}
+
+function toReminderData(reminder: Reminder, executionContext?: ToolExecutionContext) {
+  return {
+    id: reminder.id,
+    text: reminder.text,
+    remind_at: formatDate(reminder.remindAt, executionContext),
+    status: reminder.completedAt ? 'completed' : reminder.firedAt ? 'delivered' : 'pending',
+  }
+}
+
+function notFound(id: string): ToolResult {
+  return { success: false, error: `Reminder "${id}" not found` }
+}
+
+/** Number of upcoming occurrences shown when a scheduled reminder is created */
+const PREVIEW_OCCURRENCES = 3
+
+/**
+ * Read the structured schedule produced by the model. Cron and RRULE schedules
+ * are evaluated in the user's timezone.
+ * @throws If the schedule is missing or malformed
+ */
+function parseScheduleParam(value: unknown, timezone: string): SchedulerSchedule {
+  if (!value || typeof value !== 'object' || Array.isArray(value)) {
+    throw new Error('schedule must be an object')
+  }
+  const schedule = value as Record<string, unknown>
+
+  switch (schedule['type']) {
+    case 'at':
+      return { type: 'at', at: parseDateParam(schedule['at'], 'schedule.at').toISOString() }
+    case 'interval': {
+      const minutes = schedule['every_minutes']
+      if (typeof minutes !== 'number' || !Number.isFinite(minutes) || minutes < 1) {
+        throw new Error('schedule.every_minutes must be a number of at least 1')
+      }
+      return { type: 'interval', everyMs: Math.round(minutes * 60_000) }
+    }
+    case 'cron': {
+      const cron = getStringParam(schedule, 'cron')
+      if (!cron || cron.split(/\s+/).length !== 5) {
+        throw new Error('schedule.cron must be a cron expression with 5 fields')
+      }
+      return { type: 'cron', cron, timezone }
+    }
+    case 'rrule': {
+      const rrule = getStringParam(schedule, 'rrule')?.replace(/^RRULE:/i, '')
+      const dtstart = getStringParam(schedule, 'dtstart')
+      if (!rrule) throw new Error('schedule.rrule is required')
+      if (!dtstart) throw new Error('schedule.dtstart is required')
+      return { type: 'rrule', rrule, dtstart, timezone }
+    }
+    default:
+      throw new Error('schedule.type must be one of "at", "interval", "cron" or "rrule"')
+  }
+}
+
+/**
+ * Factory for the tool that creates a reminder.
+ */
+export const createReminderCreateTool: BuiltinToolFactory = (context) => ({
+  id: 'core_reminder_create',
+  ...localized('reminder_create'),
+  parameters: {
+    type: 'object',
+    properties: {
+      text: { type: 'string', description: 'What to remind the user about.' },
+      remind_at: { type: 'string', description: `When to remind the user. ${DATE_DESCRIPTION}` },
+    },
+    required: ['text', 'remind_at'],
+    additionalProperties: false,
+  },
+  requiresConfirmation: false,
+  execute: async (params, executionContext) => {
+    const resolved = resolveReminders(context, executionContext)
+    if ('error' in resolved) return resolved.error
+
+    try {
+      const reminder = await resolved.reminders.create({
+        text: getStringParam(params, 'text') ?? '',
+        remindAt: parseDateParam(params['remind_at'], 'remind_at'),
+      })
+      return { success: true, data: toReminderData(reminder, executionContext) }
+    } catch (error) {
+      return toErrorResult(error)
+    }
+  },
+})
+
+/**
+ * Factory for the tool that schedules a reminder from a structured schedule,
+ * which can be recurring. Scheduled reminders are scheduler jobs owned by the
+ * built-in extension; the user manages them under scheduled jobs in settings.
+ */
+export const createReminderScheduleTool: BuiltinToolFactory = (context) => ({
+  id: 'core_reminder_schedule',
+  ...localized('reminder_schedule'),
+  parameters: {
+    type: 'object',
+    properties: {
+      text: {
+        type: 'string',
+        description: 'What to remind the user about, or what to do or ask when the time comes.',
+      },
+      schedule: {
+        type: 'object',
+        description:
+          "When to remind the user. Cron and RRULE schedules use the user's timezone. " +
+          'Use core_get_datetime for the current time.',
+        properties: {
+          type: {
+            type: 'string',
+            enum: ['at', 'interval', 'cron', 'rrule'],
+            description:
+              '"at" for a single time, "interval" for a fixed period, "cron" or "rrule" for calendar-based recurrence.',
+          },
+          at: { type: 'string', description: `For "at": the time. ${DATE_DESCRIPTION}` },
+          every_minutes: { type: 'number', description: 'For "interval": minutes between reminders.' },
+          cron: {
+            type: 'string',
+            description: 'For "cron": a 5-field cron expression, e.g. "30 8 * * 1-5" for weekdays at 8:30.',
+          },
+          rrule: {
+            type: 'string',
+            description:
+              'For "rrule": an RFC 5545 recurrence rule, e.g. "FREQ=MONTHLY;BYDAY=MO;BYSETPOS=1".',
+          },
+          dtstart: {
+            type: 'string',
+            description:
+              'For "rrule": the first occurrence as a local date-time without offset, e.g. "2025-03-17T08:30:00".',
+          },
+        },
+        required: ['type'],
+        additionalProperties: false,
+      },
+    },
+    required: ['text', 'schedule'],
+    additionalProperties: false,
+  },
+  requiresConfirmation: false,
+  execute: async (params, executionContext) => {
+    const userId = executionContext?.userId
+    if (!context.scheduler || !userId) {
+      return { success: false, error: 'Scheduled reminders are not available' }
+    }
+    const text = getStringParam(params, 'text')
+    if (!text) {
+      return { success: false, error: 'text is required' }
+    }
+
+    const userTimezone = executionContext.timezone?.trim()
+    const timezone = userTimezone && isValidTimeZone(userTimezone) ? userTimezone : 'UTC'
+
+    try {
+      const schedule = parseScheduleParam(params['schedule'], timezone)
+      const nextRuns = context.scheduler.getUpcomingRuns(schedule, PREVIEW_OCCURRENCES)
+      if (nextRuns.length === 0) {
+        return { success: false, error: 'The schedule has no future occurrences' }
+      }
+
+      const jobId = `${SCHEDULED_REMINDER_JOB_PREFIX}${randomUUID()}`
+      await context.scheduler.schedule(BUILTIN_EXTENSION_ID, {
+        id: jobId,
+        schedule,
+        payload: { text },
+        // Deliver a missed one-off reminder, but don't pile up missed recurring ones
+        misfire: schedule.type === 'at' ? 'run_once' : 'skip',
+        userId,
+      })
+
+      return {
+        success: true,
+        data: {
+          id: `${BUILTIN_EXTENSION_ID}:${jobId}`,
+          text,
+          schedule: getScheduleDescription(
+            schedule.type,
+            getScheduleValue(schedule),
+            schedule.type === 'cron' || schedule.type === 'rrule' ? timezone : null
+          ),
+          timezone,
+          next_occurrences: nextRuns.map((date) => formatDate(date, { timezone })),
+        },
+      }
+    } catch (error) {
+      return toErrorResult(error)
+    }
+  },
+})
+
+/**
+ * Factory for the tool that lists reminders.
+ */
+export const createReminderListTool: BuiltinToolFactory = (context) => ({
+  id: 'core_reminder_list',
+  ...localized('reminder_list'),
+  parameters: {
+    type: 'object',
+    properties: {
+      include_completed: {
+        type: 'boolean',
+        description: 'Also list completed reminders. Defaults to false.',
+      },
+    },
+    additionalProperties: false,
+  },
+  requiresConfirmation: false,
+  execute: async (params, executionContext) => {
+    const resolved = resolveReminders(context, executionContext)
+    if ('error' in resolved) return resolved.error
+
+    const reminders = await resolved.reminders.list({
+      includeCompleted: params['include_completed'] === true,
+    })
+    return {
+      success: true,
+      data: { reminders: reminders.map((reminder) => toReminderData(reminder, executionContext)) },
+    }
+  },
+})
+
+/**
+ * Factory for the tool that changes the text or time of a reminder.
+ */
+export const createReminderUpdateTool: BuiltinToolFactory = (context) => ({
+  id: 'core_reminder_update',
+  ...localized('reminder_update'),
+  parameters: {
+    type: 'object',
+    properties: {
+      id: { type: 'string', description: 'ID of the reminder, as returned by core_reminder_list.' },
+      text: { type: 'string', description: 'New text.' },
+      remind_at: { type: 'string', description: `New time. ${DATE_DESCRIPTION}` },
+    },
+    required: ['id'],
+    additionalProperties: false,
+  },
+  requiresConfirmation: false,
+  execute: async (params, executionContext) => {
+    const resolved = resolveReminders(context, executionContext)
+    if ('error' in resolved) return resolved.error
+
+    const id = getStringParam(params, 'id') ?? ''
+    try {
+      const reminder = await resolved.reminders.update(id, {
+        text: getStringParam(params, 'text'),
+        remindAt:
+          params['remind_at'] !== undefined
+            ? parseDateParam(params['remind_at'], 'remind_at')
+            : undefined,
+      })
+      return reminder ? { success: true, data: toReminderData(reminder, executionContext) } : notFound(id)
+    } catch (error) {
+      return toErrorResult(error)
+    }
+  },
+})
+
+/**
+ * Factory for the tool that marks a reminder as done.
+ */
+export const createReminderCompleteTool: BuiltinToolFactory = (context) => ({
+  id: 'core_reminder_complete',
+  ...localized('reminder_complete'),
+  parameters: {
+    type: 'object',
+    properties: {
+      id: { type: 'string', description: 'ID of the reminder, as returned by core_reminder_list.' },
+    },
+    required: ['id'],
+    additionalProperties: false,
+  },
+  requiresConfirmation: false,
+  execute: async (params, executionContext) => {
+    const resolved = resolveReminders(context, executionContext)
+    if ('error' in resolved) return resolved.error
+
+    const id = getStringParam(params, 'id') ?? ''
+    const reminder = await resolved.reminders.complete(id)
+    return reminder ? { success: true, data: toReminderData(reminder, executionContext) } : notFound(id)
+  },
+})
+
+/**
+ * Factory for the tool that deletes a reminder.
+ * Requires confirmation since the reminder can't be restored.
+ */
+export const createReminderDeleteTool: BuiltinToolFactory = (context) => ({
+  id: 'core_reminder_delete',
+  ...localized('reminder_delete'),
+  parameters: {
+    type: 'object',
+    properties: {
+      id: { type: 'string', description: 'ID of the reminder, as returned by core_reminder_list.' },
+    },
+    required: ['id'],
+    additionalProperties: false,
+  },
+  execute: async (params, executionContext) => {
+    const resolved = resolveReminders(context, executionContext)
+    if ('error' in resolved) return resolved.error
+
+    const id = getStringParam(params, 'id') ?? ''
+    const deleted = id ? await resolved.reminders.delete(id) : false
+    return deleted ? { success: true, data: { id } } : notFound(id)
+  },
+})
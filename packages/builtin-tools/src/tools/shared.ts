@@ -0,0 +1,67 @@
+import type { ToolResult } from '@stina/extension-api'
+import { createTranslator } from '@stina/i18n'
+import type { ToolExecutionContext } from '../types.js'
+import { isValidTimeZone, toIsoWithTimeZone } from './dateTime.js'
+
+// Create translators for supported languages
+export const translators = {
+  en: createTranslator('en'),
+  sv: createTranslator('sv'),
+}
+
+/**
+ * Localized name and description for a built-in tool.
+ * @param key Key under tools.builtin in the locale files
+ */
+export function localized(key: string) {
+  return {
+    name: {
+      en: translators.en.t(`tools.builtin.${key}.name`),
+      sv: translators.sv.t(`tools.builtin.${key}.name`),
+    },
+    description: {
+      en: translators.en.t(`tools.builtin.${key}.description`),
+      sv: translators.sv.t(`tools.builtin.${key}.description`),
+    },
+  }
+}
+
+/**
+ * Convert a thrown error to a failed tool result.
+ */
+export function toErrorResult(error: unknown): ToolResult {
+  return { success: false, error: error instanceof Error ? error.message : String(error) }
+}
+
+/**
+ * Format a date in the user's timezone, falling back to UTC.
+ */
+export function formatDate(date: Date, executionContext?: ToolExecutionContext): string {
+  const timezone = executionContext?.timezone?.trim()
+  return timezone && isValidTimeZone(timezone) ? toIsoWithTimeZone(date, timezone) : date.toISOString()
+}
+
+/**
+ * Parse an ISO 8601 date-time parameter.
+ * A timezone offset is required so the time can't be misread in the server's timezone.
+ * @throws If the value is not a date-time with offset
+ */
+export function parseDateParam(value: unknown, name: string): Date {
+  if (typeof value !== 'string' || !/(Z|[+-]\d{2}:?\d{2})$/i.test(value.trim())) {
+    throw new Error(`${name} must be an ISO 8601 date-time with timezone offset`)
+  }
+  const date = new Date(value.trim())
+  if (Number.isNaN(date.getTime())) {
+    throw new Error(`${name} is not a valid date-time`)
+  }
+  return date
+}
+
+/**
+ * Read a string parameter.
+ * @returns The trimmed string, or undefined if missing
+ */
+export function getStringParam(params: Record<string, unknown>, name: string): string | undefined {
+  const value = params[name]
+  return typeof value === 'string' ? value.trim() : undefined
+}
@@ -0,0 +1,205 @@
+import type { ITaskRepository, Task } from '@stina/chat'
+import type { ToolResult } from '@stina/extension-api'
+import type { BuiltinToolContext, BuiltinToolFactory, ToolExecutionContext } from '../types.js'
+import { formatDate, getStringParam, localized, parseDateParam, toErrorResult } from './shared.js'
+
+/** Maximum length of a task title, in characters */
+const MAX_TITLE_LENGTH = 500
+
+const DATE_DESCRIPTION =
+  'ISO 8601 date-time with timezone offset, e.g. "2025-03-14T17:00:00+01:00". Use core_get_datetime for the current time.'
+
+/**
+ * Resolve the task repository for the user running the tool.
+ */
+function resolveTasks(
+  context: BuiltinToolContext,
+  executionContext?: ToolExecutionContext
+): { tasks: ITaskRepository } | { error: ToolResult } {
+  const userId = executionContext?.userId
+  const tasks = userId ? context.getTaskRepository?.(userId) : undefined
+  if (!tasks) {
+    return { error: { success: false, error: 'Tasks are not available' } }
+  }
+  return { tasks }
+}
+
+function toTaskData(task: Task, executionContext?: ToolExecutionContext) {
+  return {
+    id: task.id,
+    title: task.title,
+    notes: task.notes,
+    due_at: task.dueAt ? formatDate(task.dueAt, executionContext) : null,
+    completed: task.completedAt !== null,
+    completed_at: task.completedAt ? formatDate(task.completedAt, executionContext) : null,
+  }
+}
+
+function validateTitle(title: string | undefined): string {
+  if (!title) throw new Error('title is required')
+  if (title.length > MAX_TITLE_LENGTH) {
+    throw new Error(`title is longer than ${MAX_TITLE_LENGTH} characters`)
+  }
+  return title
+}
+
+function notFound(id: string): ToolResult {
+  return { success: false, error: `Task "${id}" not found` }
+}
+
+/**
+ * Factory for the tool that creates a task.
+ */
+export const createTaskCreateTool: BuiltinToolFactory = (context) => ({
+  id: 'core_task_create',
+  ...localized('task_create'),
+  parameters: {
+    type: 'object',
+    properties: {
+      title: { type: 'string', description: 'Short description of what needs to be done.' },
+      notes: { type: 'string', description: 'Optional details.' },
+      due_at: { type: 'string', description: `Optional due date. ${DATE_DESCRIPTION}` },
+    },
+    required: ['title'],
+    additionalProperties: false,
+  },
+  requiresConfirmation: false,
+  execute: async (params, executionContext) => {
+    const resolved = resolveTasks(context, executionContext)
+    if ('error' in resolved) return resolved.error
+
+    try {
+      const task = await resolved.tasks.create({
+        title: validateTitle(getStringParam(params, 'title')),
+        notes: getStringParam(params, 'notes') || null,
+        dueAt: params['due_at'] !== undefined ? parseDateParam(params['due_at'], 'due_at') : null,
+      })
+      return { success: true, data: toTaskData(task, executionContext) }
+    } catch (error) {
+      return toErrorResult(error)
+    }
+  },
+})
+
+/**
+ * Factory for the tool that lists tasks.
+ */
+export const createTaskListTool: BuiltinToolFactory = (context) => ({
+  id: 'core_task_list',
+  ...localized('task_list'),
+  parameters: {
+    type: 'object',
+    properties: {
+      include_completed: {
+        type: 'boolean',
+        description: 'Also list completed tasks. Defaults to false.',
+      },
+    },
+    additionalProperties: false,
+  },
+  requiresConfirmation: false,
+  execute: async (params, executionContext) => {
+    const resolved = resolveTasks(context, executionContext)
+    if ('error' in resolved) return resolved.error
+
+    const tasks = await resolved.tasks.list({ includeCompleted: params['include_completed'] === true })
+    return { success: true, data: { tasks: tasks.map((task) => toTaskData(task, executionContext)) } }
+  },
+})
+
+/**
+ * Factory for the tool that changes a task.
+ */
+export const createTaskUpdateTool: BuiltinToolFactory = (context) => ({
+  id: 'core_task_update',
+  ...localized('task_update'),
+  parameters: {
+    type: 'object',
+    properties: {
+      id: { type: 'string', description: 'ID of the task, as returned by core_task_list.' },
+      title: { type: 'string', description: 'New title.' },
+      notes: { type: 'string', description: 'New details. An empty string removes them.' },
+      due_at: {
+        type: ['string', 'null'],
+        description: `New due date, or null to remove it. ${DATE_DESCRIPTION}`,
+      },
+    },
+    required: ['id'],
+    additionalProperties: false,
+  },
+  requiresConfirmation: false,
+  execute: async (params, executionContext) => {
+    const resolved = resolveTasks(context, executionContext)
+    if ('error' in resolved) return resolved.error
+
+    const id = getStringParam(params, 'id') ?? ''
+    try {
+      const title = getStringParam(params, 'title')
+      const notes = getStringParam(params, 'notes')
+      const dueAt = params['due_at']
+      const task = await resolved.tasks.update(id, {
+        title: title !== undefined ? validateTitle(title) : undefined,
+        notes: notes !== undefined ? notes || null : undefined,
+        dueAt: dueAt === null ? null : dueAt !== undefined ? parseDateParam(dueAt, 'due_at') : undefined,
+      })
+      return task ? { success: true, data: toTaskData(task, executionContext) } : notFound(id)
+    } catch (error) {
+      return toErrorResult(error)
+    }
+  },
+})
+
+/**
+ * Factory for the tool that marks a task as done, or not done.
+ */
+export const createTaskCompleteTool: BuiltinToolFactory = (context) => ({
+  id: 'core_task_complete',
+  ...localized('task_complete'),
+  parameters: {
+    type: 'object',
+    properties: {
+      id: { type: 'string', description: 'ID of the task, as returned by core_task_list.' },
+      completed: {
+        type: 'boolean',
+        description: 'Set to false to reopen a completed task. Defaults to true.',
+      },
+    },
+    required: ['id'],
+    additionalProperties: false,
+  },
+  requiresConfirmation: false,
+  execute: async (params, executionContext) => {
+    const resolved = resolveTasks(context, executionContext)
+    if ('error' in resolved) return resolved.error
+
+    const id = getStringParam(params, 'id') ?? ''
+    const completed = params['completed'] !== false
+    const task = await resolved.tasks.update(id, { completedAt: completed ? new Date() : null })
+    return task ? { success: true, data: toTaskData(task, executionContext) } : notFound(id)
+  },
+})
+
+/**
+ * Factory for the tool that deletes a task.
+ * Requires confirmation since the task can't be restored.
+ */
+export const createTaskDeleteTool: BuiltinToolFactory = (context) => ({
+  id: 'core_task_delete',
+  ...localized('task_delete'),
+  parameters: {
+    type: 'object',
+    properties: {
+      id: { type: 'string', description: 'ID of the task, as returned by core_task_list.' },
+    },
+    required: ['id'],
+    additionalProperties: false,
+  },
+  execute: async (params, executionContext) => {
+    const resolved = resolveTasks(context, executionContext)
+    if ('error' in resolved) return resolved.error
+
+    const id = getStringParam(params, 'id') ?? ''
+    const deleted = id ? await resolved.tasks.delete(id) : false
+    return deleted ? { success: true, data: { id } } : notFound(id)
+  },
+})
@@ -1,5 +1,11 @@
 import type { ToolResult, LocalizedString } from '@stina/extension-api'
-import type { ToolExecutionContext } from '@stina/chat'
+import type {
+  ITaskRepository,
+  MemoryService,
+  ReminderService,
+  ToolExecutionContext,
+} from '@stina/chat'
+import type { BuiltinJobScheduler } from './reminders.js'
 
 /**
  * Context provided to built-in tools at registration time.
@@ -13,6 +19,26 @@ export interface BuiltinToolContext {
    * @deprecated Use executionContext.timezone in execute() instead
    */
   getTimezone: () => Promise<string | undefined>
+  /**
+   * Get the long-term memory service for a user.
+   * Memory tools report an error when this is missing or returns undefined.
+   */
+  getMemoryService?: (userId: string) => MemoryService | undefined
+  /**
+   * Get the task repository for a user.
+   * Task tools report an error when this is missing or returns undefined.
+   */
+  getTaskRepository?: (userId: string) => ITaskRepository | undefined
+  /**
+   * Get the reminder service for a user.
+   * Reminder tools report an error when this is missing or returns undefined.
+   */
+  getReminderService?: (userId: string) => ReminderService | undefined
+  /**
+   * Scheduler for built-in jobs.
+   * The scheduled reminder tool reports an error when this is missing.
+   */
+  scheduler?: BuiltinJobScheduler
 }
 
 /**
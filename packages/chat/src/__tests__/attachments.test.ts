@@ -0,0 +1,110 @@
+import { createHash } from 'node:crypto'
+import { existsSync, mkdtempSync, rmSync } from 'node:fs'
+import { tmpdir } from 'node:os'
+import { join } from 'node:path'
+import { afterEach, beforeEach, describe, it, expect } from 'vitest'
+import { FileAttachmentStore } from '../db/FileAttachmentStore.js'
+import {
+  storeAttachments,
+  MAX_ATTACHMENTS_PER_MESSAGE,
+  MAX_ATTACHMENT_BYTES_PER_MESSAGE,
+} from '../attachments/index.js'
+import { ChatMessageQueue } from '../orchestrator/ChatMessageQueue.js'
+
+const png = new Uint8Array([0x89, 0x50, 0x4e, 0x47])
+const pngHash = createHash('sha256').update(png).digest('hex')
+
+describe('FileAttachmentStore', () => {
+  let directory: string
+  let store: FileAttachmentStore
+
+  beforeEach(() => {
+    directory = mkdtempSync(join(tmpdir(), 'stina-attachments-test-'))
+    store = new FileAttachmentStore(directory)
+  })
+
+  afterEach(() => {
+    rmSync(directory, { recursive: true, force: true })
+  })
+
+  it('stores files by content hash', async () => {
+    const attachment = await store.put({ name: 'shot.png', mimeType: 'image/png', data: png })
+
+    expect(attachment).toEqual({ id: pngHash, name: 'shot.png', mimeType: 'image/png', size: 4 })
+    expect(existsSync(join(directory, pngHash.slice(0, 2), pngHash))).toBe(true)
+    expect(await store.get(pngHash)).toEqual(png)
+  })
+
+  it('keeps one copy of the same content under different names', async () => {
+    const first = await store.put({ name: 'a.png', mimeType: 'image/png', data: png })
+    const second = await store.put({ name: 'b.png', mimeType: 'image/png', data: png })
+
+    expect(second.id).toBe(first.id)
+    expect(second.name).toBe('b.png')
+  })
+
+  it('returns null for unknown or malformed ids', async () => {
+    expect(await store.get('0'.repeat(64))).toBeNull()
+    expect(await store.get('../../etc/passwd')).toBeNull()
+  })
+})
+
+describe('storeAttachments', () => {
+  let directory: string
+  let store: FileAttachmentStore
+
+  beforeEach(() => {
+    directory = mkdtempSync(join(tmpdir(), 'stina-attachments-test-'))
+    store = new FileAttachmentStore(directory)
+  })
+
+  afterEach(() => {
+    rmSync(directory, { recursive: true, force: true })
+  })
+
+  it('fills in a missing name and MIME type', async () => {
+    const [attachment] = await storeAttachments(store, [{ name: ' ', mimeType: '', data: png }])
+
+    expect(attachment).toMatchObject({ name: 'attachment', mimeType: 'application/octet-stream' })
+  })
+
+  it('rejects too many files', async () => {
+    const uploads = Array.from({ length: MAX_ATTACHMENTS_PER_MESSAGE + 1 }, (_, i) => ({
+      name: `${i}.png`,
+      mimeType: 'image/png',
+      data: png,
+    }))
+
+    await expect(storeAttachments(store, uploads)).rejects.toThrow('at most')
+  })
+
+  it('rejects files larger in total than allowed for one message', async () => {
+    const half = new Uint8Array(MAX_ATTACHMENT_BYTES_PER_MESSAGE / 2)
+    const uploads = [
+      { name: 'a.bin', mimeType: 'application/octet-stream', data: half },
+      { name: 'b.bin', mimeType: 'application/octet-stream', data: half },
+      { name: 'c.png', mimeType: 'image/png', data: png },
+    ]
+
+    await expect(storeAttachments(store, uploads)).rejects.toThrow('at most 50 MB in total')
+    expect(await store.get(pngHash)).toBeNull()
+  })
+})
+
+describe('ChatMessageQueue', () => {
+  it('previews messages without text by their attachment names', () => {
+    const queue = new ChatMessageQueue()
+    queue.enqueue({
+      id: 'q1',
+      text: '',
+      role: 'user',
+      attachments: [
+        { id: pngHash, name: 'shot.png', mimeType: 'image/png', size: 4 },
+        { id: pngHash, name: 'copy.png', mimeType: 'image/png', size: 4 },
+      ],
+      createdAt: '2025-01-01T00:00:00.000Z',
+    })
+
+    expect(queue.getSnapshot(false).queued[0]?.preview).toBe('shot.png, copy.png')
+  })
+})
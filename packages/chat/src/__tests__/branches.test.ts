@@ -0,0 +1,104 @@
+import { describe, it, expect } from 'vitest'
+import {
+  getBranchPath,
+  getNewestLeaf,
+  getResendableMessage,
+  getSiblingIds,
+} from '../orchestrator/branches.js'
+import type { InteractionNode } from '../orchestrator/branches.js'
+import type { Interaction, Message } from '../types/index.js'
+
+const createdAt = '2024-01-01T00:00:00.000Z'
+
+// a ─ b ─ c
+//   └ b2 ─ c2
+//        └ c3
+const nodes: InteractionNode[] = [
+  { id: 'a', parentId: null },
+  { id: 'b', parentId: 'a' },
+  { id: 'c', parentId: 'b' },
+  { id: 'b2', parentId: 'a' },
+  { id: 'c2', parentId: 'b2' },
+  { id: 'c3', parentId: 'b2' },
+]
+
+function interaction(messages: Message[]): Interaction {
+  return {
+    id: 'int-1',
+    conversationId: 'conv-1',
+    messages,
+    informationMessages: [],
+    completed: true,
+    aborted: false,
+    error: false,
+    metadata: { createdAt },
+  }
+}
+
+describe('getBranchPath', () => {
+  it('walks from the leaf to the first interaction', () => {
+    expect(getBranchPath(nodes, 'c')).toEqual(['c', 'b', 'a'])
+  })
+
+  it('falls back to the newest interaction', () => {
+    expect(getBranchPath(nodes, null)).toEqual(['c3', 'b2', 'a'])
+    expect(getBranchPath(nodes, 'deleted')).toEqual(['c3', 'b2', 'a'])
+    expect(getBranchPath([], null)).toEqual([])
+  })
+
+  it('stops at a cycle', () => {
+    const cyclic = [
+      { id: 'x', parentId: 'y' },
+      { id: 'y', parentId: 'x' },
+    ]
+    expect(getBranchPath(cyclic, 'x')).toEqual(['x', 'y'])
+  })
+})
+
+describe('getSiblingIds', () => {
+  it('lists interactions with the same parent, oldest first', () => {
+    expect(getSiblingIds(nodes, 'b2')).toEqual(['b', 'b2'])
+    expect(getSiblingIds(nodes, 'a')).toEqual(['a'])
+    expect(getSiblingIds(nodes, 'unknown')).toEqual([])
+  })
+})
+
+describe('getNewestLeaf', () => {
+  it('follows the newest child down the tree', () => {
+    expect(getNewestLeaf(nodes, 'b')).toBe('c')
+    expect(getNewestLeaf(nodes, 'b2')).toBe('c3')
+    expect(getNewestLeaf(nodes, 'c2')).toBe('c2')
+  })
+})
+
+describe('getResendableMessage', () => {
+  it('returns the user message', () => {
+    const message = getResendableMessage(
+      interaction([
+        { type: 'instruction', text: 'Prompt', metadata: { createdAt, systemPrompt: true } },
+        { type: 'user', text: 'Hi', metadata: { createdAt } },
+        { type: 'stina', text: 'Hello!', metadata: { createdAt } },
+      ])
+    )
+    expect(message).toMatchObject({ type: 'user', text: 'Hi' })
+  })
+
+  it('returns the instruction of interactions without a user message', () => {
+    const message = getResendableMessage(
+      interaction([
+        { type: 'instruction', text: 'Prompt', metadata: { createdAt, systemPrompt: true } },
+        { type: 'instruction', text: 'Greet the user', metadata: { createdAt } },
+      ])
+    )
+    expect(message).toMatchObject({ type: 'instruction', text: 'Greet the user' })
+  })
+
+  it('does not regenerate context summaries', () => {
+    const message = getResendableMessage(
+      interaction([
+        { type: 'instruction', text: 'Summary', metadata: { createdAt, contextSummary: true } },
+      ])
+    )
+    expect(message).toBeNull()
+  })
+})
@@ -0,0 +1,48 @@
+import { describe, it, expect } from 'vitest'
+import { resolveHistoryPage, toChatHistoryInteraction } from '../db/ChatHistoryReader.js'
+import type { Interaction } from '../types/index.js'
+
+const createdAt = '2024-01-01T00:00:00.000Z'
+
+describe('toChatHistoryInteraction', () => {
+  it('only exposes user and assistant messages', () => {
+    const interaction: Interaction = {
+      id: 'int-1',
+      conversationId: 'conv-1',
+      messages: [
+        { type: 'instruction', text: 'system prompt', metadata: { createdAt, systemPrompt: true } },
+        { type: 'user', text: 'Hello', metadata: { createdAt } },
+        { type: 'thinking', text: 'hmm', done: true, metadata: { createdAt } },
+        { type: 'stina', text: 'Hi there', metadata: { createdAt } },
+      ],
+      informationMessages: [],
+      completed: true,
+      aborted: false,
+      error: false,
+      metadata: { createdAt },
+    }
+
+    expect(toChatHistoryInteraction(interaction)).toEqual({
+      id: 'int-1',
+      conversationId: 'conv-1',
+      createdAt,
+      messages: [
+        { role: 'user', text: 'Hello', createdAt },
+        { role: 'assistant', text: 'Hi there', createdAt },
+      ],
+    })
+  })
+})
+
+describe('resolveHistoryPage', () => {
+  it('applies the default page', () => {
+    expect(resolveHistoryPage({ userId: 'user-1' })).toEqual({ limit: 20, offset: 0 })
+  })
+
+  it('caps the page size', () => {
+    expect(resolveHistoryPage({ userId: 'user-1', limit: 500, offset: 40 })).toEqual({
+      limit: 100,
+      offset: 40,
+    })
+  })
+})
@@ -0,0 +1,163 @@
+import { describe, it, expect } from 'vitest'
+import {
+  buildContextMessages,
+  estimatePromptTokens,
+  estimateTextTokens,
+  getContextCompactionState,
+  getInteractionsAfter,
+  resolveCompactionLimit,
+  selectInteractionsToCompact,
+} from '../orchestrator/contextBudget.js'
+import type { Conversation, Interaction, Message } from '../types/index.js'
+
+const createdAt = '2024-01-01T00:00:00.000Z'
+
+function message(type: 'user' | 'stina' | 'instruction', text: string, extra = {}): Message {
+  return { type, text, metadata: { createdAt, ...extra } }
+}
+
+function interaction(id: string, messages: Message[], error = false): Interaction {
+  return {
+    id,
+    conversationId: 'conv-1',
+    messages,
+    informationMessages: [],
+    completed: true,
+    aborted: false,
+    error,
+    metadata: { createdAt },
+  }
+}
+
+function summaryInteraction(id: string, summarizedInteractionIds: string[]): Interaction {
+  return interaction(id, [
+    message('instruction', `summary ${id}`, { contextSummary: true, summarizedInteractionIds }),
+  ])
+}
+
+const texts = (messages: Message[]) => messages.map((m) => ('text' in m ? m.text : m.type))
+
+describe('contextBudget', () => {
+  describe('estimation', () => {
+    it('estimates roughly four characters per token', () => {
+      expect(estimateTextTokens('')).toBe(0)
+      expect(estimateTextTokens('abcd')).toBe(1)
+      expect(estimateTextTokens('abcde')).toBe(2)
+    })
+
+    it('only counts messages that are sent to the provider', () => {
+      const withThinking = estimatePromptTokens({
+        systemPrompt: '',
+        messages: [
+          message('user', 'a'.repeat(40)),
+          { type: 'thinking', text: 'b'.repeat(400), done: true, metadata: { createdAt } },
+        ],
+      })
+      const withoutThinking = estimatePromptTokens({
+        systemPrompt: '',
+        messages: [message('user', 'a'.repeat(40))],
+      })
+
+      expect(withThinking).toBe(withoutThinking)
+    })
+
+    it('resolves no limit when the context length is unknown', () => {
+      expect(resolveCompactionLimit(undefined)).toBeNull()
+      expect(resolveCompactionLimit(8000)).toBe(6000)
+      expect(resolveCompactionLimit(undefined, { defaultContextLength: 1000, threshold: 0.5 })).toBe(500)
+    })
+  })
+
+  describe('buildContextMessages', () => {
+    it('replaces summarized interactions with the summary', () => {
+      const history = [
+        interaction('a', [message('user', 'first'), message('stina', 'reply one')]),
+        interaction('b', [message('user', 'second')]),
+        summaryInteraction('s1', ['a']),
+        interaction('c', [message('user', 'third')]),
+      ]
+
+      expect(texts(buildContextMessages(history))).toEqual(['summary s1', 'second', 'third'])
+    })
+
+    it('restores the original messages when a summary is reverted', () => {
+      const history = [
+        interaction('a', [message('user', 'first')]),
+        summaryInteraction('s1', ['a']),
+        interaction('b', [message('user', 'second')]),
+      ]
+
+      expect(texts(buildContextMessages(history, new Set(['s1'])))).toEqual(['first', 'second'])
+    })
+
+    it('skips errored interactions and system prompt messages', () => {
+      const history = [
+        interaction('a', [message('instruction', 'prompt', { systemPrompt: true }), message('user', 'hi')]),
+        interaction('b', [message('user', 'broken')], true),
+      ]
+
+      expect(texts(buildContextMessages(history))).toEqual(['hi'])
+    })
+  })
+
+  describe('selectInteractionsToCompact', () => {
+    it('keeps the most recent interactions out of the summary', () => {
+      const history = ['a', 'b', 'c', 'd'].map((id) => interaction(id, [message('user', id)]))
+
+      expect(selectInteractionsToCompact(history).map((i) => i.id)).toEqual(['a', 'b'])
+      expect(selectInteractionsToCompact(history, new Set(), 3).map((i) => i.id)).toEqual(['a'])
+    })
+
+    it('includes an active summary so the new summary supersedes it', () => {
+      const history = [
+        interaction('a', [message('user', 'a')]),
+        summaryInteraction('s1', ['a']),
+        interaction('b', [message('user', 'b')]),
+        interaction('c', [message('user', 'c')]),
+        interaction('d', [message('user', 'd')]),
+      ]
+
+      expect(selectInteractionsToCompact(history).map((i) => i.id)).toEqual(['s1', 'b'])
+    })
+
+    it('returns nothing when only a summary would be condensed', () => {
+      const history = [
+        interaction('a', [message('user', 'a')]),
+        summaryInteraction('s1', ['a']),
+        interaction('b', [message('user', 'b')]),
+        interaction('c', [message('user', 'c')]),
+      ]
+
+      expect(selectInteractionsToCompact(history)).toEqual([])
+    })
+  })
+
+  describe('getInteractionsAfter', () => {
+    it('returns the interactions after the given one', () => {
+      const history = ['a', 'b', 'c'].map((id) => interaction(id, [message('user', id)]))
+
+      expect(getInteractionsAfter(history, 'b').map((i) => i.id)).toEqual(['c'])
+      expect(getInteractionsAfter(history, 'older').map((i) => i.id)).toEqual(['a', 'b', 'c'])
+    })
+  })
+
+  describe('getContextCompactionState', () => {
+    it('reads the state from conversation metadata', () => {
+      const conversation: Conversation = {
+        id: 'conv-1',
+        interactions: [],
+        active: true,
+        metadata: {
+          createdAt,
+          contextCompaction: { pausedAfterInteractionId: 'b', revertedSummaryIds: ['s1', 42] },
+        },
+      }
+
+      expect(getContextCompactionState(conversation)).toEqual({
+        pausedAfterInteractionId: 'b',
+        revertedSummaryIds: ['s1'],
+      })
+      expect(getContextCompactionState(null)).toEqual({ revertedSummaryIds: [] })
+    })
+  })
+})
@@ -0,0 +1,157 @@
+import { describe, it, expect, vi } from 'vitest'
+import { ChatOrchestrator } from '../orchestrator/ChatOrchestrator.js'
+import type { IConversationRepository } from '../orchestrator/IConversationRepository.js'
+import type { ChatModelConfig } from '../orchestrator/types.js'
+import { ProviderRegistry } from '../providers/ProviderRegistry.js'
+import type { AIProvider, Conversation, Interaction, Message } from '../types/index.js'
+
+const createdAt = '2024-01-01T00:00:00.000Z'
+
+/** Model whose compaction limit (30 000 tokens) is reached by three long interactions */
+const modelConfig: ChatModelConfig = {
+  providerId: 'provider',
+  modelId: 'model',
+  contextLength: 40_000,
+}
+
+/** About 10 000 tokens */
+const longText = 'x'.repeat(40_000)
+
+function interaction(id: string, messages: Message[]): Interaction {
+  return {
+    id,
+    conversationId: 'conv-1',
+    messages,
+    informationMessages: [],
+    completed: true,
+    aborted: false,
+    error: false,
+    metadata: { createdAt },
+  }
+}
+
+function longInteraction(id: string): Interaction {
+  return interaction(id, [{ type: 'user', text: longText, metadata: { createdAt } }])
+}
+
+function summaryInteraction(id: string, summarizedInteractionIds: string[]): Interaction {
+  return interaction(id, [
+    {
+      type: 'instruction',
+      text: `summary ${id}`,
+      metadata: { createdAt, contextSummary: true, summarizedInteractionIds },
+    },
+  ])
+}
+
+/**
+ * Repository holding one conversation in memory
+ * @param interactions - Interactions of the conversation, oldest first
+ */
+function createRepository(
+  conversation: Conversation,
+  interactions: Interaction[]
+): IConversationRepository {
+  // Newest first, like the database repository
+  const stored = [...interactions].reverse()
+  return {
+    saveConversation: async () => {},
+    saveInteraction: async (saved) => {
+      stored.unshift(saved)
+    },
+    getConversation: async () => conversation,
+    getLatestActiveConversation: async () => conversation,
+    getConversationInteractions: async (_id, limit, offset) => stored.slice(offset, offset + limit),
+    countConversationInteractions: async () => stored.length,
+    getInteraction: async (_id, interactionId) =>
+      stored.find((candidate) => candidate.id === interactionId) ?? null,
+    getInteractionTree: async () => [],
+    setActiveInteraction: async () => {},
+    archiveConversation: async () => {},
+    updateConversationTitle: async () => {},
+    updateConversationMetadata: async () => {},
+  }
+}
+
+function createConversation(contextCompaction?: Record<string, unknown>): Conversation {
+  return {
+    id: 'conv-1',
+    title: 'Long chat',
+    active: true,
+    interactions: [],
+    metadata: { createdAt, ...(contextCompaction ? { contextCompaction } : {}) },
+  }
+}
+
+async function createOrchestrator(repository: IConversationRepository, pageSize = 20) {
+  const provider = {
+    id: 'provider',
+    name: 'provider',
+    sendMessage: vi.fn<AIProvider['sendMessage']>(async (_messages, _systemPrompt, onEvent) => {
+      onEvent({ type: 'content', text: 'Short answer' })
+      onEvent({ type: 'done' })
+    }),
+  }
+  const providerRegistry = new ProviderRegistry()
+  providerRegistry.register(provider)
+
+  const orchestrator = new ChatOrchestrator(
+    {
+      userId: 'user-1',
+      repository,
+      providerRegistry,
+      modelConfigProvider: { getDefault: async () => modelConfig },
+    },
+    { autoTitle: false, pageSize }
+  )
+  await orchestrator.loadConversation('conv-1')
+  return { orchestrator, provider }
+}
+
+describe('Context compaction', () => {
+  it('reverts a summary that is not among the loaded interactions', async () => {
+    const repository = createRepository(createConversation(), [
+      interaction('a', [{ type: 'user', text: 'first', metadata: { createdAt } }]),
+      summaryInteraction('s1', ['a']),
+      interaction('b', [{ type: 'user', text: 'second', metadata: { createdAt } }]),
+      interaction('c', [{ type: 'user', text: 'third', metadata: { createdAt } }]),
+    ])
+    const { orchestrator } = await createOrchestrator(repository, 2)
+
+    expect(await orchestrator.revertContextCompaction('s1')).toBe(true)
+    expect(await orchestrator.revertContextCompaction('b')).toBe(false)
+    expect(orchestrator.getState().conversation?.metadata['contextCompaction']).toEqual({
+      pausedAfterInteractionId: 'c',
+      revertedSummaryIds: ['s1'],
+    })
+  })
+
+  it('does not compact the restored history again right after a revert', async () => {
+    const repository = createRepository(
+      createConversation({ pausedAfterInteractionId: 'd', revertedSummaryIds: [] }),
+      ['a', 'b', 'c', 'd'].map(longInteraction)
+    )
+    const { orchestrator, provider } = await createOrchestrator(repository)
+
+    await orchestrator.sendMessage('Hello')
+
+    // Only the reply, no summary
+    expect(provider.sendMessage).toHaveBeenCalledOnce()
+  })
+
+  it('compacts again once the newer interactions exceed the budget', async () => {
+    const repository = createRepository(
+      createConversation({ pausedAfterInteractionId: 'd', revertedSummaryIds: [] }),
+      ['a', 'b', 'c', 'd', 'e', 'f', 'g', 'h'].map(longInteraction)
+    )
+    const { orchestrator, provider } = await createOrchestrator(repository)
+
+    await orchestrator.sendMessage('Hello')
+
+    // The summary, then the reply
+    expect(provider.sendMessage).toHaveBeenCalledTimes(2)
+    expect(orchestrator.getState().conversation?.metadata['contextCompaction']).toEqual({
+      revertedSummaryIds: [],
+    })
+  })
+})
@@ -0,0 +1,265 @@
+import { describe, it, expect } from 'vitest'
+import {
+  exportConversation,
+  exportConversationToHtml,
+  exportConversationToJson,
+  exportConversationToMarkdown,
+  importConversation,
+  parseConversationExport,
+} from '../export/index.js'
+import type { Conversation, Interaction } from '../types/index.js'
+
+const first = '2024-05-01T08:00:00.000Z'
+const second = '2024-05-01T08:05:00.000Z'
+const third = '2024-05-01T08:10:00.000Z'
+
+function createConversation(): Conversation {
+  const greeting: Interaction = {
+    id: 'int-1',
+    conversationId: 'conv-1',
+    messages: [
+      { type: 'instruction', text: 'You are Stina', metadata: { createdAt: first, systemPrompt: true } },
+      { type: 'user', text: 'Delete <old> files', metadata: { createdAt: first } },
+      { type: 'thinking', text: 'Need the file tool', done: true, metadata: { createdAt: first } },
+      {
+        type: 'tools',
+        tools: [
+          {
+            name: 'files_delete',
+            displayName: 'Delete files',
+            payload: '{"path":"/tmp/old"}',
+            result: '{"error":"denied"}',
+            confirmationStatus: 'denied',
+            confirmationPrompt: 'Delete /tmp/old?',
+            confirmationDenialReason: 'Keep them',
+            metadata: { createdAt: first },
+          },
+        ],
+        metadata: { createdAt: first },
+      },
+      { type: 'stina', text: 'Okay, I kept them.', metadata: { createdAt: first } },
+    ],
+    informationMessages: [],
+    completed: true,
+    aborted: false,
+    error: false,
+    metadata: { createdAt: first },
+  }
+
+  const summary: Interaction = {
+    id: 'int-2',
+    conversationId: 'conv-1',
+    messages: [
+      {
+        type: 'instruction',
+        text: 'Summary of earlier messages',
+        metadata: { createdAt: second, contextSummary: true, summarizedInteractionIds: ['int-1'] },
+      },
+    ],
+    informationMessages: [],
+    completed: true,
+    aborted: false,
+    error: false,
+    metadata: { createdAt: second },
+  }
+
+  // Repositories return interactions newest first
+  return {
+    id: 'conv-1',
+    title: 'Cleanup',
+    active: true,
+    interactions: [summary, greeting],
+    metadata: {
+      createdAt: first,
+      contextCompaction: { pausedAfterInteractionId: 'int-2', revertedSummaryIds: ['int-2'] },
+    },
+  }
+}
+
+/**
+ * Conversation where the summary was replaced by another reply, then switched back to
+ */
+function createBranchedConversation(): Conversation {
+  const [summary, greeting] = createConversation().interactions
+  const edited: Interaction = {
+    id: 'int-2b',
+    conversationId: 'conv-1',
+    messages: [{ type: 'user', text: 'Archive them instead', metadata: { createdAt: third } }],
+    informationMessages: [],
+    completed: true,
+    aborted: false,
+    error: false,
+    metadata: { createdAt: third },
+    parentId: 'int-1',
+  }
+
+  // Loaded with all branches, oldest first
+  return {
+    ...createConversation(),
+    interactions: [{ ...greeting!, parentId: null }, { ...summary!, parentId: 'int-1' }, edited],
+    activeInteractionId: 'int-2',
+  }
+}
+
+describe('conversation export', () => {
+  it('renders Markdown with thinking, tools and confirmation outcomes', () => {
+    const markdown = exportConversationToMarkdown(createConversation(), { timeZone: 'Europe/Stockholm' })
+
+    expect(markdown).toContain('# Cleanup')
+    expect(markdown).toContain('### 2024-05-01 10:00')
+    expect(markdown).toContain('**You:**\n\nDelete <old> files')
+    expect(markdown).toContain('<summary>Thinking</summary>\n\nNeed the file tool')
+    expect(markdown).toContain('<summary>Tool: Delete files (Denied: Keep them)</summary>')
+    expect(markdown).toContain('"path": "/tmp/old"')
+    expect(markdown).not.toContain('You are Stina')
+    expect(markdown.indexOf('Delete <old> files')).toBeLessThan(
+      markdown.indexOf('Summary of earlier messages')
+    )
+  })
+
+  it('escapes HTML in the standalone document', () => {
+    const html = exportConversationToHtml(createConversation())
+
+    expect(html.startsWith('<!DOCTYPE html>')).toBe(true)
+    expect(html).toContain('Delete &lt;old&gt; files')
+    expect(html).not.toContain('<old>')
+    expect(html).toContain('Denied: Keep them')
+    expect(html).not.toContain('<script')
+  })
+
+  it('shows only the active branch in readable exports', () => {
+    const markdown = exportConversationToMarkdown(createBranchedConversation())
+
+    expect(markdown).toContain('Summary of earlier messages')
+    expect(markdown).not.toContain('Archive them instead')
+  })
+
+  it('suggests a file name from the date and title', () => {
+    const file = exportConversation({ ...createConversation(), title: 'Städa filer!' }, 'json')
+    expect(file.fileName).toBe('stina-2024-05-01-stada-filer.json')
+    expect(file.mimeType).toContain('application/json')
+  })
+})
+
+describe('conversation import', () => {
+  it('round-trips the JSON format with new IDs', async () => {
+    const saved: { conversations: Conversation[]; interactions: Interaction[] } = {
+      conversations: [],
+      interactions: [],
+    }
+    const repository = {
+      saveConversation: async (conversation: Conversation) => {
+        saved.conversations.push(conversation)
+      },
+      saveInteraction: async (interaction: Interaction) => {
+        saved.interactions.push(interaction)
+      },
+      setActiveInteraction: async () => {},
+    }
+
+    let next = 0
+    const data = JSON.parse(JSON.stringify(exportConversationToJson(createConversation())))
+    const imported = await importConversation(repository, data, { generateId: () => `new-${++next}` })
+
+    expect(imported.id).toBe('new-1')
+    expect(saved.conversations[0]?.title).toBe('Cleanup')
+    expect(saved.interactions.map((i) => [i.id, i.conversationId])).toEqual([
+      ['new-2', 'new-1'],
+      ['new-3', 'new-1'],
+    ])
+    expect(saved.interactions[0]?.messages).toEqual(createConversation().interactions[1]?.messages)
+
+    // References between interactions follow the new IDs
+    expect(saved.interactions[1]?.messages[0]?.metadata['summarizedInteractionIds']).toEqual(['new-2'])
+    expect(imported.metadata['contextCompaction']).toEqual({
+      pausedAfterInteractionId: 'new-3',
+      revertedSummaryIds: ['new-3'],
+    })
+  })
+
+  it('leaves out token usage, which the importing user did not spend', async () => {
+    const conversation = createConversation()
+    conversation.interactions[1]!.metadata = {
+      createdAt: first,
+      providerId: 'openai',
+      modelId: 'gpt-4o',
+      usage: { inputTokens: 1200, outputTokens: 300 },
+    }
+    const saved: Interaction[] = []
+    const repository = {
+      saveConversation: async () => {},
+      saveInteraction: async (interaction: Interaction) => {
+        saved.push(interaction)
+      },
+      setActiveInteraction: async () => {},
+    }
+
+    const data = JSON.parse(JSON.stringify(exportConversationToJson(conversation)))
+    await importConversation(repository, data)
+
+    expect(saved[0]?.metadata).toEqual({
+      createdAt: first,
+      providerId: 'openai',
+      modelId: 'gpt-4o',
+    })
+  })
+
+  it('keeps every branch and the active one', async () => {
+    const saved: Interaction[] = []
+    let activeInteractionId: string | undefined
+    const repository = {
+      saveConversation: async () => {},
+      saveInteraction: async (interaction: Interaction) => {
+        saved.push(interaction)
+      },
+      setActiveInteraction: async (_conversationId: string, interactionId: string) => {
+        activeInteractionId = interactionId
+      },
+    }
+
+    let next = 0
+    const data = JSON.parse(JSON.stringify(exportConversationToJson(createBranchedConversation())))
+    const imported = await importConversation(repository, data, { generateId: () => `new-${++next}` })
+
+    expect(saved.map((interaction) => [interaction.id, interaction.parentId])).toEqual([
+      ['new-2', null],
+      ['new-3', 'new-2'],
+      ['new-4', 'new-2'],
+    ])
+    expect(activeInteractionId).toBe('new-3')
+    expect(imported.interactions.map((interaction) => interaction.id)).toEqual(['new-2', 'new-3'])
+  })
+
+  it('chains the interactions of version 1 exports', async () => {
+    const saved: Interaction[] = []
+    const repository = {
+      saveConversation: async () => {},
+      saveInteraction: async (interaction: Interaction) => {
+        saved.push(interaction)
+      },
+      setActiveInteraction: async () => {},
+    }
+
+    const data = JSON.parse(JSON.stringify(exportConversationToJson(createConversation())))
+    await importConversation(repository, { ...data, version: 1 })
+
+    expect(saved[0]?.parentId).toBeNull()
+    expect(saved[1]?.parentId).toBe(saved[0]?.id)
+  })
+
+  it('rejects data that is not a supported export', () => {
+    const data = exportConversationToJson(createConversation())
+
+    expect(() => parseConversationExport({ hello: 'world' })).toThrow('Not a Stina conversation export')
+    expect(() => parseConversationExport({ ...data, version: 99 })).toThrow('newer than supported')
+    expect(() =>
+      parseConversationExport({
+        ...data,
+        conversation: {
+          ...data.conversation,
+          interactions: [{ id: 'x', metadata: { createdAt: first }, messages: [{ type: 'evil' }] }],
+        },
+      })
+    ).toThrow('unknown type')
+  })
+})
@@ -0,0 +1,77 @@
+import { describe, it, expect } from 'vitest'
+import {
+  getConversationOverrides,
+  normalizeConversationOverrides,
+} from '../orchestrator/conversationOverrides.js'
+import { conversationToDTO, dtoToConversation } from '../mappers/index.js'
+import type { Conversation } from '../types/index.js'
+
+function conversation(overrides: unknown): Conversation {
+  return {
+    id: 'conv-1',
+    interactions: [],
+    active: true,
+    metadata: { createdAt: '2025-01-01T00:00:00.000Z', overrides },
+  }
+}
+
+describe('normalizeConversationOverrides', () => {
+  it('keeps valid values and trims the additional prompt', () => {
+    expect(
+      normalizeConversationOverrides({
+        modelConfigId: 'local-llama',
+        temperature: 0.2,
+        personalityPreset: 'concise',
+        additionalPrompt: '  Answer in Markdown.  ',
+      })
+    ).toEqual({
+      modelConfigId: 'local-llama',
+      temperature: 0.2,
+      personalityPreset: 'concise',
+      additionalPrompt: 'Answer in Markdown.',
+    })
+  })
+
+  it('leaves out empty values', () => {
+    expect(
+      normalizeConversationOverrides({
+        modelConfigId: '',
+        temperature: null,
+        personalityPreset: '',
+        additionalPrompt: '   ',
+      })
+    ).toEqual({})
+    expect(normalizeConversationOverrides(null)).toEqual({})
+  })
+
+  it('rejects invalid values', () => {
+    expect(() => normalizeConversationOverrides({ temperature: 3 })).toThrow('temperature')
+    expect(() => normalizeConversationOverrides({ temperature: '1' })).toThrow('temperature')
+    expect(() => normalizeConversationOverrides({ personalityPreset: 'grumpy' })).toThrow(
+      'Unknown personality preset'
+    )
+    expect(() => normalizeConversationOverrides({ modelConfigId: 42 })).toThrow('modelConfigId')
+    expect(() => normalizeConversationOverrides(['concise'])).toThrow('object')
+  })
+})
+
+describe('getConversationOverrides', () => {
+  it('reads the overrides from conversation metadata', () => {
+    expect(getConversationOverrides(conversation({ temperature: 1 }))).toEqual({ temperature: 1 })
+  })
+
+  it('ignores missing or invalid stored overrides', () => {
+    expect(getConversationOverrides(null)).toEqual({})
+    expect(getConversationOverrides(conversation(undefined))).toEqual({})
+    expect(getConversationOverrides(conversation({ temperature: -1 }))).toEqual({})
+  })
+
+  it('is included in conversation DTOs', () => {
+    const dto = conversationToDTO(conversation({ personalityPreset: 'creative' }))
+    expect(dto.overrides).toEqual({ personalityPreset: 'creative' })
+    expect(getConversationOverrides(dtoToConversation(dto))).toEqual({
+      personalityPreset: 'creative',
+    })
+    expect(conversationToDTO(conversation(undefined)).overrides).toBeUndefined()
+  })
+})
@@ -1,9 +1,10 @@
 import { describe, it, expect, beforeEach, afterEach } from 'vitest'
 import Database from 'better-sqlite3'
 import { drizzle, type BetterSQLite3Database } from 'drizzle-orm/better-sqlite3'
+import { sql } from 'drizzle-orm'
 import { ConversationRepository } from '../db/repository.js'
 import { chatSchema } from '../db/schema.js'
-import type { Conversation } from '../types/index.js'
+import type { Conversation, Interaction } from '../types/index.js'
 
 /**
  * Creates an in-memory SQLite database with the chat schema for testing
@@ -19,7 +20,8 @@ function createTestDb(): BetterSQLite3Database<typeof chatSchema> {
       created_at INTEGER NOT NULL,
       active INTEGER NOT NULL DEFAULT 1,
       metadata TEXT,
-      user_id TEXT NOT NULL
+      user_id TEXT NOT NULL,
+      active_interaction_id TEXT
     );
 
     CREATE INDEX idx_conversations_active ON chat_conversations(active, created_at);
@@ -37,10 +39,30 @@ function createTestDb(): BetterSQLite3Database<typeof chatSchema> {
       messages TEXT NOT NULL,
       information_messages TEXT,
       metadata TEXT,
-      read_at INTEGER
+      read_at INTEGER,
+      provider_id TEXT,
+      model_id TEXT,
+      input_tokens INTEGER,
+      output_tokens INTEGER,
+      parent_id TEXT
     );
 
     CREATE INDEX idx_interactions_conversation ON chat_interactions(conversation_id, created_at);
+    CREATE INDEX idx_interactions_created ON chat_interactions(created_at);
+    CREATE INDEX idx_interactions_parent ON chat_interactions(conversation_id, parent_id);
+
+    CREATE VIRTUAL TABLE chat_message_search USING fts5(
+      text,
+      interaction_id UNINDEXED,
+      conversation_id UNINDEXED,
+      role UNINDEXED,
+      tokenize = 'unicode61 remove_diacritics 2'
+    );
+
+    CREATE TRIGGER chat_message_search_delete AFTER DELETE ON chat_interactions
+    BEGIN
+      DELETE FROM chat_message_search WHERE interaction_id = OLD.id;
+    END;
   `)
 
   return drizzle(sqlite, { schema: chatSchema })
@@ -61,6 +83,32 @@ function createTestConversation(overrides: Partial<Conversation> = {}): Conversa
   }
 }
 
+/**
+ * Creates a test interaction with a user message and a Stina reply
+ */
+function createTestInteraction(
+  id: string,
+  conversationId: string,
+  userText: string,
+  stinaText: string
+): Interaction {
+  const createdAt = new Date().toISOString()
+  return {
+    id,
+    conversationId,
+    messages: [
+      { type: 'instruction', text: 'hidden instruction about weather', metadata: { createdAt } },
+      { type: 'user', text: userText, metadata: { createdAt } },
+      { type: 'stina', text: stinaText, metadata: { createdAt } },
+    ],
+    informationMessages: [],
+    completed: true,
+    aborted: false,
+    error: false,
+    metadata: { createdAt },
+  }
+}
+
 describe('ConversationRepository - User Isolation', () => {
   let db: BetterSQLite3Database<typeof chatSchema>
   const USER_A = 'user-a-id'
@@ -236,3 +284,130 @@ describe('ConversationRepository - User Isolation', () => {
     expect(conversation?.active).toBe(false)
   })
 })
+
+describe('ConversationRepository - Search', () => {
+  let db: BetterSQLite3Database<typeof chatSchema>
+  let repo: ConversationRepository
+
+  beforeEach(async () => {
+    db = createTestDb()
+    repo = new ConversationRepository(db, 'user-a')
+    await repo.saveConversation(createTestConversation({ id: 'conv-1', title: 'Trip' }))
+    await repo.saveInteraction(
+      createTestInteraction('int-1', 'conv-1', 'What is the weather in Göteborg?', 'Sunny <b>today</b>.')
+    )
+  })
+
+  it('should find user and Stina messages with highlighted snippets', async () => {
+    const userHits = await repo.searchMessages('goteborg')
+    expect(userHits).toHaveLength(1)
+    expect(userHits[0]).toMatchObject({
+      conversationId: 'conv-1',
+      conversationTitle: 'Trip',
+      conversationActive: true,
+      interactionId: 'int-1',
+      role: 'user',
+    })
+    expect(userHits[0]?.snippet).toContain('<mark>Göteborg</mark>')
+
+    const stinaHits = await repo.searchMessages('sun')
+    expect(stinaHits[0]?.role).toBe('stina')
+    expect(stinaHits[0]?.snippet).toBe('<mark>Sunny</mark> &lt;b&gt;today&lt;/b&gt;.')
+  })
+
+  it('should not index instructions', async () => {
+    expect(await repo.searchMessages('hidden instruction')).toEqual([])
+  })
+
+  it('should only return the users own messages', async () => {
+    const repoB = new ConversationRepository(db, 'user-b')
+    expect(await repoB.searchMessages('weather')).toEqual([])
+  })
+
+  it('should treat search operators as plain text', async () => {
+    expect(await repo.searchMessages('weather" (')).toHaveLength(1)
+    expect(await repo.searchMessages('"*')).toEqual([])
+  })
+
+  it('should drop index rows when the interaction is deleted', async () => {
+    db.run(sql`DELETE FROM chat_interactions WHERE id = 'int-1'`)
+    expect(await repo.searchMessages('weather')).toEqual([])
+  })
+})
+
+describe('ConversationRepository - Branches', () => {
+  let repo: ConversationRepository
+
+  const ids = (interactions: Interaction[]) => interactions.map((interaction) => interaction.id)
+
+  beforeEach(async () => {
+    repo = new ConversationRepository(createTestDb(), 'user-a')
+    await repo.saveConversation(createTestConversation({ id: 'conv-1' }))
+    for (const id of ['int-1', 'int-2', 'int-3']) {
+      await repo.saveInteraction(createTestInteraction(id, 'conv-1', `Question ${id}`, 'Answer'))
+    }
+  })
+
+  it('should continue the active branch when no parent is given', async () => {
+    expect(await repo.getInteractionTree('conv-1')).toEqual([
+      { id: 'int-1', parentId: null },
+      { id: 'int-2', parentId: 'int-1' },
+      { id: 'int-3', parentId: 'int-2' },
+    ])
+  })
+
+  it('should show the newest branch after an interaction is replaced', async () => {
+    await repo.saveInteraction({
+      ...createTestInteraction('int-2b', 'conv-1', 'Edited question', 'Answer'),
+      parentId: 'int-1',
+    })
+
+    expect(await repo.countConversationInteractions('conv-1')).toBe(2)
+    const interactions = await repo.getConversationInteractions('conv-1', 10, 0)
+    expect(ids(interactions)).toEqual(['int-2b', 'int-1'])
+    expect(interactions[0]?.siblingIds).toEqual(['int-2', 'int-2b'])
+    expect(interactions[1]?.siblingIds).toBeUndefined()
+
+    const conversation = await repo.getConversation('conv-1')
+    expect(ids(conversation?.interactions ?? [])).toEqual(['int-2b', 'int-1'])
+  })
+
+  it('should page through the active branch only', async () => {
+    await repo.saveInteraction({
+      ...createTestInteraction('int-1b', 'conv-1', 'Other start', 'Answer'),
+      parentId: null,
+    })
+    await repo.saveInteraction(createTestInteraction('int-2c', 'conv-1', 'Follow-up', 'Answer'))
+
+    expect(ids(await repo.getConversationInteractions('conv-1', 1, 0))).toEqual(['int-2c'])
+    expect(ids(await repo.getConversationInteractions('conv-1', 1, 1))).toEqual(['int-1b'])
+    expect(await repo.getConversationInteractions('conv-1', 1, 2)).toEqual([])
+  })
+
+  it('should switch back to an earlier branch', async () => {
+    await repo.saveInteraction({
+      ...createTestInteraction('int-2b', 'conv-1', 'Edited question', 'Answer'),
+      parentId: 'int-1',
+    })
+
+    await repo.setActiveInteraction('conv-1', 'int-3')
+
+    expect(ids(await repo.getConversationInteractions('conv-1', 10, 0))).toEqual([
+      'int-3',
+      'int-2',
+      'int-1',
+    ])
+  })
+
+  it('should load every branch with the active one marked', async () => {
+    await repo.saveInteraction({
+      ...createTestInteraction('int-2b', 'conv-1', 'Edited question', 'Answer'),
+      parentId: 'int-1',
+    })
+    await repo.setActiveInteraction('conv-1', 'int-3')
+
+    const conversation = await repo.getConversationWithBranches('conv-1')
+    expect(ids(conversation?.interactions ?? [])).toEqual(['int-1', 'int-2', 'int-3', 'int-2b'])
+    expect(conversation?.activeInteractionId).toBe('int-3')
+  })
+})
@@ -0,0 +1,81 @@
+import { describe, it, expect } from 'vitest'
+import {
+  cleanGeneratedTitle,
+  getTitleSourceMessages,
+  MAX_TITLE_LENGTH,
+} from '../orchestrator/conversationTitle.js'
+import type { Interaction, Message } from '../types/index.js'
+
+const createdAt = '2024-01-01T00:00:00.000Z'
+
+function interaction(messages: Message[]): Interaction {
+  return {
+    id: 'int-1',
+    conversationId: 'conv-1',
+    messages,
+    informationMessages: [],
+    completed: true,
+    aborted: false,
+    error: false,
+    metadata: { createdAt },
+  }
+}
+
+describe('conversationTitle', () => {
+  describe('getTitleSourceMessages', () => {
+    it('keeps the user message and answer only', () => {
+      const messages = getTitleSourceMessages(
+        interaction([
+          { type: 'instruction', text: 'System prompt', metadata: { createdAt, systemPrompt: true } },
+          { type: 'user', text: 'Plan a trip to Gothenburg', metadata: { createdAt } },
+          { type: 'thinking', text: 'Thinking...', done: true, metadata: { createdAt } },
+          { type: 'stina', text: 'Sure! When do you want to go?', metadata: { createdAt } },
+        ])
+      )
+
+      expect(messages.map((m) => m.type)).toEqual(['user', 'stina'])
+    })
+
+    it('skips interactions without a user message', () => {
+      const messages = getTitleSourceMessages(
+        interaction([
+          { type: 'instruction', text: 'Greet the user', metadata: { createdAt } },
+          { type: 'stina', text: 'Good morning!', metadata: { createdAt } },
+        ])
+      )
+
+      expect(messages).toEqual([])
+    })
+
+    it('leaves out error messages and shortens long messages', () => {
+      const messages = getTitleSourceMessages(
+        interaction([
+          { type: 'user', text: 'x'.repeat(5000), metadata: { createdAt } },
+          { type: 'stina', text: 'Provider failed', metadata: { createdAt, isError: true } },
+        ])
+      )
+
+      expect(messages).toHaveLength(1)
+      expect(messages[0] && 'text' in messages[0] ? messages[0].text.length : 0).toBeLessThan(5000)
+    })
+  })
+
+  describe('cleanGeneratedTitle', () => {
+    it('strips labels, quotes and trailing punctuation', () => {
+      expect(cleanGeneratedTitle('"Trip to Gothenburg."')).toBe('Trip to Gothenburg')
+      expect(cleanGeneratedTitle('Title: **Weekly groceries**')).toBe('Weekly groceries')
+      expect(cleanGeneratedTitle('# Resa till Göteborg\n\nEn titel om resan')).toBe('Resa till Göteborg')
+    })
+
+    it('returns null for empty answers', () => {
+      expect(cleanGeneratedTitle('')).toBeNull()
+      expect(cleanGeneratedTitle('  \n "" \n')).toBeNull()
+    })
+
+    it('limits the title length', () => {
+      const title = cleanGeneratedTitle('word '.repeat(50))
+      expect(title?.length).toBeLessThanOrEqual(MAX_TITLE_LENGTH)
+      expect(title?.endsWith('…')).toBe(true)
+    })
+  })
+})
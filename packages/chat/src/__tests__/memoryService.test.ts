@@ -0,0 +1,130 @@
+import { describe, it, expect } from 'vitest'
+import { MemoryService } from '../memory/MemoryService.js'
+import { cosineSimilarity, keywordOverlap } from '../memory/vector.js'
+import type { CreateMemoryInput, IMemoryRepository, Memory, MemoryEmbedder } from '../memory/types.js'
+import { getMemoryPrompt } from '../constants/index.js'
+
+class InMemoryRepository implements IMemoryRepository {
+  memories: Memory[] = []
+  private next = 0
+
+  async list(): Promise<Memory[]> {
+    return [...this.memories]
+  }
+
+  async get(id: string): Promise<Memory | null> {
+    return this.memories.find((memory) => memory.id === id) ?? null
+  }
+
+  async create(input: CreateMemoryInput): Promise<Memory> {
+    const now = new Date()
+    const memory: Memory = { id: `mem-${++this.next}`, ...input, createdAt: now, updatedAt: now }
+    this.memories.push(memory)
+    return memory
+  }
+
+  async updateEmbedding(id: string, embedding: number[], embeddingModel: string): Promise<void> {
+    const memory = this.memories.find((m) => m.id === id)
+    if (memory) Object.assign(memory, { embedding, embeddingModel })
+  }
+
+  async delete(id: string): Promise<boolean> {
+    const before = this.memories.length
+    this.memories = this.memories.filter((memory) => memory.id !== id)
+    return this.memories.length < before
+  }
+}
+
+/**
+ * Embeds text as counts of a few topic words, enough to tell topics apart.
+ */
+function topicEmbedder(model = 'test:topics'): MemoryEmbedder {
+  const topics = ['coffee', 'cat', 'birthday']
+  return {
+    async embed(texts) {
+      return {
+        model,
+        embeddings: texts.map((text) => topics.map((topic) => (text.toLowerCase().includes(topic) ? 1 : 0.01))),
+      }
+    },
+  }
+}
+
+describe('MemoryService', () => {
+  it('stores memories with an embedding and recalls the most relevant first', async () => {
+    const repository = new InMemoryRepository()
+    const service = new MemoryService(repository, topicEmbedder())
+
+    await service.remember('Likes their coffee black')
+    await service.remember('Has a cat called Sixten')
+    await service.remember('Birthday is on March 3')
+
+    expect(repository.memories[0]?.embeddingModel).toBe('test:topics')
+
+    const matches = await service.recall('What is the name of my cat?')
+    expect(matches[0]?.memory.content).toBe('Has a cat called Sixten')
+    expect(matches.every((match) => !match.memory.content.includes('coffee'))).toBe(true)
+  })
+
+  it('falls back to keyword matching without an embedder', async () => {
+    const service = new MemoryService(new InMemoryRepository())
+
+    const memory = await service.remember('  Works as a nurse in Umeå  ')
+    expect(memory.content).toBe('Works as a nurse in Umeå')
+    expect(memory.embedding).toBeNull()
+
+    const matches = await service.recall('Am I a nurse?')
+    expect(matches.map((match) => match.memory.id)).toEqual([memory.id])
+    expect(await service.recall('favourite colour')).toEqual([])
+  })
+
+  it('stores the memory even if embedding fails', async () => {
+    const service = new MemoryService(new InMemoryRepository(), {
+      embed: async () => {
+        throw new Error('Provider offline')
+      },
+    })
+
+    const memory = await service.remember('Prefers short answers')
+    expect(memory.embedding).toBeNull()
+  })
+
+  it('re-embeds memories saved with another model', async () => {
+    const repository = new InMemoryRepository()
+    await new MemoryService(repository, topicEmbedder('old:model')).remember('Has a cat called Sixten')
+
+    const matches = await new MemoryService(repository, topicEmbedder('new:model')).recall('cat')
+    expect(matches).toHaveLength(1)
+    expect(repository.memories[0]?.embeddingModel).toBe('new:model')
+  })
+
+  it('rejects empty memories and forgets by ID', async () => {
+    const service = new MemoryService(new InMemoryRepository())
+
+    await expect(service.remember('   ')).rejects.toThrow('empty')
+
+    const memory = await service.remember('Lives in Stockholm')
+    expect(await service.forget(memory.id)).toBe(true)
+    expect(await service.forget(memory.id)).toBe(false)
+    expect(await service.list()).toEqual([])
+  })
+})
+
+describe('memory helpers', () => {
+  it('compares vectors with cosine similarity', () => {
+    expect(cosineSimilarity([1, 0], [1, 0])).toBeCloseTo(1)
+    expect(cosineSimilarity([1, 0], [0, 1])).toBeCloseTo(0)
+    expect(cosineSimilarity([1, 0], [1, 0, 0])).toBe(0)
+  })
+
+  it('measures keyword overlap', () => {
+    expect(keywordOverlap('cat name', 'Has a cat called Sixten')).toBe(0.5)
+    expect(keywordOverlap('', 'anything')).toBe(0)
+  })
+
+  it('formats memories as a prompt chunk', () => {
+    expect(getMemoryPrompt([])).toBe('')
+    const prompt = getMemoryPrompt(['Likes coffee', ' Has a cat '])
+    expect(prompt).toContain('\n- Likes coffee\n- Has a cat')
+  })
+})
@@ -0,0 +1,136 @@
+import { describe, it, expect, vi, beforeEach } from 'vitest'
+import { AppError, ErrorCode } from '@stina/core'
+import { ChatOrchestrator } from '../orchestrator/ChatOrchestrator.js'
+import type { IConversationRepository } from '../orchestrator/IConversationRepository.js'
+import type {
+  ChatModelConfig,
+  IModelConfigProvider,
+  OrchestratorEvent,
+} from '../orchestrator/types.js'
+import { ProviderRegistry } from '../providers/ProviderRegistry.js'
+import type { AIProvider } from '../types/index.js'
+
+/**
+ * Repository that stores nothing, for tests that only look at which provider is called
+ */
+function createMockRepository(): IConversationRepository {
+  return {
+    saveConversation: async () => {},
+    saveInteraction: async () => {},
+    getConversation: async () => null,
+    getLatestActiveConversation: async () => null,
+    getConversationInteractions: async () => [],
+    countConversationInteractions: async () => 0,
+    getInteraction: async () => null,
+    getInteractionTree: async () => [],
+    setActiveInteraction: async () => {},
+    archiveConversation: async () => {},
+    updateConversationTitle: async () => {},
+    updateConversationMetadata: async () => {},
+  }
+}
+
+function createMockProvider(id: string) {
+  return {
+    id,
+    name: id,
+    sendMessage: vi.fn<AIProvider['sendMessage']>(async () => {}),
+  }
+}
+
+const modelConfigs: Record<string, ChatModelConfig> = {
+  small: { providerId: 'small-provider', modelId: 'small-model' },
+  large: { providerId: 'large-provider', modelId: 'large-model' },
+}
+
+/**
+ * Model config provider for a user who may only use the allowed model configs,
+ * refusing the others like the database-backed provider does
+ */
+function createModelConfigProvider(
+  allowed: Set<string>,
+  defaultId: string | null
+): IModelConfigProvider {
+  const load = async (modelConfigId: string) => {
+    if (!allowed.has(modelConfigId)) {
+      throw new AppError(ErrorCode.CHAT_MODEL_NOT_ALLOWED, 'You are not allowed to use this model')
+    }
+    return modelConfigs[modelConfigId] ?? null
+  }
+  return {
+    get: load,
+    getDefault: async () => (defaultId ? load(defaultId) : null),
+    hasModelAllowlist: async () => true,
+  }
+}
+
+describe('Model config policies', () => {
+  let small: ReturnType<typeof createMockProvider>
+  let large: ReturnType<typeof createMockProvider>
+  let providerRegistry: ProviderRegistry
+
+  beforeEach(() => {
+    small = createMockProvider('small-provider')
+    large = createMockProvider('large-provider')
+    providerRegistry = new ProviderRegistry()
+    providerRegistry.register(large)
+    providerRegistry.register(small)
+  })
+
+  function createOrchestrator(modelConfigProvider: IModelConfigProvider) {
+    return new ChatOrchestrator(
+      {
+        userId: 'user-1',
+        repository: createMockRepository(),
+        providerRegistry,
+        modelConfigProvider,
+      },
+      { autoTitle: false }
+    )
+  }
+
+  it('fails the message when the user may not use the default model', async () => {
+    const orchestrator = createOrchestrator(createModelConfigProvider(new Set(['small']), 'large'))
+    const events: OrchestratorEvent[] = []
+    orchestrator.on('event', (event) => events.push(event))
+
+    await orchestrator.sendMessage('Hello')
+
+    expect(large.sendMessage).not.toHaveBeenCalled()
+    expect(small.sendMessage).not.toHaveBeenCalled()
+    expect(orchestrator.getState().error?.message).toBe('You are not allowed to use this model')
+    expect(events).toContainEqual(
+      expect.objectContaining({
+        type: 'stream-error',
+        error: expect.objectContaining({ code: ErrorCode.CHAT_MODEL_NOT_ALLOWED }),
+      })
+    )
+  })
+
+  it('fails the message when a restricted user has no default model', async () => {
+    const orchestrator = createOrchestrator(createModelConfigProvider(new Set(['small']), null))
+
+    await orchestrator.sendMessage('Hello')
+
+    expect(large.sendMessage).not.toHaveBeenCalled()
+    expect(small.sendMessage).not.toHaveBeenCalled()
+    expect(orchestrator.getState().error).toMatchObject({
+      code: ErrorCode.CHAT_MODEL_NOT_ALLOWED,
+      message: 'Choose a default model you are allowed to use',
+    })
+  })
+
+  it('uses the default model when the conversation model is no longer allowed', async () => {
+    const allowed = new Set(['small', 'large'])
+    const orchestrator = createOrchestrator(createModelConfigProvider(allowed, 'small'))
+    await orchestrator.createConversation()
+    await orchestrator.setConversationOverrides({ modelConfigId: 'large' })
+    allowed.delete('large')
+
+    await orchestrator.sendMessage('Hello')
+
+    expect(large.sendMessage).not.toHaveBeenCalled()
+    expect(small.sendMessage).toHaveBeenCalledOnce()
+    expect(small.sendMessage.mock.calls[0]![3]).toMatchObject({ modelId: 'small-model' })
+  })
+})
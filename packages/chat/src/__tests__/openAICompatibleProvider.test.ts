@@ -0,0 +1,256 @@
+import { createServer } from 'node:http'
+import type { IncomingMessage, ServerResponse } from 'node:http'
+import type { AddressInfo } from 'node:net'
+import { afterEach, describe, it, expect } from 'vitest'
+import {
+  OpenAICompatibleProvider,
+  OPENAI_COMPATIBLE_PROVIDER_ID,
+  registerOpenAICompatibleProvider,
+} from '../providers/OpenAICompatibleProvider.js'
+import { ProviderRegistry } from '../providers/ProviderRegistry.js'
+import type { Message } from '../types/message.js'
+import type { StreamEvent } from '../types/provider.js'
+
+interface RecordedRequest {
+  method: string
+  path: string
+  headers: IncomingMessage['headers']
+  // eslint-disable-next-line @typescript-eslint/no-explicit-any -- inspected loosely in tests
+  body: any
+}
+
+type Handler = (request: RecordedRequest, res: ServerResponse) => void
+
+const closers: Array<() => Promise<void>> = []
+
+afterEach(async () => {
+  await Promise.all(closers.splice(0).map((close) => close()))
+})
+
+/**
+ * Start a mock OpenAI-compatible server on a random local port.
+ * Each request is answered by the next handler in the list.
+ */
+async function startServer(handlers: Handler[]) {
+  const requests: RecordedRequest[] = []
+  const server = createServer((req, res) => {
+    let raw = ''
+    req.on('data', (chunk) => (raw += chunk))
+    req.on('end', () => {
+      const request: RecordedRequest = {
+        method: req.method ?? '',
+        path: req.url ?? '',
+        headers: req.headers,
+        body: raw ? JSON.parse(raw) : undefined,
+      }
+      requests.push(request)
+      const handler = handlers[requests.length - 1]
+      if (handler) {
+        handler(request, res)
+      } else {
+        res.writeHead(500).end('Unexpected request')
+      }
+    })
+  })
+  await new Promise<void>((resolve) => server.listen(0, '127.0.0.1', resolve))
+  closers.push(() => new Promise((resolve) => server.close(() => resolve())))
+
+  const { port } = server.address() as AddressInfo
+  return { baseUrl: `http://127.0.0.1:${port}/v1`, requests }
+}
+
+/** Answer with a server-sent event stream of completion chunks */
+function stream(chunks: unknown[]): Handler {
+  return (_request, res) => {
+    res.writeHead(200, { 'Content-Type': 'text/event-stream' })
+    for (const chunk of chunks) {
+      res.write(`data: ${JSON.stringify(chunk)}\n\n`)
+    }
+    res.end('data: [DONE]\n\n')
+  }
+}
+
+const delta = (value: Record<string, unknown>) => ({ choices: [{ delta: value }] })
+
+const userMessage = (text: string): Message => ({
+  type: 'user',
+  text,
+  metadata: { createdAt: '2025-01-01T00:00:00.000Z' },
+})
+
+async function send(
+  provider: OpenAICompatibleProvider,
+  options?: Parameters<OpenAICompatibleProvider['sendMessage']>[3]
+): Promise<StreamEvent[]> {
+  const events: StreamEvent[] = []
+  await provider.sendMessage([userMessage('Hi')], 'Be brief.', (e) => events.push(e), options)
+  return events
+}
+
+describe('OpenAICompatibleProvider', () => {
+  it('streams content and usage from the server', async () => {
+    const { baseUrl, requests } = await startServer([
+      stream([
+        delta({ role: 'assistant', content: 'Hel' }),
+        delta({ content: 'lo' }),
+        { choices: [], usage: { prompt_tokens: 12, completion_tokens: 2 } },
+      ]),
+    ])
+    const provider = new OpenAICompatibleProvider({ baseUrl, apiKey: 'secret', model: 'llama' })
+
+    const events = await send(provider)
+
+    expect(events).toEqual([
+      { type: 'content', text: 'Hel' },
+      { type: 'content', text: 'lo' },
+      { type: 'done', usage: { inputTokens: 12, outputTokens: 2 } },
+    ])
+    expect(requests[0]?.path).toBe('/v1/chat/completions')
+    expect(requests[0]?.headers['authorization']).toBe('Bearer secret')
+    expect(requests[0]?.body).toMatchObject({
+      model: 'llama',
+      stream: true,
+      messages: [
+        { role: 'system', content: 'Be brief.' },
+        { role: 'user', content: 'Hi' },
+      ],
+    })
+  })
+
+  it('runs tool calls and sends the results back to the model', async () => {
+    const { baseUrl, requests } = await startServer([
+      stream([
+        delta({
+          tool_calls: [
+            { index: 0, id: 'call_1', function: { name: 'weather_get', arguments: '{"ci' } },
+          ],
+        }),
+        delta({ tool_calls: [{ index: 0, function: { arguments: 'ty":"Umeå"}' } }] }),
+      ]),
+      stream([delta({ content: 'It is 20 degrees.' })]),
+    ])
+    const provider = new OpenAICompatibleProvider({ baseUrl, model: 'llama' })
+    const calls: Array<{ toolId: string; params: Record<string, unknown> }> = []
+
+    const events = await send(provider, {
+      tools: [
+        {
+          id: 'weather.get',
+          name: 'Weather',
+          description: 'Get the weather',
+          parameters: { type: 'object', properties: { city: { type: 'string' } } },
+        },
+      ],
+      toolExecutor: async (toolId, params) => {
+        calls.push({ toolId, params })
+        return { success: true, data: { temperature: 20 } }
+      },
+      getToolDisplayName: () => 'Weather',
+    })
+
+    expect(calls).toEqual([{ toolId: 'weather.get', params: { city: 'Umeå' } }])
+    expect(events.map((e) => e.type)).toEqual(['tool', 'tool_result', 'content', 'done'])
+    expect(events[0]).toMatchObject({ name: 'weather.get', displayName: 'Weather' })
+
+    expect(requests[0]?.body.tools[0].function.name).toBe('weather_get')
+    expect(requests[1]?.body.messages.slice(-2)).toEqual([
+      {
+        role: 'assistant',
+        content: null,
+        tool_calls: [
+          {
+            id: 'call_1',
+            type: 'function',
+            function: { name: 'weather_get', arguments: '{"city":"Umeå"}' },
+          },
+        ],
+      },
+      {
+        role: 'tool',
+        tool_call_id: 'call_1',
+        content: JSON.stringify({ success: true, data: { temperature: 20 } }),
+      },
+    ])
+  })
+
+  it('uses the first model of the server when none is configured', async () => {
+    const { baseUrl, requests } = await startServer([
+      (_request, res) => {
+        res.writeHead(200, { 'Content-Type': 'application/json' })
+        res.end(JSON.stringify({ data: [{ id: 'qwen' }, { id: 'llama' }] }))
+      },
+      stream([delta({ content: 'Hi!' })]),
+    ])
+    const provider = new OpenAICompatibleProvider()
+
+    await send(provider, { settings: { baseUrl: `${baseUrl}/` } })
+
+    expect(requests.map((r) => `${r.method} ${r.path}`)).toEqual([
+      'GET /v1/models',
+      'POST /v1/chat/completions',
+    ])
+    expect(requests[1]?.body.model).toBe('qwen')
+  })
+
+  it('sends images as data URLs and describes other attachments', async () => {
+    const { baseUrl, requests } = await startServer([stream([delta({ content: 'A cat.' })])])
+    const provider = new OpenAICompatibleProvider({ baseUrl, model: 'llava' })
+    const message: Message = {
+      type: 'user',
+      text: 'What is this?',
+      attachments: [
+        { id: 'a'.repeat(64), name: 'cat.png', mimeType: 'image/png', size: 3 },
+        { id: 'b'.repeat(64), name: 'notes.pdf', mimeType: 'application/pdf', size: 2048 },
+      ],
+      metadata: { createdAt: '2025-01-01T00:00:00.000Z' },
+    }
+
+    await provider.sendMessage([message], '', () => {}, {
+      loadAttachment: async (attachment) =>
+        attachment.mimeType === 'image/png' ? new Uint8Array([1, 2, 3]) : null,
+    })
+
+    expect(requests[0]?.body.messages).toEqual([
+      {
+        role: 'user',
+        content: [
+          {
+            type: 'text',
+            text: 'What is this?\n\n[Attached file: notes.pdf (application/pdf, 2.0 KB)]',
+          },
+          { type: 'image_url', image_url: { url: 'data:image/png;base64,AQID' } },
+        ],
+      },
+    ])
+  })
+
+  it('reports server errors as stream errors', async () => {
+    const { baseUrl } = await startServer([
+      (_request, res) => {
+        res.writeHead(404).end('model "missing" not found')
+      },
+    ])
+    const provider = new OpenAICompatibleProvider({ baseUrl, model: 'missing' })
+
+    const events = await send(provider)
+
+    expect(events).toHaveLength(1)
+    expect(events[0]?.type).toBe('error')
+    expect(events[0]?.type === 'error' && events[0].error.message).toBe(
+      'OpenAI-compatible server responded with 404: model "missing" not found'
+    )
+  })
+})
+
+describe('registerOpenAICompatibleProvider', () => {
+  it('registers the provider once', () => {
+    const registry = new ProviderRegistry()
+
+    registerOpenAICompatibleProvider(registry, { STINA_LOCAL_PROVIDER_MODEL: 'llama' })
+    registerOpenAICompatibleProvider(registry, {})
+
+    expect(registry.list().map((provider) => provider.id)).toEqual([
+      OPENAI_COMPATIBLE_PROVIDER_ID,
+    ])
+  })
+})
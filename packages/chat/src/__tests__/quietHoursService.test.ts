@@ -0,0 +1,112 @@
+import { describe, it, expect } from 'vitest'
+import type { QuietHoursSettings } from '@stina/shared'
+import { QuietHoursService } from '../quietHours/QuietHoursService.js'
+import type {
+  CreateDeferredInstructionInput,
+  DeferredInstruction,
+  IDeferredInstructionRepository,
+} from '../quietHours/types.js'
+
+class InMemoryDeferredInstructionRepository implements IDeferredInstructionRepository {
+  instructions: DeferredInstruction[] = []
+  private next = 0
+
+  async list(): Promise<DeferredInstruction[]> {
+    return [...this.instructions]
+  }
+
+  async add(input: CreateDeferredInstructionInput): Promise<DeferredInstruction> {
+    const instruction: DeferredInstruction = {
+      id: `def-${++this.next}`,
+      text: input.text,
+      conversationId: input.conversationId ?? null,
+      createdAt: new Date('2025-01-15T22:30:00Z'),
+    }
+    this.instructions.push(instruction)
+    return instruction
+  }
+
+  async delete(ids: string[]): Promise<number> {
+    const before = this.instructions.length
+    this.instructions = this.instructions.filter((i) => !ids.includes(i.id))
+    return before - this.instructions.length
+  }
+}
+
+const quietSettings: QuietHoursSettings = {
+  timezone: 'UTC',
+  quietHoursEnabled: true,
+  quietHoursStart: '22:00',
+  quietHoursEnd: '07:00',
+  doNotDisturbUntil: null,
+}
+
+const at = (iso: string) => new Date(iso)
+
+function createService(options: { failDelivery?: boolean } = {}) {
+  const repository = new InMemoryDeferredInstructionRepository()
+  const delivered: Array<{ userId: string; text: string; conversationId?: string }> = []
+  const service = new QuietHoursService({
+    getSettings: async () => quietSettings,
+    getRepository: () => repository,
+    listPendingUserIds: async () => (repository.instructions.length > 0 ? ['user-1'] : []),
+    deliver: async (userId, text, conversationId) => {
+      if (options.failDelivery) throw new Error('offline')
+      delivered.push({ userId, text, conversationId })
+    },
+    getLanguage: async () => 'en',
+  })
+  return { service, repository, delivered }
+}
+
+describe('QuietHoursService', () => {
+  it('delivers instructions outside quiet hours', async () => {
+    const { service, repository, delivered } = createService()
+
+    const noon = at('2025-01-15T12:00:00Z')
+    const result = await service.appendInstruction('user-1', 'Hello', 'conv-1', noon)
+
+    expect(result.deferred).toBe(false)
+    expect(delivered).toEqual([{ userId: 'user-1', text: 'Hello', conversationId: 'conv-1' }])
+    expect(repository.instructions).toHaveLength(0)
+  })
+
+  it('defers instructions during quiet hours until they end', async () => {
+    const { service, repository, delivered } = createService()
+
+    const night = at('2025-01-15T23:00:00Z')
+    const result = await service.appendInstruction('user-1', 'Late', 'conv-1', night)
+    expect(result.deferred).toBe(true)
+    expect(delivered).toHaveLength(0)
+
+    await service.checkAll(at('2025-01-16T06:59:00Z'))
+    expect(delivered).toHaveLength(0)
+
+    await service.checkAll(at('2025-01-16T07:00:00Z'))
+    expect(delivered).toEqual([{ userId: 'user-1', text: 'Late', conversationId: 'conv-1' }])
+    expect(repository.instructions).toHaveLength(0)
+  })
+
+  it('combines several deferred instructions into one digest', async () => {
+    const { service, delivered } = createService()
+    const night = at('2025-01-15T23:00:00Z')
+    await service.appendInstruction('user-1', 'First', undefined, night)
+    await service.appendInstruction('user-1', 'Second', 'conv-2', night)
+
+    const count = await service.flush('user-1', at('2025-01-16T08:00:00Z'))
+
+    expect(count).toBe(2)
+    expect(delivered).toHaveLength(1)
+    expect(delivered[0]?.conversationId).toBeUndefined()
+    expect(delivered[0]?.text).toContain('1. [2025-01-15T22:30:00+00:00] First')
+    expect(delivered[0]?.text).toContain('2. [2025-01-15T22:30:00+00:00] Second')
+  })
+
+  it('keeps deferred instructions when delivery fails', async () => {
+    const { service, repository } = createService({ failDelivery: true })
+    await service.appendInstruction('user-1', 'Late', undefined, at('2025-01-15T23:00:00Z'))
+
+    await expect(service.flush('user-1', at('2025-01-16T08:00:00Z'))).rejects.toThrow('offline')
+    expect(repository.instructions).toHaveLength(1)
+  })
+})
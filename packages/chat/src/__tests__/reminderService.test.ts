@@ -0,0 +1,132 @@
+import { describe, it, expect } from 'vitest'
+import { ReminderService } from '../tasks/ReminderService.js'
+import type {
+  CreateReminderInput,
+  IReminderRepository,
+  Reminder,
+  ReminderScheduler,
+  UpdateReminderInput,
+} from '../tasks/types.js'
+
+class InMemoryReminderRepository implements IReminderRepository {
+  reminders: Reminder[] = []
+  private next = 0
+
+  async list(): Promise<Reminder[]> {
+    return [...this.reminders]
+  }
+
+  async get(id: string): Promise<Reminder | null> {
+    const reminder = this.reminders.find((r) => r.id === id)
+    return reminder ? { ...reminder } : null
+  }
+
+  async create(input: CreateReminderInput): Promise<Reminder> {
+    const now = new Date()
+    const reminder: Reminder = {
+      id: `rem-${++this.next}`,
+      ...input,
+      firedAt: null,
+      completedAt: null,
+      createdAt: now,
+      updatedAt: now,
+    }
+    this.reminders.push(reminder)
+    return { ...reminder }
+  }
+
+  async update(id: string, input: UpdateReminderInput): Promise<Reminder | null> {
+    const reminder = this.reminders.find((r) => r.id === id)
+    if (!reminder) return null
+    for (const [key, value] of Object.entries(input)) {
+      if (value !== undefined) Object.assign(reminder, { [key]: value })
+    }
+    return { ...reminder }
+  }
+
+  async delete(id: string): Promise<boolean> {
+    const before = this.reminders.length
+    this.reminders = this.reminders.filter((r) => r.id !== id)
+    return this.reminders.length < before
+  }
+}
+
+function createScheduler() {
+  const scheduled = new Map<string, Date>()
+  const scheduler: ReminderScheduler = {
+    schedule: async (reminder) => {
+      scheduled.set(reminder.id, reminder.remindAt)
+    },
+    cancel: async (reminderId) => {
+      scheduled.delete(reminderId)
+    },
+  }
+  return { scheduler, scheduled }
+}
+
+const at = (iso: string) => new Date(iso)
+
+describe('ReminderService', () => {
+  it('schedules new reminders', async () => {
+    const { scheduler, scheduled } = createScheduler()
+    const service = new ReminderService(new InMemoryReminderRepository(), scheduler)
+
+    const reminder = await service.create({ text: ' Call mom ', remindAt: at('2025-03-14T17:00:00Z') })
+
+    expect(reminder.text).toBe('Call mom')
+    expect(scheduled.get(reminder.id)).toEqual(at('2025-03-14T17:00:00Z'))
+  })
+
+  it('rejects empty text and invalid times', async () => {
+    const service = new ReminderService(new InMemoryReminderRepository(), createScheduler().scheduler)
+
+    await expect(service.create({ text: '  ', remindAt: new Date() })).rejects.toThrow('empty')
+    await expect(service.create({ text: 'Call', remindAt: new Date('nope') })).rejects.toThrow('Invalid')
+  })
+
+  it('reschedules a delivered reminder when its time changes', async () => {
+    const { scheduler, scheduled } = createScheduler()
+    const repository = new InMemoryReminderRepository()
+    const service = new ReminderService(repository, scheduler)
+
+    const reminder = await service.create({ text: 'Stretch', remindAt: at('2025-03-14T10:00:00Z') })
+    await service.markFired(reminder.id, at('2025-03-14T10:00:01Z'))
+
+    const updated = await service.update(reminder.id, { remindAt: at('2025-03-14T12:00:00Z') })
+    expect(updated?.firedAt).toBeNull()
+    expect(scheduled.get(reminder.id)).toEqual(at('2025-03-14T12:00:00Z'))
+  })
+
+  it('cancels delivery when completed or deleted', async () => {
+    const { scheduler, scheduled } = createScheduler()
+    const service = new ReminderService(new InMemoryReminderRepository(), scheduler)
+
+    const first = await service.create({ text: 'Water plants', remindAt: at('2025-03-14T10:00:00Z') })
+    const second = await service.create({ text: 'Pay rent', remindAt: at('2025-03-15T10:00:00Z') })
+
+    expect((await service.complete(first.id))?.completedAt).toBeInstanceOf(Date)
+    expect(await service.delete(second.id)).toBe(true)
+    expect(scheduled.size).toBe(0)
+    expect(await service.complete('missing')).toBeNull()
+  })
+
+  it('delivers a reminder once and skips completed ones', async () => {
+    const service = new ReminderService(new InMemoryReminderRepository(), createScheduler().scheduler)
+    const due = at('2025-03-14T10:00:00Z')
+
+    const reminder = await service.create({ text: 'Stand up', remindAt: due })
+    expect((await service.markFired(reminder.id, due))?.text).toBe('Stand up')
+    expect(await service.markFired(reminder.id, due)).toBeNull()
+
+    const done = await service.create({ text: 'Done already', remindAt: due })
+    await service.complete(done.id)
+    expect(await service.markFired(done.id, due)).toBeNull()
+  })
+
+  it('ignores jobs that fire before the reminder is due', async () => {
+    const service = new ReminderService(new InMemoryReminderRepository(), createScheduler().scheduler)
+
+    const reminder = await service.create({ text: 'Later', remindAt: at('2025-03-14T12:00:00Z') })
+    expect(await service.markFired(reminder.id, at('2025-03-14T10:00:00Z'))).toBeNull()
+  })
+})
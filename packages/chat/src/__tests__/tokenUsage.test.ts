@@ -0,0 +1,60 @@
+import { describe, it, expect } from 'vitest'
+import { aggregateTokenUsage, type TokenUsageRecord } from '../db/TokenUsageRepository.js'
+
+function record(overrides: Partial<TokenUsageRecord>): TokenUsageRecord {
+  return {
+    userId: 'user-a',
+    providerId: 'openai',
+    modelId: 'gpt-4o',
+    inputTokens: 100,
+    outputTokens: 10,
+    createdAt: new Date('2024-03-01T12:00:00Z'),
+    ...overrides,
+  }
+}
+
+describe('aggregateTokenUsage', () => {
+  it('sums usage per user, model and day', () => {
+    const report = aggregateTokenUsage(
+      [
+        record({}),
+        record({ userId: 'user-b', modelId: 'gpt-4o-mini', inputTokens: 50, outputTokens: 5 }),
+        record({ createdAt: new Date('2024-03-02T08:00:00Z') }),
+      ],
+      'UTC'
+    )
+
+    expect(report.totals).toEqual({ inputTokens: 250, outputTokens: 25, interactions: 3 })
+    expect(report.byUser).toEqual([
+      { userId: 'user-a', inputTokens: 200, outputTokens: 20, interactions: 2 },
+      { userId: 'user-b', inputTokens: 50, outputTokens: 5, interactions: 1 },
+    ])
+    expect(report.byModel.map((m) => [m.modelId, m.inputTokens])).toEqual([
+      ['gpt-4o', 200],
+      ['gpt-4o-mini', 50],
+    ])
+    expect(report.byDay.map((d) => [d.date, d.interactions])).toEqual([
+      ['2024-03-01', 2],
+      ['2024-03-02', 1],
+    ])
+  })
+
+  it('groups days in the requested timezone', () => {
+    const report = aggregateTokenUsage(
+      [record({ createdAt: new Date('2024-03-01T23:30:00Z') })],
+      'Europe/Stockholm'
+    )
+
+    expect(report.timezone).toBe('Europe/Stockholm')
+    expect(report.byDay[0]?.date).toBe('2024-03-02')
+  })
+
+  it('returns empty groups when there is no usage', () => {
+    const report = aggregateTokenUsage([], 'UTC')
+
+    expect(report.totals).toEqual({ inputTokens: 0, outputTokens: 0, interactions: 0 })
+    expect(report.byUser).toEqual([])
+    expect(report.byModel).toEqual([])
+    expect(report.byDay).toEqual([])
+  })
+})
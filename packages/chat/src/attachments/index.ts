@@ -0,0 +1,6 @@
+export {
+  storeAttachments,
+  MAX_ATTACHMENTS_PER_MESSAGE,
+  MAX_ATTACHMENT_BYTES_PER_MESSAGE,
+} from './storeAttachments.js'
+export type { AttachmentUpload, IAttachmentStore } from './types.js'
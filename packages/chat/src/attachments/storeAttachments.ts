@@ -0,0 +1,37 @@
+import type { MessageAttachment } from '../types/message.js'
+import type { AttachmentUpload, IAttachmentStore } from './types.js'
+
+/** Maximum number of files attached to one message */
+export const MAX_ATTACHMENTS_PER_MESSAGE = 10
+
+/** Maximum total size in bytes of the files attached to one message */
+export const MAX_ATTACHMENT_BYTES_PER_MESSAGE = 50 * 1024 * 1024
+
+/**
+ * Store the files uploaded with a message.
+ * @throws If there are more files, or more bytes, than allowed for one message
+ */
+export async function storeAttachments(
+  store: IAttachmentStore,
+  uploads: AttachmentUpload[]
+): Promise<MessageAttachment[]> {
+  if (uploads.length > MAX_ATTACHMENTS_PER_MESSAGE) {
+    throw new Error(`A message can have at most ${MAX_ATTACHMENTS_PER_MESSAGE} attachments`)
+  }
+  const totalBytes = uploads.reduce((total, upload) => total + upload.data.byteLength, 0)
+  if (totalBytes > MAX_ATTACHMENT_BYTES_PER_MESSAGE) {
+    throw new Error('The attachments of a message can be at most 50 MB in total')
+  }
+
+  const attachments: MessageAttachment[] = []
+  for (const upload of uploads) {
+    attachments.push(
+      await store.put({
+        name: upload.name.trim() || 'attachment',
+        mimeType: upload.mimeType.trim() || 'application/octet-stream',
+        data: upload.data,
+      })
+    )
+  }
+  return attachments
+}
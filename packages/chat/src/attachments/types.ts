@@ -0,0 +1,27 @@
+import type { MessageAttachment } from '../types/message.js'
+
+/**
+ * A file uploaded together with a message, before it has been stored
+ */
+export interface AttachmentUpload {
+  name: string
+  mimeType: string
+  data: Uint8Array
+}
+
+/**
+ * Content-addressed storage for message attachments.
+ * Files are keyed by the SHA-256 hash of their content, so the same file
+ * attached twice is only stored once.
+ */
+export interface IAttachmentStore {
+  /**
+   * Store a file.
+   * @returns The attachment to add to the message
+   */
+  put(upload: AttachmentUpload): Promise<MessageAttachment>
+  /**
+   * @returns The file data, or null if no file with the hash is stored
+   */
+  get(id: string): Promise<Uint8Array | null>
+}
@@ -5,4 +5,14 @@ export {
   getPromptUpdatePrefix,
   getPromptUpdateInfo,
   getPromptUpdateInstruction,
+  getContextSummarySystemPrompt,
+  getContextSummaryRequest,
+  getContextSummaryPrefix,
+  getContextSummaryInfo,
+  getConversationTitleSystemPrompt,
+  getConversationTitleRequest,
+  getMemoryPrompt,
+  isPersonalityPreset,
+  PERSONALITY_PRESETS,
 } from './systemPrompt.js'
+export type { PersonalityPreset, SystemPromptOverrides } from './systemPrompt.js'
@@ -21,6 +21,36 @@ const SECTION_ORDER: Record<PromptSection, number> = {
   tools: 20,
 }
 
+/** Personality presets selectable in settings and per conversation */
+export const PERSONALITY_PRESETS = [
+  'friendly',
+  'concise',
+  'sarcastic',
+  'professional',
+  'informative',
+  'creative',
+  'custom',
+] as const
+
+export type PersonalityPreset = (typeof PERSONALITY_PRESETS)[number]
+
+/**
+ * Check whether a value is a known personality preset.
+ */
+export function isPersonalityPreset(value: unknown): value is PersonalityPreset {
+  return PERSONALITY_PRESETS.includes(value as PersonalityPreset)
+}
+
+/**
+ * Conversation-level changes to the system prompt
+ */
+export interface SystemPromptOverrides {
+  /** Personality preset used instead of the user's setting */
+  personalityPreset?: PersonalityPreset
+  /** Extra text added to the end of the system prompt */
+  additionalPrompt?: string
+}
+
 interface PromptContext {
   lang: string
   t: (path: string, vars?: Record<string, string | number>) => string
@@ -43,14 +73,19 @@ function getPromptContext(settingsStore?: SettingsStore): PromptContext {
  * Can be overridden via Settings (app.systemPrompt)
  *
  * @param settingsStore - Optional settings store to check for user override
+ * @param overrides - Optional conversation-level personality and additional prompt
  * @returns System prompt in user's language
  */
-export function getSystemPrompt(settingsStore?: SettingsStore): string {
+export function getSystemPrompt(
+  settingsStore?: SettingsStore,
+  overrides: SystemPromptOverrides = {}
+): string {
   const override = settingsStore?.get<string>(APP_NAMESPACE, 'systemPrompt')
+  const additionalPrompt = overrides.additionalPrompt?.trim() ?? ''
 
   // Check for user override in settings
   if (typeof override === 'string' && override.trim()) {
-    return override
+    return additionalPrompt ? `${override}\n\n${additionalPrompt}` : override
   }
 
   const { t, name, nickName, lang } = getPromptContext(settingsStore)
@@ -73,7 +108,7 @@ export function getSystemPrompt(settingsStore?: SettingsStore): string {
     text: t('chat.system_prompt.purpose'),
   })
 
-  const personalityPrompt = getPersonalityPrompt(settingsStore, t)
+  const personalityPrompt = getPersonalityPrompt(settingsStore, t, overrides.personalityPreset)
   if (personalityPrompt) {
     pushChunk(chunks, {
       section: 'behavior',
@@ -88,6 +123,12 @@ export function getSystemPrompt(settingsStore?: SettingsStore): string {
     text: t('chat.system_prompt.tools'),
   })
 
+  pushChunk(chunks, {
+    section: 'behavior',
+    order: 100,
+    text: additionalPrompt,
+  })
+
   chunks.push(...getExtensionPromptChunks(lang))
 
   const sorted = chunks
@@ -133,6 +174,68 @@ export function getPromptUpdateInstruction(settingsStore?: SettingsStore): strin
   return t('chat.system_prompt.updated_instruction')
 }
 
+/**
+ * System prompt used when condensing older interactions into a summary.
+ */
+export function getContextSummarySystemPrompt(settingsStore?: SettingsStore): string {
+  const { t } = getPromptContext(settingsStore)
+  return t('chat.context_summary.system_prompt')
+}
+
+/**
+ * Request appended after the interactions that should be summarized.
+ */
+export function getContextSummaryRequest(settingsStore?: SettingsStore): string {
+  const { t } = getPromptContext(settingsStore)
+  return t('chat.context_summary.request')
+}
+
+/**
+ * Prefix placed before a stored context summary when it is sent to the model.
+ */
+export function getContextSummaryPrefix(settingsStore?: SettingsStore): string {
+  const { t } = getPromptContext(settingsStore)
+  return t('chat.context_summary.prefix')
+}
+
+/**
+ * Info message shown when older interactions have been summarized.
+ */
+export function getContextSummaryInfo(settingsStore?: SettingsStore): string {
+  const { t } = getPromptContext(settingsStore)
+  return t('chat.context_summary.info')
+}
+
+/**
+ * System prompt used when asking a model to name a conversation.
+ */
+export function getConversationTitleSystemPrompt(settingsStore?: SettingsStore): string {
+  const { t } = getPromptContext(settingsStore)
+  return t('chat.conversation_title.system_prompt')
+}
+
+/**
+ * Request appended after the messages a conversation title should be based on.
+ */
+export function getConversationTitleRequest(settingsStore?: SettingsStore): string {
+  const { t } = getPromptContext(settingsStore)
+  return t('chat.conversation_title.request')
+}
+
+/**
+ * Prompt chunk with long-term memories relevant to the current message.
+ * Added per request after the regular system prompt, so it is not stored with
+ * the conversation and does not count as a prompt change.
+ * @returns The chunk text, or an empty string when there are no memories
+ */
+export function getMemoryPrompt(memories: string[], settingsStore?: SettingsStore): string {
+  const items = memories.map((memory) => memory.trim()).filter((memory) => memory.length > 0)
+  if (items.length === 0) return ''
+
+  const { t } = getPromptContext(settingsStore)
+  return `${t('chat.memory.prompt')}\n${items.map((memory) => `- ${memory}`).join('\n')}`
+}
+
 /**
  * Add a prompt chunk if it has meaningful content.
  */
@@ -143,23 +246,17 @@ function pushChunk(target: PromptChunk[], chunk: PromptChunk): void {
 
 /**
  * Resolve the personality prompt based on app settings.
+ * A conversation's preset takes precedence over the user's.
  * Returns null when no personality chunk should be added.
  */
 function getPersonalityPrompt(
   settingsStore: SettingsStore | undefined,
-  t: (path: string, vars?: Record<string, string | number>) => string
+  t: (path: string, vars?: Record<string, string | number>) => string,
+  presetOverride?: PersonalityPreset
 ): string | null {
-  const presetValue = settingsStore?.get<string>(APP_NAMESPACE, 'personalityPreset')
-  const preset =
-    presetValue === 'friendly' ||
-    presetValue === 'concise' ||
-    presetValue === 'sarcastic' ||
-    presetValue === 'professional' ||
-    presetValue === 'informative' ||
-    presetValue === 'creative' ||
-    presetValue === 'custom'
-      ? presetValue
-      : 'friendly'
+  const presetValue =
+    presetOverride ?? settingsStore?.get<string>(APP_NAMESPACE, 'personalityPreset')
+  const preset = isPersonalityPreset(presetValue) ? presetValue : 'friendly'
   const customPrompt = settingsStore?.get<string>(APP_NAMESPACE, 'customPersonalityPrompt')
 
   if (preset === 'custom') {
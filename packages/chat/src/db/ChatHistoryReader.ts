@@ -0,0 +1,146 @@
+import { conversations, interactions } from './schema.js'
+import type { ChatDb } from './schema.js'
+import { ConversationRepository } from './repository.js'
+import { and, desc, eq, inArray, max } from 'drizzle-orm'
+import type { Interaction } from '../types/index.js'
+import type {
+  ChatConversationSummary,
+  ChatCurrentConversation,
+  ChatHistoryInteraction,
+  ChatHistoryMessage,
+  ChatHistoryQuery,
+} from '@stina/extension-api'
+
+/** Default number of items returned by chat history reads */
+const DEFAULT_HISTORY_LIMIT = 20
+
+/** Maximum number of items returned by chat history reads */
+const MAX_HISTORY_LIMIT = 100
+
+/**
+ * Read-only access to chat history for extensions.
+ * Every read is scoped to query.userId; callers are responsible for checking
+ * that the extension has the required permission.
+ * @param db - The chat database instance.
+ */
+export class ChatHistoryReader {
+  constructor(private db: ChatDb) {}
+
+  /**
+   * List the user's active conversations, newest first.
+   */
+  async listConversations(query: ChatHistoryQuery): Promise<ChatConversationSummary[]> {
+    const { limit, offset } = resolveHistoryPage(query)
+    const convs = await this.db
+      .select()
+      .from(conversations)
+      .where(and(eq(conversations.userId, query.userId), eq(conversations.active, true)))
+      .orderBy(desc(conversations.createdAt))
+      .limit(limit)
+      .offset(offset)
+
+    if (convs.length === 0) return []
+
+    const latest = await this.db
+      .select({
+        conversationId: interactions.conversationId,
+        lastMessageAt: max(interactions.createdAt),
+      })
+      .from(interactions)
+      .where(
+        inArray(
+          interactions.conversationId,
+          convs.map((c) => c.id)
+        )
+      )
+      .groupBy(interactions.conversationId)
+
+    const lastMessageAtById = new Map(latest.map((row) => [row.conversationId, row.lastMessageAt]))
+
+    return convs.map((conv) => {
+      const lastMessageAt = lastMessageAtById.get(conv.id)
+      return {
+        id: conv.id,
+        title: conv.title ?? undefined,
+        active: conv.active,
+        createdAt: conv.createdAt.toISOString(),
+        lastMessageAt: lastMessageAt ? lastMessageAt.toISOString() : undefined,
+      }
+    })
+  }
+
+  /**
+   * Get interactions in one of the user's conversations, newest first.
+   * @throws If the conversation does not exist or belongs to another user.
+   */
+  async getInteractions(
+    conversationId: string,
+    query: ChatHistoryQuery
+  ): Promise<ChatHistoryInteraction[]> {
+    const repository = new ConversationRepository(this.db, query.userId)
+    const conversation = await repository.getConversation(conversationId)
+    if (!conversation) {
+      throw new Error(`Conversation not found: ${conversationId}`)
+    }
+
+    const { limit, offset } = resolveHistoryPage(query)
+    const inters = await repository.getConversationInteractions(conversationId, limit, offset)
+    return inters.map(toChatHistoryInteraction)
+  }
+
+  /**
+   * Get the user's current (latest active) conversation with its most recent interactions.
+   */
+  async getCurrentConversation(query: ChatHistoryQuery): Promise<ChatCurrentConversation | null> {
+    const repository = new ConversationRepository(this.db, query.userId)
+    const conversation = await repository.getLatestActiveConversation()
+    if (!conversation) return null
+
+    const { limit, offset } = resolveHistoryPage(query)
+    const inters = await repository.getConversationInteractions(conversation.id, limit, offset)
+
+    return {
+      id: conversation.id,
+      title: conversation.title,
+      active: conversation.active,
+      createdAt: conversation.metadata.createdAt,
+      lastMessageAt: inters[0]?.metadata.createdAt,
+      interactions: inters.map(toChatHistoryInteraction),
+    }
+  }
+}
+
+/**
+ * Apply the default and maximum page size to a chat history query.
+ */
+export function resolveHistoryPage(query: ChatHistoryQuery): { limit: number; offset: number } {
+  return {
+    limit: Math.min(query.limit ?? DEFAULT_HISTORY_LIMIT, MAX_HISTORY_LIMIT),
+    offset: query.offset ?? 0,
+  }
+}
+
+/**
+ * Map an interaction to the shape exposed to extensions.
+ * Only user and assistant messages are included; instructions, thinking and
+ * tool calls are internal and never leave the host.
+ */
+export function toChatHistoryInteraction(interaction: Interaction): ChatHistoryInteraction {
+  const messages: ChatHistoryMessage[] = []
+  for (const message of interaction.messages) {
+    if (message.type === 'user' || message.type === 'stina') {
+      messages.push({
+        role: message.type === 'user' ? 'user' : 'assistant',
+        text: message.text,
+        createdAt: message.metadata.createdAt,
+      })
+    }
+  }
+
+  return {
+    id: interaction.id,
+    conversationId: interaction.conversationId,
+    createdAt: interaction.metadata.createdAt,
+    messages,
+  }
+}
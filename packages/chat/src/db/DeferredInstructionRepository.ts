@@ -0,0 +1,85 @@
+import { nanoid } from 'nanoid'
+import { and, asc, eq, inArray } from 'drizzle-orm'
+import { deferredInstructions } from './schema.js'
+import type { ChatDb } from './schema.js'
+import type {
+  CreateDeferredInstructionInput,
+  DeferredInstruction,
+  IDeferredInstructionRepository,
+} from '../quietHours/types.js'
+
+/**
+ * Database repository for instructions deferred during quiet hours.
+ * @param db - The chat database instance.
+ * @param userId - User ID for multi-user filtering (required).
+ */
+export class DeferredInstructionRepository implements IDeferredInstructionRepository {
+  constructor(
+    private db: ChatDb,
+    private userId: string
+  ) {}
+
+  /**
+   * List the user's deferred instructions, oldest first.
+   */
+  async list(): Promise<DeferredInstruction[]> {
+    const rows = await this.db
+      .select()
+      .from(deferredInstructions)
+      .where(eq(deferredInstructions.userId, this.userId))
+      .orderBy(asc(deferredInstructions.createdAt))
+    return rows.map(toDeferredInstruction)
+  }
+
+  /**
+   * Store an instruction for later delivery.
+   * @param input - Text and target conversation.
+   * @returns The stored instruction.
+   */
+  async add(input: CreateDeferredInstructionInput): Promise<DeferredInstruction> {
+    const instruction: DeferredInstruction = {
+      id: nanoid(),
+      text: input.text,
+      conversationId: input.conversationId ?? null,
+      createdAt: new Date(),
+    }
+
+    await this.db.insert(deferredInstructions).values({ ...instruction, userId: this.userId })
+    return instruction
+  }
+
+  /**
+   * Delete delivered instructions.
+   * @param ids - Instruction IDs.
+   * @returns The number of deleted instructions.
+   */
+  async delete(ids: string[]): Promise<number> {
+    if (ids.length === 0) return 0
+    const result = await this.db
+      .delete(deferredInstructions)
+      .where(
+        and(eq(deferredInstructions.userId, this.userId), inArray(deferredInstructions.id, ids))
+      )
+    return result.changes
+  }
+
+  /**
+   * List the users that have deferred instructions.
+   * @param db - The chat database instance.
+   */
+  static async listUserIds(db: ChatDb): Promise<string[]> {
+    const rows = await db
+      .selectDistinct({ userId: deferredInstructions.userId })
+      .from(deferredInstructions)
+    return rows.map((row) => row.userId)
+  }
+}
+
+function toDeferredInstruction(row: typeof deferredInstructions.$inferSelect): DeferredInstruction {
+  return {
+    id: row.id,
+    text: row.text,
+    conversationId: row.conversationId,
+    createdAt: row.createdAt,
+  }
+}
@@ -0,0 +1,88 @@
+import { extensionFileRoots } from './schema.js'
+import type { ChatDb } from './schema.js'
+import { eq, and } from 'drizzle-orm'
+
+/**
+ * Directory approved for an extension's file access
+ */
+export interface ExtensionFileRoot {
+  extensionId: string
+  path: string
+  access: 'read' | 'readwrite'
+  createdAt: Date
+}
+
+/**
+ * Database repository for extension file roots.
+ * Manages the directories each user has approved per extension.
+ * @param db - The chat database instance.
+ * @param userId - User ID for multi-user filtering (required).
+ */
+export class ExtensionFileRootRepository {
+  constructor(
+    private db: ChatDb,
+    private userId: string
+  ) {}
+
+  /**
+   * Get all approved roots for an extension.
+   * @param extensionId - The extension ID.
+   * @returns Array of approved roots.
+   */
+  async getForExtension(extensionId: string): Promise<ExtensionFileRoot[]> {
+    const rows = await this.db
+      .select()
+      .from(extensionFileRoots)
+      .where(
+        and(
+          eq(extensionFileRoots.userId, this.userId),
+          eq(extensionFileRoots.extensionId, extensionId)
+        )
+      )
+    return rows.map((row) => ({
+      extensionId: row.extensionId,
+      path: row.path,
+      access: row.access,
+      createdAt: row.createdAt,
+    }))
+  }
+
+  /**
+   * Approve a directory for an extension, or change its access level.
+   * @param extensionId - The extension ID.
+   * @param path - Absolute directory path.
+   * @param access - Whether the extension may only read or also write.
+   */
+  async set(extensionId: string, path: string, access: 'read' | 'readwrite'): Promise<void> {
+    await this.db
+      .insert(extensionFileRoots)
+      .values({
+        userId: this.userId,
+        extensionId,
+        path,
+        access,
+        createdAt: new Date(),
+      })
+      .onConflictDoUpdate({
+        target: [extensionFileRoots.userId, extensionFileRoots.extensionId, extensionFileRoots.path],
+        set: { access },
+      })
+  }
+
+  /**
+   * Revoke an approved directory.
+   * @param extensionId - The extension ID.
+   * @param path - The directory path as stored.
+   */
+  async remove(extensionId: string, path: string): Promise<void> {
+    await this.db
+      .delete(extensionFileRoots)
+      .where(
+        and(
+          eq(extensionFileRoots.userId, this.userId),
+          eq(extensionFileRoots.extensionId, extensionId),
+          eq(extensionFileRoots.path, path)
+        )
+      )
+  }
+}
@@ -0,0 +1,59 @@
+import { createHash } from 'node:crypto'
+import { mkdir, readFile, rename, stat, writeFile } from 'node:fs/promises'
+import path from 'node:path'
+import type { MessageAttachment } from '../types/message.js'
+import type { AttachmentUpload, IAttachmentStore } from '../attachments/types.js'
+
+const HASH_PATTERN = /^[0-9a-f]{64}$/
+
+/**
+ * Attachment store that keeps files on disk, named by their SHA-256 hash.
+ * Files are spread over subdirectories by the first two characters of the
+ * hash: `<directory>/ab/abcdef...`.
+ */
+export class FileAttachmentStore implements IAttachmentStore {
+  constructor(private readonly directory: string) {}
+
+  async put(upload: AttachmentUpload): Promise<MessageAttachment> {
+    const id = createHash('sha256').update(upload.data).digest('hex')
+    const filePath = this.getFilePath(id)
+
+    if (!(await this.exists(filePath))) {
+      await mkdir(path.dirname(filePath), { recursive: true })
+      // Write to a temporary file first so a partial file is never read as complete
+      const tempPath = `${filePath}.${process.pid}.${Date.now()}.tmp`
+      await writeFile(tempPath, upload.data)
+      await rename(tempPath, filePath)
+    }
+
+    return {
+      id,
+      name: upload.name,
+      mimeType: upload.mimeType,
+      size: upload.data.byteLength,
+    }
+  }
+
+  async get(id: string): Promise<Uint8Array | null> {
+    if (!HASH_PATTERN.test(id)) return null
+    try {
+      return new Uint8Array(await readFile(this.getFilePath(id)))
+    } catch (error) {
+      if ((error as NodeJS.ErrnoException).code === 'ENOENT') return null
+      throw error
+    }
+  }
+
+  private getFilePath(id: string): string {
+    return path.join(this.directory, id.slice(0, 2), id)
+  }
+
+  private async exists(filePath: string): Promise<boolean> {
+    try {
+      await stat(filePath)
+      return true
+    } catch {
+      return false
+    }
+  }
+}
@@ -0,0 +1,120 @@
+import { nanoid } from 'nanoid'
+import { and, asc, eq } from 'drizzle-orm'
+import { memories } from './schema.js'
+import type { ChatDb } from './schema.js'
+import type { CreateMemoryInput, IMemoryRepository, Memory } from '../memory/types.js'
+
+/**
+ * Database repository for long-term memories.
+ * Embeddings are stored as Float32 blobs.
+ * @param db - The chat database instance.
+ * @param userId - User ID for multi-user filtering (required).
+ */
+export class MemoryRepository implements IMemoryRepository {
+  constructor(
+    private db: ChatDb,
+    private userId: string
+  ) {}
+
+  /**
+   * Get all memories for the user, oldest first.
+   */
+  async list(): Promise<Memory[]> {
+    const rows = await this.db
+      .select()
+      .from(memories)
+      .where(eq(memories.userId, this.userId))
+      .orderBy(asc(memories.createdAt))
+    return rows.map(toMemory)
+  }
+
+  /**
+   * Get a memory by ID.
+   * @param id - The memory ID.
+   * @returns The memory, or null if it does not exist for the user.
+   */
+  async get(id: string): Promise<Memory | null> {
+    const rows = await this.db
+      .select()
+      .from(memories)
+      .where(and(eq(memories.id, id), eq(memories.userId, this.userId)))
+      .limit(1)
+    return rows[0] ? toMemory(rows[0]) : null
+  }
+
+  /**
+   * Store a new memory.
+   * @param input - Content and optional embedding.
+   * @returns The created memory.
+   */
+  async create(input: CreateMemoryInput): Promise<Memory> {
+    const now = new Date()
+    const memory: Memory = {
+      id: nanoid(),
+      content: input.content,
+      embedding: input.embedding,
+      embeddingModel: input.embedding ? input.embeddingModel : null,
+      createdAt: now,
+      updatedAt: now,
+    }
+
+    await this.db.insert(memories).values({
+      id: memory.id,
+      userId: this.userId,
+      content: memory.content,
+      embedding: memory.embedding ? encodeVector(memory.embedding) : null,
+      embeddingModel: memory.embeddingModel,
+      createdAt: now,
+      updatedAt: now,
+    })
+
+    return memory
+  }
+
+  /**
+   * Replace the embedding of a memory.
+   * @param id - The memory ID.
+   * @param embedding - New vector.
+   * @param embeddingModel - Model that produced the vector.
+   */
+  async updateEmbedding(id: string, embedding: number[], embeddingModel: string): Promise<void> {
+    await this.db
+      .update(memories)
+      .set({ embedding: encodeVector(embedding), embeddingModel, updatedAt: new Date() })
+      .where(and(eq(memories.id, id), eq(memories.userId, this.userId)))
+  }
+
+  /**
+   * Delete a memory.
+   * @param id - The memory ID.
+   * @returns True if a memory was deleted.
+   */
+  async delete(id: string): Promise<boolean> {
+    const result = await this.db
+      .delete(memories)
+      .where(and(eq(memories.id, id), eq(memories.userId, this.userId)))
+    return result.changes > 0
+  }
+}
+
+function encodeVector(vector: number[]): Buffer {
+  const floats = Float32Array.from(vector)
+  return Buffer.from(floats.buffer, floats.byteOffset, floats.byteLength)
+}
+
+function decodeVector(blob: Buffer): number[] {
+  // Copy so the Float32Array is correctly aligned regardless of the Buffer's offset
+  const bytes = new Uint8Array(blob)
+  return Array.from(new Float32Array(bytes.buffer, bytes.byteOffset, bytes.byteLength / 4))
+}
+
+function toMemory(row: typeof memories.$inferSelect): Memory {
+  return {
+    id: row.id,
+    content: row.content,
+    embedding: row.embedding ? decodeVector(row.embedding) : null,
+    embeddingModel: row.embeddingModel,
+    createdAt: row.createdAt,
+    updatedAt: row.updatedAt,
+  }
+}
@@ -12,6 +12,8 @@ export interface ModelConfig {
   providerExtensionId: string
   modelId: string
   settingsOverride?: Record<string, unknown>
+  /** Context window size in tokens, if known */
+  contextLength?: number
   createdAt: string
   updatedAt: string
 }
@@ -49,6 +51,7 @@ export class ModelConfigRepository {
       providerExtensionId: row.providerExtensionId,
       modelId: row.modelId,
       settingsOverride: row.settingsOverride ?? undefined,
+      contextLength: row.contextLength ?? undefined,
       createdAt: row.createdAt.toISOString(),
       updatedAt: row.updatedAt.toISOString(),
     }))
@@ -74,6 +77,7 @@ export class ModelConfigRepository {
       providerExtensionId: row.providerExtensionId,
       modelId: row.modelId,
       settingsOverride: row.settingsOverride ?? undefined,
+      contextLength: row.contextLength ?? undefined,
       createdAt: row.createdAt.toISOString(),
       updatedAt: row.updatedAt.toISOString(),
     }
@@ -92,6 +96,7 @@ export class ModelConfigRepository {
       providerExtensionId: input.providerExtensionId,
       modelId: input.modelId,
       settingsOverride: input.settingsOverride ?? null,
+      contextLength: input.contextLength ?? null,
       createdAt: now,
       updatedAt: now,
     })
@@ -124,6 +129,7 @@ export class ModelConfigRepository {
     if (input['modelId'] !== undefined) updateData['modelId'] = input['modelId']
     if (input['settingsOverride'] !== undefined)
       updateData['settingsOverride'] = input['settingsOverride']
+    if (input['contextLength'] !== undefined) updateData['contextLength'] = input['contextLength']
 
     await this.db.update(modelConfigs).set(updateData).where(eq(modelConfigs.id, id))
 
@@ -0,0 +1,118 @@
+import { nanoid } from 'nanoid'
+import { and, asc, eq, isNull } from 'drizzle-orm'
+import { reminders } from './schema.js'
+import type { ChatDb } from './schema.js'
+import type {
+  CreateReminderInput,
+  IReminderRepository,
+  ListTasksOptions,
+  Reminder,
+  UpdateReminderInput,
+} from '../tasks/types.js'
+
+/**
+ * Database repository for reminders.
+ * Scheduling is handled by ReminderService; this class only stores them.
+ * @param db - The chat database instance.
+ * @param userId - User ID for multi-user filtering (required).
+ */
+export class ReminderRepository implements IReminderRepository {
+  constructor(
+    private db: ChatDb,
+    private userId: string
+  ) {}
+
+  /**
+   * List reminders by time.
+   * @param options - Whether to include completed reminders.
+   */
+  async list(options: ListTasksOptions = {}): Promise<Reminder[]> {
+    const rows = await this.db
+      .select()
+      .from(reminders)
+      .where(
+        options.includeCompleted
+          ? eq(reminders.userId, this.userId)
+          : and(eq(reminders.userId, this.userId), isNull(reminders.completedAt))
+      )
+      .orderBy(asc(reminders.remindAt))
+    return rows.map(toReminder)
+  }
+
+  /**
+   * Get a reminder by ID.
+   * @param id - The reminder ID.
+   * @returns The reminder, or null if it does not exist for the user.
+   */
+  async get(id: string): Promise<Reminder | null> {
+    const rows = await this.db
+      .select()
+      .from(reminders)
+      .where(and(eq(reminders.id, id), eq(reminders.userId, this.userId)))
+      .limit(1)
+    return rows[0] ? toReminder(rows[0]) : null
+  }
+
+  /**
+   * Create a reminder.
+   * @param input - Text and time.
+   * @returns The created reminder.
+   */
+  async create(input: CreateReminderInput): Promise<Reminder> {
+    const now = new Date()
+    const reminder: Reminder = {
+      id: nanoid(),
+      text: input.text,
+      remindAt: input.remindAt,
+      firedAt: null,
+      completedAt: null,
+      createdAt: now,
+      updatedAt: now,
+    }
+
+    await this.db.insert(reminders).values({ ...reminder, userId: this.userId })
+    return reminder
+  }
+
+  /**
+   * Update a reminder. Fields left undefined are kept.
+   * @param id - The reminder ID.
+   * @param input - Fields to change.
+   * @returns The updated reminder, or null if it does not exist.
+   */
+  async update(id: string, input: UpdateReminderInput): Promise<Reminder | null> {
+    const set = Object.fromEntries(
+      Object.entries(input).filter(([, value]) => value !== undefined)
+    ) as UpdateReminderInput
+
+    await this.db
+      .update(reminders)
+      .set({ ...set, updatedAt: new Date() })
+      .where(and(eq(reminders.id, id), eq(reminders.userId, this.userId)))
+    return this.get(id)
+  }
+
+  /**
+   * Delete a reminder.
+   * @param id - The reminder ID.
+   * @returns True if a reminder was deleted.
+   */
+  async delete(id: string): Promise<boolean> {
+    const result = await this.db
+      .delete(reminders)
+      .where(and(eq(reminders.id, id), eq(reminders.userId, this.userId)))
+    return result.changes > 0
+  }
+}
+
+function toReminder(row: typeof reminders.$inferSelect): Reminder {
+  return {
+    id: row.id,
+    text: row.text,
+    remindAt: row.remindAt,
+    firedAt: row.firedAt,
+    completedAt: row.completedAt,
+    createdAt: row.createdAt,
+    updatedAt: row.updatedAt,
+  }
+}
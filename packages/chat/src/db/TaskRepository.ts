@@ -0,0 +1,117 @@
+import { nanoid } from 'nanoid'
+import { and, asc, eq, isNull, sql } from 'drizzle-orm'
+import { tasks } from './schema.js'
+import type { ChatDb } from './schema.js'
+import type {
+  CreateTaskInput,
+  ITaskRepository,
+  ListTasksOptions,
+  Task,
+  UpdateTaskInput,
+} from '../tasks/types.js'
+
+/**
+ * Database repository for tasks.
+ * @param db - The chat database instance.
+ * @param userId - User ID for multi-user filtering (required).
+ */
+export class TaskRepository implements ITaskRepository {
+  constructor(
+    private db: ChatDb,
+    private userId: string
+  ) {}
+
+  /**
+   * List tasks, by due date (undated last) and then creation time.
+   * @param options - Whether to include completed tasks.
+   */
+  async list(options: ListTasksOptions = {}): Promise<Task[]> {
+    const rows = await this.db
+      .select()
+      .from(tasks)
+      .where(
+        options.includeCompleted
+          ? eq(tasks.userId, this.userId)
+          : and(eq(tasks.userId, this.userId), isNull(tasks.completedAt))
+      )
+      .orderBy(sql`${tasks.dueAt} IS NULL`, asc(tasks.dueAt), asc(tasks.createdAt))
+    return rows.map(toTask)
+  }
+
+  /**
+   * Get a task by ID.
+   * @param id - The task ID.
+   * @returns The task, or null if it does not exist for the user.
+   */
+  async get(id: string): Promise<Task | null> {
+    const rows = await this.db
+      .select()
+      .from(tasks)
+      .where(and(eq(tasks.id, id), eq(tasks.userId, this.userId)))
+      .limit(1)
+    return rows[0] ? toTask(rows[0]) : null
+  }
+
+  /**
+   * Create a task.
+   * @param input - Title, notes and due date.
+   * @returns The created task.
+   */
+  async create(input: CreateTaskInput): Promise<Task> {
+    const now = new Date()
+    const task: Task = {
+      id: nanoid(),
+      title: input.title,
+      notes: input.notes ?? null,
+      dueAt: input.dueAt ?? null,
+      completedAt: null,
+      createdAt: now,
+      updatedAt: now,
+    }
+
+    await this.db.insert(tasks).values({ ...task, userId: this.userId })
+    return task
+  }
+
+  /**
+   * Update a task. Fields left undefined are kept.
+   * @param id - The task ID.
+   * @param input - Fields to change.
+   * @returns The updated task, or null if it does not exist.
+   */
+  async update(id: string, input: UpdateTaskInput): Promise<Task | null> {
+    const set = Object.fromEntries(
+      Object.entries(input).filter(([, value]) => value !== undefined)
+    ) as UpdateTaskInput
+
+    await this.db
+      .update(tasks)
+      .set({ ...set, updatedAt: new Date() })
+      .where(and(eq(tasks.id, id), eq(tasks.userId, this.userId)))
+    return this.get(id)
+  }
+
+  /**
+   * Delete a task.
+   * @param id - The task ID.
+   * @returns True if a task was deleted.
+   */
+  async delete(id: string): Promise<boolean> {
+    const result = await this.db
+      .delete(tasks)
+      .where(and(eq(tasks.id, id), eq(tasks.userId, this.userId)))
+    return result.changes > 0
+  }
+}
+
+function toTask(row: typeof tasks.$inferSelect): Task {
+  return {
+    id: row.id,
+    title: row.title,
+    notes: row.notes,
+    dueAt: row.dueAt,
+    completedAt: row.completedAt,
+    createdAt: row.createdAt,
+    updatedAt: row.updatedAt,
+  }
+}
@@ -0,0 +1,155 @@
+import { conversations, interactions } from './schema.js'
+import type { ChatDb } from './schema.js'
+import { and, eq, gte, isNotNull, lt, or, type SQL } from 'drizzle-orm'
+import type { TokenUsageReportDTO, TokenUsageTotalsDTO } from '@stina/shared'
+
+/**
+ * Token usage of a single interaction
+ */
+export interface TokenUsageRecord {
+  userId: string
+  providerId: string | null
+  modelId: string | null
+  inputTokens: number
+  outputTokens: number
+  createdAt: Date
+}
+
+/**
+ * Filter for token usage queries
+ */
+export interface TokenUsageFilter {
+  /** Only include this user's usage. When omitted, all users are included. */
+  userId?: string
+  /** Inclusive start */
+  from?: Date
+  /** Exclusive end */
+  to?: Date
+}
+
+/**
+ * Database repository for token usage reported by providers.
+ * Usage is read from chat_interactions; this repository is not user-scoped,
+ * callers decide whose usage may be read.
+ * @param db - The chat database instance.
+ */
+export class TokenUsageRepository {
+  constructor(private db: ChatDb) {}
+
+  /**
+   * List usage records matching the filter, oldest first.
+   */
+  async listRecords(filter: TokenUsageFilter = {}): Promise<TokenUsageRecord[]> {
+    const conditions: SQL[] = [
+      or(isNotNull(interactions.inputTokens), isNotNull(interactions.outputTokens))!,
+    ]
+    if (filter.userId) conditions.push(eq(conversations.userId, filter.userId))
+    if (filter.from) conditions.push(gte(interactions.createdAt, filter.from))
+    if (filter.to) conditions.push(lt(interactions.createdAt, filter.to))
+
+    const rows = await this.db
+      .select({
+        userId: conversations.userId,
+        providerId: interactions.providerId,
+        modelId: interactions.modelId,
+        inputTokens: interactions.inputTokens,
+        outputTokens: interactions.outputTokens,
+        createdAt: interactions.createdAt,
+      })
+      .from(interactions)
+      .innerJoin(conversations, eq(interactions.conversationId, conversations.id))
+      .where(and(...conditions))
+      .orderBy(interactions.createdAt)
+
+    return rows.map((row) => ({
+      userId: row.userId,
+      providerId: row.providerId,
+      modelId: row.modelId,
+      inputTokens: row.inputTokens ?? 0,
+      outputTokens: row.outputTokens ?? 0,
+      createdAt: row.createdAt,
+    }))
+  }
+
+  /**
+   * Build a usage report with per-user, per-model and per-day aggregates.
+   * @param filter - Which usage to include.
+   * @param timezone - IANA timezone used to group usage per day.
+   */
+  async getReport(filter: TokenUsageFilter, timezone: string): Promise<TokenUsageReportDTO> {
+    const records = await this.listRecords(filter)
+    return aggregateTokenUsage(records, timezone)
+  }
+}
+
+/**
+ * Aggregate usage records into totals per user, per model and per day.
+ * Groups are sorted by user ID, model ID and date respectively.
+ */
+export function aggregateTokenUsage(
+  records: TokenUsageRecord[],
+  timezone: string
+): TokenUsageReportDTO {
+  const dayFormatter = new Intl.DateTimeFormat('en-CA', {
+    timeZone: timezone,
+    year: 'numeric',
+    month: '2-digit',
+    day: '2-digit',
+  })
+
+  const totals = emptyTotals()
+  const byUser = new Map<string, TokenUsageTotalsDTO & { userId: string }>()
+  const byModel = new Map<
+    string,
+    TokenUsageTotalsDTO & { providerId: string | null; modelId: string | null }
+  >()
+  const byDay = new Map<string, TokenUsageTotalsDTO & { date: string }>()
+
+  for (const record of records) {
+    addUsage(totals, record)
+
+    let user = byUser.get(record.userId)
+    if (!user) {
+      user = { userId: record.userId, ...emptyTotals() }
+      byUser.set(record.userId, user)
+    }
+    addUsage(user, record)
+
+    const modelKey = `${record.providerId ?? ''}\u0000${record.modelId ?? ''}`
+    let model = byModel.get(modelKey)
+    if (!model) {
+      model = { providerId: record.providerId, modelId: record.modelId, ...emptyTotals() }
+      byModel.set(modelKey, model)
+    }
+    addUsage(model, record)
+
+    // en-CA formats dates as YYYY-MM-DD
+    const date = dayFormatter.format(record.createdAt)
+    let day = byDay.get(date)
+    if (!day) {
+      day = { date, ...emptyTotals() }
+      byDay.set(date, day)
+    }
+    addUsage(day, record)
+  }
+
+  return {
+    timezone,
+    totals,
+    byUser: [...byUser.values()].sort((a, b) => a.userId.localeCompare(b.userId)),
+    byModel: [...byModel.values()].sort((a, b) =>
+      (a.modelId ?? '').localeCompare(b.modelId ?? '')
+    ),
+    byDay: [...byDay.values()].sort((a, b) => a.date.localeCompare(b.date)),
+  }
+}
+
+function emptyTotals(): TokenUsageTotalsDTO {
+  return { inputTokens: 0, outputTokens: 0, interactions: 0 }
+}
+
+function addUsage(target: TokenUsageTotalsDTO, record: TokenUsageRecord): void {
+  target.inputTokens += record.inputTokens
+  target.outputTokens += record.outputTokens
+  target.interactions += 1
+}
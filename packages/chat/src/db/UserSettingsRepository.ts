@@ -17,6 +17,11 @@ const DEFAULT_SETTINGS: AppSettingsDTO = {
   personalityPreset: 'friendly',
   customPersonalityPrompt: undefined,
   scheduledJobsRetentionDays: 30,
+  titleModelConfigId: undefined,
+  quietHoursEnabled: false,
+  quietHoursStart: '22:00',
+  quietHoursEnd: '07:00',
+  doNotDisturbUntil: undefined,
 }
 
 function setSetting<K extends keyof AppSettingsDTO>(
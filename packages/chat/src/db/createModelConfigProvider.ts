@@ -0,0 +1,74 @@
+import { AppError, ErrorCode } from '@stina/core'
+import type { ChatModelConfig, IModelConfigProvider } from '../orchestrator/types.js'
+import { ModelConfigRepository } from './ModelConfigRepository.js'
+import { UserSettingsRepository } from './UserSettingsRepository.js'
+import type { ChatDb } from './schema.js'
+
+/**
+ * Options for a user's model config provider
+ */
+export interface ModelConfigProviderOptions {
+  /**
+   * The model configs the user may use, e.g. by their capability policy.
+   * Null, or leaving it out, means every model config may be used.
+   */
+  getAllowedModelConfigIds?: () => Promise<string[] | null>
+}
+
+/**
+ * Create the model config provider for a user's chats, using the model configs
+ * and the user's default and title model in the chat database.
+ * Model configs the user may not use throw an AppError with CHAT_MODEL_NOT_ALLOWED,
+ * and users with allowed model configs never fall back to another provider.
+ * @param db - The chat database instance.
+ * @param userId - The user the chats belong to.
+ */
+export function createModelConfigProvider(
+  db: ChatDb,
+  userId: string,
+  options: ModelConfigProviderOptions = {}
+): IModelConfigProvider {
+  const modelConfigRepository = new ModelConfigRepository(db)
+  const userSettingsRepository = new UserSettingsRepository(db, userId)
+  const getAllowedModelConfigIds = async () => (await options.getAllowedModelConfigIds?.()) ?? null
+
+  const loadModelConfig = async (
+    modelConfigId: string | null | undefined
+  ): Promise<ChatModelConfig | null> => {
+    if (!modelConfigId) return null
+
+    const allowedModelConfigIds = await getAllowedModelConfigIds()
+    if (allowedModelConfigIds && !allowedModelConfigIds.includes(modelConfigId)) {
+      throw new AppError(
+        ErrorCode.CHAT_MODEL_NOT_ALLOWED,
+        'You are not allowed to use this model',
+        { modelConfigId }
+      )
+    }
+
+    const config = await modelConfigRepository.get(modelConfigId)
+    if (!config) return null
+
+    return {
+      providerId: config.providerId,
+      modelId: config.modelId,
+      settingsOverride: config.settingsOverride,
+      contextLength: config.contextLength,
+    }
+  }
+
+  return {
+    async get(modelConfigId) {
+      return loadModelConfig(modelConfigId)
+    },
+    async getDefault() {
+      return loadModelConfig(await userSettingsRepository.getDefaultModelConfigId())
+    },
+    async getTitleModel() {
+      return loadModelConfig(await userSettingsRepository.getValue('titleModelConfigId'))
+    },
+    async hasModelAllowlist() {
+      return (await getAllowedModelConfigIds()) !== null
+    },
+  }
+}
@@ -0,0 +1,24 @@
+import { QuietHoursService } from '../quietHours/QuietHoursService.js'
+import type { QuietHoursServiceOptions } from '../quietHours/types.js'
+import { DeferredInstructionRepository } from './DeferredInstructionRepository.js'
+import { UserSettingsRepository } from './UserSettingsRepository.js'
+import type { ChatDb } from './schema.js'
+
+/**
+ * Create the quiet hours service for proactive instructions (extensions and
+ * reminders), using the users' settings and deferred instructions in the chat database.
+ * @param db - The chat database instance.
+ * @param options - How instructions are delivered once quiet time is over.
+ */
+export function createQuietHoursService(
+  db: ChatDb,
+  options: Pick<QuietHoursServiceOptions, 'deliver' | 'logger'>
+): QuietHoursService {
+  return new QuietHoursService({
+    getSettings: (userId) => new UserSettingsRepository(db, userId).get(),
+    getRepository: (userId) => new DeferredInstructionRepository(db, userId),
+    listPendingUserIds: () => DeferredInstructionRepository.listUserIds(db),
+    getLanguage: (userId) => new UserSettingsRepository(db, userId).getValue('language'),
+    ...options,
+  })
+}
@@ -20,6 +20,21 @@ export { QuickCommandRepository } from './QuickCommandRepository.js'
 export type { QuickCommand, CreateQuickCommandInput, UpdateQuickCommandInput } from './QuickCommandRepository.js'
 export { ToolConfirmationRepository } from './ToolConfirmationRepository.js'
 export type { ToolConfirmationOverride } from './ToolConfirmationRepository.js'
+export { ExtensionFileRootRepository } from './ExtensionFileRootRepository.js'
+export type { ExtensionFileRoot } from './ExtensionFileRootRepository.js'
+export { TokenUsageRepository, aggregateTokenUsage } from './TokenUsageRepository.js'
+export type { TokenUsageRecord, TokenUsageFilter } from './TokenUsageRepository.js'
+export { ChatHistoryReader, toChatHistoryInteraction } from './ChatHistoryReader.js'
+export { MemoryRepository } from './MemoryRepository.js'
+export { TaskRepository } from './TaskRepository.js'
+export { ReminderRepository } from './ReminderRepository.js'
+export { DeferredInstructionRepository } from './DeferredInstructionRepository.js'
+export { createQuietHoursService } from './createQuietHoursService.js'
+export { createModelConfigProvider } from './createModelConfigProvider.js'
+export type { ModelConfigProviderOptions } from './createModelConfigProvider.js'
+
+// Attachments
+export { FileAttachmentStore } from './FileAttachmentStore.js'
 
 /**
  * Get migrations path for chat package
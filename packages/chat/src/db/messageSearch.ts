@@ -0,0 +1,56 @@
+import type { Message } from '../types/index.js'
+
+/** Marks the start of a match in FTS5 snippets, replaced after HTML escaping */
+export const SNIPPET_MATCH_START = '\u0002'
+/** Marks the end of a match in FTS5 snippets, replaced after HTML escaping */
+export const SNIPPET_MATCH_END = '\u0003'
+
+/**
+ * Message text that should be searchable
+ */
+export interface SearchableMessage {
+  role: 'user' | 'stina'
+  text: string
+}
+
+/**
+ * Pick the user and Stina messages with text from an interaction.
+ * Instructions, thinking and tool calls are not indexed.
+ */
+export function getSearchableMessages(messages: Message[]): SearchableMessage[] {
+  const searchable: SearchableMessage[] = []
+  for (const message of messages) {
+    if ((message.type === 'user' || message.type === 'stina') && message.text.trim()) {
+      searchable.push({ role: message.type, text: message.text })
+    }
+  }
+  return searchable
+}
+
+/**
+ * Turn free text into an FTS5 MATCH expression.
+ * Every word must match, as a prefix, so operators and quotes typed by the
+ * user can't produce syntax errors.
+ * @returns The expression, or null if the text contains no searchable words.
+ */
+export function toFtsQuery(text: string): string | null {
+  const words = text.match(/[\p{L}\p{N}_]+/gu)
+  if (!words || words.length === 0) return null
+  return words.map((word) => `"${word}"*`).join(' ')
+}
+
+/**
+ * HTML-escape an FTS5 snippet and wrap the matches in <mark> tags.
+ */
+export function formatSnippet(snippet: string): string {
+  return snippet
+    .replace(/&/g, '&amp;')
+    .replace(/</g, '&lt;')
+    .replace(/>/g, '&gt;')
+    .replace(/"/g, '&quot;')
+    .replace(/'/g, '&#39;')
+    .split(SNIPPET_MATCH_START)
+    .join('<mark>')
+    .split(SNIPPET_MATCH_END)
+    .join('</mark>')
+}
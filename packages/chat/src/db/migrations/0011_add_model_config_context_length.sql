@@ -0,0 +1 @@
+ALTER TABLE model_configs ADD COLUMN context_length INTEGER;
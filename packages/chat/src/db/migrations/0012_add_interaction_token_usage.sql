@@ -0,0 +1,6 @@
+-- Token usage reported by providers, stored in columns for aggregation
+ALTER TABLE chat_interactions ADD COLUMN provider_id TEXT;
+ALTER TABLE chat_interactions ADD COLUMN model_id TEXT;
+ALTER TABLE chat_interactions ADD COLUMN input_tokens INTEGER;
+ALTER TABLE chat_interactions ADD COLUMN output_tokens INTEGER;
+CREATE INDEX IF NOT EXISTS idx_interactions_created ON chat_interactions(created_at);
@@ -0,0 +1,12 @@
+-- Extension file roots
+-- Directories a user has approved for an extension's sandboxed file access
+CREATE TABLE IF NOT EXISTS extension_file_roots (
+  user_id         TEXT NOT NULL,
+  extension_id    TEXT NOT NULL,
+  path            TEXT NOT NULL,
+  access          TEXT NOT NULL,  -- 'read' or 'readwrite'
+  created_at      INTEGER NOT NULL,
+  PRIMARY KEY (user_id, extension_id, path)
+);
+CREATE INDEX IF NOT EXISTS idx_file_roots_user_ext
+  ON extension_file_roots(user_id, extension_id);
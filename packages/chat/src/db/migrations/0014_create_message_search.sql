@@ -0,0 +1,27 @@
+-- Full-text search over user and Stina message text
+-- Rows are written by ConversationRepository.saveInteraction
+CREATE VIRTUAL TABLE IF NOT EXISTS chat_message_search USING fts5(
+  text,
+  interaction_id UNINDEXED,
+  conversation_id UNINDEXED,
+  role UNINDEXED,  -- 'user' or 'stina'
+  tokenize = 'unicode61 remove_diacritics 2'
+);
+
+-- Drop indexed text when interactions are deleted (including conversation cascades)
+CREATE TRIGGER IF NOT EXISTS chat_message_search_delete
+AFTER DELETE ON chat_interactions
+BEGIN
+  DELETE FROM chat_message_search WHERE interaction_id = OLD.id;
+END;
+
+-- Index existing messages
+INSERT INTO chat_message_search (text, interaction_id, conversation_id, role)
+SELECT
+  json_extract(message.value, '$.text'),
+  chat_interactions.id,
+  chat_interactions.conversation_id,
+  json_extract(message.value, '$.type')
+FROM chat_interactions, json_each(chat_interactions.messages) AS message
+WHERE json_extract(message.value, '$.type') IN ('user', 'stina')
+  AND trim(coalesce(json_extract(message.value, '$.text'), '')) != '';
@@ -0,0 +1,12 @@
+-- Long-term memories
+-- Facts Stina has been asked to remember, with an optional embedding vector
+CREATE TABLE IF NOT EXISTS memories (
+  id              TEXT PRIMARY KEY,
+  user_id         TEXT NOT NULL,
+  content         TEXT NOT NULL,
+  embedding       BLOB,           -- Float32 vector, NULL when no embedding model was available
+  embedding_model TEXT,           -- '<providerId>:<modelId>' that produced the vector
+  created_at      INTEGER NOT NULL,
+  updated_at      INTEGER NOT NULL
+);
+CREATE INDEX IF NOT EXISTS idx_memories_user ON memories(user_id);
@@ -0,0 +1,27 @@
+-- Tasks
+-- To-do items managed by the user through Stina
+CREATE TABLE IF NOT EXISTS tasks (
+  id              TEXT PRIMARY KEY,
+  user_id         TEXT NOT NULL,
+  title           TEXT NOT NULL,
+  notes           TEXT,
+  due_at          INTEGER,
+  completed_at    INTEGER,
+  created_at      INTEGER NOT NULL,
+  updated_at      INTEGER NOT NULL
+);
+CREATE INDEX IF NOT EXISTS idx_tasks_user ON tasks(user_id);
+
+-- Reminders
+-- Scheduled through the scheduler; when due the text is added to the user's active conversation
+CREATE TABLE IF NOT EXISTS reminders (
+  id              TEXT PRIMARY KEY,
+  user_id         TEXT NOT NULL,
+  text            TEXT NOT NULL,
+  remind_at       INTEGER NOT NULL,
+  fired_at        INTEGER,
+  completed_at    INTEGER,
+  created_at      INTEGER NOT NULL,
+  updated_at      INTEGER NOT NULL
+);
+CREATE INDEX IF NOT EXISTS idx_reminders_user ON reminders(user_id);
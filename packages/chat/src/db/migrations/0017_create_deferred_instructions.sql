@@ -0,0 +1,10 @@
+-- Deferred instructions
+-- Proactive instructions held back during the user's quiet hours, delivered as a digest afterwards
+CREATE TABLE IF NOT EXISTS deferred_instructions (
+  id               TEXT PRIMARY KEY,
+  user_id          TEXT NOT NULL,
+  conversation_id  TEXT,
+  text             TEXT NOT NULL,
+  created_at       INTEGER NOT NULL
+);
+CREATE INDEX IF NOT EXISTS idx_deferred_instructions_user ON deferred_instructions(user_id);
@@ -0,0 +1,18 @@
+-- Conversation branching: each interaction follows a parent interaction,
+-- and editing or regenerating a message starts a sibling branch
+ALTER TABLE chat_interactions ADD COLUMN parent_id TEXT;
+-- Last interaction of the branch shown in the conversation (NULL = newest interaction)
+ALTER TABLE chat_conversations ADD COLUMN active_interaction_id TEXT;
+CREATE INDEX IF NOT EXISTS idx_interactions_parent ON chat_interactions(conversation_id, parent_id);
+
+-- Existing conversations are a single branch: link each interaction to the one
+-- saved before it (rowid breaks ties between interactions saved the same second)
+UPDATE chat_interactions
+SET parent_id = (
+  SELECT previous.id
+  FROM chat_interactions AS previous
+  WHERE previous.conversation_id = chat_interactions.conversation_id
+    AND (previous.created_at, previous.rowid) < (chat_interactions.created_at, chat_interactions.rowid)
+  ORDER BY previous.created_at DESC, previous.rowid DESC
+  LIMIT 1
+);
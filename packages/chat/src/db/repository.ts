@@ -2,7 +2,50 @@ import { conversations, interactions } from './schema.js'
 import type { ChatDb } from './schema.js'
 import type { Conversation, Interaction } from '../types/index.js'
 import type { IConversationRepository } from '../orchestrator/IConversationRepository.js'
-import { eq, desc, and, isNull, count, inArray } from 'drizzle-orm'
+import { getBranchPath, getSiblingIds } from '../orchestrator/branches.js'
+import type { InteractionNode } from '../orchestrator/branches.js'
+import type { ChatSearchHitDTO } from '@stina/shared'
+import { eq, desc, and, isNull, inArray, sql } from 'drizzle-orm'
+import {
+  SNIPPET_MATCH_END,
+  SNIPPET_MATCH_START,
+  formatSnippet,
+  getSearchableMessages,
+  toFtsQuery,
+} from './messageSearch.js'
+
+const DEFAULT_SEARCH_LIMIT = 20
+const MAX_SEARCH_LIMIT = 100
+
+type InteractionRow = typeof interactions.$inferSelect
+
+/**
+ * Map an interaction row to the domain type.
+ * Loaded interactions are always completed.
+ * @param nodes - The conversation tree, used to list alternative branches.
+ */
+function toInteraction(row: InteractionRow, nodes: InteractionNode[]): Interaction {
+  const siblingIds = getSiblingIds(nodes, row.id)
+  return {
+    id: row.id,
+    conversationId: row.conversationId,
+    messages: row.messages,
+    informationMessages: row.informationMessages ?? [],
+    completed: true,
+    aborted: row.aborted,
+    error: row.error,
+    errorMessage: row.errorMessage ?? undefined,
+    metadata: {
+      createdAt: row.createdAt.toISOString(),
+      ...(typeof row.metadata === 'object' && row.metadata
+        ? (row.metadata as Record<string, unknown>)
+        : {}),
+    },
+    readAt: row.readAt ? row.readAt.toISOString() : undefined,
+    parentId: row.parentId,
+    siblingIds: siblingIds.length > 1 ? siblingIds : undefined,
+  }
+}
 
 /**
  * Database repository for chat data.
@@ -41,9 +84,16 @@ export class ConversationRepository implements IConversationRepository {
   }
 
   /**
-   * Save an interaction to database
+   * Save an interaction to database.
+   * Without a parentId, the interaction continues the conversation's active branch.
+   * The saved interaction becomes the end of the active branch.
    */
   async saveInteraction(interaction: Interaction): Promise<void> {
+    const parentId =
+      interaction.parentId !== undefined
+        ? interaction.parentId
+        : ((await this.getActiveBranch(interaction.conversationId)).path[0] ?? null)
+
     await this.db.insert(interactions).values({
       id: interaction.id,
       conversationId: interaction.conversationId,
@@ -55,14 +105,154 @@ export class ConversationRepository implements IConversationRepository {
       informationMessages: interaction.informationMessages,
       metadata: interaction.metadata,
       readAt: null,
+      providerId: interaction.metadata.providerId ?? null,
+      modelId: interaction.metadata.modelId ?? null,
+      inputTokens: interaction.metadata.usage?.inputTokens ?? null,
+      outputTokens: interaction.metadata.usage?.outputTokens ?? null,
+      parentId,
     })
+
+    await this.db
+      .update(conversations)
+      .set({ activeInteractionId: interaction.id })
+      .where(eq(conversations.id, interaction.conversationId))
+
+    await this.indexInteraction(interaction)
+  }
+
+  /**
+   * Get the position of every interaction in a conversation, oldest first.
+   * Interactions saved within the same second keep their insertion order.
+   */
+  async getInteractionTree(conversationId: string): Promise<InteractionNode[]> {
+    return this.db
+      .select({ id: interactions.id, parentId: interactions.parentId })
+      .from(interactions)
+      .where(eq(interactions.conversationId, conversationId))
+      .orderBy(interactions.createdAt, sql`rowid`)
   }
 
   /**
-   * Get a conversation with all its interactions.
+   * Get the conversation tree and the interactions of its active branch, newest first.
+   */
+  private async getActiveBranch(
+    conversationId: string
+  ): Promise<{ nodes: InteractionNode[]; path: string[] }> {
+    const [nodes, conv] = await Promise.all([
+      this.getInteractionTree(conversationId),
+      this.db
+        .select({ activeInteractionId: conversations.activeInteractionId })
+        .from(conversations)
+        .where(eq(conversations.id, conversationId))
+        .limit(1),
+    ])
+    return { nodes, path: getBranchPath(nodes, conv[0]?.activeInteractionId) }
+  }
+
+  /**
+   * Show the branch ending at the given interaction.
+   * Only updates if the conversation belongs to the current user.
+   */
+  async setActiveInteraction(conversationId: string, interactionId: string): Promise<void> {
+    await this.db
+      .update(conversations)
+      .set({ activeInteractionId: interactionId })
+      .where(and(eq(conversations.id, conversationId), this.getUserFilter()))
+  }
+
+  /**
+   * Add an interaction's user and Stina messages to the full-text index.
+   * Index rows are removed by a trigger when the interaction is deleted.
+   */
+  private async indexInteraction(interaction: Interaction): Promise<void> {
+    const messages = getSearchableMessages(interaction.messages)
+    if (messages.length === 0) return
+
+    const rows = messages.map(
+      (message) =>
+        sql`(${message.text}, ${interaction.id}, ${interaction.conversationId}, ${message.role})`
+    )
+    await this.db.run(sql`
+      INSERT INTO chat_message_search (text, interaction_id, conversation_id, role)
+      VALUES ${sql.join(rows, sql`, `)}
+    `)
+  }
+
+  /**
+   * Search the user's messages, best match first.
+   * Archived conversations are included.
+   * @param query - Free text; every word must match as a prefix.
+   * @param options - Page size (default 20, max 100) and offset.
+   */
+  async searchMessages(
+    query: string,
+    options: { limit?: number; offset?: number } = {}
+  ): Promise<ChatSearchHitDTO[]> {
+    const match = toFtsQuery(query)
+    if (!match) return []
+
+    const limit = Math.min(Math.max(options.limit ?? DEFAULT_SEARCH_LIMIT, 1), MAX_SEARCH_LIMIT)
+    const offset = Math.max(options.offset ?? 0, 0)
+
+    const rows = await this.db.all<{
+      interactionId: string
+      conversationId: string
+      role: 'user' | 'stina'
+      snippet: string
+      title: string | null
+      active: number
+      createdAt: number
+    }>(sql`
+      SELECT
+        chat_message_search.interaction_id AS interactionId,
+        chat_message_search.conversation_id AS conversationId,
+        chat_message_search.role AS role,
+        snippet(chat_message_search, 0, ${SNIPPET_MATCH_START}, ${SNIPPET_MATCH_END}, '…', 16) AS snippet,
+        ${conversations.title} AS title,
+        ${conversations.active} AS active,
+        ${interactions.createdAt} AS createdAt
+      FROM chat_message_search
+      JOIN ${interactions} ON ${interactions.id} = chat_message_search.interaction_id
+      JOIN ${conversations} ON ${conversations.id} = chat_message_search.conversation_id
+      WHERE chat_message_search MATCH ${match} AND ${conversations.userId} = ${this.userId}
+      ORDER BY rank
+      LIMIT ${limit} OFFSET ${offset}
+    `)
+
+    return rows.map((row) => ({
+      conversationId: row.conversationId,
+      conversationTitle: row.title ?? undefined,
+      conversationActive: Boolean(row.active),
+      interactionId: row.interactionId,
+      role: row.role,
+      snippet: formatSnippet(row.snippet),
+      // Timestamps are stored in seconds
+      createdAt: new Date(row.createdAt * 1000).toISOString(),
+    }))
+  }
+
+  /**
+   * Get a conversation with the interactions of its active branch, newest first.
    * Only returns the conversation if it belongs to the current user.
    */
   async getConversation(id: string): Promise<Conversation | null> {
+    return this.loadConversation(id, false)
+  }
+
+  /**
+   * Get a conversation with the interactions of all its branches, oldest first.
+   * activeInteractionId marks the branch the conversation shows.
+   * Only returns the conversation if it belongs to the current user.
+   */
+  async getConversationWithBranches(id: string): Promise<Conversation | null> {
+    return this.loadConversation(id, true)
+  }
+
+  /**
+   * Load a conversation with the interactions of its active branch, newest first,
+   * or of all its branches, oldest first.
+   */
+  private async loadConversation(id: string, allBranches: boolean): Promise<Conversation | null> {
     const convResults = await this.db
       .select()
       .from(conversations)
@@ -72,40 +262,30 @@ export class ConversationRepository implements IConversationRepository {
     const conv = convResults[0]
     if (!conv) return null
 
-    const inters = await this.db
+    const { nodes, path } = await this.getActiveBranch(id)
+    const rows = await this.db
       .select()
       .from(interactions)
       .where(eq(interactions.conversationId, id))
-      .orderBy(desc(interactions.createdAt))
+    const rowsById = new Map(rows.map((row) => [row.id, row]))
+    const interactionIds = allBranches ? nodes.map((node) => node.id) : path
 
     return {
       id: conv.id,
       title: conv.title ?? undefined,
       active: conv.active,
       userId: conv.userId ?? undefined,
-      interactions: inters.map((i) => ({
-        id: i.id,
-        conversationId: i.conversationId,
-        messages: i.messages,
-        informationMessages: i.informationMessages ?? [],
-        completed: true, // Loaded interactions are always completed
-        aborted: i.aborted,
-        error: i.error,
-        errorMessage: i.errorMessage ?? undefined,
-        metadata: {
-          createdAt: i.createdAt.toISOString(),
-          ...(typeof i.metadata === 'object' && i.metadata
-            ? (i.metadata as Record<string, unknown>)
-            : {}),
-        },
-        readAt: i.readAt ? i.readAt.toISOString() : undefined,
-      })),
+      interactions: interactionIds.flatMap((interactionId) => {
+        const row = rowsById.get(interactionId)
+        return row ? [toInteraction(row, nodes)] : []
+      }),
       metadata: {
         createdAt: conv.createdAt.toISOString(),
         ...(typeof conv.metadata === 'object' && conv.metadata
           ? (conv.metadata as Record<string, unknown>)
           : {}),
       },
+      activeInteractionId: allBranches ? path[0] : undefined,
     }
   }
 
@@ -229,51 +409,56 @@ export class ConversationRepository implements IConversationRepository {
   }
 
   /**
-   * Get interactions for a conversation with pagination
-   * Returns interactions ordered by createdAt DESC (newest first)
+   * Get interactions of the conversation's active branch with pagination
+   * Returns interactions newest first
    */
   async getConversationInteractions(
     conversationId: string,
     limit: number,
     offset: number
   ): Promise<Interaction[]> {
-    const inters = await this.db
-      .select()
-      .from(interactions)
-      .where(eq(interactions.conversationId, conversationId))
-      .orderBy(desc(interactions.createdAt))
-      .limit(limit)
-      .offset(offset)
-
-    return inters.map((i) => ({
-      id: i.id,
-      conversationId: i.conversationId,
-      messages: i.messages,
-      informationMessages: i.informationMessages ?? [],
-      completed: true, // Loaded interactions are always completed
-      aborted: i.aborted,
-      error: i.error,
-      errorMessage: i.errorMessage ?? undefined,
-      metadata: {
-        createdAt: i.createdAt.toISOString(),
-        ...(typeof i.metadata === 'object' && i.metadata
-          ? (i.metadata as Record<string, unknown>)
-          : {}),
-      },
-      readAt: i.readAt ? i.readAt.toISOString() : undefined,
-    }))
+    const { nodes, path } = await this.getActiveBranch(conversationId)
+    const ids = path.slice(offset, offset + limit)
+    if (ids.length === 0) return []
+
+    const rows = await this.db.select().from(interactions).where(inArray(interactions.id, ids))
+    const rowsById = new Map(rows.map((row) => [row.id, row]))
+
+    return ids.flatMap((id) => {
+      const row = rowsById.get(id)
+      return row ? [toInteraction(row, nodes)] : []
+    })
   }
 
   /**
-   * Count total interactions for a conversation
+   * Count interactions in the conversation's active branch
    */
   async countConversationInteractions(conversationId: string): Promise<number> {
-    const result = await this.db
-      .select({ count: count() })
+    const { path } = await this.getActiveBranch(conversationId)
+    return path.length
+  }
+
+  /**
+   * Get an interaction of the conversation, on any branch.
+   * Only returns the interaction if the conversation belongs to the current user.
+   */
+  async getInteraction(conversationId: string, interactionId: string): Promise<Interaction | null> {
+    const results = await this.db
+      .select({ interaction: interactions })
       .from(interactions)
-      .where(eq(interactions.conversationId, conversationId))
+      .innerJoin(conversations, eq(interactions.conversationId, conversations.id))
+      .where(
+        and(
+          eq(interactions.id, interactionId),
+          eq(interactions.conversationId, conversationId),
+          this.getUserFilter()
+        )
+      )
+      .limit(1)
 
-    return result[0]?.count ?? 0
+    const row = results[0]?.interaction
+    if (!row) return null
+    return toInteraction(row, await this.getInteractionTree(conversationId))
   }
 
   /**
@@ -1,4 +1,4 @@
-import { index, integer, primaryKey, sqliteTable, text } from 'drizzle-orm/sqlite-core'
+import { blob, index, integer, primaryKey, sqliteTable, text } from 'drizzle-orm/sqlite-core'
 import type { BetterSQLite3Database } from 'drizzle-orm/better-sqlite3'
 import type { Message, InformationMessage } from '../types/message.js'
 
@@ -16,6 +16,8 @@ export const conversations = sqliteTable(
     metadata: text('metadata', { mode: 'json' }),
     /** User ID for multi-user support (required) */
     userId: text('user_id').notNull(),
+    /** Last interaction of the branch shown in the conversation (null = newest interaction) */
+    activeInteractionId: text('active_interaction_id'),
   },
   (table) => ({
     activeIdx: index('idx_conversations_active').on(table.active, table.createdAt),
@@ -51,12 +53,24 @@ export const interactions = sqliteTable(
     metadata: text('metadata', { mode: 'json' }),
     /** Timestamp when the interaction was read by the user (null = unread) */
     readAt: integer('read_at', { mode: 'timestamp' }),
+    /** Provider that generated the response */
+    providerId: text('provider_id'),
+    /** Model that generated the response */
+    modelId: text('model_id'),
+    /** Prompt tokens reported by the provider */
+    inputTokens: integer('input_tokens'),
+    /** Completion tokens reported by the provider */
+    outputTokens: integer('output_tokens'),
+    /** Interaction this one follows (null = first interaction of a branch) */
+    parentId: text('parent_id'),
   },
   (table) => ({
     conversationIdx: index('idx_interactions_conversation').on(
       table.conversationId,
       table.createdAt
     ),
+    createdIdx: index('idx_interactions_created').on(table.createdAt),
+    parentIdx: index('idx_interactions_parent').on(table.conversationId, table.parentId),
   })
 )
 
@@ -79,6 +93,8 @@ export const modelConfigs = sqliteTable(
     modelId: text('model_id').notNull(),
     /** Provider-specific settings overrides stored as JSON */
     settingsOverride: text('settings_override', { mode: 'json' }).$type<Record<string, unknown>>(),
+    /** Context window size in tokens, if known */
+    contextLength: integer('context_length'),
     createdAt: integer('created_at', { mode: 'timestamp' }).notNull(),
     updatedAt: integer('updated_at', { mode: 'timestamp' }).notNull(),
   },
@@ -151,6 +167,110 @@ export const toolConfirmationOverrides = sqliteTable(
   })
 )
 
+/**
+ * Extension file roots table
+ * Directories a user has approved for an extension's sandboxed file access
+ */
+export const extensionFileRoots = sqliteTable(
+  'extension_file_roots',
+  {
+    userId: text('user_id').notNull(),
+    extensionId: text('extension_id').notNull(),
+    path: text('path').notNull(),
+    /** 'read' or 'readwrite' */
+    access: text('access').notNull().$type<'read' | 'readwrite'>(),
+    createdAt: integer('created_at', { mode: 'timestamp' }).notNull(),
+  },
+  (table) => ({
+    pk: primaryKey({ columns: [table.userId, table.extensionId, table.path] }),
+    userExtIdx: index('idx_file_roots_user_ext').on(table.userId, table.extensionId),
+  })
+)
+
+/**
+ * Memories table
+ * Long-term facts about the user, with an embedding for semantic recall
+ */
+export const memories = sqliteTable(
+  'memories',
+  {
+    id: text('id').primaryKey(),
+    userId: text('user_id').notNull(),
+    content: text('content').notNull(),
+    /** Float32 vector, null when no embedding model was available */
+    embedding: blob('embedding', { mode: 'buffer' }),
+    /** '<providerId>:<modelId>' that produced the embedding */
+    embeddingModel: text('embedding_model'),
+    createdAt: integer('created_at', { mode: 'timestamp' }).notNull(),
+    updatedAt: integer('updated_at', { mode: 'timestamp' }).notNull(),
+  },
+  (table) => ({
+    userIdx: index('idx_memories_user').on(table.userId),
+  })
+)
+
+/**
+ * Tasks table
+ * To-do items managed by the user through Stina
+ */
+export const tasks = sqliteTable(
+  'tasks',
+  {
+    id: text('id').primaryKey(),
+    userId: text('user_id').notNull(),
+    title: text('title').notNull(),
+    notes: text('notes'),
+    dueAt: integer('due_at', { mode: 'timestamp' }),
+    completedAt: integer('completed_at', { mode: 'timestamp' }),
+    createdAt: integer('created_at', { mode: 'timestamp' }).notNull(),
+    updatedAt: integer('updated_at', { mode: 'timestamp' }).notNull(),
+  },
+  (table) => ({
+    userIdx: index('idx_tasks_user').on(table.userId),
+  })
+)
+
+/**
+ * Reminders table
+ * Each pending reminder has a matching scheduler job
+ */
+export const reminders = sqliteTable(
+  'reminders',
+  {
+    id: text('id').primaryKey(),
+    userId: text('user_id').notNull(),
+    text: text('text').notNull(),
+    remindAt: integer('remind_at', { mode: 'timestamp' }).notNull(),
+    /** When the reminder was last delivered to the user */
+    firedAt: integer('fired_at', { mode: 'timestamp' }),
+    completedAt: integer('completed_at', { mode: 'timestamp' }),
+    createdAt: integer('created_at', { mode: 'timestamp' }).notNull(),
+    updatedAt: integer('updated_at', { mode: 'timestamp' }).notNull(),
+  },
+  (table) => ({
+    userIdx: index('idx_reminders_user').on(table.userId),
+  })
+)
+
+/**
+ * Deferred instructions table
+ * Proactive instructions held back during quiet hours
+ */
+export const deferredInstructions = sqliteTable(
+  'deferred_instructions',
+  {
+    id: text('id').primaryKey(),
+    userId: text('user_id').notNull(),
+    /** Conversation the instruction was meant for, null for the active conversation */
+    conversationId: text('conversation_id'),
+    text: text('text').notNull(),
+    createdAt: integer('created_at', { mode: 'timestamp' }).notNull(),
+  },
+  (table) => ({
+    userIdx: index('idx_deferred_instructions_user').on(table.userId),
+  })
+)
+
 /**
  * Schema export for Drizzle
  */
@@ -161,6 +281,11 @@ export const chatSchema = {
   userSettings,
   quickCommands,
   toolConfirmationOverrides,
+  extensionFileRoots,
+  memories,
+  tasks,
+  reminders,
+  deferredInstructions,
 }
 
 // eslint-disable-next-line @typescript-eslint/no-explicit-any -- chat DB is initialized in adapters-node with a different schema object.
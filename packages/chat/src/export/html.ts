@@ -0,0 +1,137 @@
+import type { Conversation } from '../types/conversation.js'
+import type { ToolCall } from '../types/message.js'
+import type { ExportEntry, ReadableExportOptions } from './utils.js'
+import {
+  describeConfirmation,
+  formatTimestamp,
+  getActiveBranchInteractions,
+  getExportEntries,
+  prettyJson,
+} from './utils.js'
+
+const STYLES = `
+  body { font-family: system-ui, -apple-system, sans-serif; max-width: 48rem; margin: 2rem auto; padding: 0 1rem; color: #1f2328; background: #fff; line-height: 1.5; }
+  header { border-bottom: 1px solid #d0d7de; margin-bottom: 1.5rem; }
+  h1 { margin-bottom: 0.25rem; }
+  time, .muted { color: #656d76; font-size: 0.875rem; }
+  section.interaction { margin: 1.5rem 0; }
+  .message { white-space: pre-wrap; padding: 0.75rem 1rem; border-radius: 0.75rem; margin: 0.5rem 0; }
+  .user { background: #ddf4ff; margin-left: 20%; }
+  .stina { background: #f6f8fa; margin-right: 20%; }
+  .instruction, .summary, .information { border-left: 3px solid #d0d7de; color: #656d76; }
+  .error { border-left: 3px solid #cf222e; color: #cf222e; }
+  .role { display: block; font-weight: 600; font-size: 0.8125rem; margin-bottom: 0.25rem; white-space: normal; }
+  details { margin: 0.5rem 0; border: 1px solid #d0d7de; border-radius: 0.5rem; padding: 0.5rem 0.75rem; }
+  summary { cursor: pointer; font-weight: 600; }
+  pre { background: #f6f8fa; padding: 0.5rem; border-radius: 0.375rem; overflow-x: auto; white-space: pre-wrap; }
+  .approved { color: #1a7f37; }
+  .denied { color: #cf222e; }
+  @media (prefers-color-scheme: dark) {
+    body { color: #e6edf3; background: #0d1117; }
+    .user { background: #0c2d6b; }
+    .stina, pre { background: #161b22; }
+    header, details { border-color: #30363d; }
+  }
+`
+
+/**
+ * Export a conversation as a standalone HTML document.
+ * All text is escaped; the document has no scripts or external resources.
+ */
+export function exportConversationToHtml(
+  conversation: Conversation,
+  options: ReadableExportOptions = {}
+): string {
+  const title = conversation.title?.trim() || 'Conversation'
+  const sections: string[] = []
+
+  for (const interaction of getActiveBranchInteractions(conversation)) {
+    const entries = getExportEntries(interaction, options)
+    if (entries.length === 0) continue
+
+    const createdAt = interaction.metadata.createdAt
+    sections.push(
+      [
+        '<section class="interaction">',
+        `<time datetime="${escapeHtml(createdAt)}">${escapeHtml(formatTimestamp(createdAt, options.timeZone))}</time>`,
+        ...entries.map(renderEntry),
+        '</section>',
+      ].join('\n')
+    )
+  }
+
+  const startedAt = conversation.metadata.createdAt
+  return `<!DOCTYPE html>
+<html>
+<head>
+<meta charset="utf-8">
+<meta name="viewport" content="width=device-width, initial-scale=1">
+<title>${escapeHtml(title)}</title>
+<style>${STYLES}</style>
+</head>
+<body>
+<header>
+<h1>${escapeHtml(title)}</h1>
+<p class="muted">Started <time datetime="${escapeHtml(startedAt)}">${escapeHtml(formatTimestamp(startedAt, options.timeZone))}</time></p>
+</header>
+<main>
+${sections.join('\n')}
+</main>
+</body>
+</html>
+`
+}
+
+function renderEntry(entry: ExportEntry): string {
+  switch (entry.kind) {
+    case 'user':
+      return message('user', 'You', entry.text)
+    case 'stina':
+      return message('stina', 'Stina', entry.text)
+    case 'instruction':
+      return message('instruction', 'Instruction', entry.text)
+    case 'information':
+      return message('information', 'Information', entry.text)
+    case 'summary':
+      return message('summary', 'Summary of earlier messages', entry.text)
+    case 'thinking':
+      return `<details class="thinking"><summary>Thinking</summary><pre>${escapeHtml(entry.text)}</pre></details>`
+    case 'tool':
+      return renderTool(entry.tool)
+    case 'error':
+      return message('error', 'Error', entry.text)
+    case 'aborted':
+      return '<p class="muted">Response stopped</p>'
+  }
+}
+
+function message(className: string, label: string, text: string): string {
+  return `<div class="message ${className}"><span class="role">${label}</span>${escapeHtml(text)}</div>`
+}
+
+function renderTool(tool: ToolCall): string {
+  const confirmation = describeConfirmation(tool)
+  const status = confirmation
+    ? ` <span class="${tool.confirmationStatus === 'approved' ? 'approved' : 'denied'}">(${escapeHtml(confirmation)})</span>`
+    : ''
+
+  return [
+    '<details class="tool">',
+    `<summary>Tool: ${escapeHtml(tool.displayName ?? tool.name)}${status}</summary>`,
+    tool.confirmationPrompt ? `<p class="muted">${escapeHtml(tool.confirmationPrompt)}</p>` : '',
+    `<p>Input</p><pre>${escapeHtml(prettyJson(tool.payload))}</pre>`,
+    tool.result ? `<p>Result</p><pre>${escapeHtml(prettyJson(tool.result))}</pre>` : '',
+    '</details>',
+  ]
+    .filter(Boolean)
+    .join('\n')
+}
+
+function escapeHtml(text: string): string {
+  return text
+    .replace(/&/g, '&amp;')
+    .replace(/</g, '&lt;')
+    .replace(/>/g, '&gt;')
+    .replace(/"/g, '&quot;')
+    .replace(/'/g, '&#39;')
+}
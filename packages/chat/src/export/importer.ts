@@ -0,0 +1,123 @@
+import { nanoid } from 'nanoid'
+import type { IConversationRepository } from '../orchestrator/IConversationRepository.js'
+import { CONTEXT_COMPACTION_METADATA_KEY, isContextSummaryMessage } from '../orchestrator/contextBudget.js'
+import type { Conversation } from '../types/conversation.js'
+import type { Interaction } from '../types/interaction.js'
+import type { Message } from '../types/message.js'
+import { parseConversationExport } from './json.js'
+import { getActiveBranchInteractions } from './utils.js'
+
+export interface ImportConversationOptions {
+  /** ID generator, for tests. Defaults to nanoid */
+  generateId?: () => string
+}
+
+/**
+ * Import a JSON conversation export into a repository.
+ *
+ * The conversation and its interactions get new IDs so the same export can be
+ * imported several times, or by another user on the same instance. References
+ * between interactions (branches and context summaries) are updated to the new IDs.
+ * Version 1 exports have no branches, so their interactions are chained in order.
+ * Token usage is left out, since the tokens were not spent by the importing user.
+ * @param repository - Repository scoped to the user who will own the conversation
+ * @param data - Parsed JSON of the export
+ * @returns The imported conversation with its active branch, interactions oldest first
+ * @throws If the data is not a supported conversation export
+ */
+export async function importConversation(
+  repository: Pick<
+    IConversationRepository,
+    'saveConversation' | 'saveInteraction' | 'setActiveInteraction'
+  >,
+  data: unknown,
+  options: ImportConversationOptions = {}
+): Promise<Conversation> {
+  const exported = parseConversationExport(data).conversation
+  const generateId = options.generateId ?? nanoid
+
+  const conversationId = generateId()
+  const interactionIds = new Map<string, string>()
+  for (const interaction of exported.interactions) {
+    interactionIds.set(interaction.id, generateId())
+  }
+  const remapId = (id: unknown): string | undefined =>
+    typeof id === 'string' ? interactionIds.get(id) : undefined
+  const remapIds = (ids: unknown): unknown =>
+    Array.isArray(ids)
+      ? ids.flatMap((id) => {
+          const newId = remapId(id)
+          return newId ? [newId] : []
+        })
+      : ids
+
+  const conversation: Conversation = {
+    id: conversationId,
+    title: exported.title,
+    active: exported.active,
+    interactions: [],
+    metadata: remapCompactionState(exported.metadata, remapId, remapIds),
+  }
+
+  const interactions: Interaction[] = []
+  for (const interaction of exported.interactions) {
+    const { usage: _usage, ...metadata } = interaction.metadata
+    const previous = interactions[interactions.length - 1]
+    interactions.push({
+      ...interaction,
+      id: interactionIds.get(interaction.id)!,
+      conversationId,
+      messages: interaction.messages.map((message) => remapSummaryMessage(message, remapIds)),
+      metadata,
+      parentId:
+        interaction.parentId === undefined
+          ? (previous?.id ?? null)
+          : interaction.parentId
+            ? (interactionIds.get(interaction.parentId) ?? null)
+            : null,
+    })
+  }
+  const activeInteractionId =
+    (exported.activeInteractionId && interactionIds.get(exported.activeInteractionId)) ||
+    interactions[interactions.length - 1]?.id
+
+  await repository.saveConversation(conversation)
+  for (const interaction of interactions) {
+    await repository.saveInteraction(interaction)
+  }
+  if (activeInteractionId) {
+    await repository.setActiveInteraction(conversationId, activeInteractionId)
+  }
+
+  const imported = { ...conversation, interactions, activeInteractionId }
+  return { ...conversation, interactions: getActiveBranchInteractions(imported) }
+}
+
+function remapSummaryMessage(message: Message, remapIds: (ids: unknown) => unknown): Message {
+  if (!isContextSummaryMessage(message)) return message
+  return {
+    ...message,
+    metadata: {
+      ...message.metadata,
+      summarizedInteractionIds: remapIds(message.metadata['summarizedInteractionIds']),
+    },
+  }
+}
+
+function remapCompactionState(
+  metadata: Conversation['metadata'],
+  remapId: (id: unknown) => string | undefined,
+  remapIds: (ids: unknown) => unknown
+): Conversation['metadata'] {
+  const state = metadata[CONTEXT_COMPACTION_METADATA_KEY]
+  if (typeof state !== 'object' || state === null) return metadata
+  const stored = state as Record<string, unknown>
+  return {
+    ...metadata,
+    [CONTEXT_COMPACTION_METADATA_KEY]: {
+      ...stored,
+      pausedAfterInteractionId: remapId(stored['pausedAfterInteractionId']),
+      revertedSummaryIds: remapIds(stored['revertedSummaryIds']),
+    },
+  }
+}
@@ -0,0 +1,77 @@
+import type { ConversationExportFormat } from '@stina/shared'
+import type { Conversation } from '../types/conversation.js'
+import { exportConversationToHtml } from './html.js'
+import { exportConversationToJson } from './json.js'
+import { exportConversationToMarkdown } from './markdown.js'
+import type { ReadableExportOptions } from './utils.js'
+
+export {
+  CONVERSATION_EXPORT_FORMAT,
+  CONVERSATION_EXPORT_VERSION,
+  exportConversationToJson,
+  parseConversationExport,
+  type ConversationExport,
+  type ExportedInteraction,
+} from './json.js'
+export { exportConversationToMarkdown } from './markdown.js'
+export { exportConversationToHtml } from './html.js'
+export { importConversation, type ImportConversationOptions } from './importer.js'
+export type { ReadableExportOptions } from './utils.js'
+
+/**
+ * Exported file content
+ */
+export interface ConversationExportFile {
+  content: string
+  mimeType: string
+  /** Suggested file name */
+  fileName: string
+}
+
+const FILE_TYPES: Record<ConversationExportFormat, { mimeType: string; extension: string }> = {
+  markdown: { mimeType: 'text/markdown; charset=utf-8', extension: 'md' },
+  json: { mimeType: 'application/json; charset=utf-8', extension: 'json' },
+  html: { mimeType: 'text/html; charset=utf-8', extension: 'html' },
+}
+
+/**
+ * Check whether a value is a supported export format.
+ */
+export function isConversationExportFormat(value: unknown): value is ConversationExportFormat {
+  return typeof value === 'string' && Object.hasOwn(FILE_TYPES, value)
+}
+
+/**
+ * Export a conversation in the given format.
+ * The conversation must include its interactions.
+ */
+export function exportConversation(
+  conversation: Conversation,
+  format: ConversationExportFormat,
+  options: ReadableExportOptions = {}
+): ConversationExportFile {
+  const content =
+    format === 'json'
+      ? `${JSON.stringify(exportConversationToJson(conversation), null, 2)}\n`
+      : format === 'html'
+        ? exportConversationToHtml(conversation, options)
+        : exportConversationToMarkdown(conversation, options)
+
+  const { mimeType, extension } = FILE_TYPES[format]
+  return { content, mimeType, fileName: `${toFileName(conversation)}.${extension}` }
+}
+
+/**
+ * Build a file name from the conversation title and start date.
+ */
+function toFileName(conversation: Conversation): string {
+  const date = conversation.metadata.createdAt.slice(0, 10)
+  const slug = (conversation.title ?? '')
+    .normalize('NFKD')
+    .replace(/[\u0300-\u036f]/g, '')
+    .toLowerCase()
+    .replace(/[^a-z0-9]+/g, '-')
+    .replace(/^-+|-+$/g, '')
+    .slice(0, 60)
+  return `stina-${date}${slug ? `-${slug}` : ''}`
+}
@@ -0,0 +1,206 @@
+import type { Conversation, ConversationMetadata } from '../types/conversation.js'
+import type { Interaction } from '../types/interaction.js'
+import type { Message, ToolCall } from '../types/message.js'
+import { sortInteractionsOldestFirst } from './utils.js'
+
+/** Identifies a Stina conversation export */
+export const CONVERSATION_EXPORT_FORMAT = 'stina.conversation'
+
+/** Current version of the JSON export format */
+export const CONVERSATION_EXPORT_VERSION = 2
+
+/**
+ * Interaction as stored in a JSON export.
+ * parentId is missing in version 1 exports, which only hold the active branch.
+ */
+export type ExportedInteraction = Omit<Interaction, 'conversationId' | 'readAt' | 'siblingIds'>
+
+/**
+ * Versioned JSON export of a conversation.
+ * Interactions are ordered oldest first and include every branch.
+ */
+export interface ConversationExport {
+  format: typeof CONVERSATION_EXPORT_FORMAT
+  version: number
+  /** ISO timestamp of the export */
+  exportedAt: string
+  conversation: {
+    id: string
+    title?: string
+    active: boolean
+    metadata: ConversationMetadata
+    interactions: ExportedInteraction[]
+    /** Last interaction of the branch the conversation shows */
+    activeInteractionId?: string
+  }
+}
+
+const MESSAGE_TYPES = new Set(['user', 'stina', 'instruction', 'information', 'thinking', 'tools'])
+
+/**
+ * Build the JSON export of a conversation.
+ * Load the conversation with all its branches to keep them in the export.
+ */
+export function exportConversationToJson(
+  conversation: Conversation,
+  exportedAt: Date = new Date()
+): ConversationExport {
+  return {
+    format: CONVERSATION_EXPORT_FORMAT,
+    version: CONVERSATION_EXPORT_VERSION,
+    exportedAt: exportedAt.toISOString(),
+    conversation: {
+      id: conversation.id,
+      title: conversation.title,
+      active: conversation.active,
+      metadata: conversation.metadata,
+      interactions: sortInteractionsOldestFirst(conversation.interactions).map((interaction) => ({
+        id: interaction.id,
+        messages: interaction.messages,
+        informationMessages: interaction.informationMessages,
+        completed: interaction.completed,
+        aborted: interaction.aborted,
+        error: interaction.error,
+        errorMessage: interaction.errorMessage,
+        metadata: interaction.metadata,
+        parentId: interaction.parentId,
+      })),
+      activeInteractionId: conversation.activeInteractionId,
+    },
+  }
+}
+
+/**
+ * Validate parsed JSON as a conversation export.
+ * @throws If the data is not a supported conversation export
+ */
+export function parseConversationExport(data: unknown): ConversationExport {
+  if (!isRecord(data) || data['format'] !== CONVERSATION_EXPORT_FORMAT) {
+    throw new Error('Not a Stina conversation export')
+  }
+
+  const version = data['version']
+  if (typeof version !== 'number' || !Number.isInteger(version) || version < 1) {
+    throw new Error('Invalid export version')
+  }
+  if (version > CONVERSATION_EXPORT_VERSION) {
+    throw new Error(`Export version ${version} is newer than supported (${CONVERSATION_EXPORT_VERSION})`)
+  }
+
+  const conversation = data['conversation']
+  if (!isRecord(conversation)) {
+    throw new Error('Export is missing the conversation')
+  }
+  if (typeof conversation['id'] !== 'string' || !conversation['id']) {
+    throw new Error('Conversation id must be a string')
+  }
+  if (conversation['title'] !== undefined && typeof conversation['title'] !== 'string') {
+    throw new Error('Conversation title must be a string')
+  }
+  if (!hasCreatedAt(conversation['metadata'])) {
+    throw new Error('Conversation metadata must include createdAt')
+  }
+  if (!Array.isArray(conversation['interactions'])) {
+    throw new Error('Conversation interactions must be an array')
+  }
+
+  const interactions = conversation['interactions'].map((interaction, index) =>
+    parseInteraction(interaction, index)
+  )
+  const activeInteractionId = conversation['activeInteractionId']
+  if (activeInteractionId !== undefined && typeof activeInteractionId !== 'string') {
+    throw new Error('Conversation activeInteractionId must be a string')
+  }
+
+  return {
+    format: CONVERSATION_EXPORT_FORMAT,
+    version,
+    exportedAt: typeof data['exportedAt'] === 'string' ? data['exportedAt'] : '',
+    conversation: {
+      id: conversation['id'],
+      title: conversation['title'] as string | undefined,
+      active: conversation['active'] !== false,
+      metadata: conversation['metadata'],
+      interactions,
+      activeInteractionId,
+    },
+  }
+}
+
+function parseInteraction(value: unknown, index: number): ExportedInteraction {
+  const where = `Interaction ${index + 1}`
+  if (!isRecord(value) || typeof value['id'] !== 'string' || !value['id']) {
+    throw new Error(`${where} must have an id`)
+  }
+  if (!hasCreatedAt(value['metadata'])) {
+    throw new Error(`${where} metadata must include createdAt`)
+  }
+  if (!Array.isArray(value['messages'])) {
+    throw new Error(`${where} messages must be an array`)
+  }
+
+  const informationMessages = value['informationMessages'] ?? []
+  if (!Array.isArray(informationMessages)) {
+    throw new Error(`${where} informationMessages must be an array`)
+  }
+
+  const parentId = value['parentId']
+  if (parentId !== undefined && parentId !== null && typeof parentId !== 'string') {
+    throw new Error(`${where} parentId must be a string or null`)
+  }
+
+  return {
+    id: value['id'],
+    messages: value['messages'].map((message) => parseMessage(message, where)),
+    informationMessages: informationMessages.map((message) => {
+      const parsed = parseMessage(message, where)
+      if (parsed.type !== 'information') {
+        throw new Error(`${where} has an invalid information message`)
+      }
+      return parsed
+    }),
+    completed: value['completed'] !== false,
+    aborted: value['aborted'] === true,
+    error: value['error'] === true,
+    errorMessage: typeof value['errorMessage'] === 'string' ? value['errorMessage'] : undefined,
+    metadata: value['metadata'],
+    parentId,
+  }
+}
+
+function parseMessage(value: unknown, where: string): Message {
+  if (!isRecord(value) || typeof value['type'] !== 'string' || !MESSAGE_TYPES.has(value['type'])) {
+    throw new Error(`${where} has a message with an unknown type`)
+  }
+  if (!hasCreatedAt(value['metadata'])) {
+    throw new Error(`${where} has a message without createdAt`)
+  }
+
+  if (value['type'] === 'tools') {
+    if (!Array.isArray(value['tools']) || !value['tools'].every(isToolCall)) {
+      throw new Error(`${where} has an invalid tools message`)
+    }
+  } else if (typeof value['text'] !== 'string') {
+    throw new Error(`${where} has a message without text`)
+  }
+
+  return value as unknown as Message
+}
+
+function isToolCall(value: unknown): value is ToolCall {
+  return (
+    isRecord(value) &&
+    typeof value['name'] === 'string' &&
+    typeof value['payload'] === 'string' &&
+    typeof value['result'] === 'string' &&
+    hasCreatedAt(value['metadata'])
+  )
+}
+
+function hasCreatedAt(value: unknown): value is { createdAt: string; [key: string]: unknown } {
+  return isRecord(value) && typeof value['createdAt'] === 'string'
+}
+
+function isRecord(value: unknown): value is Record<string, unknown> {
+  return typeof value === 'object' && value !== null && !Array.isArray(value)
+}
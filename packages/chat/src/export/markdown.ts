@@ -0,0 +1,102 @@
+import type { Conversation } from '../types/conversation.js'
+import type { ToolCall } from '../types/message.js'
+import type { ExportEntry, ReadableExportOptions } from './utils.js'
+import {
+  describeConfirmation,
+  formatTimestamp,
+  getActiveBranchInteractions,
+  getExportEntries,
+  prettyJson,
+} from './utils.js'
+
+/**
+ * Export a conversation as Markdown.
+ * Thinking and tool calls are rendered as collapsible sections.
+ */
+export function exportConversationToMarkdown(
+  conversation: Conversation,
+  options: ReadableExportOptions = {}
+): string {
+  const lines: string[] = [
+    `# ${conversation.title?.trim() || 'Conversation'}`,
+    '',
+    `_Started ${formatTimestamp(conversation.metadata.createdAt, options.timeZone)}_`,
+  ]
+
+  for (const interaction of getActiveBranchInteractions(conversation)) {
+    const entries = getExportEntries(interaction, options)
+    if (entries.length === 0) continue
+
+    lines.push('', '---', '', `### ${formatTimestamp(interaction.metadata.createdAt, options.timeZone)}`)
+    for (const entry of entries) {
+      lines.push('', ...renderEntry(entry))
+    }
+  }
+
+  return `${lines.join('\n')}\n`
+}
+
+function renderEntry(entry: ExportEntry): string[] {
+  switch (entry.kind) {
+    case 'user':
+      return ['**You:**', '', entry.text]
+    case 'stina':
+      return ['**Stina:**', '', entry.text]
+    case 'instruction':
+      return ['**Instruction:**', '', quote(entry.text)]
+    case 'information':
+      return [quote(`ℹ️ ${entry.text}`)]
+    case 'summary':
+      return ['**Summary of earlier messages:**', '', quote(entry.text)]
+    case 'thinking':
+      return ['<details>', '<summary>Thinking</summary>', '', entry.text, '', '</details>']
+    case 'tool':
+      return renderTool(entry.tool)
+    case 'error':
+      return [quote(`⚠️ Error: ${entry.text}`)]
+    case 'aborted':
+      return [quote('_Response stopped_')]
+  }
+}
+
+function renderTool(tool: ToolCall): string[] {
+  const confirmation = describeConfirmation(tool)
+  const lines = [
+    '<details>',
+    `<summary>Tool: ${escapeInline(tool.displayName ?? tool.name)}${confirmation ? ` (${escapeInline(confirmation)})` : ''}</summary>`,
+    '',
+  ]
+
+  if (tool.confirmationPrompt) {
+    lines.push(quote(tool.confirmationPrompt), '')
+  }
+  lines.push('Input:', '', ...fence(prettyJson(tool.payload), 'json'), '')
+  if (tool.result) {
+    lines.push('Result:', '', ...fence(prettyJson(tool.result)), '')
+  }
+  lines.push('</details>')
+  return lines
+}
+
+function quote(text: string): string {
+  return text
+    .split('\n')
+    .map((line) => (line ? `> ${line}` : '>'))
+    .join('\n')
+}
+
+/**
+ * Wrap text in a code fence longer than any backtick run inside it.
+ */
+function fence(text: string, language = ''): string[] {
+  const longestRun = Math.max(0, ...(text.match(/`+/g) ?? []).map((run) => run.length))
+  const marker = '`'.repeat(Math.max(3, longestRun + 1))
+  return [`${marker}${language}`, text, marker]
+}
+
+/**
+ * Escape text placed inside inline HTML in Markdown.
+ */
+function escapeInline(text: string): string {
+  return text.replace(/&/g, '&amp;').replace(/</g, '&lt;').replace(/>/g, '&gt;')
+}
@@ -0,0 +1,138 @@
+import type { Conversation } from '../types/conversation.js'
+import type { Interaction } from '../types/interaction.js'
+import type { Message, ToolCall } from '../types/message.js'
+import { getBranchPath } from '../orchestrator/branches.js'
+import { isContextSummaryMessage } from '../orchestrator/contextBudget.js'
+
+/**
+ * Options for the readable (Markdown and HTML) exports
+ */
+export interface ReadableExportOptions {
+  /** IANA timezone for timestamps. Defaults to UTC */
+  timeZone?: string
+  /** Include the system prompt sent to the provider. Defaults to false */
+  includeSystemPrompt?: boolean
+}
+
+/**
+ * Entry in a readable export, in the order it appeared
+ */
+export type ExportEntry =
+  | { kind: 'user' | 'stina' | 'instruction' | 'information' | 'summary' | 'thinking'; text: string }
+  | { kind: 'tool'; tool: ToolCall }
+  | { kind: 'error'; text: string }
+  | { kind: 'aborted' }
+
+/**
+ * Copy interactions ordered oldest first. Repositories return newest first.
+ */
+export function sortInteractionsOldestFirst(interactions: Interaction[]): Interaction[] {
+  return [...interactions].sort(
+    (a, b) => Date.parse(a.metadata.createdAt) - Date.parse(b.metadata.createdAt)
+  )
+}
+
+/**
+ * Get the interactions of the branch the conversation shows, oldest first.
+ * Conversations loaded with all their branches are narrowed to the active one.
+ */
+export function getActiveBranchInteractions(conversation: Conversation): Interaction[] {
+  const interactions = sortInteractionsOldestFirst(conversation.interactions)
+  if (!conversation.activeInteractionId) return interactions
+
+  const nodes = interactions.map((interaction) => ({
+    id: interaction.id,
+    parentId: interaction.parentId ?? null,
+  }))
+  const path = new Set(getBranchPath(nodes, conversation.activeInteractionId))
+  return interactions.filter((interaction) => path.has(interaction.id))
+}
+
+/**
+ * Flatten an interaction into the entries shown in readable exports.
+ */
+export function getExportEntries(
+  interaction: Interaction,
+  options: ReadableExportOptions
+): ExportEntry[] {
+  const entries: ExportEntry[] = interaction.informationMessages.map((message) => ({
+    kind: 'information' as const,
+    text: message.text,
+  }))
+
+  for (const message of interaction.messages) {
+    entries.push(...getMessageEntries(message, options))
+  }
+
+  if (interaction.error) {
+    entries.push({ kind: 'error', text: interaction.errorMessage ?? 'Unknown error' })
+  } else if (interaction.aborted) {
+    entries.push({ kind: 'aborted' })
+  }
+
+  return entries
+}
+
+function getMessageEntries(message: Message, options: ReadableExportOptions): ExportEntry[] {
+  switch (message.type) {
+    case 'tools':
+      return message.tools.map((tool) => ({ kind: 'tool' as const, tool }))
+    case 'instruction':
+      if (message.metadata['systemPrompt'] === true && !options.includeSystemPrompt) return []
+      if (isContextSummaryMessage(message)) return [{ kind: 'summary', text: message.text }]
+      return message.text.trim() ? [{ kind: 'instruction', text: message.text }] : []
+    default:
+      return message.text.trim() ? [{ kind: message.type, text: message.text }] : []
+  }
+}
+
+/**
+ * Describe the confirmation outcome of a tool call, if it needed confirmation.
+ */
+export function describeConfirmation(tool: ToolCall): string | null {
+  switch (tool.confirmationStatus) {
+    case 'approved':
+      return 'Approved'
+    case 'denied':
+      return tool.confirmationDenialReason
+        ? `Denied: ${tool.confirmationDenialReason}`
+        : 'Denied'
+    case 'pending':
+      return 'Not answered'
+    default:
+      return null
+  }
+}
+
+/**
+ * Pretty-print JSON text, or return it unchanged if it is not JSON.
+ */
+export function prettyJson(text: string): string {
+  try {
+    return JSON.stringify(JSON.parse(text), null, 2)
+  } catch {
+    return text
+  }
+}
+
+/**
+ * Format an ISO timestamp as "YYYY-MM-DD HH:mm" in the given timezone.
+ */
+export function formatTimestamp(iso: string, timeZone = 'UTC'): string {
+  const date = new Date(iso)
+  if (Number.isNaN(date.getTime())) return iso
+
+  const parts = new Intl.DateTimeFormat('en-CA', {
+    timeZone,
+    year: 'numeric',
+    month: '2-digit',
+    day: '2-digit',
+    hour: '2-digit',
+    minute: '2-digit',
+    hourCycle: 'h23',
+  }).formatToParts(date)
+  const get = (type: Intl.DateTimeFormatPartTypes) =>
+    parts.find((part) => part.type === type)?.value ?? ''
+
+  return `${get('year')}-${get('month')}-${get('day')} ${get('hour')}:${get('minute')}`
+}
@@ -7,6 +7,7 @@ export type {
   MessageType,
   MessageMetadata,
   UserMessage,
+  MessageAttachment,
   StinaMessage,
   InstructionMessage,
   InformationMessage,
@@ -19,7 +20,7 @@ export { MessageType as MessageTypeEnum } from './types/message.js'
 
 export type { Interaction, InteractionMetadata } from './types/interaction.js'
 export type { Conversation, ConversationMetadata } from './types/conversation.js'
-export type { AIProvider, StreamEvent } from './types/provider.js'
+export type { AIProvider, StreamEvent, EmbedOptions, TokenUsage } from './types/provider.js'
 
 // Services
 export { ConversationService, conversationService } from './services/ConversationService.js'
@@ -35,6 +36,14 @@ export {
 // Providers
 export { ProviderRegistry, providerRegistry } from './providers/ProviderRegistry.js'
 export { EchoProvider, echoProvider } from './providers/EchoProvider.js'
+export {
+  OpenAICompatibleProvider,
+  OPENAI_COMPATIBLE_PROVIDER_ID,
+  DEFAULT_OPENAI_COMPATIBLE_BASE_URL,
+  getOpenAICompatibleOptionsFromEnv,
+  registerOpenAICompatibleProvider,
+  type OpenAICompatibleProviderOptions,
+} from './providers/OpenAICompatibleProvider.js'
 
 // Orchestrator
 export { ChatOrchestrator } from './orchestrator/ChatOrchestrator.js'
@@ -46,8 +55,82 @@ export type {
   ChatOrchestratorDeps,
 } from './orchestrator/types.js'
 export type { QueueState, QueuedMessageRole } from './orchestrator/ChatMessageQueue.js'
+export {
+  getConversationOverrides,
+  normalizeConversationOverrides,
+  type ConversationOverrides,
+} from './orchestrator/conversationOverrides.js'
 export { ChatSessionManager } from './sessions/chatSessionManager.js'
 
+// Export and import
+export {
+  exportConversation,
+  exportConversationToMarkdown,
+  exportConversationToHtml,
+  exportConversationToJson,
+  parseConversationExport,
+  importConversation,
+  isConversationExportFormat,
+  CONVERSATION_EXPORT_FORMAT,
+  CONVERSATION_EXPORT_VERSION,
+  type ConversationExport,
+  type ConversationExportFile,
+  type ExportedInteraction,
+  type ImportConversationOptions,
+  type ReadableExportOptions,
+} from './export/index.js'
+
+// Memory
+export {
+  MemoryService,
+  createProviderEmbedder,
+  cosineSimilarity,
+  keywordOverlap,
+  DEFAULT_RECALL_LIMIT,
+  MAX_MEMORY_LENGTH,
+  type Memory,
+  type MemoryMatch,
+  type CreateMemoryInput,
+  type IMemoryRepository,
+  type MemoryEmbedder,
+  type RecallOptions,
+} from './memory/index.js'
+
+// Tasks and reminders
+export {
+  ReminderService,
+  MAX_REMINDER_LENGTH,
+  type Task,
+  type CreateTaskInput,
+  type UpdateTaskInput,
+  type ListTasksOptions,
+  type ITaskRepository,
+  type Reminder,
+  type CreateReminderInput,
+  type UpdateReminderInput,
+  type IReminderRepository,
+  type ReminderScheduler,
+} from './tasks/index.js'
+
+// Quiet hours
+export {
+  QuietHoursService,
+  QUIET_HOURS_CHECK_INTERVAL_MS,
+  type DeferredInstruction,
+  type CreateDeferredInstructionInput,
+  type IDeferredInstructionRepository,
+  type QuietHoursServiceOptions,
+} from './quietHours/index.js'
+
+// Attachments
+export {
+  storeAttachments,
+  MAX_ATTACHMENTS_PER_MESSAGE,
+  MAX_ATTACHMENT_BYTES_PER_MESSAGE,
+  type AttachmentUpload,
+  type IAttachmentStore,
+} from './attachments/index.js'
+
 // Mappers
 export {
   interactionToDTO,
@@ -5,6 +5,10 @@ import type {
   ChatInteractionDTO,
   ChatMessageDTO,
 } from '@stina/shared'
+import {
+  getConversationOverrides,
+  CONVERSATION_OVERRIDES_METADATA_KEY,
+} from '../orchestrator/conversationOverrides.js'
 
 /**
  * Convert domain Interaction to DTO
@@ -20,6 +24,10 @@ export function interactionToDTO(interaction: Interaction): ChatInteractionDTO {
     createdAt: interaction.metadata.createdAt,
     error: interaction.error,
     errorMessage: interaction.errorMessage,
+    modelId: interaction.metadata.modelId,
+    usage: interaction.metadata.usage,
+    parentId: interaction.parentId,
+    siblingIds: interaction.siblingIds,
   }
 }
 
@@ -36,6 +44,10 @@ export function messageToDTO(message: Message): ChatMessageDTO {
     base.text = message.text
   }
 
+  if (message.type === 'user' && message.attachments && message.attachments.length > 0) {
+    base.attachments = message.attachments.map((attachment) => ({ ...attachment }))
+  }
+
   if (message.type === 'tools') {
     base.tools = message.tools.map((tool) => ({
       name: tool.name,
@@ -70,8 +82,9 @@ export function dtoToInteraction(dto: ChatInteractionDTO, conversationId: string
     aborted: false,
     error: dto.error ?? false,
     errorMessage: dto.errorMessage,
-    metadata: { createdAt: dto.createdAt },
+    metadata: { createdAt: dto.createdAt, modelId: dto.modelId, usage: dto.usage },
     readAt: dto.readAt,
+    parentId: dto.parentId,
   }
 }
 
@@ -83,7 +96,9 @@ export function dtoToMessage(dto: ChatMessageDTO): Message {
 
   switch (dto.type) {
     case 'user':
-      return { type: 'user', text: dto.text || '', metadata }
+      return dto.attachments && dto.attachments.length > 0
+        ? { type: 'user', text: dto.text || '', attachments: dto.attachments, metadata }
+        : { type: 'user', text: dto.text || '', metadata }
     case 'stina':
       return { type: 'stina', text: dto.text || '', metadata }
     case 'instruction':
@@ -116,12 +131,14 @@ export function dtoToMessage(dto: ChatMessageDTO): Message {
  * Convert domain Conversation to DTO
  */
 export function conversationToDTO(conversation: Conversation): ChatConversationDTO {
+  const overrides = getConversationOverrides(conversation)
   return {
     id: conversation.id,
     title: conversation.title,
     interactions: conversation.interactions.map(interactionToDTO),
     active: conversation.active,
     createdAt: conversation.metadata.createdAt,
+    overrides: Object.keys(overrides).length > 0 ? overrides : undefined,
   }
 }
 
@@ -134,7 +151,10 @@ export function dtoToConversation(dto: ChatConversationDTO): Conversation {
     title: dto.title,
     interactions: dto.interactions.map((i) => dtoToInteraction(i, dto.id)),
     active: dto.active,
-    metadata: { createdAt: dto.createdAt },
+    metadata: {
+      createdAt: dto.createdAt,
+      ...(dto.overrides ? { [CONVERSATION_OVERRIDES_METADATA_KEY]: dto.overrides } : {}),
+    },
   }
 }
 
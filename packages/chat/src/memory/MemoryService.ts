@@ -0,0 +1,139 @@
+import type { IMemoryRepository, Memory, MemoryEmbedder, MemoryMatch } from './types.js'
+import { cosineSimilarity, keywordOverlap } from './vector.js'
+
+/** Default number of memories returned by recall */
+export const DEFAULT_RECALL_LIMIT = 5
+
+/** Minimum cosine similarity for a memory to count as relevant */
+const MIN_SIMILARITY = 0.3
+
+/** Minimum keyword overlap for a memory without a comparable embedding */
+const MIN_KEYWORD_OVERLAP = 0.25
+
+/** Maximum length of a single memory, in characters */
+export const MAX_MEMORY_LENGTH = 2000
+
+export interface RecallOptions {
+  /** Maximum number of memories to return */
+  limit?: number
+}
+
+/**
+ * Long-term memory for one user.
+ *
+ * Memories are embedded with the user's AI provider when it supports
+ * embeddings. Recall ranks memories by cosine similarity to the query and
+ * falls back to keyword overlap for memories without a comparable vector, so
+ * memory keeps working with providers that cannot embed.
+ */
+export class MemoryService {
+  constructor(
+    private readonly repository: IMemoryRepository,
+    private readonly embedder?: MemoryEmbedder
+  ) {}
+
+  /**
+   * Save a fact.
+   * If embedding fails the memory is stored without a vector.
+   * @throws If the content is empty or too long
+   */
+  async remember(content: string): Promise<Memory> {
+    const text = content.trim()
+    if (!text) {
+      throw new Error('Memory content is empty')
+    }
+    if (text.length > MAX_MEMORY_LENGTH) {
+      throw new Error(`Memory content is longer than ${MAX_MEMORY_LENGTH} characters`)
+    }
+
+    const embedded = await this.tryEmbed([text])
+    return this.repository.create({
+      content: text,
+      embedding: embedded?.embeddings[0] ?? null,
+      embeddingModel: embedded?.model ?? null,
+    })
+  }
+
+  /**
+   * Find the memories most relevant to a query, best match first.
+   */
+  async recall(query: string, options: RecallOptions = {}): Promise<MemoryMatch[]> {
+    const limit = options.limit ?? DEFAULT_RECALL_LIMIT
+    const text = query.trim()
+    if (!text || limit <= 0) return []
+
+    const memories = await this.repository.list()
+    if (memories.length === 0) return []
+
+    const embedded = await this.tryEmbed([text])
+    const queryVector = embedded?.embeddings[0]
+    if (embedded && queryVector) {
+      await this.reembedStale(memories, embedded.model)
+    }
+
+    const matches: MemoryMatch[] = []
+    for (const memory of memories) {
+      const comparable =
+        queryVector &&
+        memory.embedding &&
+        memory.embeddingModel === embedded?.model &&
+        memory.embedding.length === queryVector.length
+
+      const score = comparable
+        ? cosineSimilarity(queryVector, memory.embedding!)
+        : keywordOverlap(text, memory.content)
+      const threshold = comparable ? MIN_SIMILARITY : MIN_KEYWORD_OVERLAP
+      if (score >= threshold) {
+        matches.push({ memory, score })
+      }
+    }
+
+    return matches.sort((a, b) => b.score - a.score).slice(0, limit)
+  }
+
+  /**
+   * Delete a memory.
+   * @returns True if the memory existed
+   */
+  async forget(id: string): Promise<boolean> {
+    return this.repository.delete(id)
+  }
+
+  /**
+   * All memories, oldest first.
+   */
+  async list(): Promise<Memory[]> {
+    return this.repository.list()
+  }
+
+  /**
+   * Embed memories that have no vector, or one from another model, so they
+   * can be compared with the current query.
+   */
+  private async reembedStale(memories: Memory[], model: string): Promise<void> {
+    const stale = memories.filter((memory) => memory.embeddingModel !== model || !memory.embedding)
+    if (stale.length === 0) return
+
+    const embedded = await this.tryEmbed(stale.map((memory) => memory.content))
+    if (!embedded || embedded.model !== model) return
+
+    for (const [index, memory] of stale.entries()) {
+      const embedding = embedded.embeddings[index]
+      if (!embedding) continue
+      await this.repository.updateEmbedding(memory.id, embedding, model)
+      memory.embedding = embedding
+      memory.embeddingModel = model
+    }
+  }
+
+  private async tryEmbed(texts: string[]): Promise<{ model: string; embeddings: number[][] } | null> {
+    if (!this.embedder) return null
+    try {
+      const result = await this.embedder.embed(texts)
+      return result && result.embeddings.length === texts.length ? result : null
+    } catch (error) {
+      console.warn('Failed to embed memory text:', error)
+      return null
+    }
+  }
+}
@@ -0,0 +1,4 @@
+export { MemoryService, DEFAULT_RECALL_LIMIT, MAX_MEMORY_LENGTH, type RecallOptions } from './MemoryService.js'
+export { createProviderEmbedder } from './providerEmbedder.js'
+export { cosineSimilarity, keywordOverlap } from './vector.js'
+export type { Memory, MemoryMatch, CreateMemoryInput, IMemoryRepository, MemoryEmbedder } from './types.js'
@@ -0,0 +1,29 @@
+import type { ProviderRegistry } from '../providers/ProviderRegistry.js'
+import type { IModelConfigProvider } from '../orchestrator/types.js'
+import type { MemoryEmbedder } from './types.js'
+
+/**
+ * Create an embedder that uses the user's default model configuration.
+ * The provider receives the configured model and settings and picks a
+ * matching embedding model itself.
+ */
+export function createProviderEmbedder(
+  providerRegistry: ProviderRegistry,
+  modelConfigProvider: IModelConfigProvider
+): MemoryEmbedder {
+  return {
+    async embed(texts) {
+      const modelConfig = await modelConfigProvider.getDefault()
+      if (!modelConfig) return null
+
+      const provider = providerRegistry.get(modelConfig.providerId)
+      if (!provider?.embed) return null
+
+      const embeddings = await provider.embed(texts, {
+        modelId: modelConfig.modelId,
+        settings: modelConfig.settingsOverride,
+      })
+      return { model: `${modelConfig.providerId}:${modelConfig.modelId}`, embeddings }
+    },
+  }
+}
@@ -0,0 +1,59 @@
+/**
+ * A fact Stina has been asked to remember
+ */
+export interface Memory {
+  id: string
+  content: string
+  /** Embedding vector, null when no embedding model was available */
+  embedding: number[] | null
+  /** '<providerId>:<modelId>' that produced the embedding */
+  embeddingModel: string | null
+  createdAt: Date
+  updatedAt: Date
+}
+
+/**
+ * A memory with its relevance to a query
+ */
+export interface MemoryMatch {
+  memory: Memory
+  /** Relevance between 0 and 1 */
+  score: number
+}
+
+/**
+ * Input for storing a new memory
+ */
+export interface CreateMemoryInput {
+  content: string
+  embedding: number[] | null
+  embeddingModel: string | null
+}
+
+/**
+ * Storage for memories, scoped to one user
+ */
+export interface IMemoryRepository {
+  list(): Promise<Memory[]>
+  get(id: string): Promise<Memory | null>
+  create(input: CreateMemoryInput): Promise<Memory>
+  /**
+   * Replace the embedding of a memory, e.g. after changing embedding model
+   */
+  updateEmbedding(id: string, embedding: number[], embeddingModel: string): Promise<void>
+  /**
+   * @returns True if a memory was deleted
+   */
+  delete(id: string): Promise<boolean>
+}
+
+/**
+ * Turns texts into embedding vectors
+ */
+export interface MemoryEmbedder {
+  /**
+   * Embed texts with the current model.
+   * @returns The model key and one vector per text, or null when no model can embed
+   */
+  embed(texts: string[]): Promise<{ model: string; embeddings: number[][] } | null>
+}
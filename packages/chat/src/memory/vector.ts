@@ -0,0 +1,45 @@
+/**
+ * Cosine similarity between two vectors.
+ * @returns A value between -1 and 1, or 0 if the vectors can't be compared
+ */
+export function cosineSimilarity(a: number[], b: number[]): number {
+  if (a.length === 0 || a.length !== b.length) return 0
+
+  let dot = 0
+  let normA = 0
+  let normB = 0
+  for (let i = 0; i < a.length; i++) {
+    const x = a[i]!
+    const y = b[i]!
+    dot += x * y
+    normA += x * x
+    normB += y * y
+  }
+  if (normA === 0 || normB === 0) return 0
+
+  return dot / (Math.sqrt(normA) * Math.sqrt(normB))
+}
+
+/**
+ * Share of the query's words found in the text.
+ * Used when memories have no comparable embedding.
+ * @returns A value between 0 and 1
+ */
+export function keywordOverlap(query: string, text: string): number {
+  const queryWords = new Set(tokenize(query))
+  if (queryWords.size === 0) return 0
+
+  const textWords = new Set(tokenize(text))
+  let matches = 0
+  for (const word of queryWords) {
+    if (textWords.has(word)) matches++
+  }
+  return matches / queryWords.size
+}
+
+function tokenize(text: string): string[] {
+  return text
+    .toLowerCase()
+    .split(/[^\p{L}\p{N}]+/u)
+    .filter((word) => word.length > 2)
+}
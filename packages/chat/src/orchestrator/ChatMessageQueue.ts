@@ -1,3 +1,5 @@
+import type { MessageAttachment } from '../types/message.js'
+
 export type QueuedMessageRole = 'user' | 'instruction'
 export type QueuedMessageContext = 'conversation-start' | 'settings-update'
 
@@ -6,6 +8,10 @@ export interface QueuedMessage {
   text: string
   role: QueuedMessageRole
   context?: QueuedMessageContext
+  /** Stored files attached to a user message */
+  attachments?: MessageAttachment[]
+  /** Interaction this message replaces when editing or regenerating, as a new branch */
+  replacesInteractionId?: string
   createdAt: string
 }
 
@@ -55,13 +61,15 @@ export class ChatMessageQueue {
       queued: this.items.map((item) => ({
         id: item.id,
         role: item.role,
-        preview: this.buildPreview(item.text),
+        preview: this.buildPreview(item),
       })),
     }
   }
 
-  private buildPreview(text: string): string {
-    const trimmed = text.trim()
+  private buildPreview(item: QueuedMessage): string {
+    // Messages with only attachments are previewed by their file names
+    const trimmed =
+      item.text.trim() || (item.attachments ?? []).map((attachment) => attachment.name).join(', ')
     if (trimmed.length <= PREVIEW_MAX_LENGTH) return trimmed
     return `${trimmed.slice(0, PREVIEW_TRIM_LENGTH)}...`
   }
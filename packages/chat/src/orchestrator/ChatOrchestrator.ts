@@ -1,12 +1,21 @@
 import { nanoid } from 'nanoid'
-import type { Conversation, Interaction, Message } from '../types/index.js'
+import type {
+  AIProvider,
+  Conversation,
+  Interaction,
+  Message,
+  MessageAttachment,
+  TokenUsage,
+} from '../types/index.js'
 import type { IConversationRepository } from './IConversationRepository.js'
 import type {
   OrchestratorEvent,
   ChatState,
   ChatOrchestratorOptions,
   ChatOrchestratorDeps,
+  ChatModelConfig,
 } from './types.js'
+import type { ResolvedToolDefinition } from '../tools/ToolRegistry.js'
 import { ConversationService } from '../services/ConversationService.js'
 import { ChatStreamService } from '../services/ChatStreamService.js'
 import {
@@ -15,6 +24,13 @@ import {
   getPromptUpdatePrefix,
   getPromptUpdateInfo,
   getPromptUpdateInstruction,
+  getContextSummarySystemPrompt,
+  getContextSummaryRequest,
+  getContextSummaryPrefix,
+  getContextSummaryInfo,
+  getConversationTitleSystemPrompt,
+  getConversationTitleRequest,
+  getMemoryPrompt,
 } from '../constants/index.js'
 import {
   ChatMessageQueue,
@@ -22,7 +38,7 @@ import {
   type QueuedMessageRole,
   type QueuedMessageContext,
 } from './ChatMessageQueue.js'
-import { APP_NAMESPACE } from '@stina/core'
+import { APP_NAMESPACE, AppError, ErrorCode } from '@stina/core'
 import {
   setupStreamListeners,
   cleanupStreamListeners,
@@ -32,9 +48,51 @@ import {
   createToolExecutor,
   type LocalConfirmationStore,
 } from './toolConfirmation.js'
+import {
+  buildContextMessages,
+  estimatePromptTokens,
+  getContextCompactionState,
+  getContextSummary,
+  getInteractionsAfter,
+  resolveCompactionLimit,
+  selectInteractionsToCompact,
+  CONTEXT_COMPACTION_METADATA_KEY,
+  CONTEXT_SUMMARY_METADATA_KEY,
+  type ContextBudgetOptions,
+  type ContextCompactionState,
+} from './contextBudget.js'
+import { cleanGeneratedTitle, getTitleSourceMessages } from './conversationTitle.js'
+import { getNewestLeaf, getResendableMessage } from './branches.js'
+import {
+  getConversationOverrides,
+  normalizeConversationOverrides,
+  CONVERSATION_OVERRIDES_METADATA_KEY,
+  type ConversationOverrides,
+} from './conversationOverrides.js'
 
 export type OrchestratorEventCallback = (event: OrchestratorEvent) => void
 
+/**
+ * Whether a model config provider refused a model config the user may not use
+ */
+function isModelNotAllowedError(error: unknown): error is AppError {
+  return error instanceof AppError && error.code === ErrorCode.CHAT_MODEL_NOT_ALLOWED
+}
+
+/**
+ * Treat a model config the user may not use as unset, so the default model is used instead
+ */
+async function ignoreModelNotAllowed(
+  modelConfig: Promise<ChatModelConfig | null | undefined> | undefined
+): Promise<ChatModelConfig | null | undefined> {
+  try {
+    return await modelConfig
+  } catch (error) {
+    if (isModelNotAllowedError(error)) return null
+    throw error
+  }
+}
+
 /**
  * Platform-neutral chat orchestration service.
  * Coordinates conversations, providers, streaming, and persistence.
@@ -52,6 +110,10 @@ export class ChatOrchestrator {
   private conversationService = new ConversationService()
   private streamService = new ChatStreamService()
   private pageSize: number
+  private contextBudget: ContextBudgetOptions
+  private autoTitle: boolean
+  /** Conversations a title has been requested for by this instance */
+  private titledConversationIds = new Set<string>()
   public readonly instanceId = ++orchestratorIdCounter
   private eventCallbacks: OrchestratorEventCallback[] = []
   private queue = new ChatMessageQueue()
@@ -84,6 +146,8 @@ export class ChatOrchestrator {
     this.deps = deps
     this.repository = deps.repository
     this.pageSize = options.pageSize ?? 10
+    this.contextBudget = options.contextBudget ?? {}
+    this.autoTitle = options.autoTitle ?? true
     this.setupStreamListeners()
   }
 
@@ -205,20 +269,16 @@ export class ChatOrchestrator {
    * Build complete message history for provider.
    * Includes all loaded interactions plus current interaction.
    * Excludes interactions that encountered errors.
+   * Interactions covered by a context summary are replaced by the summary.
    */
   buildMessageHistory(): Message[] {
-    const allMessages: Message[] = []
     const isSystemPromptMessage = (message: Message): boolean =>
       message.type === 'instruction' && message.metadata?.['systemPrompt'] === true
 
     // Add from loaded interactions (reverse to get chronological order, oldest first)
-    // Filter out interactions that had errors - they shouldn't be included in history
     const chronological = [...this._loadedInteractions].reverse()
-    for (const interaction of chronological) {
-      if (!interaction.error) {
-        allMessages.push(...interaction.messages.filter((message) => !isSystemPromptMessage(message)))
-      }
-    }
+    const { revertedSummaryIds } = getContextCompactionState(this._conversation)
+    const allMessages = buildContextMessages(chronological, new Set(revertedSummaryIds))
 
     // Add current interaction messages
     if (this._currentInteraction) {
@@ -344,25 +404,119 @@ export class ChatOrchestrator {
 
   /**
    * Enqueue a message with optional role and queue id.
+   * Attachments must already be stored in the attachment store.
    */
   async enqueueMessage(
     text: string,
     role: QueuedMessageRole = 'user',
     queueId?: string,
-    context?: QueuedMessageContext
+    context?: QueuedMessageContext,
+    attachments?: MessageAttachment[]
   ): Promise<void> {
+    return this.enqueue(
+      {
+        text,
+        role,
+        context,
+        attachments: attachments && attachments.length > 0 ? attachments : undefined,
+      },
+      queueId
+    )
+  }
+
+  /**
+   * Send the message of an interaction again and let Stina answer anew.
+   * The new answer becomes a sibling branch, so the original is kept.
+   * @throws If the interaction is not in the active branch or has no message to send again.
+   */
+  async regenerate(interactionId: string, queueId?: string): Promise<void> {
+    const message = await this.getReplaceableMessage(interactionId)
+    return this.enqueue(
+      {
+        text: message.text,
+        role: message.type,
+        attachments: message.type === 'user' ? message.attachments : undefined,
+        replacesInteractionId: interactionId,
+      },
+      queueId
+    )
+  }
+
+  /**
+   * Edit the user's message in an interaction and send it as a new sibling branch.
+   * Attachments of the original message are kept.
+   * @throws If the interaction is not in the active branch or has no user message.
+   */
+  async editAndResend(interactionId: string, text: string, queueId?: string): Promise<void> {
+    const message = await this.getReplaceableMessage(interactionId)
+    if (message.type !== 'user') {
+      throw new Error(`Interaction ${interactionId} has no user message to edit`)
+    }
+    if (!text.trim() && !message.attachments?.length) {
+      throw new Error('Message text is required')
+    }
+
+    return this.enqueue(
+      {
+        text,
+        role: 'user',
+        attachments: message.attachments,
+        replacesInteractionId: interactionId,
+      },
+      queueId
+    )
+  }
+
+  /**
+   * Show another branch of the conversation, continuing down to its newest interaction.
+   * @param interactionId - Any interaction of the branch, usually a sibling of a loaded one.
+   * @returns false if a message is being processed or the interaction is not in the conversation
+   */
+  async switchBranch(interactionId: string): Promise<boolean> {
+    if (!this._conversation || this.isQueueProcessing) return false
+
+    const nodes = await this.repository.getInteractionTree(this._conversation.id)
+    if (!nodes.some((node) => node.id === interactionId)) return false
+
+    await this.repository.setActiveInteraction(
+      this._conversation.id,
+      getNewestLeaf(nodes, interactionId)
+    )
+    await this.loadInitialInteractions()
+    return true
+  }
+
+  /**
+   * Find the message to send again when editing or regenerating an interaction,
+   * loading older interactions of the branch until it is found.
+   */
+  private async getReplaceableMessage(interactionId: string) {
+    const find = () => this._loadedInteractions.find((loaded) => loaded.id === interactionId)
+    let interaction = find()
+    while (!interaction && this.hasMoreInteractions) {
+      const loadedCount = this._loadedInteractions.length
+      await this.loadMoreInteractions()
+      if (this._loadedInteractions.length === loadedCount) break
+      interaction = find()
+    }
+
+    if (!interaction) {
+      throw new Error(`Interaction ${interactionId} not found`)
+    }
+    const message = getResendableMessage(interaction)
+    if (!message) {
+      throw new Error(`Interaction ${interactionId} cannot be regenerated`)
+    }
+    return message
+  }
+
+  private enqueue(item: Omit<QueuedMessage, 'id' | 'createdAt'>, queueId?: string): Promise<void> {
     const id = queueId ?? nanoid()
     const done = new Promise<void>((resolve) => {
       this.pendingQueueResolves.set(id, resolve)
     })
 
-    this.queue.enqueue({
-      id,
-      text,
-      role,
-      context,
-      createdAt: new Date().toISOString(),
-    })
+    this.queue.enqueue({ ...item, id, createdAt: new Date().toISOString() })
 
     this.emitQueueUpdate()
     void this.processQueue()
@@ -443,18 +597,116 @@ export class ChatOrchestrator {
       await this.createConversation()
     }
 
-    const systemPrompt = getSystemPrompt(this.deps.settingsStore)
+    // Editing or regenerating continues from the replaced interaction's parent
+    let replaced: Interaction | undefined
+    if (job.replacesInteractionId) {
+      const index = this._loadedInteractions.findIndex((i) => i.id === job.replacesInteractionId)
+      replaced = this._loadedInteractions[index]
+      if (!replaced) {
+        this._error = new Error(`Interaction ${job.replacesInteractionId} not found`)
+        this.emitStateChange()
+        this.emitEvent({ type: 'stream-error', error: this._error, queueId: job.id })
+        return
+      }
+      this._loadedInteractions = this._loadedInteractions.slice(index + 1)
+      this._totalInteractionsCount -= index + 1
+      this.emitStateChange()
+    }
+    const branchParentId = this._loadedInteractions[0]?.id ?? null
+    // A new first interaction gets the system prompt like the original did
+    const isBranchStart = replaced !== undefined && this._loadedInteractions.length === 0
+
+    const overrides = getConversationOverrides(this._conversation)
+    const systemPrompt = getSystemPrompt(this.deps.settingsStore, overrides)
     const promptChanged = this.lastSystemPrompt !== systemPrompt
     const includeSystemPrompt =
-      isConversationStart || promptChanged || job.context === 'settings-update'
+      isConversationStart || isBranchStart || promptChanged || job.context === 'settings-update'
     const systemPromptMessageText = includeSystemPrompt
       ? job.context === 'settings-update'
         ? `${getPromptUpdatePrefix(this.deps.settingsStore)}\n\n${systemPrompt}`
         : systemPrompt
       : ''
 
+    let queueText = job.text
+    if (job.role === 'instruction' && !queueText.trim()) {
+      if (job.context === 'conversation-start') {
+        queueText = getGreetingInstruction(this.deps.settingsStore)
+      } else if (job.context === 'settings-update') {
+        queueText = getPromptUpdateInstruction(this.deps.settingsStore)
+      }
+    }
+
+    // Memories are added to the prompt sent to the provider only, so they are
+    // not stored as part of the conversation's system prompt
+    const memoryPrompt =
+      job.role === 'user' && queueText.trim() ? await this.getRelevantMemoryPrompt(queueText) : ''
+    const providerSystemPrompt = memoryPrompt ? `${systemPrompt}\n\n${memoryPrompt}` : systemPrompt
+
+    // Get model configuration if available
+    let modelConfig: ChatModelConfig | null | undefined
+    let modelNotAllowed: AppError | null = null
+    try {
+      modelConfig = await this.resolveModelConfig(overrides)
+    } catch (error) {
+      if (!isModelNotAllowedError(error)) throw error
+      modelNotAllowed = error
+    }
+
+    // Get provider - use from modelConfig if available, otherwise first available.
+    // A model the user may not use is never replaced by another provider, and users
+    // with a model allowlist always need a usable model config.
+    let provider
+    if (modelConfig) {
+      provider = this.deps.providerRegistry.get(modelConfig.providerId)
+    }
+    if (!provider && !modelNotAllowed) {
+      if (await this.deps.modelConfigProvider?.hasModelAllowlist?.()) {
+        modelNotAllowed = new AppError(
+          ErrorCode.CHAT_MODEL_NOT_ALLOWED,
+          'Choose a default model you are allowed to use'
+        )
+      } else {
+        provider = this.deps.providerRegistry.list()[0]
+      }
+    }
+
+    // Get available tools from registry
+    const toolRegistry = this.deps.toolRegistry
+    const tools = toolRegistry?.getToolDefinitions() ?? []
+
+    // Condense older interactions before the new one is added if the prompt would not fit
+    if (provider) {
+      await this.compactHistoryIfNeeded({
+        provider,
+        modelConfig,
+        systemPrompt: providerSystemPrompt,
+        pendingMessages: [
+          {
+            type: 'user',
+            text: queueText,
+            attachments: job.attachments,
+            metadata: { createdAt: new Date().toISOString() },
+          },
+        ],
+        tools,
+        queueId: job.id,
+        branch: replaced !== undefined,
+      })
+    }
+
     // Create new interaction
     const interaction = this.conversationService.createInteraction(this._conversation!.id)
+    if (replaced) {
+      interaction.parentId = this._loadedInteractions[0]?.id ?? null
+      // A context summary saved above comes in between, so there is nothing to switch to
+      if (interaction.parentId === branchParentId) {
+        interaction.siblingIds = [...(replaced.siblingIds ?? [replaced.id]), interaction.id]
+      }
+    }
+    if (provider) {
+      interaction.metadata.providerId = provider.id
+      interaction.metadata.modelId = modelConfig?.modelId
+    }
 
     if (job.context === 'settings-update') {
       const infoMessage = getPromptUpdateInfo(this.deps.settingsStore)
@@ -475,28 +727,19 @@ export class ChatOrchestrator {
           createdAt: new Date().toISOString(),
           systemPrompt: true,
           systemPromptBase: systemPrompt,
-          systemPromptContext: job.context ?? (isConversationStart ? 'conversation-start' : 'update'),
+          systemPromptContext:
+            job.context ?? (isConversationStart || isBranchStart ? 'conversation-start' : 'update'),
         },
       }
       this.conversationService.addMessage(interaction, instructionMessage)
     }
 
-    let queueText = job.text
-    if (job.role === 'instruction' && !queueText.trim()) {
-      if (job.context === 'conversation-start') {
-        queueText = getGreetingInstruction(this.deps.settingsStore)
-      } else if (job.context === 'settings-update') {
-        queueText = getPromptUpdateInstruction(this.deps.settingsStore)
-      }
-    }
-
     // Add user or instruction message
-    const messageType = job.role === 'instruction' ? 'instruction' : 'user'
-    const userMessage: Message = {
-      type: messageType,
-      text: queueText,
-      metadata: { createdAt: new Date().toISOString() },
-    }
+    const metadata = { createdAt: new Date().toISOString() }
+    const userMessage: Message =
+      job.role === 'instruction'
+        ? { type: 'instruction', text: queueText, metadata }
+        : { type: 'user', text: queueText, attachments: job.attachments, metadata }
     this.conversationService.addMessage(interaction, userMessage)
 
     this._currentInteraction = interaction
@@ -507,6 +750,7 @@ export class ChatOrchestrator {
       conversationId: interaction.conversationId,
       role: job.role,
       text: queueText,
+      attachments: job.attachments,
       systemPrompt: includeSystemPrompt ? systemPromptMessageText : undefined,
       informationMessages:
         interaction.informationMessages.length > 0 ? interaction.informationMessages : undefined,
@@ -528,21 +772,8 @@ export class ChatOrchestrator {
       }
     }
 
-    // Get model configuration if available
-    const modelConfig = await this.deps.modelConfigProvider?.getDefault()
-
-    // Get provider - use from modelConfig if available, otherwise first available
-    let provider
-    if (modelConfig) {
-      provider = this.deps.providerRegistry.get(modelConfig.providerId)
-    }
-    if (!provider) {
-      const providers = this.deps.providerRegistry.list()
-      provider = providers[0]
-    }
-
     if (!provider) {
-      this._error = new Error('No AI provider available')
+      this._error = modelNotAllowed ?? new Error('No AI provider available')
       this._isStreaming = false
       this.emitStateChange()
       this.emitEvent({ type: 'stream-error', error: this._error, queueId: job.id })
@@ -552,11 +783,8 @@ export class ChatOrchestrator {
     // Build message history and send
     const messages = this.buildMessageHistory()
 
-    // Get available tools from registry
-    const toolRegistry = this.deps.toolRegistry
-    const tools = toolRegistry?.getToolDefinitions() ?? []
-
     // Build options with model settings and tools
+    const attachmentStore = this.deps.attachmentStore
     const sendOptions = {
       modelId: modelConfig?.modelId,
       settings: modelConfig?.settingsOverride,
@@ -580,6 +808,9 @@ export class ChatOrchestrator {
           )
         : undefined,
       getToolDisplayName: this.deps.getToolDisplayName,
+      loadAttachment: attachmentStore
+        ? (attachment: MessageAttachment) => attachmentStore.get(attachment.id)
+        : undefined,
     }
 
     const streamToken = (this.streamToken += 1)
@@ -591,7 +822,7 @@ export class ChatOrchestrator {
     const sendPromise = provider
       .sendMessage(
         messages,
-        systemPrompt,
+        providerSystemPrompt,
         (event) => {
           if (this.activeStreamToken !== streamToken) return
           this.streamService.handleStreamEvent(event)
@@ -611,6 +842,271 @@ export class ChatOrchestrator {
     this.activeAbortGate = null
   }
 
+  /**
+   * Condense older interactions into a stored summary when the estimated prompt
+   * exceeds the model's context budget. Failures are logged and the request is
+   * sent with the full history.
+   */
+  private async compactHistoryIfNeeded(params: {
+    provider: AIProvider
+    modelConfig: ChatModelConfig | null | undefined
+    systemPrompt: string
+    pendingMessages: Message[]
+    tools: ResolvedToolDefinition[]
+    queueId: string
+    /** Whether the history is a new branch that the summary must continue */
+    branch?: boolean
+  }): Promise<void> {
+    if (!this._conversation) return
+
+    const tokenLimit = resolveCompactionLimit(params.modelConfig?.contextLength, this.contextBudget)
+    if (tokenLimit === null) return
+
+    const compactionState = getContextCompactionState(this._conversation)
+    const revertedSummaryIds = new Set(compactionState.revertedSummaryIds)
+    const chronological = [...this._loadedInteractions].reverse()
+    const estimate = (history: Interaction[]) =>
+      estimatePromptTokens({
+        systemPrompt: params.systemPrompt,
+        messages: [...buildContextMessages(history, revertedSummaryIds), ...params.pendingMessages],
+        tools: params.tools,
+      })
+    const estimatedTokens = estimate(chronological)
+    if (estimatedTokens <= tokenLimit) return
+
+    // After a revert, wait until the newer interactions exceed the budget on their own
+    const { pausedAfterInteractionId } = compactionState
+    if (
+      pausedAfterInteractionId &&
+      estimate(getInteractionsAfter(chronological, pausedAfterInteractionId)) <= tokenLimit
+    ) {
+      return
+    }
+
+    const toCompact = selectInteractionsToCompact(
+      chronological,
+      revertedSummaryIds,
+      this.contextBudget.keepRecentInteractions
+    )
+    if (toCompact.length === 0) return
+
+    let summary: { text: string; usage?: TokenUsage }
+    try {
+      summary = await this.summarizeInteractions(
+        params.provider,
+        params.modelConfig,
+        buildContextMessages(toCompact, revertedSummaryIds)
+      )
+    } catch (err) {
+      console.warn('Context compaction failed, sending full history:', err)
+      return
+    }
+    if (!summary.text.trim()) return
+
+    const summarizedInteractionIds = toCompact.map((interaction) => interaction.id)
+    const createdAt = new Date().toISOString()
+    const summaryInteraction = this.conversationService.createInteraction(this._conversation.id)
+    summaryInteraction.metadata.providerId = params.provider.id
+    summaryInteraction.metadata.modelId = params.modelConfig?.modelId
+    summaryInteraction.metadata.usage = summary.usage
+    if (params.branch) {
+      summaryInteraction.parentId = this._loadedInteractions[0]?.id ?? null
+    }
+    summaryInteraction.informationMessages.push({
+      type: 'information',
+      text: getContextSummaryInfo(this.deps.settingsStore),
+      metadata: { createdAt },
+    })
+    this.conversationService.addMessage(summaryInteraction, {
+      type: 'instruction',
+      text: `${getContextSummaryPrefix(this.deps.settingsStore)}\n\n${summary.text.trim()}`,
+      metadata: {
+        createdAt,
+        [CONTEXT_SUMMARY_METADATA_KEY]: true,
+        summarizedInteractionIds,
+        estimatedTokens,
+        tokenLimit,
+      },
+    })
+    this.conversationService.markCompleted(summaryInteraction)
+
+    await this.repository.saveInteraction(summaryInteraction)
+    this._loadedInteractions.unshift(summaryInteraction)
+    this._totalInteractionsCount += 1
+    if (pausedAfterInteractionId) {
+      await this.saveContextCompactionState({
+        revertedSummaryIds: compactionState.revertedSummaryIds,
+      })
+    }
+
+    this.emitEvent({
+      type: 'interaction-saved',
+      interaction: summaryInteraction,
+      queueId: params.queueId,
+    })
+    this.emitEvent({
+      type: 'context-compacted',
+      interactionId: summaryInteraction.id,
+      summarizedInteractionIds,
+      estimatedTokens,
+      tokenLimit,
+      queueId: params.queueId,
+    })
+    this.emitStateChange()
+  }
+
+  /**
+   * Ask the provider for a summary of the given messages.
+   * Tools are not offered, so the provider answers with plain text.
+   */
+  private async summarizeInteractions(
+    provider: AIProvider,
+    modelConfig: ChatModelConfig | null | undefined,
+    messages: Message[]
+  ): Promise<{ text: string; usage?: TokenUsage }> {
+    const request: Message = {
+      type: 'user',
+      text: getContextSummaryRequest(this.deps.settingsStore),
+      metadata: { createdAt: new Date().toISOString() },
+    }
+    const summaryOptions = {
+      modelId: modelConfig?.modelId,
+      settings: modelConfig?.settingsOverride,
+      context: { userId: this.deps.userId },
+    }
+
+    let text = ''
+    let usage: TokenUsage | undefined
+    let streamError = null as Error | null
+    await provider.sendMessage(
+      [...messages, request],
+      getContextSummarySystemPrompt(this.deps.settingsStore),
+      (event) => {
+        if (event.type === 'content') {
+          text += event.text
+        } else if (event.type === 'done') {
+          usage = event.usage
+        } else if (event.type === 'error') {
+          streamError = event.error
+        }
+      },
+      summaryOptions
+    )
+
+    if (streamError) {
+      throw streamError
+    }
+    return { text, usage }
+  }
+
+  /**
+   * Recall long-term memories relevant to a user message and format them as a
+   * prompt chunk. Failures are logged and the message is sent without memories.
+   */
+  private async getRelevantMemoryPrompt(text: string): Promise<string> {
+    if (!this.deps.memory) return ''
+
+    try {
+      const matches = await this.deps.memory.recall(text)
+      return getMemoryPrompt(
+        matches.map((match) => match.memory.content),
+        this.deps.settingsStore
+      )
+    } catch (error) {
+      console.warn('Failed to recall memories:', error)
+      return ''
+    }
+  }
+
+  /**
+   * Ask a model for a conversation title after the first completed interaction
+   * with a user message. The title is saved and published as a
+   * conversation-title-updated event. Failures are logged and ignored.
+   */
+  private async generateTitleIfNeeded(interaction: Interaction): Promise<void> {
+    if (!this.autoTitle || interaction.error || interaction.aborted) return
+
+    const conversationId = interaction.conversationId
+    const conversation = this._conversation
+    if (conversation?.id !== conversationId || conversation.title?.trim()) return
+    if (this.titledConversationIds.has(conversationId)) return
+
+    const sourceMessages = getTitleSourceMessages(interaction)
+    if (sourceMessages.length === 0) return
+    this.titledConversationIds.add(conversationId)
+
+    try {
+      const modelConfig =
+        (await ignoreModelNotAllowed(this.deps.modelConfigProvider?.getTitleModel?.())) ??
+        (await this.deps.modelConfigProvider?.getDefault())
+      let provider = modelConfig
+        ? this.deps.providerRegistry.get(modelConfig.providerId)
+        : undefined
+      if (!provider && !(await this.deps.modelConfigProvider?.hasModelAllowlist?.())) {
+        provider = this.deps.providerRegistry.list()[0]
+      }
+      if (!provider) return
+
+      const title = cleanGeneratedTitle(
+        await this.requestTitle(provider, modelConfig, sourceMessages)
+      )
+      if (!title) return
+
+      await this.repository.updateConversationTitle(conversationId, title)
+
+      const event: OrchestratorEvent = { type: 'conversation-title-updated', conversationId, title }
+      if (this._conversation?.id === conversationId) {
+        this._conversation.title = title
+        this.emitEvent(event)
+        this.emitStateChange()
+      } else {
+        // The user has moved on to another conversation; only notify its observers
+        this.deps.eventBus?.publish(conversationId, event)
+      }
+    } catch (err) {
+      console.warn('Conversation title generation failed:', err)
+    }
+  }
+
+  /**
+   * Ask the provider for a title for the given messages.
+   */
+  private async requestTitle(
+    provider: AIProvider,
+    modelConfig: ChatModelConfig | null | undefined,
+    messages: Message[]
+  ): Promise<string> {
+    const request: Message = {
+      type: 'user',
+      text: getConversationTitleRequest(this.deps.settingsStore),
+      metadata: { createdAt: new Date().toISOString() },
+    }
+
+    let text = ''
+    let streamError = null as Error | null
+    await provider.sendMessage(
+      [...messages, request],
+      getConversationTitleSystemPrompt(this.deps.settingsStore),
+      (event) => {
+        if (event.type === 'content') {
+          text += event.text
+        } else if (event.type === 'error') {
+          streamError = event.error
+        }
+      },
+      {
+        modelId: modelConfig?.modelId,
+        settings: modelConfig?.settingsOverride,
+        context: { userId: this.deps.userId },
+      }
+    )
+
+    if (streamError) {
+      throw streamError
+    }
+    return text
+  }
+
   /**
    * Abort current streaming interaction
    */
@@ -690,6 +1186,88 @@ export class ChatOrchestrator {
     this.emitStateChange()
   }
 
+  /**
+   * Revert a context summary so the interactions it replaced are sent in full again.
+   * Automatic compaction pauses until the interactions after the revert exceed the
+   * context budget on their own, otherwise the next message would summarize them again.
+   * @returns true if the summary was found and reverted
+   */
+  async revertContextCompaction(summaryInteractionId: string): Promise<boolean> {
+    if (!this._conversation) return false
+
+    const summaryInteraction = await this.repository.getInteraction(
+      this._conversation.id,
+      summaryInteractionId
+    )
+    if (!summaryInteraction || !getContextSummary(summaryInteraction)) {
+      return false
+    }
+
+    const state = getContextCompactionState(this._conversation)
+    if (!state.revertedSummaryIds.includes(summaryInteractionId)) {
+      state.revertedSummaryIds.push(summaryInteractionId)
+    }
+    await this.saveContextCompactionState({
+      pausedAfterInteractionId: this._loadedInteractions[0]?.id ?? summaryInteractionId,
+      revertedSummaryIds: state.revertedSummaryIds,
+    })
+
+    this.emitEvent({ type: 'context-compaction-reverted', interactionId: summaryInteractionId })
+    this.emitStateChange()
+    return true
+  }
+
+  /**
+   * Store the context compaction state in the conversation metadata.
+   */
+  private async saveContextCompactionState(state: ContextCompactionState): Promise<void> {
+    if (!this._conversation) return
+
+    this._conversation.metadata = {
+      ...this._conversation.metadata,
+      [CONTEXT_COMPACTION_METADATA_KEY]: state,
+    }
+    await this.repository.updateConversationMetadata(
+      this._conversation.id,
+      this._conversation.metadata
+    )
+  }
+
+  /**
+   * Replace the conversation's overrides of the user's model and personality settings.
+   * Empty values fall back to the user's settings. A changed personality or
+   * additional prompt is sent to the model with the next message.
+   * @returns The overrides that were stored
+   * @throws Error if no conversation is loaded or an override is invalid
+   */
+  async setConversationOverrides(input: unknown): Promise<ConversationOverrides> {
+    if (!this._conversation) {
+      throw new Error('No conversation loaded')
+    }
+
+    const overrides = normalizeConversationOverrides(input)
+    if (overrides.modelConfigId) {
+      const modelConfig = await this.deps.modelConfigProvider?.get?.(overrides.modelConfigId)
+      if (modelConfig === null) {
+        throw new Error(`Model config ${overrides.modelConfigId} not found`)
+      }
+    }
+
+    const { [CONVERSATION_OVERRIDES_METADATA_KEY]: _previous, ...metadata } =
+      this._conversation.metadata
+    this._conversation.metadata =
+      Object.keys(overrides).length > 0
+        ? { ...metadata, [CONVERSATION_OVERRIDES_METADATA_KEY]: overrides }
+        : metadata
+    await this.repository.updateConversationMetadata(
+      this._conversation.id,
+      this._conversation.metadata
+    )
+
+    this.emitStateChange()
+    return overrides
+  }
+
   /**
    * Cleanup resources
    */
@@ -735,6 +1313,9 @@ export class ChatOrchestrator {
         resetStreamingState: () => this.resetStreamingState(),
         addMessage: (interaction, message) =>
           this.conversationService.addMessage(interaction, message),
+        onInteractionCompleted: (interaction) => {
+          void this.generateTitleIfNeeded(interaction)
+        },
       }
     )
   }
@@ -766,6 +1347,28 @@ export class ChatOrchestrator {
     }
   }
 
+  /**
+   * Get the model config for a message: the conversation's model if it still
+   * exists and the user may use it, otherwise the user's default, with the
+   * temperature override applied.
+   * @throws AppError with CHAT_MODEL_NOT_ALLOWED if the user may not use the default
+   */
+  private async resolveModelConfig(
+    overrides: ConversationOverrides
+  ): Promise<ChatModelConfig | null | undefined> {
+    const provider = this.deps.modelConfigProvider
+    const modelConfig =
+      (overrides.modelConfigId
+        ? await ignoreModelNotAllowed(provider?.get?.(overrides.modelConfigId))
+        : null) ?? (await provider?.getDefault())
+
+    if (!modelConfig || overrides.temperature === undefined) return modelConfig
+    return {
+      ...modelConfig,
+      settingsOverride: { ...modelConfig.settingsOverride, temperature: overrides.temperature },
+    }
+  }
+
   private resolveStoredSystemPrompt(conversation: Conversation | null): string | null {
     if (!conversation) return null
     const stored = conversation.metadata?.['systemPrompt']
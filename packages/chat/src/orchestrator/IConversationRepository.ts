@@ -1,4 +1,5 @@
 import type { Conversation, Interaction } from '../types/index.js'
+import type { InteractionNode } from './branches.js'
 
 /**
  * Platform-neutral repository interface for conversation persistence.
@@ -15,7 +16,9 @@ export interface IConversationRepository {
   saveConversation(conversation: Conversation): Promise<void>
 
   /**
-   * Save an interaction to the database
+   * Save an interaction to the database.
+   * Without a parentId it continues the active branch; either way it becomes
+   * the end of the active branch.
    */
   saveInteraction(interaction: Interaction): Promise<void>
 
@@ -30,8 +33,8 @@ export interface IConversationRepository {
   getLatestActiveConversation(): Promise<Conversation | null>
 
   /**
-   * Get interactions for a conversation with pagination
-   * Returns interactions ordered by createdAt DESC (newest first)
+   * Get interactions of the conversation's active branch with pagination
+   * Returns interactions newest first
    */
   getConversationInteractions(
     conversationId: string,
@@ -40,10 +43,25 @@ export interface IConversationRepository {
   ): Promise<Interaction[]>
 
   /**
-   * Count total interactions for a conversation
+   * Count interactions in the conversation's active branch
    */
   countConversationInteractions(conversationId: string): Promise<number>
 
+  /**
+   * Get an interaction of the conversation, on any branch
+   */
+  getInteraction(conversationId: string, interactionId: string): Promise<Interaction | null>
+
+  /**
+   * Get the position of every interaction in a conversation, oldest first
+   */
+  getInteractionTree(conversationId: string): Promise<InteractionNode[]>
+
+  /**
+   * Show the branch ending at the given interaction
+   */
+  setActiveInteraction(conversationId: string, interactionId: string): Promise<void>
+
   /**
    * Archive a conversation (set active = false)
    */
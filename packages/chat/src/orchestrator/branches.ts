@@ -0,0 +1,93 @@
+/**
+ * Conversation branches.
+ *
+ * Interactions form a tree: each one follows a parent interaction, and editing
+ * or regenerating a message adds a sibling next to the original. The
+ * conversation shows one branch at a time, from its first interaction down to
+ * the active interaction. These helpers walk that tree.
+ */
+
+import type { Interaction, InstructionMessage, UserMessage } from '../types/index.js'
+import { isContextSummaryMessage } from './contextBudget.js'
+
+/** Position of an interaction in the conversation tree */
+export interface InteractionNode {
+  id: string
+  /** Interaction this one follows (null = first interaction of a branch) */
+  parentId: string | null
+}
+
+/**
+ * Get the branch ending at the given interaction, newest first.
+ * Falls back to the newest interaction when `leafId` is missing or unknown.
+ * @param nodes - All interactions of the conversation, oldest first.
+ * @param leafId - Last interaction of the branch.
+ */
+export function getBranchPath(nodes: InteractionNode[], leafId?: string | null): string[] {
+  const byId = new Map(nodes.map((node) => [node.id, node]))
+  let node = (leafId ? byId.get(leafId) : undefined) ?? nodes[nodes.length - 1]
+
+  const path: string[] = []
+  const seen = new Set<string>()
+  while (node && !seen.has(node.id)) {
+    path.push(node.id)
+    seen.add(node.id)
+    node = node.parentId ? byId.get(node.parentId) : undefined
+  }
+  return path
+}
+
+/**
+ * Get the interactions that share the given interaction's parent, including
+ * itself, oldest first.
+ * @param nodes - All interactions of the conversation, oldest first.
+ */
+export function getSiblingIds(nodes: InteractionNode[], id: string): string[] {
+  const node = nodes.find((candidate) => candidate.id === id)
+  if (!node) return []
+  return nodes.filter((candidate) => candidate.parentId === node.parentId).map((n) => n.id)
+}
+
+/**
+ * Get the last interaction of the newest branch that passes through the given
+ * interaction, by following the newest child at each step.
+ * @param nodes - All interactions of the conversation, oldest first.
+ */
+export function getNewestLeaf(nodes: InteractionNode[], id: string): string {
+  const newestChild = new Map<string, string>()
+  for (const node of nodes) {
+    if (node.parentId) newestChild.set(node.parentId, node.id)
+  }
+
+  let leafId = id
+  const seen = new Set<string>([id])
+  let childId = newestChild.get(leafId)
+  while (childId && !seen.has(childId)) {
+    leafId = childId
+    seen.add(childId)
+    childId = newestChild.get(leafId)
+  }
+  return leafId
+}
+
+/**
+ * Get the message that started an interaction, so it can be sent again:
+ * the user's message, or the instruction for interactions Stina started herself.
+ * Returns null for interactions that cannot be regenerated, such as context summaries.
+ */
+export function getResendableMessage(
+  interaction: Interaction
+): UserMessage | InstructionMessage | null {
+  const userMessage = interaction.messages.find(
+    (message): message is UserMessage => message.type === 'user'
+  )
+  if (userMessage) return userMessage
+
+  const instruction = interaction.messages.find(
+    (message): message is InstructionMessage =>
+      message.type === 'instruction' &&
+      message.metadata?.['systemPrompt'] !== true &&
+      !isContextSummaryMessage(message)
+  )
+  return instruction ?? null
+}
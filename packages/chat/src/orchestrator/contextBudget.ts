@@ -0,0 +1,261 @@
+/**
+ * Context window budgeting for ChatOrchestrator.
+ *
+ * Estimates how large a prompt is compared to the model's context window and
+ * decides which older interactions should be condensed into a stored summary.
+ * Summaries are regular instruction messages, so the original interactions are
+ * never modified and a summary can be reverted by marking it in the
+ * conversation metadata. After a revert, compaction pauses until the newer
+ * interactions fill the budget on their own.
+ */
+
+import type { ResolvedToolDefinition } from '../tools/ToolRegistry.js'
+import type { Conversation, Interaction, Message } from '../types/index.js'
+
+/** Average number of characters per token for mixed natural-language text */
+const CHARS_PER_TOKEN = 4
+
+/** Tokens added per message for role markers and separators */
+const MESSAGE_OVERHEAD_TOKENS = 4
+
+/** Share of the context window the prompt may use before compaction starts */
+export const DEFAULT_COMPACTION_THRESHOLD = 0.75
+
+/** Number of most recent interactions that are always kept verbatim */
+export const DEFAULT_KEEP_RECENT_INTERACTIONS = 2
+
+/** Message metadata key marking an instruction message as a context summary */
+export const CONTEXT_SUMMARY_METADATA_KEY = 'contextSummary'
+
+/** Conversation metadata key holding the compaction state */
+export const CONTEXT_COMPACTION_METADATA_KEY = 'contextCompaction'
+
+/**
+ * Options controlling when and how history is compacted
+ */
+export interface ContextBudgetOptions {
+  /** Share of the context window (0-1) that triggers compaction. Default 0.75 */
+  threshold?: number
+  /** Number of most recent interactions never included in a summary. Default 2 */
+  keepRecentInteractions?: number
+  /**
+   * Context length used when the model config does not specify one.
+   * When neither is known, no compaction is done.
+   */
+  defaultContextLength?: number
+}
+
+/**
+ * Compaction state stored in conversation metadata
+ */
+export interface ContextCompactionState {
+  /**
+   * Newest interaction when a summary was last reverted. Compaction waits until
+   * the interactions after it exceed the budget, so the restored history is not
+   * summarized again by the next message.
+   */
+  pausedAfterInteractionId?: string
+  /** IDs of summary interactions that have been reverted */
+  revertedSummaryIds: string[]
+}
+
+/**
+ * A context summary found in an interaction
+ */
+export interface ContextSummary {
+  interactionId: string
+  message: Message
+  summarizedInteractionIds: string[]
+}
+
+/**
+ * Estimate the number of tokens in a text.
+ * This is a heuristic; providers do not expose their tokenizers.
+ */
+export function estimateTextTokens(text: string): number {
+  if (!text) return 0
+  return Math.ceil(text.length / CHARS_PER_TOKEN)
+}
+
+/**
+ * Estimate the number of tokens a message contributes to the prompt.
+ * Only message types that are sent to providers are counted.
+ */
+export function estimateMessageTokens(message: Message): number {
+  switch (message.type) {
+    case 'user':
+    case 'stina':
+    case 'instruction':
+      return estimateTextTokens(message.text) + MESSAGE_OVERHEAD_TOKENS
+    default:
+      return 0
+  }
+}
+
+/**
+ * Estimate the total prompt size for a request.
+ */
+export function estimatePromptTokens(input: {
+  systemPrompt: string
+  messages: Message[]
+  tools?: ResolvedToolDefinition[]
+}): number {
+  let total = estimateTextTokens(input.systemPrompt)
+  for (const message of input.messages) {
+    total += estimateMessageTokens(message)
+  }
+  if (input.tools && input.tools.length > 0) {
+    total += estimateTextTokens(JSON.stringify(input.tools))
+  }
+  return total
+}
+
+/**
+ * Resolve the token limit at which compaction starts.
+ * @returns The limit, or null when the context length is unknown.
+ */
+export function resolveCompactionLimit(
+  contextLength: number | undefined,
+  options: ContextBudgetOptions = {}
+): number | null {
+  const length = contextLength ?? options.defaultContextLength
+  if (!length || length <= 0) return null
+
+  const threshold = options.threshold ?? DEFAULT_COMPACTION_THRESHOLD
+  return Math.floor(length * threshold)
+}
+
+/**
+ * Read the compaction state from conversation metadata.
+ */
+export function getContextCompactionState(conversation: Conversation | null): ContextCompactionState {
+  const stored = conversation?.metadata?.[CONTEXT_COMPACTION_METADATA_KEY] as
+    | Partial<ContextCompactionState>
+    | undefined
+
+  return {
+    pausedAfterInteractionId:
+      typeof stored?.pausedAfterInteractionId === 'string'
+        ? stored.pausedAfterInteractionId
+        : undefined,
+    revertedSummaryIds: Array.isArray(stored?.revertedSummaryIds)
+      ? stored.revertedSummaryIds.filter((id): id is string => typeof id === 'string')
+      : [],
+  }
+}
+
+/**
+ * Get the interactions that came after the given one.
+ * All interactions are returned when it is not among them, since it is then older
+ * than the loaded history or on another branch.
+ * @param chronological - Interactions, oldest first.
+ */
+export function getInteractionsAfter(
+  chronological: Interaction[],
+  interactionId: string
+): Interaction[] {
+  const index = chronological.findIndex((interaction) => interaction.id === interactionId)
+  return chronological.slice(index + 1)
+}
+
+/**
+ * Check if a message is a stored context summary.
+ */
+export function isContextSummaryMessage(message: Message): boolean {
+  return message.type === 'instruction' && message.metadata?.[CONTEXT_SUMMARY_METADATA_KEY] === true
+}
+
+/**
+ * Find the context summary stored in an interaction, if any.
+ */
+export function getContextSummary(interaction: Interaction): ContextSummary | null {
+  const message = interaction.messages.find(isContextSummaryMessage)
+  if (!message) return null
+
+  const ids = message.metadata['summarizedInteractionIds']
+  return {
+    interactionId: interaction.id,
+    message,
+    summarizedInteractionIds: Array.isArray(ids)
+      ? ids.filter((id): id is string => typeof id === 'string')
+      : [],
+  }
+}
+
+/**
+ * Collect the IDs of interactions replaced by active (non-reverted) summaries.
+ */
+function getSummarizedInteractionIds(
+  interactions: Interaction[],
+  revertedSummaryIds: ReadonlySet<string>
+): Set<string> {
+  const summarized = new Set<string>()
+  for (const interaction of interactions) {
+    if (revertedSummaryIds.has(interaction.id)) continue
+    const summary = getContextSummary(interaction)
+    if (!summary) continue
+    for (const id of summary.summarizedInteractionIds) {
+      summarized.add(id)
+    }
+  }
+  return summarized
+}
+
+/**
+ * Build the provider message history from chronological interactions.
+ * Interactions covered by an active summary are replaced by that summary,
+ * which is placed before the remaining interactions.
+ * Errored interactions, system prompt messages and reverted summaries are skipped.
+ */
+export function buildContextMessages(
+  chronological: Interaction[],
+  revertedSummaryIds: ReadonlySet<string> = new Set()
+): Message[] {
+  const isSystemPromptMessage = (message: Message): boolean =>
+    message.type === 'instruction' && message.metadata?.['systemPrompt'] === true
+
+  const candidates = chronological.filter((interaction) => !interaction.error)
+  const summarized = getSummarizedInteractionIds(candidates, revertedSummaryIds)
+
+  const summaryMessages: Message[] = []
+  const messages: Message[] = []
+
+  for (const interaction of candidates) {
+    if (summarized.has(interaction.id)) continue
+
+    const reverted = revertedSummaryIds.has(interaction.id)
+    for (const message of interaction.messages) {
+      if (isSystemPromptMessage(message)) continue
+      if (isContextSummaryMessage(message)) {
+        if (!reverted) summaryMessages.push(message)
+        continue
+      }
+      messages.push(message)
+    }
+  }
+
+  return [...summaryMessages, ...messages]
+}
+
+/**
+ * Select the interactions that should be condensed into a new summary.
+ * Active summaries are included so that the new summary supersedes them.
+ * @returns Chronological interactions to summarize (empty when nothing can be compacted).
+ */
+export function selectInteractionsToCompact(
+  chronological: Interaction[],
+  revertedSummaryIds: ReadonlySet<string> = new Set(),
+  keepRecentInteractions = DEFAULT_KEEP_RECENT_INTERACTIONS
+): Interaction[] {
+  const candidates = chronological.filter(
+    (interaction) => !interaction.error && !revertedSummaryIds.has(interaction.id)
+  )
+  const summarized = getSummarizedInteractionIds(candidates, revertedSummaryIds)
+  const active = candidates.filter((interaction) => !summarized.has(interaction.id))
+
+  const selected = active.slice(0, Math.max(0, active.length - keepRecentInteractions))
+
+  // A lone summary has nothing new to condense
+  const hasNewContent = selected.some((interaction) => !getContextSummary(interaction))
+  return hasNewContent ? selected : []
+}
@@ -0,0 +1,96 @@
+/**
+ * Conversation overrides.
+ *
+ * The model and personality normally come from the user's settings and apply
+ * to every conversation. A conversation can override them, for example to use
+ * a local model for private notes while another conversation uses a cloud model.
+ */
+
+import type { Conversation } from '../types/index.js'
+import { isPersonalityPreset, type PersonalityPreset } from '../constants/index.js'
+
+/** Conversation metadata key holding the overrides */
+export const CONVERSATION_OVERRIDES_METADATA_KEY = 'overrides'
+
+/** Highest temperature accepted as an override */
+export const MAX_OVERRIDE_TEMPERATURE = 2
+
+/**
+ * Settings a conversation uses instead of the user's
+ */
+export interface ConversationOverrides {
+  /** Model config used instead of the user's default model */
+  modelConfigId?: string
+  /** Sampling temperature sent to the provider */
+  temperature?: number
+  /** Personality preset used instead of the user's */
+  personalityPreset?: PersonalityPreset
+  /** Extra text added to the end of the system prompt */
+  additionalPrompt?: string
+}
+
+/**
+ * Get the overrides stored on a conversation.
+ * Values that are no longer valid are left out.
+ */
+export function getConversationOverrides(conversation: Conversation | null): ConversationOverrides {
+  const stored = conversation?.metadata?.[CONVERSATION_OVERRIDES_METADATA_KEY] as
+    | Record<string, unknown>
+    | undefined
+  if (!stored || typeof stored !== 'object') return {}
+
+  try {
+    return normalizeConversationOverrides(stored)
+  } catch {
+    return {}
+  }
+}
+
+/**
+ * Validate overrides received from a client.
+ * Empty values are left out, so they fall back to the user's settings.
+ * @throws Error if a value has the wrong type or is out of range
+ */
+export function normalizeConversationOverrides(input: unknown): ConversationOverrides {
+  if (input === null || input === undefined) return {}
+  if (typeof input !== 'object' || Array.isArray(input)) {
+    throw new Error('Overrides must be an object')
+  }
+
+  const { modelConfigId, temperature, personalityPreset, additionalPrompt } = input as Record<
+    string,
+    unknown
+  >
+  const overrides: ConversationOverrides = {}
+
+  if (modelConfigId !== undefined && modelConfigId !== null && modelConfigId !== '') {
+    if (typeof modelConfigId !== 'string') throw new Error('modelConfigId must be a string')
+    overrides.modelConfigId = modelConfigId
+  }
+
+  if (temperature !== undefined && temperature !== null) {
+    if (
+      typeof temperature !== 'number' ||
+      !Number.isFinite(temperature) ||
+      temperature < 0 ||
+      temperature > MAX_OVERRIDE_TEMPERATURE
+    ) {
+      throw new Error(`temperature must be a number from 0 to ${MAX_OVERRIDE_TEMPERATURE}`)
+    }
+    overrides.temperature = temperature
+  }
+
+  if (personalityPreset !== undefined && personalityPreset !== null && personalityPreset !== '') {
+    if (!isPersonalityPreset(personalityPreset)) {
+      throw new Error(`Unknown personality preset: ${String(personalityPreset)}`)
+    }
+    overrides.personalityPreset = personalityPreset
+  }
+
+  if (additionalPrompt !== undefined && additionalPrompt !== null) {
+    if (typeof additionalPrompt !== 'string') throw new Error('additionalPrompt must be a string')
+    if (additionalPrompt.trim()) overrides.additionalPrompt = additionalPrompt.trim()
+  }
+
+  return overrides
+}
@@ -0,0 +1,62 @@
+/**
+ * Automatic conversation titles.
+ *
+ * After the first completed interaction with a user message, the orchestrator
+ * asks a model for a short title. These helpers pick the messages the title is
+ * based on and clean up the model's answer.
+ */
+
+import type { Interaction, Message, StinaMessage, UserMessage } from '../types/index.js'
+
+/** Maximum length of a generated title, in characters */
+export const MAX_TITLE_LENGTH = 80
+
+/** Maximum length of each message sent to the model when asking for a title */
+const MAX_SOURCE_MESSAGE_LENGTH = 2000
+
+/**
+ * Get the messages a title should be based on: the user's message and Stina's
+ * answer, without system prompts, thinking, tools or error messages.
+ * Returns an empty array when the interaction has no user message.
+ */
+export function getTitleSourceMessages(interaction: Interaction): Message[] {
+  const messages = interaction.messages.filter(
+    (message): message is UserMessage | StinaMessage =>
+      (message.type === 'user' || message.type === 'stina') &&
+      message.metadata?.['isError'] !== true &&
+      message.text.trim().length > 0
+  )
+  if (!messages.some((message) => message.type === 'user')) return []
+
+  return messages.map((message) => ({
+    ...message,
+    text:
+      message.text.length > MAX_SOURCE_MESSAGE_LENGTH
+        ? `${message.text.slice(0, MAX_SOURCE_MESSAGE_LENGTH)}…`
+        : message.text,
+  }))
+}
+
+/**
+ * Clean up a title suggested by a model.
+ * Keeps the first non-empty line and strips labels, quotes and Markdown.
+ * @returns The title, or null if nothing usable remains
+ */
+export function cleanGeneratedTitle(text: string): string | null {
+  const line = text
+    .split('\n')
+    .map((part) => part.trim())
+    .find((part) => part.length > 0)
+  if (!line) return null
+
+  const title = line
+    .replace(/^#+\s*/, '')
+    .replace(/^(title|titel)\s*:\s*/i, '')
+    .replace(/^[*_`]+|[*_`]+$/g, '')
+    .replace(/^["'“”‘’«»]+|["'“”‘’«»]+$/g, '')
+    .replace(/\.+$/, '')
+    .trim()
+  if (!title) return null
+
+  return title.length > MAX_TITLE_LENGTH ? `${title.slice(0, MAX_TITLE_LENGTH - 1).trimEnd()}…` : title
+}
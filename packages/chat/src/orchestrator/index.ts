@@ -12,3 +12,27 @@ export type {
   IModelConfigProvider,
 } from './types.js'
 export type { QueueState, QueuedMessageRole } from './ChatMessageQueue.js'
+export type { InteractionNode } from './branches.js'
+
+// Conversation overrides
+export {
+  getConversationOverrides,
+  normalizeConversationOverrides,
+  CONVERSATION_OVERRIDES_METADATA_KEY,
+  MAX_OVERRIDE_TEMPERATURE,
+} from './conversationOverrides.js'
+export type { ConversationOverrides } from './conversationOverrides.js'
+
+// Context budgeting
+export {
+  estimateTextTokens,
+  estimateMessageTokens,
+  estimatePromptTokens,
+  resolveCompactionLimit,
+  getContextCompactionState,
+  getContextSummary,
+  isContextSummaryMessage,
+  DEFAULT_COMPACTION_THRESHOLD,
+  DEFAULT_KEEP_RECENT_INTERACTIONS,
+} from './contextBudget.js'
+export type { ContextBudgetOptions, ContextCompactionState, ContextSummary } from './contextBudget.js'
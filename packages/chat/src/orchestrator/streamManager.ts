@@ -5,7 +5,7 @@
  * stream events into orchestrator events and state updates.
  */
 
-import type { Message, ToolCall, Interaction } from '../types/index.js'
+import type { Message, ToolCall, Interaction, TokenUsage } from '../types/index.js'
 import type { ChatStreamService } from '../services/ChatStreamService.js'
 import type { IConversationRepository } from './IConversationRepository.js'
 import type { OrchestratorEvent } from './types.js'
@@ -40,6 +40,8 @@ export interface StreamManagerCallbacks {
   emitStateChange: () => void
   resetStreamingState: () => void
   addMessage: (interaction: Interaction, message: Message) => void
+  /** Called after an interaction has completed without errors and been saved */
+  onInteractionCompleted: (interaction: Interaction) => void
 }
 
 /**
@@ -104,7 +106,7 @@ export function setupStreamListeners(
   streamService.on('tool-complete', toolCompleteHandler)
   listeners.push({ event: 'tool-complete', handler: toolCompleteHandler })
 
-  const streamCompleteHandler = async (finalMessages: Message[]) => {
+  const streamCompleteHandler = async (finalMessages: Message[], usage?: TokenUsage) => {
     const queueId = callbacks.getActiveQueueId()
     state.isStreaming = false
 
@@ -114,6 +116,10 @@ export function setupStreamListeners(
         callbacks.addMessage(state.currentInteraction!, msg)
       })
 
+      if (usage) {
+        state.currentInteraction.metadata.usage = usage
+      }
+
       // Save to repository
       await repository.saveInteraction(state.currentInteraction)
 
@@ -126,6 +132,7 @@ export function setupStreamListeners(
         interaction: state.currentInteraction,
         queueId,
       })
+      callbacks.onInteractionCompleted(state.currentInteraction)
 
       // Clear current interaction
       state.currentInteraction = null
@@ -133,7 +140,7 @@ export function setupStreamListeners(
 
     callbacks.resetStreamingState()
     callbacks.emitStateChange()
-    callbacks.emitEvent({ type: 'stream-complete', messages: finalMessages, queueId })
+    callbacks.emitEvent({ type: 'stream-complete', messages: finalMessages, usage, queueId })
   }
   streamService.on('stream-complete', streamCompleteHandler)
   listeners.push({ event: 'stream-complete', handler: streamCompleteHandler })
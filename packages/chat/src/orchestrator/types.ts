@@ -1,4 +1,12 @@
-import type { Conversation, Interaction, Message, ToolCall, InformationMessage } from '../types/index.js'
+import type {
+  Conversation,
+  Interaction,
+  Message,
+  ToolCall,
+  InformationMessage,
+  MessageAttachment,
+  TokenUsage,
+} from '../types/index.js'
 import type { SettingsStore } from '@stina/core'
 import type { IConversationRepository } from './IConversationRepository.js'
 import type { ProviderRegistry } from '../providers/ProviderRegistry.js'
@@ -6,6 +14,9 @@ import type { ToolRegistry } from '../tools/ToolRegistry.js'
 import type { QueueState, QueuedMessageRole } from './ChatMessageQueue.js'
 import type { ConversationEventBus } from '../events/index.js'
 import type { PendingConfirmationStore } from '../confirmations/index.js'
+import type { ContextBudgetOptions } from './contextBudget.js'
+import type { MemoryService } from '../memory/MemoryService.js'
+import type { IAttachmentStore } from '../attachments/types.js'
 
 /**
  * Model configuration for chat
@@ -14,14 +25,33 @@ export interface ChatModelConfig {
   providerId: string
   modelId: string
   settingsOverride?: Record<string, unknown>
+  /** Context window size in tokens, used for history compaction */
+  contextLength?: number
 }
 
 /**
- * Interface for fetching model configuration
+ * Interface for fetching model configuration.
+ * Throw an AppError with CHAT_MODEL_NOT_ALLOWED for model configs the user may not use;
+ * messages then fail instead of falling back to another provider.
  */
 export interface IModelConfigProvider {
   /** Get the default model configuration */
   getDefault(): Promise<ChatModelConfig | null>
+  /**
+   * Get a model configuration by ID, used for conversations that override the model.
+   * Return null if it does not exist. Without this method, overrides are ignored.
+   */
+  get?(modelConfigId: string): Promise<ChatModelConfig | null>
+  /**
+   * Get the model configuration used to generate conversation titles.
+   * Return null (or leave out) to use the default model.
+   */
+  getTitleModel?(): Promise<ChatModelConfig | null>
+  /**
+   * Whether the user may only use some model configs. Messages without a usable
+   * model config then fail instead of using the first provider.
+   */
+  hasModelAllowlist?(): Promise<boolean>
 }
 
 /**
@@ -38,7 +68,7 @@ export type OrchestratorEvent =
   | ({ type: 'tool-start'; name: string; displayName?: string; payload?: string } & OrchestratorEventContext)
   | ({ type: 'tool-complete'; tool: ToolCall } & OrchestratorEventContext)
   | ({ type: 'tool-confirmation-pending'; toolCallName: string; toolDisplayName?: string; toolPayload: string; confirmationPrompt: string } & OrchestratorEventContext)
-  | ({ type: 'stream-complete'; messages: Message[] } & OrchestratorEventContext)
+  | ({ type: 'stream-complete'; messages: Message[]; usage?: TokenUsage } & OrchestratorEventContext)
   | ({ type: 'stream-error'; error: Error } & OrchestratorEventContext)
   | ({
       type: 'interaction-started'
@@ -46,11 +76,21 @@ export type OrchestratorEvent =
       conversationId: string
       role: QueuedMessageRole
       text: string
+      attachments?: MessageAttachment[]
       systemPrompt?: string
       informationMessages?: InformationMessage[]
     } & OrchestratorEventContext)
   | ({ type: 'interaction-saved'; interaction: Interaction } & OrchestratorEventContext)
   | ({ type: 'conversation-created'; conversation: Conversation } & OrchestratorEventContext)
+  | ({
+      type: 'context-compacted'
+      interactionId: string
+      summarizedInteractionIds: string[]
+      estimatedTokens: number
+      tokenLimit: number
+    } & OrchestratorEventContext)
+  | ({ type: 'context-compaction-reverted'; interactionId: string } & OrchestratorEventContext)
+  | ({ type: 'conversation-title-updated'; conversationId: string; title: string } & OrchestratorEventContext)
   | ({ type: 'queue-update'; queue: QueueState } & OrchestratorEventContext)
   | ({ type: 'state-change' } & OrchestratorEventContext)
 
@@ -76,6 +116,13 @@ export interface ChatState {
 export interface ChatOrchestratorOptions {
   /** Number of interactions to load per page */
   pageSize?: number
+  /** Context window budgeting and history compaction */
+  contextBudget?: ContextBudgetOptions
+  /**
+   * Generate a title for untitled conversations after the first completed
+   * interaction with a user message. Default true.
+   */
+  autoTitle?: boolean
 }
 
 /**
@@ -94,6 +141,18 @@ export interface ChatOrchestratorDeps {
   modelConfigProvider?: IModelConfigProvider
   /** Optional tool registry for tool execution */
   toolRegistry?: ToolRegistry
+  /**
+   * Long-term memory for the user.
+   * When provided, memories relevant to each user message are added to the
+   * system prompt sent to the provider.
+   */
+  memory?: MemoryService
+  /**
+   * Storage for message attachments.
+   * Providers load attached files from here; without it they only get the
+   * attachment descriptions.
+   */
+  attachmentStore?: IAttachmentStore
   /**
    * Get the localized display name for a tool.
    * If not provided, tool IDs will be used as display names.
@@ -0,0 +1,480 @@
+import type { ToolResult } from '@stina/extension-api'
+import { isImageMimeType, withAttachmentDescriptions } from '@stina/shared'
+import type {
+  AIProvider,
+  EmbedOptions,
+  SendMessageOptions,
+  StreamEvent,
+  TokenUsage,
+} from '../types/provider.js'
+import type { Message, UserMessage } from '../types/message.js'
+import type { ResolvedToolDefinition } from '../tools/ToolRegistry.js'
+import type { ProviderRegistry } from './ProviderRegistry.js'
+
+/** Provider ID of the built-in OpenAI-compatible provider */
+export const OPENAI_COMPATIBLE_PROVIDER_ID = 'openai-compatible'
+
+/** Default server: Ollama's OpenAI-compatible endpoint */
+export const DEFAULT_OPENAI_COMPATIBLE_BASE_URL = 'http://localhost:11434/v1'
+
+/** Safety limit for model → tool → model round trips in one response */
+const MAX_TOOL_ITERATIONS = 10
+
+/**
+ * Options for OpenAICompatibleProvider.
+ * Model configs can override `baseUrl`, `apiKey` and `temperature` through
+ * their provider settings.
+ */
+export interface OpenAICompatibleProviderOptions {
+  /** Provider ID. Default 'openai-compatible' */
+  id?: string
+  /** Display name */
+  name?: string
+  /** Base URL of the API, including the version path (e.g. http://localhost:8080/v1) */
+  baseUrl?: string
+  /** API key sent as a bearer token, if the server requires one */
+  apiKey?: string
+  /** Model used when the model config does not name one. Default: the server's first model */
+  model?: string
+  /** Fetch implementation, for tests */
+  fetch?: typeof fetch
+}
+
+interface ResolvedConfig {
+  baseUrl: string
+  apiKey?: string
+  temperature?: number
+}
+
+type WireMessage =
+  | { role: 'system'; content: string }
+  | { role: 'user'; content: string | WireContentPart[] }
+  | { role: 'assistant'; content: string | null; tool_calls?: WireToolCall[] }
+  | { role: 'tool'; tool_call_id: string; content: string }
+
+type WireContentPart =
+  | { type: 'text'; text: string }
+  | { type: 'image_url'; image_url: { url: string } }
+
+interface WireToolCall {
+  id: string
+  type: 'function'
+  function: { name: string; arguments: string }
+}
+
+interface CompletionChunk {
+  choices?: Array<{
+    delta?: {
+      content?: string | null
+      /** Reasoning text (llama.cpp, vLLM and DeepSeek use `reasoning_content`) */
+      reasoning_content?: string | null
+      reasoning?: string | null
+      tool_calls?: Array<{
+        index?: number
+        id?: string
+        function?: { name?: string; arguments?: string }
+      }>
+    }
+  }>
+  usage?: { prompt_tokens?: number; completion_tokens?: number } | null
+  error?: { message?: string }
+}
+
+/**
+ * Built-in provider for servers that speak the OpenAI-compatible
+ * `/v1/chat/completions` streaming API, such as llama.cpp, Ollama and vLLM.
+ * Works without any extension installed. Tool calls are run through the
+ * orchestrator's tool executor.
+ */
+export class OpenAICompatibleProvider implements AIProvider {
+  readonly id: string
+  readonly name: string
+
+  private readonly fetchImpl: typeof fetch
+  /** First model reported by each server, used when no model is configured */
+  private readonly defaultModels = new Map<string, string>()
+
+  constructor(private readonly options: OpenAICompatibleProviderOptions = {}) {
+    this.id = options.id ?? OPENAI_COMPATIBLE_PROVIDER_ID
+    this.name = options.name ?? 'OpenAI-compatible server'
+    this.fetchImpl = options.fetch ?? ((input, init) => fetch(input, init))
+  }
+
+  async sendMessage(
+    messages: Message[],
+    systemPrompt: string,
+    onEvent: (event: StreamEvent) => void,
+    options?: SendMessageOptions
+  ): Promise<void> {
+    try {
+      const config = this.resolveConfig(options?.settings)
+      const model = options?.modelId || (await this.getDefaultModel(config))
+      const wireMessages = await toWireMessages(messages, systemPrompt, options?.loadAttachment)
+      const tools = options?.tools?.length ? toWireTools(options.tools) : undefined
+
+      let usage: TokenUsage | undefined
+      for (let iteration = 0; iteration < MAX_TOOL_ITERATIONS; iteration++) {
+        const result = await this.streamCompletion(
+          config,
+          { model, messages: wireMessages, tools: tools?.definitions },
+          onEvent
+        )
+        if (result.usage) {
+          usage = {
+            inputTokens: (usage?.inputTokens ?? 0) + result.usage.inputTokens,
+            outputTokens: (usage?.outputTokens ?? 0) + result.usage.outputTokens,
+          }
+        }
+
+        if (result.toolCalls.length === 0 || !options?.toolExecutor) break
+
+        wireMessages.push({
+          role: 'assistant',
+          content: result.content || null,
+          tool_calls: result.toolCalls,
+        })
+
+        for (const call of result.toolCalls) {
+          const toolId = tools?.toolIds.get(call.function.name) ?? call.function.name
+          const displayName = options.getToolDisplayName?.(toolId)
+          onEvent({ type: 'tool', name: toolId, displayName, payload: call.function.arguments })
+
+          const output = JSON.stringify(
+            await executeToolCall(options.toolExecutor, toolId, call.function.arguments)
+          )
+          onEvent({ type: 'tool_result', name: toolId, displayName, result: output })
+          wireMessages.push({ role: 'tool', tool_call_id: call.id, content: output })
+        }
+      }
+
+      onEvent({ type: 'done', usage })
+    } catch (error) {
+      onEvent({
+        type: 'error',
+        error: error instanceof Error ? error : new Error(String(error)),
+      })
+    }
+  }
+
+  async embed(texts: string[], options?: EmbedOptions): Promise<number[][]> {
+    const config = this.resolveConfig(options?.settings)
+    const model = options?.modelId || (await this.getDefaultModel(config))
+    const response = await this.request(config, '/embeddings', { model, input: texts })
+    const body = (await response.json()) as {
+      data?: Array<{ index?: number; embedding: number[] }>
+    }
+    if (!Array.isArray(body.data) || body.data.length !== texts.length) {
+      throw new Error('OpenAI-compatible server returned an invalid embeddings response')
+    }
+    return [...body.data]
+      .sort((a, b) => (a.index ?? 0) - (b.index ?? 0))
+      .map((item) => item.embedding)
+  }
+
+  /**
+   * List the models the server offers.
+   * @param settings - Provider settings from a model config
+   */
+  async listModels(
+    settings?: Record<string, unknown>
+  ): Promise<Array<{ id: string; name: string }>> {
+    const config = this.resolveConfig(settings)
+    const response = await this.request(config, '/models')
+    const body = (await response.json()) as { data?: Array<{ id?: unknown }> }
+    return (body.data ?? [])
+      .map((model) => model.id)
+      .filter((id): id is string => typeof id === 'string' && id.length > 0)
+      .map((id) => ({ id, name: id }))
+  }
+
+  private resolveConfig(settings: Record<string, unknown> | undefined): ResolvedConfig {
+    const baseUrl =
+      stringSetting(settings, 'baseUrl') ??
+      this.options.baseUrl ??
+      DEFAULT_OPENAI_COMPATIBLE_BASE_URL
+    const temperature = settings?.['temperature']
+    return {
+      baseUrl: baseUrl.replace(/\/+$/, ''),
+      apiKey: stringSetting(settings, 'apiKey') ?? this.options.apiKey,
+      temperature: typeof temperature === 'number' ? temperature : undefined,
+    }
+  }
+
+  private async getDefaultModel(config: ResolvedConfig): Promise<string> {
+    if (this.options.model) return this.options.model
+
+    const cached = this.defaultModels.get(config.baseUrl)
+    if (cached) return cached
+
+    const [first] = await this.listModels({ baseUrl: config.baseUrl, apiKey: config.apiKey })
+    if (!first) {
+      throw new Error(`No model configured and ${config.baseUrl} does not list any models`)
+    }
+    this.defaultModels.set(config.baseUrl, first.id)
+    return first.id
+  }
+
+  private async request(config: ResolvedConfig, path: string, body?: unknown): Promise<Response> {
+    const headers: Record<string, string> = {}
+    if (body !== undefined) headers['Content-Type'] = 'application/json'
+    if (config.apiKey) headers['Authorization'] = `Bearer ${config.apiKey}`
+
+    const response = await this.fetchImpl(`${config.baseUrl}${path}`, {
+      method: body !== undefined ? 'POST' : 'GET',
+      headers,
+      body: body !== undefined ? JSON.stringify(body) : undefined,
+    })
+    if (!response.ok) {
+      const detail = (await response.text().catch(() => '')).trim().slice(0, 500)
+      throw new Error(
+        `OpenAI-compatible server responded with ${response.status}${detail ? `: ${detail}` : ''}`
+      )
+    }
+    return response
+  }
+
+  /**
+   * Send one streaming chat completion request and forward text and reasoning
+   * as they arrive.
+   * @returns The full text, the requested tool calls and token usage
+   */
+  private async streamCompletion(
+    config: ResolvedConfig,
+    params: { model: string; messages: WireMessage[]; tools?: unknown[] },
+    onEvent: (event: StreamEvent) => void
+  ): Promise<{ content: string; toolCalls: WireToolCall[]; usage?: TokenUsage }> {
+    const response = await this.request(config, '/chat/completions', {
+      model: params.model,
+      messages: params.messages,
+      ...(params.tools ? { tools: params.tools } : {}),
+      ...(config.temperature !== undefined ? { temperature: config.temperature } : {}),
+      stream: true,
+      stream_options: { include_usage: true },
+    })
+    if (!response.body) {
+      throw new Error('OpenAI-compatible server returned an empty response')
+    }
+
+    let content = ''
+    let usage: TokenUsage | undefined
+    const toolCalls: WireToolCall[] = []
+
+    for await (const chunk of readServerSentEvents(response.body)) {
+      if (chunk.error) {
+        throw new Error(chunk.error.message ?? 'OpenAI-compatible server reported an error')
+      }
+      if (chunk.usage) {
+        usage = {
+          inputTokens: chunk.usage.prompt_tokens ?? 0,
+          outputTokens: chunk.usage.completion_tokens ?? 0,
+        }
+      }
+
+      const delta = chunk.choices?.[0]?.delta
+      if (!delta) continue
+
+      const reasoning = delta.reasoning_content ?? delta.reasoning
+      if (reasoning) onEvent({ type: 'thinking', text: reasoning })
+      if (delta.content) {
+        content += delta.content
+        onEvent({ type: 'content', text: delta.content })
+      }
+
+      // Tool calls arrive in fragments keyed by index; the arguments are streamed as text
+      for (const fragment of delta.tool_calls ?? []) {
+        const index = fragment.index ?? toolCalls.length
+        const call = (toolCalls[index] ??= {
+          id: '',
+          type: 'function',
+          function: { name: '', arguments: '' },
+        })
+        if (fragment.id) call.id = fragment.id
+        if (fragment.function?.name) call.function.name += fragment.function.name
+        if (fragment.function?.arguments) call.function.arguments += fragment.function.arguments
+      }
+    }
+
+    const completedCalls = toolCalls
+      .filter((call) => call && call.function.name)
+      .map((call, index) => ({ ...call, id: call.id || `call_${index}` }))
+    return { content, toolCalls: completedCalls, usage }
+  }
+}
+
+/**
+ * Read the `data:` payloads of a server-sent event stream until `[DONE]`.
+ */
+async function* readServerSentEvents(
+  body: ReadableStream<Uint8Array>
+): AsyncGenerator<CompletionChunk> {
+  const reader = body.getReader()
+  const decoder = new TextDecoder()
+  let buffer = ''
+
+  try {
+    while (true) {
+      const { done, value } = await reader.read()
+      buffer += done ? decoder.decode() : decoder.decode(value, { stream: true })
+
+      const lines = buffer.split(/\r?\n/)
+      buffer = done ? '' : (lines.pop() ?? '')
+
+      for (const line of lines) {
+        if (!line.startsWith('data:')) continue
+        const data = line.slice('data:'.length).trim()
+        if (!data) continue
+        if (data === '[DONE]') return
+        yield JSON.parse(data) as CompletionChunk
+      }
+
+      if (done) return
+    }
+  } finally {
+    reader.releaseLock()
+  }
+}
+
+/**
+ * Convert chat messages to OpenAI chat messages.
+ * Information, thinking and tools messages have no matching role and are left out.
+ */
+async function toWireMessages(
+  messages: Message[],
+  systemPrompt: string,
+  loadAttachment: SendMessageOptions['loadAttachment']
+): Promise<WireMessage[]> {
+  const hasSystemPromptMessage = messages.some(
+    (message) => message.type === 'instruction' && message.metadata?.['systemPrompt'] === true
+  )
+  const wire: WireMessage[] =
+    systemPrompt.trim() && !hasSystemPromptMessage
+      ? [{ role: 'system', content: systemPrompt }]
+      : []
+
+  for (const message of messages) {
+    switch (message.type) {
+      case 'user':
+        wire.push({ role: 'user', content: await toWireUserContent(message, loadAttachment) })
+        break
+      case 'stina':
+        wire.push({ role: 'assistant', content: message.text })
+        break
+      case 'instruction':
+        wire.push({ role: 'system', content: message.text })
+        break
+    }
+  }
+  return wire
+}
+
+/**
+ * Images are sent as data URLs for vision models. Other files, and images that
+ * cannot be loaded, are described in the text.
+ */
+async function toWireUserContent(
+  message: UserMessage,
+  loadAttachment: SendMessageOptions['loadAttachment']
+): Promise<string | WireContentPart[]> {
+  const attachments = message.attachments ?? []
+  const images: WireContentPart[] = []
+  const described: typeof attachments = []
+
+  for (const attachment of attachments) {
+    const data = isImageMimeType(attachment.mimeType)
+      ? await loadAttachment?.(attachment).catch(() => null)
+      : null
+    if (data) {
+      const url = `data:${attachment.mimeType};base64,${Buffer.from(data).toString('base64')}`
+      images.push({ type: 'image_url', image_url: { url } })
+    } else {
+      described.push(attachment)
+    }
+  }
+
+  const text = withAttachmentDescriptions(message.text, described)
+  if (images.length === 0) return text
+  return text.trim() ? [{ type: 'text', text }, ...images] : images
+}
+
+/**
+ * Convert tool definitions to OpenAI function tools.
+ * Function names may only contain letters, digits, underscores and dashes, so
+ * other characters are replaced and the original tool IDs kept for lookup.
+ */
+function toWireTools(tools: ResolvedToolDefinition[]): {
+  definitions: unknown[]
+  toolIds: Map<string, string>
+} {
+  const toolIds = new Map<string, string>()
+  const definitions = tools.map((tool) => {
+    const name = tool.id.replace(/[^a-zA-Z0-9_-]/g, '_').slice(0, 64)
+    toolIds.set(name, tool.id)
+    return {
+      type: 'function',
+      function: {
+        name,
+        description: tool.description,
+        parameters: tool.parameters ?? { type: 'object', properties: {} },
+      },
+    }
+  })
+  return { definitions, toolIds }
+}
+
+async function executeToolCall(
+  toolExecutor: NonNullable<SendMessageOptions['toolExecutor']>,
+  toolId: string,
+  rawArguments: string
+): Promise<ToolResult> {
+  let params: Record<string, unknown>
+  try {
+    const parsed: unknown = rawArguments.trim() ? JSON.parse(rawArguments) : {}
+    if (!parsed || typeof parsed !== 'object' || Array.isArray(parsed)) {
+      return { success: false, error: 'Tool arguments must be a JSON object' }
+    }
+    params = parsed as Record<string, unknown>
+  } catch {
+    return { success: false, error: 'Tool arguments are not valid JSON' }
+  }
+
+  try {
+    return await toolExecutor(toolId, params)
+  } catch (error) {
+    return { success: false, error: error instanceof Error ? error.message : String(error) }
+  }
+}
+
+function stringSetting(
+  settings: Record<string, unknown> | undefined,
+  key: string
+): string | undefined {
+  const value = settings?.[key]
+  return typeof value === 'string' && value.trim() ? value.trim() : undefined
+}
+
+/**
+ * Read provider options from environment variables:
+ * `STINA_LOCAL_PROVIDER_URL`, `STINA_LOCAL_PROVIDER_API_KEY` and `STINA_LOCAL_PROVIDER_MODEL`.
+ */
+export function getOpenAICompatibleOptionsFromEnv(
+  env: Record<string, string | undefined>
+): OpenAICompatibleProviderOptions {
+  return {
+    baseUrl: env['STINA_LOCAL_PROVIDER_URL'] || undefined,
+    apiKey: env['STINA_LOCAL_PROVIDER_API_KEY'] || undefined,
+    model: env['STINA_LOCAL_PROVIDER_MODEL'] || undefined,
+  }
+}
+
+/**
+ * Register the built-in provider for local OpenAI-compatible servers, so chat
+ * works without any provider extension. Options are read from `env`, see
+ * getOpenAICompatibleOptionsFromEnv. Does nothing if the provider is already registered.
+ */
+export function registerOpenAICompatibleProvider(
+  registry: ProviderRegistry,
+  env: Record<string, string | undefined>
+): void {
+  if (registry.has(OPENAI_COMPATIBLE_PROVIDER_ID)) return
+  registry.register(new OpenAICompatibleProvider(getOpenAICompatibleOptionsFromEnv(env)))
+}
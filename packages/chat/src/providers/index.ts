@@ -1,2 +1,10 @@
 export { ProviderRegistry, providerRegistry } from './ProviderRegistry.js'
 export { EchoProvider, echoProvider } from './EchoProvider.js'
+export {
+  OpenAICompatibleProvider,
+  OPENAI_COMPATIBLE_PROVIDER_ID,
+  DEFAULT_OPENAI_COMPATIBLE_BASE_URL,
+  getOpenAICompatibleOptionsFromEnv,
+  registerOpenAICompatibleProvider,
+  type OpenAICompatibleProviderOptions,
+} from './OpenAICompatibleProvider.js'
@@ -0,0 +1,129 @@
+import { createTranslator, getLang } from '@stina/i18n'
+import { isQuietTime, toIsoWithTimeZone } from '@stina/shared'
+import type { DeferredInstruction, QuietHoursServiceOptions } from './types.js'
+
+/** How often users with deferred instructions are checked for the end of quiet time */
+export const QUIET_HOURS_CHECK_INTERVAL_MS = 60_000
+
+/**
+ * Holds back proactive instructions during the user's quiet hours and
+ * do-not-disturb, and delivers them when the quiet time ends.
+ * A single deferred instruction is delivered as is; several are combined
+ * into one digest in the active conversation.
+ */
+export class QuietHoursService {
+  private timer: ReturnType<typeof setInterval> | null = null
+  private readonly flushing = new Set<string>()
+
+  constructor(private readonly options: QuietHoursServiceOptions) {}
+
+  /**
+   * Deliver an instruction, or store it for later if it is quiet time for the user.
+   * @returns Whether the instruction was deferred
+   */
+  async appendInstruction(
+    userId: string,
+    text: string,
+    conversationId?: string,
+    now = new Date()
+  ): Promise<{ deferred: boolean }> {
+    const settings = await this.options.getSettings(userId)
+    if (isQuietTime(settings, now)) {
+      await this.options.getRepository(userId).add({ text, conversationId })
+      return { deferred: true }
+    }
+
+    await this.options.deliver(userId, text, conversationId)
+    return { deferred: false }
+  }
+
+  /**
+   * Deliver the user's deferred instructions if quiet time has ended.
+   * The instructions are kept if delivery fails.
+   * @returns The number of delivered instructions
+   */
+  async flush(userId: string, now = new Date()): Promise<number> {
+    if (this.flushing.has(userId)) return 0
+    this.flushing.add(userId)
+    try {
+      const settings = await this.options.getSettings(userId)
+      if (isQuietTime(settings, now)) return 0
+
+      const repository = this.options.getRepository(userId)
+      const instructions = await repository.list()
+      if (instructions.length === 0) return 0
+
+      const [first] = instructions
+      if (instructions.length === 1 && first) {
+        await this.options.deliver(userId, first.text, first.conversationId ?? undefined)
+      } else {
+        const text = await this.formatDigest(userId, instructions, settings.timezone)
+        await this.options.deliver(userId, text)
+      }
+
+      await repository.delete(instructions.map((instruction) => instruction.id))
+      return instructions.length
+    } finally {
+      this.flushing.delete(userId)
+    }
+  }
+
+  /**
+   * Deliver deferred instructions for every user whose quiet time has ended.
+   */
+  async checkAll(now = new Date()): Promise<void> {
+    let userIds: string[]
+    try {
+      userIds = await this.options.listPendingUserIds()
+    } catch (error) {
+      this.options.logger?.warn('Failed to list users with deferred instructions', {
+        error: error instanceof Error ? error.message : String(error),
+      })
+      return
+    }
+
+    for (const userId of userIds) {
+      try {
+        await this.flush(userId, now)
+      } catch (error) {
+        this.options.logger?.warn('Failed to deliver deferred instructions', {
+          userId,
+          error: error instanceof Error ? error.message : String(error),
+        })
+      }
+    }
+  }
+
+  /**
+   * Start checking for the end of quiet time. Instructions deferred before a
+   * restart are picked up by the first check.
+   */
+  start(): void {
+    if (this.timer) return
+    this.timer = setInterval(() => void this.checkAll(), QUIET_HOURS_CHECK_INTERVAL_MS)
+    void this.checkAll()
+  }
+
+  stop(): void {
+    if (this.timer) {
+      clearInterval(this.timer)
+      this.timer = null
+    }
+  }
+
+  private async formatDigest(
+    userId: string,
+    instructions: DeferredInstruction[],
+    timezone: string
+  ): Promise<string> {
+    const language = await this.options.getLanguage?.(userId)
+    const { t } = createTranslator(language ?? getLang())
+    const items = instructions
+      .map((instruction, index) => {
+        const receivedAt = toIsoWithTimeZone(instruction.createdAt, timezone || 'UTC')
+        return `${index + 1}. [${receivedAt}] ${instruction.text}`
+      })
+      .join('\n\n')
+    return t('chat.quiet_hours.digest', { count: instructions.length, items })
+  }
+}
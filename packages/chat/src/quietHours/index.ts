@@ -0,0 +1,7 @@
+export { QuietHoursService, QUIET_HOURS_CHECK_INTERVAL_MS } from './QuietHoursService.js'
+export type {
+  DeferredInstruction,
+  CreateDeferredInstructionInput,
+  IDeferredInstructionRepository,
+  QuietHoursServiceOptions,
+} from './types.js'
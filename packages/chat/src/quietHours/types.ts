@@ -0,0 +1,47 @@
+import type { QuietHoursSettings } from '@stina/shared'
+
+/**
+ * A proactive instruction held back during quiet time
+ */
+export interface DeferredInstruction {
+  id: string
+  text: string
+  /** Conversation the instruction was meant for, null for the active conversation */
+  conversationId: string | null
+  createdAt: Date
+}
+
+export interface CreateDeferredInstructionInput {
+  text: string
+  conversationId?: string | null
+}
+
+/**
+ * Storage for deferred instructions, scoped to one user
+ */
+export interface IDeferredInstructionRepository {
+  /**
+   * Deferred instructions, oldest first
+   */
+  list(): Promise<DeferredInstruction[]>
+  add(input: CreateDeferredInstructionInput): Promise<DeferredInstruction>
+  /**
+   * @returns The number of deleted instructions
+   */
+  delete(ids: string[]): Promise<number>
+}
+
+export interface QuietHoursServiceOptions {
+  /** Quiet hours settings for a user */
+  getSettings: (userId: string) => Promise<QuietHoursSettings>
+  getRepository: (userId: string) => IDeferredInstructionRepository
+  /** Users that have deferred instructions, checked when quiet time may have ended */
+  listPendingUserIds: () => Promise<string[]>
+  /** Add an instruction to the user's conversation and run it */
+  deliver: (userId: string, text: string, conversationId?: string) => Promise<void>
+  /** The user's language, used for the digest text */
+  getLanguage?: (userId: string) => Promise<string | undefined>
+  logger?: {
+    warn: (message: string, context?: Record<string, unknown>) => void
+  }
+}
@@ -63,7 +63,7 @@ export class ChatStreamService extends EventEmitter {
         break
 
       case 'done':
-        this.emit('stream-complete', this.buildMessages())
+        this.emit('stream-complete', this.buildMessages(), event.usage)
         this.reset()
         break
 
@@ -0,0 +1,127 @@
+import type {
+  CreateReminderInput,
+  IReminderRepository,
+  ListTasksOptions,
+  Reminder,
+  ReminderScheduler,
+} from './types.js'
+
+/** Maximum length of a reminder text, in characters */
+export const MAX_REMINDER_LENGTH = 1000
+
+/**
+ * Reminders for one user.
+ * Keeps the stored reminders and their scheduled deliveries in sync.
+ */
+export class ReminderService {
+  constructor(
+    private readonly repository: IReminderRepository,
+    private readonly scheduler: ReminderScheduler
+  ) {}
+
+  /**
+   * Reminders ordered by time.
+   */
+  async list(options?: ListTasksOptions): Promise<Reminder[]> {
+    return this.repository.list(options)
+  }
+
+  async get(id: string): Promise<Reminder | null> {
+    return this.repository.get(id)
+  }
+
+  /**
+   * Create and schedule a reminder.
+   * @throws If the text is empty or the time is invalid
+   */
+  async create(input: CreateReminderInput): Promise<Reminder> {
+    const text = validateText(input.text)
+    validateTime(input.remindAt)
+
+    const reminder = await this.repository.create({ text, remindAt: input.remindAt })
+    await this.scheduler.schedule(reminder)
+    return reminder
+  }
+
+  /**
+   * Change the text or time of a reminder.
+   * A new time reschedules the reminder, also if it has already been delivered.
+   * @returns The updated reminder, or null if it does not exist
+   * @throws If the text is empty or the time is invalid
+   */
+  async update(id: string, input: { text?: string; remindAt?: Date }): Promise<Reminder | null> {
+    const existing = await this.repository.get(id)
+    if (!existing) return null
+
+    const text = input.text !== undefined ? validateText(input.text) : undefined
+    if (input.remindAt !== undefined) validateTime(input.remindAt)
+
+    const rescheduled =
+      input.remindAt !== undefined && input.remindAt.getTime() !== existing.remindAt.getTime()
+    const updated = await this.repository.update(id, {
+      text,
+      remindAt: input.remindAt,
+      ...(rescheduled ? { firedAt: null, completedAt: null } : {}),
+    })
+
+    if (updated && rescheduled) {
+      await this.scheduler.schedule(updated)
+    }
+    return updated
+  }
+
+  /**
+   * Mark a reminder as done and cancel any pending delivery.
+   * @returns The updated reminder, or null if it does not exist
+   */
+  async complete(id: string): Promise<Reminder | null> {
+    const updated = await this.repository.update(id, { completedAt: new Date() })
+    if (updated) {
+      await this.scheduler.cancel(id)
+    }
+    return updated
+  }
+
+  /**
+   * Delete a reminder and cancel any pending delivery.
+   * @returns True if the reminder existed
+   */
+  async delete(id: string): Promise<boolean> {
+    const deleted = await this.repository.delete(id)
+    if (deleted) {
+      await this.scheduler.cancel(id)
+    }
+    return deleted
+  }
+
+  /**
+   * Record that a scheduled reminder is being delivered.
+   * @returns The reminder to deliver, or null if it was deleted, completed or
+   * moved to a later time since it was scheduled
+   */
+  async markFired(id: string, now = new Date()): Promise<Reminder | null> {
+    const reminder = await this.repository.get(id)
+    if (!reminder || reminder.completedAt || reminder.firedAt) return null
+    // Stale job from before the reminder was moved (timestamps are stored in seconds)
+    if (reminder.remindAt.getTime() - now.getTime() > 1000) return null
+
+    return this.repository.update(id, { firedAt: now })
+  }
+}
+
+function validateText(value: string): string {
+  const text = value.trim()
+  if (!text) {
+    throw new Error('Reminder text is empty')
+  }
+  if (text.length > MAX_REMINDER_LENGTH) {
+    throw new Error(`Reminder text is longer than ${MAX_REMINDER_LENGTH} characters`)
+  }
+  return text
+}
+
+function validateTime(value: Date): void {
+  if (Number.isNaN(value.getTime())) {
+    throw new Error('Invalid reminder time')
+  }
+}
@@ -0,0 +1,13 @@
+export { ReminderService, MAX_REMINDER_LENGTH } from './ReminderService.js'
+export type {
+  Task,
+  CreateTaskInput,
+  UpdateTaskInput,
+  ListTasksOptions,
+  ITaskRepository,
+  Reminder,
+  CreateReminderInput,
+  UpdateReminderInput,
+  IReminderRepository,
+  ReminderScheduler,
+} from './types.js'
@@ -0,0 +1,106 @@
+/**
+ * A to-do item
+ */
+export interface Task {
+  id: string
+  title: string
+  notes: string | null
+  dueAt: Date | null
+  completedAt: Date | null
+  createdAt: Date
+  updatedAt: Date
+}
+
+export interface CreateTaskInput {
+  title: string
+  notes?: string | null
+  dueAt?: Date | null
+}
+
+export interface UpdateTaskInput {
+  title?: string
+  notes?: string | null
+  dueAt?: Date | null
+  completedAt?: Date | null
+}
+
+export interface ListTasksOptions {
+  /** Include completed items. Default false */
+  includeCompleted?: boolean
+}
+
+/**
+ * Storage for tasks, scoped to one user
+ */
+export interface ITaskRepository {
+  /**
+   * Tasks ordered by due date (undated last), then creation time
+   */
+  list(options?: ListTasksOptions): Promise<Task[]>
+  get(id: string): Promise<Task | null>
+  create(input: CreateTaskInput): Promise<Task>
+  /**
+   * @returns The updated task, or null if it does not exist
+   */
+  update(id: string, input: UpdateTaskInput): Promise<Task | null>
+  /**
+   * @returns True if a task was deleted
+   */
+  delete(id: string): Promise<boolean>
+}
+
+/**
+ * Something the user should be reminded about at a given time
+ */
+export interface Reminder {
+  id: string
+  text: string
+  remindAt: Date
+  /** When the reminder was delivered, null while pending */
+  firedAt: Date | null
+  completedAt: Date | null
+  createdAt: Date
+  updatedAt: Date
+}
+
+export interface CreateReminderInput {
+  text: string
+  remindAt: Date
+}
+
+export interface UpdateReminderInput {
+  text?: string
+  remindAt?: Date
+  firedAt?: Date | null
+  completedAt?: Date | null
+}
+
+/**
+ * Storage for reminders, scoped to one user
+ */
+export interface IReminderRepository {
+  /**
+   * Reminders ordered by time
+   */
+  list(options?: ListTasksOptions): Promise<Reminder[]>
+  get(id: string): Promise<Reminder | null>
+  create(input: CreateReminderInput): Promise<Reminder>
+  /**
+   * @returns The updated reminder, or null if it does not exist
+   */
+  update(id: string, input: UpdateReminderInput): Promise<Reminder | null>
+  /**
+   * @returns True if a reminder was deleted
+   */
+  delete(id: string): Promise<boolean>
+}
+
+/**
+ * Schedules delivery of reminders for one user, usually through SchedulerService
+ */
+export interface ReminderScheduler {
+  /** Schedule, or reschedule, delivery at reminder.remindAt */
+  schedule(reminder: Reminder): Promise<void>
+  /** Cancel a scheduled delivery */
+  cancel(reminderId: string): Promise<void>
+}
@@ -41,4 +41,10 @@ export interface Conversation {
    * Metadata
    */
   metadata: ConversationMetadata
+
+  /**
+   * Last interaction of the branch the conversation shows.
+   * Set when the conversation is loaded with all its branches.
+   */
+  activeInteractionId?: string
 }
@@ -4,6 +4,7 @@ export type {
   MessageType,
   MessageMetadata,
   UserMessage,
+  MessageAttachment,
   StinaMessage,
   InstructionMessage,
   InformationMessage,
@@ -21,4 +22,4 @@ export type { Interaction, InteractionMetadata } from './interaction.js'
 export type { Conversation, ConversationMetadata } from './conversation.js'
 
 // Provider types
-export type { AIProvider, StreamEvent, SendMessageOptions } from './provider.js'
+export type { AIProvider, StreamEvent, SendMessageOptions, EmbedOptions, TokenUsage } from './provider.js'
@@ -1,10 +1,17 @@
 import type { Message, InformationMessage } from './message.js'
+import type { TokenUsage } from './provider.js'
 
 /**
  * Metadata for an interaction
  */
 export interface InteractionMetadata {
   createdAt: string
+  /** Provider that generated the response */
+  providerId?: string
+  /** Model that generated the response */
+  modelId?: string
+  /** Token usage reported by the provider */
+  usage?: TokenUsage
   [key: string]: unknown
 }
 
@@ -63,4 +70,16 @@ export interface Interaction {
    * When this interaction was read (undefined = unread)
    */
   readAt?: string
+
+  /**
+   * Interaction this one follows in its branch (null = first interaction).
+   * Left undefined, saving links it to the end of the conversation's active branch.
+   */
+  parentId?: string | null
+
+  /**
+   * Interactions that share this interaction's parent, including itself, oldest first.
+   * Set on loaded interactions when editing or regenerating has created more than one branch.
+   */
+  siblingIds?: string[]
 }
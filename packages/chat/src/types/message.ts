@@ -39,12 +39,27 @@ export interface ToolCall {
   confirmationDenialReason?: string
 }
 
+/**
+ * File attached to a user message.
+ * The file itself is kept in the attachment store, keyed by its content hash.
+ */
+export interface MessageAttachment {
+  /** Content hash (SHA-256, hex) */
+  id: string
+  name: string
+  mimeType: string
+  /** Size in bytes */
+  size: number
+}
+
 /**
  * User message
  */
 export interface UserMessage {
   type: typeof MessageType.USER
   text: string
+  /** Attached images and files */
+  attachments?: MessageAttachment[]
   metadata: MessageMetadata
 }
 
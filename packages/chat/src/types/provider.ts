@@ -1,4 +1,14 @@
-import type { Message } from './message.js'
+import type { ToolResult } from '@stina/extension-api'
+import type { Message, MessageAttachment } from './message.js'
+import type { ResolvedToolDefinition } from '../tools/ToolRegistry.js'
+
+/**
+ * Token usage reported by a provider for one response
+ */
+export interface TokenUsage {
+  inputTokens: number
+  outputTokens: number
+}
 
 /**
  * Streaming events from AI provider
@@ -8,7 +18,7 @@ export type StreamEvent =
   | { type: 'tool'; name: string; displayName?: string; payload: string }
   | { type: 'tool_result'; name: string; displayName?: string; result: string }
   | { type: 'content'; text: string }
-  | { type: 'done' }
+  | { type: 'done'; usage?: TokenUsage }
   | { type: 'error'; error: Error }
 
 /**
@@ -19,11 +29,35 @@ export interface SendMessageOptions {
   settings?: Record<string, unknown>
   /** Model ID to use */
   modelId?: string
+  /** Request context (user info, not provider config) */
+  context?: { userId?: string; [key: string]: unknown }
+  /** Tools the model may call */
+  tools?: ResolvedToolDefinition[]
+  /**
+   * Run a tool the model asked for.
+   * Providers that handle tool calls themselves feed the result back to the model.
+   */
+  toolExecutor?: (toolId: string, params: Record<string, unknown>) => Promise<ToolResult>
   /**
    * Get the localized display name for a tool.
    * Called when emitting tool events to get a user-friendly name.
    */
   getToolDisplayName?: (toolId: string) => string | undefined
+  /**
+   * Load the data of a message attachment.
+   * Providers that cannot read attachments describe them in the text instead.
+   */
+  loadAttachment?: (attachment: MessageAttachment) => Promise<Uint8Array | null>
+}
+
+/**
+ * Options for embed call
+ */
+export interface EmbedOptions {
+  /** Provider-specific settings (e.g., URL for Ollama) */
+  settings?: Record<string, unknown>
+  /** Embedding model ID to use */
+  modelId?: string
 }
 
 /**
@@ -54,4 +88,12 @@ export interface AIProvider {
     onEvent: (event: StreamEvent) => void,
     options?: SendMessageOptions
   ): Promise<void>
+
+  /**
+   * Generate embedding vectors, one per text.
+   * Optional; providers without embedding support leave it out.
+   * @param texts - Texts to embed
+   * @param options - Optional settings and model config
+   */
+  embed?(texts: string[], options?: EmbedOptions): Promise<number[][]>
 }
@@ -30,6 +30,7 @@ export const ErrorCode = {
   CHAT_PROVIDER_NOT_FOUND: 'CHAT_PROVIDER_NOT_FOUND',
   CHAT_PROVIDER_ERROR: 'CHAT_PROVIDER_ERROR',
   CHAT_STREAM_ERROR: 'CHAT_STREAM_ERROR',
+  CHAT_MODEL_NOT_ALLOWED: 'CHAT_MODEL_NOT_ALLOWED',
 
   // General errors
   UNKNOWN: 'UNKNOWN',
@@ -56,11 +56,23 @@ export type {
   SchedulerAPI,
   SchedulerJobRequest,
   SchedulerSchedule,
+  SchedulerRetryPolicy,
   SchedulerFirePayload,
   UserAPI,
   UserProfile,
   ChatAPI,
   ChatInstructionMessage,
+  ChatHistoryQuery,
+  ChatConversationSummary,
+  ChatHistoryMessage,
+  ChatHistoryInteraction,
+  ChatCurrentConversation,
+  FileRootAccess,
+  FileRoot,
+  FileEncoding,
+  FileEntry,
+  FileStat,
+  FilesAPI,
   LogAPI,
 
   // Background workers
@@ -83,8 +95,13 @@ export type {
   AIProvider,
   ModelInfo,
   ChatMessage,
+  ChatContentPart,
+  ChatTextPart,
+  ChatImagePart,
+  ChatFilePart,
   ChatOptions,
   GetModelsOptions,
+  EmbedOptions,
   StreamEvent,
   ToolCall,
 
@@ -109,6 +126,8 @@ export type {
   SettingsChangedMessage,
   ProviderChatRequestMessage,
   ProviderModelsRequestMessage,
+  ProviderEmbedRequestMessage,
+  ProviderEmbedResponseMessage,
   ToolExecuteRequestMessage,
   ToolExecuteResponseMessage,
   ActionExecuteRequestMessage,
@@ -6,6 +6,7 @@ import type {
   ChatMessage,
   ChatOptions,
   GetModelsOptions,
+  EmbedOptions,
   StreamEvent,
   ToolResult,
   ActionResult,
@@ -24,6 +25,7 @@ export type HostToWorkerMessage =
   | SchedulerFireMessage
   | ProviderChatRequestMessage
   | ProviderModelsRequestMessage
+  | ProviderEmbedRequestMessage
   | ToolExecuteRequestMessage
   | ActionExecuteRequestMessage
   | ResponseMessage
@@ -82,6 +84,16 @@ export interface ProviderModelsRequestMessage {
   }
 }
 
+export interface ProviderEmbedRequestMessage {
+  type: 'provider-embed-request'
+  id: string
+  payload: {
+    providerId: string
+    texts: string[]
+    options?: EmbedOptions
+  }
+}
+
 export interface ToolExecuteRequestMessage {
   type: 'tool-execute-request'
   id: string
@@ -165,6 +177,7 @@ export type WorkerToHostMessage =
   | StreamEventMessage
   | LogMessage
   | ProviderModelsResponseMessage
+  | ProviderEmbedResponseMessage
   | ToolExecuteResponseMessage
   | ActionExecuteResponseMessage
   | StreamingFetchAckMessage
@@ -207,6 +220,14 @@ export type RequestMethod =
   | 'scheduler.cancel'
   | 'scheduler.reportFireResult'
   | 'chat.appendInstruction'
+  | 'chat.listConversations'
+  | 'chat.getInteractions'
+  | 'chat.getCurrentConversation'
+  | 'files.listRoots'
+  | 'files.readFile'
+  | 'files.writeFile'
+  | 'files.list'
+  | 'files.stat'
   | 'database.execute'
   // Simple key-value storage methods
   | 'storage.set'
@@ -252,6 +273,8 @@ export interface ProviderRegisteredMessage {
   payload: {
     id: string
     name: string
+    /** Whether the provider implements embed() */
+    supportsEmbeddings?: boolean
   }
 }
 
@@ -289,6 +312,15 @@ export interface ProviderModelsResponseMessage {
   }
 }
 
+export interface ProviderEmbedResponseMessage {
+  type: 'provider-embed-response'
+  payload: {
+    requestId: string
+    embeddings: number[][]
+    error?: string
+  }
+}
+
 export interface ToolExecuteResponseMessage {
   type: 'tool-execute-response'
   payload: {
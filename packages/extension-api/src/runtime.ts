@@ -23,6 +23,15 @@ import type {
   UserProfile,
   ChatAPI,
   ChatInstructionMessage,
+  ChatHistoryQuery,
+  ChatConversationSummary,
+  ChatHistoryInteraction,
+  ChatCurrentConversation,
+  FilesAPI,
+  FileRoot,
+  FileEncoding,
+  FileEntry,
+  FileStat,
   StorageAPI,
   SecretsAPI,
   LogAPI,
@@ -34,6 +43,7 @@ import type {
   ChatMessage,
   ChatOptions,
   GetModelsOptions,
+  EmbedOptions,
   BackgroundWorkersAPI,
   BackgroundTaskConfig,
   BackgroundTaskCallback,
@@ -190,6 +200,10 @@ async function handleHostMessage(message: HostToWorkerMessage): Promise<void> {
       await handleProviderModelsRequest(message.id, message.payload)
       break
 
+    case 'provider-embed-request':
+      await handleProviderEmbedRequest(message.id, message.payload)
+      break
+
     case 'tool-execute-request':
       await handleToolExecuteRequest(message.id, message.payload)
       break
@@ -488,6 +502,46 @@ async function handleProviderModelsRequest(
   }
 }
 
+async function handleProviderEmbedRequest(
+  requestId: string,
+  payload: { providerId: string; texts: string[]; options?: EmbedOptions }
+): Promise<void> {
+  const provider = registeredProviders.get(payload.providerId)
+  if (!provider?.embed) {
+    postMessage({
+      type: 'provider-embed-response',
+      payload: {
+        requestId,
+        embeddings: [],
+        error: provider
+          ? `Provider ${payload.providerId} does not support embeddings`
+          : `Provider ${payload.providerId} not found`,
+      },
+    })
+    return
+  }
+
+  try {
+    const embeddings = await provider.embed(payload.texts, payload.options)
+    postMessage({
+      type: 'provider-embed-response',
+      payload: {
+        requestId,
+        embeddings,
+      },
+    })
+  } catch (error) {
+    postMessage({
+      type: 'provider-embed-response',
+      payload: {
+        requestId,
+        embeddings: [],
+        error: error instanceof Error ? error.message : String(error),
+      },
+    })
+  }
+}
+
 async function handleToolExecuteRequest(
   requestId: string,
   payload: { toolId: string; params: Record<string, unknown>; userId?: string }
@@ -710,7 +764,11 @@ function buildContext(
         registeredProviders.set(provider.id, provider)
         postMessage({
           type: 'provider-registered',
-          payload: { id: provider.id, name: provider.name },
+          payload: {
+            id: provider.id,
+            name: provider.name,
+            supportsEmbeddings: typeof provider.embed === 'function',
+          },
         })
         return {
           dispose: () => {
@@ -831,17 +889,72 @@ function buildContext(
   }
 
   // Add chat API if permitted
-  if (hasPermission('chat.message.write')) {
+  // Each method is checked by the host against its own permission
+  if (
+    hasPermission('chat.message.write') ||
+    hasPermission('chat.history.read') ||
+    hasPermission('chat.current.read')
+  ) {
     const chatApi: ChatAPI = {
       async appendInstruction(message: ChatInstructionMessage): Promise<void> {
         // Extensions must explicitly set message.userId if they want user-scoped messages
         // Use context.userId from the ExecutionContext in tool/action execute
         await sendRequest<void>('chat.appendInstruction', message)
       },
+
+      async listConversations(query: ChatHistoryQuery): Promise<ChatConversationSummary[]> {
+        return sendRequest<ChatConversationSummary[]>('chat.listConversations', query)
+      },
+
+      async getInteractions(
+        conversationId: string,
+        query: ChatHistoryQuery
+      ): Promise<ChatHistoryInteraction[]> {
+        return sendRequest<ChatHistoryInteraction[]>('chat.getInteractions', {
+          ...query,
+          conversationId,
+        })
+      },
+
+      async getCurrentConversation(query: ChatHistoryQuery): Promise<ChatCurrentConversation | null> {
+        return sendRequest<ChatCurrentConversation | null>('chat.getCurrentConversation', query)
+      },
     }
     ;(context as { chat: ChatAPI }).chat = chatApi
   }
 
+  // Add files API if permitted
+  // The host resolves paths against the roots the user has approved
+  if (hasPermission('files.read') || hasPermission('files.write')) {
+    const filesApi: FilesAPI = {
+      async listRoots(userId: string): Promise<FileRoot[]> {
+        return sendRequest<FileRoot[]>('files.listRoots', { userId })
+      },
+
+      async readFile(userId: string, path: string, encoding?: FileEncoding): Promise<string> {
+        return sendRequest<string>('files.readFile', { userId, path, encoding })
+      },
+
+      async writeFile(
+        userId: string,
+        path: string,
+        content: string,
+        encoding?: FileEncoding
+      ): Promise<void> {
+        await sendRequest<void>('files.writeFile', { userId, path, content, encoding })
+      },
+
+      async list(userId: string, path: string): Promise<FileEntry[]> {
+        return sendRequest<FileEntry[]>('files.list', { userId, path })
+      },
+
+      async stat(userId: string, path: string): Promise<FileStat> {
+        return sendRequest<FileStat>('files.stat', { userId, path })
+      },
+    }
+    ;(context as { files: FilesAPI }).files = filesApi
+  }
+
   // Add storage API if permitted (new collection-based storage)
   if (hasPermission('storage.collections')) {
     ;(context as { storage: StorageAPI }).storage = buildExtensionStorageAPI(sendRequest)
@@ -967,6 +1080,7 @@ export type {
   ChatMessage,
   ChatOptions,
   GetModelsOptions,
+  EmbedOptions,
   StreamEvent,
   // Storage and secrets
   StorageAPI,
@@ -126,6 +126,9 @@ export interface ExtensionContext {
   /** Chat access (if permitted) */
   readonly chat?: ChatAPI
 
+  /** Sandboxed file access within user-approved directories (if permitted) */
+  readonly files?: FilesAPI
+
   /** Collection-based document storage (if permitted) */
   readonly storage?: StorageAPI
 
@@ -260,6 +263,32 @@ export type SchedulerSchedule =
   | { type: 'at'; at: string }
   | { type: 'cron'; cron: string; timezone?: string }
   | { type: 'interval'; everyMs: number }
+  | {
+      type: 'rrule'
+      /** RFC 5545 recurrence rule, e.g. `FREQ=MONTHLY;BYDAY=MO,TU,WE,TH,FR;BYSETPOS=-1` */
+      rrule: string
+      /** Start of the recurrence (ISO 8601). Without an offset it is local to `timezone` */
+      dtstart: string
+      /** IANA time zone the rule is evaluated in. Defaults to UTC */
+      timezone?: string
+      /** Excluded occurrences (ISO 8601) */
+      exdates?: string[]
+    }
+
+/**
+ * Retry policy for failed scheduler runs.
+ * The delay before retry n is `initialDelayMs * multiplier^(n - 1)`, capped at `maxDelayMs`.
+ */
+export interface SchedulerRetryPolicy {
+  /** Maximum number of retries after a failed run */
+  maxAttempts: number
+  /** Delay before the first retry, in milliseconds */
+  initialDelayMs: number
+  /** Factor the delay grows by for each retry. Defaults to 2 */
+  multiplier?: number
+  /** Maximum delay between retries, in milliseconds */
+  maxDelayMs?: number
+}
 
 /**
  * Scheduler job request
@@ -269,6 +298,12 @@ export interface SchedulerJobRequest {
   schedule: SchedulerSchedule
   payload?: Record<string, unknown>
   misfire?: 'run_once' | 'skip'
+  /**
+   * Retry runs whose onFire callback throws, with backoff.
+   * Retries fire alongside the regular schedule; the next regular fire
+   * cancels any pending retry.
+   */
+  retry?: SchedulerRetryPolicy
   /**
    * User ID for the job owner.
    * All scheduled jobs must be associated with a user. The userId
@@ -288,6 +323,10 @@ export interface SchedulerFirePayload {
   delayMs: number
   /** User ID for the job owner */
   userId: string
+  /** Retry attempt number, starting at 1. Not set for regular fires */
+  retryAttempt?: number
+  /** Set when the user started the run (run now) instead of the schedule */
+  manual?: boolean
 }
 
 /**
@@ -332,10 +371,170 @@ export interface ChatInstructionMessage {
 }
 
 /**
- * Chat API for appending instructions
+ * Query for reading a user's chat history
+ */
+export interface ChatHistoryQuery {
+  /** User whose chat history is read. Use context.userId from the ExecutionContext. */
+  userId: string
+  /** Maximum number of items to return (default 20, max 100) */
+  limit?: number
+  /** Number of items to skip */
+  offset?: number
+}
+
+/**
+ * Conversation as seen by extensions
+ */
+export interface ChatConversationSummary {
+  id: string
+  title?: string
+  active: boolean
+  /** ISO timestamp */
+  createdAt: string
+  /** ISO timestamp of the latest interaction */
+  lastMessageAt?: string
+}
+
+/**
+ * Message in a chat history interaction.
+ * Only messages written by the user or the assistant are exposed.
+ */
+export interface ChatHistoryMessage {
+  role: 'user' | 'assistant'
+  text: string
+  /** ISO timestamp */
+  createdAt: string
+}
+
+/**
+ * Interaction (one user turn with its replies) as seen by extensions
+ */
+export interface ChatHistoryInteraction {
+  id: string
+  conversationId: string
+  /** ISO timestamp */
+  createdAt: string
+  messages: ChatHistoryMessage[]
+}
+
+/**
+ * The user's current conversation with its most recent interactions
+ */
+export interface ChatCurrentConversation extends ChatConversationSummary {
+  /** Most recent interactions, newest first */
+  interactions: ChatHistoryInteraction[]
+}
+
+/**
+ * Chat API for appending instructions and reading conversations
  */
 export interface ChatAPI {
+  /**
+   * Append an instruction message to a conversation.
+   * Requires "chat.message.write".
+   */
   appendInstruction(message: ChatInstructionMessage): Promise<void>
+
+  /**
+   * List the user's active conversations, newest first.
+   * Requires "chat.history.read".
+   */
+  listConversations(query: ChatHistoryQuery): Promise<ChatConversationSummary[]>
+
+  /**
+   * Get interactions in one of the user's conversations, newest first.
+   * Requires "chat.history.read".
+   */
+  getInteractions(conversationId: string, query: ChatHistoryQuery): Promise<ChatHistoryInteraction[]>
+
+  /**
+   * Get the user's current conversation, or null if there is none.
+   * `limit` controls how many recent interactions are included.
+   * Requires "chat.current.read".
+   */
+  getCurrentConversation(query: ChatHistoryQuery): Promise<ChatCurrentConversation | null>
+}
+
+/**
+ * Access level of an approved directory root
+ */
+export type FileRootAccess = 'read' | 'readwrite'
+
+/**
+ * Directory the user has approved for an extension
+ */
+export interface FileRoot {
+  /** Absolute path of the directory */
+  path: string
+  access: FileRootAccess
+}
+
+/**
+ * Encoding used when reading and writing file content
+ */
+export type FileEncoding = 'utf8' | 'base64'
+
+/**
+ * Entry returned when listing a directory
+ */
+export interface FileEntry {
+  name: string
+  /** Absolute path of the entry */
+  path: string
+  type: 'file' | 'directory' | 'other'
+}
+
+/**
+ * File or directory metadata
+ */
+export interface FileStat {
+  /** Absolute path */
+  path: string
+  type: 'file' | 'directory' | 'other'
+  /** Size in bytes */
+  size: number
+  /** ISO timestamp */
+  modifiedAt: string
+  /** ISO timestamp */
+  createdAt: string
+}
+
+/**
+ * Sandboxed file access API.
+ *
+ * Paths must be absolute and inside one of the directory roots the user has
+ * approved for the extension. Roots are approved per user, so every call
+ * takes the user ID (use context.userId from the ExecutionContext).
+ * Reading requires "files.read"; writing requires "files.write" and a root
+ * approved with "readwrite" access.
+ */
+export interface FilesAPI {
+  /**
+   * List the directory roots the user has approved for this extension.
+   */
+  listRoots(userId: string): Promise<FileRoot[]>
+
+  /**
+   * Read a file.
+   * @param encoding - Content encoding (default 'utf8')
+   */
+  readFile(userId: string, path: string, encoding?: FileEncoding): Promise<string>
+
+  /**
+   * Write a file, creating missing parent directories inside the root.
+   * @param encoding - Content encoding (default 'utf8')
+   */
+  writeFile(userId: string, path: string, content: string, encoding?: FileEncoding): Promise<void>
+
+  /**
+   * List the entries of a directory.
+   */
+  list(userId: string, path: string): Promise<FileEntry[]>
+
+  /**
+   * Get metadata for a file or directory.
+   */
+  stat(userId: string, path: string): Promise<FileStat>
 }
 
 
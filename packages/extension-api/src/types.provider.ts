@@ -31,8 +31,11 @@ export interface AIProvider {
 
   /**
    * Optional: Generate embeddings
+   * @param texts Texts to embed
+   * @param options Optional model and settings from the user's model configuration
+   * @returns One vector per text, in the same order
    */
-  embed?(texts: string[]): Promise<number[][]>
+  embed?(texts: string[], options?: EmbedOptions): Promise<number[][]>
 }
 
 /**
@@ -54,13 +57,61 @@ export interface ModelInfo {
  */
 export interface ChatMessage {
   role: 'user' | 'assistant' | 'system' | 'tool'
+  /**
+   * Message text. When the message has attachments, their descriptions are
+   * appended, so providers that ignore `parts` still know about them.
+   */
   content: string
+  /**
+   * Content parts for multimodal messages (text, images and files).
+   * Only set for user messages with attachments; `content` is the text fallback.
+   */
+  parts?: ChatContentPart[]
   /** For assistant messages: tool calls made by the model */
   tool_calls?: ToolCall[]
   /** For tool messages: the ID of the tool call this is a response to */
   tool_call_id?: string
 }
 
+/**
+ * Part of a multimodal chat message
+ */
+export type ChatContentPart = ChatTextPart | ChatImagePart | ChatFilePart
+
+/**
+ * Text content
+ */
+export interface ChatTextPart {
+  type: 'text'
+  text: string
+}
+
+/**
+ * Image content, e.g. a screenshot
+ */
+export interface ChatImagePart {
+  type: 'image'
+  /** MIME type, e.g. image/png */
+  mimeType: string
+  /** Base64-encoded image data */
+  data: string
+  /** Original file name */
+  name?: string
+}
+
+/**
+ * Other file content, e.g. a PDF
+ */
+export interface ChatFilePart {
+  type: 'file'
+  /** Original file name */
+  name: string
+  /** MIME type, e.g. application/pdf */
+  mimeType: string
+  /** Base64-encoded file data */
+  data: string
+}
+
 /**
  * A tool call made by the model
  */
@@ -101,6 +152,16 @@ export interface GetModelsOptions {
   settings?: Record<string, unknown>
 }
 
+/**
+ * Options for embed
+ */
+export interface EmbedOptions {
+  /** Chat model configured by the user; the provider may pick a matching embedding model */
+  model?: string
+  /** Provider-specific settings from model configuration */
+  settings?: Record<string, unknown>
+}
+
 /**
  * Streaming events from chat
  */
@@ -56,9 +56,14 @@ export type {
   AIProvider,
   ModelInfo,
   ChatMessage,
+  ChatContentPart,
+  ChatTextPart,
+  ChatImagePart,
+  ChatFilePart,
   ToolCall,
   ChatOptions,
   GetModelsOptions,
+  EmbedOptions,
   StreamEvent,
 } from './types.provider.js'
 
@@ -77,12 +82,24 @@ export type {
   ActionsAPI,
   EventsAPI,
   SchedulerSchedule,
+  SchedulerRetryPolicy,
   SchedulerJobRequest,
   SchedulerFirePayload,
   SchedulerAPI,
   UserProfile,
   UserAPI,
   ChatInstructionMessage,
+  ChatHistoryQuery,
+  ChatConversationSummary,
+  ChatHistoryMessage,
+  ChatHistoryInteraction,
+  ChatCurrentConversation,
+  FileRootAccess,
+  FileRoot,
+  FileEncoding,
+  FileEntry,
+  FileStat,
+  FilesAPI,
   ChatAPI,
   LogAPI,
   ExtensionModule,
@@ -1,18 +1,24 @@
 /**
  * Chat Request Handler
  *
- * Handles chat.appendInstruction requests.
+ * Handles chat.appendInstruction and chat history read requests.
  */
 
-import type { RequestMethod } from '@stina/extension-api'
+import type { ChatHistoryQuery, RequestMethod } from '@stina/extension-api'
 import type { RequestHandler, HandlerContext } from './ExtensionHost.handlers.js'
-import { getPayloadValue } from './ExtensionHost.handlers.js'
+import { getPayloadValue, getRequiredString } from './ExtensionHost.handlers.js'
+import { validateUserId } from './ExtensionHost.validation.js'
 
 /**
  * Handler for chat-related requests
  */
 export class ChatHandler implements RequestHandler {
-  readonly methods = ['chat.appendInstruction'] as const
+  readonly methods = [
+    'chat.appendInstruction',
+    'chat.listConversations',
+    'chat.getInteractions',
+    'chat.getCurrentConversation',
+  ] as const
 
   /**
    * Handle a chat request
@@ -55,8 +61,78 @@ export class ChatHandler implements RequestHandler {
         return undefined
       }
 
+      case 'chat.listConversations': {
+        const check = ctx.extension.permissionChecker.checkChatHistoryRead()
+        if (!check.allowed) {
+          throw new Error(check.reason)
+        }
+
+        if (!ctx.options.chat) {
+          throw new Error('Chat bridge not configured')
+        }
+
+        return ctx.options.chat.listConversations(ctx.extensionId, parseHistoryQuery(payload))
+      }
+
+      case 'chat.getInteractions': {
+        const check = ctx.extension.permissionChecker.checkChatHistoryRead()
+        if (!check.allowed) {
+          throw new Error(check.reason)
+        }
+
+        if (!ctx.options.chat) {
+          throw new Error('Chat bridge not configured')
+        }
+
+        const conversationId = getPayloadValue<string>(payload, 'conversationId')
+        if (!conversationId || typeof conversationId !== 'string') {
+          throw new Error('conversationId is required')
+        }
+
+        return ctx.options.chat.getInteractions(
+          ctx.extensionId,
+          conversationId,
+          parseHistoryQuery(payload)
+        )
+      }
+
+      case 'chat.getCurrentConversation': {
+        const check = ctx.extension.permissionChecker.checkChatCurrentRead()
+        if (!check.allowed) {
+          throw new Error(check.reason)
+        }
+
+        if (!ctx.options.chat) {
+          throw new Error('Chat bridge not configured')
+        }
+
+        return ctx.options.chat.getCurrentConversation(ctx.extensionId, parseHistoryQuery(payload))
+      }
+
       default:
         throw new Error(`Unknown chat method: ${method}`)
     }
   }
 }
+
+/**
+ * Validate a chat history query payload.
+ * History is always read for an explicit user; there is no fallback user.
+ * Defaults and the maximum page size are applied by the chat history reader.
+ */
+function parseHistoryQuery(payload: unknown): ChatHistoryQuery {
+  const userId = getRequiredString(payload, 'userId', 'userId is required')
+  validateUserId(userId)
+
+  const limit = getPayloadValue<number>(payload, 'limit')
+  const offset = getPayloadValue<number>(payload, 'offset')
+
+  if (limit !== undefined && (!Number.isInteger(limit) || limit < 1)) {
+    throw new Error('limit must be a positive integer')
+  }
+  if (offset !== undefined && (!Number.isInteger(offset) || offset < 0)) {
+    throw new Error('offset must be a non-negative integer')
+  }
+
+  return { userId, limit, offset }
+}
@@ -0,0 +1,107 @@
+/**
+ * Files Request Handler
+ *
+ * Handles files.* requests for sandboxed file access.
+ */
+
+import type {
+  FileEncoding,
+  FileEntry,
+  FileRoot,
+  FileStat,
+  RequestMethod,
+} from '@stina/extension-api'
+import type { RequestHandler, HandlerContext } from './ExtensionHost.handlers.js'
+import { getPayloadValue, getRequiredString } from './ExtensionHost.handlers.js'
+import { validateUserId } from './ExtensionHost.validation.js'
+
+/**
+ * Callbacks for file operations.
+ * Implementations are responsible for restricting paths to approved roots.
+ */
+export interface FilesCallbacks {
+  listRoots(extensionId: string, userId: string): Promise<FileRoot[]>
+  readFile(extensionId: string, userId: string, path: string, encoding: FileEncoding): Promise<string>
+  writeFile(
+    extensionId: string,
+    userId: string,
+    path: string,
+    content: string,
+    encoding: FileEncoding
+  ): Promise<void>
+  list(extensionId: string, userId: string, path: string): Promise<FileEntry[]>
+  stat(extensionId: string, userId: string, path: string): Promise<FileStat>
+}
+
+/**
+ * Handler for files requests.
+ */
+export class FilesHandler implements RequestHandler {
+  readonly methods = [
+    'files.listRoots',
+    'files.readFile',
+    'files.writeFile',
+    'files.list',
+    'files.stat',
+  ] as const
+
+  constructor(private readonly callbacks: FilesCallbacks) {}
+
+  async handle(ctx: HandlerContext, method: RequestMethod, payload: unknown): Promise<unknown> {
+    // Writing needs files.write, everything else needs files.read
+    const check =
+      method === 'files.writeFile'
+        ? ctx.extension.permissionChecker.checkFilesWrite()
+        : ctx.extension.permissionChecker.checkFilesRead()
+    if (!check.allowed) {
+      throw new Error(check.reason)
+    }
+
+    const userId = getRequiredString(payload, 'userId')
+    validateUserId(userId)
+
+    switch (method) {
+      case 'files.listRoots':
+        return this.callbacks.listRoots(ctx.extensionId, userId)
+
+      case 'files.readFile': {
+        const path = getRequiredString(payload, 'path')
+        return this.callbacks.readFile(ctx.extensionId, userId, path, getEncoding(payload))
+      }
+
+      case 'files.writeFile': {
+        const path = getRequiredString(payload, 'path')
+        const content = getPayloadValue<string>(payload, 'content')
+        if (typeof content !== 'string') {
+          throw new Error('content must be a string')
+        }
+        await this.callbacks.writeFile(ctx.extensionId, userId, path, content, getEncoding(payload))
+        return undefined
+      }
+
+      case 'files.list': {
+        const path = getRequiredString(payload, 'path')
+        return this.callbacks.list(ctx.extensionId, userId, path)
+      }
+
+      case 'files.stat': {
+        const path = getRequiredString(payload, 'path')
+        return this.callbacks.stat(ctx.extensionId, userId, path)
+      }
+
+      default:
+        throw new Error(`Unknown files method: ${method}`)
+    }
+  }
+}
+
+/**
+ * Read and validate the optional encoding from a payload.
+ */
+function getEncoding(payload: unknown): FileEncoding {
+  const encoding = getPayloadValue<string>(payload, 'encoding') ?? 'utf8'
+  if (encoding !== 'utf8' && encoding !== 'base64') {
+    throw new Error(`Unsupported encoding: ${encoding}`)
+  }
+  return encoding
+}
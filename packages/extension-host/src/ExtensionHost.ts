@@ -15,6 +15,7 @@ import type {
   ChatMessage,
   ChatOptions,
   GetModelsOptions,
+  EmbedOptions,
   ModelInfo,
   SchedulerFirePayload,
   ActionResult,
@@ -333,6 +334,25 @@ export abstract class ExtensionHost extends EventEmitter<ExtensionHostEvents> {
     return this.sendProviderModelsRequest(provider.extensionId, providerId, options)
   }
 
+  /**
+   * Generate embeddings with a provider
+   * @param providerId The provider ID
+   * @param texts Texts to embed
+   * @param options Optional model and settings from the model configuration
+   * @returns One vector per text
+   */
+  async embed(providerId: string, texts: string[], options?: EmbedOptions): Promise<number[][]> {
+    const provider = this.getProvider(providerId)
+    if (!provider) {
+      throw new Error(`Provider "${providerId}" not found`)
+    }
+    if (!provider.supportsEmbeddings) {
+      throw new Error(`Provider "${providerId}" does not support embeddings`)
+    }
+
+    return this.sendProviderEmbedRequest(provider.extensionId, providerId, texts, options)
+  }
+
   /**
    * Execute a tool
    * @param extensionId The extension that provides the tool
@@ -499,7 +519,7 @@ export abstract class ExtensionHost extends EventEmitter<ExtensionHostEvents> {
   private handleProviderRegistered(
     extensionId: string,
     extension: LoadedExtension,
-    payload: { id: string; name: string }
+    payload: { id: string; name: string; supportsEmbeddings?: boolean }
   ): void {
     const check = extension.permissionChecker.checkProviderRegistration()
     if (!check.allowed) {
@@ -534,6 +554,7 @@ export abstract class ExtensionHost extends EventEmitter<ExtensionHostEvents> {
       extensionId,
       configView: manifestProvider?.configView,
       defaultSettings: manifestProvider?.defaultSettings,
+      supportsEmbeddings: payload.supportsEmbeddings === true,
     }
 
     extension.registeredProviders.set(payload.id, provider)
@@ -713,6 +734,16 @@ export abstract class ExtensionHost extends EventEmitter<ExtensionHostEvents> {
     options?: GetModelsOptions
   ): Promise<ModelInfo[]>
 
+  /**
+   * Send a provider embed request to a worker
+   */
+  protected abstract sendProviderEmbedRequest(
+    extensionId: string,
+    providerId: string,
+    texts: string[],
+    options?: EmbedOptions
+  ): Promise<number[][]>
+
   /**
    * Send a tool execute request to a worker
    * @param extensionId Extension ID
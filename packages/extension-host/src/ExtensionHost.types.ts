@@ -10,6 +10,10 @@ import type {
   LocalizedString,
   SchedulerJobRequest,
   ChatInstructionMessage,
+  ChatHistoryQuery,
+  ChatConversationSummary,
+  ChatHistoryInteraction,
+  ChatCurrentConversation,
   UserProfile,
 } from '@stina/extension-api'
 import type { PermissionChecker } from './PermissionChecker.js'
@@ -55,6 +59,8 @@ export interface ProviderInfo {
   configView?: ProviderConfigView
   /** Default settings for this provider */
   defaultSettings?: Record<string, unknown>
+  /** Whether the provider can generate embeddings */
+  supportsEmbeddings?: boolean
 }
 
 /**
@@ -104,6 +110,16 @@ export interface ExtensionHostOptions {
   }
   chat?: {
     appendInstruction: (extensionId: string, message: ChatInstructionMessage) => Promise<void>
+    listConversations: (extensionId: string, query: ChatHistoryQuery) => Promise<ChatConversationSummary[]>
+    getInteractions: (
+      extensionId: string,
+      conversationId: string,
+      query: ChatHistoryQuery
+    ) => Promise<ChatHistoryInteraction[]>
+    getCurrentConversation: (
+      extensionId: string,
+      query: ChatHistoryQuery
+    ) => Promise<ChatCurrentConversation | null>
   }
   user?: {
     getProfile: (extensionId: string) => Promise<UserProfile>
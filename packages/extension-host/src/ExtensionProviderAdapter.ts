@@ -6,7 +6,14 @@
  */
 
 import type { ExtensionHost, ProviderInfo } from './ExtensionHost.js'
-import type { ChatMessage, StreamEvent as ExtStreamEvent, ToolDefinition, ToolResult } from '@stina/extension-api'
+import type {
+  ChatContentPart,
+  ChatMessage,
+  StreamEvent as ExtStreamEvent,
+  ToolDefinition,
+  ToolResult,
+} from '@stina/extension-api'
+import { isImageMimeType, withAttachmentDescriptions } from '@stina/shared'
 
 /**
  * StreamEvent as expected by packages/chat
@@ -16,7 +23,7 @@ export type ChatStreamEvent =
   | { type: 'tool'; name: string; displayName?: string; payload: string }
   | { type: 'tool_result'; name: string; displayName?: string; result: string }
   | { type: 'content'; text: string }
-  | { type: 'done' }
+  | { type: 'done'; usage?: { inputTokens: number; outputTokens: number } }
   | { type: 'error'; error: Error }
 
 /**
@@ -26,9 +33,20 @@ export interface ChatMessage_Chat {
   type: 'user' | 'stina' | 'instruction' | 'information' | 'thinking' | 'tools'
   text?: string
   tools?: Array<{ name: string; payload: string; result: string }>
+  attachments?: ChatAttachment[]
   metadata: { createdAt: string; [key: string]: unknown }
 }
 
+/**
+ * File attached to a packages/chat user message
+ */
+export interface ChatAttachment {
+  id: string
+  name: string
+  mimeType: string
+  size: number
+}
+
 /**
  * Options for sendMessage call
  */
@@ -51,6 +69,18 @@ export interface ChatSendMessageOptions {
    * Called when emitting tool events to get a user-friendly name.
    */
   getToolDisplayName?: (toolId: string) => string | undefined
+  /** Load the data of a message attachment */
+  loadAttachment?: (attachment: ChatAttachment) => Promise<Uint8Array | null>
+}
+
+/**
+ * Options for embed call
+ */
+export interface ChatEmbedOptions {
+  /** Provider-specific settings (e.g., URL for Ollama) */
+  settings?: Record<string, unknown>
+  /** Embedding model ID to use */
+  modelId?: string
 }
 
 /**
@@ -65,6 +95,7 @@ export interface ChatAIProvider {
     onEvent: (event: ChatStreamEvent) => void,
     options?: ChatSendMessageOptions
   ): Promise<void>
+  embed?(texts: string[], options?: ChatEmbedOptions): Promise<number[][]>
 }
 
 /**
@@ -84,6 +115,38 @@ function convertToExtensionMessage(msg: ChatMessage_Chat): ChatMessage | null {
   }
 }
 
+/**
+ * Convert a user message with attachments to an extension-api ChatMessage.
+ * The content describes the attachments for providers that ignore `parts`.
+ * Attachments that cannot be loaded are only described.
+ */
+async function convertUserMessageWithAttachments(
+  msg: ChatMessage_Chat,
+  attachments: ChatAttachment[],
+  loadAttachment: ChatSendMessageOptions['loadAttachment']
+): Promise<ChatMessage> {
+  const text = msg.text || ''
+  const parts: ChatContentPart[] = text.trim() ? [{ type: 'text', text }] : []
+
+  for (const attachment of attachments) {
+    const data = await loadAttachment?.(attachment).catch(() => null)
+    if (!data) continue
+
+    const base64 = Buffer.from(data).toString('base64')
+    parts.push(
+      isImageMimeType(attachment.mimeType)
+        ? { type: 'image', mimeType: attachment.mimeType, data: base64, name: attachment.name }
+        : { type: 'file', name: attachment.name, mimeType: attachment.mimeType, data: base64 }
+    )
+  }
+
+  return {
+    role: 'user',
+    content: withAttachmentDescriptions(text, attachments),
+    parts,
+  }
+}
+
 /**
  * Creates an adapter that wraps an extension provider to work with ChatOrchestrator
  */
@@ -110,14 +173,23 @@ export function createExtensionProviderAdapter(
           ? [{ role: 'system', content: systemPrompt }]
           : []
 
-      const extMessages: ChatMessage[] = [
-        ...systemMessages,
-        ...messages
-          .map(convertToExtensionMessage)
-          .filter((m): m is ChatMessage => m !== null),
-      ]
-
       try {
+        const convertedMessages = await Promise.all(
+          messages.map((message) =>
+            message.type === 'user' && message.attachments && message.attachments.length > 0
+              ? convertUserMessageWithAttachments(
+                  message,
+                  message.attachments,
+                  options?.loadAttachment
+                )
+              : convertToExtensionMessage(message)
+          )
+        )
+        const extMessages: ChatMessage[] = [
+          ...systemMessages,
+          ...convertedMessages.filter((m): m is ChatMessage => m !== null),
+        ]
+
         // Build chat options with settings from modelConfig
         const chatOptions = {
           model: options?.modelId,
@@ -131,6 +203,9 @@ export function createExtensionProviderAdapter(
         const maxIterations = 10 // Safety limit to prevent infinite loops
         let iterations = 0
 
+        // Usage is reported per provider call, so sum it over all loop iterations
+        let usage: { inputTokens: number; outputTokens: number } | undefined
+
         while (continueLoop && iterations < maxIterations) {
           iterations++
           continueLoop = false // Will be set to true if we need another iteration
@@ -170,6 +245,13 @@ export function createExtensionProviderAdapter(
               if (idx !== -1) {
                 pendingToolCalls.splice(idx, 1)
               }
+            } else if (event.type === 'done') {
+              if (event.usage) {
+                usage = {
+                  inputTokens: (usage?.inputTokens ?? 0) + event.usage.inputTokens,
+                  outputTokens: (usage?.outputTokens ?? 0) + event.usage.outputTokens,
+                }
+              }
             } else {
               const converted = convertStreamEvent(event)
               if (converted) {
@@ -238,7 +320,7 @@ export function createExtensionProviderAdapter(
         }
 
         // Always emit done at the end
-        onEvent({ type: 'done' })
+        onEvent({ type: 'done', usage })
       } catch (error) {
         onEvent({
           type: 'error',
@@ -246,6 +328,17 @@ export function createExtensionProviderAdapter(
         })
       }
     },
+
+    // Only expose embed when the extension provider implements it
+    ...(providerInfo.supportsEmbeddings
+      ? {
+          embed: (texts: string[], options?: ChatEmbedOptions) =>
+            extensionHost.embed(providerInfo.id, texts, {
+              model: options?.modelId,
+              settings: options?.settings,
+            }),
+        }
+      : {}),
   }
 }
 
@@ -0,0 +1,113 @@
+/**
+ * FileSandbox Tests
+ *
+ * Tests for file access restricted to approved roots.
+ */
+
+import { describe, it, expect, beforeEach, afterEach } from 'vitest'
+import { FileSandbox, isWithin } from './FileSandbox.js'
+import type { FileRoot } from '@stina/extension-api'
+import { mkdirSync, mkdtempSync, readFileSync, realpathSync, rmSync, symlinkSync, writeFileSync } from 'node:fs'
+import { join } from 'node:path'
+import { tmpdir } from 'node:os'
+
+describe('FileSandbox', () => {
+  let tempDir: string
+  let notesDir: string
+  let outsideDir: string
+  let roots: FileRoot[]
+  let sandbox: FileSandbox
+
+  beforeEach(() => {
+    tempDir = realpathSync(mkdtempSync(join(tmpdir(), 'stina-files-test-')))
+    notesDir = join(tempDir, 'notes')
+    outsideDir = join(tempDir, 'outside')
+    mkdirSync(notesDir)
+    mkdirSync(outsideDir)
+    writeFileSync(join(notesDir, 'todo.md'), '# Todo')
+    writeFileSync(join(outsideDir, 'secret.txt'), 'secret')
+
+    roots = [{ path: notesDir, access: 'read' }]
+    sandbox = new FileSandbox({
+      list: async (extensionId, userId) => (extensionId === 'ext-1' && userId === 'user-1' ? roots : []),
+    })
+  })
+
+  afterEach(() => {
+    if (tempDir) {
+      rmSync(tempDir, { recursive: true, force: true })
+    }
+  })
+
+  it('should read files inside an approved root', async () => {
+    const content = await sandbox.readFile('ext-1', 'user-1', join(notesDir, 'todo.md'), 'utf8')
+    expect(content).toBe('# Todo')
+  })
+
+  it('should list and stat inside an approved root', async () => {
+    const entries = await sandbox.list('ext-1', 'user-1', notesDir)
+    expect(entries).toEqual([{ name: 'todo.md', path: join(notesDir, 'todo.md'), type: 'file' }])
+
+    const stat = await sandbox.stat('ext-1', 'user-1', join(notesDir, 'todo.md'))
+    expect(stat.type).toBe('file')
+    expect(stat.size).toBe(6)
+  })
+
+  it('should reject paths outside the approved roots', async () => {
+    await expect(
+      sandbox.readFile('ext-1', 'user-1', join(outsideDir, 'secret.txt'), 'utf8')
+    ).rejects.toThrow('not in an approved directory')
+    await expect(
+      sandbox.readFile('ext-1', 'user-1', join(notesDir, '..', 'outside', 'secret.txt'), 'utf8')
+    ).rejects.toThrow('not in an approved directory')
+  })
+
+  it('should reject symlinks that point outside a root', async () => {
+    symlinkSync(outsideDir, join(notesDir, 'link'))
+    await expect(
+      sandbox.readFile('ext-1', 'user-1', join(notesDir, 'link', 'secret.txt'), 'utf8')
+    ).rejects.toThrow('not in an approved directory')
+  })
+
+  it('should reject relative paths', async () => {
+    await expect(sandbox.readFile('ext-1', 'user-1', 'notes/todo.md', 'utf8')).rejects.toThrow(
+      'must be absolute'
+    )
+  })
+
+  it('should scope roots per extension and user', async () => {
+    await expect(
+      sandbox.readFile('ext-1', 'user-2', join(notesDir, 'todo.md'), 'utf8')
+    ).rejects.toThrow('not in an approved directory')
+    await expect(
+      sandbox.readFile('ext-2', 'user-1', join(notesDir, 'todo.md'), 'utf8')
+    ).rejects.toThrow('not in an approved directory')
+  })
+
+  it('should only write to roots approved for writing', async () => {
+    const target = join(notesDir, 'reports', 'weekly.md')
+    await expect(sandbox.writeFile('ext-1', 'user-1', target, 'report', 'utf8')).rejects.toThrow(
+      'approved for writing'
+    )
+
+    roots = [{ path: notesDir, access: 'readwrite' }]
+    await sandbox.writeFile('ext-1', 'user-1', target, 'report', 'utf8')
+    expect(readFileSync(target, 'utf8')).toBe('report')
+  })
+
+  it('should reject files larger than the read limit', async () => {
+    const small = new FileSandbox({ list: async () => roots }, { maxReadBytes: 3 })
+    await expect(small.readFile('ext-1', 'user-1', join(notesDir, 'todo.md'), 'utf8')).rejects.toThrow(
+      'too large'
+    )
+  })
+})
+
+describe('isWithin', () => {
+  it('should treat siblings with a shared prefix as outside', () => {
+    expect(isWithin('/data/notes', '/data/notes')).toBe(true)
+    expect(isWithin('/data/notes', '/data/notes/a.md')).toBe(true)
+    expect(isWithin('/data/notes', '/data/notes-private/a.md')).toBe(false)
+    expect(isWithin('/data/notes', '/data')).toBe(false)
+  })
+})
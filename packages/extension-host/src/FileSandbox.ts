@@ -0,0 +1,210 @@
+/**
+ * File Sandbox
+ *
+ * Performs file operations for extensions, restricted to the directory roots
+ * a user has approved for each extension. Node.js only.
+ */
+
+import { mkdir, readdir, readFile, realpath, stat, writeFile } from 'node:fs/promises'
+import { basename, dirname, isAbsolute, join, relative, resolve, sep } from 'node:path'
+import type {
+  FileEncoding,
+  FileEntry,
+  FileRoot,
+  FileRootAccess,
+  FileStat,
+} from '@stina/extension-api'
+import type { FilesCallbacks } from './ExtensionHost.handlers.files.js'
+
+/** Default maximum size of a file that can be read (10 MB) */
+const DEFAULT_MAX_READ_BYTES = 10 * 1024 * 1024
+
+/**
+ * Source of the directory roots a user has approved for an extension
+ */
+export interface FileRootsProvider {
+  list(extensionId: string, userId: string): Promise<FileRoot[]>
+}
+
+export interface FileSandboxOptions {
+  /** Maximum size of a file that can be read, in bytes */
+  maxReadBytes?: number
+}
+
+/**
+ * File access restricted to approved roots.
+ *
+ * Both the requested path and the roots are resolved through symlinks before
+ * the containment check, so a link inside a root cannot be used to escape it.
+ */
+export class FileSandbox implements FilesCallbacks {
+  private readonly maxReadBytes: number
+
+  constructor(
+    private readonly roots: FileRootsProvider,
+    options: FileSandboxOptions = {}
+  ) {
+    this.maxReadBytes = options.maxReadBytes ?? DEFAULT_MAX_READ_BYTES
+  }
+
+  async listRoots(extensionId: string, userId: string): Promise<FileRoot[]> {
+    return this.roots.list(extensionId, userId)
+  }
+
+  async readFile(
+    extensionId: string,
+    userId: string,
+    path: string,
+    encoding: FileEncoding
+  ): Promise<string> {
+    const target = await this.resolveExisting(extensionId, userId, path, 'read')
+    const info = await stat(target)
+    if (!info.isFile()) {
+      throw new Error(`Not a file: ${path}`)
+    }
+    if (info.size > this.maxReadBytes) {
+      throw new Error(`File is too large to read (${info.size} bytes, max ${this.maxReadBytes})`)
+    }
+    const content = await readFile(target)
+    return content.toString(encoding)
+  }
+
+  async writeFile(
+    extensionId: string,
+    userId: string,
+    path: string,
+    content: string,
+    encoding: FileEncoding
+  ): Promise<void> {
+    const { target, root } = await this.resolveForWrite(extensionId, userId, path)
+    if (target === root) {
+      throw new Error(`Cannot write to a root directory: ${path}`)
+    }
+    await mkdir(dirname(target), { recursive: true })
+    await writeFile(target, Buffer.from(content, encoding))
+  }
+
+  async list(extensionId: string, userId: string, path: string): Promise<FileEntry[]> {
+    const target = await this.resolveExisting(extensionId, userId, path, 'read')
+    const entries = await readdir(target, { withFileTypes: true })
+    return entries.map((entry) => ({
+      name: entry.name,
+      path: join(target, entry.name),
+      type: entry.isFile() ? 'file' : entry.isDirectory() ? 'directory' : 'other',
+    }))
+  }
+
+  async stat(extensionId: string, userId: string, path: string): Promise<FileStat> {
+    const target = await this.resolveExisting(extensionId, userId, path, 'read')
+    const info = await stat(target)
+    return {
+      path: target,
+      type: info.isFile() ? 'file' : info.isDirectory() ? 'directory' : 'other',
+      size: info.size,
+      modifiedAt: info.mtime.toISOString(),
+      createdAt: info.birthtime.toISOString(),
+    }
+  }
+
+  /**
+   * Resolve an existing path and check that it is inside an approved root.
+   */
+  private async resolveExisting(
+    extensionId: string,
+    userId: string,
+    path: string,
+    access: FileRootAccess
+  ): Promise<string> {
+    const target = await realpath(normalizeAbsolute(path))
+    await this.findRoot(extensionId, userId, target, access, path)
+    return target
+  }
+
+  /**
+   * Resolve a path that may not exist yet and check that it is inside a
+   * root approved for writing. Symlinks are resolved on the nearest existing
+   * ancestor.
+   */
+  private async resolveForWrite(
+    extensionId: string,
+    userId: string,
+    path: string
+  ): Promise<{ target: string; root: string }> {
+    const requested = normalizeAbsolute(path)
+    const missing: string[] = []
+    let existing = requested
+
+    for (;;) {
+      try {
+        existing = await realpath(existing)
+        break
+      } catch (error) {
+        if ((error as NodeJS.ErrnoException).code !== 'ENOENT') throw error
+        const parent = dirname(existing)
+        if (parent === existing) throw error
+        missing.unshift(basename(existing))
+        existing = parent
+      }
+    }
+
+    const target = join(existing, ...missing)
+    const root = await this.findRoot(extensionId, userId, target, 'readwrite', path)
+    return { target, root }
+  }
+
+  /**
+   * Find the approved root containing the resolved target.
+   * @returns The resolved root path
+   * @throws If no root with the required access contains the target
+   */
+  private async findRoot(
+    extensionId: string,
+    userId: string,
+    target: string,
+    access: FileRootAccess,
+    requestedPath: string
+  ): Promise<string> {
+    const roots = await this.roots.list(extensionId, userId)
+    for (const root of roots) {
+      if (access === 'readwrite' && root.access !== 'readwrite') continue
+
+      let rootPath: string
+      try {
+        rootPath = await realpath(root.path)
+      } catch {
+        // Approved directory no longer exists
+        continue
+      }
+
+      if (isWithin(rootPath, target)) {
+        return rootPath
+      }
+    }
+
+    throw new Error(
+      access === 'readwrite'
+        ? `Path is not in a directory approved for writing: ${requestedPath}`
+        : `Path is not in an approved directory: ${requestedPath}`
+    )
+  }
+}
+
+/**
+ * Check that a path is absolute and normalize it.
+ */
+function normalizeAbsolute(path: string): string {
+  if (!isAbsolute(path)) {
+    throw new Error(`Path must be absolute: ${path}`)
+  }
+  return resolve(path)
+}
+
+/**
+ * Check whether target is root itself or a descendant of root.
+ * Both paths must already be resolved.
+ */
+export function isWithin(root: string, target: string): boolean {
+  const rel = relative(root, target)
+  if (rel === '') return true
+  return rel !== '..' && !rel.startsWith(`..${sep}`) && !isAbsolute(rel)
+}
@@ -17,6 +17,7 @@ import type {
   ChatMessage,
   ChatOptions,
   GetModelsOptions,
+  EmbedOptions,
   ModelInfo,
   ToolResult,
   ActionResult,
@@ -36,6 +37,8 @@ import { ChatHandler } from './ExtensionHost.handlers.chat.js'
 import { NetworkHandler } from './ExtensionHost.handlers.network.js'
 import { NewStorageHandler, type NewStorageCallbacks } from './ExtensionHost.handlers.newStorage.js'
 import { SecretsHandler, type SecretsCallbacks } from './ExtensionHost.handlers.secrets.js'
+import { FilesHandler } from './ExtensionHost.handlers.files.js'
+import { FileSandbox, type FileRootsProvider } from './FileSandbox.js'
 import { ToolsRequestHandler } from './ExtensionHost.handlers.tools.js'
 
 // ============================================================================
@@ -56,6 +59,8 @@ export interface NodeExtensionHostOptions extends ExtensionHostOptions {
   storageCallbacks?: NewStorageCallbacks
   /** Secrets callbacks - if provided, enables secrets.manage permission */
   secretsCallbacks?: SecretsCallbacks
+  /** Approved directory roots - if provided, enables files.read and files.write permissions */
+  fileRoots?: FileRootsProvider
 }
 
 // ============================================================================
@@ -74,6 +79,7 @@ export class NodeExtensionHost extends ExtensionHost {
   private readonly toolPending = new PendingRequestManager(60000)
   private readonly actionPending = new PendingRequestManager(30000)
   private readonly modelsPending = new PendingRequestManager(30000)
+  private readonly embedPending = new PendingRequestManager(60000)
   /** Background task manager for all extensions */
   private readonly backgroundTaskManager: BackgroundTaskManager
   private readonly nodeOptions: NodeExtensionHostOptions
@@ -120,7 +126,7 @@ export class NodeExtensionHost extends ExtensionHost {
   }
 
   /**
-   * Override to add Node.js-specific handlers for storage and secrets.
+   * Override to add Node.js-specific handlers for storage, secrets and files.
    */
   protected override createHandlerRegistry(): HandlerRegistry {
     const registry = new HandlerRegistry()
@@ -158,6 +164,11 @@ export class NodeExtensionHost extends ExtensionHost {
       registry.register(new SecretsHandler(options.secretsCallbacks))
     }
 
+    // Register files handler if approved roots can be looked up
+    if (options.fileRoots) {
+      registry.register(new FilesHandler(new FileSandbox(options.fileRoots)))
+    }
+
     return registry
   }
 
@@ -290,6 +301,7 @@ export class NodeExtensionHost extends ExtensionHost {
     this.toolPending.rejectAll(reason)
     this.actionPending.rejectAll(reason)
     this.modelsPending.rejectAll(reason)
+    this.embedPending.rejectAll(reason)
 
     this.workers.delete(extensionId)
   }
@@ -472,6 +484,28 @@ export class NodeExtensionHost extends ExtensionHost {
     return promise
   }
 
+  protected async sendProviderEmbedRequest(
+    extensionId: string,
+    providerId: string,
+    texts: string[],
+    options?: EmbedOptions
+  ): Promise<number[][]> {
+    const requestId = generateMessageId()
+
+    // Create pending request with automatic timeout handling
+    const promise = this.embedPending.create<number[][]>(requestId, {
+      timeoutMessage: 'Embed request timeout',
+    })
+
+    this.sendToWorker(extensionId, {
+      type: 'provider-embed-request',
+      id: requestId,
+      payload: { providerId, texts, options },
+    })
+
+    return promise
+  }
+
   protected async sendToolExecuteRequest(
     extensionId: string,
     toolId: string,
@@ -549,6 +583,17 @@ export class NodeExtensionHost extends ExtensionHost {
       return
     }
 
+    // Handle provider embed response using the pending manager
+    if (message.type === 'provider-embed-response') {
+      const { requestId, embeddings, error } = message.payload
+      if (error) {
+        this.embedPending.reject(requestId, error)
+      } else {
+        this.embedPending.resolve(requestId, embeddings)
+      }
+      return
+    }
+
     // Handle tool execute response using the pending manager
     if (message.type === 'tool-execute-response') {
       const { requestId, result, error } = message.payload
@@ -140,6 +140,36 @@ describe('PermissionChecker', () => {
     })
   })
 
+  describe('checkChatHistoryRead', () => {
+    it('should allow access when permission is granted', () => {
+      const checker = new PermissionChecker(['chat.history.read'])
+      const result = checker.checkChatHistoryRead()
+      expect(result.allowed).toBe(true)
+    })
+
+    it('should not be granted by chat.current.read', () => {
+      const checker = new PermissionChecker(['chat.current.read'])
+      const result = checker.checkChatHistoryRead()
+      expect(result.allowed).toBe(false)
+      expect(result.reason).toContain('chat.history.read')
+    })
+  })
+
+  describe('checkChatCurrentRead', () => {
+    it('should allow access when permission is granted', () => {
+      const checker = new PermissionChecker(['chat.current.read'])
+      const result = checker.checkChatCurrentRead()
+      expect(result.allowed).toBe(true)
+    })
+
+    it('should deny access when permission is not granted', () => {
+      const checker = new PermissionChecker(['chat.message.write'])
+      const result = checker.checkChatCurrentRead()
+      expect(result.allowed).toBe(false)
+      expect(result.reason).toContain('chat.current.read')
+    })
+  })
+
   describe('Security scenarios', () => {
     it('should prevent malicious collection names', () => {
       const checker = new PermissionChecker({
@@ -336,6 +336,58 @@ export class PermissionChecker {
     }
   }
 
+  /**
+   * Check if reading the user's conversation history is allowed
+   */
+  checkChatHistoryRead(): PermissionCheckResult {
+    if (this.hasPermission('chat.history.read')) {
+      return { allowed: true }
+    }
+    return {
+      allowed: false,
+      reason: 'Chat history read not allowed. Required permission: chat.history.read',
+    }
+  }
+
+  /**
+   * Check if reading the user's current conversation is allowed
+   */
+  checkChatCurrentRead(): PermissionCheckResult {
+    if (this.hasPermission('chat.current.read')) {
+      return { allowed: true }
+    }
+    return {
+      allowed: false,
+      reason: 'Current conversation read not allowed. Required permission: chat.current.read',
+    }
+  }
+
+  /**
+   * Check if reading files in approved directories is allowed
+   */
+  checkFilesRead(): PermissionCheckResult {
+    if (this.hasPermission('files.read')) {
+      return { allowed: true }
+    }
+    return {
+      allowed: false,
+      reason: 'File read not allowed. Required permission: files.read',
+    }
+  }
+
+  /**
+   * Check if writing files in approved directories is allowed
+   */
+  checkFilesWrite(): PermissionCheckResult {
+    if (this.hasPermission('files.write')) {
+      return { allowed: true }
+    }
+    return {
+      allowed: false,
+      reason: 'File write not allowed. Required permission: files.write',
+    }
+  }
+
   /**
    * Check if background workers access is allowed
    */
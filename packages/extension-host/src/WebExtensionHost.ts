@@ -15,6 +15,7 @@ import type {
   ChatMessage,
   ChatOptions,
   GetModelsOptions,
+  EmbedOptions,
   ModelInfo,
   ToolResult,
   ActionResult,
@@ -61,6 +62,7 @@ export class WebExtensionHost extends ExtensionHost {
   private readonly toolPending = new PendingRequestManager(60000)
   private readonly actionPending = new PendingRequestManager(30000)
   private readonly modelsPending = new PendingRequestManager(30000)
+  private readonly embedPending = new PendingRequestManager(60000)
   private readonly extensionStorage = new Map<string, Map<string, unknown>>()
   private readonly webOptions: WebExtensionHostOptions
 
@@ -192,6 +194,7 @@ export class WebExtensionHost extends ExtensionHost {
     this.toolPending.rejectAll(reason)
     this.actionPending.rejectAll(reason)
     this.modelsPending.rejectAll(reason)
+    this.embedPending.rejectAll(reason)
 
     this.workers.delete(extensionId)
     this.extensionStorage.delete(extensionId)
@@ -478,6 +481,28 @@ export class WebExtensionHost extends ExtensionHost {
     return promise
   }
 
+  protected async sendProviderEmbedRequest(
+    extensionId: string,
+    providerId: string,
+    texts: string[],
+    options?: EmbedOptions
+  ): Promise<number[][]> {
+    const requestId = generateMessageId()
+
+    // Create pending request with automatic timeout handling
+    const promise = this.embedPending.create<number[][]>(requestId, {
+      timeoutMessage: 'Embed request timeout',
+    })
+
+    this.sendToWorker(extensionId, {
+      type: 'provider-embed-request',
+      id: requestId,
+      payload: { providerId, texts, options },
+    })
+
+    return promise
+  }
+
   // Override to handle stream events and tool responses
   protected override handleWorkerMessage(extensionId: string, message: WorkerToHostMessage): void {
     // Handle streaming fetch acknowledgments for backpressure control
@@ -531,6 +556,17 @@ export class WebExtensionHost extends ExtensionHost {
       return
     }
 
+    // Handle provider embed response using the pending manager
+    if (message.type === 'provider-embed-response') {
+      const { requestId, embeddings, error } = message.payload
+      if (error) {
+        this.embedPending.reject(requestId, error)
+      } else {
+        this.embedPending.resolve(requestId, embeddings)
+      }
+      return
+    }
+
     // Delegate to parent
     super.handleWorkerMessage(extensionId, message)
   }
@@ -25,6 +25,8 @@ export type {
 // Node.js implementation
 export { NodeExtensionHost } from './NodeExtensionHost.js'
 export type { NodeExtensionHostOptions } from './NodeExtensionHost.js'
+export { FileSandbox } from './FileSandbox.js'
+export type { FileRootsProvider, FileSandboxOptions } from './FileSandbox.js'
 
 // Web/Browser implementation
 export { WebExtensionHost } from './WebExtensionHost.js'
@@ -39,6 +41,7 @@ export type {
   ChatAIProvider,
   ChatStreamEvent,
   ChatSendMessageOptions,
+  ChatEmbedOptions,
 } from './ExtensionProviderAdapter.js'
 
 // Tool adapter (bridges extension tools to packages/chat ToolRegistry)
@@ -71,6 +74,7 @@ export type {
   AIProvider,
   SchedulerJobRequest,
   SchedulerSchedule,
+  SchedulerRetryPolicy,
   SchedulerFirePayload,
   ChatInstructionMessage,
   StreamEvent,
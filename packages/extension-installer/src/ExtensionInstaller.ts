@@ -21,6 +21,8 @@ import type {
   InstallResult,
   SearchOptions,
   InstallLocalResult,
+  ExtensionActor,
+  ExtensionAuditEvent,
 } from './types.js'
 import type { ExtensionManifest } from '@stina/extension-api'
 
@@ -70,8 +72,13 @@ export class ExtensionInstaller {
 
   /**
    * Installs an extension from the registry
+   * @param actor Who installs the extension, for the audit log
    */
-  async install(extensionId: string, version?: string): Promise<InstallResult> {
+  async install(
+    extensionId: string,
+    version?: string,
+    actor?: ExtensionActor
+  ): Promise<InstallResult> {
     try {
       // Check if already installed
       if (this.storage.isInstalled(extensionId)) {
@@ -118,6 +125,14 @@ export class ExtensionInstaller {
       // Register the installation
       this.storage.registerExtension(extensionId, versionEntry.version)
 
+      await this.emitAuditEvent({
+        action: 'extension.installed',
+        extensionId,
+        version: versionEntry.version,
+        source: 'registry',
+        actor,
+      })
+
       return {
         success: true,
         extensionId,
@@ -137,15 +152,23 @@ export class ExtensionInstaller {
 
   /**
    * Uninstalls an extension
+   * @param actor Who uninstalls the extension, for the audit log
    */
-  async uninstall(extensionId: string, deleteData?: boolean): Promise<{ success: boolean; error?: string }> {
+  async uninstall(
+    extensionId: string,
+    deleteData?: boolean,
+    actor?: ExtensionActor
+  ): Promise<{ success: boolean; error?: string }> {
     try {
-      if (!this.storage.isInstalled(extensionId)) {
+      const installed = this.storage.getInstalledExtension(extensionId)
+      if (!installed) {
         return {
           success: false,
           error: `Extension "${extensionId}" is not installed`,
         }
       }
+      // Read before the files are removed, for the audit log
+      const permissions = this.storage.loadManifest(extensionId)?.permissions ?? []
 
       // Delete extension data from database if requested
       if (deleteData) {
@@ -171,6 +194,15 @@ export class ExtensionInstaller {
       // Unregister
       this.storage.unregisterExtension(extensionId)
 
+      await this.emitAuditEvent({
+        action: 'extension.uninstalled',
+        extensionId,
+        version: installed.version,
+        permissions,
+        source: installed.isUploadedLocal ? 'local' : 'registry',
+        actor,
+      })
+
       return { success: true }
     } catch (error) {
       return {
@@ -182,8 +214,13 @@ export class ExtensionInstaller {
 
   /**
    * Updates an extension to the latest version (or specific version)
+   * @param actor Who updates the extension, for the audit log
    */
-  async update(extensionId: string, version?: string): Promise<InstallResult> {
+  async update(
+    extensionId: string,
+    version?: string,
+    actor?: ExtensionActor
+  ): Promise<InstallResult> {
     const installed = this.storage.getInstalledExtension(extensionId)
 
     if (!installed) {
@@ -237,6 +274,14 @@ export class ExtensionInstaller {
     // Update registration
     this.storage.registerExtension(extensionId, targetVersion.version)
 
+    await this.emitAuditEvent({
+      action: 'extension.updated',
+      extensionId,
+      version: targetVersion.version,
+      source: 'registry',
+      actor,
+    })
+
     return {
       success: true,
       extensionId,
@@ -313,6 +358,33 @@ export class ExtensionInstaller {
     return this.storage.getExtensionPath(extensionId)
   }
 
+  // ===========================================================================
+  // Audit Log
+  // ===========================================================================
+
+  /**
+   * Pass an installer action to onAuditEvent.
+   * Permissions are read from the installed manifest unless given.
+   * Failures are logged so they never undo a finished installation.
+   */
+  private async emitAuditEvent(
+    event: Omit<ExtensionAuditEvent, 'permissions'> & { permissions?: string[] }
+  ): Promise<void> {
+    if (!this.options.onAuditEvent) return
+
+    try {
+      const permissions =
+        event.permissions ?? this.storage.loadManifest(event.extensionId)?.permissions ?? []
+      await this.options.onAuditEvent({ ...event, permissions })
+    } catch (error) {
+      this.options.logger?.warn('Failed to record extension audit event', {
+        extensionId: event.extensionId,
+        action: event.action,
+        error: error instanceof Error ? error.message : String(error),
+      })
+    }
+  }
+
   // ===========================================================================
   // Local Extension Operations
   // ===========================================================================
@@ -333,9 +405,13 @@ export class ExtensionInstaller {
    * WARNING: Local extensions are not verified and run at your own risk.
    *
    * @param zipStream - Readable stream containing the ZIP file
+   * @param actor - Who installs the extension, for the audit log
    * @returns Result of the installation
    */
-  async installLocalExtension(zipStream: Readable): Promise<InstallLocalResult> {
+  async installLocalExtension(
+    zipStream: Readable,
+    actor?: ExtensionActor
+  ): Promise<InstallLocalResult> {
     const tempPath = join(this.options.extensionsPath, `.temp-local-${randomUUID()}.zip`)
     let extractedPath: string | undefined
 
@@ -404,6 +480,13 @@ export class ExtensionInstaller {
         this.storage.registerUploadedLocalExtension(extensionId, version)
 
         this.options.logger?.info('Local extension installed', { extensionId, version })
+        await this.emitAuditEvent({
+          action: 'extension.installed',
+          extensionId,
+          version,
+          source: 'local',
+          actor,
+        })
 
         return {
           success: true,
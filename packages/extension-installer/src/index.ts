@@ -39,4 +39,6 @@ export type {
   InstallLocalResult,
   SearchOptions,
   ExtensionInstallerOptions,
+  ExtensionActor,
+  ExtensionAuditEvent,
 } from './types.js'
@@ -180,6 +180,27 @@ export interface InstallLocalResult {
   warning?: string
 }
 
+/**
+ * Who performed an installer action. Passed through to onAuditEvent.
+ */
+export interface ExtensionActor {
+  userId?: string
+  ip?: string
+}
+
+/**
+ * An installer action for the audit log
+ */
+export interface ExtensionAuditEvent {
+  action: 'extension.installed' | 'extension.updated' | 'extension.uninstalled'
+  extensionId: string
+  version: string | null
+  /** Permissions the extension is granted by its manifest */
+  permissions: string[]
+  source: 'registry' | 'local'
+  actor?: ExtensionActor
+}
+
 /**
  * Search options
  */
@@ -210,4 +231,6 @@ export interface ExtensionInstallerOptions {
   }
   /** Callback to delete extension data from the database */
   onDeleteExtensionData?: (extensionId: string) => Promise<void>
+  /** Callback to record successful installs, updates and uninstalls in an audit log */
+  onAuditEvent?: (event: ExtensionAuditEvent) => Promise<void> | void
 }
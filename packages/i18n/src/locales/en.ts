@@ -33,6 +33,14 @@ const en = {
     in_queue: 'In Queue:',
     remove_from_queue: 'Remove from queue',
     input_placeholder: 'Message Stina...',
+    attach_files: 'Attach files',
+    remove_attachment: 'Remove {{name}}',
+    edit_message: 'Edit message',
+    send_edited_message: 'Send',
+    regenerate: 'Regenerate answer',
+    previous_branch: 'Previous version',
+    next_branch: 'Next version',
+    branch_position: '{{index}} of {{count}}',
     tool_input: 'Input',
     tool_output: 'Output',
     tool_no_input: 'Tool has no input',
@@ -63,6 +71,27 @@ const en = {
           'You are creative, imaginative, and playful.\n- Offer fresh ideas, metaphors, and alternative angles.\n- Use vivid language when helpful, but keep instructions clear.\n- Balance originality with accuracy; do not invent facts.\n- Suggest multiple options or paths when relevant.\n- Keep it structured: start with a short overview, then expand if asked.\n- Avoid rambling; creativity should still move the user forward.',
       },
     },
+    context_summary: {
+      system_prompt:
+        'You condense chat conversations. Write a compact summary of the conversation you are given so that it can replace the original messages. Keep facts, decisions, open tasks, names, dates, and preferences the user has stated. Leave out greetings and small talk. Write in the same language as the conversation.',
+      request: 'Summarize the conversation above. Reply with the summary only.',
+      prefix:
+        "Summary of earlier parts of this conversation. Older messages were condensed to fit the model's context window:",
+      info: "Earlier messages were summarized to fit the model's context window.",
+    },
+    conversation_title: {
+      system_prompt:
+        'You name chat conversations. Reply with a short, descriptive title for the conversation you are given, at most six words. Write the title in English. Reply with the title only, without quotes or punctuation at the end.',
+      request: 'Suggest a title for the conversation above.',
+    },
+    memory: {
+      prompt:
+        'Things you remember about the user from earlier conversations. Use them when relevant, and do not mention that they come from memory unless asked:',
+    },
+    quiet_hours: {
+      digest:
+        'Quiet hours have ended. These {{count}} automatic messages arrived while the user did not want to be disturbed. Give the user one short summary of them and mention anything that still needs attention:\n\n{{items}}',
+    },
     tool_confirmation: {
       title: 'Confirm Tool Execution',
       default_prompt: 'Allow {{toolName}} to run?',
@@ -166,12 +195,12 @@ const en = {
     no_settings: 'This extension has no configurable settings.',
     settings_saved: 'Settings saved',
     settings_error: 'Failed to save settings',
-    // Admin-only messaging
+    // Admin-only and capability messaging
     admin_only: 'Admin only',
-    admin_only_install: 'Only administrators can install extensions',
-    admin_only_uninstall: 'Only administrators can uninstall extensions',
+    not_allowed_install: 'You are not allowed to install extensions',
+    not_allowed_uninstall: 'You are not allowed to uninstall extensions',
+    not_allowed_update: 'You are not allowed to update extensions',
     admin_only_enable_disable: 'Only administrators can enable or disable extensions',
-    admin_only_manage: 'Only administrators can manage extensions',
     // Tools tab
     tab_tools: 'Tools',
     no_tools: 'This extension does not register any tools.',
@@ -179,6 +208,16 @@ const en = {
     requires_confirmation: 'Requires confirmation',
     reset_to_default: 'Reset to default',
     reset_all_confirmations: 'Reset all to defaults',
+    // Files tab
+    tab_files: 'Files',
+    file_roots_description:
+      'The extension can only access files inside the folders you approve here.',
+    no_file_roots: 'No folders approved yet.',
+    file_root_path: 'Folder path',
+    file_access_read: 'Read only',
+    file_access_readwrite: 'Read and write',
+    add_file_root: 'Approve folder',
+    remove_file_root: 'Revoke',
     // Manifest validation
     manifest_invalid: 'Invalid manifest – extension will not work',
     // Uninstall confirmation
@@ -234,6 +273,91 @@ const en = {
           'time, or when you need temporal context for scheduling or time-related tasks. ' +
           'Returns ISO timestamp with timezone offset, epoch milliseconds, and UTC offset information.',
       },
+      remember: {
+        name: 'Remember',
+        description:
+          'Save a fact about the user to long-term memory so it can be recalled in later conversations. ' +
+          'Use this when the user asks you to remember something, or shares a lasting preference or detail ' +
+          'about themselves. Write the fact as a short self-contained sentence.',
+      },
+      recall: {
+        name: 'Recall',
+        description:
+          'Search long-term memory for facts saved earlier. Relevant memories are usually already included ' +
+          'in your instructions; use this tool to look for something specific or to find the ID of a memory to forget.',
+      },
+      forget: {
+        name: 'Forget',
+        description:
+          'Delete a fact from long-term memory. Use this when the user asks you to forget something ' +
+          'or when a saved fact is no longer true. Find the memory ID with the recall tool first.',
+      },
+      task_create: {
+        name: 'Create Task',
+        description:
+          "Add a task to the user's to-do list, optionally with notes and a due date. " +
+          'Use this when the user wants to keep track of something they need to do.',
+      },
+      task_list: {
+        name: 'List Tasks',
+        description:
+          "List the user's tasks, ordered by due date. Completed tasks are left out unless requested. " +
+          'Use this to answer questions about the to-do list or to find the ID of a task.',
+      },
+      task_update: {
+        name: 'Update Task',
+        description: 'Change the title, notes or due date of a task. Find the task ID with the list tool first.',
+      },
+      task_complete: {
+        name: 'Complete Task',
+        description: 'Mark a task as done, or reopen a completed task.',
+      },
+      task_delete: {
+        name: 'Delete Task',
+        description:
+          'Permanently delete a task. Prefer completing tasks that are done; delete only when the user asks to remove one.',
+      },
+      reminder_create: {
+        name: 'Create Reminder',
+        description:
+          'Remind the user about something at a specific time. When the time comes, the reminder is added ' +
+          "to the user's active conversation so you can tell them about it.",
+      },
+      reminder_schedule: {
+        name: 'Schedule Reminder',
+        description:
+          'Schedule a reminder or recurring check-in from a structured schedule, e.g. "in two hours" or ' +
+          '"every weekday at 8:30". Returns the next occurrences; confirm them to the user. ' +
+          'The user can pause or remove scheduled reminders under scheduled jobs in settings.',
+      },
+      reminder_list: {
+        name: 'List Reminders',
+        description:
+          "List the user's reminders, ordered by time. Completed reminders are left out unless requested.",
+      },
+      reminder_update: {
+        name: 'Update Reminder',
+        description:
+          'Change the text or time of a reminder. A new time schedules the reminder again, also if it has already been delivered.',
+      },
+      reminder_complete: {
+        name: 'Complete Reminder',
+        description: 'Mark a reminder as done. A pending reminder will then not be delivered.',
+      },
+      reminder_delete: {
+        name: 'Delete Reminder',
+        description: 'Permanently delete a reminder and cancel its delivery.',
+      },
+      reminder_due: {
+        instruction:
+          'A reminder the user asked for is due now ({{time}}): "{{text}}". Tell the user about it. ' +
+          'The reminder ID is {{id}}; mark it as complete if the user says it is done.',
+      },
+      scheduled_reminder_due: {
+        instruction:
+          'A scheduled reminder the user asked for is due now ({{time}}): "{{text}}". ' +
+          'Tell the user about it, or do what it asks.',
+      },
     },
   },
   settings: {
@@ -265,6 +389,8 @@ const en = {
       set_as_default: 'Set as default model',
       save_model: 'Save Model',
       default: 'Default',
+      title_model: 'Model for conversation titles',
+      title_model_default: 'Same as default model',
       danger_zone: 'Danger Zone',
       delete_model: 'Delete Model',
       delete_model_description: 'Permanently remove this model configuration.',
@@ -313,6 +439,56 @@ const en = {
       nickname: 'Nickname',
       nickname_placeholder: 'Enter a nickname',
       nickname_hint: 'This name will be used when addressing you.',
+      access_tokens: {
+        title: 'Personal access tokens',
+        description:
+          'Tokens let scripts use the API as you. Send them as a Bearer token in the Authorization header.',
+        name: 'Name',
+        name_placeholder: 'What the token is used for',
+        scopes: 'Scopes',
+        scope_descriptions: {
+          chat_read: 'Read conversations',
+          chat_write: 'Send messages and manage conversations',
+          jobs_read: 'Read scheduled jobs',
+          jobs_write: 'Manage scheduled jobs',
+          settings_read: 'Read settings',
+          settings_write: 'Change settings',
+        },
+        expiry: 'Expires',
+        expiry_days: 'In {{days}} days',
+        expiry_never: 'Never',
+        create: 'Create token',
+        created_hint: 'Copy the token now. It will not be shown again.',
+        copy: 'Copy token',
+        done: 'Done',
+        last_used: 'Last used',
+        never_used: 'Never',
+        expires: 'Expires',
+        revoke: 'Revoke token',
+        revoke_confirm: "Revoke '{{name}}'? Scripts using it will stop working.",
+        empty: 'No personal access tokens',
+      },
+      sessions: {
+        title: 'Sessions',
+        description: 'Devices and browsers where you are signed in.',
+        device: 'Device',
+        last_active: 'Last active',
+        signed_in: 'Signed in',
+        current: 'This device',
+        unknown_device: 'Unknown device',
+        revoke: 'Sign out',
+        revoke_confirm:
+          'Sign out this session? The device will need to log in again within a few minutes.',
+        empty: 'No active sessions',
+      },
+      single_sign_on: {
+        title: 'Single sign-on',
+        provider: 'your identity provider',
+        linked: 'Your account is linked to {{provider}}. You can log in with it instead of a passkey.',
+        not_linked: 'Link your account to {{provider}} to log in with it instead of a passkey.',
+        link: 'Link account',
+        unlink: 'Unlink account',
+      },
     },
     localization: {
       title: 'Localization',
@@ -337,6 +513,18 @@ const en = {
       test: 'Test',
       testMessage: 'This is a test notification from Stina!',
       webSoundInfo: 'Your browser handles notification sounds automatically.',
+      quiet_hours_title: 'Quiet Hours',
+      quiet_hours_description:
+        'During quiet hours Stina shows no notifications and holds back automatic messages from reminders and extensions. They are delivered as a summary when the quiet hours end.',
+      quiet_hours_enabled: 'Use quiet hours',
+      quiet_hours_start: 'From',
+      quiet_hours_end: 'To',
+      quiet_hours_invalid: 'Enter a time as HH:MM',
+      dnd_title: 'Do Not Disturb',
+      dnd_description: 'Pause notifications and automatic messages for a while.',
+      dnd_active: 'Do not disturb is on until {{time}}.',
+      dnd_for_hours: '{{count}} h',
+      dnd_off: 'Turn off',
       sounds: {
         default: 'Default',
         subtle: 'Subtle',
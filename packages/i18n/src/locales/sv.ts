@@ -33,6 +33,14 @@ const sv = {
     in_queue: 'I kö:',
     remove_from_queue: 'Ta bort från kö',
     input_placeholder: 'Skriv till Stina...',
+    attach_files: 'Bifoga filer',
+    remove_attachment: 'Ta bort {{name}}',
+    edit_message: 'Redigera meddelande',
+    send_edited_message: 'Skicka',
+    regenerate: 'Generera nytt svar',
+    previous_branch: 'Föregående version',
+    next_branch: 'Nästa version',
+    branch_position: '{{index}} av {{count}}',
     tool_input: 'Indata',
     tool_output: 'Utdata',
     tool_no_input: 'Verktyget har inga indata',
@@ -61,6 +69,27 @@ const sv = {
           'Du är kreativ, fantasifull och lekfull.\n- Erbjud nya idéer, metaforer och alternativa vinklar.\n- Använd levande språk när det hjälper, men håll instruktioner tydliga.\n- Balansera originalitet med korrekthet; hitta inte på fakta.\n- Föreslå flera möjliga vägar när det är relevant.\n- Håll struktur: börja med en kort översikt och fördjupa vid behov.\n- Undvik att sväva ut; kreativitet ska fortfarande hjälpa användaren framåt.',
       },
     },
+    context_summary: {
+      system_prompt:
+        'Du sammanfattar chattkonversationer. Skriv en kompakt sammanfattning av konversationen du får så att den kan ersätta de ursprungliga meddelandena. Behåll fakta, beslut, öppna uppgifter, namn, datum och preferenser som användaren har nämnt. Utelämna hälsningar och småprat. Skriv på samma språk som konversationen.',
+      request: 'Sammanfatta konversationen ovan. Svara endast med sammanfattningen.',
+      prefix:
+        'Sammanfattning av tidigare delar av den här konversationen. Äldre meddelanden har komprimerats för att rymmas i modellens kontextfönster:',
+      info: 'Tidigare meddelanden sammanfattades för att rymmas i modellens kontextfönster.',
+    },
+    conversation_title: {
+      system_prompt:
+        'Du namnger chattkonversationer. Svara med en kort, beskrivande titel för konversationen du får, högst sex ord. Skriv titeln på svenska. Svara endast med titeln, utan citattecken eller skiljetecken i slutet.',
+      request: 'Föreslå en titel för konversationen ovan.',
+    },
+    memory: {
+      prompt:
+        'Saker du minns om användaren från tidigare samtal. Använd dem när de är relevanta, och nämn inte att de kommer från ditt minne om du inte blir tillfrågad:',
+    },
+    quiet_hours: {
+      digest:
+        'Tysta timmar är över. Dessa {{count}} automatiska meddelanden kom medan användaren inte ville bli störd. Ge användaren en kort sammanfattning av dem och nämn det som fortfarande behöver åtgärdas:\n\n{{items}}',
+    },
     tool_confirmation: {
       title: 'Bekräfta verktygskörning',
       default_prompt: 'Tillåt {{toolName}} att köra?',
@@ -166,10 +195,10 @@ const sv = {
     settings_error: 'Kunde inte spara inställningar',
     // Admin-only messaging
     admin_only: 'Endast admin',
-    admin_only_install: 'Endast administratörer kan installera tillägg',
-    admin_only_uninstall: 'Endast administratörer kan avinstallera tillägg',
+    not_allowed_install: 'Du har inte behörighet att installera tillägg',
+    not_allowed_uninstall: 'Du har inte behörighet att avinstallera tillägg',
+    not_allowed_update: 'Du har inte behörighet att uppdatera tillägg',
     admin_only_enable_disable: 'Endast administratörer kan aktivera eller inaktivera tillägg',
-    admin_only_manage: 'Endast administratörer kan hantera tillägg',
     // Tools tab
     tab_tools: 'Verktyg',
     no_tools: 'Detta tillägg registrerar inga verktyg.',
@@ -177,6 +206,16 @@ const sv = {
     requires_confirmation: 'Kräver bekräftelse',
     reset_to_default: 'Återställ',
     reset_all_confirmations: 'Återställ alla till standard',
+    // Files tab
+    tab_files: 'Filer',
+    file_roots_description:
+      'Tillägget kan bara komma åt filer i de mappar du godkänner här.',
+    no_file_roots: 'Inga mappar godkända ännu.',
+    file_root_path: 'Sökväg till mapp',
+    file_access_read: 'Endast läsning',
+    file_access_readwrite: 'Läsning och skrivning',
+    add_file_root: 'Godkänn mapp',
+    remove_file_root: 'Återkalla',
     // Manifest validation
     manifest_invalid: 'Ogiltigt manifest – tillägget fungerar inte',
     // Uninstall confirmation
@@ -232,6 +271,92 @@ const sv = {
           'tid, eller när du behöver tidskontext för schemaläggning eller tidsrelaterade uppgifter. ' +
           'Returnerar ISO-tidsstämpel med tidszonsoffset, epoch-millisekunder och UTC-offsetinformation.',
       },
+      remember: {
+        name: 'Kom ihåg',
+        description:
+          'Spara ett faktum om användaren i långtidsminnet så att det kan hämtas i senare samtal. ' +
+          'Använd detta när användaren ber dig komma ihåg något, eller berättar om en bestående preferens ' +
+          'eller detalj om sig själv. Skriv faktumet som en kort, fristående mening.',
+      },
+      recall: {
+        name: 'Hämta minnen',
+        description:
+          'Sök i långtidsminnet efter fakta som sparats tidigare. Relevanta minnen finns oftast redan i dina ' +
+          'instruktioner; använd detta verktyg för att leta efter något specifikt eller hitta ID:t för ett minne som ska glömmas.',
+      },
+      forget: {
+        name: 'Glöm',
+        description:
+          'Ta bort ett faktum från långtidsminnet. Använd detta när användaren ber dig glömma något ' +
+          'eller när ett sparat faktum inte längre stämmer. Hitta minnets ID med verktyget för att hämta minnen först.',
+      },
+      task_create: {
+        name: 'Skapa uppgift',
+        description:
+          'Lägg till en uppgift i användarens att göra-lista, eventuellt med anteckningar och förfallodatum. ' +
+          'Använd detta när användaren vill hålla koll på något de behöver göra.',
+      },
+      task_list: {
+        name: 'Lista uppgifter',
+        description:
+          'Lista användarens uppgifter, sorterade efter förfallodatum. Avklarade uppgifter utelämnas om de inte efterfrågas. ' +
+          'Använd detta för att svara på frågor om att göra-listan eller för att hitta ID:t för en uppgift.',
+      },
+      task_update: {
+        name: 'Uppdatera uppgift',
+        description:
+          'Ändra titel, anteckningar eller förfallodatum för en uppgift. Hitta uppgiftens ID med listverktyget först.',
+      },
+      task_complete: {
+        name: 'Klarmarkera uppgift',
+        description: 'Markera en uppgift som klar, eller återöppna en avklarad uppgift.',
+      },
+      task_delete: {
+        name: 'Ta bort uppgift',
+        description:
+          'Ta bort en uppgift permanent. Klarmarkera hellre uppgifter som är gjorda; ta bara bort när användaren ber om det.',
+      },
+      reminder_create: {
+        name: 'Skapa påminnelse',
+        description:
+          'Påminn användaren om något vid en viss tidpunkt. När det är dags läggs påminnelsen till ' +
+          'i användarens aktiva konversation så att du kan berätta om den.',
+      },
+      reminder_schedule: {
+        name: 'Schemalägg påminnelse',
+        description:
+          'Schemalägg en påminnelse eller återkommande avstämning från ett strukturerat schema, t.ex. "om två timmar" eller ' +
+          '"varje vardag kl. 8:30". Returnerar kommande tillfällen; bekräfta dem för användaren. ' +
+          'Användaren kan pausa eller ta bort schemalagda påminnelser under schemalagda jobb i inställningarna.',
+      },
+      reminder_list: {
+        name: 'Lista påminnelser',
+        description:
+          'Lista användarens påminnelser, sorterade efter tid. Avklarade påminnelser utelämnas om de inte efterfrågas.',
+      },
+      reminder_update: {
+        name: 'Uppdatera påminnelse',
+        description:
+          'Ändra text eller tid för en påminnelse. En ny tid schemalägger påminnelsen igen, även om den redan har levererats.',
+      },
+      reminder_complete: {
+        name: 'Klarmarkera påminnelse',
+        description: 'Markera en påminnelse som klar. En väntande påminnelse levereras då inte.',
+      },
+      reminder_delete: {
+        name: 'Ta bort påminnelse',
+        description: 'Ta bort en påminnelse permanent och avbryt leveransen.',
+      },
+      reminder_due: {
+        instruction:
+          'En påminnelse som användaren bad om är aktuell nu ({{time}}): "{{text}}". Berätta om den för användaren. ' +
+          'Påminnelsens ID är {{id}}; markera den som klar om användaren säger att den är avklarad.',
+      },
+      scheduled_reminder_due: {
+        instruction:
+          'En schemalagd påminnelse som användaren bad om är aktuell nu ({{time}}): "{{text}}". ' +
+          'Berätta om den för användaren, eller gör det den ber om.',
+      },
     },
   },
   settings: {
@@ -265,6 +390,8 @@ const sv = {
       set_as_default: 'Ange som standardmodell',
       save_model: 'Spara modell',
       default: 'Standard',
+      title_model: 'Modell för konversationstitlar',
+      title_model_default: 'Samma som standardmodellen',
       danger_zone: 'Farozon',
       delete_model: 'Ta bort modell',
       delete_model_description: 'Ta bort denna modellkonfiguration permanent.',
@@ -313,6 +440,56 @@ const sv = {
       nickname: 'Smeknamn',
       nickname_placeholder: 'Ange ett smeknamn',
       nickname_hint: 'Detta namn används när du tilltalas.',
+      access_tokens: {
+        title: 'Personliga åtkomsttokens',
+        description:
+          'Med tokens kan skript använda API:t som dig. Skicka dem som Bearer-token i Authorization-headern.',
+        name: 'Namn',
+        name_placeholder: 'Vad token används till',
+        scopes: 'Behörigheter',
+        scope_descriptions: {
+          chat_read: 'Läsa konversationer',
+          chat_write: 'Skicka meddelanden och hantera konversationer',
+          jobs_read: 'Läsa schemalagda jobb',
+          jobs_write: 'Hantera schemalagda jobb',
+          settings_read: 'Läsa inställningar',
+          settings_write: 'Ändra inställningar',
+        },
+        expiry: 'Upphör',
+        expiry_days: 'Om {{days}} dagar',
+        expiry_never: 'Aldrig',
+        create: 'Skapa token',
+        created_hint: 'Kopiera token nu. Den visas inte igen.',
+        copy: 'Kopiera token',
+        done: 'Klar',
+        last_used: 'Senast använd',
+        never_used: 'Aldrig',
+        expires: 'Upphör',
+        revoke: 'Återkalla token',
+        revoke_confirm: "Återkalla '{{name}}'? Skript som använder den slutar fungera.",
+        empty: 'Inga personliga åtkomsttokens',
+      },
+      sessions: {
+        title: 'Sessioner',
+        description: 'Enheter och webbläsare där du är inloggad.',
+        device: 'Enhet',
+        last_active: 'Senast aktiv',
+        signed_in: 'Inloggad',
+        current: 'Den här enheten',
+        unknown_device: 'Okänd enhet',
+        revoke: 'Logga ut',
+        revoke_confirm:
+          'Logga ut den här sessionen? Enheten behöver logga in igen inom några minuter.',
+        empty: 'Inga aktiva sessioner',
+      },
+      single_sign_on: {
+        title: 'Enkel inloggning',
+        provider: 'din identitetsleverantör',
+        linked: 'Ditt konto är kopplat till {{provider}}. Du kan logga in med det i stället för en passkey.',
+        not_linked: 'Koppla ditt konto till {{provider}} för att logga in med det i stället för en passkey.',
+        link: 'Koppla konto',
+        unlink: 'Koppla bort konto',
+      },
     },
     localization: {
       title: 'Lokalisering',
@@ -337,6 +514,18 @@ const sv = {
       test: 'Testa',
       testMessage: 'Det här är en testnotifikation från Stina!',
       webSoundInfo: 'Din webbläsare hanterar notifikationsljud automatiskt.',
+      quiet_hours_title: 'Tysta timmar',
+      quiet_hours_description:
+        'Under tysta timmar visar Stina inga notifikationer och håller inne automatiska meddelanden från påminnelser och tillägg. De levereras som en sammanfattning när de tysta timmarna är slut.',
+      quiet_hours_enabled: 'Använd tysta timmar',
+      quiet_hours_start: 'Från',
+      quiet_hours_end: 'Till',
+      quiet_hours_invalid: 'Ange en tid som TT:MM',
+      dnd_title: 'Stör ej',
+      dnd_description: 'Pausa notifikationer och automatiska meddelanden en stund.',
+      dnd_active: 'Stör ej är på till {{time}}.',
+      dnd_for_hours: '{{count}} h',
+      dnd_off: 'Stäng av',
       sounds: {
         default: 'Standard',
         subtle: 'Subtilt',
@@ -12,6 +12,8 @@ interface FakeRepoOptions {
   retentionByUser?: Record<string, number | undefined>
   deleteCounts?: Record<string, number>
   legacyDeleteCount?: number
+  runDeleteCounts?: Record<string, number>
+  orphanedRunDeleteCount?: number
   throwForUser?: string
   throwForLegacy?: boolean
   throwForListUserIds?: boolean
@@ -20,6 +22,7 @@ interface FakeRepoOptions {
 const createFakeRepo = (options: FakeRepoOptions = {}) => {
   const userDeleteCalls: DeleteCall[] = []
   const legacyDeleteCalls: string[] = []
+  const runDeleteCalls: DeleteCall[] = []
 
   const repo = {
     listUserIds: vi.fn(() => {
@@ -42,12 +45,18 @@ const createFakeRepo = (options: FakeRepoOptions = {}) => {
       }
       return options.legacyDeleteCount ?? 0
     }),
+    deleteRunsBefore: vi.fn((userId: string, beforeIso: string) => {
+      runDeleteCalls.push({ userId, beforeIso })
+      return options.runDeleteCounts?.[userId] ?? 0
+    }),
+    deleteOrphanedRuns: vi.fn(() => options.orphanedRunDeleteCount ?? 0),
   } satisfies Partial<SchedulerRepository>
 
   return {
     repo: repo as unknown as SchedulerRepository,
     userDeleteCalls,
     legacyDeleteCalls,
+    runDeleteCalls,
     listUserIdsMock: repo.listUserIds,
     deleteDisabledBeforeMock: repo.deleteDisabledBefore,
     deleteDisabledLegacyBeforeMock: repo.deleteDisabledLegacyBefore,
@@ -216,6 +225,29 @@ describe('SchedulerCleanupService', () => {
       expect(total).toBe(3)
     })
 
+    it('applies the retention to run history and removes runs of deleted jobs', async () => {
+      const { repo, runDeleteCalls } = createFakeRepo({
+        userIds: ['u1'],
+        deleteCounts: { u1: 1 },
+        runDeleteCounts: { u1: 10 },
+        orphanedRunDeleteCount: 2,
+      })
+
+      const service = new SchedulerCleanupService({
+        repository: repo,
+        getRetentionDays: () => 30,
+        legacyRetentionDays: 0,
+        now: () => NOW,
+      })
+
+      const total = await service.runOnce()
+
+      expect(total).toBe(13)
+      expect(runDeleteCalls).toEqual([
+        { userId: 'u1', beforeIso: new Date(NOW.getTime() - 30 * MS_PER_DAY).toISOString() },
+      ])
+    })
+
     it('supports both sync and async getRetentionDays callbacks', async () => {
       const { repo } = createFakeRepo({
         userIds: ['sync-user', 'async-user'],
@@ -40,8 +40,8 @@ const DEFAULT_LEGACY_RETENTION_DAYS = 30
 const MS_PER_DAY = 24 * 60 * 60 * 1000
 
 /**
- * Periodically removes completed (disabled) scheduled jobs that are older
- * than the user's configured retention window. Runs in-process alongside the
+ * Periodically removes completed (disabled) scheduled jobs and run history
+ * that are older than the user's configured retention window. Runs in-process alongside the
  * SchedulerService and is safe to start/stop together with it.
  */
 export class SchedulerCleanupService {
@@ -119,6 +119,15 @@ export class SchedulerCleanupService {
             deleted,
           })
         }
+        const deletedRuns = this.repository.deleteRunsBefore(userId, beforeIso)
+        if (deletedRuns > 0) {
+          totalDeleted += deletedRuns
+          this.logger?.info('Scheduler cleanup removed old run history', {
+            userId,
+            retentionDays,
+            deleted: deletedRuns,
+          })
+        }
       } catch (error) {
         this.logger?.error('Scheduler cleanup failed for user', {
           userId,
@@ -146,6 +155,18 @@ export class SchedulerCleanupService {
       }
     }
 
+    try {
+      const deleted = this.repository.deleteOrphanedRuns()
+      if (deleted > 0) {
+        totalDeleted += deleted
+        this.logger?.info('Scheduler cleanup removed run history of deleted jobs', { deleted })
+      }
+    } catch (error) {
+      this.logger?.error('Scheduler cleanup failed for orphaned runs', {
+        error: error instanceof Error ? error.message : String(error),
+      })
+    }
+
     return totalDeleted
   }
 
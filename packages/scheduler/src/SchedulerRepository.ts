@@ -1,8 +1,9 @@
-import { and, eq, isNull, lt, sql } from 'drizzle-orm'
+import { and, count, desc, eq, isNull, lt, notExists, sql } from 'drizzle-orm'
 import type { BetterSQLite3Database } from 'drizzle-orm/better-sqlite3'
-import { schedulerJobs } from './schema.js'
+import { schedulerJobRuns, schedulerJobs } from './schema.js'
 
 export type SchedulerJobRow = typeof schedulerJobs.$inferSelect
+export type SchedulerJobRunRow = typeof schedulerJobRuns.$inferSelect
 
 /**
  * Repository for accessing scheduled jobs data.
@@ -46,14 +47,81 @@ export class SchedulerRepository {
       .delete(schedulerJobs)
       .where(and(eq(schedulerJobs.id, id), eq(schedulerJobs.userId, userId)))
       .run()
+    if (result.changes > 0) {
+      this.db
+        .delete(schedulerJobRuns)
+        .where(and(eq(schedulerJobRuns.jobId, id), eq(schedulerJobRuns.userId, userId)))
+        .run()
+    }
     return result.changes > 0
   }
 
+  /**
+   * List the run history of a scheduled job, newest first.
+   * @param id The composite job ID (extensionId:jobId)
+   * @param userId The user ID to verify ownership
+   * @param options Pagination options
+   * @returns The requested page of runs and the total number of runs
+   */
+  listRunsForUser(
+    id: string,
+    userId: string,
+    options: { limit: number; offset: number }
+  ): { runs: SchedulerJobRunRow[]; total: number } {
+    const where = and(eq(schedulerJobRuns.jobId, id), eq(schedulerJobRuns.userId, userId))
+    const runs = this.db
+      .select()
+      .from(schedulerJobRuns)
+      .where(where)
+      .orderBy(desc(schedulerJobRuns.firedAt))
+      .limit(options.limit)
+      .offset(options.offset)
+      .all()
+    const total = this.db.select({ value: count() }).from(schedulerJobRuns).where(where).get()
+    return { runs, total: total?.value ?? 0 }
+  }
+
+  /**
+   * Permanently delete run history entries for a user that were fired before
+   * the supplied cutoff.
+   * @param userId The user whose old runs should be removed
+   * @param beforeIso ISO timestamp; runs fired before this are removed
+   * @returns Number of deleted rows
+   */
+  deleteRunsBefore(userId: string, beforeIso: string): number {
+    const result = this.db
+      .delete(schedulerJobRuns)
+      .where(and(eq(schedulerJobRuns.userId, userId), lt(schedulerJobRuns.firedAt, beforeIso)))
+      .run()
+    return result.changes
+  }
+
+  /**
+   * Permanently delete run history entries whose job no longer exists, e.g.
+   * after the job was removed by retention cleanup or by its extension.
+   * @returns Number of deleted rows
+   */
+  deleteOrphanedRuns(): number {
+    const result = this.db
+      .delete(schedulerJobRuns)
+      .where(
+        notExists(
+          this.db
+            .select({ id: schedulerJobs.id })
+            .from(schedulerJobs)
+            .where(eq(schedulerJobs.id, schedulerJobRuns.jobId))
+        )
+      )
+      .run()
+    return result.changes
+  }
+
   /**
    * Permanently delete disabled (completed) scheduled jobs for a user that
    * are older than the supplied cutoff. A job's age is measured from
    * `lastRunAt` if present, otherwise `updatedAt` (the time the job was
-   * disabled). Enabled jobs are never deleted.
+   * disabled). Enabled jobs, paused jobs and jobs with a pending retry are
+   * never deleted.
    * @param userId The user whose old jobs should be removed
    * @param beforeIso ISO timestamp; jobs older than this are removed
    * @returns Number of deleted rows
@@ -66,6 +134,8 @@ export class SchedulerRepository {
         and(
           eq(schedulerJobs.userId, userId),
           eq(schedulerJobs.enabled, false),
+          isNull(schedulerJobs.retryAt),
+          isNull(schedulerJobs.pausedAt),
           lt(ageColumn, beforeIso)
         )
       )
@@ -2,6 +2,7 @@ import Database from 'better-sqlite3'
 import { readFileSync } from 'node:fs'
 import { drizzle } from 'drizzle-orm/better-sqlite3'
 import { beforeEach, afterEach, describe, expect, it, vi } from 'vitest'
+import { SchedulerRepository } from './SchedulerRepository.js'
 import { SchedulerService } from './SchedulerService.js'
 
 const migration0001 = readFileSync(
@@ -17,11 +18,33 @@ const migration0003 = readFileSync(
   'utf-8'
 )
 
+const migration0004 = readFileSync(
+  new URL('./migrations/0004_create_scheduler_job_runs.sql', import.meta.url),
+  'utf-8'
+)
+const migration0005 = readFileSync(
+  new URL('./migrations/0005_add_retry_policy.sql', import.meta.url),
+  'utf-8'
+)
+const migration0006 = readFileSync(
+  new URL('./migrations/0006_add_pause_and_manual_runs.sql', import.meta.url),
+  'utf-8'
+)
+
+const migration0007 = readFileSync(
+  new URL('./migrations/0007_add_cancelled_at.sql', import.meta.url),
+  'utf-8'
+)
+
 const createDb = () => {
   const rawDb = new Database(':memory:')
   rawDb.exec(migration0001)
   rawDb.exec(migration0002)
   rawDb.exec(migration0003)
+  rawDb.exec(migration0004)
+  rawDb.exec(migration0005)
+  rawDb.exec(migration0006)
+  rawDb.exec(migration0007)
   const db = drizzle(rawDb)
   return { rawDb, db }
 }
@@ -204,4 +227,499 @@ describe('SchedulerService', () => {
     scheduler.stop()
     rawDb.close()
   })
+
+  it('records run history with delay, duration and result', async () => {
+    vi.setSystemTime(new Date('2025-01-01T00:00:00Z'))
+    const { rawDb, db } = createDb()
+    const repository = new SchedulerRepository(db)
+
+    const scheduler = new SchedulerService({
+      db,
+      onFire: () => true,
+    })
+
+    scheduler.start()
+    scheduler.schedule('ext', {
+      id: 'job-runs',
+      schedule: { type: 'interval', everyMs: 60_000 },
+      userId: 'user-1',
+    })
+
+    await vi.advanceTimersByTimeAsync(60_000)
+    await vi.advanceTimersByTimeAsync(1500)
+    scheduler.updateJobResult('ext', 'job-runs', true)
+    await vi.advanceTimersByTimeAsync(58_500)
+    await vi.advanceTimersByTimeAsync(500)
+    scheduler.updateJobResult('ext', 'job-runs', false, 'boom')
+
+    const history = repository.listRunsForUser('ext:job-runs', 'user-1', { limit: 10, offset: 0 })
+    expect(history.total).toBe(2)
+    expect(history.runs.map((run) => [run.status, run.durationMs, run.error])).toEqual([
+      ['error', 500, 'boom'],
+      ['success', 1500, null],
+    ])
+    expect(history.runs[1]).toMatchObject({
+      scheduledFor: '2025-01-01T00:01:00.000Z',
+      firedAt: '2025-01-01T00:01:00.000Z',
+      delayMs: 0,
+    })
+
+    const page = repository.listRunsForUser('ext:job-runs', 'user-1', { limit: 1, offset: 1 })
+    expect(page.runs.map((run) => run.status)).toEqual(['success'])
+    expect(repository.listRunsForUser('ext:job-runs', 'user-2', { limit: 10, offset: 0 }).total).toBe(0)
+
+    expect(repository.delete('ext:job-runs', 'user-1')).toBe(true)
+    expect(repository.listRunsForUser('ext:job-runs', 'user-1', { limit: 10, offset: 0 }).total).toBe(0)
+
+    scheduler.stop()
+    rawDb.close()
+  })
+
+  it('records skipped runs and runs without a handler', async () => {
+    vi.setSystemTime(new Date('2025-01-01T00:10:00Z'))
+    const { rawDb, db } = createDb()
+    const repository = new SchedulerRepository(db)
+
+    const scheduler = new SchedulerService({
+      db,
+      onFire: (event) => event.payload.id !== 'no-handler',
+    })
+
+    scheduler.schedule('ext', {
+      id: 'late',
+      schedule: { type: 'at', at: '2025-01-01T00:00:00Z' },
+      misfire: 'skip',
+      userId: 'user-1',
+    })
+    scheduler.schedule('ext', {
+      id: 'no-handler',
+      schedule: { type: 'at', at: '2025-01-01T00:10:00Z' },
+      userId: 'user-1',
+    })
+    scheduler.start()
+    await vi.advanceTimersByTimeAsync(1000)
+
+    const late = repository.listRunsForUser('ext:late', 'user-1', { limit: 10, offset: 0 })
+    expect(late.runs).toHaveLength(1)
+    expect(late.runs[0]).toMatchObject({ status: 'skipped', delayMs: 600_000 })
+
+    const noHandler = repository.listRunsForUser('ext:no-handler', 'user-1', { limit: 10, offset: 0 })
+    expect(noHandler.runs[0]?.status).toBe('error')
+    expect(noHandler.runs[0]?.error).toBeTruthy()
+
+    scheduler.stop()
+    rawDb.close()
+  })
+
+  it('removes run history older than the cutoff and of deleted jobs', () => {
+    const { rawDb, db } = createDb()
+    const repository = new SchedulerRepository(db)
+    const insertRun = rawDb.prepare(
+      `INSERT INTO scheduler_job_runs (id, job_id, user_id, scheduled_for, fired_at, delay_ms, status)
+       VALUES (?, ?, 'user-1', ?, ?, 0, 'success')`
+    )
+    rawDb
+      .prepare(
+        `INSERT INTO scheduler_jobs (id, extension_id, job_id, user_id, schedule_type, schedule_value, next_run_at, enabled, created_at, updated_at)
+         VALUES ('ext:kept', 'ext', 'kept', 'user-1', 'interval', '60000', '2025-02-01T00:00:00.000Z', 1, '2025-01-01T00:00:00.000Z', '2025-01-01T00:00:00.000Z')`
+      )
+      .run()
+    insertRun.run('old', 'ext:kept', '2025-01-01T00:00:00.000Z', '2025-01-01T00:00:00.000Z')
+    insertRun.run('new', 'ext:kept', '2025-01-20T00:00:00.000Z', '2025-01-20T00:00:00.000Z')
+    insertRun.run('orphan', 'ext:gone', '2025-01-20T00:00:00.000Z', '2025-01-20T00:00:00.000Z')
+
+    expect(repository.deleteRunsBefore('user-1', '2025-01-10T00:00:00.000Z')).toBe(1)
+    expect(repository.deleteOrphanedRuns()).toBe(1)
+    const remaining = rawDb.prepare('SELECT id FROM scheduler_job_runs').all()
+    expect(remaining).toEqual([{ id: 'new' }])
+
+    rawDb.close()
+  })
+
+  it('retries a failed one-shot job with backoff', async () => {
+    vi.setSystemTime(new Date('2025-01-01T00:00:00Z'))
+    const { rawDb, db } = createDb()
+    const fired: Array<number | undefined> = []
+
+    const scheduler = new SchedulerService({
+      db,
+      onFire: (event) => {
+        fired.push(event.payload.retryAttempt)
+        return true
+      },
+    })
+
+    scheduler.start()
+    scheduler.schedule('ext', {
+      id: 'job-retry',
+      schedule: { type: 'at', at: '2025-01-01T00:00:01Z' },
+      retry: { maxAttempts: 2, initialDelayMs: 1000, multiplier: 3 },
+      userId: 'user-1',
+    })
+
+    await vi.advanceTimersByTimeAsync(1000)
+    expect(fired).toEqual([undefined])
+    scheduler.updateJobResult('ext', 'job-retry', false, 'boom')
+
+    await vi.advanceTimersByTimeAsync(999)
+    expect(fired).toEqual([undefined])
+    await vi.advanceTimersByTimeAsync(1)
+    expect(fired).toEqual([undefined, 1])
+    scheduler.updateJobResult('ext', 'job-retry', false, 'boom')
+
+    // Second retry waits initialDelayMs * multiplier
+    await vi.advanceTimersByTimeAsync(2999)
+    expect(fired).toEqual([undefined, 1])
+    await vi.advanceTimersByTimeAsync(1)
+    expect(fired).toEqual([undefined, 1, 2])
+    scheduler.updateJobResult('ext', 'job-retry', false, 'boom')
+
+    // No attempts left
+    await vi.advanceTimersByTimeAsync(60_000)
+    expect(fired).toEqual([undefined, 1, 2])
+
+    const runs = new SchedulerRepository(db).listRunsForUser('ext:job-retry', 'user-1', {
+      limit: 10,
+      offset: 0,
+    })
+    expect(runs.runs.map((run) => run.attempt)).toEqual([2, 1, 0])
+
+    scheduler.stop()
+    rawDb.close()
+  })
+
+  it('lets the next regular fire replace a pending retry', async () => {
+    vi.setSystemTime(new Date('2025-01-01T00:00:00Z'))
+    const { rawDb, db } = createDb()
+    const fired: Array<number | undefined> = []
+
+    const scheduler = new SchedulerService({
+      db,
+      onFire: (event) => {
+        fired.push(event.payload.retryAttempt)
+        return true
+      },
+    })
+
+    scheduler.start()
+    scheduler.schedule('ext', {
+      id: 'job-interval',
+      schedule: { type: 'interval', everyMs: 10_000 },
+      retry: { maxAttempts: 5, initialDelayMs: 30_000 },
+      userId: 'user-1',
+    })
+
+    await vi.advanceTimersByTimeAsync(10_000)
+    scheduler.updateJobResult('ext', 'job-interval', false, 'boom')
+
+    // The regular fire at 20s comes before the retry at 40s and cancels it
+    await vi.advanceTimersByTimeAsync(10_000)
+    scheduler.updateJobResult('ext', 'job-interval', true)
+    await vi.advanceTimersByTimeAsync(25_000)
+    expect(fired).toEqual([undefined, undefined, undefined, undefined])
+
+    scheduler.stop()
+    rawDb.close()
+  })
+
+  it('does not retry failures of paused or cancelled one-shot jobs', async () => {
+    vi.setSystemTime(new Date('2025-01-01T00:00:00Z'))
+    const { rawDb, db } = createDb()
+    const fired: string[] = []
+
+    const scheduler = new SchedulerService({
+      db,
+      onFire: (event) => {
+        fired.push(event.payload.id)
+        return true
+      },
+    })
+
+    scheduler.start()
+    const retry = { maxAttempts: 3, initialDelayMs: 1000 }
+    const schedule = { type: 'at' as const, at: '2025-01-01T00:00:01Z' }
+    scheduler.schedule('ext', { id: 'job-paused', schedule, retry, userId: 'user-1' })
+    scheduler.schedule('ext', { id: 'job-cancelled', schedule, retry, userId: 'user-1' })
+
+    await vi.advanceTimersByTimeAsync(1000)
+    expect(fired).toEqual(['job-paused', 'job-cancelled'])
+
+    // Both are stopped before their failures are reported. A fired one-shot job is
+    // disabled and cannot be paused through pauseJob, so the pause is written directly.
+    rawDb
+      .prepare('UPDATE scheduler_jobs SET paused_at = ? WHERE id = ?')
+      .run('2025-01-01T00:00:01.500Z', 'ext:job-paused')
+    scheduler.cancel('ext', 'job-cancelled')
+    scheduler.updateJobResult('ext', 'job-paused', false, 'boom')
+    scheduler.updateJobResult('ext', 'job-cancelled', false, 'boom')
+
+    const repo = new SchedulerRepository(db)
+    expect(repo.getByIdForUser('ext:job-paused', 'user-1')?.retryAt).toBeNull()
+    expect(repo.getByIdForUser('ext:job-cancelled', 'user-1')?.retryAt).toBeNull()
+
+    await vi.advanceTimersByTimeAsync(60_000)
+    expect(fired).toEqual(['job-paused', 'job-cancelled'])
+
+    scheduler.stop()
+    rawDb.close()
+  })
+
+  it('does not fire a pending retry while the job is paused', async () => {
+    vi.setSystemTime(new Date('2025-01-01T00:00:00Z'))
+    const { rawDb, db } = createDb()
+    const fired: Array<number | undefined> = []
+
+    const scheduler = new SchedulerService({
+      db,
+      onFire: (event) => {
+        fired.push(event.payload.retryAttempt)
+        return true
+      },
+    })
+
+    scheduler.start()
+    scheduler.schedule('ext', {
+      id: 'job-retry',
+      schedule: { type: 'at', at: '2025-01-01T00:00:01Z' },
+      retry: { maxAttempts: 2, initialDelayMs: 5000 },
+      userId: 'user-1',
+    })
+
+    await vi.advanceTimersByTimeAsync(1000)
+    scheduler.updateJobResult('ext', 'job-retry', false, 'boom')
+    expect(new SchedulerRepository(db).getByIdForUser('ext:job-retry', 'user-1')?.retryAt).toBe(
+      '2025-01-01T00:00:06.000Z'
+    )
+
+    // A retry left over from before the pause must wait for the job to be resumed
+    rawDb.exec("UPDATE scheduler_jobs SET paused_at = '2025-01-01T00:00:02.000Z'")
+    await vi.advanceTimersByTimeAsync(60_000)
+    expect(fired).toEqual([undefined])
+
+    scheduler.stop()
+    rawDb.close()
+  })
+
+  it('rejects invalid retry policies', () => {
+    const { rawDb, db } = createDb()
+    const scheduler = new SchedulerService({ db, onFire: () => true })
+
+    expect(() =>
+      scheduler.schedule('ext', {
+        id: 'job-invalid',
+        schedule: { type: 'interval', everyMs: 1000 },
+        retry: { maxAttempts: 1.5, initialDelayMs: 1000 },
+        userId: 'user-1',
+      })
+    ).toThrow('retry.maxAttempts')
+    expect(() =>
+      scheduler.schedule('ext', {
+        id: 'job-invalid',
+        schedule: { type: 'interval', everyMs: 1000 },
+        retry: { maxAttempts: 1, initialDelayMs: 1000, multiplier: 0.5 },
+        userId: 'user-1',
+      })
+    ).toThrow('retry.multiplier')
+
+    rawDb.close()
+  })
+
+  it('fires rrule schedules and disables them when the recurrence ends', async () => {
+    vi.setSystemTime(new Date('2025-01-01T00:00:00Z'))
+    const { rawDb, db } = createDb()
+    const fired: string[] = []
+
+    const scheduler = new SchedulerService({
+      db,
+      onFire: (event) => {
+        fired.push(event.payload.scheduledFor)
+        return true
+      },
+    })
+
+    scheduler.start()
+    scheduler.schedule('ext', {
+      id: 'job-rrule',
+      schedule: {
+        type: 'rrule',
+        rrule: 'FREQ=DAILY;COUNT=3',
+        dtstart: '2025-01-01T09:00:00',
+        timezone: 'Europe/Stockholm',
+        exdates: ['2025-01-02T09:00:00'],
+      },
+      userId: 'user-1',
+    })
+
+    await vi.advanceTimersByTimeAsync(4 * 24 * 60 * 60 * 1000)
+    expect(fired).toEqual(['2025-01-01T08:00:00.000Z', '2025-01-03T08:00:00.000Z'])
+
+    const row = rawDb
+      .prepare('SELECT enabled FROM scheduler_jobs WHERE id = ?')
+      .get('ext:job-rrule') as { enabled: number }
+    expect(row.enabled).toBe(0)
+
+    expect(() =>
+      scheduler.schedule('ext', {
+        id: 'job-rrule-ended',
+        schedule: { type: 'rrule', rrule: 'FREQ=DAILY;UNTIL=20241231', dtstart: '2024-12-01T09:00:00Z' },
+        userId: 'user-1',
+      })
+    ).toThrow('no future occurrences')
+
+    scheduler.stop()
+    rawDb.close()
+  })
+
+  it('does not fire paused jobs, even when rescheduled', async () => {
+    vi.setSystemTime(new Date('2025-01-01T00:00:00Z'))
+    const { rawDb, db } = createDb()
+    const fired: string[] = []
+
+    const scheduler = new SchedulerService({
+      db,
+      onFire: (event) => {
+        fired.push(event.payload.firedAt)
+        return true
+      },
+    })
+
+    scheduler.start()
+    const job = {
+      id: 'job-paused',
+      schedule: { type: 'interval' as const, everyMs: 1000 },
+      userId: 'user-1',
+    }
+    scheduler.schedule('ext', job)
+
+    expect(scheduler.pauseJob('ext:job-paused', 'user-2')).toBe(false)
+    expect(scheduler.pauseJob('ext:job-paused', 'user-1')).toBe(true)
+    scheduler.schedule('ext', job)
+
+    await vi.advanceTimersByTimeAsync(5000)
+    expect(fired).toEqual([])
+
+    const paused = new SchedulerRepository(db).getByIdForUser('ext:job-paused', 'user-1')
+    expect(paused?.enabled).toBe(false)
+    expect(paused?.pausedAt).toBe('2025-01-01T00:00:00.000Z')
+
+    scheduler.stop()
+    rawDb.close()
+  })
+
+  it('resumes paused jobs with the chosen misfire handling', async () => {
+    vi.setSystemTime(new Date('2025-01-01T00:00:00Z'))
+    const { rawDb, db } = createDb()
+    const fired: string[] = []
+
+    const scheduler = new SchedulerService({
+      db,
+      onFire: (event) => {
+        fired.push(`${event.payload.id}@${event.payload.firedAt}`)
+        return true
+      },
+    })
+
+    scheduler.start()
+    for (const id of ['job-once', 'job-skip']) {
+      scheduler.schedule('ext', {
+        id,
+        schedule: { type: 'interval', everyMs: 60_000 },
+        userId: 'user-1',
+      })
+      scheduler.pauseJob(`ext:${id}`, 'user-1')
+    }
+
+    await vi.advanceTimersByTimeAsync(150_000)
+    expect(scheduler.resumeJob('ext:job-once', 'user-1', 'run_once')).toBe(true)
+    expect(scheduler.resumeJob('ext:job-skip', 'user-1', 'skip')).toBe(true)
+    expect(scheduler.resumeJob('ext:job-skip', 'user-1')).toBe(false)
+
+    await vi.advanceTimersByTimeAsync(0)
+    expect(fired).toEqual(['job-once@2025-01-01T00:02:30.000Z'])
+
+    await vi.advanceTimersByTimeAsync(60_000)
+    expect(fired.slice(1).sort()).toEqual([
+      'job-once@2025-01-01T00:03:30.000Z',
+      'job-skip@2025-01-01T00:03:30.000Z',
+    ])
+
+    scheduler.stop()
+    rawDb.close()
+  })
+
+  it('runs a job now with the manual flag without changing its schedule', async () => {
+    vi.setSystemTime(new Date('2025-01-01T00:00:00Z'))
+    const { rawDb, db } = createDb()
+    const fired: Array<{ id: string; manual?: boolean }> = []
+    let available = true
+
+    const scheduler = new SchedulerService({
+      db,
+      onFire: (event) => {
+        fired.push({ id: event.payload.id, manual: event.payload.manual })
+        return available
+      },
+    })
+
+    scheduler.start()
+    scheduler.schedule('ext', {
+      id: 'job-manual',
+      schedule: { type: 'at', at: '2025-01-02T00:00:00Z' },
+      userId: 'user-1',
+      retry: { maxAttempts: 3, initialDelayMs: 1000 },
+    })
+
+    expect(scheduler.runJobNow('ext:job-manual', 'user-2')).toBe(false)
+    expect(scheduler.runJobNow('ext:job-manual', 'user-1')).toBe(true)
+    expect(fired).toEqual([{ id: 'job-manual', manual: true }])
+
+    // Failed manual runs are not retried
+    scheduler.updateJobResult('ext', 'job-manual', false, 'Boom')
+
+    const repo = new SchedulerRepository(db)
+    const job = repo.getByIdForUser('ext:job-manual', 'user-1')
+    expect(job?.enabled).toBe(true)
+    expect(job?.nextRunAt).toBe('2025-01-02T00:00:00.000Z')
+    expect(job?.retryAt).toBeNull()
+
+    const { runs } = repo.listRunsForUser('ext:job-manual', 'user-1', { limit: 10, offset: 0 })
+    expect(runs).toHaveLength(1)
+    expect(runs[0]).toMatchObject({ manual: true, status: 'error', error: 'Boom' })
+
+    available = false
+    expect(() => scheduler.runJobNow('ext:job-manual', 'user-1')).toThrow('No handler available')
+    expect(repo.getByIdForUser('ext:job-manual', 'user-1')?.enabled).toBe(true)
+
+    scheduler.stop()
+    rawDb.close()
+  })
+
+  it('previews upcoming runs without registering a job', () => {
+    vi.setSystemTime(new Date('2025-01-01T00:00:00Z'))
+    const { rawDb, db } = createDb()
+    const scheduler = new SchedulerService({ db, onFire: () => true })
+
+    const weekdays = scheduler.getUpcomingRuns(
+      { type: 'cron', cron: '30 8 * * 1-5', timezone: 'Europe/Stockholm' },
+      3
+    )
+    expect(weekdays.map((date) => date.toISOString())).toEqual([
+      '2025-01-01T07:30:00.000Z',
+      '2025-01-02T07:30:00.000Z',
+      '2025-01-03T07:30:00.000Z',
+    ])
+
+    const ending = scheduler.getUpcomingRuns(
+      { type: 'rrule', rrule: 'FREQ=DAILY;COUNT=2', dtstart: '2025-01-01T09:00:00Z' },
+      3
+    )
+    expect(ending).toHaveLength(2)
+    expect(scheduler.getUpcomingRuns({ type: 'at', at: '2024-12-31T00:00:00Z' }, 3)).toEqual([])
+    expect(() => scheduler.getUpcomingRuns({ type: 'interval', everyMs: 0 }, 3)).toThrow(
+      'Invalid schedule.everyMs'
+    )
+    expect(rawDb.prepare('SELECT COUNT(*) AS count FROM scheduler_jobs').get()).toEqual({ count: 0 })
+
+    rawDb.close()
+  })
 })
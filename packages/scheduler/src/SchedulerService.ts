@@ -1,7 +1,9 @@
+import { randomUUID } from 'node:crypto'
 import * as cronParser from 'cron-parser'
-import { and, asc, eq, lte } from 'drizzle-orm'
+import { and, asc, desc, eq, isNotNull, isNull, lte, or, sql } from 'drizzle-orm'
 import type { BetterSQLite3Database } from 'drizzle-orm/better-sqlite3'
-import { schedulerJobs } from './schema.js'
+import { schedulerJobRuns, schedulerJobs } from './schema.js'
+import { assertValidRRuleSchedule, getNextRRuleOccurrence, type RRuleScheduleValue } from './rrule.js'
 
 export type SchedulerMisfirePolicy = 'run_once' | 'skip'
 
@@ -9,12 +11,67 @@ export type SchedulerSchedule =
   | { type: 'at'; at: string }
   | { type: 'cron'; cron: string; timezone?: string }
   | { type: 'interval'; everyMs: number }
+  | {
+      type: 'rrule'
+      /** RFC 5545 recurrence rule, e.g. `FREQ=MONTHLY;BYDAY=MO,TU,WE,TH,FR;BYSETPOS=-1` */
+      rrule: string
+      /** Start of the recurrence (ISO 8601). Without an offset it is local to `timezone` */
+      dtstart: string
+      /** IANA time zone the rule is evaluated in. Defaults to UTC */
+      timezone?: string
+      /** Excluded occurrences (ISO 8601) */
+      exdates?: string[]
+    }
+
+/**
+ * Retry policy for failed runs.
+ * The delay before retry n is `initialDelayMs * multiplier^(n - 1)`, capped at `maxDelayMs`.
+ */
+export interface SchedulerRetryPolicy {
+  /** Maximum number of retries after a failed run */
+  maxAttempts: number
+  /** Delay before the first retry, in milliseconds */
+  initialDelayMs: number
+  /** Factor the delay grows by for each retry. Defaults to 2 */
+  multiplier?: number
+  /** Maximum delay between retries, in milliseconds */
+  maxDelayMs?: number
+}
+
+/**
+ * Get the value a schedule is stored with in `schedule_value`, as expected by
+ * getScheduleDescription.
+ */
+export function getScheduleValue(schedule: SchedulerSchedule): string {
+  switch (schedule.type) {
+    case 'at':
+      return schedule.at
+    case 'interval':
+      return String(schedule.everyMs)
+    case 'cron':
+      return schedule.cron
+    case 'rrule': {
+      const value: RRuleScheduleValue = {
+        rrule: schedule.rrule,
+        dtstart: schedule.dtstart,
+        exdates: schedule.exdates,
+      }
+      return JSON.stringify(value)
+    }
+  }
+}
 
 export interface SchedulerJobRequest {
   id: string
   schedule: SchedulerSchedule
   payload?: Record<string, unknown>
   misfire?: SchedulerMisfirePolicy
+  /**
+   * Retry failed runs (reported through updateJobResult) with backoff.
+   * Retries fire alongside the regular schedule; the next regular fire
+   * cancels any pending retry.
+   */
+  retry?: SchedulerRetryPolicy
   /**
    * User ID for the job owner.
    * All scheduled jobs must be associated with a user.
@@ -30,6 +87,10 @@ export interface SchedulerFirePayload {
   delayMs: number
   /** User ID for the job owner */
   userId: string
+  /** Retry attempt number, starting at 1. Not set for regular fires */
+  retryAttempt?: number
+  /** Set when the user started the run (run now) instead of the schedule */
+  manual?: boolean
 }
 
 export interface SchedulerFireEvent {
@@ -61,6 +122,8 @@ type SchedulerJobRow = typeof schedulerJobs.$inferSelect
 // Values larger than this cause TimeoutOverflowWarning and get clamped to 1
 const MAX_TIMEOUT_MS = 2147483647
 
+const DEFAULT_RETRY_MULTIPLIER = 2
+
 export class SchedulerService {
   private readonly db: SchedulerDb
   private readonly onFire: (event: SchedulerFireEvent) => boolean
@@ -106,15 +169,21 @@ export class SchedulerService {
     this.assertValidJob(job)
     const now = this.now()
     const scheduleType = job.schedule.type
-    const scheduleValue = this.getScheduleValue(job.schedule)
+    const scheduleValue = getScheduleValue(job.schedule)
     const timezone =
-      job.schedule.type === 'cron' ? job.schedule.timezone ?? null : null
+      job.schedule.type === 'cron' || job.schedule.type === 'rrule'
+        ? job.schedule.timezone ?? null
+        : null
     const misfirePolicy: SchedulerMisfirePolicy = job.misfire ?? 'run_once'
     const nextRunAt = this.computeNextRunAt(job.schedule, now, timezone)
+    if (!nextRunAt) {
+      throw new Error('Schedule has no future occurrences')
+    }
 
     const id = this.buildJobId(extensionId, job.id)
     const nowIso = now.toISOString()
     const payloadJson = job.payload ? JSON.stringify(job.payload) : null
+    const retryPolicyJson = job.retry ? JSON.stringify(job.retry) : null
     const userId = job.userId
 
     this.db
@@ -129,6 +198,7 @@ export class SchedulerService {
         payloadJson,
         timezone,
         misfirePolicy,
+        retryPolicyJson,
         nextRunAt,
         enabled: true,
         createdAt: nowIso,
@@ -143,8 +213,13 @@ export class SchedulerService {
           payloadJson,
           timezone,
           misfirePolicy,
+          retryPolicyJson,
+          retryAttempt: 0,
+          retryAt: null,
           nextRunAt,
-          enabled: true,
+          cancelledAt: null,
+          // Re-registering a job must not undo a pause by the user
+          enabled: sql`${schedulerJobs.pausedAt} IS NULL`,
           updatedAt: nowIso,
         },
       })
@@ -154,21 +229,183 @@ export class SchedulerService {
   }
 
   /**
-   * Disable a scheduled job for an extension.
+   * Get the next run times of a schedule without registering a job.
+   * @param schedule The schedule to preview
+   * @param count Maximum number of run times to return
+   * @param from Only return run times after this time. Defaults to now
+   * @returns Up to `count` run times, fewer if the schedule ends
+   * @throws If the schedule is invalid
+   */
+  getUpcomingRuns(schedule: SchedulerSchedule, count: number, from: Date = this.now()): Date[] {
+    this.assertValidSchedule(schedule)
+    if (schedule.type === 'at') {
+      const at = new Date(schedule.at)
+      return count > 0 && at > from ? [at] : []
+    }
+
+    const timezone = schedule.type === 'interval' ? null : schedule.timezone ?? null
+    const runs: Date[] = []
+    let cursor = from
+    while (runs.length < count) {
+      const next = this.computeNextRunAt(schedule, cursor, timezone)
+      if (!next) break
+      cursor = new Date(next)
+      runs.push(cursor)
+    }
+    return runs
+  }
+
+  /**
+   * Disable a scheduled job for an extension, including any pending retry.
+   * A cancelled job can no longer be resumed by the user.
    */
   cancel(extensionId: string, jobId: string): void {
     const id = this.buildJobId(extensionId, jobId)
+    const nowIso = this.now().toISOString()
     this.db
       .update(schedulerJobs)
-      .set({ enabled: false, updatedAt: this.now().toISOString() })
+      .set({
+        enabled: false,
+        retryAt: null,
+        pausedAt: null,
+        cancelledAt: nowIso,
+        updatedAt: nowIso,
+      })
       .where(eq(schedulerJobs.id, id))
       .run()
     this.scheduleNextTick()
   }
 
+  /**
+   * Pause a job on behalf of its owner. A paused job does not fire until it
+   * is resumed, even if its extension schedules it again.
+   * @param id The composite job ID (extensionId:jobId)
+   * @param userId The user ID to verify ownership
+   * @returns false if the job was not found or is not owned by the user
+   * @throws If the job has completed or been cancelled
+   */
+  pauseJob(id: string, userId: string): boolean {
+    const row = this.db
+      .select()
+      .from(schedulerJobs)
+      .where(and(eq(schedulerJobs.id, id), eq(schedulerJobs.userId, userId)))
+      .get()
+    if (!row) return false
+    if (row.pausedAt) return true
+    if (!row.enabled) {
+      throw new Error('Only active jobs can be paused')
+    }
+
+    const nowIso = this.now().toISOString()
+    this.db
+      .update(schedulerJobs)
+      .set({ enabled: false, pausedAt: nowIso, retryAt: null, updatedAt: nowIso })
+      .where(eq(schedulerJobs.id, id))
+      .run()
+    this.scheduleNextTick()
+    return true
+  }
+
+  /**
+   * Resume a paused job.
+   * @param id The composite job ID (extensionId:jobId)
+   * @param userId The user ID to verify ownership
+   * @param misfire What to do with runs missed while paused: `run_once` fires
+   *   the job once right away, `skip` continues with the next occurrence.
+   *   A one-shot job whose time passed is completed without firing when skipped.
+   * @returns false if the job was not found, not owned by the user or not paused
+   */
+  resumeJob(id: string, userId: string, misfire: SchedulerMisfirePolicy = 'run_once'): boolean {
+    const row = this.db
+      .select()
+      .from(schedulerJobs)
+      .where(and(eq(schedulerJobs.id, id), eq(schedulerJobs.userId, userId)))
+      .get()
+    if (!row?.pausedAt) return false
+
+    const now = this.now()
+    const nowIso = now.toISOString()
+    let nextRunAt: string | null = row.nextRunAt
+    if (nextRunAt < nowIso) {
+      if (misfire === 'run_once') {
+        nextRunAt = nowIso
+      } else if (row.scheduleType === 'at') {
+        nextRunAt = null
+      } else {
+        nextRunAt = this.tryComputeNextRunAt(this.parseSchedule(row), now, row.timezone, row.id)
+      }
+    }
+
+    this.db
+      .update(schedulerJobs)
+      .set({
+        enabled: nextRunAt !== null,
+        pausedAt: null,
+        nextRunAt: nextRunAt ?? row.nextRunAt,
+        updatedAt: nowIso,
+      })
+      .where(eq(schedulerJobs.id, id))
+      .run()
+    this.scheduleNextTick()
+    return true
+  }
+
+  /**
+   * Fire a job once right away, without changing its schedule. Works for
+   * paused and completed jobs too. The fire has `manual: true` in its payload.
+   * @param id The composite job ID (extensionId:jobId)
+   * @param userId The user ID to verify ownership
+   * @returns false if the job was not found or is not owned by the user
+   * @throws If no handler is available for the job (e.g. the extension is not loaded)
+   */
+  runJobNow(id: string, userId: string): boolean {
+    const row = this.db
+      .select()
+      .from(schedulerJobs)
+      .where(and(eq(schedulerJobs.id, id), eq(schedulerJobs.userId, userId)))
+      .get()
+    if (!row?.userId) return false
+
+    const firedAt = this.now().toISOString()
+    const runId = this.recordRun(row, {
+      scheduledFor: firedAt,
+      firedAt,
+      delayMs: 0,
+      status: 'running',
+      manual: true,
+    })
+    const payload = row.payloadJson ? this.safeParsePayload(row.payloadJson) : undefined
+    const accepted = this.onFire({
+      extensionId: row.extensionId,
+      payload: {
+        id: row.jobId,
+        payload,
+        scheduledFor: firedAt,
+        firedAt,
+        delayMs: 0,
+        userId: row.userId,
+        manual: true,
+      },
+    })
+
+    if (!accepted) {
+      this.finishRun(runId, firedAt, this.now(), 'error', 'No handler available for the job')
+      throw new Error('No handler available for the job')
+    }
+
+    this.db
+      .update(schedulerJobs)
+      .set({ lastRunAt: firedAt, updatedAt: firedAt })
+      .where(eq(schedulerJobs.id, id))
+      .run()
+    return true
+  }
+
   /**
    * Update the result of a job execution.
    * Called by the extension host after a scheduler fire callback completes.
+   * Also completes the job's latest running entry in the run history, and
+   * schedules a retry for failed runs if the job has a retry policy.
    * @param extensionId The extension that owns the job
    * @param jobId The job ID within the extension
    * @param success Whether the callback succeeded
@@ -176,15 +413,83 @@ export class SchedulerService {
    */
   updateJobResult(extensionId: string, jobId: string, success: boolean, error?: string): void {
     const id = this.buildJobId(extensionId, jobId)
+    const now = this.now()
+    const runError = success ? null : (error ?? 'Unknown error')
     this.db
       .update(schedulerJobs)
       .set({
         lastRunStatus: success ? 'success' : 'error',
-        lastRunError: success ? null : (error ?? 'Unknown error'),
-        updatedAt: this.now().toISOString(),
+        lastRunError: runError,
+        updatedAt: now.toISOString(),
       })
       .where(eq(schedulerJobs.id, id))
       .run()
+
+    const run = this.db
+      .select({
+        id: schedulerJobRuns.id,
+        firedAt: schedulerJobRuns.firedAt,
+        manual: schedulerJobRuns.manual,
+      })
+      .from(schedulerJobRuns)
+      .where(and(eq(schedulerJobRuns.jobId, id), eq(schedulerJobRuns.status, 'running')))
+      .orderBy(desc(schedulerJobRuns.firedAt))
+      .limit(1)
+      .get()
+    if (run) {
+      this.finishRun(run.id, run.firedAt, now, success ? 'success' : 'error', runError)
+    }
+
+    // Manual runs are for testing and are not retried
+    if (!success && !run?.manual) {
+      this.scheduleRetry(id, now)
+    }
+  }
+
+  /**
+   * Schedule the next retry of a failed job, if its retry policy allows one.
+   */
+  private scheduleRetry(id: string, now: Date): void {
+    const row = this.db.select().from(schedulerJobs).where(eq(schedulerJobs.id, id)).get()
+    if (!row?.retryPolicyJson || !row.userId) return
+    // Paused and cancelled jobs are not retried
+    if (row.pausedAt || row.cancelledAt) return
+    // One-shot jobs are disabled once they have fired, other disabled jobs are not retried
+    if (!row.enabled && row.scheduleType !== 'at') return
+
+    const policy = this.safeParseRetryPolicy(row.retryPolicyJson)
+    if (!policy) return
+
+    const attempt = row.retryAttempt + 1
+    if (attempt > policy.maxAttempts) {
+      this.logger?.info('Scheduler job failed after all retries', {
+        extensionId: row.extensionId,
+        jobId: row.jobId,
+        retries: policy.maxAttempts,
+      })
+      return
+    }
+
+    const multiplier = policy.multiplier ?? DEFAULT_RETRY_MULTIPLIER
+    const delayMs = Math.min(
+      policy.initialDelayMs * Math.pow(multiplier, attempt - 1),
+      policy.maxDelayMs ?? Number.POSITIVE_INFINITY
+    )
+    const retryAt = new Date(now.getTime() + delayMs).toISOString()
+
+    this.db
+      .update(schedulerJobs)
+      .set({ retryAttempt: attempt, retryAt, updatedAt: now.toISOString() })
+      .where(eq(schedulerJobs.id, id))
+      .run()
+
+    this.logger?.debug('Scheduler job retry scheduled', {
+      extensionId: row.extensionId,
+      jobId: row.jobId,
+      attempt,
+      retryAt,
+    })
+    this.scheduleNextTick()
   }
 
   private scheduleNextTick(delayMs?: number): void {
@@ -216,12 +521,22 @@ export class SchedulerService {
       const rows = this.db
         .select()
         .from(schedulerJobs)
-        .where(and(eq(schedulerJobs.enabled, true), lte(schedulerJobs.nextRunAt, nowIso)))
+        .where(
+          or(
+            and(eq(schedulerJobs.enabled, true), lte(schedulerJobs.nextRunAt, nowIso)),
+            and(lte(schedulerJobs.retryAt, nowIso), isNull(schedulerJobs.pausedAt))
+          )
+        )
         .orderBy(asc(schedulerJobs.nextRunAt))
         .all()
 
       for (const row of rows) {
-        this.fireJob(row, now)
+        // A due regular fire replaces a pending retry
+        if (row.enabled && row.nextRunAt <= nowIso) {
+          this.fireJob(row, now)
+        } else {
+          this.fireRetry(row, now)
+        }
       }
     } catch (error) {
       this.logger?.error('Scheduler tick failed', {
@@ -251,30 +566,22 @@ export class SchedulerService {
 
     const misfirePolicy = row.misfirePolicy ?? 'run_once'
     const shouldSkip = delayMs > 0 && misfirePolicy === 'skip'
+    const runId = this.recordRun(row, {
+      scheduledFor,
+      firedAt,
+      delayMs,
+      status: shouldSkip ? 'skipped' : 'running',
+    })
 
     if (!shouldSkip) {
-      const payload = row.payloadJson ? this.safeParsePayload(row.payloadJson) : undefined
-      const shouldContinue = this.onFire({
-        extensionId: row.extensionId,
-        payload: {
-          id: row.jobId,
-          payload,
-          scheduledFor,
-          firedAt,
-          delayMs,
-          userId: row.userId,
-        },
+      const delivered = this.deliver(row, runId, {
+        id: row.jobId,
+        scheduledFor,
+        firedAt,
+        delayMs,
+        userId: row.userId,
       })
-
-      // If onFire returns false, disable the job (e.g., extension no longer loaded)
-      if (!shouldContinue) {
-        this.logger?.warn('Scheduler job handler returned false, disabling job', {
-          extensionId: row.extensionId,
-          jobId: row.jobId,
-        })
-        this.disableJob(row.id, firedAt)
-        return
-      }
+      if (!delivered) return
     }
 
     const scheduleType = row.scheduleType
@@ -292,7 +599,137 @@ export class SchedulerService {
     this.updateNextRun(row.id, firedAt, nextRunAt)
   }
 
+  /**
+   * Fire a pending retry of a failed job. Retries ignore the misfire policy
+   * and do not change the regular schedule.
+   */
+  private fireRetry(row: SchedulerJobRow, now: Date): void {
+    const scheduledFor = row.retryAt
+    if (!scheduledFor || !row.userId) return
+
+    const firedAt = now.toISOString()
+    const delayMs = Math.max(0, now.getTime() - new Date(scheduledFor).getTime())
+    this.db
+      .update(schedulerJobs)
+      .set({ retryAt: null, lastRunAt: firedAt, updatedAt: firedAt })
+      .where(eq(schedulerJobs.id, row.id))
+      .run()
+
+    const runId = this.recordRun(row, {
+      scheduledFor,
+      firedAt,
+      delayMs,
+      status: 'running',
+      attempt: row.retryAttempt,
+    })
+    this.deliver(row, runId, {
+      id: row.jobId,
+      scheduledFor,
+      firedAt,
+      delayMs,
+      userId: row.userId,
+      retryAttempt: row.retryAttempt,
+    })
+  }
+
+  /**
+   * Pass a fire to the handler. If the handler returns false (e.g. the
+   * extension is no longer loaded), the job is disabled.
+   * @returns Whether the handler accepted the fire
+   */
+  private deliver(
+    row: SchedulerJobRow,
+    runId: string,
+    fire: Omit<SchedulerFirePayload, 'payload'>
+  ): boolean {
+    const payload = row.payloadJson ? this.safeParsePayload(row.payloadJson) : undefined
+    const shouldContinue = this.onFire({
+      extensionId: row.extensionId,
+      payload: { ...fire, payload },
+    })
+
+    if (!shouldContinue) {
+      this.logger?.warn('Scheduler job handler returned false, disabling job', {
+        extensionId: row.extensionId,
+        jobId: row.jobId,
+      })
+      this.finishRun(runId, fire.firedAt, this.now(), 'error', 'No handler available for the job')
+      this.disableJob(row.id, fire.firedAt)
+    }
+    return shouldContinue
+  }
+
+  /**
+   * Add an entry to the run history.
+   * @returns The run ID
+   */
+  private recordRun(
+    row: SchedulerJobRow,
+    run: {
+      scheduledFor: string
+      firedAt: string
+      delayMs: number
+      status: 'running' | 'skipped'
+      attempt?: number
+      manual?: boolean
+    }
+  ): string {
+    const id = randomUUID()
+    this.db
+      .insert(schedulerJobRuns)
+      .values({
+        id,
+        jobId: row.id,
+        userId: row.userId,
+        scheduledFor: run.scheduledFor,
+        firedAt: run.firedAt,
+        delayMs: run.delayMs,
+        durationMs: run.status === 'skipped' ? 0 : null,
+        status: run.status,
+        attempt: run.attempt ?? 0,
+        manual: run.manual ?? false,
+      })
+      .run()
+    return id
+  }
+
+  private finishRun(
+    id: string,
+    firedAt: string,
+    finishedAt: Date,
+    status: 'success' | 'error',
+    error: string | null
+  ): void {
+    const durationMs = Math.max(0, finishedAt.getTime() - new Date(firedAt).getTime())
+    this.db
+      .update(schedulerJobRuns)
+      .set({ status, error, durationMs })
+      .where(eq(schedulerJobRuns.id, id))
+      .run()
+  }
+
   private getNextDelay(): number | null {
+    const delays = [this.getNextRunDelay(), this.getNextRetryDelay()].filter(
+      (delay): delay is number => delay !== null
+    )
+    return delays.length > 0 ? Math.min(...delays) : null
+  }
+
+  private getNextRetryDelay(): number | null {
+    const row = this.db
+      .select({ retryAt: schedulerJobs.retryAt })
+      .from(schedulerJobs)
+      .where(and(isNotNull(schedulerJobs.retryAt), isNull(schedulerJobs.pausedAt)))
+      .orderBy(asc(schedulerJobs.retryAt))
+      .limit(1)
+      .get()
+    if (!row?.retryAt) return null
+
+    const retryTime = new Date(row.retryAt).getTime()
+    return Number.isNaN(retryTime) ? null : retryTime - this.now().getTime()
+  }
+
+  private getNextRunDelay(): number | null {
     const rows = this.db
       .select({ id: schedulerJobs.id, nextRunAt: schedulerJobs.nextRunAt })
       .from(schedulerJobs)
@@ -326,6 +763,8 @@ export class SchedulerService {
       .set({
         lastRunAt: firedAt,
         nextRunAt,
+        retryAttempt: 0,
+        retryAt: null,
         updatedAt: firedAt,
       })
       .where(eq(schedulerJobs.id, id))
@@ -338,17 +777,22 @@ export class SchedulerService {
       .set({
         enabled: false,
         lastRunAt: firedAt,
+        retryAttempt: 0,
+        retryAt: null,
         updatedAt: firedAt,
       })
       .where(eq(schedulerJobs.id, id))
       .run()
   }
 
+  /**
+   * @returns The next run time, or null if the schedule has no more occurrences
+   */
   private computeNextRunAt(
     schedule: SchedulerSchedule,
     from: Date,
     timezone?: string | null
-  ): string {
+  ): string | null {
     switch (schedule.type) {
       case 'at': {
         const date = this.parseDate(schedule.at)
@@ -370,17 +814,10 @@ export class SchedulerService {
         })
         return interval.next().toDate().toISOString()
       }
-    }
-  }
-
-  private getScheduleValue(schedule: SchedulerSchedule): string {
-    switch (schedule.type) {
-      case 'at':
-        return schedule.at
-      case 'interval':
-        return String(schedule.everyMs)
-      case 'cron':
-        return schedule.cron
+      case 'rrule': {
+        const next = getNextRRuleOccurrence(schedule, timezone, from)
+        return next ? next.toISOString() : null
+      }
     }
   }
 
@@ -390,6 +827,11 @@ export class SchedulerService {
         return { type: 'interval', everyMs: Number(row.scheduleValue) }
       case 'cron':
         return { type: 'cron', cron: row.scheduleValue, timezone: row.timezone ?? undefined }
+      case 'rrule': {
+        // An unreadable value fails validation in computeNextRunAt, which disables the job
+        const value = this.safeParseRRuleValue(row.scheduleValue)
+        return { type: 'rrule', ...value, timezone: row.timezone ?? undefined }
+      }
       default:
         return { type: 'at', at: row.scheduleValue }
     }
@@ -421,6 +863,33 @@ export class SchedulerService {
       throw new Error('Job userId is required')
     }
     this.assertValidSchedule(job.schedule)
+    if (job.retry !== undefined) {
+      this.assertValidRetryPolicy(job.retry)
+    }
+  }
+
+  private assertValidRetryPolicy(policy: SchedulerRetryPolicy): void {
+    if (!policy || typeof policy !== 'object') {
+      throw new Error('Invalid retry policy')
+    }
+    if (!Number.isInteger(policy.maxAttempts) || policy.maxAttempts < 0) {
+      throw new Error('Invalid retry.maxAttempts value')
+    }
+    if (!Number.isFinite(policy.initialDelayMs) || policy.initialDelayMs <= 0) {
+      throw new Error('Invalid retry.initialDelayMs value')
+    }
+    if (
+      policy.multiplier !== undefined &&
+      (!Number.isFinite(policy.multiplier) || policy.multiplier < 1)
+    ) {
+      throw new Error('Invalid retry.multiplier value')
+    }
+    if (
+      policy.maxDelayMs !== undefined &&
+      (!Number.isFinite(policy.maxDelayMs) || policy.maxDelayMs <= 0)
+    ) {
+      throw new Error('Invalid retry.maxDelayMs value')
+    }
   }
 
   private assertValidSchedule(schedule: SchedulerSchedule): void {
@@ -454,6 +923,10 @@ export class SchedulerService {
         }
         return
       }
+      case 'rrule': {
+        assertValidRRuleSchedule(schedule, schedule.timezone)
+        return
+      }
     }
   }
 
@@ -479,6 +952,32 @@ export class SchedulerService {
     }
   }
 
+  private safeParseRRuleValue(value: string): RRuleScheduleValue {
+    try {
+      const parsed = JSON.parse(value) as Partial<RRuleScheduleValue>
+      return {
+        rrule: typeof parsed.rrule === 'string' ? parsed.rrule : '',
+        dtstart: typeof parsed.dtstart === 'string' ? parsed.dtstart : '',
+        exdates: Array.isArray(parsed.exdates) ? parsed.exdates : undefined,
+      }
+    } catch {
+      return { rrule: '', dtstart: '' }
+    }
+  }
+
+  private safeParseRetryPolicy(policy: string): SchedulerRetryPolicy | null {
+    try {
+      const parsed = JSON.parse(policy) as SchedulerRetryPolicy
+      this.assertValidRetryPolicy(parsed)
+      return parsed
+    } catch (error) {
+      this.logger?.warn('Invalid scheduler retry policy', {
+        error: error instanceof Error ? error.message : String(error),
+      })
+      return null
+    }
+  }
+
   private buildJobId(extensionId: string, jobId: string): string {
     return `${extensionId}:${jobId}`
   }
@@ -1,9 +1,10 @@
 import path from 'node:path'
 import { fileURLToPath } from 'node:url'
 
-export { SchedulerService } from './SchedulerService.js'
+export { SchedulerService, getScheduleValue } from './SchedulerService.js'
 export type {
   SchedulerMisfirePolicy,
+  SchedulerRetryPolicy,
   SchedulerSchedule,
   SchedulerJobRequest,
   SchedulerFirePayload,
@@ -11,15 +12,17 @@ export type {
   SchedulerServiceOptions,
   SchedulerDb,
 } from './SchedulerService.js'
-export { schedulerJobs, schedulerSchema } from './schema.js'
+export { schedulerJobRuns, schedulerJobs, schedulerSchema } from './schema.js'
 export { SchedulerRepository } from './SchedulerRepository.js'
-export type { SchedulerJobRow } from './SchedulerRepository.js'
+export type { SchedulerJobRow, SchedulerJobRunRow } from './SchedulerRepository.js'
 export { SchedulerCleanupService } from './SchedulerCleanupService.js'
 export type {
   SchedulerCleanupServiceOptions,
   SchedulerCleanupLogger,
 } from './SchedulerCleanupService.js'
 export { getScheduleDescription } from './scheduleDescription.js'
+export { parseRRule, getNextRRuleOccurrence, describeRRule } from './rrule.js'
+export type { ParsedRRule, RRuleFrequency, RRuleWeekday, RRuleScheduleValue } from './rrule.js'
 
 /**
  * Get migrations path for scheduler package
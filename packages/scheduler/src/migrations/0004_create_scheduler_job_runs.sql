@@ -0,0 +1,15 @@
+-- One row per fire of a scheduled job, so earlier failures are kept after later runs
+CREATE TABLE IF NOT EXISTS scheduler_job_runs (
+  id TEXT PRIMARY KEY,
+  job_id TEXT NOT NULL,
+  user_id TEXT,
+  scheduled_for TEXT NOT NULL,
+  fired_at TEXT NOT NULL,
+  delay_ms INTEGER NOT NULL,
+  duration_ms INTEGER,
+  status TEXT NOT NULL,
+  error TEXT
+);
+
+CREATE INDEX IF NOT EXISTS idx_scheduler_job_runs_job_fired ON scheduler_job_runs(job_id, fired_at);
+CREATE INDEX IF NOT EXISTS idx_scheduler_job_runs_user_fired ON scheduler_job_runs(user_id, fired_at);
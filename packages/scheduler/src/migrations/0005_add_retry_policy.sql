@@ -0,0 +1,6 @@
+ALTER TABLE scheduler_jobs ADD COLUMN retry_policy_json TEXT;
+ALTER TABLE scheduler_jobs ADD COLUMN retry_attempt INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE scheduler_jobs ADD COLUMN retry_at TEXT;
+ALTER TABLE scheduler_job_runs ADD COLUMN attempt INTEGER NOT NULL DEFAULT 0;
+
+CREATE INDEX IF NOT EXISTS idx_scheduler_jobs_retry_at ON scheduler_jobs(retry_at);
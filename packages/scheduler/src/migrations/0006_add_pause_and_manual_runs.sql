@@ -0,0 +1,2 @@
+ALTER TABLE scheduler_jobs ADD COLUMN paused_at TEXT;
+ALTER TABLE scheduler_job_runs ADD COLUMN manual INTEGER NOT NULL DEFAULT 0;
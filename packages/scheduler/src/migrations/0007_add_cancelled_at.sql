@@ -0,0 +1 @@
+ALTER TABLE scheduler_jobs ADD COLUMN cancelled_at TEXT;
@@ -0,0 +1,147 @@
+import { describe, expect, it } from 'vitest'
+import { describeRRule, getNextRRuleOccurrence, parseRRule } from './rrule.js'
+import type { RRuleScheduleValue } from './rrule.js'
+
+const occurrences = (
+  schedule: RRuleScheduleValue,
+  timezone: string | null,
+  from: string,
+  limit = 5
+): string[] => {
+  const result: string[] = []
+  let after = new Date(from)
+  while (result.length < limit) {
+    const next = getNextRRuleOccurrence(schedule, timezone, after)
+    if (!next) break
+    result.push(next.toISOString())
+    after = next
+  }
+  return result
+}
+
+describe('parseRRule', () => {
+  it('parses rule parts', () => {
+    const rule = parseRRule('RRULE:FREQ=MONTHLY;INTERVAL=2;BYDAY=2TU,-1FR;COUNT=5')
+    expect(rule.freq).toBe('MONTHLY')
+    expect(rule.interval).toBe(2)
+    expect(rule.count).toBe(5)
+    expect(rule.byDay).toEqual([
+      { weekday: 2, ordinal: 2 },
+      { weekday: 5, ordinal: -1 },
+    ])
+  })
+
+  it.each([
+    ['BYDAY=MO', 'must contain FREQ'],
+    ['FREQ=HOURLY', 'Unsupported RRULE frequency'],
+    ['FREQ=DAILY;BYWEEKNO=1', 'Unsupported RRULE part'],
+    ['FREQ=DAILY;INTERVAL=0', 'INTERVAL'],
+    ['FREQ=WEEKLY;BYDAY=2TU', 'ordinals'],
+    ['FREQ=DAILY;UNTIL=2027-03-01', 'UNTIL'],
+  ])('rejects %s', (rrule, message) => {
+    expect(() => parseRRule(rrule)).toThrow(message)
+  })
+})
+
+describe('getNextRRuleOccurrence', () => {
+  it('handles every second Tuesday across DST', () => {
+    const schedule = { rrule: 'FREQ=WEEKLY;INTERVAL=2;BYDAY=TU', dtstart: '2025-01-07T09:00:00' }
+    expect(occurrences(schedule, 'Europe/Stockholm', '2025-03-01T00:00:00Z', 4)).toEqual([
+      '2025-03-04T08:00:00.000Z',
+      '2025-03-18T08:00:00.000Z',
+      '2025-04-01T07:00:00.000Z',
+      '2025-04-15T07:00:00.000Z',
+    ])
+  })
+
+  it('handles the last weekday of the month', () => {
+    const schedule = {
+      rrule: 'FREQ=MONTHLY;BYDAY=MO,TU,WE,TH,FR;BYSETPOS=-1',
+      dtstart: '2025-05-01T09:00:00Z',
+    }
+    expect(occurrences(schedule, null, '2025-05-01T00:00:00Z', 3)).toEqual([
+      '2025-05-30T09:00:00.000Z',
+      '2025-06-30T09:00:00.000Z',
+      '2025-07-31T09:00:00.000Z',
+    ])
+  })
+
+  it('ends after COUNT occurrences, counting from dtstart', () => {
+    const schedule = { rrule: 'FREQ=DAILY;COUNT=3', dtstart: '2025-01-01T08:30:00Z' }
+    expect(occurrences(schedule, null, '2024-12-01T00:00:00Z')).toEqual([
+      '2025-01-01T08:30:00.000Z',
+      '2025-01-02T08:30:00.000Z',
+      '2025-01-03T08:30:00.000Z',
+    ])
+    expect(getNextRRuleOccurrence(schedule, null, new Date('2025-01-01T12:00:00Z'))?.toISOString()).toBe(
+      '2025-01-02T08:30:00.000Z'
+    )
+  })
+
+  it('ends at UNTIL and skips EXDATEs', () => {
+    const schedule = {
+      rrule: 'FREQ=DAILY;UNTIL=20250104',
+      dtstart: '2025-01-01T08:30:00',
+      exdates: ['2025-01-02T08:30:00'],
+    }
+    expect(occurrences(schedule, 'Europe/Stockholm', '2024-12-01T00:00:00Z')).toEqual([
+      '2025-01-01T07:30:00.000Z',
+      '2025-01-03T07:30:00.000Z',
+      '2025-01-04T07:30:00.000Z',
+    ])
+  })
+
+  it('moves times in the DST gap forward', () => {
+    const schedule = { rrule: 'FREQ=DAILY', dtstart: '2025-03-29T02:30:00' }
+    expect(occurrences(schedule, 'Europe/Stockholm', '2025-03-29T00:00:00Z', 3)).toEqual([
+      '2025-03-29T01:30:00.000Z',
+      // 02:30 does not exist on 2025-03-30, so the run is at 03:30 CEST
+      '2025-03-30T01:30:00.000Z',
+      '2025-03-31T00:30:00.000Z',
+    ])
+  })
+
+  it('skips months without the day', () => {
+    const schedule = { rrule: 'FREQ=YEARLY;BYMONTH=2;BYMONTHDAY=-1', dtstart: '2024-01-01T10:00:00Z' }
+    expect(occurrences(schedule, null, '2024-01-01T00:00:00Z', 2)).toEqual([
+      '2024-02-29T10:00:00.000Z',
+      '2025-02-28T10:00:00.000Z',
+    ])
+  })
+
+  it('returns null for rules that never match', () => {
+    const schedule = { rrule: 'FREQ=YEARLY;BYMONTH=2;BYMONTHDAY=30', dtstart: '2025-01-01T10:00:00Z' }
+    expect(getNextRRuleOccurrence(schedule, null, new Date('2025-01-01T00:00:00Z'))).toBeNull()
+  })
+})
+
+describe('describeRRule', () => {
+  it.each([
+    [
+      { rrule: 'FREQ=WEEKLY;INTERVAL=2;BYDAY=TU', dtstart: '2025-01-07T09:00:00' },
+      'Europe/Stockholm',
+      'Every 2 weeks on Tuesday at 09:00 (Europe/Stockholm)',
+    ],
+    [
+      { rrule: 'FREQ=MONTHLY;BYDAY=MO,TU,WE,TH,FR;BYSETPOS=-1', dtstart: '2025-01-01T08:30:00' },
+      null,
+      'Monthly on the last weekday at 08:30',
+    ],
+    [
+      {
+        rrule: 'FREQ=WEEKLY;UNTIL=20270301;COUNT=10',
+        dtstart: '2025-01-06T09:00:00',
+        exdates: ['2025-02-03T09:00:00'],
+      },
+      null,
+      'Weekly on Monday at 09:00 until 2027-03-01, 10 times, except 1 date',
+    ],
+    [
+      { rrule: 'FREQ=DAILY;BYHOUR=9,17;BYMINUTE=0', dtstart: '2025-01-06T09:00:00' },
+      null,
+      'Daily at 09:00 and 17:00',
+    ],
+  ])('describes %j', (schedule, timezone, expected) => {
+    expect(describeRRule(schedule, timezone)).toBe(expected)
+  })
+})
@@ -0,0 +1,818 @@
+/**
+ * RFC 5545 recurrence rules (RRULE) for the scheduler.
+ *
+ * Occurrences are generated in the wall-clock time of the schedule's time zone
+ * and converted to UTC afterwards, so a rule that fires at 09:00 keeps firing
+ * at 09:00 local time across DST changes. Local times that do not exist (the
+ * hour skipped when clocks go forward) are moved forward by the length of the
+ * gap, and ambiguous times use the first occurrence, as RFC 5545 specifies.
+ *
+ * Supported rule parts: FREQ (DAILY, WEEKLY, MONTHLY, YEARLY), INTERVAL,
+ * COUNT, UNTIL, BYMONTH, BYMONTHDAY, BYDAY (with ordinals such as 2TU or -1FR
+ * in monthly and yearly rules), BYHOUR, BYMINUTE, BYSECOND, BYSETPOS and WKST.
+ */
+
+export type RRuleFrequency = 'DAILY' | 'WEEKLY' | 'MONTHLY' | 'YEARLY'
+
+export interface RRuleWeekday {
+  /** Day of the week, 0 = Sunday (as in Date.getUTCDay) */
+  weekday: number
+  /** Occurrence within the month or year, e.g. 2 for 2TU or -1 for -1FR */
+  ordinal?: number
+}
+
+export interface ParsedRRule {
+  freq: RRuleFrequency
+  interval: number
+  count?: number
+  until?: { value: string; utc: boolean; dateOnly: boolean }
+  byMonth: number[]
+  byMonthDay: number[]
+  byDay: RRuleWeekday[]
+  byHour: number[]
+  byMinute: number[]
+  bySecond: number[]
+  bySetPos: number[]
+  /** First day of the week, 0 = Sunday. Defaults to Monday */
+  wkst: number
+}
+
+/**
+ * The parts of an rrule schedule, as stored in the job's schedule value.
+ */
+export interface RRuleScheduleValue {
+  /** Recurrence rule, e.g. `FREQ=WEEKLY;INTERVAL=2;BYDAY=TU`. A leading `RRULE:` is allowed */
+  rrule: string
+  /**
+   * Start of the recurrence (ISO 8601). Without an offset it is a wall-clock
+   * time in the schedule's time zone. Its time of day is used when the rule
+   * has no BYHOUR/BYMINUTE/BYSECOND.
+   */
+  dtstart: string
+  /** Excluded occurrences (ISO 8601, same rules as dtstart) */
+  exdates?: string[]
+}
+
+const WEEKDAY_CODES = ['SU', 'MO', 'TU', 'WE', 'TH', 'FR', 'SA'] as const
+const WEEKDAY_NAMES = ['Sunday', 'Monday', 'Tuesday', 'Wednesday', 'Thursday', 'Friday', 'Saturday']
+const MONTH_NAMES = [
+  'January',
+  'February',
+  'March',
+  'April',
+  'May',
+  'June',
+  'July',
+  'August',
+  'September',
+  'October',
+  'November',
+  'December',
+]
+const FREQUENCIES: RRuleFrequency[] = ['DAILY', 'WEEKLY', 'MONTHLY', 'YEARLY']
+
+const SECOND_MS = 1000
+const DAY_MS = 24 * 60 * 60 * 1000
+
+/** How far past the search start occurrences are looked for before giving up */
+const MAX_SEARCH_YEARS = 100
+
+/**
+ * Parse an RRULE string.
+ * @throws If the rule is invalid or uses unsupported parts
+ */
+export function parseRRule(rrule: string): ParsedRRule {
+  const text = rrule.trim().replace(/^RRULE:/i, '')
+  if (!text) {
+    throw new Error('RRULE is empty')
+  }
+
+  const rule: ParsedRRule = {
+    freq: 'DAILY',
+    interval: 1,
+    byMonth: [],
+    byMonthDay: [],
+    byDay: [],
+    byHour: [],
+    byMinute: [],
+    bySecond: [],
+    bySetPos: [],
+    wkst: 1,
+  }
+  let hasFreq = false
+
+  for (const part of text.split(';')) {
+    if (!part) continue
+    const [rawName, value] = part.split('=', 2)
+    const name = rawName?.trim().toUpperCase()
+    if (!name || value === undefined || value.trim() === '') {
+      throw new Error(`Invalid RRULE part: ${part}`)
+    }
+    const upperValue = value.trim().toUpperCase()
+
+    switch (name) {
+      case 'FREQ':
+        if (!FREQUENCIES.includes(upperValue as RRuleFrequency)) {
+          throw new Error(`Unsupported RRULE frequency: ${value}`)
+        }
+        rule.freq = upperValue as RRuleFrequency
+        hasFreq = true
+        break
+      case 'INTERVAL':
+        rule.interval = parseIntegerInRange(name, upperValue, 1, Number.MAX_SAFE_INTEGER)
+        break
+      case 'COUNT':
+        rule.count = parseIntegerInRange(name, upperValue, 1, Number.MAX_SAFE_INTEGER)
+        break
+      case 'UNTIL': {
+        const match = /^(\d{8})(T\d{6}(Z)?)?$/.exec(upperValue)
+        if (!match) {
+          throw new Error(`Invalid RRULE UNTIL value: ${value}`)
+        }
+        rule.until = { value: upperValue, utc: Boolean(match[3]), dateOnly: !match[2] }
+        break
+      }
+      case 'BYMONTH':
+        rule.byMonth = parseIntegerList(name, upperValue, 1, 12)
+        break
+      case 'BYMONTHDAY':
+        rule.byMonthDay = parseIntegerList(name, upperValue, -31, 31, true)
+        break
+      case 'BYDAY':
+        rule.byDay = upperValue.split(',').map((day) => parseWeekday(day))
+        break
+      case 'BYHOUR':
+        rule.byHour = parseIntegerList(name, upperValue, 0, 23)
+        break
+      case 'BYMINUTE':
+        rule.byMinute = parseIntegerList(name, upperValue, 0, 59)
+        break
+      case 'BYSECOND':
+        rule.bySecond = parseIntegerList(name, upperValue, 0, 59)
+        break
+      case 'BYSETPOS':
+        rule.bySetPos = parseIntegerList(name, upperValue, -366, 366, true)
+        break
+      case 'WKST': {
+        const index = WEEKDAY_CODES.indexOf(upperValue as (typeof WEEKDAY_CODES)[number])
+        if (index === -1) {
+          throw new Error(`Invalid RRULE WKST value: ${value}`)
+        }
+        rule.wkst = index
+        break
+      }
+      default:
+        throw new Error(`Unsupported RRULE part: ${name}`)
+    }
+  }
+
+  if (!hasFreq) {
+    throw new Error('RRULE must contain FREQ')
+  }
+  if ((rule.freq === 'DAILY' || rule.freq === 'WEEKLY') && rule.byDay.some((day) => day.ordinal)) {
+    throw new Error('BYDAY ordinals are only allowed in MONTHLY and YEARLY rules')
+  }
+  if (rule.freq === 'WEEKLY' && rule.byMonthDay.length > 0) {
+    throw new Error('BYMONTHDAY is not allowed in WEEKLY rules')
+  }
+
+  return rule
+}
+
+/**
+ * Check that an rrule schedule can be used: the rule parses, the time zone
+ * exists, and dtstart and all exdates are valid dates.
+ * @throws With a description of the first problem found
+ */
+export function assertValidRRuleSchedule(schedule: RRuleScheduleValue, timezone?: string | null): void {
+  if (!schedule.rrule || typeof schedule.rrule !== 'string') {
+    throw new Error('Invalid schedule.rrule value')
+  }
+  parseRRule(schedule.rrule)
+  if (timezone) {
+    try {
+      getFormatter(timezone)
+    } catch {
+      throw new Error(`Invalid schedule.timezone value: ${timezone}`)
+    }
+  }
+  if (typeof schedule.dtstart !== 'string' || parseDateTime(schedule.dtstart, timezone) === null) {
+    throw new Error('Invalid schedule.dtstart value')
+  }
+  if (schedule.exdates !== undefined) {
+    if (!Array.isArray(schedule.exdates)) {
+      throw new Error('Invalid schedule.exdates value')
+    }
+    for (const exdate of schedule.exdates) {
+      if (typeof exdate !== 'string' || parseDateTime(exdate, timezone) === null) {
+        throw new Error(`Invalid schedule.exdates value: ${String(exdate)}`)
+      }
+    }
+  }
+}
+
+/**
+ * Get the first occurrence of an rrule schedule after a point in time.
+ * @param schedule The schedule
+ * @param timezone IANA time zone the rule is evaluated in. Defaults to UTC
+ * @param after Occurrences at or before this time are skipped
+ * @returns The occurrence, or null if the recurrence has ended
+ * @throws If the schedule is invalid
+ */
+export function getNextRRuleOccurrence(
+  schedule: RRuleScheduleValue,
+  timezone: string | null | undefined,
+  after: Date
+): Date | null {
+  const rule = parseRRule(schedule.rrule)
+  const tz = timezone || null
+  const start = parseDateTime(schedule.dtstart, tz)
+  if (start === null) {
+    throw new Error('Invalid schedule.dtstart value')
+  }
+  const dtstart = toWallTime(truncateToSeconds(start), tz)
+  const exdates = new Set(
+    (schedule.exdates ?? []).flatMap((exdate) => {
+      const instant = parseDateTime(exdate, tz)
+      return instant === null ? [] : [truncateToSeconds(instant)]
+    })
+  )
+  const until = rule.until ? parseUntil(rule.until, tz) : null
+
+  const afterMs = after.getTime()
+  const afterWall = toWallTime(afterMs, tz)
+  // Walls well before both limits are converted lazily; DST can only move them by hours
+  const skipConversionBefore = Math.min(afterWall, until === null ? afterWall : toWallTime(until, tz)) - 2 * DAY_MS
+
+  const startPeriod = rule.count === undefined ? getFirstRelevantPeriod(rule, dtstart, afterWall) : 0
+  const lastYear = Math.max(wallParts(afterWall).year, wallParts(dtstart).year) + MAX_SEARCH_YEARS
+  let generated = 0
+
+  for (let period = startPeriod; ; period++) {
+    const days = getPeriodDays(rule, dtstart, period)
+    if (days.periodYear > lastYear) return null
+
+    const walls = applySetPos(
+      rule.bySetPos,
+      days.days.flatMap((day) => getTimesOfDay(rule, dtstart, day))
+    )
+
+    for (const wall of walls) {
+      if (wall < dtstart) continue
+
+      generated++
+      if (wall >= skipConversionBefore) {
+        const instant = fromWallTime(wall, tz)
+        if (until !== null && instant > until) return null
+        if (instant > afterMs && !exdates.has(instant)) return new Date(instant)
+      }
+      if (rule.count !== undefined && generated >= rule.count) return null
+    }
+  }
+}
+
+/**
+ * Describe an rrule schedule in English, e.g.
+ * "Every 2 weeks on Tuesday at 09:00" or "Monthly on the last weekday at 08:30, 10 times".
+ * @throws If the rule cannot be parsed
+ */
+export function describeRRule(schedule: RRuleScheduleValue, timezone: string | null): string {
+  const rule = parseRRule(schedule.rrule)
+  const start = parseDateTime(schedule.dtstart, timezone)
+  const dtstart = start === null ? null : wallParts(toWallTime(start, timezone))
+
+  const parts: string[] = [describeFrequency(rule)]
+
+  const days = describeDays(rule, dtstart)
+  if (days) parts.push(days)
+
+  if (rule.byMonth.length > 0) {
+    const months = [...rule.byMonth].sort((a, b) => a - b)
+    parts.push(`in ${joinList(months.map((month) => MONTH_NAMES[month - 1] ?? String(month)), 'and')}`)
+  }
+
+  const times = describeTimes(rule, dtstart)
+  if (times) parts.push(times)
+
+  let description = parts.join(' ')
+  if (rule.until) {
+    const { value } = rule.until
+    description += ` until ${value.slice(0, 4)}-${value.slice(4, 6)}-${value.slice(6, 8)}`
+  }
+  if (rule.count !== undefined) {
+    description += `, ${rule.count} ${rule.count === 1 ? 'time' : 'times'}`
+  }
+  const exceptions = schedule.exdates?.length ?? 0
+  if (exceptions > 0) {
+    description += `, except ${exceptions} ${exceptions === 1 ? 'date' : 'dates'}`
+  }
+  if (timezone) {
+    description += ` (${timezone})`
+  }
+  return description
+}
+
+// ============================================================================
+// Occurrence generation
+// ============================================================================
+
+/**
+ * Find a period index shortly before the one containing `afterWall`, so rules
+ * without COUNT do not need to be expanded from dtstart.
+ */
+function getFirstRelevantPeriod(rule: ParsedRRule, dtstart: number, afterWall: number): number {
+  if (afterWall <= dtstart) return 0
+
+  const start = wallParts(dtstart)
+  const target = wallParts(afterWall)
+  const periods =
+    rule.freq === 'DAILY'
+      ? Math.floor((afterWall - dtstart) / DAY_MS)
+      : rule.freq === 'WEEKLY'
+        ? Math.floor((afterWall - dtstart) / (7 * DAY_MS))
+        : rule.freq === 'MONTHLY'
+          ? (target.year - start.year) * 12 + (target.month - start.month)
+          : target.year - start.year
+  return Math.max(0, Math.floor(periods / rule.interval) - 1)
+}
+
+/**
+ * Get the candidate days (as wall-clock midnights) of a period, before time
+ * of day and BYSETPOS are applied.
+ */
+function getPeriodDays(
+  rule: ParsedRRule,
+  dtstart: number,
+  period: number
+): { periodYear: number; days: number[] } {
+  const start = wallParts(dtstart)
+  const startDay = Date.UTC(start.year, start.month - 1, start.day)
+  const step = period * rule.interval
+
+  switch (rule.freq) {
+    case 'DAILY': {
+      const day = startDay + step * DAY_MS
+      const parts = wallParts(day)
+      const matches =
+        matchesMonth(rule, parts.month) &&
+        matchesMonthDay(rule, parts) &&
+        (rule.byDay.length === 0 || rule.byDay.some((spec) => spec.weekday === parts.weekday))
+      return { periodYear: parts.year, days: matches ? [day] : [] }
+    }
+    case 'WEEKLY': {
+      const weekStart = startDay - ((start.weekday - rule.wkst + 7) % 7) * DAY_MS + step * 7 * DAY_MS
+      const weekdays =
+        rule.byDay.length > 0 ? rule.byDay.map((spec) => spec.weekday) : [start.weekday]
+      const days: number[] = []
+      for (let offset = 0; offset < 7; offset++) {
+        const day = weekStart + offset * DAY_MS
+        const parts = wallParts(day)
+        if (weekdays.includes(parts.weekday) && matchesMonth(rule, parts.month)) {
+          days.push(day)
+        }
+      }
+      return { periodYear: wallParts(weekStart).year, days }
+    }
+    case 'MONTHLY': {
+      const monthIndex = start.month - 1 + step
+      const year = start.year + Math.floor(monthIndex / 12)
+      const month = (monthIndex % 12) + 1
+      const days = matchesMonth(rule, month) ? getMonthDays(rule, start, year, month) : []
+      return { periodYear: year, days }
+    }
+    case 'YEARLY': {
+      const year = start.year + step
+      if (rule.byDay.length > 0 && rule.byMonth.length === 0 && rule.byMonthDay.length === 0) {
+        return { periodYear: year, days: getYearWeekdays(rule.byDay, year) }
+      }
+      const months =
+        rule.byMonth.length > 0
+          ? rule.byMonth
+          : rule.byMonthDay.length > 0 || rule.byDay.length > 0
+            ? [1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12]
+            : [start.month]
+      const days = [...months]
+        .sort((a, b) => a - b)
+        .flatMap((month) => getMonthDays(rule, start, year, month))
+      return { periodYear: year, days }
+    }
+  }
+}
+
+function getMonthDays(
+  rule: ParsedRRule,
+  start: WallParts,
+  year: number,
+  month: number
+): number[] {
+  const daysInMonth = getDaysInMonth(year, month)
+  let days: number[]
+
+  if (rule.byMonthDay.length === 0 && rule.byDay.length === 0) {
+    days = start.day <= daysInMonth ? [start.day] : []
+  } else {
+    const byMonthDay = rule.byMonthDay
+      .map((day) => (day > 0 ? day : daysInMonth + 1 + day))
+      .filter((day) => day >= 1 && day <= daysInMonth)
+    const byDay = getMonthWeekdays(rule.byDay, year, month, daysInMonth)
+    days =
+      rule.byMonthDay.length > 0 && rule.byDay.length > 0
+        ? byMonthDay.filter((day) => byDay.includes(day))
+        : rule.byMonthDay.length > 0
+          ? byMonthDay
+          : byDay
+  }
+
+  return [...new Set(days)].sort((a, b) => a - b).map((day) => Date.UTC(year, month - 1, day))
+}
+
+function getMonthWeekdays(
+  specs: RRuleWeekday[],
+  year: number,
+  month: number,
+  daysInMonth: number
+): number[] {
+  const firstWeekday = new Date(Date.UTC(year, month - 1, 1)).getUTCDay()
+  return specs.flatMap((spec) => {
+    const first = 1 + ((spec.weekday - firstWeekday + 7) % 7)
+    const all: number[] = []
+    for (let day = first; day <= daysInMonth; day += 7) all.push(day)
+    return selectOrdinal(all, spec.ordinal)
+  })
+}
+
+function getYearWeekdays(specs: RRuleWeekday[], year: number): number[] {
+  const firstDay = Date.UTC(year, 0, 1)
+  const firstWeekday = new Date(firstDay).getUTCDay()
+  const daysInYear = (Date.UTC(year + 1, 0, 1) - firstDay) / DAY_MS
+  const days = specs.flatMap((spec) => {
+    const all: number[] = []
+    for (let day = (spec.weekday - firstWeekday + 7) % 7; day < daysInYear; day += 7) {
+      all.push(firstDay + day * DAY_MS)
+    }
+    return selectOrdinal(all, spec.ordinal)
+  })
+  return [...new Set(days)].sort((a, b) => a - b)
+}
+
+function selectOrdinal<T>(values: T[], ordinal: number | undefined): T[] {
+  if (!ordinal) return values
+  const value = ordinal > 0 ? values[ordinal - 1] : values[values.length + ordinal]
+  return value === undefined ? [] : [value]
+}
+
+function getTimesOfDay(rule: ParsedRRule, dtstart: number, day: number): number[] {
+  const start = wallParts(dtstart)
+  const hours = rule.byHour.length > 0 ? rule.byHour : [start.hour]
+  const minutes = rule.byMinute.length > 0 ? rule.byMinute : [start.minute]
+  const seconds = rule.bySecond.length > 0 ? rule.bySecond : [start.second]
+
+  const times: number[] = []
+  for (const hour of hours) {
+    for (const minute of minutes) {
+      for (const second of seconds) {
+        times.push(day + ((hour * 60 + minute) * 60 + second) * SECOND_MS)
+      }
+    }
+  }
+  return times
+}
+
+function applySetPos(bySetPos: number[], walls: number[]): number[] {
+  const sorted = [...new Set(walls)].sort((a, b) => a - b)
+  if (bySetPos.length === 0) return sorted
+  const selected = bySetPos.flatMap((position) => selectOrdinal(sorted, position))
+  return [...new Set(selected)].sort((a, b) => a - b)
+}
+
+function matchesMonth(rule: ParsedRRule, month: number): boolean {
+  return rule.byMonth.length === 0 || rule.byMonth.includes(month)
+}
+
+function matchesMonthDay(rule: ParsedRRule, parts: WallParts): boolean {
+  if (rule.byMonthDay.length === 0) return true
+  const daysInMonth = getDaysInMonth(parts.year, parts.month)
+  return rule.byMonthDay.some((day) => (day > 0 ? day : daysInMonth + 1 + day) === parts.day)
+}
+
+// ============================================================================
+// Descriptions
+// ============================================================================
+
+function describeFrequency(rule: ParsedRRule): string {
+  const units: Record<RRuleFrequency, [string, string]> = {
+    DAILY: ['Daily', 'days'],
+    WEEKLY: ['Weekly', 'weeks'],
+    MONTHLY: ['Monthly', 'months'],
+    YEARLY: ['Yearly', 'years'],
+  }
+  const [single, plural] = units[rule.freq]
+  return rule.interval === 1 ? single : `Every ${rule.interval} ${plural}`
+}
+
+function describeDays(rule: ParsedRRule, dtstart: WallParts | null): string | null {
+  if (rule.byDay.length > 0) {
+    const withOrdinals = rule.byDay.filter((spec) => spec.ordinal)
+    const plain = rule.byDay.filter((spec) => !spec.ordinal)
+
+    if (rule.bySetPos.length > 0 && withOrdinals.length === 0) {
+      const positions = joinList(rule.bySetPos.map(ordinalWord), 'and')
+      return `on the ${positions} ${describeWeekdaySet(plain.map((spec) => spec.weekday))}`
+    }
+
+    const phrases = [
+      ...withOrdinals.map(
+        (spec) => `the ${ordinalWord(spec.ordinal ?? 1)} ${WEEKDAY_NAMES[spec.weekday]}`
+      ),
+      ...(plain.length > 0 ? [describeWeekdayList(plain.map((spec) => spec.weekday))] : []),
+    ]
+    const days = `on ${joinList(phrases, 'and')}`
+    if (rule.byMonthDay.length > 0) {
+      return `${days} when it is ${describeMonthDays(rule.byMonthDay)}`
+    }
+    return days
+  }
+
+  if (rule.byMonthDay.length > 0) {
+    return `on ${describeMonthDays(rule.byMonthDay)}`
+  }
+
+  if (!dtstart) return null
+  switch (rule.freq) {
+    case 'WEEKLY':
+      return `on ${WEEKDAY_NAMES[dtstart.weekday]}`
+    case 'MONTHLY':
+      return `on day ${dtstart.day}`
+    case 'YEARLY':
+      return rule.byMonth.length > 0
+        ? `on day ${dtstart.day}`
+        : `on ${MONTH_NAMES[dtstart.month - 1]} ${dtstart.day}`
+    default:
+      return null
+  }
+}
+
+function describeWeekdayList(weekdays: number[]): string {
+  if (isWeekdaySet(weekdays, [1, 2, 3, 4, 5])) return 'weekdays'
+  if (isWeekdaySet(weekdays, [0, 6])) return 'weekends'
+  return joinList(sortWeekdays(weekdays).map((weekday) => WEEKDAY_NAMES[weekday] ?? ''), 'and')
+}
+
+function describeWeekdaySet(weekdays: number[]): string {
+  if (weekdays.length === 0) return 'day'
+  if (isWeekdaySet(weekdays, [1, 2, 3, 4, 5])) return 'weekday'
+  if (isWeekdaySet(weekdays, [0, 6])) return 'weekend day'
+  if (weekdays.length === 7) return 'day'
+  return joinList(sortWeekdays(weekdays).map((weekday) => WEEKDAY_NAMES[weekday] ?? ''), 'or')
+}
+
+function describeMonthDays(days: number[]): string {
+  const phrases = days.map((day) =>
+    day === -1 ? 'the last day' : day < 0 ? `the ${ordinalWord(day)} day` : `day ${day}`
+  )
+  return joinList(phrases, 'and')
+}
+
+function describeTimes(rule: ParsedRRule, dtstart: WallParts | null): string | null {
+  if (!dtstart && rule.byHour.length === 0) return null
+  const hours = rule.byHour.length > 0 ? rule.byHour : [dtstart?.hour ?? 0]
+  const minutes = rule.byMinute.length > 0 ? rule.byMinute : [dtstart?.minute ?? 0]
+  const seconds = rule.bySecond.length > 0 ? rule.bySecond : [dtstart?.second ?? 0]
+
+  const times: string[] = []
+  for (const hour of [...hours].sort((a, b) => a - b)) {
+    for (const minute of [...minutes].sort((a, b) => a - b)) {
+      for (const second of [...seconds].sort((a, b) => a - b)) {
+        const time = `${pad(hour)}:${pad(minute)}`
+        times.push(second > 0 ? `${time}:${pad(second)}` : time)
+      }
+    }
+  }
+  if (times.length > 6) {
+    return `at ${times.length} times a day`
+  }
+  return `at ${joinList(times, 'and')}`
+}
+
+function ordinalWord(value: number): string {
+  if (value === -1) return 'last'
+  if (value < 0) return `${ordinalWord(-value)} to last`
+  const words = ['first', 'second', 'third', 'fourth', 'fifth']
+  const word = words[value - 1]
+  if (word) return word
+  const suffix =
+    value % 100 >= 11 && value % 100 <= 13
+      ? 'th'
+      : value % 10 === 1
+        ? 'st'
+        : value % 10 === 2
+          ? 'nd'
+          : value % 10 === 3
+            ? 'rd'
+            : 'th'
+  return `${value}${suffix}`
+}
+
+function joinList(items: string[], conjunction: 'and' | 'or'): string {
+  if (items.length <= 1) return items[0] ?? ''
+  return `${items.slice(0, -1).join(', ')} ${conjunction} ${items[items.length - 1]}`
+}
+
+function isWeekdaySet(weekdays: number[], expected: number[]): boolean {
+  const set = new Set(weekdays)
+  return set.size === expected.length && expected.every((weekday) => set.has(weekday))
+}
+
+function sortWeekdays(weekdays: number[]): number[] {
+  // Monday first
+  return [...new Set(weekdays)].sort((a, b) => ((a + 6) % 7) - ((b + 6) % 7))
+}
+
+function pad(value: number): string {
+  return String(value).padStart(2, '0')
+}
+
+// ============================================================================
+// Parsing helpers
+// ============================================================================
+
+function parseIntegerInRange(name: string, value: string, min: number, max: number): number {
+  if (!/^[+-]?\d+$/.test(value)) {
+    throw new Error(`Invalid RRULE ${name} value: ${value}`)
+  }
+  const parsed = parseInt(value, 10)
+  if (parsed < min || parsed > max) {
+    throw new Error(`Invalid RRULE ${name} value: ${value}`)
+  }
+  return parsed
+}
+
+function parseIntegerList(
+  name: string,
+  value: string,
+  min: number,
+  max: number,
+  nonZero = false
+): number[] {
+  return value.split(',').map((item) => {
+    const parsed = parseIntegerInRange(name, item.trim(), min, max)
+    if (nonZero && parsed === 0) {
+      throw new Error(`Invalid RRULE ${name} value: ${item}`)
+    }
+    return parsed
+  })
+}
+
+function parseWeekday(value: string): RRuleWeekday {
+  const match = /^([+-]?\d{1,2})?(SU|MO|TU|WE|TH|FR|SA)$/.exec(value.trim())
+  if (!match) {
+    throw new Error(`Invalid RRULE BYDAY value: ${value}`)
+  }
+  const weekday = WEEKDAY_CODES.indexOf(match[2] as (typeof WEEKDAY_CODES)[number])
+  if (match[1] === undefined) {
+    return { weekday }
+  }
+  const ordinal = parseInt(match[1], 10)
+  if (ordinal === 0 || Math.abs(ordinal) > 53) {
+    throw new Error(`Invalid RRULE BYDAY value: ${value}`)
+  }
+  return { weekday, ordinal }
+}
+
+/**
+ * Parse an ISO 8601 date or date-time. Values without an offset are
+ * wall-clock times in the given time zone.
+ * @returns Epoch milliseconds, or null if the value is invalid
+ */
+function parseDateTime(value: string, timezone: string | null | undefined): number | null {
+  const local = /^(\d{4})-(\d{2})-(\d{2})(?:[T ](\d{2}):(\d{2})(?::(\d{2})(?:\.\d+)?)?)?$/.exec(
+    value.trim()
+  )
+  if (local) {
+    const [, year, month, day, hour, minute, second] = local
+    const wall = Date.UTC(
+      Number(year),
+      Number(month) - 1,
+      Number(day),
+      Number(hour ?? 0),
+      Number(minute ?? 0),
+      Number(second ?? 0)
+    )
+    if (Number.isNaN(wall) || wallParts(wall).day !== Number(day)) return null
+    return fromWallTime(wall, timezone || null)
+  }
+
+  const instant = new Date(value).getTime()
+  return Number.isNaN(instant) ? null : instant
+}
+
+function parseUntil(until: NonNullable<ParsedRRule['until']>, timezone: string | null): number {
+  const { value } = until
+  const wall = Date.UTC(
+    Number(value.slice(0, 4)),
+    Number(value.slice(4, 6)) - 1,
+    Number(value.slice(6, 8)),
+    until.dateOnly ? 23 : Number(value.slice(9, 11)),
+    until.dateOnly ? 59 : Number(value.slice(11, 13)),
+    until.dateOnly ? 59 : Number(value.slice(13, 15))
+  )
+  return until.utc ? wall : fromWallTime(wall, timezone)
+}
+
+// ============================================================================
+// Time zone helpers
+// ============================================================================
+
+interface WallParts {
+  year: number
+  /** 1-12 */
+  month: number
+  day: number
+  hour: number
+  minute: number
+  second: number
+  /** 0 = Sunday */
+  weekday: number
+}
+
+const formatters = new Map<string, Intl.DateTimeFormat>()
+
+function getFormatter(timezone: string): Intl.DateTimeFormat {
+  let formatter = formatters.get(timezone)
+  if (!formatter) {
+    formatter = new Intl.DateTimeFormat('en-US', {
+      timeZone: timezone,
+      hourCycle: 'h23',
+      year: 'numeric',
+      month: '2-digit',
+      day: '2-digit',
+      hour: '2-digit',
+      minute: '2-digit',
+      second: '2-digit',
+    })
+    formatters.set(timezone, formatter)
+  }
+  return formatter
+}
+
+/**
+ * Convert an instant to its wall-clock time in a time zone, expressed as
+ * milliseconds as if the wall-clock time were UTC.
+ */
+function toWallTime(instant: number, timezone: string | null): number {
+  if (!timezone || timezone === 'UTC') return instant
+
+  const values: Record<string, number> = {}
+  for (const part of getFormatter(timezone).formatToParts(new Date(instant))) {
+    if (part.type !== 'literal') values[part.type] = Number(part.value)
+  }
+  const milliseconds = ((instant % SECOND_MS) + SECOND_MS) % SECOND_MS
+  return (
+    Date.UTC(
+      values['year'] ?? 1970,
+      (values['month'] ?? 1) - 1,
+      values['day'] ?? 1,
+      (values['hour'] ?? 0) % 24,
+      values['minute'] ?? 0,
+      values['second'] ?? 0
+    ) + milliseconds
+  )
+}
+
+/**
+ * Convert a wall-clock time in a time zone to an instant. Times in a DST gap
+ * are moved forward by the gap; ambiguous times resolve to the earlier instant.
+ */
+function fromWallTime(wall: number, timezone: string | null): number {
+  if (!timezone || timezone === 'UTC') return wall
+
+  const offsetBefore = toWallTime(wall - DAY_MS, timezone) - (wall - DAY_MS)
+  const offsetAfter = toWallTime(wall + DAY_MS, timezone) - (wall + DAY_MS)
+
+  const early = wall - offsetBefore
+  if (toWallTime(early, timezone) === wall) return early
+  const late = wall - offsetAfter
+  if (toWallTime(late, timezone) === wall) return late
+  return early
+}
+
+function wallParts(wall: number): WallParts {
+  const date = new Date(wall)
+  return {
+    year: date.getUTCFullYear(),
+    month: date.getUTCMonth() + 1,
+    day: date.getUTCDate(),
+    hour: date.getUTCHours(),
+    minute: date.getUTCMinutes(),
+    second: date.getUTCSeconds(),
+    weekday: date.getUTCDay(),
+  }
+}
+
+function getDaysInMonth(year: number, month: number): number {
+  return new Date(Date.UTC(year, month, 0)).getUTCDate()
+}
+
+function truncateToSeconds(instant: number): number {
+  return Math.floor(instant / SECOND_MS) * SECOND_MS
+}
@@ -98,6 +98,22 @@ describe('getScheduleDescription', () => {
     })
   })
 
+  describe('rrule schedule type', () => {
+    it('describes the stored rule', () => {
+      const value = JSON.stringify({
+        rrule: 'FREQ=MONTHLY;BYDAY=2TU;COUNT=10',
+        dtstart: '2025-01-01T10:00:00',
+      })
+      expect(getScheduleDescription('rrule', value, 'Europe/Stockholm')).toBe(
+        'Monthly on the second Tuesday at 10:00, 10 times (Europe/Stockholm)'
+      )
+    })
+
+    it('falls back to the raw value when it cannot be parsed', () => {
+      expect(getScheduleDescription('rrule', 'not json', null)).toBe('RRULE: not json')
+    })
+  })
+
   describe('unknown schedule type', () => {
     it('returns unknown schedule message', () => {
       // @ts-expect-error - testing invalid input
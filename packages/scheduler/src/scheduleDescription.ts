@@ -1,8 +1,10 @@
+import { describeRRule, type RRuleScheduleValue } from './rrule.js'
+
 /**
  * Generate a human-readable description for a schedule.
  */
 export function getScheduleDescription(
-  scheduleType: 'at' | 'cron' | 'interval',
+  scheduleType: 'at' | 'cron' | 'interval' | 'rrule',
   scheduleValue: string,
   timezone: string | null
 ): string {
@@ -25,6 +27,13 @@ export function getScheduleDescription(
       if (ms < 86400000) return `Every ${Math.round(ms / 3600000)} hours`
       return `Every ${Math.round(ms / 86400000)} days`
     }
+    case 'rrule': {
+      try {
+        return describeRRule(JSON.parse(scheduleValue) as RRuleScheduleValue, timezone)
+      } catch {
+        return `RRULE: ${scheduleValue}`
+      }
+    }
     default:
       return 'Unknown schedule'
   }
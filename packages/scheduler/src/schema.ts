@@ -10,7 +10,7 @@ export const schedulerJobs = sqliteTable(
     userId: text('user_id'),
     scheduleType: text('schedule_type')
       .notNull()
-      .$type<'at' | 'cron' | 'interval'>(),
+      .$type<'at' | 'cron' | 'interval' | 'rrule'>(),
     scheduleValue: text('schedule_value').notNull(),
     payloadJson: text('payload_json'),
     timezone: text('timezone'),
@@ -25,12 +25,52 @@ export const schedulerJobs = sqliteTable(
     updatedAt: text('updated_at').notNull(),
     lastRunStatus: text('last_run_status').$type<'success' | 'error'>(),
     lastRunError: text('last_run_error'),
+    /** Retry policy for failed runs (JSON), null if failed runs are not retried */
+    retryPolicyJson: text('retry_policy_json'),
+    /** Attempt number of the latest or pending retry; 0 after a regular fire */
+    retryAttempt: integer('retry_attempt').notNull().default(0),
+    /** When the pending retry fires, null if no retry is pending */
+    retryAt: text('retry_at'),
+    /** When the user paused the job, null if not paused. Paused jobs are disabled */
+    pausedAt: text('paused_at'),
+    /** When the extension cancelled the job, null if not cancelled. Cancelled jobs are disabled */
+    cancelledAt: text('cancelled_at'),
   },
   (table) => ({
     nextRunIdx: index('idx_scheduler_jobs_next_run').on(table.nextRunAt),
     enabledIdx: index('idx_scheduler_jobs_enabled').on(table.enabled),
     userIdIdx: index('idx_scheduler_jobs_user_id').on(table.userId),
+    retryAtIdx: index('idx_scheduler_jobs_retry_at').on(table.retryAt),
   })
 )
 
-export const schedulerSchema = { schedulerJobs }
+export const schedulerJobRuns = sqliteTable(
+  'scheduler_job_runs',
+  {
+    id: text('id').primaryKey(),
+    /** Composite job ID (extensionId:jobId), see schedulerJobs.id */
+    jobId: text('job_id').notNull(),
+    userId: text('user_id'),
+    scheduledFor: text('scheduled_for').notNull(),
+    firedAt: text('fired_at').notNull(),
+    delayMs: integer('delay_ms').notNull(),
+    /** Time from fire until the result was reported; null while running */
+    durationMs: integer('duration_ms'),
+    /**
+     * running: fired, waiting for the result.
+     * skipped: not fired because of the misfire policy.
+     */
+    status: text('status').notNull().$type<'running' | 'success' | 'error' | 'skipped'>(),
+    error: text('error'),
+    /** Retry attempt number, 0 for regular fires */
+    attempt: integer('attempt').notNull().default(0),
+    /** Whether the run was started by the user (run now) */
+    manual: integer('manual', { mode: 'boolean' }).notNull().default(false),
+  },
+  (table) => ({
+    jobFiredIdx: index('idx_scheduler_job_runs_job_fired').on(table.jobId, table.firedAt),
+    userFiredIdx: index('idx_scheduler_job_runs_user_fired').on(table.userId, table.firedAt),
+  })
+)
+
+export const schedulerSchema = { schedulerJobs, schedulerJobRuns }
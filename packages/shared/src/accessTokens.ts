@@ -0,0 +1,25 @@
+/**
+ * Personal access tokens.
+ *
+ * Long-lived tokens for scripts and automations. Each token is limited to
+ * the scopes it was created with.
+ */
+
+/** Scopes a personal access token can be given */
+export const PERSONAL_ACCESS_TOKEN_SCOPES = [
+  'chat:read',
+  'chat:write',
+  'jobs:read',
+  'jobs:write',
+  'settings:read',
+  'settings:write',
+] as const
+
+export type PersonalAccessTokenScope = (typeof PERSONAL_ACCESS_TOKEN_SCOPES)[number]
+
+/**
+ * Whether a value is a known personal access token scope.
+ */
+export function isPersonalAccessTokenScope(value: unknown): value is PersonalAccessTokenScope {
+  return PERSONAL_ACCESS_TOKEN_SCOPES.includes(value as PersonalAccessTokenScope)
+}
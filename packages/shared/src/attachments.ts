@@ -0,0 +1,52 @@
+/**
+ * Message attachments.
+ *
+ * Helpers shared by providers to describe attachments as text, for models
+ * that cannot read the attached files themselves.
+ */
+
+import type { ChatAttachmentDTO } from './types.js'
+
+/**
+ * The attachment details needed to describe it
+ */
+export type AttachmentInfo = Pick<ChatAttachmentDTO, 'name' | 'mimeType' | 'size'>
+
+/**
+ * Whether the MIME type is an image that vision models can read.
+ */
+export function isImageMimeType(mimeType: string): boolean {
+  return /^image\/(png|jpe?g|gif|webp)$/i.test(mimeType.trim())
+}
+
+/**
+ * Format a size in bytes, e.g. `512 B`, `12.3 KB` or `4.5 MB`.
+ */
+export function formatAttachmentSize(bytes: number): string {
+  if (bytes < 1024) return `${bytes} B`
+  if (bytes < 1024 * 1024) return `${(bytes / 1024).toFixed(1)} KB`
+  return `${(bytes / (1024 * 1024)).toFixed(1)} MB`
+}
+
+/**
+ * Describe an attachment in one line, e.g.
+ * `[Attached file: report.pdf (application/pdf, 1.2 MB)]`.
+ */
+export function describeAttachment(attachment: AttachmentInfo): string {
+  const kind = isImageMimeType(attachment.mimeType) ? 'image' : 'file'
+  const size = formatAttachmentSize(attachment.size)
+  return `[Attached ${kind}: ${attachment.name} (${attachment.mimeType}, ${size})]`
+}
+
+/**
+ * Message text followed by a description of each attachment.
+ * Used as the content for providers that do not support attachments.
+ */
+export function withAttachmentDescriptions(
+  text: string,
+  attachments: AttachmentInfo[] | undefined
+): string {
+  if (!attachments || attachments.length === 0) return text
+  const descriptions = attachments.map(describeAttachment).join('\n')
+  return text.trim() ? `${text}\n\n${descriptions}` : descriptions
+}
@@ -0,0 +1,57 @@
+/**
+ * Audit log actions.
+ *
+ * Security-relevant actions are recorded in an append-only audit log
+ * that admins can read.
+ */
+
+/** Actions recorded in the audit log */
+export const AUDIT_ACTIONS = [
+  /** A user logged in with a passkey or single sign-on */
+  'auth.login',
+  /** A login attempt was rejected */
+  'auth.login_failed',
+  /** A single sign-on identity was linked to an account */
+  'auth.oidc_linked',
+  /** A single sign-on identity was unlinked from an account */
+  'auth.oidc_unlinked',
+  /** A new account was created */
+  'user.registered',
+  /** An admin changed the role of a user */
+  'user.role_changed',
+  /** An admin deleted a user */
+  'user.deleted',
+  /** An admin invited a user */
+  'invitation.created',
+  /** An invitation was used to create an account */
+  'invitation.used',
+  /** An admin deleted an invitation */
+  'invitation.deleted',
+  /** An admin changed the policy of a role */
+  'policy.role_updated',
+  /** An admin changed the policy of a single user */
+  'policy.user_updated',
+  /** An admin removed the policy of a single user */
+  'policy.user_removed',
+  /** An extension was installed, with the permissions it was granted */
+  'extension.installed',
+  /** An extension was updated, with the permissions it was granted */
+  'extension.updated',
+  /** An extension was uninstalled */
+  'extension.uninstalled',
+  /** A tool call that required confirmation was approved */
+  'tool.approved',
+  /** A tool call that required confirmation was denied */
+  'tool.denied',
+  /** The confirmation setting of a tool was changed */
+  'tool.confirmation_changed',
+] as const
+
+export type AuditAction = (typeof AUDIT_ACTIONS)[number]
+
+/**
+ * Whether a value is a known audit action.
+ */
+export function isAuditAction(value: unknown): value is AuditAction {
+  return AUDIT_ACTIONS.includes(value as AuditAction)
+}
@@ -0,0 +1,23 @@
+/**
+ * User capabilities.
+ *
+ * Capabilities are granted to roles and can be overridden for single users.
+ * Admins always have every capability.
+ */
+
+/** Capabilities that can be granted to roles and users */
+export const USER_CAPABILITIES = [
+  /** Install, update and uninstall extensions */
+  'extensions:install',
+  /** Approve tool calls that require confirmation, and turn off the confirmation */
+  'tools:approve',
+] as const
+
+export type UserCapability = (typeof USER_CAPABILITIES)[number]
+
+/**
+ * Whether a value is a known user capability.
+ */
+export function isUserCapability(value: unknown): value is UserCapability {
+  return USER_CAPABILITIES.includes(value as UserCapability)
+}
@@ -1,3 +1,8 @@
 export * from './types.js'
 export * from './notifications.js'
 export * from './timezone.js'
+export * from './quietHours.js'
+export * from './attachments.js'
+export * from './accessTokens.js'
+export * from './capabilities.js'
+export * from './audit.js'
@@ -42,5 +42,11 @@ export interface NotificationResult {
   /** Whether the notification was shown */
   shown: boolean
   /** Reason why the notification was not shown (if applicable) */
-  reason?: 'permission-denied' | 'window-focused' | 'disabled' | 'empty-content' | 'error'
+  reason?:
+    | 'permission-denied'
+    | 'window-focused'
+    | 'disabled'
+    | 'empty-content'
+    | 'quiet-hours'
+    | 'error'
 }
@@ -0,0 +1,78 @@
+import { describe, it, expect } from 'vitest'
+import { getQuietTimeEnd, isQuietTime, parseTimeOfDay } from './quietHours.js'
+import type { QuietHoursSettings } from './quietHours.js'
+
+const settings = (overrides: Partial<QuietHoursSettings> = {}): QuietHoursSettings => ({
+  timezone: 'Europe/Stockholm',
+  quietHoursEnabled: true,
+  quietHoursStart: '22:00',
+  quietHoursEnd: '07:00',
+  doNotDisturbUntil: null,
+  ...overrides,
+})
+
+const at = (iso: string) => new Date(iso)
+
+describe('parseTimeOfDay', () => {
+  it('parses HH:MM and rejects invalid times', () => {
+    expect(parseTimeOfDay('07:30')).toBe(450)
+    expect(parseTimeOfDay('7:05')).toBe(425)
+    expect(parseTimeOfDay('24:00')).toBeNull()
+    expect(parseTimeOfDay('12:60')).toBeNull()
+    expect(parseTimeOfDay('noon')).toBeNull()
+  })
+})
+
+describe('isQuietTime', () => {
+  it('handles quiet hours that span midnight in the user timezone', () => {
+    // Stockholm is UTC+1 in January
+    expect(isQuietTime(settings(), at('2025-01-15T20:59:00Z'))).toBe(false)
+    expect(isQuietTime(settings(), at('2025-01-15T21:00:00Z'))).toBe(true)
+    expect(isQuietTime(settings(), at('2025-01-16T05:59:00Z'))).toBe(true)
+    expect(isQuietTime(settings(), at('2025-01-16T06:00:00Z'))).toBe(false)
+  })
+
+  it('handles quiet hours within a day', () => {
+    const daytime = settings({ timezone: 'UTC', quietHoursStart: '12:00', quietHoursEnd: '13:00' })
+    expect(isQuietTime(daytime, at('2025-01-15T12:30:00Z'))).toBe(true)
+    expect(isQuietTime(daytime, at('2025-01-15T13:00:00Z'))).toBe(false)
+  })
+
+  it('is off when disabled or when start equals end', () => {
+    expect(isQuietTime(settings({ quietHoursEnabled: false }), at('2025-01-15T23:00:00Z'))).toBe(
+      false
+    )
+    const sameTimes = settings({ quietHoursStart: '22:00', quietHoursEnd: '22:00' })
+    expect(isQuietTime(sameTimes, at('2025-01-15T23:00:00Z'))).toBe(false)
+  })
+
+  it('respects do-not-disturb outside the quiet hours', () => {
+    const dnd = settings({ quietHoursEnabled: false, doNotDisturbUntil: '2025-01-15T14:00:00Z' })
+    expect(isQuietTime(dnd, at('2025-01-15T13:00:00Z'))).toBe(true)
+    expect(isQuietTime(dnd, at('2025-01-15T14:00:00Z'))).toBe(false)
+  })
+})
+
+describe('getQuietTimeEnd', () => {
+  it('returns null outside quiet time', () => {
+    expect(getQuietTimeEnd(settings(), at('2025-01-15T12:00:00Z'))).toBeNull()
+  })
+
+  it('returns the end of the quiet hours', () => {
+    expect(getQuietTimeEnd(settings(), at('2025-01-15T23:12:34Z'))).toEqual(
+      at('2025-01-16T06:00:00Z')
+    )
+  })
+
+  it('follows do-not-disturb into the quiet hours', () => {
+    const dnd = settings({ doNotDisturbUntil: '2025-01-15T21:30:00Z' })
+    expect(getQuietTimeEnd(dnd, at('2025-01-15T19:00:00Z'))).toEqual(at('2025-01-16T06:00:00Z'))
+  })
+
+  it('ends at the local time across a DST change', () => {
+    // Clocks move forward at 02:00 local time on 2025-03-30 in Stockholm
+    expect(getQuietTimeEnd(settings(), at('2025-03-29T22:00:00Z'))).toEqual(
+      at('2025-03-30T05:00:00Z')
+    )
+  })
+})
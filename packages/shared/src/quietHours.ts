@@ -0,0 +1,127 @@
+/**
+ * Quiet hours and do-not-disturb.
+ *
+ * During quiet time Stina holds back proactive messages (extension and
+ * scheduler instructions) and does not show notifications.
+ */
+
+import type { AppSettingsDTO } from './types.js'
+import { getUtcOffsetMinutesForTimeZone } from './timezone.js'
+
+/**
+ * The settings that decide whether it is quiet time
+ */
+export type QuietHoursSettings = Pick<
+  AppSettingsDTO,
+  'timezone' | 'quietHoursEnabled' | 'quietHoursStart' | 'quietHoursEnd' | 'doNotDisturbUntil'
+>
+
+const MINUTES_PER_DAY = 24 * 60
+
+/**
+ * Parse a time of day in `HH:MM` format.
+ * @returns Minutes after midnight, or null if the value is invalid
+ */
+export function parseTimeOfDay(value: string | undefined | null): number | null {
+  const match = /^(\d{1,2}):(\d{2})$/.exec(value?.trim() ?? '')
+  if (!match) return null
+  const hours = Number(match[1])
+  const minutes = Number(match[2])
+  if (hours > 23 || minutes > 59) return null
+  return hours * 60 + minutes
+}
+
+/**
+ * Whether do-not-disturb is switched on at the given time.
+ */
+export function isDoNotDisturbActive(
+  settings: Pick<QuietHoursSettings, 'doNotDisturbUntil'>,
+  now = new Date()
+): boolean {
+  const until = getDoNotDisturbEnd(settings)
+  return until !== null && until.getTime() > now.getTime()
+}
+
+/**
+ * Whether the user should not be disturbed at the given time, either because
+ * of do-not-disturb or because it is within the daily quiet hours.
+ * Quiet hours may span midnight (e.g. 22:00-07:00). Equal start and end
+ * times mean no quiet hours.
+ */
+export function isQuietTime(settings: QuietHoursSettings, now = new Date()): boolean {
+  return isDoNotDisturbActive(settings, now) || getQuietHoursEnd(settings, now) !== null
+}
+
+/**
+ * When the current quiet time ends.
+ * Chained periods are followed, so do-not-disturb that lasts into the quiet
+ * hours ends when the quiet hours do.
+ * @returns The end time, or null if it is not quiet time
+ */
+export function getQuietTimeEnd(settings: QuietHoursSettings, now = new Date()): Date | null {
+  let end = now
+  // Do-not-disturb and quiet hours can alternate at most a couple of times
+  for (let i = 0; i < 4; i++) {
+    const next = isDoNotDisturbActive(settings, end)
+      ? getDoNotDisturbEnd(settings)
+      : getQuietHoursEnd(settings, end)
+    if (!next) break
+    end = next
+  }
+  return end === now ? null : end
+}
+
+function getDoNotDisturbEnd(settings: Pick<QuietHoursSettings, 'doNotDisturbUntil'>): Date | null {
+  if (!settings.doNotDisturbUntil) return null
+  const until = new Date(settings.doNotDisturbUntil)
+  return Number.isNaN(until.getTime()) ? null : until
+}
+
+/**
+ * When the daily quiet hours that the given time falls within end.
+ * @returns The end time, or null if the time is outside the quiet hours
+ */
+function getQuietHoursEnd(settings: QuietHoursSettings, now: Date): Date | null {
+  if (!settings.quietHoursEnabled) return null
+  const start = parseTimeOfDay(settings.quietHoursStart)
+  const end = parseTimeOfDay(settings.quietHoursEnd)
+  if (start === null || end === null || start === end) return null
+
+  const timeZone = resolveTimeZone(settings.timezone)
+  const local = getLocalTime(now, timeZone)
+  const minute = local.minutes
+  const inWindow = start < end ? minute >= start && minute < end : minute >= start || minute < end
+  if (!inWindow) return null
+
+  const minutesLeft = (end - minute + MINUTES_PER_DAY) % MINUTES_PER_DAY
+  const endTime = new Date(
+    now.getTime() - local.seconds * 1000 - now.getMilliseconds() + minutesLeft * 60_000
+  )
+  // Wall-clock minutes differ from elapsed minutes across a DST change
+  const offsetChange =
+    getUtcOffsetMinutesForTimeZone(timeZone, endTime) -
+    getUtcOffsetMinutesForTimeZone(timeZone, now)
+  return new Date(endTime.getTime() + offsetChange * 60_000)
+}
+
+function resolveTimeZone(timeZone: string | undefined): string {
+  if (!timeZone) return 'UTC'
+  try {
+    new Intl.DateTimeFormat('en-US', { timeZone })
+    return timeZone
+  } catch {
+    return 'UTC'
+  }
+}
+
+function getLocalTime(date: Date, timeZone: string): { minutes: number; seconds: number } {
+  const parts = new Intl.DateTimeFormat('en-GB', {
+    timeZone,
+    hour: '2-digit',
+    minute: '2-digit',
+    second: '2-digit',
+    hourCycle: 'h23',
+  }).formatToParts(date)
+  const get = (type: string) => Number(parts.find((p) => p.type === type)?.value ?? 0)
+  return { minutes: get('hour') * 60 + get('minute'), seconds: get('second') }
+}
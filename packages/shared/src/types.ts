@@ -1,4 +1,7 @@
 import type { NotificationSoundId } from './notifications.js'
+import type { PersonalAccessTokenScope } from './accessTokens.js'
+import type { UserCapability } from './capabilities.js'
+import type { AuditAction } from './audit.js'
 
 /**
  * Greeting response from the hello endpoint/function
@@ -77,9 +80,32 @@ export interface ChatMessageDTO {
     /** User's response when denied with text */
     confirmationDenialReason?: string
   }>
+  /** Files attached to a user message */
+  attachments?: ChatAttachmentDTO[]
   createdAt: string
 }
 
+/**
+ * File attached to a chat message
+ */
+export interface ChatAttachmentDTO {
+  /** Content hash (SHA-256, hex) identifying the stored file */
+  id: string
+  name: string
+  mimeType: string
+  /** Size in bytes */
+  size: number
+}
+
+/**
+ * File sent together with a chat message, before it has been stored
+ */
+export interface ChatAttachmentUploadDTO {
+  name: string
+  mimeType: string
+  data: Uint8Array
+}
+
 /**
  * Chat interaction DTO
  */
@@ -94,6 +120,89 @@ export interface ChatInteractionDTO {
   errorMessage?: string
   /** When this interaction was read (undefined = unread) */
   readAt?: string
+  /** Model that generated the response */
+  modelId?: string
+  /** Token usage reported by the provider */
+  usage?: TokenUsageDTO
+  /** Interaction this one follows in its branch (null = first interaction) */
+  parentId?: string | null
+  /** Alternative branches at this point, including this interaction, oldest first */
+  siblingIds?: string[]
+}
+
+/**
+ * Token usage reported by a provider
+ */
+export interface TokenUsageDTO {
+  inputTokens: number
+  outputTokens: number
+}
+
+/**
+ * Summed token usage for a group of interactions
+ */
+export interface TokenUsageTotalsDTO extends TokenUsageDTO {
+  /** Number of interactions with reported usage */
+  interactions: number
+}
+
+/**
+ * Query for a token usage report
+ */
+export interface TokenUsageQueryDTO {
+  /** Inclusive start (ISO 8601) */
+  from?: string
+  /** Exclusive end (ISO 8601) */
+  to?: string
+  /** IANA timezone used to group usage per day. Defaults to the user's timezone setting */
+  timezone?: string
+}
+
+/**
+ * Token usage report with per-user, per-model and per-day aggregates
+ */
+export interface TokenUsageReportDTO {
+  /** Timezone used for the per-day grouping */
+  timezone: string
+  totals: TokenUsageTotalsDTO
+  byUser: Array<TokenUsageTotalsDTO & { userId: string }>
+  byModel: Array<TokenUsageTotalsDTO & { providerId: string | null; modelId: string | null }>
+  /** Per-day usage, date formatted as YYYY-MM-DD */
+  byDay: Array<TokenUsageTotalsDTO & { date: string }>
+}
+
+/**
+ * File format for conversation exports.
+ * Only the JSON format can be imported again.
+ */
+export type ConversationExportFormat = 'markdown' | 'json' | 'html'
+
+/**
+ * Full-text search across the user's conversations
+ */
+export interface ChatSearchQueryDTO {
+  /** Words to search for. Every word must match, as a prefix */
+  query: string
+  /** Maximum number of hits (default 20, max 100) */
+  limit?: number
+  offset?: number
+}
+
+/**
+ * Message matching a full-text search, best match first
+ */
+export interface ChatSearchHitDTO {
+  conversationId: string
+  conversationTitle?: string
+  /** Whether the conversation is active (false when archived) */
+  conversationActive: boolean
+  interactionId: string
+  /** Who wrote the matching message */
+  role: 'user' | 'stina'
+  /** HTML-escaped excerpt of the message with matches wrapped in <mark> tags */
+  snippet: string
+  /** ISO timestamp of the interaction */
+  createdAt: string
 }
 
 /**
@@ -116,6 +225,133 @@ export interface ChatConversationDTO {
   interactions: ChatInteractionDTO[]
   active: boolean
   createdAt: string
+  /** Settings the conversation uses instead of the user's */
+  overrides?: ChatConversationOverridesDTO
+}
+
+/**
+ * Conversation-level overrides of the user's model and personality settings.
+ * Left out values use the user's settings.
+ */
+export interface ChatConversationOverridesDTO {
+  /** Model config used instead of the user's default model */
+  modelConfigId?: string
+  /** Sampling temperature (0-2) */
+  temperature?: number
+  /** Personality preset ID used instead of the user's */
+  personalityPreset?: string
+  /** Extra text added to the end of the system prompt */
+  additionalPrompt?: string
+}
+
+/**
+ * Personal access token DTO (the token itself is only returned when created)
+ */
+export interface PersonalAccessTokenDTO {
+  id: string
+  /** Name given by the user, e.g. "Home automation" */
+  name: string
+  /** Start of the token, to recognize it by */
+  tokenPrefix: string
+  scopes: PersonalAccessTokenScope[]
+  createdAt: string
+  /** When the token stops working, or null if it never expires */
+  expiresAt: string | null
+  lastUsedAt: string | null
+}
+
+/**
+ * A newly created personal access token
+ */
+export interface CreatedPersonalAccessTokenDTO {
+  /** The token to send as `Authorization: Bearer <token>`. It cannot be shown again */
+  token: string
+  personalAccessToken: PersonalAccessTokenDTO
+}
+
+/**
+ * Session DTO. A session is a device or browser that is signed in with a refresh token.
+ */
+export interface SessionDTO {
+  id: string
+  /** User agent of the device, if known */
+  userAgent: string | null
+  /** IP address the session was created from, if known */
+  ip: string | null
+  createdAt: string
+  /** Last time the session refreshed its access token */
+  lastUsedAt: string | null
+  expiresAt: string
+  /** Whether this is the session making the request */
+  current: boolean
+}
+
+/**
+ * Capability policy of a role or a single user.
+ * Null values are not set: role policies then use the built-in defaults,
+ * and user policies use their role's policy.
+ */
+export interface CapabilityPolicyDTO {
+  /** Capabilities granted */
+  capabilities: UserCapability[] | null
+  /** Model configs that may be used (null for all) */
+  allowedModelConfigIds: string[] | null
+}
+
+/**
+ * What a user may do after combining the role and user policies
+ */
+export interface EffectivePolicyDTO {
+  capabilities: UserCapability[]
+  /** Model configs the user may use, or null for all */
+  allowedModelConfigIds: string[] | null
+}
+
+/**
+ * An entry in the audit log
+ */
+export interface AuditEventDTO {
+  id: string
+  createdAt: string
+  /** User who performed the action, or null if not known (e.g. a failed login) */
+  actorUserId: string | null
+  /** Username of the actor at the time of the action, if known */
+  actorUsername: string | null
+  action: AuditAction
+  /** Kind of thing the action was performed on, e.g. "user" or "extension" */
+  targetType: string | null
+  targetId: string | null
+  /** Extra information about the action */
+  details: Record<string, unknown> | null
+  /** IP address the action was performed from, if known */
+  ip: string | null
+}
+
+/**
+ * Filters for listing audit log entries
+ */
+export interface AuditEventQueryDTO {
+  action?: AuditAction
+  /** Only actions performed by this user */
+  userId?: string
+  targetType?: string
+  targetId?: string
+  /** ISO 8601 time the entries must be at or after */
+  from?: string
+  /** ISO 8601 time the entries must be before */
+  to?: string
+  /** Maximum number of entries (default 50, max 500) */
+  limit?: number
+  offset?: number
+}
+
+/**
+ * A page of audit log entries, newest first
+ */
+export interface AuditEventListDTO {
+  events: AuditEventDTO[]
+  /** Number of entries matching the filters */
+  total: number
 }
 
 /**
@@ -136,6 +372,8 @@ export interface ModelConfigDTO {
   modelId: string
   /** Provider-specific settings overrides */
   settingsOverride?: Record<string, unknown>
+  /** Context window size in tokens, used to decide when to summarize older messages */
+  contextLength?: number
   /** Creation timestamp */
   createdAt: string
   /** Last update timestamp */
@@ -170,6 +408,19 @@ export interface AppSettingsDTO {
    * removed automatically. Use `0` to keep them indefinitely.
    */
   scheduledJobsRetentionDays: number
+  /**
+   * Model config used to generate conversation titles (null to clear).
+   * When not set, the default model is used.
+   */
+  titleModelConfigId?: string | null
+  /** Hold back proactive messages and notifications during the daily quiet hours */
+  quietHoursEnabled: boolean
+  /** Start of the quiet hours as `HH:MM` in the user's timezone */
+  quietHoursStart: string
+  /** End of the quiet hours as `HH:MM`. May be earlier than the start to span midnight */
+  quietHoursEnd: string
+  /** Do not disturb until this time (ISO string, null to clear) */
+  doNotDisturbUntil?: string | null
 }
 
 /**
@@ -200,7 +451,7 @@ export interface ScheduledJobSummaryDTO {
   /** User who owns the job */
   userId: string
   /** Type of schedule */
-  scheduleType: 'at' | 'cron' | 'interval'
+  scheduleType: 'at' | 'cron' | 'interval' | 'rrule'
   /** Human-readable schedule description */
   scheduleDescription: string
   /** Next scheduled run time (ISO string) */
@@ -209,6 +460,8 @@ export interface ScheduledJobSummaryDTO {
   lastRunAt: string | null
   /** Whether the job is enabled */
   enabled: boolean
+  /** Whether the user has paused the job. Paused jobs are not enabled */
+  paused: boolean
   /** Creation timestamp (ISO string) */
   createdAt: string
 }
@@ -217,7 +470,7 @@ export interface ScheduledJobSummaryDTO {
  * Scheduled job detail DTO (with full information)
  */
 export interface ScheduledJobDetailDTO extends ScheduledJobSummaryDTO {
-  /** Raw schedule value (date/cron/ms) */
+  /** Raw schedule value (date/cron/ms, JSON for rrule) */
   scheduleValue: string
   /** Timezone for cron jobs */
   timezone: string | null
@@ -227,4 +480,63 @@ export interface ScheduledJobDetailDTO extends ScheduledJobSummaryDTO {
   payload: Record<string, unknown> | null
   /** Resolved extension name */
   extensionName: string | null
+  /** When the pending retry of a failed run fires (ISO string), null if none */
+  retryAt: string | null
+  /** A page of the job's run history, newest first */
+  history: ScheduledJobHistoryDTO
+}
+
+/**
+ * A single run of a scheduled job
+ */
+export interface ScheduledJobRunDTO {
+  id: string
+  /** When the run was scheduled (ISO string) */
+  scheduledFor: string
+  /** When the job actually fired (ISO string) */
+  firedAt: string
+  /** Milliseconds between scheduledFor and firedAt */
+  delayMs: number
+  /** Milliseconds until the result was reported, null while running */
+  durationMs: number | null
+  /** Run outcome. Skipped runs were not fired because of the misfire policy */
+  status: 'running' | 'success' | 'error' | 'skipped'
+  /** Error message for failed runs */
+  error: string | null
+  /** Retry attempt number, 0 for regular runs */
+  attempt: number
+  /** Whether the user started the run (run now) */
+  manual: boolean
+}
+
+/**
+ * A page of a scheduled job's run history
+ */
+export interface ScheduledJobHistoryDTO {
+  runs: ScheduledJobRunDTO[]
+  /** Total number of recorded runs */
+  total: number
+  limit: number
+  offset: number
+}
+
+/**
+ * Pagination options for a scheduled job's run history
+ */
+export interface ScheduledJobHistoryOptions {
+  /** Number of runs to return. Defaults to 20 */
+  runsLimit?: number
+  /** Number of runs to skip */
+  runsOffset?: number
+}
+
+/**
+ * Options for resuming a paused scheduled job
+ */
+export interface ScheduledJobResumeOptions {
+  /**
+   * What to do with runs missed while paused: run the job once right away
+   * (`run_once`, default) or continue with the next occurrence (`skip`)
+   */
+  misfire?: 'run_once' | 'skip'
 }
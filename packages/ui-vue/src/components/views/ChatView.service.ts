@@ -7,9 +7,14 @@ import type {
   QueueState,
   QueuedMessageRole,
   InformationMessage,
+  MessageAttachment,
 } from '@stina/chat'
 import { dtoToInteraction, dtoToConversation } from '@stina/chat/mappers'
-import type { ChatConversationDTO, ChatInteractionDTO } from '@stina/shared'
+import type {
+  ChatAttachmentUploadDTO,
+  ChatConversationDTO,
+  ChatInteractionDTO,
+} from '@stina/shared'
 import { useApi } from '../../composables/useApi.js'
 import { useWindowFocus } from '../../composables/useWindowFocus.js'
 
@@ -41,6 +46,24 @@ export interface UseChatOptions {
   onBackgroundInstruction?: (interaction: Interaction) => void
 }
 
+/**
+ * Build a multipart chat stream request body from the message fields and files.
+ */
+function createChatStreamFormData(
+  fields: Record<string, string | undefined>,
+  attachments: ChatAttachmentUploadDTO[]
+): FormData {
+  const form = new FormData()
+  for (const [name, value] of Object.entries(fields)) {
+    if (value !== undefined) form.append(name, value)
+  }
+  for (const attachment of attachments) {
+    const blob = new Blob([attachment.data], { type: attachment.mimeType })
+    form.append('files', blob, attachment.name)
+  }
+  return form
+}
+
 function createClientId(): string {
   if (typeof crypto !== 'undefined' && typeof crypto.randomUUID === 'function') {
     return crypto.randomUUID()
@@ -62,12 +85,14 @@ type SSEEvent = (
   | { type: 'stream-error'; error: string }
   | { type: 'interaction-saved'; interaction: ChatInteractionDTO }
   | { type: 'conversation-created'; conversation: ChatConversationDTO }
+  | { type: 'conversation-title-updated'; conversationId: string; title: string }
   | {
       type: 'interaction-started'
       interactionId: string
       conversationId: string
       role: QueuedMessageRole
       text: string
+      attachments?: MessageAttachment[]
       systemPrompt?: string
       informationMessages?: InformationMessage[]
     }
@@ -126,6 +151,14 @@ export function useChat(options: UseChatOptions = {}) {
   const queuedItems = computed(() => queueState.value.queued)
   const isQueueProcessing = computed(() => queueState.value.isProcessing)
 
+  /**
+   * Whether messages can be edited, regenerated or switched to another branch.
+   * Waits until nothing is streaming or queued, so the branch does not change underneath.
+   */
+  const canBranch = computed(
+    () => !isStreaming.value && !isQueueProcessing.value && queuedItems.value.length === 0
+  )
+
   /**
    * IDs of interactions that have not been read yet
    */
@@ -392,6 +425,12 @@ export function useChat(options: UseChatOptions = {}) {
         subscribeToConversationEvents(event.conversation.id)
         break
 
+      case 'conversation-title-updated':
+        if (currentConversation.value?.id === event.conversationId) {
+          currentConversation.value = { ...currentConversation.value, title: event.title }
+        }
+        break
+
       case 'interaction-started': {
         // Check if this is the same interaction we're already showing (avoid duplicates)
         if (currentInteraction.value && currentInteraction.value.id === event.interactionId) {
@@ -438,6 +477,7 @@ export function useChat(options: UseChatOptions = {}) {
         messages.push({
           type: messageType,
           text: event.text,
+          attachments: event.attachments,
           metadata: { createdAt: new Date().toISOString() },
         } as Message)
 
@@ -463,6 +503,14 @@ export function useChat(options: UseChatOptions = {}) {
             break
           }
 
+          // Another client edited or regenerated a message: reload the new branch
+          const headId = loadedInteractions.value[0]?.id ?? null
+          if (interaction.parentId !== undefined && interaction.parentId !== headId) {
+            currentInteraction.value = null
+            void loadInitialInteractions()
+            break
+          }
+
           // If the chat is currently in focus, treat the new interaction as
           // already read so it never flashes the unread highlight, and persist
           // that state on the server so other clients agree.
@@ -506,7 +554,16 @@ export function useChat(options: UseChatOptions = {}) {
    */
   async function sendMessage(
     text: string,
-    options: { role?: QueuedMessageRole; context?: 'conversation-start' | 'settings-update' } = {}
+    options: {
+      role?: QueuedMessageRole
+      context?: 'conversation-start' | 'settings-update'
+      /** Files to attach to a user message */
+      attachments?: ChatAttachmentUploadDTO[]
+      /** Answer this interaction's message again, as a new branch */
+      regenerateInteractionId?: string
+      /** Replace this interaction's user message with the text, as a new branch */
+      editInteractionId?: string
+    } = {}
   ): Promise<void> {
     error.value = null
     const queueId = createClientId()
@@ -525,6 +582,9 @@ export function useChat(options: UseChatOptions = {}) {
             role: options.role ?? 'user',
             context: options.context,
             sessionId: sessionId.value,
+            attachments: options.attachments,
+            regenerateInteractionId: options.regenerateInteractionId,
+            editInteractionId: options.editInteractionId,
             // Adapter to convert ChatStreamEvent to SSEEvent
             onEvent: (event) => handleSSEEvent(event as SSEEvent),
           }
@@ -548,17 +608,28 @@ export function useChat(options: UseChatOptions = {}) {
     requestControllers.set(queueId, controller)
 
     try {
+      const fields = {
+        conversationId: currentConversation.value?.id,
+        message: text,
+        queueId,
+        role: options.role ?? 'user',
+        context: options.context,
+        sessionId: sessionId.value,
+        regenerateInteractionId: options.regenerateInteractionId,
+        editInteractionId: options.editInteractionId,
+      }
+      const attachments = options.attachments ?? []
       const response = await fetch('/api/chat/stream', {
         method: 'POST',
-        headers: { 'Content-Type': 'application/json', ...getAuthHeaders() },
-        body: JSON.stringify({
-          conversationId: currentConversation.value?.id,
-          message: text,
-          queueId,
-          role: options.role ?? 'user',
-          context: options.context,
-          sessionId: sessionId.value,
-        }),
+        // Multipart requests get their Content-Type (with boundary) from the form data
+        headers:
+          attachments.length > 0
+            ? getAuthHeaders()
+            : { 'Content-Type': 'application/json', ...getAuthHeaders() },
+        body:
+          attachments.length > 0
+            ? createChatStreamFormData(fields, attachments)
+            : JSON.stringify(fields),
         signal: controller.signal,
       })
 
@@ -616,6 +687,63 @@ export function useChat(options: UseChatOptions = {}) {
     }
   }
 
+  /**
+   * Drop an interaction and everything after it from the shown branch,
+   * so its replacement streams in at the right place.
+   */
+  function truncateBranchAt(interactionId: string): void {
+    const index = loadedInteractions.value.findIndex((i) => i.id === interactionId)
+    if (index === -1) return
+    loadedInteractions.value = loadedInteractions.value.slice(index + 1)
+    totalInteractionsCount.value -= index + 1
+  }
+
+  /**
+   * Let Stina answer an interaction's message again. The old answer is kept as a branch.
+   */
+  async function regenerate(interactionId: string): Promise<void> {
+    truncateBranchAt(interactionId)
+    await sendMessage('', { regenerateInteractionId: interactionId })
+  }
+
+  /**
+   * Send an edited version of an interaction's user message as a new branch.
+   */
+  async function editAndResend(interactionId: string, text: string): Promise<void> {
+    truncateBranchAt(interactionId)
+    await sendMessage(text, { editInteractionId: interactionId })
+  }
+
+  /**
+   * Show another branch of the current conversation.
+   * @param interactionId - A sibling of a shown interaction.
+   */
+  async function switchBranch(interactionId: string): Promise<void> {
+    if (!currentConversation.value) return
+    const conversationId = currentConversation.value.id
+
+    try {
+      error.value = null
+      // Use IPC-based branch switch if available (Electron)
+      if (api.chat.switchBranch) {
+        await api.chat.switchBranch(conversationId, interactionId, sessionId.value)
+      } else {
+        // Fall back to HTTP (web)
+        const response = await fetch('/api/chat/branch/switch', {
+          method: 'POST',
+          headers: { 'Content-Type': 'application/json', ...getAuthHeaders() },
+          body: JSON.stringify({ conversationId, interactionId, sessionId: sessionId.value }),
+        })
+        if (!response.ok) {
+          throw new Error(`HTTP error: ${response.status}`)
+        }
+      }
+      await loadInitialInteractions()
+    } catch (err) {
+      error.value = err as Error
+    }
+  }
+
   /**
    * Start a new conversation
    */
@@ -1016,6 +1144,17 @@ export function useChat(options: UseChatOptions = {}) {
       return
     }
 
+    // Another client switched to a different branch of this conversation
+    if (event.type === 'conversation-updated') {
+      if (event.sessionId && event.sessionId === sessionId.value) {
+        return
+      }
+      if (currentConversation.value && event.conversationId === currentConversation.value.id) {
+        await loadInitialInteractions()
+      }
+      return
+    }
+
     if (event.type === 'instruction-received') {
       // Only refresh if we have a conversation and the event is for it
       // or if no conversationId in event (applies to any conversation)
@@ -1136,6 +1275,7 @@ export function useChat(options: UseChatOptions = {}) {
     queueState,
     queuedItems,
     isQueueProcessing,
+    canBranch,
     pendingConfirmation,
     confirmationError,
     error,
@@ -1155,6 +1295,9 @@ export function useChat(options: UseChatOptions = {}) {
     archiveConversation,
     removeQueued,
     abortStreaming,
+    regenerate,
+    editAndResend,
+    switchBranch,
     respondToConfirmation,
     markAllAsRead,
     clearReadingState,
@@ -9,6 +9,7 @@ export type {
   PanelViewInfo,
   ExtensionToolInfo,
   ToolConfirmationOverride,
+  ExtensionFileRoot,
   ActionInfo,
   ExtensionEvent,
   ChatEvent,
@@ -3,7 +3,9 @@ import type {
   NotificationContext,
   NotificationResult,
   NotificationSoundId,
+  QuietHoursSettings,
 } from '@stina/shared'
+import { isQuietTime } from '@stina/shared'
 import { stripMarkdown } from '../utils/stripMarkdown.js'
 
 /**
@@ -54,18 +56,32 @@ export interface NotificationAdapter {
 export class NotificationService {
   private adapter: NotificationAdapter
   private getCurrentView: () => string
+  private quietHours: QuietHoursSettings | null = null
 
   constructor(adapter: NotificationAdapter, getCurrentView: () => string) {
     this.adapter = adapter
     this.getCurrentView = getCurrentView
   }
 
+  /**
+   * Set the user's quiet hours and do-not-disturb settings.
+   * No notifications are shown during quiet time.
+   */
+  setQuietHours(settings: QuietHoursSettings | null): void {
+    this.quietHours = settings
+  }
+
   /**
    * Show a notification if appropriate based on current context.
-   * Will only show if the window is not focused or user is not in chat view.
+   * Will only show if the window is not focused or user is not in chat view,
+   * and never during the user's quiet hours.
    * Sound is handled by the OS notification system.
    */
   async maybeShowNotification(options: NotificationOptions): Promise<NotificationResult> {
+    if (this.quietHours && isQuietTime(this.quietHours)) {
+      return { shown: false, reason: 'quiet-hours' }
+    }
+
     const context: NotificationContext = {
       isWindowFocused: this.adapter.checkWindowFocus(),
       currentView: this.getCurrentView() as 'chat' | 'tools' | 'settings',
@@ -90,7 +106,7 @@ export class NotificationService {
   }
 
   /**
-   * Show a test notification (always shows, ignores context).
+   * Show a test notification (always shows, ignores context and quiet hours).
    * Sound is handled by the OS notification system.
    */
   async showTestNotification(options: NotificationOptions): Promise<NotificationResult> {
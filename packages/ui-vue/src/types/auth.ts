@@ -17,4 +17,7 @@ export type {
   RegistrationOptionsResponse,
   AuthResponse,
   InvitationValidation,
+  OidcStatus,
+  OidcConfig,
+  OidcConfigInput,
 } from '@stina/api-client'
@@ -0,0 +1,31 @@
+const BROWSERS: Array<[RegExp, string]> = [
+  [/Stina Electron App|Electron\//, 'Stina'],
+  [/Edg\//, 'Edge'],
+  [/OPR\//, 'Opera'],
+  [/Firefox\//, 'Firefox'],
+  [/Chrome\//, 'Chrome'],
+  [/Safari\//, 'Safari'],
+]
+
+const PLATFORMS: Array<[RegExp, string]> = [
+  [/iPhone|iPad/, 'iOS'],
+  [/Android/, 'Android'],
+  [/Mac OS X|Macintosh/, 'macOS'],
+  [/Windows/, 'Windows'],
+  [/CrOS/, 'ChromeOS'],
+  [/Linux/, 'Linux'],
+]
+
+/**
+ * Describe a user agent in a few words, e.g. "Firefox · macOS".
+ * Returns the user agent as is if it is not recognized, or null if it is empty.
+ */
+export function describeUserAgent(userAgent: string | null | undefined): string | null {
+  if (!userAgent) return null
+
+  const browser = BROWSERS.find(([pattern]) => pattern.test(userAgent))?.[1]
+  const platform = PLATFORMS.find(([pattern]) => pattern.test(userAgent))?.[1]
+
+  if (browser && platform) return `${browser} · ${platform}`
+  return browser ?? platform ?? userAgent
+}